@@ -67,8 +67,11 @@ DEFAULT_SYSTEM_PARAMETERS = {
     "enable_disk_cluster_replicas": "true",
     "enable_eager_delta_joins": "true",
     "enable_equivalence_propagation": "true",
+    "enable_explain_analyze": "true",
     "enable_explain_broken": "true",
     "enable_expressions_in_limit_syntax": "true",
+    "enable_incremental_window_functions": "true",
+    "enable_join_hints": "true",
     "enable_logical_compaction_window": "true",
     "enable_multi_worker_storage_persist_sink": "true",
     "enable_mysql_source": "true",
@@ -0,0 +1,54 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+
+from materialize.mzcompose.service import (
+    Service,
+)
+
+
+class MongoDb(Service):
+    DEFAULT_VERSION = "7.0.5"
+    DEFAULT_REPLICA_SET = "rs0"
+
+    def __init__(
+        self,
+        name: str = "mongodb",
+        version: str = DEFAULT_VERSION,
+        port: int = 27017,
+        replica_set: str = DEFAULT_REPLICA_SET,
+    ) -> None:
+        super().__init__(
+            name=name,
+            config={
+                "image": f"mongo:{version}",
+                "init": True,
+                "ports": [port],
+                # Change streams are only available on replica sets, so run a
+                # single-member replica set that `initiate` has to be called on.
+                "command": [
+                    "--replSet",
+                    replica_set,
+                    "--bind_ip_all",
+                    f"--port={port}",
+                ],
+                "healthcheck": {
+                    "test": [
+                        "CMD",
+                        "mongosh",
+                        "--quiet",
+                        "--eval",
+                        "db.runCommand({ ping: 1 }).ok",
+                    ],
+                    "interval": "1s",
+                    "start_period": "60s",
+                },
+            },
+        )
+        self.replica_set = replica_set
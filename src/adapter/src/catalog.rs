@@ -461,6 +461,12 @@ impl Catalog {
             CatalogType::VarChar => CatalogType::VarChar,
             CatalogType::Int2Vector => CatalogType::Int2Vector,
             CatalogType::MzAclItem => CatalogType::MzAclItem,
+            CatalogType::Inet => CatalogType::Inet,
+            CatalogType::Cidr => CatalogType::Cidr,
+            CatalogType::MacAddr => CatalogType::MacAddr,
+            CatalogType::Enum { labels } => CatalogType::Enum {
+                labels: labels.clone(),
+            },
         };
 
         BuiltinType {
@@ -3032,6 +3038,7 @@ impl Catalog {
                 oid,
                 items: BTreeMap::new(),
                 functions: BTreeMap::new(),
+                function_overloads: BTreeMap::new(),
                 types: BTreeMap::new(),
                 owner_id,
                 privileges,
@@ -3598,6 +3605,10 @@ impl ExprHumanizer for ConnCatalog<'_> {
             | Map {
                 custom_id: Some(global_id),
                 ..
+            }
+            | Enum {
+                custom_id: Some(global_id),
+                ..
             } => {
                 let item = self.get_item(global_id);
                 self.minimal_qualification(item.name()).to_string()
@@ -3610,6 +3621,13 @@ impl ExprHumanizer for ConnCatalog<'_> {
                 self.humanize_scalar_type(&ScalarType::String),
                 self.humanize_scalar_type(value_type)
             ),
+            Enum { labels, .. } => format!(
+                "enum({})",
+                labels
+                    .iter()
+                    .map(|l| format!("'{}'", l.replace('\'', "''")))
+                    .join(", ")
+            ),
             Record {
                 custom_id: Some(id),
                 ..
@@ -5397,6 +5415,9 @@ mod tests {
                         | ScalarType::VarChar { .. }
                         | ScalarType::Jsonb
                         | ScalarType::Uuid
+                        | ScalarType::Inet
+                        | ScalarType::Cidr
+                        | ScalarType::MacAddr
                         | ScalarType::Array(_)
                         | ScalarType::Record { .. }
                         | ScalarType::Oid
@@ -5405,6 +5426,7 @@ mod tests {
                         | ScalarType::RegClass
                         | ScalarType::Int2Vector
                         | ScalarType::Range { .. }
+                        | ScalarType::Enum { .. }
                         | ScalarType::PgLegacyName => {}
                     }
                 }
@@ -18,8 +18,8 @@ use mz_catalog::builtin::{
     MZ_AGGREGATES, MZ_ARRAY_TYPES, MZ_AUDIT_EVENTS, MZ_AWS_CONNECTIONS,
     MZ_AWS_PRIVATELINK_CONNECTIONS, MZ_BASE_TYPES, MZ_CLUSTERS, MZ_CLUSTER_REPLICAS,
     MZ_CLUSTER_REPLICA_METRICS, MZ_CLUSTER_REPLICA_SIZES, MZ_CLUSTER_REPLICA_STATUSES, MZ_COLUMNS,
-    MZ_COMMENTS, MZ_CONNECTIONS, MZ_DATABASES, MZ_DEFAULT_PRIVILEGES, MZ_EGRESS_IPS, MZ_FUNCTIONS,
-    MZ_INDEXES, MZ_INDEX_COLUMNS, MZ_INTERNAL_CLUSTER_REPLICAS, MZ_KAFKA_CONNECTIONS,
+    MZ_COMMENTS, MZ_CONNECTIONS, MZ_DATABASES, MZ_DEFAULT_PRIVILEGES, MZ_EGRESS_IPS, MZ_ENUM_TYPES,
+    MZ_FUNCTIONS, MZ_INDEXES, MZ_INDEX_COLUMNS, MZ_INTERNAL_CLUSTER_REPLICAS, MZ_KAFKA_CONNECTIONS,
     MZ_KAFKA_SINKS, MZ_KAFKA_SOURCES, MZ_LIST_TYPES, MZ_MAP_TYPES, MZ_MATERIALIZED_VIEWS,
     MZ_OBJECT_DEPENDENCIES, MZ_OPERATORS, MZ_POSTGRES_SOURCES, MZ_PSEUDO_TYPES, MZ_ROLES,
     MZ_ROLE_MEMBERS, MZ_SCHEMAS, MZ_SECRETS, MZ_SESSIONS, MZ_SINKS, MZ_SOURCES,
@@ -29,7 +29,7 @@ use mz_catalog::builtin::{
 use mz_catalog::config::AwsPrincipalContext;
 use mz_catalog::memory::error::{Error, ErrorKind};
 use mz_catalog::memory::objects::{
-    CatalogItem, ClusterVariant, Connection, DataSourceDesc, Database, Func, Index,
+    CatalogItem, ClusterVariant, Connection, DataSourceDesc, Database, Func, FuncDefinition, Index,
     MaterializedView, Sink, Table, Type, View,
 };
 use mz_catalog::SYSTEM_CONN_ID;
@@ -46,13 +46,13 @@ use mz_repr::adt::array::ArrayDimension;
 use mz_repr::adt::jsonb::Jsonb;
 use mz_repr::adt::mz_acl_item::{AclMode, MzAclItem, PrivilegeMap};
 use mz_repr::role_id::RoleId;
-use mz_repr::{Datum, Diff, GlobalId, Row, RowPacker};
+use mz_repr::{Datum, Diff, GlobalId, Row, RowPacker, ScalarType};
 use mz_sql::ast::{CreateIndexStatement, Statement};
 use mz_sql::catalog::{
     CatalogCluster, CatalogDatabase, CatalogSchema, CatalogType, DefaultPrivilegeObject,
     TypeCategory,
 };
-use mz_sql::func::FuncImplCatalogDetails;
+use mz_sql::func::{FuncImplCatalogDetails, SqlFunctionBody};
 use mz_sql::names::{CommentObjectId, ResolvedDatabaseSpecifier, SchemaId, SchemaSpecifier};
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_storage_types::connections::aws::{AwsAuth, AwsConnection};
@@ -79,6 +79,15 @@ pub struct BuiltinTableUpdate {
     pub diff: Diff,
 }
 
+/// The contents of a function implementation's row in `mz_functions`.
+struct FuncImplUpdate {
+    oid: u32,
+    arg_type_ids: Vec<String>,
+    variadic_type_id: Option<String>,
+    return_type_id: Option<String>,
+    return_is_set: bool,
+}
+
 impl CatalogState {
     pub fn pack_depends_update(
         &self,
@@ -402,7 +411,7 @@ impl CatalogState {
                     self.pack_type_update(id, oid, schema_id, name, owner_id, privileges, ty, diff)
                 }
                 CatalogItem::Func(func) => {
-                    self.pack_func_update(id, schema_id, name, owner_id, func, diff)
+                    self.pack_func_update(id, oid, schema_id, name, owner_id, func, diff)
                 }
                 CatalogItem::Secret(_) => {
                     self.pack_secret_update(id, oid, schema_id, name, owner_id, privileges, diff)
@@ -620,6 +629,9 @@ impl CatalogState {
                     }
                     mz_storage_types::connections::Connection::Ssh { .. } => "ssh-tunnel",
                     mz_storage_types::connections::Connection::MySql { .. } => "mysql",
+                    mz_storage_types::connections::Connection::Http { .. } => "http",
+                    mz_storage_types::connections::Connection::SqlServer { .. } => "sql-server",
+                    mz_storage_types::connections::Connection::MongoDb { .. } => "mongodb",
                 }),
                 Datum::String(&owner_id.to_string()),
                 privileges,
@@ -666,7 +678,10 @@ impl CatalogState {
             }
             mz_storage_types::connections::Connection::Csr(_)
             | mz_storage_types::connections::Connection::Postgres(_)
-            | mz_storage_types::connections::Connection::MySql(_) => (),
+            | mz_storage_types::connections::Connection::MySql(_)
+            | mz_storage_types::connections::Connection::Http(_)
+            | mz_storage_types::connections::Connection::SqlServer(_)
+            | mz_storage_types::connections::Connection::MongoDb(_) => (),
         };
         updates
     }
@@ -924,6 +939,7 @@ impl CatalogState {
                     diff,
                 });
             }
+            StorageSinkConnection::Http(_) | StorageSinkConnection::Postgres(_) => (),
         };
 
         let create_stmt = mz_sql::parse::parse(&sink.create_sql)
@@ -1125,6 +1141,19 @@ impl CatalogState {
                 append_modifier(&mut packer, value_modifiers);
                 self.resolve_builtin_table(&MZ_MAP_TYPES)
             }
+            CatalogType::Enum { labels } => {
+                packer.push(Datum::String(&id.to_string()));
+                packer
+                    .push_array(
+                        &[ArrayDimension {
+                            lower_bound: 1,
+                            length: labels.len(),
+                        }],
+                        labels.iter().map(|l| Datum::String(l)),
+                    )
+                    .expect("labels is 1 dimensional, and its length is used for the array length");
+                self.resolve_builtin_table(&MZ_ENUM_TYPES)
+            }
             CatalogType::Pseudo => {
                 packer.push(Datum::String(&id.to_string()));
                 self.resolve_builtin_table(&MZ_PSEUDO_TYPES)
@@ -1158,28 +1187,61 @@ impl CatalogState {
     fn pack_func_update(
         &self,
         id: GlobalId,
+        oid: u32,
         schema_id: &SchemaSpecifier,
         name: &str,
         owner_id: &RoleId,
         func: &Func,
         diff: Diff,
     ) -> Vec<BuiltinTableUpdate> {
-        let mut updates = vec![];
-        for func_impl_details in func.inner.func_impls() {
-            let arg_type_ids = func_impl_details
-                .arg_typs
-                .iter()
-                .map(|typ| self.get_system_type(typ).id().to_string())
-                .collect::<Vec<_>>();
+        let func_impls = match &func.definition {
+            FuncDefinition::Builtin(inner) => inner
+                .func_impls()
+                .into_iter()
+                .map(|details| {
+                    let type_id = |typ: &str| self.get_system_type(typ).id().to_string();
+                    FuncImplUpdate {
+                        oid: details.oid,
+                        arg_type_ids: details.arg_typs.iter().map(|typ| type_id(typ)).collect(),
+                        variadic_type_id: details.variadic_typ.map(type_id),
+                        return_type_id: details.return_typ.map(type_id),
+                        return_is_set: details.return_is_set,
+                    }
+                })
+                .collect(),
+            // User-defined functions have a single implementation, which is
+            // identified by the OID of the function's catalog entry.
+            FuncDefinition::Sql { function, .. } => {
+                let (return_type_id, return_is_set) = match &function.body {
+                    SqlFunctionBody::Scalar { return_type, .. } => {
+                        (self.scalar_type_id(return_type), false)
+                    }
+                    SqlFunctionBody::Table { .. } => (self.get_system_type("record").id(), true),
+                };
+                vec![FuncImplUpdate {
+                    oid,
+                    arg_type_ids: function
+                        .param_types
+                        .iter()
+                        .map(|typ| self.scalar_type_id(typ).to_string())
+                        .collect(),
+                    variadic_type_id: None,
+                    return_type_id: Some(return_type_id.to_string()),
+                    return_is_set,
+                }]
+            }
+        };
 
+        let mut updates = vec![];
+        for func_impl in func_impls {
             let mut row = Row::default();
             row.packer()
                 .push_array(
                     &[ArrayDimension {
                         lower_bound: 1,
-                        length: arg_type_ids.len(),
+                        length: func_impl.arg_type_ids.len(),
                     }],
-                    arg_type_ids.iter().map(|id| Datum::String(id)),
+                    func_impl.arg_type_ids.iter().map(|id| Datum::String(id)),
                 )
                 .expect(
                     "arg_type_ids is 1 dimensional, and its length is used for the array length",
@@ -1190,33 +1252,23 @@ impl CatalogState {
                 id: self.resolve_builtin_table(&MZ_FUNCTIONS),
                 row: Row::pack_slice(&[
                     Datum::String(&id.to_string()),
-                    Datum::UInt32(func_impl_details.oid),
+                    Datum::UInt32(func_impl.oid),
                     Datum::String(&schema_id.to_string()),
                     Datum::String(name),
                     arg_type_ids,
-                    Datum::from(
-                        func_impl_details
-                            .variadic_typ
-                            .map(|typ| self.get_system_type(typ).id().to_string())
-                            .as_deref(),
-                    ),
-                    Datum::from(
-                        func_impl_details
-                            .return_typ
-                            .map(|typ| self.get_system_type(typ).id().to_string())
-                            .as_deref(),
-                    ),
-                    func_impl_details.return_is_set.into(),
+                    Datum::from(func_impl.variadic_type_id.as_deref()),
+                    Datum::from(func_impl.return_type_id.as_deref()),
+                    func_impl.return_is_set.into(),
                     Datum::String(&owner_id.to_string()),
                 ]),
                 diff,
             });
 
-            if let mz_sql::func::Func::Aggregate(_) = func.inner {
+            if let mz_sql::func::Func::Aggregate(_) = func.inner() {
                 updates.push(BuiltinTableUpdate {
                     id: self.resolve_builtin_table(&MZ_AGGREGATES),
                     row: Row::pack_slice(&[
-                        Datum::UInt32(func_impl_details.oid),
+                        Datum::UInt32(func_impl.oid),
                         // TODO(materialize#3326): Support ordered-set aggregate functions.
                         Datum::String("n"),
                         Datum::Int16(0),
@@ -1228,6 +1280,31 @@ impl CatalogState {
         updates
     }
 
+    /// Returns the ID of the catalog type that describes `typ`.
+    fn scalar_type_id(&self, typ: &ScalarType) -> GlobalId {
+        match typ {
+            ScalarType::List {
+                custom_id: Some(id),
+                ..
+            }
+            | ScalarType::Map {
+                custom_id: Some(id),
+                ..
+            }
+            | ScalarType::Record {
+                custom_id: Some(id),
+                ..
+            }
+            | ScalarType::Enum {
+                custom_id: Some(id),
+                ..
+            } => *id,
+            _ => self
+                .get_system_type(mz_pgrepr::Type::from(typ).catalog_name())
+                .id(),
+        }
+    }
+
     pub fn pack_op_update(
         &self,
         operator: &str,
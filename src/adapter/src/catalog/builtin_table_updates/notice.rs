@@ -98,6 +98,7 @@ impl Catalog {
         DataflowMetainfo {
             optimizer_notices,
             index_usage_types: df_meta.index_usage_types,
+            statistics: df_meta.statistics,
         }
     }
 }
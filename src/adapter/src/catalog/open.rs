@@ -33,8 +33,8 @@ use mz_catalog::durable::objects::{
 use mz_catalog::durable::{Transaction, SYSTEM_CLUSTER_ID_ALLOC_KEY, SYSTEM_REPLICA_ID_ALLOC_KEY};
 use mz_catalog::memory::error::{Error, ErrorKind};
 use mz_catalog::memory::objects::{
-    CatalogEntry, CatalogItem, CommentsMap, DataSourceDesc, Database, DefaultPrivileges, Func, Log,
-    Role, Schema, Source, Table, Type,
+    CatalogEntry, CatalogItem, CommentsMap, DataSourceDesc, Database, DefaultPrivileges, Func,
+    FuncDefinition, Log, Role, Schema, Source, Table, Type,
 };
 use mz_catalog::SYSTEM_CONN_ID;
 use mz_cluster_client::ReplicaId;
@@ -316,6 +316,7 @@ impl Catalog {
                         oid,
                         items: BTreeMap::new(),
                         functions: BTreeMap::new(),
+                        function_overloads: BTreeMap::new(),
                         types: BTreeMap::new(),
                         owner_id,
                         privileges: PrivilegeMap::from_mz_acl_items(privileges),
@@ -526,7 +527,11 @@ impl Catalog {
                                 id,
                                 oid,
                                 name.clone(),
-                                CatalogItem::Func(Func { inner: func.inner }),
+                                CatalogItem::Func(Func {
+                                    create_sql: None,
+                                    definition: FuncDefinition::Builtin(func.inner),
+                                    resolved_ids: ResolvedIds(BTreeSet::new()),
+                                }),
                                 MZ_SYSTEM_ROLE_ID,
                                 PrivilegeMap::default(),
                             );
@@ -636,16 +641,18 @@ impl Catalog {
                     log_logging: config.logging.log_logging,
                     interval: config.logging.interval,
                 };
+                let location = state.concretize_replica_location(
+                    config.location,
+                    &vec![],
+                    cluster_azs.get(&cluster_id).map(|zones| &**zones),
+                )?;
                 let config = ReplicaConfig {
-                    location: state.concretize_replica_location(
-                        config.location,
-                        &vec![],
-                        cluster_azs.get(&cluster_id).map(|zones| &**zones),
-                    )?,
                     compute: ComputeReplicaConfig {
                         logging,
                         idle_arrangement_merge_effort: config.idle_arrangement_merge_effort,
+                        arrangement_spill: location.disk(),
                     },
+                    location,
                 };
 
                 allocated_replicas.push(mz_catalog::durable::ClusterReplica {
@@ -895,6 +902,13 @@ impl Catalog {
                         builtin_table_updates
                             .extend(catalog.state.pack_item_update(*function_id, 1));
                     }
+                    for function_ids in schema.function_overloads.values() {
+                        // The first overload is in `schema.functions`.
+                        for function_id in function_ids.iter().skip(1) {
+                            builtin_table_updates
+                                .extend(catalog.state.pack_item_update(*function_id, 1));
+                        }
+                    }
                     for (_item_name, type_id) in &schema.types {
                         builtin_table_updates.extend(catalog.state.pack_item_update(*type_id, 1));
                     }
@@ -1294,6 +1308,10 @@ impl Catalog {
                 CatalogItem::View(_) => {
                     // Views don't have any external objects to drop.
                 }
+                CatalogItem::Func(_) if id.is_user() => {
+                    // User-defined functions don't have any external objects
+                    // to drop.
+                }
                 CatalogItem::Type(_)
                 | CatalogItem::Func(_)
                 | CatalogItem::Secret(_)
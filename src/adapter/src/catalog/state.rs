@@ -36,8 +36,8 @@ use mz_catalog::config::{AwsPrincipalContext, ClusterReplicaSizeMap};
 use mz_catalog::memory::error::{Error, ErrorKind};
 use mz_catalog::memory::objects::{
     CatalogEntry, CatalogItem, Cluster, ClusterConfig, ClusterReplica, ClusterReplicaProcessStatus,
-    CommentsMap, Connection, DataSourceDesc, Database, DefaultPrivileges, Index, MaterializedView,
-    Role, Schema, Secret, Sink, Source, Table, Type, View,
+    CommentsMap, Connection, DataSourceDesc, Database, DefaultPrivileges, Func, FuncDefinition,
+    Index, MaterializedView, Role, Schema, Secret, Sink, Source, Table, Type, View,
 };
 use mz_catalog::SYSTEM_CONN_ID;
 use mz_controller::clusters::{
@@ -66,15 +66,16 @@ use mz_sql::catalog::{
     CatalogSchema, CatalogTypeDetails, EnvironmentId, IdReference, NameReference, SessionCatalog,
     SystemObjectType, TypeReference,
 };
+use mz_sql::func::SqlFunction;
 use mz_sql::names::{
     CommentObjectId, DatabaseId, FullItemName, FullSchemaName, ItemQualifiers, ObjectId,
     PartialItemName, QualifiedItemName, QualifiedSchemaName, RawDatabaseSpecifier,
     ResolvedDatabaseSpecifier, ResolvedIds, SchemaId, SchemaSpecifier, SystemObjectId,
 };
 use mz_sql::plan::{
-    CreateConnectionPlan, CreateIndexPlan, CreateMaterializedViewPlan, CreateSecretPlan,
-    CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan, CreateViewPlan, Params,
-    Plan, PlanContext,
+    CreateConnectionPlan, CreateFunctionPlan, CreateIndexPlan, CreateMaterializedViewPlan,
+    CreateSecretPlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan,
+    CreateViewPlan, Params, Plan, PlanContext,
 };
 use mz_sql::rbac;
 use mz_sql::session::user::MZ_SYSTEM_ROLE_ID;
@@ -301,7 +302,8 @@ impl CatalogState {
             CatalogItem::Log(_) => out.push(id),
             item @ (CatalogItem::View(_)
             | CatalogItem::MaterializedView(_)
-            | CatalogItem::Connection(_)) => {
+            | CatalogItem::Connection(_)
+            | CatalogItem::Func(_)) => {
                 // TODO(jkosh44) Unclear if this table wants to include all uses or only references.
                 for id in &item.references().0 {
                     self.introspection_dependencies_inner(*id, out);
@@ -312,7 +314,6 @@ impl CatalogState {
             CatalogItem::Table(_)
             | CatalogItem::Source(_)
             | CatalogItem::Type(_)
-            | CatalogItem::Func(_)
             | CatalogItem::Secret(_) => (),
         }
     }
@@ -1003,6 +1004,15 @@ impl CatalogState {
                 resolved_ids,
                 cluster_id: in_cluster,
             }),
+            Plan::CreateFunction(CreateFunctionPlan { function, .. }) => CatalogItem::Func(Func {
+                create_sql: Some(function.create_sql),
+                definition: FuncDefinition::Sql {
+                    inner: Arc::new(function.inner.func()),
+                    function: function.inner,
+                    overloads: vec![id],
+                },
+                resolved_ids,
+            }),
             Plan::CreateType(CreateTypePlan { typ, .. }) => CatalogItem::Type(Type {
                 create_sql: Some(typ.create_sql),
                 desc: typ.inner.desc(&session_catalog)?,
@@ -1127,7 +1137,25 @@ impl CatalogState {
             conn_id,
         );
 
+        let mut overloads = None;
         let prev_id = match entry.item() {
+            CatalogItem::Func(Func {
+                definition: FuncDefinition::Sql { .. },
+                ..
+            }) => {
+                // User-defined functions can be overloaded. The name resolves
+                // to the first overload, whose implementations include those
+                // of all other overloads.
+                let ids = schema
+                    .function_overloads
+                    .entry(entry.name().item.clone())
+                    .or_default();
+                ids.insert(entry.id());
+                let first = *ids.first().expect("just inserted");
+                overloads = Some(ids.clone());
+                schema.functions.insert(entry.name().item.clone(), first);
+                None
+            }
             CatalogItem::Func(_) => schema
                 .functions
                 .insert(entry.name().item.clone(), entry.id()),
@@ -1142,6 +1170,42 @@ impl CatalogState {
         );
 
         self.entry_by_id.insert(entry.id(), entry.clone());
+        if let Some(overloads) = overloads {
+            self.update_function_overloads(&overloads);
+        }
+    }
+
+    /// Updates the user-defined functions `ids`, which share a name, so that
+    /// calls to any of them select among the implementations of all of them.
+    fn update_function_overloads(&mut self, ids: &BTreeSet<GlobalId>) {
+        let functions: Vec<_> = ids
+            .iter()
+            .map(|id| match self.entry_by_id[id].item() {
+                CatalogItem::Func(func) => func
+                    .sql_function()
+                    .expect("overloads are user-defined functions")
+                    .clone(),
+                _ => unreachable!("overloads are user-defined functions"),
+            })
+            .collect();
+        let inner = Arc::new(SqlFunction::overloaded_func(&functions));
+        let overloads: Vec<_> = ids.iter().copied().collect();
+        for id in ids {
+            let entry = self.entry_by_id.get_mut(id).expect("catalog out of sync");
+            if let CatalogItem::Func(Func {
+                definition:
+                    FuncDefinition::Sql {
+                        inner: entry_inner,
+                        overloads: entry_overloads,
+                        ..
+                    },
+                ..
+            }) = &mut entry.item
+            {
+                *entry_inner = Arc::clone(&inner);
+                *entry_overloads = overloads.clone();
+            }
+        }
     }
 
     #[mz_ore::instrument(level = "trace")]
@@ -1165,6 +1229,7 @@ impl CatalogState {
         }
 
         let conn_id = metadata.item().conn_id().unwrap_or(&SYSTEM_CONN_ID);
+        let mut overloads = None;
         let schema = self.get_schema_mut(
             &metadata.name().qualifiers.database_spec,
             &metadata.name().qualifiers.schema_spec,
@@ -1175,16 +1240,35 @@ impl CatalogState {
                 .types
                 .remove(&metadata.name().item)
                 .expect("catalog out of sync");
+        } else if metadata.item_type() == CatalogItemType::Func {
+            let name = &metadata.name().item;
+            match schema.function_overloads.get_mut(name) {
+                Some(ids) => {
+                    assert!(ids.remove(&id), "catalog out of sync");
+                    match ids.first() {
+                        Some(first) => {
+                            schema.functions.insert(name.clone(), *first);
+                            overloads = Some(ids.clone());
+                        }
+                        None => {
+                            schema.function_overloads.remove(name);
+                            schema.functions.remove(name).expect("catalog out of sync");
+                        }
+                    }
+                }
+                None => {
+                    schema.functions.remove(name).expect("catalog out of sync");
+                }
+            }
         } else {
-            // Functions would need special handling, but we don't yet support
-            // dropping functions.
-            assert_ne!(metadata.item_type(), CatalogItemType::Func);
-
             schema
                 .items
                 .remove(&metadata.name().item)
                 .expect("catalog out of sync");
         };
+        if let Some(overloads) = overloads {
+            self.update_function_overloads(&overloads);
+        }
 
         if !id.is_system() {
             if let Some(cluster_id) = metadata.item().cluster_id() {
@@ -1639,6 +1723,7 @@ impl CatalogState {
                 oid,
                 items: BTreeMap::new(),
                 functions: BTreeMap::new(),
+                function_overloads: BTreeMap::new(),
                 types: BTreeMap::new(),
                 owner_id,
                 privileges: PrivilegeMap::from_mz_acl_items(vec![rbac::owner_privilege(
@@ -502,6 +502,7 @@ impl Coordinator {
                     | Statement::Execute(_)
                     | Statement::ExplainPlan(_)
                     | Statement::ExplainPushdown(_)
+                    | Statement::ExplainAnalyze(_)
                     | Statement::ExplainTimestamp(_)
                     | Statement::ExplainSinkSchema(_)
                     | Statement::Fetch(_)
@@ -519,10 +520,13 @@ impl Coordinator {
                     }
 
                     Statement::Insert(InsertStatement {
-                        source, returning, ..
+                        source,
+                        on_conflict: None,
+                        returning,
+                        ..
                     }) if returning.is_empty() && ConstantVisitor::insert_source(source) => {
                         // Inserting from constant values statements that do not need to execute on
-                        // any cluster (no RETURNING) is always safe.
+                        // any cluster (no RETURNING, no ON CONFLICT) is always safe.
                     }
 
                     Statement::AlterObjectRename(_) | Statement::AlterObjectSwap(_) => {
@@ -559,6 +563,7 @@ impl Coordinator {
                     | Statement::CreateClusterReplica(_)
                     | Statement::CreateConnection(_)
                     | Statement::CreateDatabase(_)
+                    | Statement::CreateFunction(_)
                     | Statement::CreateIndex(_)
                     | Statement::CreateMaterializedView(_)
                     | Statement::CreateRole(_)
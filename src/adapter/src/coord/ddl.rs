@@ -1135,7 +1135,10 @@ impl Coordinator {
                                 Connection::Csr(_)
                                 | Connection::Ssh(_)
                                 | Connection::Aws(_)
-                                | Connection::MySql(_) => {}
+                                | Connection::MySql(_)
+                                | Connection::Http(_)
+                                | Connection::SqlServer(_)
+                                | Connection::MongoDb(_) => {}
                             }
                         }
                         CatalogItem::Table(_) => {
@@ -1290,7 +1293,10 @@ impl Coordinator {
                 Connection::Csr(_)
                 | Connection::Ssh(_)
                 | Connection::Aws(_)
-                | Connection::MySql(_) => {}
+                | Connection::MySql(_)
+                | Connection::Http(_)
+                | Connection::SqlServer(_)
+                | Connection::MongoDb(_) => {}
             }
         }
         self.validate_resource_limit(
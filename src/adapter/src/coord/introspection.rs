@@ -82,6 +82,7 @@ pub fn auto_run_on_introspection<'a, 's, 'p>(
         | Plan::CreateMaterializedView(_)
         | Plan::CreateIndex(_)
         | Plan::CreateType(_)
+        | Plan::CreateFunction(_)
         | Plan::Comment(_)
         | Plan::DiscardTemp
         | Plan::DiscardAll
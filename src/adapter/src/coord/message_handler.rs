@@ -25,6 +25,7 @@ use mz_persist_client::usage::ShardsUsageReferenced;
 use mz_sql::ast::Statement;
 use mz_sql::names::ResolvedIds;
 use mz_sql::plan::{CreateSourcePlans, Plan};
+use mz_sql::rbac;
 use mz_storage_types::controller::CollectionMetadata;
 use opentelemetry::trace::TraceContextExt;
 use rand::{rngs, Rng, SeedableRng};
@@ -447,7 +448,7 @@ impl Coordinator {
             return;
         }
 
-        let (subsource_stmts, stmt) = match result {
+        let (type_stmts, subsource_stmts, stmt) = match result {
             Ok(ok) => ok,
             Err(e) => return ctx.retire(Err(e)),
         };
@@ -455,8 +456,66 @@ impl Coordinator {
         let mut create_source_plans: Vec<CreateSourcePlans> = vec![];
         let mut id_allocation = BTreeMap::new();
 
-        // First we'll allocate global ids for each subsource and plan them
+        // Subsources can only be planned once the types of their columns exist, so we create the
+        // enum types that mirror upstream enums first. They remain if creating the source fails,
+        // and are reused by the next attempt.
+        if !type_stmts.is_empty() {
+            let mut ops = vec![];
+            for (transient_id, type_stmt) in type_stmts {
+                let resolved_ids = mz_sql::names::visit_dependencies(&type_stmt);
+                let plan = match self.plan_statement(
+                    ctx.session(),
+                    Statement::CreateType(type_stmt),
+                    &params,
+                    &resolved_ids,
+                ) {
+                    Ok(plan @ Plan::CreateType(_)) => plan,
+                    Ok(_) => {
+                        unreachable!("planning CREATE TYPE must result in a Plan::CreateType")
+                    }
+                    Err(e) => return ctx.retire(Err(e)),
+                };
+                if let Err(e) = rbac::check_plan(
+                    &self.catalog().for_session(ctx.session()),
+                    &self
+                        .active_conns()
+                        .into_iter()
+                        .map(|(conn_id, conn_meta)| {
+                            (conn_id.unhandled(), *conn_meta.authenticated_role_id())
+                        })
+                        .collect(),
+                    ctx.session(),
+                    &plan,
+                    None,
+                    &resolved_ids,
+                ) {
+                    return ctx.retire(Err(e.into()));
+                }
+                let Plan::CreateType(plan) = plan else {
+                    unreachable!("checked above")
+                };
+                let type_id = match self.catalog_mut().allocate_user_id().await {
+                    Ok(id) => id,
+                    Err(e) => return ctx.retire(Err(e.into())),
+                };
+                match self.create_type_op(ctx.session(), type_id, plan, resolved_ids) {
+                    Ok(op) => ops.push(op),
+                    Err(e) => return ctx.retire(Err(e)),
+                }
+                id_allocation.insert(transient_id, type_id);
+            }
+            if let Err(e) = self.catalog_transact(Some(ctx.session()), ops).await {
+                return ctx.retire(Err(e));
+            }
+        }
+
+        // Then we'll allocate global ids for each subsource and plan them
         for (transient_id, subsource_stmt) in subsource_stmts {
+            let subsource_stmt =
+                match mz_sql::names::resolve_transient_ids(&id_allocation, subsource_stmt) {
+                    Ok(ok) => ok,
+                    Err(e) => return ctx.retire(Err(e.into())),
+                };
             let resolved_ids = mz_sql::names::visit_dependencies(&subsource_stmt);
             let source_id = match self.catalog_mut().allocate_user_id().await {
                 Ok(id) => id,
@@ -213,6 +213,12 @@ impl Coordinator {
                         .await;
                     ctx.retire(result);
                 }
+                Plan::CreateFunction(plan) => {
+                    let result = self
+                        .sequence_create_function(ctx.session(), plan, resolved_ids)
+                        .await;
+                    ctx.retire(result);
+                }
                 Plan::Comment(plan) => {
                     let result = self.sequence_comment_on(ctx.session(), plan).await;
                     ctx.retire(result);
@@ -766,7 +772,18 @@ impl Coordinator {
         session: &mut Session,
         mut plan: plan::SendDiffsPlan,
     ) -> Result<ExecuteResponse, AdapterError> {
-        let affected_rows = {
+        let affected_rows = if let MutationKind::Upsert = plan.kind {
+            // Like PostgreSQL, an upsert reports each inserted or updated row
+            // once, and does not count the retraction of an updated row.
+            let affected_rows: Diff = plan
+                .updates
+                .iter()
+                .map(|(_, diff)| *diff)
+                .filter(|diff| *diff > 0)
+                .sum();
+            differential_dataflow::consolidation::consolidate(&mut plan.updates);
+            usize::try_from(affected_rows).expect("positive isize must fit")
+        } else {
             let mut affected_rows = Diff::from(0);
             let mut all_positive_diffs = true;
             // If all diffs are positive, the number of affected rows is just the
@@ -825,7 +842,7 @@ impl Coordinator {
         }
         Ok(match plan.kind {
             MutationKind::Delete => ExecuteResponse::Deleted(affected_rows),
-            MutationKind::Insert => ExecuteResponse::Inserted(affected_rows),
+            MutationKind::Insert | MutationKind::Upsert => ExecuteResponse::Inserted(affected_rows),
             MutationKind::Update => ExecuteResponse::Updated(affected_rows / 2),
         })
     }
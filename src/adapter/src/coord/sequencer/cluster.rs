@@ -190,19 +190,21 @@ impl Coordinator {
             ReplicaLogging::default()
         };
 
+        let location = self.catalog().concretize_replica_location(
+            location,
+            &self
+                .catalog()
+                .system_config()
+                .allowed_cluster_replica_sizes(),
+            azs,
+        )?;
         let config = ReplicaConfig {
-            location: self.catalog().concretize_replica_location(
-                location,
-                &self
-                    .catalog()
-                    .system_config()
-                    .allowed_cluster_replica_sizes(),
-                azs,
-            )?,
             compute: ComputeReplicaConfig {
                 logging,
                 idle_arrangement_merge_effort: compute.idle_arrangement_merge_effort,
+                arrangement_spill: location.disk(),
             },
+            location,
         };
 
         ops.push(catalog::Op::CreateClusterReplica {
@@ -323,19 +325,21 @@ impl Coordinator {
                 ReplicaLogging::default()
             };
 
+            let location = self.catalog().concretize_replica_location(
+                location,
+                &self
+                    .catalog()
+                    .system_config()
+                    .allowed_cluster_replica_sizes(),
+                None,
+            )?;
             let config = ReplicaConfig {
-                location: self.catalog().concretize_replica_location(
-                    location,
-                    &self
-                        .catalog()
-                        .system_config()
-                        .allowed_cluster_replica_sizes(),
-                    None,
-                )?,
                 compute: ComputeReplicaConfig {
                     logging,
                     idle_arrangement_merge_effort: compute.idle_arrangement_merge_effort,
+                    arrangement_spill: location.disk(),
                 },
+                location,
             };
 
             ops.push(catalog::Op::CreateClusterReplica {
@@ -454,21 +458,23 @@ impl Coordinator {
             ReplicaLogging::default()
         };
 
+        let location = self.catalog().concretize_replica_location(
+            location,
+            &self
+                .catalog()
+                .system_config()
+                .allowed_cluster_replica_sizes(),
+            // Planning ensures all replicas in this codepath
+            // are unmanaged.
+            None,
+        )?;
         let config = ReplicaConfig {
-            location: self.catalog().concretize_replica_location(
-                location,
-                &self
-                    .catalog()
-                    .system_config()
-                    .allowed_cluster_replica_sizes(),
-                // Planning ensures all replicas in this codepath
-                // are unmanaged.
-                None,
-            )?,
             compute: ComputeReplicaConfig {
                 logging,
                 idle_arrangement_merge_effort: compute.idle_arrangement_merge_effort,
+                arrangement_spill: location.disk(),
             },
+            location,
         };
 
         let cluster = self.catalog().get_cluster(cluster_id);
@@ -28,12 +28,14 @@ use mz_ore::task::spawn;
 use mz_ore::tracing::OpenTelemetryContext;
 use mz_ore::vec::VecExt;
 use mz_ore::{soft_assert_or_log, task};
+use mz_persist_client::stats::{SnapshotPartStats, SnapshotPartsStats};
 use mz_repr::adt::jsonb::Jsonb;
 use mz_repr::adt::mz_acl_item::{MzAclItem, PrivilegeMap};
 use mz_repr::explain::json::json_string;
 use mz_repr::explain::{ExplainFormat, ExprHumanizer};
 use mz_repr::role_id::RoleId;
-use mz_repr::{Datum, Diff, GlobalId, Row, RowArena, Timestamp};
+use mz_repr::stats::{CollectionStatistics, ColumnStatistics};
+use mz_repr::{Datum, Diff, GlobalId, RelationDesc, Row, RowArena, Timestamp};
 use mz_sql::ast::IndexOptionName;
 use mz_sql::catalog::{
     CatalogCluster, CatalogClusterReplica, CatalogDatabase, CatalogError,
@@ -47,13 +49,14 @@ use mz_sql::names::{
 // Import `plan` module, but only import select elements to avoid merge conflicts on use statements.
 use mz_adapter_types::connection::ConnectionId;
 use mz_catalog::memory::objects::{
-    CatalogItem, Cluster, Connection, DataSourceDesc, Secret, Sink, Source, Table, Type,
+    CatalogItem, Cluster, Connection, DataSourceDesc, Func, FuncDefinition, Secret, Sink, Source,
+    Table, Type,
 };
 use mz_ore::instrument;
 use mz_sql::plan::{
     AlterConnectionAction, AlterConnectionPlan, ExplainSinkSchemaPlan, Explainee,
     ExplaineeStatement, IndexOption, MutationKind, Params, Plan, PlannedAlterRoleOption,
-    PlannedRoleVariable, QueryWhen, SideEffectingFunc, UpdatePrivilege, VariableValue,
+    PlannedRoleVariable, QueryWhen, SideEffectingFunc, SinkSchema, UpdatePrivilege, VariableValue,
 };
 use mz_sql::session::metadata::SessionMetadata;
 use mz_sql::session::user::UserKind;
@@ -72,6 +75,7 @@ use mz_ssh_util::keys::SshKeyPairSet;
 use mz_storage_client::controller::{CollectionDescription, DataSource, DataSourceOther};
 use mz_storage_types::connections::inline::IntoInlineConnection;
 use mz_storage_types::controller::StorageError;
+use mz_storage_types::stats::RelationPartStats;
 use mz_transform::notice::{OptimizerNoticeApi, OptimizerNoticeKind, RawOptimizerNotice};
 use mz_transform::EmptyStatisticsOracle;
 use timely::progress::Antichain;
@@ -837,6 +841,22 @@ impl Coordinator {
         plan: plan::CreateTypePlan,
         resolved_ids: ResolvedIds,
     ) -> Result<ExecuteResponse, AdapterError> {
+        let id = self.catalog_mut().allocate_user_id().await?;
+        let op = self.create_type_op(session, id, plan, resolved_ids)?;
+        match self.catalog_transact(Some(session), vec![op]).await {
+            Ok(()) => Ok(ExecuteResponse::CreatedType),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns the catalog operation that creates the type described by `plan` with ID `id`.
+    pub(crate) fn create_type_op(
+        &self,
+        session: &Session,
+        id: GlobalId,
+        plan: plan::CreateTypePlan,
+        resolved_ids: ResolvedIds,
+    ) -> Result<catalog::Op, AdapterError> {
         let typ = Type {
             create_sql: Some(plan.typ.create_sql),
             desc: plan.typ.inner.desc(&self.catalog().for_session(session))?,
@@ -847,15 +867,44 @@ impl Coordinator {
             },
             resolved_ids,
         };
-        let id = self.catalog_mut().allocate_user_id().await?;
-        let op = catalog::Op::CreateItem {
+        Ok(catalog::Op::CreateItem {
             id,
             name: plan.name,
             item: CatalogItem::Type(typ),
             owner_id: *session.current_role_id(),
+        })
+    }
+
+    #[instrument]
+    pub(super) async fn sequence_create_function(
+        &mut self,
+        session: &Session,
+        plan: plan::CreateFunctionPlan,
+        resolved_ids: ResolvedIds,
+    ) -> Result<ExecuteResponse, AdapterError> {
+        let id = self.catalog_mut().allocate_user_id().await?;
+        let func = Func {
+            create_sql: Some(plan.function.create_sql),
+            definition: FuncDefinition::Sql {
+                inner: Arc::new(plan.function.inner.func()),
+                function: plan.function.inner,
+                overloads: vec![id],
+            },
+            resolved_ids,
         };
-        match self.catalog_transact(Some(session), vec![op]).await {
-            Ok(()) => Ok(ExecuteResponse::CreatedType),
+        let ops = itertools::chain(
+            plan.replace
+                .map(|id| catalog::Op::DropObject(ObjectId::Item(id))),
+            std::iter::once(catalog::Op::CreateItem {
+                id,
+                name: plan.name,
+                item: CatalogItem::Func(func),
+                owner_id: *session.current_role_id(),
+            }),
+        )
+        .collect();
+        match self.catalog_transact(Some(session), ops).await {
+            Ok(()) => Ok(ExecuteResponse::CreatedFunction),
             Err(err) => Err(err),
         }
     }
@@ -1359,15 +1408,20 @@ impl Coordinator {
 
     pub(super) fn sequence_explain_schema(
         &mut self,
-        ExplainSinkSchemaPlan { json_schema, .. }: ExplainSinkSchemaPlan,
+        ExplainSinkSchemaPlan { schema, .. }: ExplainSinkSchemaPlan,
     ) -> Result<ExecuteResponse, AdapterError> {
-        let json_value: serde_json::Value = serde_json::from_str(&json_schema).map_err(|e| {
-            AdapterError::Explain(mz_repr::explain::ExplainError::SerdeJsonError(e))
-        })?;
-
-        let json_string = json_string(&json_value);
+        let schema = match schema {
+            SinkSchema::Avro(json_schema) => {
+                let json_value: serde_json::Value =
+                    serde_json::from_str(&json_schema).map_err(|e| {
+                        AdapterError::Explain(mz_repr::explain::ExplainError::SerdeJsonError(e))
+                    })?;
+                json_string(&json_value)
+            }
+            SinkSchema::Protobuf(proto_schema) => proto_schema,
+        };
         Ok(Self::send_immediate_rows(vec![Row::pack_slice(&[
-            Datum::String(&json_string),
+            Datum::String(&schema),
         ])]))
     }
 
@@ -2414,6 +2468,19 @@ impl Coordinator {
                         // updated value.
                         MutationKind::Update | MutationKind::Delete => diffs.push((row, -1)),
                         MutationKind::Insert => diffs.push((row, 1)),
+                        // Upserts carry a trailing marker column that says
+                        // whether the row should be inserted or retracted.
+                        // A null marker flags a row that was proposed for
+                        // insertion more than once.
+                        MutationKind::Upsert => {
+                            let mut datums = datum_vec.borrow_with(&row);
+                            let diff = match datums.pop().expect("upsert marker column") {
+                                Datum::True => 1,
+                                Datum::False => -1,
+                                _ => return Err(AdapterError::UpsertAffectsRowTwice),
+                            };
+                            diffs.push((Row::pack_slice(&datums), diff));
+                        }
                     }
                 }
                 for (row, diff) in &diffs {
@@ -4149,12 +4216,18 @@ impl Coordinator {
 
 #[derive(Debug)]
 struct CachedStatisticsOracle {
-    cache: BTreeMap<GlobalId, usize>,
+    cache: BTreeMap<GlobalId, CollectionStatistics>,
 }
 
 impl CachedStatisticsOracle {
+    /// Collects statistics for `ids` as of `as_of`.
+    ///
+    /// `descs` holds the name and relation description of the collections, which are needed to
+    /// interpret the statistics that persist keeps about their columns. Collections without a
+    /// description only get a cardinality estimate.
     pub async fn new<T: Clone + std::fmt::Debug + timely::PartialOrder + Send + Sync>(
         ids: &BTreeSet<GlobalId>,
+        descs: &BTreeMap<GlobalId, (String, RelationDesc)>,
         as_of: &Antichain<T>,
         storage: &dyn mz_storage_client::controller::StorageController<Timestamp = T>,
     ) -> Result<Self, StorageError> {
@@ -4163,24 +4236,129 @@ impl CachedStatisticsOracle {
         for id in ids {
             let stats = storage.snapshot_stats(*id, as_of.clone()).await;
 
-            match stats {
-                Ok(stats) => {
-                    cache.insert(*id, stats.num_updates);
-                }
+            let cardinality = match stats {
+                Ok(stats) => stats.num_updates,
                 Err(StorageError::IdentifierMissing(id)) => {
-                    ::tracing::debug!("no statistics for {id}")
+                    ::tracing::debug!("no statistics for {id}");
+                    continue;
                 }
                 Err(e) => return Err(e),
-            }
+            };
+
+            let columns = match descs.get(id) {
+                Some((name, desc)) => {
+                    match storage.snapshot_parts_stats(*id, as_of.clone()).await {
+                        Ok(parts_stats) => column_statistics(name, desc, &parts_stats),
+                        Err(StorageError::IdentifierMissing(id)) => {
+                            ::tracing::debug!("no part statistics for {id}");
+                            BTreeMap::new()
+                        }
+                        Err(e) => return Err(e),
+                    }
+                }
+                None => BTreeMap::new(),
+            };
+
+            cache.insert(
+                *id,
+                CollectionStatistics {
+                    cardinality,
+                    columns,
+                },
+            );
         }
 
         Ok(Self { cache })
     }
 }
 
+/// Combines the statistics that persist keeps about the parts of a shard into statistics about
+/// the columns of the whole collection.
+///
+/// A column is left out if any part lacks statistics for it. The number of distinct values is an
+/// upper bound: every non-null value of a part is assumed distinct, unless the part's minimum and
+/// maximum agree.
+fn column_statistics(
+    name: &str,
+    desc: &RelationDesc,
+    parts_stats: &SnapshotPartsStats,
+) -> BTreeMap<usize, ColumnStatistics> {
+    #[derive(Default)]
+    struct ColumnSummary {
+        null_count: usize,
+        non_null_count: usize,
+        distinct_count: usize,
+        min: Option<Row>,
+        max: Option<Row>,
+    }
+
+    let mut summaries = (0..desc.arity())
+        .map(|_| Some(ColumnSummary::default()))
+        .collect::<Vec<_>>();
+
+    for SnapshotPartStats { stats, .. } in &parts_stats.parts {
+        let Some(stats) = stats else {
+            return BTreeMap::new();
+        };
+        let stats = stats.decode();
+        let stats = RelationPartStats::new(name, &parts_stats.metrics, desc, &stats);
+        let Some(len) = stats.len() else {
+            return BTreeMap::new();
+        };
+        let arena = RowArena::new();
+
+        for (idx, summary) in summaries.iter_mut().enumerate() {
+            let Some(column) = summary else {
+                continue;
+            };
+            let Some((min, max, null_count)) = stats.col_min_max_nulls(idx, &arena) else {
+                *summary = None;
+                continue;
+            };
+
+            let non_null_count = len.saturating_sub(null_count);
+            column.null_count += null_count;
+            column.non_null_count += non_null_count;
+            column.distinct_count += match (min, max) {
+                (Some(min), Some(max)) if min == max => 1,
+                _ => non_null_count,
+            };
+            if let Some(min) = min {
+                if column.min.as_ref().map_or(true, |m| min < m.unpack_first()) {
+                    column.min = Some(Row::pack_slice(&[min]));
+                }
+            }
+            if let Some(max) = max {
+                if column.max.as_ref().map_or(true, |m| max > m.unpack_first()) {
+                    column.max = Some(Row::pack_slice(&[max]));
+                }
+            }
+        }
+    }
+
+    summaries
+        .into_iter()
+        .enumerate()
+        .filter_map(|(idx, summary)| {
+            let summary = summary?;
+            let statistics = ColumnStatistics::new(
+                summary.null_count,
+                std::cmp::min(summary.distinct_count, summary.non_null_count),
+                summary.min.as_ref().map(|min| min.unpack_first()),
+                summary.max.as_ref().map(|max| max.unpack_first()),
+            );
+            Some((idx, statistics))
+        })
+        .collect()
+}
+
 impl mz_transform::StatisticsOracle for CachedStatisticsOracle {
     fn cardinality_estimate(&self, id: GlobalId) -> Option<usize> {
-        self.cache.get(&id).map(|estimate| *estimate)
+        self.cache.get(&id).map(|statistics| statistics.cardinality)
+    }
+
+    fn collection_statistics(&self, id: GlobalId) -> Option<&CollectionStatistics> {
+        self.cache.get(&id)
     }
 }
 
@@ -4205,9 +4383,24 @@ impl Coordinator {
             self.catalog().system_config().optimizer_stats_timeout()
         };
 
+        let descs = source_ids
+            .iter()
+            .filter_map(|id| {
+                let entry = self.catalog().try_get_entry(id)?;
+                let desc = entry.desc_opt()?.into_owned();
+                let name = self.catalog().resolve_full_name(entry.name(), None);
+                Some((*id, (name.to_string(), desc)))
+            })
+            .collect();
+
         let cached_stats = mz_ore::future::timeout(
             timeout,
-            CachedStatisticsOracle::new(source_ids, query_as_of, self.controller.storage.as_ref()),
+            CachedStatisticsOracle::new(
+                source_ids,
+                &descs,
+                query_as_of,
+                self.controller.storage.as_ref(),
+            ),
         )
         .await;
 
@@ -4300,6 +4493,9 @@ impl Coordinator {
                 OptimizerNoticeKind::IndexKeyEmpty => {
                     system_vars.enable_notices_for_index_empty_key()
                 }
+                OptimizerNoticeKind::JoinHintNotApplied => {
+                    system_vars.enable_notices_for_join_hint_not_applied()
+                }
             };
             if notice_enabled {
                 // We don't need to redact the notice parts because
@@ -97,7 +97,7 @@ impl Coordinator {
             to,
             connection,
             connection_id,
-            format_params,
+            format,
             max_file_size,
         }: plan::CopyToPlan,
         target_cluster: TargetCluster,
@@ -140,7 +140,7 @@ impl Coordinator {
                     uri,
                     connection,
                     connection_id,
-                    format_params,
+                    format,
                     max_file_size,
                 }),
                 explain_ctx: ExplainContext::None,
@@ -703,12 +703,16 @@ impl Coordinator {
 
         let params = std::iter::zip(params.types.iter(), params.datums.iter())
             .map(|(r#type, datum)| {
-                mz_pgrepr::Value::from_datum(datum, r#type).map(|val| {
-                    let mut buf = BytesMut::new();
-                    val.encode_text(&mut buf);
-                    String::from_utf8(Into::<Vec<u8>>::into(buf))
-                        .expect("Serialization shouldn't produce non-UTF-8 strings.")
-                })
+                // Parameters were decoded from their text or binary client
+                // representation, so enum parameters always name a label.
+                mz_pgrepr::Value::from_datum(datum, r#type)
+                    .expect("parameters are valid values of their types")
+                    .map(|val| {
+                        let mut buf = BytesMut::new();
+                        val.encode_text(&mut buf);
+                        String::from_utf8(Into::<Vec<u8>>::into(buf))
+                            .expect("Serialization shouldn't produce non-UTF-8 strings.")
+                    })
             })
             .collect();
         let record = StatementBeganExecutionRecord {
@@ -93,6 +93,8 @@ pub enum AdapterError {
     },
     /// The selection value for a table mutation operation refers to an invalid object.
     InvalidTableMutationSelection,
+    /// An `INSERT ... ON CONFLICT DO UPDATE` proposed the same key more than once.
+    UpsertAffectsRowTwice,
     /// Expression violated a column's constraint
     ConstraintViolation(NotNullViolation),
     /// Transaction cluster was dropped in the middle of a transaction.
@@ -361,6 +363,11 @@ impl AdapterError {
             AdapterError::NoClusterReplicasAvailable(_) => {
                 Some("You can create cluster replicas using CREATE CLUSTER REPLICA".into())
             }
+            AdapterError::UpsertAffectsRowTwice => Some(
+                "Ensure that no rows proposed for insertion within the same command have \
+                 duplicate constrained values."
+                    .into(),
+            ),
             AdapterError::UntargetedLogRead { .. } => Some(
                 "Use `SET cluster_replica = <replica-name>` to target a specific replica in the \
                  active cluster. Note that subsequent queries will only be answered by \
@@ -438,6 +445,7 @@ impl AdapterError {
             AdapterError::InvalidStorageClusterSize { .. } => SqlState::FEATURE_NOT_SUPPORTED,
             AdapterError::SourceOrSinkSizeRequired { .. } => SqlState::FEATURE_NOT_SUPPORTED,
             AdapterError::InvalidTableMutationSelection => SqlState::INVALID_TRANSACTION_STATE,
+            AdapterError::UpsertAffectsRowTwice => SqlState::CARDINALITY_VIOLATION,
             AdapterError::ConstraintViolation(NotNullViolation(_)) => SqlState::NOT_NULL_VIOLATION,
             AdapterError::ConcurrentClusterDrop => SqlState::INVALID_TRANSACTION_STATE,
             AdapterError::NoClusterReplicasAvailable(_) => SqlState::FEATURE_NOT_SUPPORTED,
@@ -569,6 +577,9 @@ impl fmt::Display for AdapterError {
             AdapterError::InvalidTableMutationSelection => {
                 f.write_str("invalid selection: operation may only refer to user-defined tables")
             }
+            AdapterError::UpsertAffectsRowTwice => {
+                f.write_str("ON CONFLICT DO UPDATE command cannot affect row a second time")
+            }
             AdapterError::ConstraintViolation(not_null_violation) => {
                 write!(f, "{}", not_null_violation)
             }
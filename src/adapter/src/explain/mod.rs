@@ -70,6 +70,7 @@ where
         finishing: Default::default(),
         duration: Default::default(),
         optimizer_notices,
+        statistics: dataflow_metainfo.statistics.clone(),
     };
 
     Ok(Explainable::new(&mut plan).explain(&format, &context)?)
@@ -98,6 +99,7 @@ where
         finishing: Default::default(),
         duration: Default::default(),
         optimizer_notices: Default::default(),
+        statistics: Default::default(),
     };
 
     Ok(Explainable::new(&mut plan).explain(&format, &context)?)
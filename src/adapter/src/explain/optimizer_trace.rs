@@ -200,6 +200,7 @@ impl OptimizerTrace {
                 humanizer,
                 config.redacted,
             )?,
+            statistics: dataflow_metainfo.statistics.clone(),
         };
 
         // Drain trace entries of types produced by local optimizer stages.
@@ -220,6 +221,7 @@ impl OptimizerTrace {
                 humanizer,
                 config.redacted,
             )?,
+            statistics: dataflow_metainfo.statistics.clone(),
         };
         results.extend(itertools::chain!(
             self.drain_explainable_entries::<DataflowDescription<OptimizedMirRelationExpr>>(
@@ -223,7 +223,7 @@ impl<'s> Optimize<LocalMirPlan<Resolved<'s>>> for Optimizer {
                         prefix: self.copy_to_context.uri.to_string(),
                         max_file_size: self.copy_to_context.max_file_size,
                         desc: self.copy_to_context.desc.clone(),
-                        format: self.copy_to_context.format_params.clone(),
+                        format: self.copy_to_context.format.clone(),
                     },
                     aws_connection: aws_connection.clone(),
                     connection_id: self.copy_to_context.connection_id,
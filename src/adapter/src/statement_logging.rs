@@ -204,6 +204,7 @@ impl From<&ExecuteResponse> for StatementEndedExecutionReason {
             | ExecuteResponse::CreatedViews
             | ExecuteResponse::CreatedMaterializedView
             | ExecuteResponse::CreatedType
+            | ExecuteResponse::CreatedFunction
             | ExecuteResponse::Deallocate { .. }
             | ExecuteResponse::DeclaredCursor
             | ExecuteResponse::Deleted(_)
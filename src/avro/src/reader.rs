@@ -145,6 +145,12 @@ impl<R: AvroRead> BlockIter<R> {
             inner: Reader::with_schema(reader_schema, inner)?,
         })
     }
+
+    /// Get a reference to the schema that the objects in each `Block` must be
+    /// decoded with. See [`Reader::schema`].
+    pub fn schema(&self) -> &Schema {
+        self.inner.schema()
+    }
 }
 
 impl<R: AvroRead> Iterator for BlockIter<R> {
@@ -854,6 +854,96 @@ pub const TYPE_UUID_ARRAY: BuiltinType<NameReference> = BuiltinType {
     },
 };
 
+pub const TYPE_INET: BuiltinType<NameReference> = BuiltinType {
+    name: "inet",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_INET_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Inet,
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 910,
+            typreceive_oid: 2496,
+        }),
+    },
+};
+
+pub const TYPE_INET_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_inet",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_INET_ARRAY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_INET.name,
+        },
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 750,
+            typreceive_oid: 2400,
+        }),
+    },
+};
+
+pub const TYPE_CIDR: BuiltinType<NameReference> = BuiltinType {
+    name: "cidr",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_CIDR_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Cidr,
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 1267,
+            typreceive_oid: 2498,
+        }),
+    },
+};
+
+pub const TYPE_CIDR_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_cidr",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_CIDR_ARRAY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_CIDR.name,
+        },
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 750,
+            typreceive_oid: 2400,
+        }),
+    },
+};
+
+pub const TYPE_MACADDR: BuiltinType<NameReference> = BuiltinType {
+    name: "macaddr",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_MACADDR_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::MacAddr,
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 436,
+            typreceive_oid: 2494,
+        }),
+    },
+};
+
+pub const TYPE_MACADDR_ARRAY: BuiltinType<NameReference> = BuiltinType {
+    name: "_macaddr",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_MACADDR_ARRAY_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Array {
+            element_reference: TYPE_MACADDR.name,
+        },
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 750,
+            typreceive_oid: 2400,
+        }),
+    },
+};
+
 pub const TYPE_JSONB: BuiltinType<NameReference> = BuiltinType {
     name: "jsonb",
     schema: PG_CATALOG_SCHEMA,
@@ -940,6 +1030,20 @@ pub const TYPE_ANYNONARRAY: BuiltinType<NameReference> = BuiltinType {
     },
 };
 
+pub const TYPE_ANYENUM: BuiltinType<NameReference> = BuiltinType {
+    name: "anyenum",
+    schema: PG_CATALOG_SCHEMA,
+    oid: oid::TYPE_ANYENUM_OID,
+    details: CatalogTypeDetails {
+        typ: CatalogType::Pseudo,
+        array_id: None,
+        pg_metadata: Some(CatalogTypePgMetadata {
+            typinput_oid: 3504,
+            typreceive_oid: 0,
+        }),
+    },
+};
+
 pub const TYPE_ANYRANGE: BuiltinType<NameReference> = BuiltinType {
     name: "anyrange",
     schema: PG_CATALOG_SCHEMA,
@@ -1822,6 +1926,14 @@ pub static MZ_COMPUTE_ERROR_COUNTS_RAW: Lazy<BuiltinLog> = Lazy::new(|| BuiltinL
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_LIR_MAPPING_PER_WORKER: Lazy<BuiltinLog> = Lazy::new(|| BuiltinLog {
+    name: "mz_lir_mapping_per_worker",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::LOG_MZ_LIR_MAPPING_PER_WORKER_OID,
+    variant: LogVariant::Compute(ComputeLog::LirMapping),
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_ACTIVE_PEEKS_PER_WORKER: Lazy<BuiltinLog> = Lazy::new(|| BuiltinLog {
     name: "mz_active_peeks_per_worker",
     schema: MZ_INTERNAL_SCHEMA,
@@ -2343,6 +2455,19 @@ pub static MZ_ROLE_MEMBERS: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     is_retained_metrics_object: false,
     access: vec![PUBLIC_SELECT],
 });
+pub static MZ_ENUM_TYPES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
+    name: "mz_enum_types",
+    schema: MZ_CATALOG_SCHEMA,
+    oid: oid::TABLE_MZ_ENUM_TYPES_OID,
+    desc: RelationDesc::empty()
+        .with_column("id", ScalarType::String.nullable(false))
+        .with_column(
+            "labels",
+            ScalarType::Array(Box::new(ScalarType::String)).nullable(false),
+        ),
+    is_retained_metrics_object: false,
+    access: vec![PUBLIC_SELECT],
+});
 pub static MZ_PSEUDO_TYPES: Lazy<BuiltinTable> = Lazy::new(|| BuiltinTable {
     name: "mz_pseudo_types",
     schema: MZ_CATALOG_SCHEMA,
@@ -3884,6 +4009,7 @@ FROM
             UNION ALL SELECT id, 'b' FROM mz_catalog.mz_base_types
             UNION ALL SELECT id, 'l' FROM mz_catalog.mz_list_types
             UNION ALL SELECT id, 'm' FROM mz_catalog.mz_map_types
+            UNION ALL SELECT id, 'e' FROM mz_catalog.mz_enum_types
             UNION ALL SELECT id, 'p' FROM mz_catalog.mz_pseudo_types
         )
             AS t ON mz_types.id = t.id
@@ -4003,10 +4129,12 @@ pub static PG_ENUM: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     column_defs: None,
     sql: "SELECT
     NULL::pg_catalog.oid AS oid,
-    NULL::pg_catalog.oid AS enumtypid,
-    NULL::pg_catalog.float4 AS enumsortorder,
-    NULL::pg_catalog.text AS enumlabel
-WHERE false",
+    mz_types.oid AS enumtypid,
+    labels.position::pg_catalog.float4 AS enumsortorder,
+    labels.label AS enumlabel
+FROM mz_catalog.mz_enum_types
+    JOIN mz_catalog.mz_types ON mz_types.id = mz_enum_types.id,
+    unnest(mz_enum_types.labels) WITH ORDINALITY AS labels (label, position)",
     access: vec![PUBLIC_SELECT],
 });
 
@@ -4386,6 +4514,18 @@ HAVING pg_catalog.sum(count) != 0",
     access: vec![PUBLIC_SELECT],
 });
 
+pub static MZ_LIR_MAPPING: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
+    name: "mz_lir_mapping",
+    schema: MZ_INTERNAL_SCHEMA,
+    oid: oid::VIEW_MZ_LIR_MAPPING_OID,
+    column_defs: None,
+    sql: "
+SELECT export_id, lir_id, operator, parent_lir_id, nesting, operator_id_start, operator_id_end
+FROM mz_internal.mz_lir_mapping_per_worker
+WHERE worker_id = 0",
+    access: vec![PUBLIC_SELECT],
+});
+
 pub static MZ_COMPUTE_OPERATOR_HYDRATION_STATUSES: Lazy<BuiltinView> = Lazy::new(|| BuiltinView {
     name: "mz_compute_operator_hydration_statuses",
     schema: MZ_INTERNAL_SCHEMA,
@@ -6723,6 +6863,7 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Type(&TYPE_ANY),
         Builtin::Type(&TYPE_ANYARRAY),
         Builtin::Type(&TYPE_ANYELEMENT),
+        Builtin::Type(&TYPE_ANYENUM),
         Builtin::Type(&TYPE_ANYNONARRAY),
         Builtin::Type(&TYPE_ANYRANGE),
         Builtin::Type(&TYPE_BOOL),
@@ -6775,6 +6916,12 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Type(&TYPE_TIMESTAMPTZ_ARRAY),
         Builtin::Type(&TYPE_UUID),
         Builtin::Type(&TYPE_UUID_ARRAY),
+        Builtin::Type(&TYPE_INET),
+        Builtin::Type(&TYPE_INET_ARRAY),
+        Builtin::Type(&TYPE_CIDR),
+        Builtin::Type(&TYPE_CIDR_ARRAY),
+        Builtin::Type(&TYPE_MACADDR),
+        Builtin::Type(&TYPE_MACADDR_ARRAY),
         Builtin::Type(&TYPE_VARCHAR),
         Builtin::Type(&TYPE_VARCHAR_ARRAY),
         Builtin::Type(&TYPE_INT2_VECTOR),
@@ -6859,6 +7006,7 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Log(&MZ_COMPUTE_IMPORT_FRONTIERS_PER_WORKER),
         Builtin::Log(&MZ_COMPUTE_DELAYS_HISTOGRAM_RAW),
         Builtin::Log(&MZ_COMPUTE_ERROR_COUNTS_RAW),
+        Builtin::Log(&MZ_LIR_MAPPING_PER_WORKER),
         Builtin::Table(&MZ_KAFKA_SINKS),
         Builtin::Table(&MZ_KAFKA_CONNECTIONS),
         Builtin::Table(&MZ_KAFKA_SOURCES),
@@ -6880,6 +7028,7 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::Table(&MZ_BASE_TYPES),
         Builtin::Table(&MZ_LIST_TYPES),
         Builtin::Table(&MZ_MAP_TYPES),
+        Builtin::Table(&MZ_ENUM_TYPES),
         Builtin::Table(&MZ_ROLES),
         Builtin::Table(&MZ_ROLE_MEMBERS),
         Builtin::Table(&MZ_PSEUDO_TYPES),
@@ -7067,6 +7216,7 @@ pub static BUILTINS_STATIC: Lazy<Vec<Builtin<NameReference>>> = Lazy::new(|| {
         Builtin::View(&MZ_MATERIALIZATION_LAG),
         Builtin::View(&MZ_COMPUTE_ERROR_COUNTS_PER_WORKER),
         Builtin::View(&MZ_COMPUTE_ERROR_COUNTS),
+        Builtin::View(&MZ_LIR_MAPPING),
         Builtin::View(&MZ_COMPUTE_OPERATOR_HYDRATION_STATUSES),
         Builtin::Source(&MZ_CLUSTER_REPLICA_FRONTIERS),
         Builtin::Source(&MZ_CLUSTER_REPLICA_HEARTBEATS),
@@ -116,8 +116,12 @@ impl<'a> Transaction<'a> {
             items: TableTransaction::new(items, |a: &ItemValue, b| {
                 let a_type = a.item_type();
                 let b_type = b.item_type();
+                // Functions can be overloaded; the planner ensures that the
+                // overloads of a name differ in their parameter types.
+                let overload = a_type == CatalogItemType::Func && b_type == CatalogItemType::Func;
                 a.schema_id == b.schema_id
                     && a.name == b.name
+                    && !overload
                     && ((a_type != CatalogItemType::Type && b_type != CatalogItemType::Type)
                         || (a_type == CatalogItemType::Type && b_type.conflicts_with_type())
                         || (b_type == CatalogItemType::Type && a_type.conflicts_with_type()))
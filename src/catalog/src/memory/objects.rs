@@ -12,6 +12,7 @@
 use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet};
 use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
 use mz_adapter_types::compaction::CompactionWindow;
@@ -41,6 +42,7 @@ use mz_sql::catalog::{
     DefaultPrivilegeAclItem, DefaultPrivilegeObject, IdReference, RoleAttributes, RoleMembership,
     RoleVars, SystemObjectType,
 };
+use mz_sql::func::SqlFunction;
 use mz_sql::names::{
     Aug, CommentObjectId, DatabaseId, FullItemName, QualifiedItemName, QualifiedSchemaName,
     ResolvedDatabaseSpecifier, ResolvedIds, SchemaId, SchemaSpecifier,
@@ -92,6 +94,10 @@ pub struct Schema {
     pub oid: u32,
     pub items: BTreeMap<String, GlobalId>,
     pub functions: BTreeMap<String, GlobalId>,
+    /// The IDs of the overloads of user-defined functions, by name.
+    ///
+    /// `functions` maps each of these names to the first of its IDs.
+    pub function_overloads: BTreeMap<String, BTreeSet<GlobalId>>,
     pub types: BTreeMap<String, GlobalId>,
     pub owner_id: RoleId,
     pub privileges: PrivilegeMap,
@@ -659,10 +665,15 @@ impl Sink {
 
     /// Output format of the sink.
     pub fn format(&self) -> &str {
-        let StorageSinkConnection::Kafka(connection) = &self.connection;
-        match &connection.format {
-            KafkaSinkFormat::Avro { .. } => "avro",
-            KafkaSinkFormat::Json => "json",
+        match &self.connection {
+            StorageSinkConnection::Kafka(connection) => match &connection.format {
+                KafkaSinkFormat::Avro { .. } => "avro",
+                KafkaSinkFormat::Json => "json",
+                KafkaSinkFormat::Protobuf { .. } => "protobuf",
+            },
+            StorageSinkConnection::Http(_) => "json",
+            // Rows are written using the upstream table's own column types.
+            StorageSinkConnection::Postgres(_) => "postgres",
         }
     }
 
@@ -720,8 +731,57 @@ pub struct Type {
 
 #[derive(Debug, Clone, Serialize)]
 pub struct Func {
+    /// Parse-able SQL that defines this function, if it is user-defined.
+    pub create_sql: Option<String>,
     #[serde(skip)]
-    pub inner: &'static mz_sql::func::Func,
+    pub definition: FuncDefinition,
+    pub resolved_ids: ResolvedIds,
+}
+
+impl Func {
+    /// Returns the implementations of this function.
+    pub fn inner(&self) -> &mz_sql::func::Func {
+        match &self.definition {
+            FuncDefinition::Builtin(func) => func,
+            FuncDefinition::Sql { inner, .. } => inner,
+        }
+    }
+
+    /// Returns the definition of this function, if it is user-defined.
+    pub fn sql_function(&self) -> Option<&SqlFunction> {
+        match &self.definition {
+            FuncDefinition::Builtin(_) => None,
+            FuncDefinition::Sql { function, .. } => Some(function),
+        }
+    }
+
+    /// Returns the IDs of all overloads of this function, if it is
+    /// user-defined.
+    pub fn overloads(&self) -> &[GlobalId] {
+        match &self.definition {
+            FuncDefinition::Builtin(_) => &[],
+            FuncDefinition::Sql { overloads, .. } => overloads,
+        }
+    }
+}
+
+/// Where the implementations of a [`Func`] come from.
+#[derive(Debug, Clone)]
+pub enum FuncDefinition {
+    /// A built-in function.
+    Builtin(&'static mz_sql::func::Func),
+    /// A user-defined SQL function, whose body is inlined at its call sites.
+    ///
+    /// User-defined functions can be overloaded by creating several functions
+    /// with the same name and different parameter types. Each overload is its
+    /// own catalog item.
+    Sql {
+        function: SqlFunction,
+        /// The implementations of all overloads of the function.
+        inner: Arc<mz_sql::func::Func>,
+        /// The IDs of all overloads of the function, including this one.
+        overloads: Vec<GlobalId>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -777,12 +837,9 @@ impl CatalogItem {
         }
     }
 
-    pub fn func(
-        &self,
-        entry: &CatalogEntry,
-    ) -> Result<&'static mz_sql::func::Func, SqlCatalogError> {
+    pub fn func(&self, entry: &CatalogEntry) -> Result<&mz_sql::func::Func, SqlCatalogError> {
         match &self {
-            CatalogItem::Func(func) => Ok(func.inner),
+            CatalogItem::Func(func) => Ok(func.inner()),
             _ => Err(SqlCatalogError::UnexpectedType {
                 name: entry.name().item.to_string(),
                 actual_type: entry.item_type(),
@@ -827,7 +884,7 @@ impl CatalogItem {
     pub fn references(&self) -> &ResolvedIds {
         static EMPTY: Lazy<ResolvedIds> = Lazy::new(|| ResolvedIds(BTreeSet::new()));
         match self {
-            CatalogItem::Func(_) => &*EMPTY,
+            CatalogItem::Func(func) => &func.resolved_ids,
             CatalogItem::Index(idx) => &idx.resolved_ids,
             CatalogItem::Sink(sink) => &sink.resolved_ids,
             CatalogItem::Source(source) => &source.resolved_ids,
@@ -849,8 +906,9 @@ impl CatalogItem {
     pub fn uses(&self) -> BTreeSet<GlobalId> {
         let mut uses = self.references().0.clone();
         match self {
-            // TODO(jkosh44) This isn't really correct for functions. They may use other objects in
-            // their implementation. However, currently there's no way to get that information.
+            // TODO(jkosh44) This isn't really correct for built-in functions. They may use other
+            // objects in their implementation. However, currently there's no way to get that
+            // information.
             CatalogItem::Func(_) => {}
             CatalogItem::Index(_) => {}
             CatalogItem::Sink(_) => {}
@@ -959,7 +1017,11 @@ impl CatalogItem {
                 i.create_sql = i.create_sql.map(do_rewrite).transpose()?;
                 Ok(CatalogItem::Type(i))
             }
-            CatalogItem::Func(i) => Ok(CatalogItem::Func(i.clone())),
+            CatalogItem::Func(i) => {
+                let mut i = i.clone();
+                i.create_sql = i.create_sql.map(do_rewrite).transpose()?;
+                Ok(CatalogItem::Func(i))
+            }
         }
     }
 
@@ -1022,6 +1084,11 @@ impl CatalogItem {
                 i.create_sql = do_rewrite(i.create_sql)?;
                 Ok(CatalogItem::Secret(i))
             }
+            CatalogItem::Func(i) if !rename_self => {
+                let mut i = i.clone();
+                i.create_sql = i.create_sql.map(do_rewrite).transpose()?;
+                Ok(CatalogItem::Func(i))
+            }
             CatalogItem::Func(_) | CatalogItem::Type(_) => {
                 unreachable!("{}s cannot be renamed", self.typ())
             }
@@ -1168,7 +1235,11 @@ impl CatalogItem {
                 .clone(),
             CatalogItem::Secret(secret) => secret.create_sql.clone(),
             CatalogItem::Connection(connection) => connection.create_sql.clone(),
-            CatalogItem::Func(_) => unreachable!("cannot serialize functions yet"),
+            CatalogItem::Func(func) => func
+                .create_sql
+                .as_ref()
+                .expect("builtin functions cannot be serialized")
+                .clone(),
         }
     }
 
@@ -1194,7 +1265,9 @@ impl CatalogItem {
             CatalogItem::Type(typ) => typ.create_sql.expect("builtin types cannot be serialized"),
             CatalogItem::Secret(secret) => secret.create_sql,
             CatalogItem::Connection(connection) => connection.create_sql,
-            CatalogItem::Func(_) => unreachable!("cannot serialize functions yet"),
+            CatalogItem::Func(func) => func
+                .create_sql
+                .expect("builtin functions cannot be serialized"),
         }
     }
 }
@@ -1218,7 +1291,7 @@ impl CatalogEntry {
     }
 
     /// Returns the [`mz_sql::func::Func`] associated with this `CatalogEntry`.
-    pub fn func(&self) -> Result<&'static mz_sql::func::Func, SqlCatalogError> {
+    pub fn func(&self) -> Result<&mz_sql::func::Func, SqlCatalogError> {
         self.item.func(self)
     }
 
@@ -1877,6 +1950,11 @@ impl mz_sql::catalog::CatalogSchema for Schema {
             self.items
                 .values()
                 .chain(self.functions.values())
+                .chain(
+                    self.function_overloads
+                        .values()
+                        .flat_map(|ids| ids.iter().skip(1)),
+                )
                 .chain(self.types.values())
                 .copied(),
         )
@@ -2001,10 +2079,24 @@ impl mz_sql::catalog::CatalogItem for CatalogEntry {
         self.desc(name)
     }
 
-    fn func(&self) -> Result<&'static mz_sql::func::Func, SqlCatalogError> {
+    fn func(&self) -> Result<&mz_sql::func::Func, SqlCatalogError> {
         self.func()
     }
 
+    fn sql_function(&self) -> Option<&SqlFunction> {
+        match self.item() {
+            CatalogItem::Func(func) => func.sql_function(),
+            _ => None,
+        }
+    }
+
+    fn function_overloads(&self) -> &[GlobalId] {
+        match self.item() {
+            CatalogItem::Func(func) => func.overloads(),
+            _ => &[],
+        }
+    }
+
     fn source_desc(&self) -> Result<Option<&SourceDesc<ReferencedConnection>>, SqlCatalogError> {
         self.source_desc()
     }
@@ -2033,7 +2125,9 @@ impl mz_sql::catalog::CatalogItem for CatalogEntry {
             }
             CatalogItem::Secret(Secret { create_sql, .. }) => create_sql,
             CatalogItem::Connection(Connection { create_sql, .. }) => create_sql,
-            CatalogItem::Func(_) => "<builtin>",
+            CatalogItem::Func(Func { create_sql, .. }) => {
+                create_sql.as_deref().unwrap_or("<builtin>")
+            }
             CatalogItem::Log(_) => "<builtin>",
         }
     }
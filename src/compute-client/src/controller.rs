@@ -110,6 +110,9 @@ pub struct ComputeReplicaConfig {
     ///
     /// See [`differential_dataflow::Config::idle_merge_effort`].
     pub idle_arrangement_merge_effort: Option<u32>,
+    /// Whether to spill the row data of large arrangement batches to the replica's scratch
+    /// directory.
+    pub arrangement_spill: bool,
 }
 
 /// Logging configuration of a replica.
@@ -522,6 +525,7 @@ where
                 log_logging: config.logging.log_logging,
                 index_logs: Default::default(),
             },
+            arrangement_spill: config.arrangement_spill,
             idle_arrangement_merge_effort,
             arrangement_exert_proportionality,
             grpc_client: self.compute.config.grpc_client.clone(),
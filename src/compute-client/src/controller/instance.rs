@@ -582,6 +582,7 @@ where
         let dummy_logging_config = Default::default();
         instance.send(ComputeCommand::CreateInstance(InstanceConfig {
             logging: dummy_logging_config,
+            arrangement_spill: false,
         }));
 
         instance
@@ -39,6 +39,7 @@ type Client<T> = Partitioned<ComputeGrpcClient, ComputeCommand<T>, ComputeRespon
 pub(super) struct ReplicaConfig {
     pub location: ClusterReplicaLocation,
     pub logging: LoggingConfig,
+    pub arrangement_spill: bool,
     pub idle_arrangement_merge_effort: u32,
     pub arrangement_exert_proportionality: u32,
     pub grpc_client: GrpcClientParameters,
@@ -252,8 +253,13 @@ where
     /// Most `ComputeCommand`s are independent of the target replica, but some
     /// contain replica-specific fields that must be adjusted before sending.
     fn specialize_command(&self, command: &mut ComputeCommand<T>) {
-        if let ComputeCommand::CreateInstance(InstanceConfig { logging }) = command {
+        if let ComputeCommand::CreateInstance(InstanceConfig {
+            logging,
+            arrangement_spill,
+        }) = command
+        {
             *logging = self.config.logging.clone();
+            *arrangement_spill = self.config.arrangement_spill;
         }
 
         if let ComputeCommand::CreateTimely { config, epoch } = command {
@@ -329,12 +329,16 @@ impl Arbitrary for ComputeCommand<mz_repr::Timestamp> {
 pub struct InstanceConfig {
     /// TODO(#25239): Add documentation.
     pub logging: LoggingConfig,
+    /// Whether to spill the row data of large arrangement batches to the replica's scratch
+    /// directory.
+    pub arrangement_spill: bool,
 }
 
 impl RustType<ProtoInstanceConfig> for InstanceConfig {
     fn into_proto(&self) -> ProtoInstanceConfig {
         ProtoInstanceConfig {
             logging: Some(self.logging.into_proto()),
+            arrangement_spill: self.arrangement_spill,
         }
     }
 
@@ -343,6 +347,7 @@ impl RustType<ProtoInstanceConfig> for InstanceConfig {
             logging: proto
                 .logging
                 .into_rust_if_some("ProtoCreateInstance::logging")?,
+            arrangement_spill: proto.arrangement_spill,
         })
     }
 }
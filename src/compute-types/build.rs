@@ -57,6 +57,7 @@ fn main() {
                 "compute-types/src/plan/reduce.proto",
                 "compute-types/src/plan/threshold.proto",
                 "compute-types/src/plan/top_k.proto",
+                "compute-types/src/plan/window.proto",
             ],
             &[".."],
         )
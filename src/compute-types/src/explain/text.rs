@@ -26,7 +26,7 @@ use std::ops::Deref;
 
 use itertools::{izip, Itertools};
 use mz_expr::explain::{fmt_text_constant_rows, HumanizedExplain, HumanizerMode};
-use mz_expr::{Id, MirScalarExpr};
+use mz_expr::{Id, LagLeadType, MirScalarExpr};
 use mz_ore::str::{separated, IndentLike, StrExt};
 use mz_repr::explain::text::DisplayText;
 use mz_repr::explain::{
@@ -328,6 +328,87 @@ impl DisplayText<PlanRenderingContext<'_, Plan>> for Plan {
                     input.fmt_text(f, ctx)
                 })?;
             }
+            Window {
+                input,
+                key_val_plan,
+                window_plan,
+                input_key,
+                mfp_after,
+                lir_id: _,
+            } => {
+                use crate::plan::window::WindowPlan;
+                match window_plan {
+                    WindowPlan::LagLead(plan) => {
+                        let func = match plan.lag_lead {
+                            LagLeadType::Lag => "lag",
+                            LagLeadType::Lead => "lead",
+                        };
+                        let order_by = mode.seq(std::slice::from_ref(&plan.order_by), None);
+                        let order_by = separated(", ", order_by);
+                        writeln!(
+                            f,
+                            "{}Window::LagLead {} order_by=[{}]{}",
+                            ctx.indent, func, order_by, annotations
+                        )?;
+                    }
+                    WindowPlan::RowNumber(plan) => {
+                        let order_by = mode.seq(std::slice::from_ref(&plan.order_by), None);
+                        let order_by = separated(", ", order_by);
+                        writeln!(
+                            f,
+                            "{}Window::RowNumber order_by=[{}]{}",
+                            ctx.indent, order_by, annotations
+                        )?;
+                    }
+                    WindowPlan::FirstValue(plan) => {
+                        let order_by = mode.seq(std::slice::from_ref(&plan.order_by), None);
+                        let order_by = separated(", ", order_by);
+                        writeln!(
+                            f,
+                            "{}Window::FirstValue order_by=[{}]{}",
+                            ctx.indent, order_by, annotations
+                        )?;
+                    }
+                    WindowPlan::Sum(plan) => {
+                        let func = mode.expr(&plan.func, None);
+                        let order_by = mode.seq(std::slice::from_ref(&plan.order_by), None);
+                        let order_by = separated(", ", order_by);
+                        let units = if plan.peers { "range" } else { "rows" };
+                        writeln!(
+                            f,
+                            "{}Window::Sum {} order_by=[{}] units={}{}",
+                            ctx.indent, func, order_by, units, annotations
+                        )?;
+                    }
+                }
+                ctx.indented(|ctx| {
+                    writeln!(f, "{}val_plan", ctx.indent)?;
+                    ctx.indented(|ctx| {
+                        let val_plan = mode.expr(key_val_plan.val_plan.deref(), None);
+                        val_plan.fmt_text(f, ctx)
+                    })?;
+                    if key_val_plan.key_plan.deref().is_identity() {
+                        writeln!(f, "{}key_plan=id", ctx.indent)?;
+                    } else {
+                        writeln!(f, "{}key_plan", ctx.indent)?;
+                        ctx.indented(|ctx| {
+                            let key_plan = mode.expr(key_val_plan.key_plan.deref(), None);
+                            key_plan.fmt_text(f, ctx)
+                        })?;
+                    }
+                    if let Some(key) = input_key {
+                        let key = mode.seq(key, None);
+                        let key = CompactScalars(key);
+                        writeln!(f, "{}input_key={}", ctx.indent, key)?;
+                    }
+                    if !mfp_after.is_identity() {
+                        writeln!(f, "{}mfp_after", ctx.indent)?;
+                        ctx.indented(|ctx| mode.expr(mfp_after, None).fmt_text(f, ctx))?;
+                    }
+
+                    input.fmt_text(f, ctx)
+                })?;
+            }
             TopK {
                 input,
                 top_k_plan,
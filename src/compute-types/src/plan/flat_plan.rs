@@ -24,6 +24,7 @@ use crate::plan::join::JoinPlan;
 use crate::plan::reduce::{KeyValPlan, ReducePlan};
 use crate::plan::threshold::ThresholdPlan;
 use crate::plan::top_k::TopKPlan;
+use crate::plan::window::WindowPlan;
 use crate::plan::{AvailableCollections, GetPlan, LirId, Plan, ProtoLetRecLimit};
 
 include!(concat!(
@@ -197,6 +198,24 @@ pub enum FlatPlanNode<T = mz_repr::Timestamp> {
         /// MFP must be free from temporal predicates so that it can be readily evaluated.
         mfp_after: MapFilterProject,
     },
+    /// Window function evaluation by key, maintained incrementally.
+    ///
+    /// This stage replaces a `FlatMap` that unnests the list produced by a `Reduce` computing a
+    /// window aggregate. Its output records are the key of the reduction followed by an element of
+    /// the list, before `mfp_after` is applied.
+    Window {
+        /// The input collection.
+        input: LirId,
+        /// A plan for changing input records into key, value pairs, where the value is the input
+        /// of the window aggregate.
+        key_val_plan: KeyValPlan,
+        /// A plan for computing the window function.
+        window_plan: WindowPlan,
+        /// The particular arrangement of the input we expect to use, if any.
+        input_key: Option<Vec<MirScalarExpr>>,
+        /// Linear operator to apply to each output record.
+        mfp_after: MapFilterProject,
+    },
     /// Key-based "Top K" operator, retaining the first K records in each group.
     TopK {
         /// The input collection.
@@ -456,6 +475,25 @@ impl<T> From<Plan<T>> for FlatPlan<T> {
 
                     todo.push(*input);
                 }
+                Plan::Window {
+                    input,
+                    key_val_plan,
+                    window_plan,
+                    input_key,
+                    mfp_after,
+                    lir_id,
+                } => {
+                    let node = Window {
+                        input: input.lir_id(),
+                        key_val_plan,
+                        window_plan,
+                        input_key,
+                        mfp_after,
+                    };
+                    insert_node(lir_id, node);
+
+                    todo.push(*input);
+                }
                 Plan::TopK {
                     input,
                     top_k_plan,
@@ -729,8 +767,56 @@ impl<T: Clone> FlatPlanNode<T> {
 }
 
 impl<T> FlatPlanNode<T> {
+    /// Returns a short, human-readable description of this node.
+    ///
+    /// The descriptions match the operator names used when explaining physical plans.
+    pub fn humanize(&self) -> String {
+        use FlatPlanNode::*;
+
+        match self {
+            Constant { rows: Ok(_) } => "Constant".into(),
+            Constant { rows: Err(_) } => "Error".into(),
+            Get { id, plan, .. } => match plan {
+                GetPlan::PassArrangements => format!("Get::PassArrangements {id}"),
+                GetPlan::Arrangement(..) => format!("Get::Arrangement {id}"),
+                GetPlan::Collection(_) => format!("Get::Collection {id}"),
+            },
+            LetRec { .. } => "LetRec".into(),
+            Mfp { .. } => "Mfp".into(),
+            FlatMap { func, .. } => format!("FlatMap {func}"),
+            Join { plan, .. } => match plan {
+                JoinPlan::Linear(_) => "Join::Linear".into(),
+                JoinPlan::Delta(_) => "Join::Delta".into(),
+            },
+            Reduce { plan, .. } => match plan {
+                ReducePlan::Distinct => "Reduce::Distinct".into(),
+                ReducePlan::Accumulable(_) => "Reduce::Accumulable".into(),
+                ReducePlan::Hierarchical(_) => "Reduce::Hierarchical".into(),
+                ReducePlan::Basic(_) => "Reduce::Basic".into(),
+                ReducePlan::Collation(_) => "Reduce::Collation".into(),
+            },
+            Window { window_plan, .. } => match window_plan {
+                WindowPlan::LagLead(_) => "Window::LagLead".into(),
+                WindowPlan::RowNumber(_) => "Window::RowNumber".into(),
+                WindowPlan::FirstValue(_) => "Window::FirstValue".into(),
+                WindowPlan::Sum(_) => "Window::Sum".into(),
+            },
+            TopK { top_k_plan, .. } => match top_k_plan {
+                TopKPlan::MonotonicTop1(_) => "TopK::MonotonicTop1".into(),
+                TopKPlan::MonotonicTopK(_) => "TopK::MonotonicTopK".into(),
+                TopKPlan::Basic(_) => "TopK::Basic".into(),
+            },
+            Negate { .. } => "Negate".into(),
+            Threshold { threshold_plan, .. } => match threshold_plan {
+                ThresholdPlan::Basic(_) => "Threshold::Basic".into(),
+            },
+            Union { .. } => "Union".into(),
+            ArrangeBy { .. } => "ArrangeBy".into(),
+        }
+    }
+
     /// Returns the IDs of input nodes to this node.
-    fn input_lir_ids(&self) -> impl Iterator<Item = LirId> {
+    pub fn input_lir_ids(&self) -> impl Iterator<Item = LirId> {
         use FlatPlanNode::*;
 
         let mut list = Vec::new();
@@ -745,6 +831,7 @@ impl<T> FlatPlanNode<T> {
             Mfp { input, .. }
             | FlatMap { input, .. }
             | Reduce { input, .. }
+            | Window { input, .. }
             | TopK { input, .. }
             | Negate { input, .. }
             | Threshold { input, .. }
@@ -775,6 +862,7 @@ impl<T> FlatPlanNode<T> {
             Mfp { input, .. }
             | FlatMap { input, .. }
             | Reduce { input, .. }
+            | Window { input, .. }
             | TopK { input, .. }
             | Negate { input, .. }
             | Threshold { input, .. }
@@ -932,6 +1020,19 @@ impl RustType<ProtoFlatPlanNode> for FlatPlanNode {
                 input_key: input_k_into(input_key),
                 mfp_after: Some(mfp_after.into_proto()),
             }),
+            Self::Window {
+                input,
+                key_val_plan,
+                window_plan,
+                input_key,
+                mfp_after,
+            } => Kind::Window(ProtoWindow {
+                input: *input,
+                key_val_plan: Some(key_val_plan.into_proto()),
+                window_plan: Some(window_plan.into_proto()),
+                input_key: input_k_into(input_key),
+                mfp_after: Some(mfp_after.into_proto()),
+            }),
             Self::TopK { input, top_k_plan } => Kind::TopK(ProtoTopK {
                 input: *input,
                 top_k_plan: Some(top_k_plan.into_proto()),
@@ -1047,6 +1148,19 @@ impl RustType<ProtoFlatPlanNode> for FlatPlanNode {
                 input_key: input_k_try_into(proto.input_key)?,
                 mfp_after: proto.mfp_after.into_rust_if_some("Proto::mfp_after")?,
             },
+            Kind::Window(proto) => Self::Window {
+                input: proto.input,
+                key_val_plan: proto
+                    .key_val_plan
+                    .into_rust_if_some("ProtoWindow::key_val_plan")?,
+                window_plan: proto
+                    .window_plan
+                    .into_rust_if_some("ProtoWindow::window_plan")?,
+                input_key: input_k_try_into(proto.input_key)?,
+                mfp_after: proto
+                    .mfp_after
+                    .into_rust_if_some("ProtoWindow::mfp_after")?,
+            },
             Kind::TopK(proto) => Self::TopK {
                 input: proto.input,
                 top_k_plan: proto
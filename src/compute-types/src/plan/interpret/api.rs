@@ -31,6 +31,7 @@ use crate::plan::join::JoinPlan;
 use crate::plan::reduce::{KeyValPlan, ReducePlan};
 use crate::plan::threshold::ThresholdPlan;
 use crate::plan::top_k::TopKPlan;
+use crate::plan::window::WindowPlan;
 use crate::plan::{AvailableCollections, GetPlan, Plan};
 
 /// An [abstract interpreter] for [Plan] expressions.
@@ -106,6 +107,17 @@ pub trait Interpreter<T = mz_repr::Timestamp> {
         mfp_after: &MapFilterProject,
     ) -> Self::Domain;
 
+    /// Interprets a [Plan::Window] node.
+    fn window(
+        &self,
+        ctx: &Context<Self::Domain>,
+        input: Self::Domain,
+        key_val_plan: &KeyValPlan,
+        window_plan: &WindowPlan,
+        input_key: &Option<Vec<MirScalarExpr>>,
+        mfp_after: &MapFilterProject,
+    ) -> Self::Domain;
+
     /// TODO(#25239): Add documentation.
     fn top_k(
         &self,
@@ -424,6 +436,26 @@ where
                         mfp_after,
                     ))
                 }
+                Window {
+                    input,
+                    key_val_plan,
+                    window_plan,
+                    input_key,
+                    mfp_after,
+                    lir_id: _,
+                } => {
+                    // Descend recursively into all children.
+                    let input = self.apply_rec(input, rg)?;
+                    // Interpret the current node.
+                    Ok(self.interpret.window(
+                        &self.ctx,
+                        input,
+                        key_val_plan,
+                        window_plan,
+                        input_key,
+                        mfp_after,
+                    ))
+                }
                 TopK {
                     input,
                     top_k_plan,
@@ -735,6 +767,30 @@ where
                     // Pass the interpretation result up.
                     Ok(result)
                 }
+                Window {
+                    input,
+                    key_val_plan,
+                    window_plan,
+                    input_key,
+                    mfp_after,
+                    lir_id: _,
+                } => {
+                    // Descend recursively into all children.
+                    let input = self.apply_rec(input, rg)?;
+                    // Interpret the current node.
+                    let result = self.interpret.window(
+                        &self.ctx,
+                        input.clone(),
+                        key_val_plan,
+                        window_plan,
+                        input_key,
+                        mfp_after,
+                    );
+                    // Mutate the current node using the given `action`.
+                    (self.action)(expr, &result, &[input]);
+                    // Pass the interpretation result up.
+                    Ok(result)
+                }
                 TopK {
                     input,
                     top_k_plan,
@@ -24,6 +24,7 @@ use crate::plan::join::JoinPlan;
 use crate::plan::reduce::{KeyValPlan, ReducePlan};
 use crate::plan::threshold::ThresholdPlan;
 use crate::plan::top_k::TopKPlan;
+use crate::plan::window::WindowPlan;
 use crate::plan::{AvailableCollections, GetPlan};
 
 /// Represents a boolean physical monotonicity property, where the bottom value
@@ -185,6 +186,20 @@ impl<T> Interpreter<T> for SingleTimeMonotonic<'_, T> {
         PhysicallyMonotonic(!ctx.is_rec)
     }
 
+    fn window(
+        &self,
+        ctx: &Context<Self::Domain>,
+        _input: Self::Domain,
+        _key_val_plan: &KeyValPlan,
+        _window_plan: &WindowPlan,
+        _input_key: &Option<Vec<MirScalarExpr>>,
+        _mfp_after: &MapFilterProject,
+    ) -> Self::Domain {
+        // The window function output is produced by a reduction, so the
+        // judgment here is the same as for reduce.
+        PhysicallyMonotonic(!ctx.is_rec)
+    }
+
     fn top_k(
         &self,
         ctx: &Context<Self::Domain>,
@@ -14,11 +14,11 @@ use std::collections::BTreeMap;
 use mz_expr::JoinImplementation::{DeltaQuery, Differential, IndexedFilter, Unimplemented};
 use mz_expr::{
     permutation_for_arrangement, Id, JoinInputMapper, MapFilterProject, MirRelationExpr,
-    MirScalarExpr, OptimizedMirRelationExpr,
+    MirScalarExpr, OptimizedMirRelationExpr, TableFunc,
 };
 use mz_ore::{soft_assert_eq_or_log, soft_panic_or_log};
 use mz_repr::optimize::OptimizerFeatures;
-use mz_repr::GlobalId;
+use mz_repr::{GlobalId, ScalarType};
 use timely::progress::Timestamp;
 
 use crate::dataflows::{BuildDesc, DataflowDescription, IndexImport};
@@ -26,6 +26,7 @@ use crate::plan::join::{DeltaJoinPlan, JoinPlan, LinearJoinPlan};
 use crate::plan::reduce::{KeyValPlan, ReducePlan};
 use crate::plan::threshold::ThresholdPlan;
 use crate::plan::top_k::TopKPlan;
+use crate::plan::window::WindowPlan;
 use crate::plan::{AvailableCollections, GetPlan, LirId, Plan};
 
 pub(super) struct Context {
@@ -37,6 +38,8 @@ pub(super) struct Context {
     debug_info: LirDebugInfo,
     /// Whether to enable fusion of MFPs in reductions.
     enable_reduce_mfp_fusion: bool,
+    /// Whether to enable the incremental rendering of window functions.
+    enable_incremental_window_functions: bool,
 }
 
 impl Context {
@@ -49,6 +52,7 @@ impl Context {
                 id: GlobalId::Transient(0),
             },
             enable_reduce_mfp_fusion: features.enable_reduce_mfp_fusion,
+            enable_incremental_window_functions: features.enable_incremental_window_functions,
         }
     }
 
@@ -382,41 +386,49 @@ impl Context {
                 )
             }
             MirRelationExpr::FlatMap { input, func, exprs } => {
-                let (input, keys) = self.lower_mir_expr(input)?;
-                // This stage can absorb arbitrary MFP instances.
-                let mfp = mfp.take();
-                let mut exprs = exprs.clone();
-                let input_key = if let Some((k, permutation, _)) = keys.arbitrary_arrangement() {
-                    // We don't permute the MFP here, because it runs _after_ the table function,
-                    // whose output is in a fixed order.
-                    //
-                    // We _do_, however, need to permute the `expr`s that provide input to the
-                    // `func`.
-                    for expr in &mut exprs {
-                        expr.permute_map(permutation);
-                    }
-
-                    Some(k.clone())
+                // Window functions with a dedicated rendering replace both the unnesting
+                // of the list and the reduction that produces it.
+                if let Some(lowered) = self.lower_window(input, func, exprs, &mut mfp)? {
+                    lowered
                 } else {
-                    None
-                };
-                // Return the plan, and no arrangements.
-                (
-                    Plan::FlatMap {
-                        input: Box::new(input),
-                        func: func.clone(),
-                        exprs: exprs.clone(),
-                        mfp_after: mfp,
-                        input_key,
-                        lir_id: self.allocate_lir_id(),
-                    },
-                    AvailableCollections::new_raw(),
-                )
+                    let (input, keys) = self.lower_mir_expr(input)?;
+                    // This stage can absorb arbitrary MFP instances.
+                    let mfp = mfp.take();
+                    let mut exprs = exprs.clone();
+                    let input_key = if let Some((k, permutation, _)) = keys.arbitrary_arrangement()
+                    {
+                        // We don't permute the MFP here, because it runs _after_ the table
+                        // function, whose output is in a fixed order.
+                        //
+                        // We _do_, however, need to permute the `expr`s that provide input to
+                        // the `func`.
+                        for expr in &mut exprs {
+                            expr.permute_map(permutation);
+                        }
+
+                        Some(k.clone())
+                    } else {
+                        None
+                    };
+                    // Return the plan, and no arrangements.
+                    (
+                        Plan::FlatMap {
+                            input: Box::new(input),
+                            func: func.clone(),
+                            exprs: exprs.clone(),
+                            mfp_after: mfp,
+                            input_key,
+                            lir_id: self.allocate_lir_id(),
+                        },
+                        AvailableCollections::new_raw(),
+                    )
+                }
             }
             MirRelationExpr::Join {
                 inputs,
                 equivalences,
                 implementation,
+                hints: _,
             } => {
                 let input_mapper = JoinInputMapper::new(inputs);
 
@@ -870,6 +882,108 @@ This is not expected to cause incorrect results, but could indicate a performanc
         Ok((plan, keys))
     }
 
+    /// Attempts to lower a `FlatMap` that unnests the list produced by a window aggregate to a
+    /// [`Plan::Window`], absorbing `mfp` into it.
+    ///
+    /// Returns `None` if there is no dedicated rendering for the window aggregate, if the list
+    /// is referenced by anything but the `FlatMap`, or if the dedicated rendering is disabled.
+    fn lower_window<T: Timestamp>(
+        &mut self,
+        input: &MirRelationExpr,
+        func: &TableFunc,
+        exprs: &[MirScalarExpr],
+        mfp: &mut MapFilterProject,
+    ) -> Result<Option<(Plan<T>, AvailableCollections)>, String> {
+        if !self.enable_incremental_window_functions {
+            return Ok(None);
+        }
+        let (TableFunc::UnnestList { el_typ }, [MirScalarExpr::Column(list_col)]) = (func, exprs)
+        else {
+            return Ok(None);
+        };
+        // The input must be a projection of a reduction computing a single window aggregate.
+        let (project, reduce) = MapFilterProject::extract_from_expression(input);
+        if !project.expressions.is_empty() || !project.predicates.is_empty() {
+            return Ok(None);
+        }
+        let MirRelationExpr::Reduce {
+            input,
+            group_key,
+            aggregates,
+            monotonic: _,
+            expected_group_size: _,
+        } = reduce
+        else {
+            return Ok(None);
+        };
+        let [aggregate] = &aggregates[..] else {
+            return Ok(None);
+        };
+        // The unnested list must be the output of the aggregate, and the only use of it.
+        let key_arity = group_key.len();
+        if project.projection.get(*list_col) != Some(&key_arity)
+            || mfp
+                .demand()
+                .iter()
+                .any(|c| project.projection.get(*c) == Some(&key_arity))
+        {
+            return Ok(None);
+        }
+        let Some(window_plan) = WindowPlan::create_from(aggregate, &input.typ().column_types)
+        else {
+            return Ok(None);
+        };
+
+        let input_arity = input.arity();
+        let (input, keys) = self.lower_mir_expr(input)?;
+        let (input_key, permutation_and_new_arity) =
+            if let Some((input_key, permutation, thinning)) = keys.arbitrary_arrangement() {
+                (
+                    Some(input_key.clone()),
+                    Some((permutation.clone(), thinning.len() + input_key.len())),
+                )
+            } else {
+                (None, None)
+            };
+        let key_val_plan = KeyValPlan::new(
+            input_arity,
+            group_key,
+            aggregates,
+            permutation_and_new_arity,
+        );
+
+        // The window rendering produces the key followed by a list element. From these, rebuild
+        // the input of `mfp`, with a null standing in for the list, which `mfp` does not demand.
+        let list_typ = ScalarType::List {
+            element_type: Box::new(el_typ.clone()),
+            custom_id: None,
+        };
+        let rebuild = MapFilterProject::new(key_arity + 1)
+            .map([MirScalarExpr::literal_null(list_typ)])
+            .project(
+                project
+                    .projection
+                    .iter()
+                    .map(|c| if *c == key_arity { key_arity + 1 } else { *c })
+                    .chain([key_arity]),
+            );
+        let mut mfp_after = MapFilterProject::compose(rebuild, mfp.take());
+        mfp_after.optimize();
+
+        // Return the plan, and no arrangements.
+        Ok(Some((
+            Plan::Window {
+                input: Box::new(input),
+                key_val_plan,
+                window_plan,
+                input_key,
+                mfp_after,
+                lir_id: self.allocate_lir_id(),
+            },
+            AvailableCollections::new_raw(),
+        )))
+    }
+
     /// Replace the plan with another one
     /// that has the collection in some additional forms.
     pub fn arrange_by<T>(
@@ -38,6 +38,7 @@ use crate::plan::reduce::{KeyValPlan, ReducePlan};
 use crate::plan::threshold::ThresholdPlan;
 use crate::plan::top_k::TopKPlan;
 use crate::plan::transform::{Transform, TransformConfig};
+use crate::plan::window::WindowPlan;
 
 mod lowering;
 
@@ -48,6 +49,7 @@ pub mod reduce;
 pub mod threshold;
 pub mod top_k;
 pub mod transform;
+pub mod window;
 
 include!(concat!(env!("OUT_DIR"), "/mz_compute_types.plan.rs"));
 
@@ -342,6 +344,27 @@ pub enum Plan<T = mz_repr::Timestamp> {
         /// A dataflow-local identifier.
         lir_id: LirId,
     },
+    /// Window function evaluation by key, maintained incrementally.
+    ///
+    /// This stage replaces a `FlatMap` that unnests the list produced by a `Reduce` computing a
+    /// window aggregate. Its output records are the key of the reduction followed by an element
+    /// of the list, before `mfp_after` is applied.
+    Window {
+        /// The input collection.
+        input: Box<Plan<T>>,
+        /// A plan for changing input records into key, value pairs, where the value is
+        /// the input of the window aggregate.
+        key_val_plan: KeyValPlan,
+        /// A plan for computing the window function.
+        window_plan: WindowPlan,
+        /// The particular arrangement of the input we expect to use,
+        /// if any
+        input_key: Option<Vec<MirScalarExpr>>,
+        /// Linear operator to apply to each output record.
+        mfp_after: MapFilterProject,
+        /// A dataflow-local identifier.
+        lir_id: LirId,
+    },
     /// Key-based "Top K" operator, retaining the first K records in each group.
     TopK {
         /// The input collection.
@@ -437,6 +460,7 @@ impl<T> Plan<T> {
             Mfp { input, .. }
             | FlatMap { input, .. }
             | Reduce { input, .. }
+            | Window { input, .. }
             | TopK { input, .. }
             | Negate { input, .. }
             | Threshold { input, .. }
@@ -476,6 +500,7 @@ impl<T> Plan<T> {
             Mfp { input, .. }
             | FlatMap { input, .. }
             | Reduce { input, .. }
+            | Window { input, .. }
             | TopK { input, .. }
             | Negate { input, .. }
             | Threshold { input, .. }
@@ -508,6 +533,7 @@ impl<T> Plan<T> {
             | FlatMap { lir_id, .. }
             | Join { lir_id, .. }
             | Reduce { lir_id, .. }
+            | Window { lir_id, .. }
             | TopK { lir_id, .. }
             | Negate { lir_id, .. }
             | Threshold { lir_id, .. }
@@ -650,6 +676,28 @@ impl Arbitrary for Plan {
                         },
                     )
                     .boxed(),
+                //Plan::Window
+                (
+                    inner.clone(),
+                    any::<KeyValPlan>(),
+                    any::<WindowPlan>(),
+                    any::<Option<Vec<MirScalarExpr>>>(),
+                    any::<MapFilterProject>(),
+                    any::<LirId>(),
+                )
+                    .prop_map(
+                        |(input, key_val_plan, window_plan, input_key, mfp_after, lir_id)| {
+                            Plan::Window {
+                                input: input.into(),
+                                key_val_plan,
+                                window_plan,
+                                input_key,
+                                mfp_after,
+                                lir_id,
+                            }
+                        },
+                    )
+                    .boxed(),
                 //Plan::TopK
                 (inner.clone(), any::<TopKPlan>(), any::<LirId>())
                     .prop_map(|(input, top_k_plan, lir_id)| Plan::TopK {
@@ -1103,6 +1151,14 @@ impl<T> CollectionPlan for Plan<T> {
                 mfp_after: _,
                 lir_id: _,
             }
+            | Plan::Window {
+                input,
+                key_val_plan: _,
+                window_plan: _,
+                input_key: _,
+                mfp_after: _,
+                lir_id: _,
+            }
             | Plan::TopK {
                 input,
                 top_k_plan: _,
@@ -983,12 +983,21 @@ pub fn reduction_type(func: &AggregateFunc) -> ReductionType {
         | AggregateFunc::MinDate
         | AggregateFunc::MinTimestamp
         | AggregateFunc::MinTimestampTz => ReductionType::Hierarchical,
+        // Ordered-set aggregates cannot be combined from partial results, so they are basic
+        // reductions. Rendering maintains them in trees over the values of each group, rather
+        // than evaluating them over the whole group on every update.
+        AggregateFunc::PercentileCont { .. }
+        | AggregateFunc::PercentileDisc { .. }
+        | AggregateFunc::Mode { .. } => ReductionType::Basic,
         AggregateFunc::JsonbAgg { .. }
         | AggregateFunc::JsonbObjectAgg { .. }
         | AggregateFunc::MapAgg { .. }
         | AggregateFunc::ArrayConcat { .. }
         | AggregateFunc::ListConcat { .. }
         | AggregateFunc::StringAgg { .. }
+        | AggregateFunc::ApproxCountDistinct { .. }
+        | AggregateFunc::HllSketch { .. }
+        | AggregateFunc::HllUnion
         | AggregateFunc::RowNumber { .. }
         | AggregateFunc::Rank { .. }
         | AggregateFunc::DenseRank { .. }
@@ -0,0 +1,479 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Window function planning logic.
+//!
+//! Window functions are lowered to MIR as a `Reduce` that gathers each window partition into a
+//! list with a window aggregate (for example [`AggregateFunc::LagLead`]), followed by a `FlatMap`
+//! that unnests the list back into rows. Rendered as a basic reduction, such a pair re-sorts and
+//! re-emits the whole partition whenever any of its rows changes.
+//!
+//! We build a plan ([WindowPlan]) for the pairs that have a dedicated rendering, which maintains
+//! the window function output incrementally instead. All variants order a partition by a single
+//! ORDER BY column whose values embed into 64 bits, and assign each row a position derived from its
+//! ORDER BY value (see [WindowPlan::position]). They maintain a radix tree over the occupied
+//! positions of each partition, where an update touches one path from a leaf to the root, and the
+//! output changes of the rows that the update affects. We provide the following variants:
+//! * The [LagLeadPlan] computes `lag` and `lead` with an offset of one. Each node of the tree
+//!     summarizes its smallest and largest occupied positions, from which we derive the neighbour
+//!     of each position.
+//! * The [RowNumberPlan] computes `row_number`. Each node of the tree counts the rows below it,
+//!     and we push the number of rows before each node down the tree.
+//! * The [FirstValuePlan] computes `first_value` over frames that start at the beginning of the
+//!     partition and contain the current row, for which the result is the value of the first row
+//!     of the partition. We read it off the root of the tree of the [LagLeadPlan].
+//! * The [SumPlan] computes running sums of integers, whose frame spans from the beginning of the
+//!     partition to the current row. Like the [RowNumberPlan], we push the sum of the rows before
+//!     each node down the tree.
+//!
+//! Other window functions, frames with offsets, multiple ORDER BY columns, `IGNORE NULLS`, and
+//! sums of floats and numerics (whose sums depend on the order of evaluation) are rendered as a
+//! reduction.
+
+use mz_expr::{
+    AggregateExpr, AggregateFunc, ColumnOrder, LagLeadType, MirScalarExpr, VariadicFunc,
+    WindowFrame, WindowFrameBound, WindowFrameUnits,
+};
+use mz_ore::cast::ReinterpretCast;
+use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
+use mz_repr::{ColumnType, Datum, ScalarType};
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+include!(concat!(env!("OUT_DIR"), "/mz_compute_types.plan.window.rs"));
+
+/// A plan describing how to compute a window function.
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
+pub enum WindowPlan {
+    /// `lag` or `lead` with an offset of one.
+    LagLead(LagLeadPlan),
+    /// `row_number`.
+    RowNumber(RowNumberPlan),
+    /// `first_value` over a frame that starts at the beginning of the partition.
+    FirstValue(FirstValuePlan),
+    /// A running sum of integers.
+    Sum(SumPlan),
+}
+
+impl RustType<ProtoWindowPlan> for WindowPlan {
+    fn into_proto(&self) -> ProtoWindowPlan {
+        use proto_window_plan::Kind::*;
+        ProtoWindowPlan {
+            kind: Some(match self {
+                WindowPlan::LagLead(plan) => LagLead(plan.into_proto()),
+                WindowPlan::RowNumber(plan) => RowNumber(plan.into_proto()),
+                WindowPlan::FirstValue(plan) => FirstValue(plan.into_proto()),
+                WindowPlan::Sum(plan) => Sum(plan.into_proto()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoWindowPlan) -> Result<Self, TryFromProtoError> {
+        use proto_window_plan::Kind::*;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoWindowPlan::kind"))?;
+        Ok(match kind {
+            LagLead(plan) => WindowPlan::LagLead(plan.into_rust()?),
+            RowNumber(plan) => WindowPlan::RowNumber(plan.into_rust()?),
+            FirstValue(plan) => WindowPlan::FirstValue(plan.into_rust()?),
+            Sum(plan) => WindowPlan::Sum(plan.into_rust()?),
+        })
+    }
+}
+
+impl WindowPlan {
+    /// Attempts to plan the window aggregate `aggregate` of a `Reduce` whose input has the
+    /// column types `input_types`.
+    ///
+    /// Returns `None` if there is no dedicated rendering for the aggregate, in which case it
+    /// must be rendered as a reduction followed by a `FlatMap`.
+    pub fn create_from(aggregate: &AggregateExpr, input_types: &[ColumnType]) -> Option<Self> {
+        if aggregate.distinct {
+            return None;
+        }
+        // The input of the aggregate is `row(<inner>, <order by>...)`, where the contents of
+        // `<inner>` depend on the window function.
+        let [inner, order_exprs @ ..] = record_fields(&aggregate.expr)? else {
+            return None;
+        };
+        let plan = match &aggregate.func {
+            AggregateFunc::LagLead {
+                order_by,
+                lag_lead,
+                ignore_nulls,
+            } => {
+                if *ignore_nulls {
+                    return None;
+                }
+                // `<inner>` is `row(<original row>, row(<value>, <offset>, <default>))`.
+                let [_original_row, args] = record_fields(inner)? else {
+                    return None;
+                };
+                let [_value, offset, _default] = record_fields(args)? else {
+                    return None;
+                };
+                if !matches!(offset.as_literal(), Some(Ok(Datum::Int32(1)))) {
+                    return None;
+                }
+                WindowPlan::LagLead(LagLeadPlan {
+                    lag_lead: lag_lead.clone(),
+                    order_by: single_order_by(order_by)?,
+                })
+            }
+            AggregateFunc::RowNumber { order_by } => WindowPlan::RowNumber(RowNumberPlan {
+                order_by: single_order_by(order_by)?,
+            }),
+            AggregateFunc::FirstValue {
+                order_by,
+                window_frame,
+            } => {
+                // Frames that start at the beginning of the partition and reach the current row
+                // all start with the first row of the partition.
+                if !matches!(
+                    window_frame.start_bound,
+                    WindowFrameBound::UnboundedPreceding
+                ) || !matches!(
+                    window_frame.end_bound,
+                    WindowFrameBound::CurrentRow
+                        | WindowFrameBound::OffsetFollowing(_)
+                        | WindowFrameBound::UnboundedFollowing
+                ) {
+                    return None;
+                }
+                WindowPlan::FirstValue(FirstValuePlan {
+                    order_by: single_order_by(order_by)?,
+                })
+            }
+            AggregateFunc::WindowAggregate {
+                wrapped_aggregate,
+                order_by,
+                window_frame,
+            } => {
+                if !SumPlan::supports_func(wrapped_aggregate) {
+                    return None;
+                }
+                let WindowFrame {
+                    units,
+                    start_bound: WindowFrameBound::UnboundedPreceding,
+                    end_bound: WindowFrameBound::CurrentRow,
+                } = window_frame
+                else {
+                    return None;
+                };
+                let peers = match units {
+                    WindowFrameUnits::Rows => false,
+                    WindowFrameUnits::Range => true,
+                    WindowFrameUnits::Groups => return None,
+                };
+                WindowPlan::Sum(SumPlan {
+                    func: (**wrapped_aggregate).clone(),
+                    order_by: single_order_by(order_by)?,
+                    peers,
+                })
+            }
+            _ => return None,
+        };
+        let order_type = order_exprs
+            .get(plan.order_by().column)?
+            .typ(input_types)
+            .scalar_type;
+        Self::supports_order_type(&order_type).then_some(plan)
+    }
+
+    /// The ordering of the window, where `column` indexes the ORDER BY values.
+    pub fn order_by(&self) -> &ColumnOrder {
+        match self {
+            WindowPlan::LagLead(plan) => &plan.order_by,
+            WindowPlan::RowNumber(plan) => &plan.order_by,
+            WindowPlan::FirstValue(plan) => &plan.order_by,
+            WindowPlan::Sum(plan) => &plan.order_by,
+        }
+    }
+
+    /// The number of position bits that each level of the radix tree consumes.
+    ///
+    /// Each node groups up to `2^RADIX_BITS` children, which bounds the work to update a node. A
+    /// smaller radix would make the tree taller, and with it the number of arrangements that each
+    /// update passes through.
+    pub const RADIX_BITS: u32 = 4;
+    /// The number of levels of the radix tree above its leaves.
+    ///
+    /// Positions have 65 significant bits, which the levels must cover.
+    pub const LEVELS: u32 = 17;
+
+    /// Reports whether ORDER BY values of type `typ` can be mapped to positions.
+    pub fn supports_order_type(typ: &ScalarType) -> bool {
+        matches!(
+            typ,
+            ScalarType::Int16
+                | ScalarType::Int32
+                | ScalarType::Int64
+                | ScalarType::UInt16
+                | ScalarType::UInt32
+                | ScalarType::UInt64
+                | ScalarType::Date
+                | ScalarType::Timestamp { .. }
+                | ScalarType::TimestampTz { .. }
+                | ScalarType::MzTimestamp
+        )
+    }
+
+    /// Maps an ORDER BY value to its position in the window ordering.
+    ///
+    /// Rows with smaller positions come first, and rows with equal positions are peers. Non-null
+    /// values occupy the positions `0..2^64` if nulls come last and `1..=2^64` otherwise, which
+    /// leaves the remaining position for nulls.
+    ///
+    /// Panics if `datum` is not of a type accepted by [WindowPlan::supports_order_type].
+    pub fn position(&self, datum: Datum) -> u128 {
+        let order_by = self.order_by();
+        let Some(value) = order_preserving_u64(datum) else {
+            return if order_by.nulls_last { 1 << 64 } else { 0 };
+        };
+        let value = if order_by.desc {
+            u64::MAX - value
+        } else {
+            value
+        };
+        if order_by.nulls_last {
+            u128::from(value)
+        } else {
+            u128::from(value) + 1
+        }
+    }
+}
+
+/// Returns the only ORDER BY column, if there is exactly one.
+fn single_order_by(order_by: &[ColumnOrder]) -> Option<ColumnOrder> {
+    match order_by {
+        [order_by] => Some(order_by.clone()),
+        _ => None,
+    }
+}
+
+/// Returns the fields of `expr` if it constructs a record.
+fn record_fields(expr: &MirScalarExpr) -> Option<&[MirScalarExpr]> {
+    match expr {
+        MirScalarExpr::CallVariadic {
+            func: VariadicFunc::RecordCreate { .. },
+            exprs,
+        } => Some(exprs),
+        _ => None,
+    }
+}
+
+/// A plan for `lag` and `lead` with an offset of one, and without `IGNORE NULLS`.
+///
+/// Rows at the same position are peers, which are ordered by the rest of their contents. The
+/// neighbouring positions of each occupied position are found with a radix tree over positions,
+/// whose nodes summarize their smallest and largest occupied positions.
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
+pub struct LagLeadPlan {
+    /// Whether to compute `lag` or `lead`.
+    pub lag_lead: LagLeadType,
+    /// The ordering of the window, where `order_by.column` indexes the ORDER BY values.
+    pub order_by: ColumnOrder,
+}
+
+impl RustType<ProtoLagLeadPlan> for LagLeadPlan {
+    fn into_proto(&self) -> ProtoLagLeadPlan {
+        ProtoLagLeadPlan {
+            lead: matches!(self.lag_lead, LagLeadType::Lead),
+            order_by: Some(self.order_by.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoLagLeadPlan) -> Result<Self, TryFromProtoError> {
+        Ok(LagLeadPlan {
+            lag_lead: if proto.lead {
+                LagLeadType::Lead
+            } else {
+                LagLeadType::Lag
+            },
+            order_by: proto
+                .order_by
+                .into_rust_if_some("ProtoLagLeadPlan::order_by")?,
+        })
+    }
+}
+
+/// A plan for `row_number`.
+///
+/// Each node of the radix tree counts the rows below it. The row number of a row is one more than
+/// the number of rows at smaller positions, plus the number of its peers that precede it.
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
+pub struct RowNumberPlan {
+    /// The ordering of the window, where `order_by.column` indexes the ORDER BY values.
+    pub order_by: ColumnOrder,
+}
+
+impl RustType<ProtoRowNumberPlan> for RowNumberPlan {
+    fn into_proto(&self) -> ProtoRowNumberPlan {
+        ProtoRowNumberPlan {
+            order_by: Some(self.order_by.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoRowNumberPlan) -> Result<Self, TryFromProtoError> {
+        Ok(RowNumberPlan {
+            order_by: proto
+                .order_by
+                .into_rust_if_some("ProtoRowNumberPlan::order_by")?,
+        })
+    }
+}
+
+/// A plan for `first_value` over a frame that starts at the beginning of the partition and
+/// contains the current row.
+///
+/// The result of every row is the value of the first row of the partition, which the root of the
+/// radix tree of the [LagLeadPlan] summarizes.
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
+pub struct FirstValuePlan {
+    /// The ordering of the window, where `order_by.column` indexes the ORDER BY values.
+    pub order_by: ColumnOrder,
+}
+
+impl RustType<ProtoFirstValuePlan> for FirstValuePlan {
+    fn into_proto(&self) -> ProtoFirstValuePlan {
+        ProtoFirstValuePlan {
+            order_by: Some(self.order_by.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoFirstValuePlan) -> Result<Self, TryFromProtoError> {
+        Ok(FirstValuePlan {
+            order_by: proto
+                .order_by
+                .into_rust_if_some("ProtoFirstValuePlan::order_by")?,
+        })
+    }
+}
+
+/// A plan for a sum of integers over the frame `UNBOUNDED PRECEDING AND CURRENT ROW`.
+///
+/// Each node of the radix tree sums the values below it. The result of a row is the sum of the
+/// rows at smaller positions, plus the sum of its peers that are in the frame.
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Ord, PartialOrd)]
+pub struct SumPlan {
+    /// The sum to compute, which is one of the sums accepted by [SumPlan::supports_func].
+    pub func: AggregateFunc,
+    /// The ordering of the window, where `order_by.column` indexes the ORDER BY values.
+    pub order_by: ColumnOrder,
+    /// Whether the frame of a row contains all of its peers (`RANGE`), or only the peers up to
+    /// the row itself (`ROWS`).
+    pub peers: bool,
+}
+
+impl RustType<ProtoSumPlan> for SumPlan {
+    fn into_proto(&self) -> ProtoSumPlan {
+        ProtoSumPlan {
+            func: Some(self.func.into_proto()),
+            order_by: Some(self.order_by.into_proto()),
+            peers: self.peers,
+        }
+    }
+
+    fn from_proto(proto: ProtoSumPlan) -> Result<Self, TryFromProtoError> {
+        Ok(SumPlan {
+            func: proto.func.into_rust_if_some("ProtoSumPlan::func")?,
+            order_by: proto.order_by.into_rust_if_some("ProtoSumPlan::order_by")?,
+            peers: proto.peers,
+        })
+    }
+}
+
+impl SumPlan {
+    /// Reports whether `func` is a sum that we can maintain in the radix tree.
+    ///
+    /// Sums of integers are exact, and so do not depend on the order in which we add them up.
+    pub fn supports_func(func: &AggregateFunc) -> bool {
+        matches!(
+            func,
+            AggregateFunc::SumInt16
+                | AggregateFunc::SumInt32
+                | AggregateFunc::SumInt64
+                | AggregateFunc::SumUInt16
+                | AggregateFunc::SumUInt32
+                | AggregateFunc::SumUInt64
+        )
+    }
+}
+
+/// Maps a non-null datum to a `u64` such that the order of the datums is preserved.
+fn order_preserving_u64(datum: Datum) -> Option<u64> {
+    // Flipping the sign bit maps signed integers onto unsigned integers in order.
+    let signed = |i: i64| u64::reinterpret_cast(i) ^ (1 << 63);
+    match datum {
+        Datum::Null => None,
+        Datum::Int16(i) => Some(signed(i.into())),
+        Datum::Int32(i) => Some(signed(i.into())),
+        Datum::Int64(i) => Some(signed(i)),
+        Datum::UInt16(u) => Some(u.into()),
+        Datum::UInt32(u) => Some(u.into()),
+        Datum::UInt64(u) => Some(u),
+        Datum::Date(d) => Some(signed(d.unix_epoch_days().into())),
+        // Timestamps have at most microsecond precision.
+        Datum::Timestamp(t) => Some(signed(t.timestamp_micros())),
+        Datum::TimestampTz(t) => Some(signed(t.timestamp_micros())),
+        Datum::MzTimestamp(t) => Some(t.into()),
+        datum => panic!("unsupported ORDER BY value for window plan: {datum:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_proto::protobuf_roundtrip;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    proptest! {
+       #[mz_ore::test]
+        fn window_plan_protobuf_roundtrip(expect in any::<WindowPlan>() ) {
+            let actual = protobuf_roundtrip::<_, ProtoWindowPlan>(&expect);
+            assert!(actual.is_ok());
+            assert_eq!(actual.unwrap(), expect);
+        }
+    }
+
+    #[mz_ore::test]
+    fn window_positions() {
+        let plan = |desc, nulls_last| {
+            WindowPlan::RowNumber(RowNumberPlan {
+                order_by: ColumnOrder {
+                    column: 0,
+                    desc,
+                    nulls_last,
+                },
+            })
+        };
+        let values = [
+            Datum::Int64(i64::MIN),
+            Datum::Int64(-1),
+            Datum::Int64(0),
+            Datum::Int64(1),
+            Datum::Int64(i64::MAX),
+        ];
+
+        let asc = plan(false, true);
+        for pair in values.windows(2) {
+            assert!(asc.position(pair[0]) < asc.position(pair[1]));
+        }
+        assert!(asc.position(Datum::Int64(i64::MAX)) < asc.position(Datum::Null));
+
+        let desc = plan(true, false);
+        for pair in values.windows(2) {
+            assert!(desc.position(pair[0]) > desc.position(pair[1]));
+        }
+        assert!(desc.position(Datum::Null) < desc.position(Datum::Int64(i64::MAX)));
+        assert!(desc.position(Datum::Int64(i64::MIN)) < 1 << 65);
+    }
+}
@@ -0,0 +1,314 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Spilling of cold arrangement batches to local disk.
+//!
+//! Arrangement batches produced by merging large batches are the oldest and largest batches of a
+//! trace, and are read only by further merges and by lookups. On replicas with spilling enabled,
+//! the row data of such batches is written in chunks to memory-mapped, unlinked files in the
+//! replica's scratch directory. The kernel writes the data back to disk in the background, pages
+//! it in on demand when merges and lookups read it, and can evict it again under memory pressure
+//! without needing swap space.
+//!
+//! Creating and mapping the files involves blocking system calls, so each worker has a spill
+//! thread that prepares chunks ahead of time. Workers never wait for the spill thread: if no
+//! chunk is ready, the merge continues in memory.
+
+use std::cell::{Cell, RefCell};
+use std::path::{Path, PathBuf};
+
+use crossbeam_channel::{Receiver, TryRecvError};
+use memmap2::MmapMut;
+use mz_ore::region::Region;
+use tracing::warn;
+
+/// The minimum size in bytes of the row data of a merged batch for it to be spilled.
+const SPILL_MIN_BYTES: usize = 64 << 20;
+
+/// The size in bytes of the chunks in which row data is spilled.
+const SPILL_CHUNK_BYTES: usize = 8 << 20;
+
+/// The number of chunks a spill thread prepares ahead of time.
+const PREPARED_CHUNKS: usize = 2;
+
+/// A worker's handle to its spill thread.
+#[derive(Clone, Debug)]
+pub struct Spiller {
+    /// The minimum size in bytes of the row data of a merged batch for it to be spilled.
+    pub min_bytes: usize,
+    /// The size in bytes of the chunks in which row data is spilled.
+    pub chunk_bytes: usize,
+    /// Chunks prepared by the spill thread.
+    chunks: Receiver<MmapMut>,
+}
+
+impl Spiller {
+    /// Starts a spill thread that creates spill files in `path`, using the default sizes.
+    pub fn start(name: String, path: PathBuf) -> std::io::Result<Self> {
+        Self::start_with_sizes(name, path, SPILL_MIN_BYTES, SPILL_CHUNK_BYTES)
+    }
+
+    /// Starts a spill thread that creates spill files of `chunk_bytes` bytes in `path`.
+    ///
+    /// The thread exits once all clones of the returned handle have been dropped.
+    pub fn start_with_sizes(
+        name: String,
+        path: PathBuf,
+        min_bytes: usize,
+        chunk_bytes: usize,
+    ) -> std::io::Result<Self> {
+        let (tx, rx) = crossbeam_channel::bounded(PREPARED_CHUNKS);
+        std::thread::Builder::new().name(name).spawn(move || loop {
+            match map_spill_file(&path, chunk_bytes) {
+                Ok(chunk) => {
+                    if tx.send(chunk).is_err() {
+                        break;
+                    }
+                }
+                // Once a spill file cannot be created or its disk space cannot be reserved, the
+                // thread exits and workers keep all further batches in memory.
+                Err(err) => {
+                    warn!(?path, "failed to create arrangement spill file: {err}");
+                    break;
+                }
+            }
+        })?;
+        Ok(Self {
+            min_bytes,
+            chunk_bytes,
+            chunks: rx,
+        })
+    }
+
+    /// Returns the number of chunks the spill thread has ready.
+    #[cfg(test)]
+    pub fn ready_chunks(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Returns a spill region with a capacity of `chunk_bytes`, if the spill thread has one ready.
+    pub fn try_chunk(&self) -> Option<SpillRegion> {
+        match self.chunks.try_recv() {
+            Ok(mmap) => Some(SpillRegion::Spilled { mmap, len: 0 }),
+            Err(TryRecvError::Empty | TryRecvError::Disconnected) => None,
+        }
+    }
+}
+
+thread_local! {
+    /// The spiller of the current worker, or `None` if spilling is disabled.
+    ///
+    /// Batch containers are created by differential dataflow without access to worker state, so
+    /// the spiller is installed per worker thread instead.
+    static SPILLER: RefCell<Option<Spiller>> = RefCell::new(None);
+    /// Bytes of arrangement row data the current worker holds in memory.
+    static RESIDENT_BYTES: Cell<usize> = Cell::new(0);
+    /// Bytes of arrangement row data the current worker has spilled to disk.
+    static SPILLED_BYTES: Cell<usize> = Cell::new(0);
+}
+
+/// Installs the spiller of the current worker, or disables spilling if `spiller` is `None`.
+///
+/// Batches that already have been spilled remain on disk.
+pub fn set_spiller(spiller: Option<Spiller>) {
+    SPILLER.with(|s| *s.borrow_mut() = spiller);
+}
+
+/// Returns the spiller of the current worker, or `None` if spilling is disabled.
+pub fn spiller() -> Option<Spiller> {
+    SPILLER.with(|s| s.borrow().clone())
+}
+
+/// The amount of arrangement row data a worker holds in memory and on disk.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SpillStats {
+    /// Bytes held in memory.
+    pub resident_bytes: usize,
+    /// Bytes spilled to disk, whether or not they are currently paged in.
+    pub spilled_bytes: usize,
+}
+
+/// Returns the amount of arrangement row data the current worker holds in memory and on disk.
+pub fn spill_stats() -> SpillStats {
+    SpillStats {
+        resident_bytes: RESIDENT_BYTES.with(Cell::get),
+        spilled_bytes: SPILLED_BYTES.with(Cell::get),
+    }
+}
+
+fn add_bytes(counter: &'static std::thread::LocalKey<Cell<usize>>, bytes: usize) {
+    counter.with(|c| c.set(c.get().saturating_add(bytes)));
+}
+
+fn sub_bytes(counter: &'static std::thread::LocalKey<Cell<usize>>, bytes: usize) {
+    counter.with(|c| c.set(c.get().saturating_sub(bytes)));
+}
+
+/// Byte storage for arrangement row data that is either resident in memory or spilled to disk.
+pub enum SpillRegion {
+    /// Data held in memory.
+    Resident(Region<u8>),
+    /// Data held in an unlinked file on disk that is mapped into memory.
+    Spilled {
+        /// The mapping of the whole file.
+        mmap: MmapMut,
+        /// The number of bytes written to the file.
+        len: usize,
+    },
+}
+
+impl SpillRegion {
+    /// Creates a resident region that can hold at least `capacity` bytes.
+    pub fn new_resident(capacity: usize) -> Self {
+        let region = Region::new_auto(capacity);
+        add_bytes(&RESIDENT_BYTES, region.capacity());
+        SpillRegion::Resident(region)
+    }
+
+    /// The number of bytes in the region.
+    pub fn len(&self) -> usize {
+        match self {
+            SpillRegion::Resident(region) => region.len(),
+            SpillRegion::Spilled { len, .. } => *len,
+        }
+    }
+
+    /// The number of bytes the region can hold.
+    pub fn capacity(&self) -> usize {
+        match self {
+            SpillRegion::Resident(region) => region.capacity(),
+            SpillRegion::Spilled { mmap, .. } => mmap.len(),
+        }
+    }
+
+    /// Returns true if the region has been spilled to disk.
+    pub fn is_spilled(&self) -> bool {
+        matches!(self, SpillRegion::Spilled { .. })
+    }
+
+    /// Appends `slice` to the region.
+    ///
+    /// Panics if the region does not have sufficient capacity.
+    pub fn extend_from_slice(&mut self, slice: &[u8]) {
+        match self {
+            SpillRegion::Resident(region) => region.extend_from_slice(slice),
+            SpillRegion::Spilled { mmap, len } => {
+                mmap[*len..*len + slice.len()].copy_from_slice(slice);
+                *len += slice.len();
+                add_bytes(&SPILLED_BYTES, slice.len());
+            }
+        }
+    }
+
+    /// Visit the in-memory allocation to determine its size and capacity.
+    ///
+    /// Spilled regions don't report any allocation, as their memory is managed by the kernel.
+    #[inline]
+    pub fn heap_size(&self, mut callback: impl FnMut(usize, usize)) {
+        if let SpillRegion::Resident(region) = self {
+            callback(region.len(), region.capacity());
+        }
+    }
+}
+
+impl std::ops::Deref for SpillRegion {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        match self {
+            SpillRegion::Resident(region) => &region[..],
+            SpillRegion::Spilled { mmap, len } => &mmap[..*len],
+        }
+    }
+}
+
+impl Drop for SpillRegion {
+    fn drop(&mut self) {
+        match self {
+            SpillRegion::Resident(region) => sub_bytes(&RESIDENT_BYTES, region.capacity()),
+            SpillRegion::Spilled { len, .. } => sub_bytes(&SPILLED_BYTES, *len),
+        }
+    }
+}
+
+/// Creates an unlinked file of `len` bytes in `path` and maps it into memory.
+///
+/// The disk space of the file is allocated up front, so that writing to the mapping cannot fail
+/// with `SIGBUS` when the disk fills up. The space is released once the mapping is dropped.
+fn map_spill_file(path: &Path, len: usize) -> std::io::Result<MmapMut> {
+    let file = tempfile::tempfile_in(path)?;
+    allocate(&file, len)?;
+    // SAFETY: The file is unlinked and only reachable through `file`, so nothing else can modify
+    // or truncate it while it is mapped.
+    unsafe { MmapMut::map_mut(&file) }
+}
+
+/// Extends `file` to `len` bytes and allocates disk space for all of them.
+#[cfg(target_os = "linux")]
+fn allocate(file: &std::fs::File, len: usize) -> std::io::Result<()> {
+    use std::os::fd::AsRawFd;
+
+    let len = libc::off_t::try_from(len).expect("spill chunk size fits in off_t");
+    // SAFETY: `file` is an open file descriptor for the duration of the call.
+    match unsafe { libc::posix_fallocate(file.as_raw_fd(), 0, len) } {
+        0 => Ok(()),
+        errno => Err(std::io::Error::from_raw_os_error(errno)),
+    }
+}
+
+/// Extends `file` to `len` bytes and allocates disk space for all of them.
+///
+/// Without `posix_fallocate`, the file is filled with zeroes instead.
+#[cfg(not(target_os = "linux"))]
+fn allocate(file: &std::fs::File, len: usize) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let zeroes = vec![0; std::cmp::min(len, 1 << 20)];
+    let mut writer = file;
+    let mut remaining = len;
+    while remaining > 0 {
+        let n = std::cmp::min(remaining, zeroes.len());
+        writer.write_all(&zeroes[..n])?;
+        remaining -= n;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `mmap`
+    fn spill_region() {
+        let dir = tempfile::tempdir().unwrap();
+        let spiller = Spiller::start_with_sizes("spill".into(), dir.path().into(), 0, 16).unwrap();
+        let before = spill_stats();
+
+        let mut region = loop {
+            if let Some(region) = spiller.try_chunk() {
+                break region;
+            }
+            std::thread::yield_now();
+        };
+        assert!(region.is_spilled());
+        assert_eq!(region.capacity(), 16);
+
+        region.extend_from_slice(b"hello");
+        region.extend_from_slice(b"world");
+        assert_eq!(&*region, b"helloworld");
+        assert_eq!(region.len(), 10);
+        assert_eq!(spill_stats().spilled_bytes, before.spilled_bytes + 10);
+        assert_eq!(spill_stats().resident_bytes, before.resident_bytes);
+
+        drop(region);
+        assert_eq!(spill_stats().spilled_bytes, before.spilled_bytes);
+    }
+}
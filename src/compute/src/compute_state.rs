@@ -57,6 +57,7 @@ use tracing::{debug, error, info, span, warn, Level};
 use uuid::Uuid;
 
 use crate::arrangement::manager::{SpecializedTraceHandle, TraceBundle, TraceManager};
+use crate::arrangement::spill::{set_spiller, Spiller};
 use crate::logging;
 use crate::logging::compute::ComputeEvent;
 use crate::metrics::ComputeMetrics;
@@ -290,6 +291,10 @@ impl<'a, A: Allocate + 'static> ActiveComputeState<'a, A> {
         self.compute_state.apply_worker_config();
 
         self.initialize_logging(&config.logging);
+
+        if config.arrangement_spill {
+            self.initialize_arrangement_spill();
+        }
     }
 
     fn handle_update_configuration(&mut self, params: ComputeParameters) {
@@ -451,6 +456,23 @@ impl<'a, A: Allocate + 'static> ActiveComputeState<'a, A> {
         }
     }
 
+    /// Starts the spill thread of this worker, which prepares the files that cold arrangement
+    /// batches are spilled to.
+    fn initialize_arrangement_spill(&mut self) {
+        let Some(path) = &self.compute_state.context.scratch_directory else {
+            debug!("not enabling arrangement spilling, scratch directory not specified");
+            return;
+        };
+        let name = format!("spill-{}", self.timely_worker.index());
+        match Spiller::start(name, path.clone()) {
+            Ok(spiller) => {
+                info!(?path, "enabling arrangement spilling");
+                set_spiller(Some(spiller));
+            }
+            Err(err) => warn!(?path, "failed to start arrangement spill thread: {err}"),
+        }
+    }
+
     /// Initializes timely dataflow logging and publishes as a view.
     pub fn initialize_logging(&mut self, config: &LoggingConfig) {
         if self.compute_state.compute_logger.is_some() {
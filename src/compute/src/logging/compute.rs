@@ -20,6 +20,7 @@ use differential_dataflow::collection::AsCollection;
 use differential_dataflow::operators::arrange::Arranged;
 use differential_dataflow::trace::{BatchReader, Cursor, TraceReader};
 use differential_dataflow::Collection;
+use mz_compute_types::plan::LirId;
 use mz_ore::cast::CastFrom;
 use mz_repr::{Datum, Diff, GlobalId, Timestamp};
 use mz_timely_util::replay::MzReplay;
@@ -128,6 +129,45 @@ pub enum ComputeEvent {
         /// The change in error count.
         diff: i64,
     },
+    /// The LIR nodes of a dataflow export have been rendered.
+    LirMapping {
+        /// Identifier of the export.
+        export_id: GlobalId,
+        /// The rendered LIR nodes, with their metadata.
+        mappings: Vec<(LirId, LirMetadata)>,
+    },
+}
+
+/// Metadata about a rendered LIR node.
+#[derive(Debug, Clone, PartialOrd, PartialEq)]
+pub struct LirMetadata {
+    /// A short, human-readable description of the LIR operator.
+    operator: String,
+    /// The LIR node consuming the output of this node, if any.
+    parent_lir_id: Option<LirId>,
+    /// The depth of this node in the LIR plan tree.
+    nesting: u8,
+    /// The range of dataflow operator IDs created when rendering this node.
+    ///
+    /// The start is inclusive, the end exclusive.
+    operator_span: (usize, usize),
+}
+
+impl LirMetadata {
+    /// Create new LIR metadata from its arguments.
+    pub fn new(
+        operator: String,
+        parent_lir_id: Option<LirId>,
+        nesting: u8,
+        operator_span: (usize, usize),
+    ) -> Self {
+        Self {
+            operator,
+            parent_lir_id,
+            nesting,
+            operator_span,
+        }
+    }
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
@@ -211,6 +251,7 @@ pub(super) fn construct<A: Allocate + 'static>(
         let (mut arrangement_heap_allocations_out, arrangement_heap_allocations) =
             demux.new_output();
         let (mut error_count_out, error_count) = demux.new_output();
+        let (mut lir_mapping_out, lir_mapping) = demux.new_output();
 
         let mut demux_state = DemuxState::new(worker2);
         let mut demux_buffer = Vec::new();
@@ -227,6 +268,7 @@ pub(super) fn construct<A: Allocate + 'static>(
                 let mut arrangement_heap_capacity = arrangement_heap_capacity_out.activate();
                 let mut arrangement_heap_allocations = arrangement_heap_allocations_out.activate();
                 let mut error_count = error_count_out.activate();
+                let mut lir_mapping = lir_mapping_out.activate();
 
                 input.for_each(|cap, data| {
                     data.swap(&mut demux_buffer);
@@ -243,6 +285,7 @@ pub(super) fn construct<A: Allocate + 'static>(
                         arrangement_heap_capacity: arrangement_heap_capacity.session(&cap),
                         arrangement_heap_allocations: arrangement_heap_allocations.session(&cap),
                         error_count: error_count.session(&cap),
+                        lir_mapping: lir_mapping.session(&cap),
                     };
 
                     for (time, logger_id, event) in demux_buffer.drain(..) {
@@ -380,6 +423,25 @@ pub(super) fn construct<A: Allocate + 'static>(
             }
         });
 
+        let mut packer = PermutedRowPacker::new(ComputeLog::LirMapping);
+        let lir_mapping = lir_mapping.as_collection().map({
+            let mut scratch = String::new();
+            move |datum| {
+                let metadata = datum.metadata;
+                let (start, end) = metadata.operator_span;
+                packer.pack_slice(&[
+                    make_string_datum(datum.export_id, &mut scratch),
+                    Datum::UInt64(datum.lir_id),
+                    Datum::UInt64(u64::cast_from(worker_id)),
+                    Datum::String(&metadata.operator),
+                    metadata.parent_lir_id.map_or(Datum::Null, Datum::UInt64),
+                    Datum::UInt16(u16::from(metadata.nesting)),
+                    Datum::UInt64(u64::cast_from(start)),
+                    Datum::UInt64(u64::cast_from(end)),
+                ])
+            }
+        });
+
         use ComputeLog::*;
         let logs = [
             (DataflowCurrent, dataflow_current),
@@ -393,6 +455,7 @@ pub(super) fn construct<A: Allocate + 'static>(
             (ArrangementHeapCapacity, arrangement_heap_capacity),
             (ArrangementHeapAllocations, arrangement_heap_allocations),
             (ErrorCount, error_count),
+            (LirMapping, lir_mapping),
         ];
 
         // Build the output arrangements.
@@ -470,6 +533,8 @@ struct ExportState {
     /// This must be a signed integer, since per-worker error counts can be negative, only the
     /// cross-worker total has to sum up to a non-negative value.
     error_count: i64,
+    /// The LIR nodes rendered for this export.
+    lir_mappings: Vec<(LirId, LirMetadata)>,
 }
 
 impl ExportState {
@@ -479,6 +544,7 @@ impl ExportState {
             reported_frontier: MutableAntichain::new(),
             imports: Default::default(),
             error_count: 0,
+            lir_mappings: Vec::new(),
         }
     }
 }
@@ -519,6 +585,7 @@ struct DemuxOutput<'a> {
     arrangement_heap_capacity: OutputSession<'a, ArrangementHeapDatum>,
     arrangement_heap_allocations: OutputSession<'a, ArrangementHeapDatum>,
     error_count: OutputSession<'a, ErrorCountDatum>,
+    lir_mapping: OutputSession<'a, LirMappingDatum>,
 }
 
 #[derive(Clone)]
@@ -573,6 +640,13 @@ struct ErrorCountDatum {
     count: i64,
 }
 
+#[derive(Clone)]
+struct LirMappingDatum {
+    export_id: GlobalId,
+    lir_id: LirId,
+    metadata: LirMetadata,
+}
+
 /// Event handler of the demux operator.
 struct DemuxHandler<'a, 'b, A: Allocate + 'static> {
     /// State kept by the demux operator.
@@ -641,6 +715,10 @@ impl<A: Allocate> DemuxHandler<'_, '_, A> {
             }
             DataflowShutdown { dataflow_index } => self.handle_dataflow_shutdown(dataflow_index),
             ErrorCount { export_id, diff } => self.handle_error_count(export_id, diff),
+            LirMapping {
+                export_id,
+                mappings,
+            } => self.handle_lir_mapping(export_id, mappings),
         }
     }
 
@@ -704,6 +782,16 @@ impl<A: Allocate> DemuxHandler<'_, '_, A> {
             };
             self.output.error_count.give((datum, ts, -1));
         }
+
+        // Remove LIR mapping logging for this export.
+        for (lir_id, metadata) in export.lir_mappings {
+            let datum = LirMappingDatum {
+                export_id: id,
+                lir_id,
+                metadata,
+            };
+            self.output.lir_mapping.give((datum, ts, -1));
+        }
     }
 
     fn handle_dataflow_dropped(&mut self, id: usize) {
@@ -768,6 +856,29 @@ impl<A: Allocate> DemuxHandler<'_, '_, A> {
         export.error_count = new_count;
     }
 
+    fn handle_lir_mapping(&mut self, export_id: GlobalId, mappings: Vec<(LirId, LirMetadata)>) {
+        let ts = self.ts();
+
+        let Some(export) = self.state.exports.get_mut(&export_id) else {
+            error!(
+                export = ?export_id,
+                "missing exports entry at time of LIR mapping",
+            );
+            return;
+        };
+
+        for (lir_id, metadata) in &mappings {
+            let datum = LirMappingDatum {
+                export_id,
+                lir_id: *lir_id,
+                metadata: metadata.clone(),
+            };
+            self.output.lir_mapping.give((datum, ts, 1));
+        }
+
+        export.lir_mappings.extend(mappings);
+    }
+
     fn handle_peek_install(&mut self, peek: Peek, peek_type: PeekType) {
         let uuid = peek.uuid;
         let ts = self.ts();
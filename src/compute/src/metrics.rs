@@ -15,6 +15,8 @@ use mz_repr::SharedRow;
 use prometheus::core::{AtomicF64, GenericCounter};
 use prometheus::Histogram;
 
+use crate::arrangement::spill::spill_stats;
+
 /// Metrics exposed by compute replicas.
 //
 // Most of the metrics here use the `raw` implementations, rather than the `DeleteOnDrop` wrappers
@@ -37,6 +39,8 @@ pub struct ComputeMetrics {
     // arrangements
     arrangement_maintenance_seconds_total: raw::CounterVec,
     arrangement_maintenance_active_info: raw::UIntGaugeVec,
+    arrangement_resident_bytes: raw::UIntGaugeVec,
+    arrangement_spilled_bytes: raw::UIntGaugeVec,
 
     // timely step timings
     //
@@ -89,6 +93,16 @@ impl ComputeMetrics {
                 help: "Whether maintenance is currently occuring.",
                 var_labels: ["worker_id"],
             )),
+            arrangement_resident_bytes: registry.register(metric!(
+                name: "mz_arrangement_resident_bytes",
+                help: "The number of bytes of arrangement row data held in memory.",
+                var_labels: ["worker_id"],
+            )),
+            arrangement_spilled_bytes: registry.register(metric!(
+                name: "mz_arrangement_spilled_bytes",
+                help: "The number of bytes of arrangement row data spilled to disk.",
+                var_labels: ["worker_id"],
+            )),
             timely_step_duration_seconds: registry.register(metric!(
                 name: "mz_timely_step_duration_seconds",
                 help: "The time spent in each compute step_or_park call",
@@ -178,6 +192,19 @@ impl ComputeMetrics {
             .with_label_values(&[&worker])
             .set(u64::cast_from(binding.borrow().byte_capacity()));
     }
+
+    /// Record the amount of arrangement row data held in memory and spilled to disk.
+    pub fn record_arrangement_spill_metrics(&self, worker_id: usize) {
+        let worker = worker_id.to_string();
+
+        let stats = spill_stats();
+        self.arrangement_resident_bytes
+            .with_label_values(&[&worker])
+            .set(u64::cast_from(stats.resident_bytes));
+        self.arrangement_spilled_bytes
+            .with_label_values(&[&worker])
+            .set(u64::cast_from(stats.spilled_bytes));
+    }
 }
 
 /// Metrics maintained by the trace manager.
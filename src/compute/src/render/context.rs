@@ -86,6 +86,12 @@ where
     ///
     /// `None` if no hydration events should be logged in this context.
     pub(super) hydration_logger: Option<HydrationLogger>,
+    /// A logger for compute events, used to report the operators rendered for LIR nodes.
+    ///
+    /// `None` if no LIR mappings should be logged in this context.
+    pub(super) compute_logger: Option<crate::logging::compute::Logger>,
+    /// The IDs of the exports of the dataflow associated with this context.
+    pub(super) export_ids: Vec<GlobalId>,
     /// Specification for rendering linear joins.
     pub(super) linear_join_spec: LinearJoinSpec,
     /// Per-worker dynamic configuration.
@@ -121,6 +127,13 @@ where
             })
         };
 
+        // Skip LIR mapping logging for transient dataflows, for the same reason as above.
+        let compute_logger = if dataflow.is_transient() {
+            None
+        } else {
+            compute_state.compute_logger.clone()
+        };
+
         Self {
             scope,
             debug_name: dataflow.debug_name.clone(),
@@ -130,6 +143,8 @@ where
             bindings: BTreeMap::new(),
             shutdown_token: Default::default(),
             hydration_logger,
+            compute_logger,
+            export_ids: dataflow.export_ids().collect(),
             linear_join_spec: compute_state.linear_join_spec,
             worker_config: compute_state.worker_config.clone(),
         }
@@ -131,14 +131,14 @@ use timely::dataflow::{Scope, Stream};
 use timely::order::Product;
 use timely::progress::timestamp::Refines;
 use timely::progress::{Antichain, Timestamp};
-use timely::worker::Worker as TimelyWorker;
+use timely::worker::{AsWorker, Worker as TimelyWorker};
 use timely::PartialOrder;
 
 use crate::arrangement::manager::TraceBundle;
 use crate::compute_state::ComputeState;
 use crate::extensions::arrange::{ArrangementSize, KeyCollection, MzArrange};
 use crate::extensions::reduce::MzReduce;
-use crate::logging::compute::{LogDataflowErrors, LogImportFrontiers};
+use crate::logging::compute::{ComputeEvent, LirMetadata, LogDataflowErrors, LogImportFrontiers};
 use crate::render::context::{
     ArrangementFlavor, Context, ShutdownToken, SpecializedArrangement, SpecializedArrangementImport,
 };
@@ -152,6 +152,7 @@ mod reduce;
 pub mod sinks;
 mod threshold;
 mod top_k;
+mod window;
 
 pub use context::CollectionBundle;
 pub use join::LinearJoinSpec;
@@ -746,9 +747,37 @@ where
         // Rendered collections by their `LirId`.
         let mut collections = BTreeMap::new();
 
+        // Parents and nesting depths of nodes, only computed if LIR mappings are logged.
+        let mut parents = BTreeMap::new();
+        let mut nesting = BTreeMap::new();
+        let mut lir_mappings = Vec::new();
+        if self.compute_logger.is_some() {
+            for id in topological_order.iter().rev() {
+                let depth = nesting.get(id).copied().unwrap_or(0u8);
+                for input in nodes[id].input_lir_ids() {
+                    parents.entry(input).or_insert(*id);
+                    nesting.entry(input).or_insert(depth.saturating_add(1));
+                }
+            }
+        }
+
         for id in topological_order {
             let node = nodes.remove(&id).unwrap();
+            let operator = self.compute_logger.is_some().then(|| node.humanize());
+
+            let operator_id_start = self.scope.peek_identifier();
             let mut bundle = self.render_plan_node(node, &collections);
+            let operator_id_end = self.scope.peek_identifier();
+
+            if let Some(operator) = operator {
+                let metadata = LirMetadata::new(
+                    operator,
+                    parents.get(&id).copied(),
+                    nesting.get(&id).copied().unwrap_or(0),
+                    (operator_id_start, operator_id_end),
+                );
+                lir_mappings.push((id, metadata));
+            }
 
             if ENABLE_OPERATOR_HYDRATION_STATUS_LOGGING.get(&self.worker_config) {
                 self.log_operator_hydration(&mut bundle, id);
@@ -757,6 +786,15 @@ where
             collections.insert(id, bundle);
         }
 
+        if let Some(logger) = &self.compute_logger {
+            for export_id in &self.export_ids {
+                logger.log(ComputeEvent::LirMapping {
+                    export_id: *export_id,
+                    mappings: lir_mappings.clone(),
+                });
+            }
+        }
+
         collections
             .remove(&root_id)
             .expect("FlatPlan invariant (1)")
@@ -911,6 +949,16 @@ where
                 let mfp_option = (!mfp_after.is_identity()).then_some(mfp_after);
                 self.render_reduce(input, key_val_plan, plan, input_key, mfp_option)
             }
+            Window {
+                input,
+                key_val_plan,
+                window_plan,
+                input_key,
+                mfp_after,
+            } => {
+                let input = expect_input(input);
+                self.render_window(input, key_val_plan, window_plan, input_key, mfp_after)
+            }
             TopK { input, top_k_plan } => {
                 let input = expect_input(input);
                 self.render_topk(input, top_k_plan)
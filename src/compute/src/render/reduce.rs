@@ -53,6 +53,8 @@ use crate::typedefs::{
     KeyBatcher, RowErrSpine, RowRowArrangement, RowRowSpine, RowSpine, RowValSpine,
 };
 
+mod ordered_set;
+
 impl<G, T> Context<G, T>
 where
     G: Scope,
@@ -770,6 +772,24 @@ where
             }
         }
 
+        // Ordered-set aggregates maintain trees over the values of each group, rather than
+        // evaluating the aggregate over the whole group on every update.
+        if func.is_ordered_set() {
+            let must_validate = validating && err_output.is_none();
+            let (oks, errs) = self.build_ordered_set_aggregate(
+                partial,
+                &func,
+                must_validate,
+                key_arity,
+                mfp_after,
+            );
+            let err_output = match (err_output, errs) {
+                (Some(distinct_errs), Some(errs)) => Some(distinct_errs.concat(&errs)),
+                (distinct_errs, errs) => distinct_errs.or(errs),
+            };
+            return (oks, err_output);
+        }
+
         // Allocations for the two closures.
         let mut datums1 = DatumVec::new();
         let mut datums2 = DatumVec::new();
@@ -782,12 +802,13 @@ where
             move |key, source, target| {
                 // We respect the multiplicity here (unlike in hierarchical aggregation)
                 // because we don't know that the aggregation method is not sensitive
-                // to the number of records.
-                let iter = source.iter().flat_map(|(v, w)| {
+                // to the number of records. HyperLogLog aggregates consume the
+                // multiplicities directly, rather than seeing repeated values.
+                let iter = source.iter().map(|(v, w)| {
                     // Note that in the non-positive case, this is wrong, but harmless because
                     // our other reduction will produce an error.
                     let count = usize::try_from(*w).unwrap_or(0);
-                    std::iter::repeat(v.into_datum_iter(None).next().unwrap()).take(count)
+                    (v.into_datum_iter(None).next().unwrap(), count)
                 });
 
                 let temp_storage = RowArena::new();
@@ -796,9 +817,9 @@ where
                 datums_local.extend(datum_iter);
                 let key_len = datums_local.len();
                 datums_local.push(
-                    // Note that this is not necessarily a window aggregation, in which case
-                    // `eval_fast_window_agg` delegates to the normal `eval`.
-                    func.eval_fast_window_agg::<_, window_agg_helpers::OneByOneAggrImpls>(
+                    // Note that this is not necessarily a window or HyperLogLog aggregation,
+                    // in which case `eval_with_multiplicities` delegates to the normal `eval`.
+                    func.eval_with_multiplicities::<_, window_agg_helpers::OneByOneAggrImpls>(
                         iter,
                         &temp_storage,
                     ),
@@ -842,11 +863,11 @@ where
 
                         // We know that `mfp_after` can error if it exists, so try to evaluate it here.
                         let Some(mfp) = &mfp_after2 else { return };
-                        let iter = source.iter().flat_map(|(mut v, w)| {
+                        let iter = source.iter().map(|(mut v, w)| {
                             let count = usize::try_from(*w).unwrap_or(0);
                             // This would ideally use `into_datum_iter` but we cannot as it needs to
                             // borrow `v` and only presents datums with that lifetime, not any longer.
-                            std::iter::repeat(v.next().unwrap()).take(count)
+                            (v.next().unwrap(), count)
                         });
 
                         let temp_storage = RowArena::new();
@@ -854,10 +875,11 @@ where
                         let mut datums_local = datums2.borrow();
                         datums_local.extend(datum_iter);
                         datums_local.push(
-                            func2.eval_fast_window_agg::<_, window_agg_helpers::OneByOneAggrImpls>(
-                                iter,
-                                &temp_storage,
-                            ),
+                            func2
+                                .eval_with_multiplicities::<_, window_agg_helpers::OneByOneAggrImpls>(
+                                    iter,
+                                    &temp_storage,
+                                ),
                         );
                         if let Result::Err(e) = mfp.evaluate_inner(&mut datums_local, &temp_storage)
                         {
@@ -2370,6 +2392,12 @@ mod monoids {
             | AggregateFunc::ArrayConcat { .. }
             | AggregateFunc::ListConcat { .. }
             | AggregateFunc::StringAgg { .. }
+            | AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::Mode { .. }
+            | AggregateFunc::ApproxCountDistinct { .. }
+            | AggregateFunc::HllSketch { .. }
+            | AggregateFunc::HllUnion
             | AggregateFunc::RowNumber { .. }
             | AggregateFunc::Rank { .. }
             | AggregateFunc::DenseRank { .. }
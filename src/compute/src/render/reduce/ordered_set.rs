@@ -0,0 +1,879 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Incremental rendering of ordered-set aggregates.
+//!
+//! Evaluating `percentile_cont`, `percentile_disc`, or `mode` over all values of a group takes
+//! time linear in the size of the group, which would be spent again on every update to the group.
+//! Instead, we maintain the values in trees whose nodes each have a bounded number of children, so
+//! that an update only revisits the nodes on a path from a leaf to the root.
+//!
+//! Percentiles use an order-statistic tree: values are placed at a 64-bit position that preserves
+//! their order, and each node of a radix tree over the positions counts the values below each of
+//! its children. The ranks a percentile needs are then found by descending from the root, one
+//! level per reduction. Values that share a position are sorted at the leaf.
+//!
+//! The mode uses a hierarchical reduction, like `MIN` and `MAX` do: the number of occurrences of
+//! each value is counted, and each level of a radix tree over the hashes of the values retains the
+//! most frequent value of its children.
+
+use std::collections::BTreeMap;
+
+use differential_dataflow::hashable::Hashable;
+use differential_dataflow::lattice::Lattice;
+use differential_dataflow::Collection;
+use mz_expr::{interpolate_percentile, percentile_ranks, AggregateFunc, EvalError, SafeMfpPlan};
+use mz_ore::cast::{CastFrom, ReinterpretCast};
+use mz_repr::adt::numeric::Numeric;
+use mz_repr::fixed_length::IntoRowByTypes;
+use mz_repr::{Datum, DatumVec, Diff, Row, RowArena, SharedRow};
+use mz_storage_types::errors::DataflowError;
+use timely::container::columnation::Columnation;
+use timely::dataflow::Scope;
+use timely::progress::timestamp::Refines;
+use timely::progress::Timestamp;
+
+use crate::extensions::arrange::MzArrange;
+use crate::extensions::reduce::MzReduce;
+use crate::render::context::Context;
+use crate::render::reduce::evaluate_mfp_after;
+use crate::row_spine::DatumSeq;
+use crate::typedefs::{RowErrSpine, RowRowArrangement, RowRowSpine};
+
+/// The number of bits of a position or hash that each level of a tree consumes.
+const RADIX_BITS: u32 = 4;
+/// The number of children of each node of a tree.
+const RADIX: usize = 1 << RADIX_BITS;
+/// The number of levels of the order-statistic tree over 64-bit positions.
+const PERCENTILE_LEVELS: u32 = u64::BITS / RADIX_BITS;
+/// The number of levels of the hierarchical reduction over the high 32 bits of hashes.
+const MODE_LEVELS: u32 = u32::BITS / RADIX_BITS;
+
+impl<G, T> Context<G, T>
+where
+    G: Scope,
+    G::Timestamp: Lattice + Refines<T> + Columnation,
+    T: Timestamp + Lattice + Columnation,
+{
+    /// Renders an ordered-set aggregate over `input`, which holds the group key and the single
+    /// aggregated datum of each input row, and applies `mfp_after` to the result.
+    ///
+    /// Reports non-positive accumulations if `validating`, and errors of `mfp_after`.
+    pub(crate) fn build_ordered_set_aggregate<S>(
+        &self,
+        input: Collection<S, (Row, Row), Diff>,
+        func: &AggregateFunc,
+        validating: bool,
+        key_arity: usize,
+        mfp_after: Option<SafeMfpPlan>,
+    ) -> (
+        RowRowArrangement<S>,
+        Option<Collection<S, DataflowError, Diff>>,
+    )
+    where
+        S: Scope<Timestamp = G::Timestamp>,
+    {
+        // Each group reports the fraction of its percentiles, or null for the mode, and whether
+        // it has non-null values, along with the number of its input rows. This establishes the
+        // groups that produce output.
+        let mode = matches!(func, AggregateFunc::Mode { .. });
+        let headers = input.map(move |(key, val)| {
+            let binding = SharedRow::get();
+            let mut row_builder = binding.borrow_mut();
+            let (value, fraction) = match val.unpack_first() {
+                value if mode => (value, Datum::Null),
+                Datum::List(fields) => {
+                    let mut fields = fields.iter();
+                    (fields.next().unwrap(), fields.next().unwrap())
+                }
+                _ => (Datum::Null, Datum::Null),
+            };
+            row_builder
+                .packer()
+                .extend([Datum::False, fraction, Datum::from(!value.is_null())]);
+            (key, row_builder.clone())
+        });
+
+        let (results, leaf_errs) = match func {
+            AggregateFunc::PercentileCont { desc, multiple } => {
+                self.render_percentile(input, headers.clone(), true, *desc, *multiple, key_arity)
+            }
+            AggregateFunc::PercentileDisc { desc, multiple } => {
+                self.render_percentile(input, headers.clone(), false, *desc, *multiple, key_arity)
+            }
+            AggregateFunc::Mode { desc } => self.render_mode(input, *desc, key_arity),
+            _ => unreachable!("not an ordered-set aggregate: {func:?}"),
+        };
+
+        // Assemble the output of each group from its header and the results of the trees.
+        let mut datums1 = DatumVec::new();
+        let mut datums2 = DatumVec::new();
+        let mfp_after1 = mfp_after.clone();
+        let mfp_after2 = mfp_after.filter(|mfp| mfp.could_error());
+        let func1 = func.clone();
+        let func2 = func.clone();
+        let arranged = headers
+            .concat(&results)
+            .mz_arrange::<RowRowSpine<_, _>>("Arranged OrderedSet output");
+        let oks = arranged.mz_reduce_abelian::<_, RowRowSpine<_, _>>(
+            "OrderedSet output",
+            move |key, source, target| {
+                let temp_storage = RowArena::new();
+                let Some(result) = ordered_set_output(&func1, source, &temp_storage) else {
+                    return;
+                };
+                let mut datums_local = datums1.borrow();
+                datums_local.extend(key.into_datum_iter(None));
+                let key_len = datums_local.len();
+                datums_local.push(result);
+                if let Some(row) =
+                    evaluate_mfp_after(&mfp_after1, &mut datums_local, &temp_storage, key_len)
+                {
+                    target.push((row, 1));
+                }
+            },
+        );
+
+        let mut err_output = leaf_errs.filter(|_| validating);
+        if validating || mfp_after2.is_some() {
+            let error_logger = self.error_logger();
+            let errs = arranged
+                .mz_reduce_abelian::<_, RowErrSpine<_, _>>(
+                    "OrderedSet Error Check",
+                    move |key, source, target| {
+                        // Negative counts would be surprising, but until we are 100% certain we
+                        // won't see them, we should report when we do. The headers account for
+                        // every input row, including those whose value is null.
+                        if validating {
+                            for (value, count) in source.iter() {
+                                if *count > 0 {
+                                    continue;
+                                }
+                                let value = Row::pack(value.into_datum_iter(None));
+                                let message = "Non-positive accumulation in OrderedSet";
+                                error_logger
+                                    .log(message, &format!("value={value:?}, count={count}"));
+                                target.push((EvalError::Internal(message.to_string()).into(), 1));
+                                return;
+                            }
+                        }
+
+                        let Some(mfp) = &mfp_after2 else { return };
+                        let temp_storage = RowArena::new();
+                        let Some(result) = ordered_set_output(&func2, source, &temp_storage) else {
+                            return;
+                        };
+                        let mut datums_local = datums2.borrow();
+                        datums_local.extend(key.into_datum_iter(None));
+                        datums_local.push(result);
+                        if let Err(e) = mfp.evaluate_inner(&mut datums_local, &temp_storage) {
+                            target.push((e.into(), 1));
+                        }
+                    },
+                )
+                .as_collection(|_, v| v.into_owned());
+            err_output = Some(match err_output {
+                Some(leaf_errs) => leaf_errs.concat(&errs),
+                None => errs,
+            });
+        }
+        (oks, err_output)
+    }
+
+    /// Renders the order-statistic tree of `percentile_cont` (if `cont`) or `percentile_disc`.
+    ///
+    /// Produces, keyed by the group key, the value at each rank a percentile needs, and the
+    /// errors of validating the leaves.
+    fn render_percentile<S>(
+        &self,
+        input: Collection<S, (Row, Row), Diff>,
+        headers: Collection<S, (Row, Row), Diff>,
+        cont: bool,
+        desc: bool,
+        multiple: bool,
+        key_arity: usize,
+    ) -> (
+        Collection<S, (Row, Row), Diff>,
+        Option<Collection<S, DataflowError, Diff>>,
+    )
+    where
+        S: Scope<Timestamp = G::Timestamp>,
+    {
+        // Place each non-null value at its position, keyed by the group key and the position.
+        let leaves = input.flat_map(|(key, val)| {
+            let Datum::List(fields) = val.unpack_first() else {
+                return None;
+            };
+            let value = fields.iter().next().unwrap();
+            if value.is_null() {
+                return None;
+            }
+            let binding = SharedRow::get();
+            let mut row_builder = binding.borrow_mut();
+            let leaf = pack_node(&mut row_builder, key.iter(), order_key(value));
+            row_builder.packer().extend([Datum::False, value]);
+            Some((leaf, row_builder.clone()))
+        });
+
+        // The number of values below each child of a node at `level` are the multiplicities of
+        // the children, keyed by the group key and the node.
+        let counts = |level: u32| {
+            leaves.map(move |(leaf, _)| {
+                let binding = SharedRow::get();
+                let mut row_builder = binding.borrow_mut();
+                let position = leaf.iter().nth(key_arity).unwrap().unwrap_uint64();
+                let node = pack_node(
+                    &mut row_builder,
+                    leaf.iter().take(key_arity),
+                    node_at(position, level),
+                );
+                let child = node_at(position, level - 1) & (u64::cast_from(RADIX) - 1);
+                row_builder
+                    .packer()
+                    .extend([Datum::False, Datum::UInt64(child)]);
+                (node, row_builder.clone())
+            })
+        };
+
+        // The root is keyed by the group key alone. It determines the ranks of each fraction from
+        // the number of values, and routes them to its children.
+        let roots = counts(PERCENTILE_LEVELS).map(move |(node, count)| {
+            let binding = SharedRow::get();
+            let mut row_builder = binding.borrow_mut();
+            row_builder.packer().extend(node.iter().take(key_arity));
+            (row_builder.clone(), count)
+        });
+        let root_headers = headers.map(|(key, header)| {
+            let binding = SharedRow::get();
+            let mut row_builder = binding.borrow_mut();
+            row_builder
+                .packer()
+                .extend([Datum::True, header.iter().nth(1).unwrap()]);
+            (key, row_builder.clone())
+        });
+        let mut queries = roots
+            .concat(&root_headers)
+            .mz_arrange::<RowRowSpine<_, _>>("Arranged Percentile root")
+            .mz_reduce_abelian::<_, RowRowSpine<_, _>>(
+                "Percentile root",
+                move |_key, source, target| {
+                    let mut fraction = None;
+                    let mut children = [0; RADIX];
+                    for (value, count) in source.iter() {
+                        let mut datums = value.into_datum_iter(None);
+                        if datums.next() == Some(Datum::True) {
+                            if *count > 0 {
+                                fraction.get_or_insert(datums.next().unwrap());
+                            }
+                        } else {
+                            let child = usize::cast_from(datums.next().unwrap().unwrap_uint64());
+                            // Note that in the non-positive case, this is wrong, but harmless
+                            // because the error check will produce an error.
+                            children[child] += usize::try_from(*count).unwrap_or(0);
+                        }
+                    }
+                    let total: usize = children.iter().sum();
+                    let Some(fraction) = fraction.filter(|_| total > 0) else {
+                        return;
+                    };
+                    let fractions: Vec<_> = match (multiple, fraction) {
+                        (_, Datum::Null) => return,
+                        (false, fraction) => vec![fraction],
+                        (true, fractions) => fractions.unwrap_array().elements().iter().collect(),
+                    };
+
+                    let binding = SharedRow::get();
+                    let mut row_builder = binding.borrow_mut();
+                    for (index, fraction) in fractions.into_iter().enumerate() {
+                        // Null fractions produce null, and `CheckPercentileFraction` has already
+                        // rejected fractions out of range.
+                        let Datum::Float64(fraction) = fraction else {
+                            continue;
+                        };
+                        if !(0.0..=1.0).contains(&*fraction) {
+                            continue;
+                        }
+                        let (first, second, weight) = percentile_ranks(cont, total, *fraction);
+                        let ranks = if cont {
+                            vec![first, second]
+                        } else {
+                            vec![first]
+                        };
+                        for (slot, rank) in ranks.iter().enumerate() {
+                            // The tree ranks values in ascending order.
+                            let rank = if desc { total - 1 - rank } else { *rank };
+                            let query = Query {
+                                index: u64::cast_from(index),
+                                slot: u64::cast_from(slot),
+                                rank: u64::cast_from(rank),
+                                weight,
+                            };
+                            if let Some((child, query)) = query.route(&children) {
+                                let mut packer = row_builder.packer();
+                                packer.push(Datum::UInt64(child));
+                                query.push_into(&mut packer);
+                                target.push((row_builder.clone(), 1));
+                            }
+                        }
+                    }
+                },
+            )
+            .as_collection(move |key, routed| {
+                let binding = SharedRow::get();
+                let mut row_builder = binding.borrow_mut();
+                let mut routed = routed.into_datum_iter(None);
+                let child = routed.next().unwrap().unwrap_uint64();
+                let node = pack_node(&mut row_builder, key.into_datum_iter(None), child);
+                let mut packer = row_builder.packer();
+                packer.push(Datum::True);
+                packer.extend(routed);
+                (node, row_builder.clone())
+            });
+
+        // Descend the tree. Each level routes the queries at its nodes to the children that hold
+        // the ranks they ask for.
+        for level in (1..PERCENTILE_LEVELS).rev() {
+            queries = counts(level)
+                .concat(&queries)
+                .mz_arrange::<RowRowSpine<_, _>>(&format!("Arranged Percentile level {level}"))
+                .mz_reduce_abelian::<_, RowRowSpine<_, _>>(
+                    &format!("Percentile level {level}"),
+                    move |_key, source, target| {
+                        let mut children = [0; RADIX];
+                        let mut pending = Vec::new();
+                        for (value, count) in source.iter() {
+                            let mut datums = value.into_datum_iter(None);
+                            if datums.next() == Some(Datum::True) {
+                                pending.push(Query::unpack(datums));
+                            } else {
+                                let child =
+                                    usize::cast_from(datums.next().unwrap().unwrap_uint64());
+                                children[child] += usize::try_from(*count).unwrap_or(0);
+                            }
+                        }
+
+                        let binding = SharedRow::get();
+                        let mut row_builder = binding.borrow_mut();
+                        for query in pending {
+                            if let Some((child, query)) = query.route(&children) {
+                                let mut packer = row_builder.packer();
+                                packer.push(Datum::UInt64(child));
+                                query.push_into(&mut packer);
+                                target.push((row_builder.clone(), 1));
+                            }
+                        }
+                    },
+                )
+                .as_collection(move |key, routed| {
+                    let binding = SharedRow::get();
+                    let mut row_builder = binding.borrow_mut();
+                    let mut routed = routed.into_datum_iter(None);
+                    let child = routed.next().unwrap().unwrap_uint64();
+                    let mut key = key.into_datum_iter(None);
+                    let group = key.by_ref().take(key_arity).collect::<Vec<_>>();
+                    let parent = key.next().unwrap().unwrap_uint64();
+                    let node = pack_node(&mut row_builder, group, (parent << RADIX_BITS) | child);
+                    let mut packer = row_builder.packer();
+                    packer.push(Datum::True);
+                    packer.extend(routed);
+                    (node, row_builder.clone())
+                });
+        }
+
+        // At the leaves, the queries pick their ranks among the values at the position.
+        let arranged = leaves
+            .concat(&queries)
+            .mz_arrange::<RowRowSpine<_, _>>("Arranged Percentile leaves");
+
+        // Negative counts would be surprising, but until we are 100% certain we won't see them,
+        // we should report when we do.
+        let error_logger = self.error_logger();
+        let errs = arranged
+            .mz_reduce_abelian::<_, RowErrSpine<_, _>>(
+                "Percentile Error Check",
+                move |_key, source, target| {
+                    for (value, count) in source.iter() {
+                        if *count > 0 {
+                            continue;
+                        }
+                        let value = Row::pack(value.into_datum_iter(None));
+                        let message = "Non-positive accumulation in Percentile";
+                        error_logger.log(message, &format!("value={value:?}, count={count}"));
+                        target.push((EvalError::Internal(message.to_string()).into(), 1));
+                        return;
+                    }
+                },
+            )
+            .as_collection(|_, v| v.into_owned());
+
+        let results = arranged
+            .mz_reduce_abelian::<_, RowRowSpine<_, _>>(
+                "Percentile leaves",
+                move |_key, source, target| {
+                    let mut values = Vec::new();
+                    let mut queries = Vec::new();
+                    for (value, count) in source.iter() {
+                        let mut datums = value.into_datum_iter(None);
+                        if datums.next() == Some(Datum::True) {
+                            queries.push(Query::unpack(datums));
+                        } else {
+                            let count = u64::try_from(*count).unwrap_or(0);
+                            values.push((datums.next().unwrap(), count));
+                        }
+                    }
+                    // Values that share a position are ordered by the datums themselves.
+                    values.sort();
+
+                    let binding = SharedRow::get();
+                    let mut row_builder = binding.borrow_mut();
+                    for query in queries {
+                        let mut rank = query.rank;
+                        let value = values.iter().find_map(|(value, count)| {
+                            if rank < *count {
+                                Some(*value)
+                            } else {
+                                rank -= count;
+                                None
+                            }
+                        });
+                        if let Some(value) = value {
+                            row_builder.packer().extend([
+                                Datum::True,
+                                Datum::UInt64(query.index),
+                                Datum::UInt64(query.slot),
+                                value,
+                                Datum::Float64(query.weight.into()),
+                            ]);
+                            target.push((row_builder.clone(), 1));
+                        }
+                    }
+                },
+            )
+            .as_collection(move |key, result| {
+                let binding = SharedRow::get();
+                let mut row_builder = binding.borrow_mut();
+                row_builder
+                    .packer()
+                    .extend(key.into_datum_iter(None).take(key_arity));
+                (row_builder.clone(), result.into_owned())
+            });
+
+        (results, Some(errs))
+    }
+
+    /// Renders the hierarchical reduction of `mode`.
+    ///
+    /// Produces, keyed by the group key, the most frequent value, and the errors of validating
+    /// the counts of the values.
+    fn render_mode<S>(
+        &self,
+        input: Collection<S, (Row, Row), Diff>,
+        desc: bool,
+        key_arity: usize,
+    ) -> (
+        Collection<S, (Row, Row), Diff>,
+        Option<Collection<S, DataflowError, Diff>>,
+    )
+    where
+        S: Scope<Timestamp = G::Timestamp>,
+    {
+        // Count the occurrences of each non-null value.
+        let arranged = input
+            .flat_map(|(key, val)| {
+                let value = val.unpack_first();
+                if value.is_null() {
+                    return None;
+                }
+                let binding = SharedRow::get();
+                let mut row_builder = binding.borrow_mut();
+                let mut packer = row_builder.packer();
+                packer.extend(key.iter());
+                packer.push(value);
+                Some((row_builder.clone(), Row::default()))
+            })
+            .mz_arrange::<RowRowSpine<_, _>>("Arranged Mode values");
+
+        // Negative counts would be surprising, but until we are 100% certain we won't see them,
+        // we should report when we do.
+        let error_logger = self.error_logger();
+        let errs = arranged
+            .mz_reduce_abelian::<_, RowErrSpine<_, _>>(
+                "Mode Error Check",
+                move |key, source, target| {
+                    for (_, count) in source.iter() {
+                        if *count > 0 {
+                            continue;
+                        }
+                        let value = Row::pack(key.into_datum_iter(None));
+                        let message = "Non-positive accumulation in Mode";
+                        error_logger.log(message, &format!("value={value:?}, count={count}"));
+                        target.push((EvalError::Internal(message.to_string()).into(), 1));
+                        return;
+                    }
+                },
+            )
+            .as_collection(|_, v| v.into_owned());
+
+        // Each value is a candidate at the leaf its hash selects.
+        let mut candidates = arranged
+            .mz_reduce_abelian::<_, RowRowSpine<_, _>>("Mode count", |_key, source, target| {
+                let count: Diff = source.iter().map(|(_, count)| *count).sum();
+                if count > 0 {
+                    target.push((Row::pack_slice(&[Datum::Int64(count)]), 1));
+                }
+            })
+            .as_collection(move |key, count| {
+                let binding = SharedRow::get();
+                let mut row_builder = binding.borrow_mut();
+                let value = key.into_datum_iter(None).nth(key_arity).unwrap();
+                let hash = Row::pack_slice(&[value]).hashed() >> u32::BITS;
+                let group = key.into_datum_iter(None).take(key_arity);
+                let node = pack_node(&mut row_builder, group, hash);
+                row_builder
+                    .packer()
+                    .extend([count.into_datum_iter(None).next().unwrap(), value]);
+                (node, row_builder.clone())
+            });
+
+        // Climb the tree. Each level retains the most frequent value among its children, with
+        // ties broken by the order of the values.
+        for level in 1..=MODE_LEVELS {
+            candidates = candidates
+                .map(move |(node, candidate)| {
+                    let binding = SharedRow::get();
+                    let mut row_builder = binding.borrow_mut();
+                    let child = node.iter().nth(key_arity).unwrap().unwrap_uint64();
+                    let parent = pack_node(
+                        &mut row_builder,
+                        node.iter().take(key_arity),
+                        child >> RADIX_BITS,
+                    );
+                    (parent, candidate)
+                })
+                .mz_arrange::<RowRowSpine<_, _>>(&format!("Arranged Mode level {level}"))
+                .mz_reduce_abelian::<_, RowRowSpine<_, _>>(
+                    &format!("Mode level {level}"),
+                    move |_key, source, target| {
+                        let best = source
+                            .iter()
+                            .filter(|(_, count)| *count > 0)
+                            .map(|(candidate, _)| {
+                                let mut datums = candidate.into_datum_iter(None);
+                                (
+                                    datums.next().unwrap().unwrap_int64(),
+                                    datums.next().unwrap(),
+                                )
+                            })
+                            .reduce(|best, candidate| {
+                                let better = candidate.0 > best.0
+                                    || (candidate.0 == best.0
+                                        && if desc {
+                                            candidate.1 > best.1
+                                        } else {
+                                            candidate.1 < best.1
+                                        });
+                                if better {
+                                    candidate
+                                } else {
+                                    best
+                                }
+                            });
+                        if let Some((count, value)) = best {
+                            target.push((Row::pack_slice(&[Datum::Int64(count), value]), 1));
+                        }
+                    },
+                )
+                .as_collection(|k, v| (k.into_owned(), v.into_owned()));
+        }
+
+        let results = candidates.map(move |(node, candidate)| {
+            let binding = SharedRow::get();
+            let mut row_builder = binding.borrow_mut();
+            row_builder.packer().extend(node.iter().take(key_arity));
+            let key = row_builder.clone();
+            row_builder
+                .packer()
+                .extend([Datum::True, candidate.iter().nth(1).unwrap()]);
+            (key, row_builder.clone())
+        });
+
+        (results, Some(errs))
+    }
+}
+
+/// A request for the value at `rank` among the values below a node, on behalf of slot `slot` of
+/// the percentile of fraction `index`.
+struct Query {
+    index: u64,
+    slot: u64,
+    rank: u64,
+    weight: f64,
+}
+
+impl Query {
+    /// Determines the child that holds the rank, given the number of values below each child,
+    /// and the query relative to that child.
+    fn route(self, children: &[usize; RADIX]) -> Option<(u64, Self)> {
+        let mut rank = self.rank;
+        for (child, count) in children.iter().enumerate() {
+            let count = u64::cast_from(*count);
+            if rank < count {
+                return Some((u64::cast_from(child), Query { rank, ..self }));
+            }
+            rank -= count;
+        }
+        None
+    }
+
+    fn push_into(&self, packer: &mut mz_repr::RowPacker) {
+        packer.extend([
+            Datum::UInt64(self.index),
+            Datum::UInt64(self.slot),
+            Datum::UInt64(self.rank),
+            Datum::Float64(self.weight.into()),
+        ]);
+    }
+
+    fn unpack<'a, I>(datums: I) -> Self
+    where
+        I: IntoIterator<Item = Datum<'a>>,
+    {
+        let mut datums = datums.into_iter();
+        Query {
+            index: datums.next().unwrap().unwrap_uint64(),
+            slot: datums.next().unwrap().unwrap_uint64(),
+            rank: datums.next().unwrap().unwrap_uint64(),
+            weight: datums.next().unwrap().unwrap_float64(),
+        }
+    }
+}
+
+/// Computes the output of an ordered-set aggregate from the headers of the group, which are
+/// tagged with `Datum::False`, and the results of its tree, which are tagged with `Datum::True`.
+///
+/// Returns `None` if the group has no input rows.
+fn ordered_set_output<'a>(
+    func: &AggregateFunc,
+    source: &[(DatumSeq<'a>, Diff)],
+    temp_storage: &'a RowArena,
+) -> Option<Datum<'a>> {
+    let mut fraction = None;
+    let mut has_values = false;
+    let mut results = BTreeMap::new();
+    for (value, count) in source.iter() {
+        if *count <= 0 {
+            continue;
+        }
+        let mut datums = *value;
+        if datums.next() == Some(Datum::False) {
+            fraction.get_or_insert(datums.next().unwrap());
+            has_values |= datums.next() == Some(Datum::True);
+        } else if let AggregateFunc::Mode { .. } = func {
+            results.insert((0, 0), (datums.next().unwrap(), 0.0));
+        } else {
+            let index = datums.next().unwrap().unwrap_uint64();
+            let slot = datums.next().unwrap().unwrap_uint64();
+            let value = datums.next().unwrap();
+            let weight = datums.next().unwrap().unwrap_float64();
+            results.insert((index, slot), (value, weight));
+        }
+    }
+    let fraction = fraction?;
+
+    let percentile = |index: u64, cont: bool| {
+        let (first, _) = results.get(&(index, 0))?;
+        if !cont {
+            return Some(*first);
+        }
+        let (second, weight) = results.get(&(index, 1))?;
+        Some(Datum::from(interpolate_percentile(
+            first.unwrap_float64(),
+            second.unwrap_float64(),
+            *weight,
+        )))
+    };
+    let (cont, multiple) = match func {
+        AggregateFunc::PercentileCont { multiple, .. } => (true, *multiple),
+        AggregateFunc::PercentileDisc { multiple, .. } => (false, *multiple),
+        AggregateFunc::Mode { .. } => {
+            return Some(
+                results
+                    .get(&(0, 0))
+                    .map_or(Datum::Null, |(value, _)| *value),
+            )
+        }
+        _ => unreachable!("not an ordered-set aggregate: {func:?}"),
+    };
+    if fraction.is_null() || !has_values {
+        Some(Datum::Null)
+    } else if !multiple {
+        Some(percentile(0, cont).unwrap_or(Datum::Null))
+    } else {
+        let fractions = fraction.unwrap_array();
+        let dims: Vec<_> = fractions.dims().into_iter().collect();
+        let values: Vec<_> = (0..fractions.elements().iter().count())
+            .map(|index| percentile(u64::cast_from(index), cont).unwrap_or(Datum::Null))
+            .collect();
+        Some(temp_storage.make_datum(|packer| {
+            packer
+                .push_array(&dims, values)
+                .expect("results have the same shape as the fractions")
+        }))
+    }
+}
+
+/// Maps a non-null datum to a position such that the order of the datums is preserved, although
+/// distinct datums may share a position.
+///
+/// Types without an order-preserving mapping share the position zero, which degrades their
+/// percentiles to sorting the whole group at a single leaf.
+fn order_key(datum: Datum) -> u64 {
+    // Flipping the sign bit maps signed integers onto unsigned integers in order.
+    let signed = |i: i64| u64::reinterpret_cast(i) ^ (1 << 63);
+    // Flipping all bits of negative floats and the sign bit of positive floats maps them onto
+    // unsigned integers in order. Negative zero equals zero, and NaN is greater than any other
+    // float.
+    let float = |f: f64| {
+        if f.is_nan() {
+            u64::MAX
+        } else if f == 0.0 {
+            1 << 63
+        } else if f.is_sign_negative() {
+            !f.to_bits()
+        } else {
+            f.to_bits() | (1 << 63)
+        }
+    };
+    // Byte strings are ordered by their first eight bytes, padded with zeros.
+    let prefix = |bytes: &[u8]| {
+        let mut prefix = [0; 8];
+        let len = bytes.len().min(prefix.len());
+        prefix[..len].copy_from_slice(&bytes[..len]);
+        u64::from_be_bytes(prefix)
+    };
+    match datum {
+        Datum::False => 0,
+        Datum::True => 1,
+        Datum::Int16(i) => signed(i.into()),
+        Datum::Int32(i) => signed(i.into()),
+        Datum::Int64(i) => signed(i),
+        Datum::UInt8(u) => u.into(),
+        Datum::UInt16(u) => u.into(),
+        Datum::UInt32(u) => u.into(),
+        Datum::UInt64(u) => u,
+        Datum::Float32(f) => float(f.into_inner().into()),
+        Datum::Float64(f) => float(f.into_inner()),
+        Datum::Numeric(n) => float(numeric_to_f64(n.0)),
+        Datum::Date(d) => signed(d.unix_epoch_days().into()),
+        // Timestamps have at most microsecond precision.
+        Datum::Timestamp(t) => signed(t.timestamp_micros()),
+        Datum::TimestampTz(t) => signed(t.timestamp_micros()),
+        Datum::MzTimestamp(t) => t.into(),
+        Datum::String(s) => prefix(s.as_bytes()),
+        Datum::Bytes(b) => prefix(b),
+        Datum::Uuid(u) => prefix(u.as_bytes().as_slice()),
+        _ => 0,
+    }
+}
+
+/// Converts a numeric to the nearest float, which preserves the order of numerics. NaN converts
+/// to NaN, which is greater than any other numeric.
+fn numeric_to_f64(n: Numeric) -> f64 {
+    n.to_string().parse().unwrap_or(f64::NAN)
+}
+
+/// Returns the node at `level` above `position`, where level zero is the position itself.
+fn node_at(position: u64, level: u32) -> u64 {
+    position.checked_shr(RADIX_BITS * level).unwrap_or(0)
+}
+
+/// Packs `key` followed by `node` into `row_builder`, and returns a copy of it.
+fn pack_node<'a, I>(row_builder: &mut Row, key: I, node: u64) -> Row
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let mut packer = row_builder.packer();
+    packer.extend(key);
+    packer.push(Datum::UInt64(node));
+    row_builder.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn order_key_preserves_order() {
+        let groups: Vec<Vec<Datum>> = vec![
+            vec![
+                Datum::Int64(i64::MIN),
+                Datum::Int64(-1),
+                Datum::Int64(0),
+                Datum::Int64(1),
+                Datum::Int64(i64::MAX),
+            ],
+            vec![
+                Datum::from(f64::NEG_INFINITY),
+                Datum::from(-1.5f64),
+                Datum::from(-0.0f64),
+                Datum::from(0.0f64),
+                Datum::from(f64::MIN_POSITIVE),
+                Datum::from(2.5f64),
+                Datum::from(f64::INFINITY),
+                Datum::from(f64::NAN),
+            ],
+            vec![
+                Datum::from(Numeric::from(-12)),
+                Datum::from(Numeric::from(0)),
+                Datum::from(Numeric::from(7)),
+                Datum::from(Numeric::nan()),
+            ],
+            vec![
+                Datum::String(""),
+                Datum::String("a"),
+                Datum::String("a\u{0}"),
+                Datum::String("abcdefgh"),
+                Datum::String("abcdefghz"),
+                Datum::String("b"),
+            ],
+        ];
+        for group in groups {
+            for pair in group.windows(2) {
+                assert!(pair[0] <= pair[1], "{pair:?} is not ordered");
+                assert!(
+                    order_key(pair[0]) <= order_key(pair[1]),
+                    "{pair:?} maps out of order"
+                );
+            }
+        }
+    }
+
+    #[mz_ore::test]
+    fn query_routes_to_child() {
+        let mut children = [0; RADIX];
+        children[2] = 3;
+        children[5] = 4;
+        let query = |rank| Query {
+            index: 0,
+            slot: 0,
+            rank,
+            weight: 0.0,
+        };
+        let route = |rank| {
+            query(rank)
+                .route(&children)
+                .map(|(child, query)| (child, query.rank))
+        };
+        assert_eq!(route(0), Some((2, 0)));
+        assert_eq!(route(2), Some((2, 2)));
+        assert_eq!(route(3), Some((5, 0)));
+        assert_eq!(route(6), Some((5, 3)));
+        assert_eq!(route(7), None);
+    }
+}
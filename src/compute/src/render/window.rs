@@ -0,0 +1,915 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Window function execution logic.
+//!
+//! Consult [WindowPlan] documentation for details.
+
+use std::collections::BTreeMap;
+
+use differential_dataflow::collection::AsCollection;
+use differential_dataflow::consolidation::consolidate;
+use differential_dataflow::Collection;
+use mz_compute_types::plan::reduce::KeyValPlan;
+use mz_compute_types::plan::window::{LagLeadPlan, SumPlan, WindowPlan};
+use mz_expr::{AggregateFunc, EvalError, LagLeadType, MapFilterProject, MirScalarExpr};
+use mz_repr::fixed_length::IntoRowByTypes;
+use mz_repr::{Datum, DatumVec, Diff, Row, RowArena, RowPacker, SharedRow};
+use mz_storage_types::errors::DataflowError;
+use mz_timely_util::operator::CollectionExt;
+use timely::dataflow::Scope;
+
+use crate::extensions::arrange::MzArrange;
+use crate::extensions::reduce::MzReduce;
+use crate::render::context::{CollectionBundle, Context};
+use crate::row_spine::DatumSeq;
+use crate::typedefs::{RowErrSpine, RowRowArrangement, RowRowSpine};
+
+impl<G> Context<G>
+where
+    G: Scope,
+    G::Timestamp: crate::render::RenderTimestamp,
+{
+    /// Renders a window aggregate followed by the unnesting of its output, and applies
+    /// `mfp_after` to the result.
+    pub(crate) fn render_window(
+        &mut self,
+        input: CollectionBundle<G>,
+        key_val_plan: KeyValPlan,
+        window_plan: WindowPlan,
+        input_key: Option<Vec<MirScalarExpr>>,
+        mfp_after: MapFilterProject,
+    ) -> CollectionBundle<G> {
+        let (oks, errs) = input.scope().region_named("Window", |inner| {
+            let KeyValPlan {
+                mut key_plan,
+                mut val_plan,
+            } = key_val_plan;
+            let key_arity = key_plan.projection.len();
+            let leaf_plan = window_plan.clone();
+            let mut datums = DatumVec::new();
+            let (leaf_input, err_input): (
+                timely::dataflow::Stream<_, (Result<(Row, Row), DataflowError>, _, _)>,
+                _,
+            ) = input
+                .enter_region(inner)
+                .flat_map(input_key.map(|k| (k, None)), || {
+                    // Determine the columns we'll need from the row.
+                    let mut demand = Vec::new();
+                    demand.extend(key_plan.demand());
+                    demand.extend(val_plan.demand());
+                    demand.sort();
+                    demand.dedup();
+                    // remap column references to the subset we use.
+                    let mut demand_map = BTreeMap::new();
+                    for column in demand.iter() {
+                        demand_map.insert(*column, demand_map.len());
+                    }
+                    let demand_map_len = demand_map.len();
+                    key_plan.permute(demand_map.clone(), demand_map_len);
+                    val_plan.permute(demand_map, demand_map_len);
+                    let skips = mz_compute_types::plan::reduce::convert_indexes_to_skips(demand);
+                    move |row_datums, time, diff| {
+                        let binding = SharedRow::get();
+                        let mut row_builder = binding.borrow_mut();
+                        let temp_storage = RowArena::new();
+
+                        let mut row_iter = row_datums.drain(..);
+                        let mut datums_local = datums.borrow();
+                        // Unpack only the demanded columns.
+                        for skip in skips.iter() {
+                            datums_local.push(row_iter.nth(*skip).unwrap());
+                        }
+
+                        // Evaluate the key expressions.
+                        let key = match key_plan.evaluate_into(
+                            &mut datums_local,
+                            &temp_storage,
+                            &mut row_builder,
+                        ) {
+                            Err(e) => {
+                                return Some((
+                                    Err(DataflowError::from(e)),
+                                    time.clone(),
+                                    diff.clone(),
+                                ))
+                            }
+                            Ok(key) => key.expect("Row expected as no predicate was used"),
+                        };
+                        // Evaluate the value expressions.
+                        // The prior evaluation may have left additional columns we should delete.
+                        datums_local.truncate(skips.len());
+                        let mut val = match val_plan.evaluate_iter(&mut datums_local, &temp_storage)
+                        {
+                            Err(e) => {
+                                return Some((
+                                    Err(DataflowError::from(e)),
+                                    time.clone(),
+                                    diff.clone(),
+                                ))
+                            }
+                            Ok(val) => val.expect("Row expected as no predicate was used"),
+                        };
+                        let (position, leaf) = decode_window_input(&leaf_plan, val.next().unwrap());
+                        let key = pack_node(&mut row_builder, key.iter(), position);
+                        row_builder.packer().extend([
+                            Datum::False,
+                            leaf.original_row,
+                            leaf.value,
+                            leaf.default,
+                        ]);
+                        let val = row_builder.clone();
+                        Some((Ok((key, val)), time.clone(), diff.clone()))
+                    }
+                });
+
+            // Demux out the potential errors from key and value selector evaluation.
+            let (leaves, err) = leaf_input
+                .as_collection()
+                .consolidate_stream()
+                .flat_map_fallible("OkErrDemux", Some);
+
+            let (oks, errs) = match window_plan {
+                WindowPlan::LagLead(plan) => self.render_lag_lead(leaves, plan, key_arity),
+                WindowPlan::FirstValue(_) => self.render_first_value(leaves, key_arity),
+                WindowPlan::RowNumber(_) => {
+                    self.render_prefix(leaves, PrefixOutput::RowNumber, key_arity)
+                }
+                WindowPlan::Sum(plan) => {
+                    self.render_prefix(leaves, PrefixOutput::Sum(plan), key_arity)
+                }
+            };
+            let errs = err_input.concat(&err).concat(&errs);
+            (oks.leave_region(), errs.leave_region())
+        });
+
+        let (oks, errs) = CollectionBundle::from_collections(oks, errs).as_collection_core(
+            mfp_after,
+            None,
+            self.until.clone(),
+        );
+        CollectionBundle::from_collections(oks, errs)
+    }
+
+    /// Renders a [LagLeadPlan] over `leaves`, which are keyed by the window partition key and the
+    /// position of the row, and hold the rows as produced by [decode_window_input].
+    ///
+    /// Produces rows of the window partition key followed by the output list element, as well as
+    /// errors from input validation.
+    fn render_lag_lead<S>(
+        &self,
+        leaves: Collection<S, (Row, Row), Diff>,
+        plan: LagLeadPlan,
+        key_arity: usize,
+    ) -> (Collection<S, Row, Diff>, Collection<S, DataflowError, Diff>)
+    where
+        S: Scope<Timestamp = G::Timestamp>,
+    {
+        let arranged = leaves.mz_arrange::<RowRowSpine<_, _>>("Arranged LagLead leaves");
+        let errs = self.render_error_check(&arranged, "LagLead");
+        let (_roots, neighbours) =
+            self.render_summaries(&arranged, Some(plan.lag_lead.clone()), key_arity, "LagLead");
+
+        // Compute the output of each row from the neighbour of its position and its peers.
+        let lag_lead = plan.lag_lead;
+        let oks = leaves
+            .concatenate(neighbours)
+            .mz_arrange::<RowRowSpine<_, _>>("Arranged LagLead output")
+            .mz_reduce_abelian::<_, RowRowSpine<_, _>>(
+                "LagLead output",
+                move |_key, source, target| {
+                    let mut neighbour = None;
+                    let mut rows = Vec::with_capacity(source.len());
+                    for (value, count) in source.iter() {
+                        let mut datums = value.into_datum_iter(None);
+                        if datums.next() == Some(Datum::True) {
+                            neighbour = datums.next();
+                        } else {
+                            let original_row = datums.next().unwrap();
+                            let value = datums.next().unwrap();
+                            let default = datums.next().unwrap();
+                            // Note that in the non-positive case, this is wrong, but harmless
+                            // because the error check will produce an error.
+                            let count = usize::try_from(*count).unwrap_or(0);
+                            rows.push((original_row, value, default, count));
+                        }
+                    }
+                    if matches!(lag_lead, LagLeadType::Lead) {
+                        rows.reverse();
+                    }
+
+                    let binding = SharedRow::get();
+                    let mut row_builder = binding.borrow_mut();
+                    let mut previous = neighbour;
+                    for (original_row, value, default, count) in rows {
+                        for _ in 0..count {
+                            let result = previous.unwrap_or(default);
+                            row_builder.packer().push_list([result, original_row]);
+                            target.push((row_builder.clone(), 1));
+                            previous = Some(value);
+                        }
+                    }
+                    consolidate(target);
+                },
+            )
+            .as_collection(move |k, v| unpack_output(k, v, key_arity));
+
+        (oks, errs)
+    }
+
+    /// Renders a [FirstValuePlan](mz_compute_types::plan::window::FirstValuePlan) over `leaves`,
+    /// which are keyed by the window partition key and the position of the row.
+    ///
+    /// The root of the radix tree of [Context::render_summaries] holds the value of the first row
+    /// of each partition, which we join with the rows of the partition.
+    fn render_first_value<S>(
+        &self,
+        leaves: Collection<S, (Row, Row), Diff>,
+        key_arity: usize,
+    ) -> (Collection<S, Row, Diff>, Collection<S, DataflowError, Diff>)
+    where
+        S: Scope<Timestamp = G::Timestamp>,
+    {
+        let arranged = leaves.mz_arrange::<RowRowSpine<_, _>>("Arranged FirstValue leaves");
+        let errs = self.render_error_check(&arranged, "FirstValue");
+        let (roots, _neighbours) = self.render_summaries(&arranged, None, key_arity, "FirstValue");
+
+        let firsts = roots
+            .map(move |(key, summary)| {
+                // Skip the smallest position of the summary to find its first value.
+                let first = summary.iter().nth(2).unwrap();
+                (
+                    Row::pack(key.iter().take(key_arity)),
+                    Row::pack_slice(&[first]),
+                )
+            })
+            .mz_arrange::<RowRowSpine<_, _>>("Arranged FirstValue roots");
+        let rows = leaves
+            .map(move |(key, leaf)| {
+                let original_row = leaf.iter().nth(1).unwrap();
+                (
+                    Row::pack(key.iter().take(key_arity)),
+                    Row::pack_slice(&[original_row]),
+                )
+            })
+            .mz_arrange::<RowRowSpine<_, _>>("Arranged FirstValue rows");
+
+        let oks = self.linear_join_spec.render(
+            &rows,
+            &firsts,
+            self.shutdown_token.clone(),
+            move |key, row, first| {
+                let binding = SharedRow::get();
+                let mut row_builder = binding.borrow_mut();
+                let original_row = row.into_datum_iter(None).next().unwrap();
+                let first = first.into_datum_iter(None).next().unwrap();
+                let mut packer = row_builder.packer();
+                packer.extend(key.into_datum_iter(None));
+                packer.push_list([first, original_row]);
+                Some(row_builder.clone())
+            },
+        );
+
+        (oks, errs)
+    }
+
+    /// Renders `row_number` or a [SumPlan] over `leaves`, which are keyed by the window partition
+    /// key and the position of the row.
+    ///
+    /// Each level of the radix tree totals the rows below each node, and reports for each child
+    /// the totals of the children before it. We then descend the tree, and add up these totals
+    /// along the path from the root to each position, which yields the totals of the rows at
+    /// smaller positions. Finally, each row adds the totals of its preceding peers.
+    fn render_prefix<S>(
+        &self,
+        leaves: Collection<S, (Row, Row), Diff>,
+        output: PrefixOutput,
+        key_arity: usize,
+    ) -> (Collection<S, Row, Diff>, Collection<S, DataflowError, Diff>)
+    where
+        S: Scope<Timestamp = G::Timestamp>,
+    {
+        let name = match &output {
+            PrefixOutput::RowNumber => "RowNumber",
+            PrefixOutput::Sum(_) => "Sum",
+        };
+        let arranged = leaves.mz_arrange::<RowRowSpine<_, _>>(&format!("Arranged {name} leaves"));
+        let errs = self.render_error_check(&arranged, name);
+
+        // Total the rows at each occupied position, which forms the leaves of the radix tree.
+        let mut totals = arranged
+            .mz_reduce_abelian::<_, RowRowSpine<_, _>>(
+                &format!("{name} Leaf"),
+                move |_key, source, target| {
+                    let mut totals = Totals::default();
+                    for (value, count) in source.iter() {
+                        totals.add_value(value.into_datum_iter(None).nth(2).unwrap(), *count);
+                    }
+                    let binding = SharedRow::get();
+                    let mut row_builder = binding.borrow_mut();
+                    totals.push_into(&mut row_builder.packer());
+                    target.push((row_builder.clone(), 1));
+                },
+            )
+            .as_collection(|k, v| (k.into_owned(), v.into_owned()));
+
+        // Climb the radix tree. Each level groups the totals of the children of each node, reports
+        // the totals of the children before each child, and totals the node itself.
+        let mut befores = Vec::new();
+        for level in 1..=WindowPlan::LEVELS {
+            let level_output = totals
+                .map(move |(key, totals)| {
+                    let binding = SharedRow::get();
+                    let mut row_builder = binding.borrow_mut();
+                    let child = unpack_node(key.iter().skip(key_arity));
+                    let node = pack_node(
+                        &mut row_builder,
+                        key.iter().take(key_arity),
+                        child >> WindowPlan::RADIX_BITS,
+                    );
+                    let mut packer = row_builder.packer();
+                    push_node(&mut packer, child);
+                    packer.extend(totals.iter());
+                    (node, row_builder.clone())
+                })
+                .mz_arrange::<RowRowSpine<_, _>>(&format!("Arranged {name} level {level}"))
+                .mz_reduce_abelian::<_, RowRowSpine<_, _>>(
+                    &format!("{name} level {level}"),
+                    move |_key, source, target| {
+                        let mut children: Vec<_> = source
+                            .iter()
+                            .filter(|(_, count)| *count > 0)
+                            .map(|(value, _)| {
+                                let mut datums = value.into_datum_iter(None);
+                                let child = unpack_node(datums.by_ref().take(2));
+                                (child, Totals::unpack(datums))
+                            })
+                            .collect();
+                        if children.is_empty() {
+                            return;
+                        }
+                        children.sort_by_key(|(child, _)| *child);
+
+                        let binding = SharedRow::get();
+                        let mut row_builder = binding.borrow_mut();
+                        let mut before = Totals::default();
+                        for (child, totals) in children {
+                            let mut packer = row_builder.packer();
+                            packer.push(Datum::True);
+                            push_node(&mut packer, child);
+                            before.push_into(&mut packer);
+                            target.push((row_builder.clone(), 1));
+                            before.add(&totals);
+                        }
+                        let mut packer = row_builder.packer();
+                        packer.push(Datum::False);
+                        before.push_into(&mut packer);
+                        target.push((row_builder.clone(), 1));
+                    },
+                )
+                .as_collection(|k, v| (k.into_owned(), v.into_owned()));
+
+            totals = level_output.flat_map(|(key, output)| {
+                let mut datums = output.iter();
+                (datums.next() == Some(Datum::False)).then(|| (key, Row::pack(datums)))
+            });
+            befores.push(level_output.flat_map(|(key, output)| {
+                let mut datums = output.iter();
+                (datums.next() == Some(Datum::True)).then(|| (key, Row::pack(datums)))
+            }));
+        }
+
+        // Descend the radix tree. The totals before a child are the totals before its node, plus
+        // the totals of the children before it. The root has nothing before it, so the totals
+        // before its children are those that the top level reported.
+        let mut offsets = befores
+            .pop()
+            .expect("at least one level")
+            .map(move |(key, before)| child_totals(key_arity, key.iter(), before.iter()));
+        for (level, befores) in (1..WindowPlan::LEVELS).zip(befores).rev() {
+            offsets = befores
+                .map(|(key, before)| {
+                    let mut row = Row::default();
+                    let mut packer = row.packer();
+                    packer.push(Datum::False);
+                    packer.extend(before.iter());
+                    (key, row)
+                })
+                .concat(&offsets.map(|(key, offset)| {
+                    let mut row = Row::default();
+                    let mut packer = row.packer();
+                    packer.push(Datum::True);
+                    packer.extend(offset.iter());
+                    (key, row)
+                }))
+                .mz_arrange::<RowRowSpine<_, _>>(&format!("Arranged {name} offsets {level}"))
+                .mz_reduce_abelian::<_, RowRowSpine<_, _>>(
+                    &format!("{name} offsets {level}"),
+                    move |_key, source, target| {
+                        let mut offset = Totals::default();
+                        let mut children = Vec::with_capacity(source.len());
+                        for (value, count) in source.iter() {
+                            if *count <= 0 {
+                                continue;
+                            }
+                            let mut datums = value.into_datum_iter(None);
+                            if datums.next() == Some(Datum::True) {
+                                offset = Totals::unpack(datums);
+                            } else {
+                                let child = unpack_node(datums.by_ref().take(2));
+                                children.push((child, Totals::unpack(datums)));
+                            }
+                        }
+
+                        let binding = SharedRow::get();
+                        let mut row_builder = binding.borrow_mut();
+                        for (child, before) in children {
+                            let mut totals = offset;
+                            totals.add(&before);
+                            let mut packer = row_builder.packer();
+                            push_node(&mut packer, child);
+                            totals.push_into(&mut packer);
+                            target.push((row_builder.clone(), 1));
+                        }
+                    },
+                )
+                .as_collection(move |k, v| {
+                    child_totals(key_arity, k.into_datum_iter(None), v.into_datum_iter(None))
+                });
+        }
+
+        // Compute the output of each row from the totals before its position and its peers.
+        let offsets = offsets.map(|(key, offset)| {
+            let mut row = Row::default();
+            let mut packer = row.packer();
+            packer.push(Datum::True);
+            packer.extend(offset.iter());
+            (key, row)
+        });
+        let oks = leaves
+            .concat(&offsets)
+            .mz_arrange::<RowRowSpine<_, _>>(&format!("Arranged {name} output"))
+            .mz_reduce_abelian::<_, RowRowSpine<_, _>>(
+                &format!("{name} output"),
+                move |_key, source, target| {
+                    let mut totals = Totals::default();
+                    let mut rows = Vec::with_capacity(source.len());
+                    for (value, count) in source.iter() {
+                        let mut datums = value.into_datum_iter(None);
+                        if datums.next() == Some(Datum::True) {
+                            totals = Totals::unpack(datums);
+                        } else {
+                            let original_row = datums.next().unwrap();
+                            let value = datums.next().unwrap();
+                            rows.push((original_row, value, *count));
+                        }
+                    }
+
+                    let binding = SharedRow::get();
+                    let mut row_builder = binding.borrow_mut();
+                    match &output {
+                        PrefixOutput::RowNumber => {
+                            for (original_row, _value, count) in rows {
+                                for _ in 0..count {
+                                    totals.rows += 1;
+                                    let result = Datum::Int64(totals.rows);
+                                    row_builder.packer().push_list([result, original_row]);
+                                    target.push((row_builder.clone(), 1));
+                                }
+                            }
+                        }
+                        PrefixOutput::Sum(plan) if plan.peers => {
+                            for (_original_row, value, count) in rows.iter() {
+                                totals.add_value(*value, *count);
+                            }
+                            let result = totals.sum_result(&plan.func);
+                            for (original_row, _value, count) in rows {
+                                for _ in 0..count {
+                                    row_builder.packer().push_list([result, original_row]);
+                                    target.push((row_builder.clone(), 1));
+                                }
+                            }
+                        }
+                        PrefixOutput::Sum(plan) => {
+                            for (original_row, value, count) in rows {
+                                for _ in 0..count {
+                                    totals.add_value(value, 1);
+                                    let result = totals.sum_result(&plan.func);
+                                    row_builder.packer().push_list([result, original_row]);
+                                    target.push((row_builder.clone(), 1));
+                                }
+                            }
+                        }
+                    }
+                    consolidate(target);
+                },
+            )
+            .as_collection(move |k, v| unpack_output(k, v, key_arity));
+
+        (oks, errs)
+    }
+
+    /// Summarizes the occupied positions of `arranged` in a radix tree, and returns the summaries
+    /// of the roots of the trees, keyed by the window partition key and the root node.
+    ///
+    /// If `lag_lead` is set, also returns the neighbour of each position that has one, keyed by
+    /// the window partition key and the position.
+    fn render_summaries<S>(
+        &self,
+        arranged: &RowRowArrangement<S>,
+        lag_lead: Option<LagLeadType>,
+        key_arity: usize,
+        name: &str,
+    ) -> (
+        Collection<S, (Row, Row), Diff>,
+        Vec<Collection<S, (Row, Row), Diff>>,
+    )
+    where
+        S: Scope<Timestamp = G::Timestamp>,
+    {
+        // Summarize each occupied position, which forms the leaves of the radix tree.
+        let mut summaries = arranged
+            .mz_reduce_abelian::<_, RowRowSpine<_, _>>(
+                &format!("{name} Leaf"),
+                move |key, source, target| {
+                    let position = unpack_node(key.into_datum_iter(None).skip(key_arity));
+                    let mut values = source
+                        .iter()
+                        .filter(|(_, count)| *count > 0)
+                        .map(|(value, _)| value.into_datum_iter(None).nth(2).unwrap());
+                    let Some(first) = values.next() else { return };
+                    let last = values.last().unwrap_or(first);
+                    let summary = Summary {
+                        min: position,
+                        first,
+                        max: position,
+                        last,
+                    };
+                    let binding = SharedRow::get();
+                    let mut row_builder = binding.borrow_mut();
+                    summary.pack_into(&mut row_builder, false);
+                    target.push((row_builder.clone(), 1));
+                },
+            )
+            .as_collection(|k, v| (k.into_owned(), v.into_owned()));
+
+        // Climb the radix tree. Each level groups the summaries of the children of each node,
+        // reports the neighbours between adjacent children, and summarizes the node itself.
+        let mut neighbours = Vec::new();
+        for level in 1..=WindowPlan::LEVELS {
+            let level_lag_lead = lag_lead.clone();
+            let output = summaries
+                .map(move |(key, summary)| {
+                    let binding = SharedRow::get();
+                    let mut row_builder = binding.borrow_mut();
+                    let child = unpack_node(key.iter().skip(key_arity));
+                    let node = pack_node(
+                        &mut row_builder,
+                        key.iter().take(key_arity),
+                        child >> WindowPlan::RADIX_BITS,
+                    );
+                    (node, summary)
+                })
+                .mz_arrange::<RowRowSpine<_, _>>(&format!("Arranged {name} level {level}"))
+                .mz_reduce_abelian::<_, RowRowSpine<_, _>>(
+                    &format!("{name} level {level}"),
+                    move |_key, source, target| {
+                        let mut children: Vec<_> = source
+                            .iter()
+                            .filter(|(_, count)| *count > 0)
+                            .map(|(summary, _)| Summary::unpack(summary))
+                            .collect();
+                        children.sort_by_key(|child| child.min);
+
+                        let binding = SharedRow::get();
+                        let mut row_builder = binding.borrow_mut();
+                        if let Some(lag_lead) = &level_lag_lead {
+                            for pair in children.windows(2) {
+                                let (position, value) = match lag_lead {
+                                    LagLeadType::Lag => (pair[1].min, pair[0].last),
+                                    LagLeadType::Lead => (pair[0].max, pair[1].first),
+                                };
+                                let mut packer = row_builder.packer();
+                                packer.push(Datum::True);
+                                push_node(&mut packer, position);
+                                packer.push(value);
+                                target.push((row_builder.clone(), 1));
+                            }
+                        }
+                        if let (Some(first), Some(last)) = (children.first(), children.last()) {
+                            let summary = Summary {
+                                min: first.min,
+                                first: first.first,
+                                max: last.max,
+                                last: last.last,
+                            };
+                            summary.pack_into(&mut row_builder, true);
+                            target.push((row_builder.clone(), 1));
+                        }
+                    },
+                )
+                .as_collection(|k, v| (k.into_owned(), v.into_owned()));
+
+            summaries = output.flat_map(|(key, output)| {
+                let mut datums = output.iter();
+                (datums.next() == Some(Datum::False)).then(|| (key, Row::pack(datums)))
+            });
+            if lag_lead.is_some() {
+                neighbours.push(output.flat_map(move |(key, output)| {
+                    let mut datums = output.iter();
+                    if datums.next() != Some(Datum::True) {
+                        return None;
+                    }
+                    let binding = SharedRow::get();
+                    let mut row_builder = binding.borrow_mut();
+                    let position = unpack_node(datums.by_ref().take(2));
+                    let key = pack_node(&mut row_builder, key.iter().take(key_arity), position);
+                    row_builder
+                        .packer()
+                        .extend([Datum::True, datums.next().unwrap()]);
+                    Some((key, row_builder.clone()))
+                }));
+            }
+        }
+
+        (summaries, neighbours)
+    }
+
+    /// Reports non-positive accumulations among the arranged leaves of a window function.
+    fn render_error_check<S>(
+        &self,
+        arranged: &RowRowArrangement<S>,
+        name: &'static str,
+    ) -> Collection<S, DataflowError, Diff>
+    where
+        S: Scope<Timestamp = G::Timestamp>,
+    {
+        // Negative counts would be surprising, but until we are 100% certain we won't see them,
+        // we should report when we do.
+        let error_logger = self.error_logger();
+        arranged
+            .mz_reduce_abelian::<_, RowErrSpine<_, _>>(
+                &format!("{name} Error Check"),
+                move |_key, source, target| {
+                    for (value, count) in source.iter() {
+                        if *count > 0 {
+                            continue;
+                        }
+                        let value = Row::pack(value.into_datum_iter(None));
+                        let message = "Non-positive accumulation in window function";
+                        let details = format!("window={name}, value={value:?}, count={count}");
+                        error_logger.log(message, &details);
+                        target.push((EvalError::Internal(message.to_string()).into(), 1));
+                        return;
+                    }
+                },
+            )
+            .as_collection(|_, v| v.into_owned())
+    }
+}
+
+/// The output of [Context::render_prefix].
+enum PrefixOutput {
+    /// `row_number`.
+    RowNumber,
+    /// A running sum.
+    Sum(SumPlan),
+}
+
+/// Packs the window partition key of `key` followed by the output list element `value`.
+fn unpack_output(key: DatumSeq, value: DatumSeq, key_arity: usize) -> Row {
+    let binding = SharedRow::get();
+    let mut row_builder = binding.borrow_mut();
+    let mut packer = row_builder.packer();
+    packer.extend(key.into_datum_iter(None).take(key_arity));
+    packer.extend(value.into_datum_iter(None));
+    row_builder.clone()
+}
+
+/// Rekeys the totals of a child, given the key of its node and the child followed by its totals.
+fn child_totals<'a, K, V>(key_arity: usize, key: K, value: V) -> (Row, Row)
+where
+    K: IntoIterator<Item = Datum<'a>>,
+    V: IntoIterator<Item = Datum<'a>>,
+{
+    let binding = SharedRow::get();
+    let mut row_builder = binding.borrow_mut();
+    let mut value = value.into_iter();
+    let child = unpack_node(value.by_ref().take(2));
+    let key = pack_node(&mut row_builder, key.into_iter().take(key_arity), child);
+    (key, Row::pack(value))
+}
+
+/// The fields of a row of a window partition.
+struct WindowInput<'a> {
+    original_row: Datum<'a>,
+    /// The argument of the window function, or null if it has none.
+    value: Datum<'a>,
+    /// The default of `lag` and `lead`, or null for other window functions.
+    default: Datum<'a>,
+}
+
+/// Decodes the input of a window aggregate, and determines the position of the row.
+///
+/// The input is `row(<inner>, <order by>...)`, where `<inner>` is
+/// * `row(<original row>, row(<value>, <offset>, <default>))` for `lag` and `lead`,
+/// * `list[<original row>]` for `row_number`, and
+/// * `row(<original row>, <value>)` for `first_value` and sums.
+fn decode_window_input<'a>(plan: &WindowPlan, datum: Datum<'a>) -> (u128, WindowInput<'a>) {
+    let mut fields = datum.unwrap_list().iter();
+    let mut inner = fields.next().unwrap().unwrap_list().iter();
+    let position = plan.position(fields.nth(plan.order_by().column).unwrap());
+    let original_row = inner.next().unwrap();
+    let (value, default) = match plan {
+        WindowPlan::LagLead(_) => {
+            let mut args = inner.next().unwrap().unwrap_list().iter();
+            let value = args.next().unwrap();
+            let default = args.nth(1).unwrap();
+            (value, default)
+        }
+        WindowPlan::RowNumber(_) => (Datum::Null, Datum::Null),
+        WindowPlan::FirstValue(_) | WindowPlan::Sum(_) => (inner.next().unwrap(), Datum::Null),
+    };
+    let input = WindowInput {
+        original_row,
+        value,
+        default,
+    };
+    (position, input)
+}
+
+/// A summary of the occupied positions below a node of the radix tree.
+struct Summary<'a> {
+    /// The smallest occupied position.
+    min: u128,
+    /// The value of the first row at the smallest occupied position.
+    first: Datum<'a>,
+    /// The largest occupied position.
+    max: u128,
+    /// The value of the last row at the largest occupied position.
+    last: Datum<'a>,
+}
+
+impl<'a> Summary<'a> {
+    /// Packs the summary into `row`, preceded by `Datum::False` if `tagged` is set.
+    fn pack_into(&self, row: &mut Row, tagged: bool) {
+        let mut packer = row.packer();
+        if tagged {
+            packer.push(Datum::False);
+        }
+        push_node(&mut packer, self.min);
+        packer.push(self.first);
+        push_node(&mut packer, self.max);
+        packer.push(self.last);
+    }
+
+    /// Unpacks a summary packed without a tag.
+    fn unpack(summary: &'a DatumSeq<'a>) -> Self {
+        let mut datums = summary.into_datum_iter(None);
+        let min = unpack_node(datums.by_ref().take(2));
+        let first = datums.next().unwrap();
+        let max = unpack_node(datums.by_ref().take(2));
+        let last = datums.next().unwrap();
+        Summary {
+            min,
+            first,
+            max,
+            last,
+        }
+    }
+}
+
+/// The totals of a set of rows of a window partition.
+#[derive(Clone, Copy, Default)]
+struct Totals {
+    /// The number of rows.
+    rows: i64,
+    /// The sum of the non-null values of the rows.
+    sum: i128,
+    /// The number of rows with a non-null value.
+    values: i64,
+}
+
+impl Totals {
+    /// Adds `count` rows with the value `value`.
+    fn add_value(&mut self, value: Datum, count: Diff) {
+        self.rows += count;
+        let value = match value {
+            Datum::Null => return,
+            Datum::Int16(i) => i128::from(i),
+            Datum::Int32(i) => i128::from(i),
+            Datum::Int64(i) => i128::from(i),
+            Datum::UInt16(u) => i128::from(u),
+            Datum::UInt32(u) => i128::from(u),
+            Datum::UInt64(u) => i128::from(u),
+            value => panic!("unsupported value for window sum: {value:?}"),
+        };
+        self.sum += value * i128::from(count);
+        self.values += count;
+    }
+
+    /// Adds the rows of `other`.
+    fn add(&mut self, other: &Totals) {
+        self.rows += other.rows;
+        self.sum += other.sum;
+        self.values += other.values;
+    }
+
+    /// Pushes the totals, with the sum as its high and low 64 bits.
+    fn push_into(&self, packer: &mut RowPacker) {
+        let high = i64::try_from(self.sum >> 64).expect("shifted to 64 bits");
+        let low = u64::try_from(self.sum & i128::from(u64::MAX)).expect("masked to 64 bits");
+        packer.push(Datum::Int64(self.rows));
+        packer.push(Datum::Int64(high));
+        packer.push(Datum::UInt64(low));
+        packer.push(Datum::Int64(self.values));
+    }
+
+    /// Reads totals pushed by [Totals::push_into].
+    fn unpack<'a, I>(datums: I) -> Self
+    where
+        I: IntoIterator<Item = Datum<'a>>,
+    {
+        let mut datums = datums.into_iter();
+        let rows = datums.next().unwrap().unwrap_int64();
+        let high = datums.next().unwrap().unwrap_int64();
+        let low = datums.next().unwrap().unwrap_uint64();
+        let values = datums.next().unwrap().unwrap_int64();
+        Totals {
+            rows,
+            sum: (i128::from(high) << 64) | i128::from(low),
+            values,
+        }
+    }
+
+    /// The result of the sum `func` over the rows, which matches the result of `func` as a
+    /// reduction.
+    fn sum_result(&self, func: &AggregateFunc) -> Datum<'static> {
+        if self.values == 0 {
+            return Datum::Null;
+        }
+        match func {
+            AggregateFunc::SumInt16 | AggregateFunc::SumInt32 => {
+                // This conversion is safe, as long as we have less than 2^32 summands.
+                #[allow(clippy::as_conversions)]
+                Datum::Int64(self.sum as i64)
+            }
+            AggregateFunc::SumUInt16 | AggregateFunc::SumUInt32 => {
+                if !self.sum.is_negative() {
+                    // We adopt an unsigned wrapping behavior to match the reduction.
+                    #[allow(clippy::as_conversions)]
+                    Datum::UInt64(self.sum as u64)
+                } else {
+                    // Only negative counts, which the error check reports, lead here.
+                    Datum::Null
+                }
+            }
+            AggregateFunc::SumInt64 => Datum::from(self.sum),
+            AggregateFunc::SumUInt64 => {
+                if !self.sum.is_negative() {
+                    Datum::from(self.sum)
+                } else {
+                    // Only negative counts, which the error check reports, lead here.
+                    Datum::Null
+                }
+            }
+            func => panic!("unsupported window sum: {func:?}"),
+        }
+    }
+}
+
+/// Packs `key` followed by `node` into `row_builder`, and returns a copy of it.
+fn pack_node<'a, I>(row_builder: &mut Row, key: I, node: u128) -> Row
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let mut packer = row_builder.packer();
+    packer.extend(key);
+    push_node(&mut packer, node);
+    row_builder.clone()
+}
+
+/// Pushes a node or position as its high and low 64 bits.
+fn push_node(packer: &mut RowPacker, node: u128) {
+    let high = u64::try_from(node >> 64).expect("positions have at most 65 bits");
+    let low = u64::try_from(node & u128::from(u64::MAX)).expect("masked to 64 bits");
+    packer.push(Datum::UInt64(high));
+    packer.push(Datum::UInt64(low));
+}
+
+/// Reads a node or position pushed by [push_node].
+fn unpack_node<'a, I>(datums: I) -> u128
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let mut datums = datums.into_iter();
+    let high = datums.next().unwrap().unwrap_uint64();
+    let low = datums.next().unwrap().unwrap_uint64();
+    (u128::from(high) << 64) | u128::from(low)
+}
@@ -100,12 +100,15 @@ mod container {
     use differential_dataflow::trace::cursor::MyTrait;
     use differential_dataflow::trace::implementations::BatchContainer;
     use differential_dataflow::trace::implementations::OffsetList;
-    use mz_ore::region::Region;
     use mz_repr::{read_datum, Datum, Row};
 
+    use crate::arrangement::spill::{spiller, SpillRegion, Spiller};
+
     /// A slice container with four bytes overhead per slice.
     pub struct DatumContainer {
         batches: Vec<DatumBatch>,
+        /// The spiller to draw batches from, if the container holds the cold data of a large merge.
+        spill: Option<Spiller>,
     }
 
     impl DatumContainer {
@@ -119,7 +122,7 @@ mod container {
             );
             for batch in self.batches.iter() {
                 crate::row_spine::offset_list_size(&batch.offsets, &mut callback);
-                callback(batch.storage.len(), batch.storage.capacity());
+                batch.storage.heap_size(&mut callback);
             }
         }
     }
@@ -132,10 +135,21 @@ mod container {
             if let Some(batch) = self.batches.last_mut() {
                 let success = batch.try_push(item.bytes);
                 if !success {
-                    // double the lengths from `batch`.
-                    let item_cap = 2 * batch.offsets.len();
-                    let byte_cap = std::cmp::max(2 * batch.storage.capacity(), item.bytes.len());
-                    let mut new_batch = DatumBatch::with_capacities(item_cap, byte_cap);
+                    let mut new_batch = match &self.spill {
+                        // Continue with another chunk of the same size.
+                        Some(spiller) => DatumBatch::with_spiller(
+                            spiller,
+                            batch.offsets.len(),
+                            std::cmp::max(spiller.chunk_bytes, item.bytes.len()),
+                        ),
+                        None => {
+                            // double the lengths from `batch`.
+                            let item_cap = 2 * batch.offsets.len();
+                            let byte_cap =
+                                std::cmp::max(2 * batch.storage.capacity(), item.bytes.len());
+                            DatumBatch::with_capacities(item_cap, byte_cap)
+                        }
+                    };
                     assert!(new_batch.try_push(item.bytes));
                     self.batches.push(new_batch);
                 }
@@ -145,6 +159,7 @@ mod container {
         fn with_capacity(size: usize) -> Self {
             Self {
                 batches: vec![DatumBatch::with_capacities(size, size)],
+                spill: None,
             }
         }
 
@@ -159,8 +174,19 @@ mod container {
                 item_cap += batch.offsets.len() - 1;
                 byte_cap += batch.storage.len();
             }
+            // The result of merging large batches is cold, and is built in chunks that are
+            // spilled to disk.
+            let spill = spiller().filter(|spiller| byte_cap >= spiller.min_bytes);
+            let batch = match &spill {
+                Some(spiller) => {
+                    let chunk_items = item_cap * spiller.chunk_bytes / byte_cap.max(1);
+                    DatumBatch::with_spiller(spiller, chunk_items, spiller.chunk_bytes)
+                }
+                None => DatumBatch::with_capacities(item_cap, byte_cap),
+            };
             Self {
-                batches: vec![DatumBatch::with_capacities(item_cap, byte_cap)],
+                batches: vec![batch],
+                spill,
             }
         }
 
@@ -187,10 +213,11 @@ mod container {
 
     /// A batch of slice storage.
     ///
-    /// The backing storage for this batch will not be resized.
+    /// The backing storage for this batch will not be resized, and is either held in memory or
+    /// spilled to disk.
     pub struct DatumBatch {
         offsets: OffsetList,
-        storage: Region<u8>,
+        storage: SpillRegion,
     }
 
     impl DatumBatch {
@@ -220,7 +247,25 @@ mod container {
             offsets.push(0);
             Self {
                 offsets,
-                storage: Region::new_auto(byte_cap.next_power_of_two()),
+                storage: SpillRegion::new_resident(byte_cap.next_power_of_two()),
+            }
+        }
+
+        /// Creates a batch backed by a spilled chunk, or by memory if the spiller has no chunk
+        /// ready or `byte_cap` exceeds the chunk size.
+        fn with_spiller(spiller: &Spiller, item_cap: usize, byte_cap: usize) -> Self {
+            let chunk = if byte_cap <= spiller.chunk_bytes {
+                spiller.try_chunk()
+            } else {
+                None
+            };
+            match chunk {
+                Some(storage) => {
+                    let mut offsets = OffsetList::with_capacity(item_cap + 1);
+                    offsets.push(0);
+                    Self { offsets, storage }
+                }
+                None => Self::with_capacities(item_cap, byte_cap),
             }
         }
     }
@@ -301,6 +346,92 @@ mod container {
             *self
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use mz_repr::{Datum, Row};
+
+        use super::*;
+        use crate::arrangement::spill::set_spiller;
+
+        fn row(i: i64) -> Row {
+            Row::pack_slice(&[Datum::String(&format!("key{i}")), Datum::Int64(i)])
+        }
+
+        fn container(rows: &[Row]) -> DatumContainer {
+            let mut container = DatumContainer::with_capacity(0);
+            for row in rows {
+                container.copy(DatumSeq::borrow_as(row));
+            }
+            container
+        }
+
+        fn sort(rows: &mut [Row]) {
+            rows.sort_by(|a, b| DatumSeq::borrow_as(a).cmp(&DatumSeq::borrow_as(b)));
+        }
+
+        /// Merges containers with spilling enabled and looks up keys in the result.
+        #[mz_ore::test]
+        #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `mmap`
+        fn spilled_merge_and_lookup() {
+            let dir = tempfile::tempdir().unwrap();
+            let spiller =
+                Spiller::start_with_sizes("spill".into(), dir.path().into(), 0, 256).unwrap();
+            while spiller.ready_chunks() == 0 {
+                std::thread::yield_now();
+            }
+            set_spiller(Some(spiller));
+
+            let mut even: Vec<_> = (0..1000).step_by(2).map(row).collect();
+            let mut odd: Vec<_> = (1..1000).step_by(2).map(row).collect();
+            sort(&mut even);
+            sort(&mut odd);
+            let cont1 = container(&even);
+            let cont2 = container(&odd);
+
+            let mut merged = DatumContainer::merge_capacity(&cont1, &cont2);
+            let (mut i, mut j) = (0, 0);
+            while i < cont1.len() || j < cont2.len() {
+                if j == cont2.len() || (i < cont1.len() && cont1.index(i) <= cont2.index(j)) {
+                    merged.copy(cont1.index(i));
+                    i += 1;
+                } else {
+                    merged.copy(cont2.index(j));
+                    j += 1;
+                }
+            }
+            set_spiller(None);
+
+            assert!(merged.batches.len() > 1);
+            assert!(merged.batches[0].storage.is_spilled());
+
+            let mut expected: Vec<_> = (0..1000).map(row).collect();
+            sort(&mut expected);
+            assert_eq!(merged.len(), expected.len());
+            for (index, row) in expected.iter().enumerate() {
+                assert_eq!(&merged.index(index).into_owned(), row);
+            }
+
+            // Look up keys by binary search, as cursors seeking in a batch do.
+            let lookup = |key: &Row| {
+                let (mut lower, mut upper) = (0, merged.len());
+                while lower < upper {
+                    let middle = (lower + upper) / 2;
+                    match merged.index(middle).compare(key) {
+                        Ordering::Less => lower = middle + 1,
+                        Ordering::Equal => return Some(middle),
+                        Ordering::Greater => upper = middle,
+                    }
+                }
+                None
+            };
+            for (index, row) in expected.iter().enumerate() {
+                assert_eq!(lookup(row), Some(index));
+            }
+            assert_eq!(lookup(&row(1000)), None);
+            assert_eq!(lookup(&row(-1)), None);
+        }
+    }
 }
 
 mod offset_opt {
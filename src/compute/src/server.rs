@@ -489,6 +489,8 @@ impl<'w, A: Allocate + 'static> Worker<'w, A> {
 
             self.metrics
                 .record_shared_row_metrics(self.timely_worker.index());
+            self.metrics
+                .record_arrangement_spill_metrics(self.timely_worker.index());
         }
     }
 
@@ -197,6 +197,14 @@ impl ReplicaLocation {
             ReplicaLocation::Unmanaged(_) => false,
         }
     }
+
+    /// Returns whether the replica has local disk attached.
+    pub fn disk(&self) -> bool {
+        match self {
+            ReplicaLocation::Managed(ManagedReplicaLocation { disk, .. }) => *disk,
+            ReplicaLocation::Unmanaged(_) => false,
+        }
+    }
 }
 
 /// The "role" of a cluster, which is currently used to determine the
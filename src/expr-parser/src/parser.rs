@@ -70,7 +70,7 @@ pub fn try_parse_def(catalog: &TestCatalog, s: &str) -> Result<Def, String> {
 mod relation {
     use std::collections::BTreeMap;
 
-    use mz_expr::{AccessStrategy, Id, JoinImplementation, LocalId, MirRelationExpr};
+    use mz_expr::{AccessStrategy, Id, JoinHints, JoinImplementation, LocalId, MirRelationExpr};
     use mz_repr::{Diff, RelationType, Row, ScalarType};
 
     use super::*;
@@ -361,6 +361,7 @@ mod relation {
             inputs,
             equivalences: vec![],
             implementation: JoinImplementation::Unimplemented,
+            hints: JoinHints::default(),
         })
     }
 
@@ -380,6 +381,7 @@ mod relation {
             inputs,
             equivalences,
             implementation: JoinImplementation::Unimplemented,
+            hints: JoinHints::default(),
         })
     }
 
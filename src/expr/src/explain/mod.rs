@@ -20,6 +20,7 @@ use mz_repr::explain::{
     AnnotatedPlan, Explain, ExplainConfig, ExplainError, ExprHumanizer, ScalarOps,
     UnsupportedFormat, UsedIndexes,
 };
+use mz_repr::stats::CollectionStatistics;
 use mz_repr::GlobalId;
 
 use crate::interpret::{Interpreter, MfpEval, Trace};
@@ -48,6 +49,9 @@ pub struct ExplainContext<'a> {
     // this decision if we want to every make this print in the json output in a machine readable
     // way.
     pub optimizer_notices: Vec<String>,
+    // Statistics about the collections read by the explained plan, used to derive the
+    // `cardinality` attribute.
+    pub statistics: BTreeMap<GlobalId, CollectionStatistics>,
 }
 
 /// A structure produced by the `explain_$format` methods in
@@ -526,6 +526,7 @@ impl MirRelationExpr {
                     implementation @ (JoinImplementation::Differential(..)
                     | JoinImplementation::DeltaQuery(..)
                     | JoinImplementation::Unimplemented),
+                hints: _,
             } => {
                 let has_equivalences = !equivalences.is_empty();
 
@@ -0,0 +1,253 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! HyperLogLog sketches for approximate distinct counting.
+//!
+//! A sketch with precision `p` has `2^p` one-byte registers. Each value is
+//! hashed to 64 bits, of which the top `p` bits select a register and the
+//! remaining bits determine the register's *rank*: the position of their
+//! leftmost one bit. A register holds the maximum rank of the values that
+//! map to it, from which the number of distinct values can be estimated with
+//! a relative standard error of about `1.04 / sqrt(2^p)`.
+//!
+//! Registers only ever grow, so sketches cannot absorb retractions. Dataflows
+//! instead maintain the multiset of `(register, rank)` pairs of their input,
+//! which has at most `2^p * (65 - p)` distinct elements regardless of the
+//! input's cardinality, and build a sketch from it on demand. See
+//! [`register_of`].
+//!
+//! Sketches are exchanged with users as `bytea` values consisting of the
+//! precision followed by the registers.
+
+use std::fmt;
+
+use mz_ore::cast::{CastFrom, CastLossy};
+use mz_repr::{Datum, Row};
+
+/// The smallest supported precision.
+pub const MIN_PRECISION: u8 = 4;
+
+/// The largest supported precision.
+pub const MAX_PRECISION: u8 = 18;
+
+/// The precision used when none is specified.
+pub const DEFAULT_PRECISION: u8 = 12;
+
+/// Computes the register and rank for `datum` in a sketch with the given
+/// precision, encoded into a single integer as `register << 8 | rank`.
+///
+/// The hash is computed over the datum's row encoding with a fixed seed, so
+/// it is stable across processes and may be stored in sketches.
+pub fn register_of(datum: Datum, precision: u8) -> i32 {
+    let hash = seahash::hash(Row::pack_slice(&[datum]).data());
+    let register = hash >> (64 - u32::from(precision));
+    let rank = (hash << precision)
+        .leading_zeros()
+        .min(64 - u32::from(precision))
+        + 1;
+    let encoded = register << 8 | u64::from(rank);
+    i32::try_from(encoded).expect("precision is at most MAX_PRECISION")
+}
+
+/// A HyperLogLog sketch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sketch {
+    precision: u8,
+    registers: Vec<u8>,
+}
+
+impl Sketch {
+    /// Creates an empty sketch with the given precision.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `precision` is not within [`MIN_PRECISION`] and
+    /// [`MAX_PRECISION`].
+    pub fn new(precision: u8) -> Sketch {
+        assert!(
+            (MIN_PRECISION..=MAX_PRECISION).contains(&precision),
+            "invalid HyperLogLog precision {precision}"
+        );
+        Sketch {
+            precision,
+            registers: vec![0; 1 << precision],
+        }
+    }
+
+    /// Returns the precision of the sketch.
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    /// Records a register and rank pair produced by [`register_of`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `encoded` was produced for a different precision.
+    pub fn insert(&mut self, encoded: i32) {
+        let encoded = u32::try_from(encoded).expect("encoded registers are positive");
+        let register = &mut self.registers[usize::cast_from(encoded >> 8)];
+        let rank = u8::try_from(encoded & 0xff).expect("masked to eight bits");
+        *register = (*register).max(rank);
+    }
+
+    /// Merges `other` into this sketch, so that it estimates the number of
+    /// distinct values in the union of both inputs. If the precisions differ,
+    /// the result has the smaller of the two.
+    pub fn merge(&mut self, other: &Sketch) {
+        if other.precision < self.precision {
+            *self = self.fold(other.precision);
+        }
+        let other = other.fold(self.precision);
+        for (register, rank) in self.registers.iter_mut().zip(other.registers) {
+            *register = (*register).max(rank);
+        }
+    }
+
+    /// Converts the sketch to a lower precision.
+    ///
+    /// The register bits dropped by the lower precision become the leading
+    /// bits of the remaining hash, so the rank is recomputed from them.
+    fn fold(&self, precision: u8) -> Sketch {
+        if precision == self.precision {
+            return self.clone();
+        }
+        let shift = u32::from(self.precision - precision);
+        let mut folded = Sketch::new(precision);
+        for (index, rank) in self.registers.iter().copied().enumerate() {
+            if rank == 0 {
+                continue;
+            }
+            let index = u32::try_from(index).expect("at most 2^MAX_PRECISION registers");
+            let dropped = index & ((1 << shift) - 1);
+            let rank = if dropped == 0 {
+                rank + u8::try_from(shift).expect("shift is at most MAX_PRECISION")
+            } else {
+                u8::try_from(dropped.leading_zeros() - (32 - shift) + 1)
+                    .expect("rank is at most shift")
+            };
+            let register = &mut folded.registers[usize::cast_from(index >> shift)];
+            *register = (*register).max(rank);
+        }
+        folded
+    }
+
+    /// Estimates the number of distinct values recorded in the sketch.
+    pub fn estimate(&self) -> i64 {
+        let m = f64::cast_lossy(self.registers.len());
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / m),
+        };
+        let mut sum = 0.0;
+        let mut zeros = 0_usize;
+        for rank in &self.registers {
+            sum += 2f64.powi(-i32::from(*rank));
+            if *rank == 0 {
+                zeros += 1;
+            }
+        }
+        let mut estimate = alpha * m * m / sum;
+        // Linear counting is more accurate for small cardinalities.
+        if estimate <= 2.5 * m && zeros > 0 {
+            estimate = m * (m / f64::cast_lossy(zeros)).ln();
+        }
+        i64::cast_lossy(estimate.round())
+    }
+
+    /// Decodes a sketch from its `bytea` representation.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Sketch, InvalidSketchError> {
+        let (precision, registers) = match bytes.split_first() {
+            Some((precision, registers))
+                if (MIN_PRECISION..=MAX_PRECISION).contains(precision)
+                    && registers.len() == 1 << precision =>
+            {
+                (*precision, registers)
+            }
+            _ => return Err(InvalidSketchError),
+        };
+        if registers
+            .iter()
+            .any(|rank| u32::from(*rank) > 65 - u32::from(precision))
+        {
+            return Err(InvalidSketchError);
+        }
+        Ok(Sketch {
+            precision,
+            registers: registers.to_vec(),
+        })
+    }
+
+    /// Encodes the sketch as a `bytea` value.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(1 + self.registers.len());
+        bytes.push(self.precision);
+        bytes.extend(&self.registers);
+        bytes
+    }
+}
+
+/// The error returned when decoding a malformed sketch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidSketchError;
+
+impl fmt::Display for InvalidSketchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid HyperLogLog sketch")
+    }
+}
+
+impl std::error::Error for InvalidSketchError {}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::Datum;
+
+    use super::*;
+
+    fn sketch_of(values: impl IntoIterator<Item = i64>, precision: u8) -> Sketch {
+        let mut sketch = Sketch::new(precision);
+        for value in values {
+            sketch.insert(register_of(Datum::Int64(value), precision));
+        }
+        sketch
+    }
+
+    #[mz_ore::test]
+    fn test_estimate() {
+        assert_eq!(Sketch::new(DEFAULT_PRECISION).estimate(), 0);
+        assert_eq!(sketch_of([1, 1, 1], DEFAULT_PRECISION).estimate(), 1);
+        for n in [100, 10_000, 100_000] {
+            let estimate = sketch_of(0..n, DEFAULT_PRECISION).estimate();
+            let error = f64::cast_lossy((estimate - n).abs()) / f64::cast_lossy(n);
+            assert!(error < 0.05, "estimated {estimate} for {n}");
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_merge() {
+        let mut a = sketch_of(0..5_000, 12);
+        let b = sketch_of(2_500..10_000, 10);
+        a.merge(&b);
+        assert_eq!(a.precision(), 10);
+        assert_eq!(a, sketch_of(0..10_000, 10));
+    }
+
+    #[mz_ore::test]
+    fn test_bytes() {
+        let sketch = sketch_of(0..1_000, 8);
+        let bytes = sketch.to_bytes();
+        assert_eq!(bytes.len(), 257);
+        assert_eq!(Sketch::from_bytes(&bytes), Ok(sketch));
+        assert_eq!(Sketch::from_bytes(&[]), Err(InvalidSketchError));
+        assert_eq!(Sketch::from_bytes(&bytes[1..]), Err(InvalidSketchError));
+    }
+}
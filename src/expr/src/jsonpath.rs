@@ -0,0 +1,1557 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! The SQL/JSON path language.
+//!
+//! A path is evaluated against a `jsonb` value and produces a sequence of
+//! `jsonb` items. This module implements the dialect accepted by PostgreSQL's
+//! `jsonpath` type, with the exception of the datetime methods:
+//!
+//!   * `lax` and `strict` modes,
+//!   * the `$` root, `@` current item, `$name` variable and `last` subscript
+//!     references,
+//!   * member (`.key`, `."key"`, `.*`), element (`[n]`, `[n to m]`, `[*]`)
+//!     and recursive (`.**`, `.**{n to m}`) accessors,
+//!   * filters (`? (...)`) with comparisons, `&&`, `||`, `!`, `exists`,
+//!   `like_regex`, `starts with` and `is unknown`,
+//!   * arithmetic (`+`, `-`, `*`, `/`, `%`), and
+//!   * the `type`, `size`, `double`, `ceiling`, `floor`, `abs` and `keyvalue`
+//!     item methods.
+//!
+//! In lax mode, structural errors such as accessing a missing key are
+//! ignored and arrays are unwrapped or wrapped as needed to match the
+//! accessor. In strict mode they are reported as errors. Errors raised while
+//! evaluating a filter's predicate never escape the filter; they make the
+//! predicate unknown instead.
+//!
+//! Paths are passed as `text`. Constant paths are parsed once, when the
+//! function call is planned; other paths are parsed each time they are
+//! evaluated.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use dec::Rounding;
+use mz_lowertest::{MzReflect, ReflectedTypeInfo};
+use mz_ore::cast::CastFrom;
+use mz_proto::{RustType, TryFromProtoError};
+use mz_repr::adt::numeric::{self, Numeric};
+use mz_repr::{strconv, Datum, DatumMap, RowArena};
+use proptest::prelude::any;
+use proptest::prop_compose;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::scalar::ProtoJsonbPath;
+use crate::EvalError;
+
+/// A parsed SQL/JSON path.
+///
+/// Like [`mz_repr::adt::regex::Regex`], two paths are considered equal iff
+/// their text is identical, and the ordering and hash are based on the text
+/// too. Paths are serialized as their text and parsed again when deserialized.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct JsonPath {
+    text: String,
+    strict: bool,
+    expr: Expr,
+}
+
+impl PartialEq for JsonPath {
+    fn eq(&self, other: &JsonPath) -> bool {
+        self.text == other.text
+    }
+}
+
+impl Eq for JsonPath {}
+
+impl PartialOrd for JsonPath {
+    fn partial_cmp(&self, other: &JsonPath) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for JsonPath {
+    fn cmp(&self, other: &JsonPath) -> Ordering {
+        self.text.cmp(&other.text)
+    }
+}
+
+impl Hash for JsonPath {
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        self.text.hash(hasher);
+    }
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl From<JsonPath> for String {
+    fn from(path: JsonPath) -> String {
+        path.text
+    }
+}
+
+impl TryFrom<String> for JsonPath {
+    type Error = EvalError;
+
+    fn try_from(text: String) -> Result<JsonPath, EvalError> {
+        JsonPath::parse(&text)
+    }
+}
+
+impl RustType<ProtoJsonbPath> for Option<JsonPath> {
+    fn into_proto(&self) -> ProtoJsonbPath {
+        ProtoJsonbPath {
+            path: self.as_ref().map(|path| path.text.clone()),
+        }
+    }
+
+    fn from_proto(proto: ProtoJsonbPath) -> Result<Self, TryFromProtoError> {
+        proto
+            .path
+            .map(|path| JsonPath::parse(&path))
+            .transpose()
+            .map_err(|e| TryFromProtoError::JsonPathDeserializationError(e.to_string()))
+    }
+}
+
+/// Paths are represented as strings, which carry no type information.
+impl MzReflect for JsonPath {
+    fn add_to_reflected_type_info(_rti: &mut ReflectedTypeInfo) {}
+}
+
+prop_compose! {
+    /// Generates the path of a SQL/JSON path function call, if it was parsed
+    /// when the call was planned.
+    pub fn any_json_path()
+                (strict in any::<bool>(), key in "k[a-z]{0,2}", index in 0..3u32,
+                 parsed in any::<bool>())
+                -> Option<JsonPath> {
+        let mode = if strict { "strict" } else { "lax" };
+        let text = format!("{mode} $.{key}[{index}]");
+        parsed.then(|| JsonPath::parse(&text).unwrap())
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Root,
+    Current,
+    Last,
+    Variable(String),
+    Literal(Literal),
+    Member(Box<Expr>, String),
+    WildcardMember(Box<Expr>),
+    Element(Box<Expr>, Vec<Subscript>),
+    WildcardElement(Box<Expr>),
+    Descendants {
+        expr: Box<Expr>,
+        min: u32,
+        max: Option<u32>,
+    },
+    Filter(Box<Expr>, Box<Expr>),
+    Method(Box<Expr>, Method),
+    Unary(UnaryOp, Box<Expr>),
+    Arith(ArithOp, Box<Expr>, Box<Expr>),
+    Compare(CmpOp, Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Exists(Box<Expr>),
+    IsUnknown(Box<Expr>),
+    StartsWith(Box<Expr>, Box<Expr>),
+    LikeRegex(Box<Expr>, Regex),
+}
+
+impl Expr {
+    /// Reports whether the expression is a predicate, which evaluates to
+    /// true, false or unknown rather than to a sequence of items.
+    fn is_predicate(&self) -> bool {
+        matches!(
+            self,
+            Expr::Compare(..)
+                | Expr::And(..)
+                | Expr::Or(..)
+                | Expr::Not(..)
+                | Expr::Exists(..)
+                | Expr::IsUnknown(..)
+                | Expr::StartsWith(..)
+                | Expr::LikeRegex(..)
+        )
+    }
+
+    /// Calls `f` on the name of each variable referenced by the expression.
+    fn visit_variables<'e>(&'e self, f: &mut impl FnMut(&'e str)) {
+        match self {
+            Expr::Root | Expr::Current | Expr::Last | Expr::Literal(_) => {}
+            Expr::Variable(name) => f(name.as_str()),
+            Expr::Member(expr, _)
+            | Expr::WildcardMember(expr)
+            | Expr::WildcardElement(expr)
+            | Expr::Descendants { expr, .. }
+            | Expr::Method(expr, _)
+            | Expr::Unary(_, expr)
+            | Expr::Not(expr)
+            | Expr::Exists(expr)
+            | Expr::IsUnknown(expr)
+            | Expr::LikeRegex(expr, _) => expr.visit_variables(f),
+            Expr::Element(expr, subscripts) => {
+                expr.visit_variables(f);
+                for subscript in subscripts {
+                    match subscript {
+                        Subscript::Index(index) => index.visit_variables(f),
+                        Subscript::Range(from, to) => {
+                            from.visit_variables(f);
+                            to.visit_variables(f);
+                        }
+                    }
+                }
+            }
+            Expr::Filter(left, right)
+            | Expr::Arith(_, left, right)
+            | Expr::Compare(_, left, right)
+            | Expr::And(left, right)
+            | Expr::Or(left, right)
+            | Expr::StartsWith(left, right) => {
+                left.visit_variables(f);
+                right.visit_variables(f);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Literal {
+    Null,
+    Bool(bool),
+    Number(Numeric),
+    String(String),
+}
+
+#[derive(Debug, Clone)]
+enum Subscript {
+    Index(Expr),
+    Range(Expr, Expr),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Method {
+    Type,
+    Size,
+    Double,
+    Ceiling,
+    Floor,
+    Abs,
+    KeyValue,
+}
+
+impl Method {
+    fn from_name(name: &str) -> Option<Method> {
+        match name {
+            "type" => Some(Method::Type),
+            "size" => Some(Method::Size),
+            "double" => Some(Method::Double),
+            "ceiling" => Some(Method::Ceiling),
+            "floor" => Some(Method::Floor),
+            "abs" => Some(Method::Abs),
+            "keyvalue" => Some(Method::KeyValue),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Method::Type => ".type()",
+            Method::Size => ".size()",
+            Method::Double => ".double()",
+            Method::Ceiling => ".ceiling()",
+            Method::Floor => ".floor()",
+            Method::Abs => ".abs()",
+            Method::KeyValue => ".keyvalue()",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum UnaryOp {
+    Plus,
+    Minus,
+}
+
+impl fmt::Display for UnaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            UnaryOp::Plus => "+",
+            UnaryOp::Minus => "-",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ArithOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+}
+
+impl fmt::Display for ArithOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ArithOp::Add => "+",
+            ArithOp::Sub => "-",
+            ArithOp::Mul => "*",
+            ArithOp::Div => "/",
+            ArithOp::Mod => "%",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CmpOp {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+impl CmpOp {
+    fn test(&self, ordering: Ordering) -> bool {
+        match self {
+            CmpOp::Eq => ordering == Ordering::Equal,
+            CmpOp::NotEq => ordering != Ordering::Equal,
+            CmpOp::Lt => ordering == Ordering::Less,
+            CmpOp::LtEq => ordering != Ordering::Greater,
+            CmpOp::Gt => ordering == Ordering::Greater,
+            CmpOp::GtEq => ordering != Ordering::Less,
+        }
+    }
+}
+
+impl JsonPath {
+    /// Parses a path from its textual representation.
+    pub fn parse(s: &str) -> Result<JsonPath, EvalError> {
+        let (strict, expr) = Parser::new(s)?.parse_path()?;
+        Ok(JsonPath {
+            text: s.to_owned(),
+            strict,
+            expr,
+        })
+    }
+
+    /// Evaluates the path against `target`, resolving variables from `vars`,
+    /// which must be a `jsonb` object.
+    ///
+    /// Returns `Ok(None)` if evaluation failed and `silent` is set, so that
+    /// each caller can decide how to represent the failure.
+    pub fn query<'a>(
+        &self,
+        target: Datum<'a>,
+        vars: Datum<'a>,
+        silent: bool,
+        temp_storage: &'a RowArena,
+    ) -> Result<Option<Vec<Datum<'a>>>, EvalError> {
+        let Datum::Map(vars) = vars else {
+            return Err(EvalError::JsonPathError(
+                "\"vars\" argument is not an object".into(),
+            ));
+        };
+        let mut missing = None;
+        self.expr.visit_variables(&mut |name| {
+            if missing.is_none() && !vars.iter().any(|(key, _)| key == name) {
+                missing = Some(name);
+            }
+        });
+        if let Some(name) = missing {
+            return Err(EvalError::JsonPathError(format!(
+                "could not find jsonpath variable \"{}\"",
+                name
+            )));
+        }
+
+        let evaluator = Evaluator {
+            strict: self.strict,
+            root: target,
+            vars,
+            temp_storage,
+        };
+        let mut items = vec![];
+        match evaluator.eval(&self.expr, target, None, &mut items) {
+            Ok(()) => Ok(Some(items)),
+            Err(_) if silent => Ok(None),
+            Err(e) => Err(EvalError::JsonPathError(e)),
+        }
+    }
+}
+
+/// Evaluates `path` against `target`, as described in [`JsonPath::query`].
+///
+/// If the path was not parsed when the function call was planned, it is
+/// parsed from `text`.
+pub fn query<'a>(
+    target: Datum<'a>,
+    path: Option<&JsonPath>,
+    text: &str,
+    vars: Datum<'a>,
+    silent: bool,
+    temp_storage: &'a RowArena,
+) -> Result<Option<Vec<Datum<'a>>>, EvalError> {
+    match path {
+        Some(path) => path.query(target, vars, silent, temp_storage),
+        None => JsonPath::parse(text)?.query(target, vars, silent, temp_storage),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Root,
+    Current,
+    Variable(String),
+    Ident(String),
+    String(String),
+    Number(Numeric),
+    Punct(&'static str),
+}
+
+const PUNCTUATION: &[&str] = &[
+    "**", "==", "!=", "<>", "<=", ">=", "&&", "||", ".", "[", "]", "(", ")", "{", "}", ",", "?",
+    "*", "+", "-", "/", "%", "<", ">", "!",
+];
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn is_ident_continue(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn syntax_error(near: Option<&str>) -> EvalError {
+    EvalError::JsonPathError(match near {
+        Some(near) => format!("syntax error at or near \"{}\" of jsonpath input", near),
+        None => "syntax error at end of jsonpath input".into(),
+    })
+}
+
+/// Splits `s` into tokens, each paired with the text it was lexed from.
+fn lex(s: &str) -> Result<Vec<(Token, &str)>, EvalError> {
+    let mut tokens = vec![];
+    let mut rest = s.trim_start();
+    while let Some(c) = rest.chars().next() {
+        let (token, len) = if c == '"' {
+            lex_string(rest)?
+        } else if c == '$' {
+            let name = &rest[1..];
+            if name.starts_with('"') {
+                let (token, len) = lex_string(name)?;
+                let Token::String(name) = token else {
+                    unreachable!()
+                };
+                (Token::Variable(name), len + 1)
+            } else {
+                let len = name.find(|c| !is_ident_continue(c)).unwrap_or(name.len());
+                if len == 0 {
+                    (Token::Root, 1)
+                } else {
+                    (Token::Variable(name[..len].into()), len + 1)
+                }
+            }
+        } else if c == '@' {
+            (Token::Current, 1)
+        } else if c.is_ascii_digit() {
+            lex_number(rest)?
+        } else if is_ident_start(c) {
+            let len = rest.find(|c| !is_ident_continue(c)).unwrap_or(rest.len());
+            (Token::Ident(rest[..len].into()), len)
+        } else if let Some(punct) = PUNCTUATION.iter().find(|p| rest.starts_with(**p)) {
+            (Token::Punct(*punct), punct.len())
+        } else {
+            return Err(syntax_error(Some(&rest[..c.len_utf8()])));
+        };
+        tokens.push((token, &rest[..len]));
+        rest = rest[len..].trim_start();
+    }
+    Ok(tokens)
+}
+
+fn lex_number(s: &str) -> Result<(Token, usize), EvalError> {
+    let digits = |from: usize| {
+        s[from..]
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(s.len(), |len| from + len)
+    };
+    let mut len = digits(0);
+    if s[len..].starts_with('.') && s[len + 1..].starts_with(|c: char| c.is_ascii_digit()) {
+        len = digits(len + 1);
+    }
+    if s[len..].starts_with(['e', 'E']) {
+        let exponent = s[len + 1..]
+            .strip_prefix(['+', '-'])
+            .map_or(len + 1, |_| len + 2);
+        if s[exponent..].starts_with(|c: char| c.is_ascii_digit()) {
+            len = digits(exponent);
+        }
+    }
+    if let Some(c) = s[len..].chars().next().filter(|c| is_ident_start(*c)) {
+        return Err(EvalError::JsonPathError(format!(
+            "trailing junk after numeric literal at or near \"{}\" of jsonpath input",
+            &s[..len + c.len_utf8()]
+        )));
+    }
+    match strconv::parse_numeric(&s[..len]) {
+        Ok(n) => Ok((Token::Number(n.0), len)),
+        Err(_) => Err(syntax_error(Some(&s[..len]))),
+    }
+}
+
+fn lex_string(s: &str) -> Result<(Token, usize), EvalError> {
+    let mut out = String::new();
+    let mut chars = s.char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((Token::String(out), i + 1)),
+            '\\' => {
+                let c = match chars.next() {
+                    Some((_, 'b')) => '\u{8}',
+                    Some((_, 'f')) => '\u{c}',
+                    Some((_, 'n')) => '\n',
+                    Some((_, 'r')) => '\r',
+                    Some((_, 't')) => '\t',
+                    Some((_, 'v')) => '\u{b}',
+                    Some((j, 'u')) => {
+                        let hex = s.get(j + 1..j + 5).unwrap_or("");
+                        let c = u32::from_str_radix(hex, 16)
+                            .ok()
+                            .filter(|_| hex.len() == 4)
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| {
+                                EvalError::JsonPathError(format!(
+                                    "invalid Unicode escape sequence at or near \"\\u{}\" of \
+                                     jsonpath input",
+                                    hex
+                                ))
+                            })?;
+                        for _ in 0..4 {
+                            chars.next();
+                        }
+                        c
+                    }
+                    Some((_, c)) => c,
+                    None => break,
+                };
+                out.push(c);
+            }
+            c => out.push(c),
+        }
+    }
+    Err(EvalError::JsonPathError(
+        "unterminated quoted string in jsonpath input".into(),
+    ))
+}
+
+struct Parser<'s> {
+    tokens: Vec<(Token, &'s str)>,
+    pos: usize,
+    /// How many filters enclose the current position, in which `@` is valid.
+    filter_depth: usize,
+    /// How many array subscripts enclose the current position, in which
+    /// `last` is valid.
+    subscript_depth: usize,
+}
+
+impl<'s> Parser<'s> {
+    fn new(s: &'s str) -> Result<Parser<'s>, EvalError> {
+        Ok(Parser {
+            tokens: lex(s)?,
+            pos: 0,
+            filter_depth: 0,
+            subscript_depth: 0,
+        })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn error(&self) -> EvalError {
+        syntax_error(self.tokens.get(self.pos).map(|(_, text)| *text))
+    }
+
+    fn consume_punct(&mut self, punct: &'static str) -> bool {
+        if self.peek() == Some(&Token::Punct(punct)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn consume_keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Ident(ident)) if ident == keyword => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn expect_punct(&mut self, punct: &'static str) -> Result<(), EvalError> {
+        if self.consume_punct(punct) {
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), EvalError> {
+        if self.consume_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    /// Parses a path into its mode, which is strict if the flag is set, and
+    /// its expression.
+    fn parse_path(mut self) -> Result<(bool, Expr), EvalError> {
+        let strict = if self.consume_keyword("strict") {
+            true
+        } else {
+            self.consume_keyword("lax");
+            false
+        };
+        let expr = self.parse_or()?;
+        if self.pos < self.tokens.len() {
+            return Err(self.error());
+        }
+        Ok((strict, expr))
+    }
+
+    /// Parses a predicate, with the error reported at the position where it
+    /// started if the expression turns out not to be one.
+    fn parse_predicate(
+        &mut self,
+        parse: fn(&mut Self) -> Result<Expr, EvalError>,
+    ) -> Result<Expr, EvalError> {
+        let start = self.pos;
+        let expr = parse(self)?;
+        if expr.is_predicate() {
+            Ok(expr)
+        } else {
+            self.pos = start;
+            Err(self.error())
+        }
+    }
+
+    /// Parses a value expression, with the error reported at the position
+    /// where it started if the expression turns out to be a predicate.
+    fn parse_value(
+        &mut self,
+        parse: fn(&mut Self) -> Result<Expr, EvalError>,
+    ) -> Result<Expr, EvalError> {
+        let start = self.pos;
+        let expr = parse(self)?;
+        if expr.is_predicate() {
+            self.pos = start;
+            Err(self.error())
+        } else {
+            Ok(expr)
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, EvalError> {
+        let mut expr = self.parse_and()?;
+        while self.peek() == Some(&Token::Punct("||")) {
+            if !expr.is_predicate() {
+                return Err(self.error());
+            }
+            self.pos += 1;
+            let right = self.parse_predicate(Self::parse_and)?;
+            expr = Expr::Or(Box::new(expr), Box::new(right));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, EvalError> {
+        let mut expr = self.parse_not()?;
+        while self.peek() == Some(&Token::Punct("&&")) {
+            if !expr.is_predicate() {
+                return Err(self.error());
+            }
+            self.pos += 1;
+            let right = self.parse_predicate(Self::parse_not)?;
+            expr = Expr::And(Box::new(expr), Box::new(right));
+        }
+        Ok(expr)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, EvalError> {
+        if self.consume_punct("!") {
+            let expr = self.parse_predicate(Self::parse_not)?;
+            Ok(Expr::Not(Box::new(expr)))
+        } else {
+            self.parse_comparison()
+        }
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, EvalError> {
+        let expr = self.parse_additive()?;
+        let op = match self.peek() {
+            Some(Token::Punct("==")) => CmpOp::Eq,
+            Some(Token::Punct("!=" | "<>")) => CmpOp::NotEq,
+            Some(Token::Punct("<")) => CmpOp::Lt,
+            Some(Token::Punct("<=")) => CmpOp::LtEq,
+            Some(Token::Punct(">")) => CmpOp::Gt,
+            Some(Token::Punct(">=")) => CmpOp::GtEq,
+            Some(Token::Ident(ident)) if ident == "is" => {
+                if !expr.is_predicate() {
+                    return Err(self.error());
+                }
+                self.pos += 1;
+                self.expect_keyword("unknown")?;
+                return Ok(Expr::IsUnknown(Box::new(expr)));
+            }
+            Some(Token::Ident(ident)) if ident == "starts" => {
+                if expr.is_predicate() {
+                    return Err(self.error());
+                }
+                self.pos += 1;
+                self.expect_keyword("with")?;
+                let prefix = match self.next() {
+                    Some(Token::String(s)) => Expr::Literal(Literal::String(s)),
+                    Some(Token::Variable(name)) => Expr::Variable(name),
+                    _ => {
+                        self.pos -= 1;
+                        return Err(self.error());
+                    }
+                };
+                return Ok(Expr::StartsWith(Box::new(expr), Box::new(prefix)));
+            }
+            Some(Token::Ident(ident)) if ident == "like_regex" => {
+                if expr.is_predicate() {
+                    return Err(self.error());
+                }
+                self.pos += 1;
+                let regex = self.parse_regex()?;
+                return Ok(Expr::LikeRegex(Box::new(expr), regex));
+            }
+            _ => return Ok(expr),
+        };
+        if expr.is_predicate() {
+            return Err(self.error());
+        }
+        self.pos += 1;
+        let right = self.parse_value(Self::parse_additive)?;
+        Ok(Expr::Compare(op, Box::new(expr), Box::new(right)))
+    }
+
+    fn parse_regex(&mut self) -> Result<Regex, EvalError> {
+        let Some(Token::String(pattern)) = self.peek().cloned() else {
+            return Err(self.error());
+        };
+        self.pos += 1;
+        let mut builder = RegexBuilder::new(&pattern);
+        if self.consume_keyword("flag") {
+            let Some(Token::String(flags)) = self.peek().cloned() else {
+                return Err(self.error());
+            };
+            self.pos += 1;
+            let mut literal = false;
+            for flag in flags.chars() {
+                match flag {
+                    'i' => {
+                        builder.case_insensitive(true);
+                    }
+                    's' => {
+                        builder.dot_matches_new_line(true);
+                    }
+                    'm' => {
+                        builder.multi_line(true);
+                    }
+                    'x' => {
+                        builder.ignore_whitespace(true);
+                    }
+                    'q' => literal = true,
+                    _ => {
+                        return Err(EvalError::JsonPathError(format!(
+                            "unrecognized flag character \"{}\" in LIKE_REGEX predicate",
+                            flag
+                        )))
+                    }
+                }
+            }
+            // The `q` flag quotes the pattern, which leaves only the `i` flag
+            // meaningful.
+            if literal {
+                builder = RegexBuilder::new(&regex::escape(&pattern));
+                builder.case_insensitive(flags.contains('i'));
+            }
+        }
+        builder
+            .build()
+            .map_err(|e| EvalError::JsonPathError(format!("invalid regular expression: {}", e)))
+    }
+
+    fn parse_additive(&mut self) -> Result<Expr, EvalError> {
+        let mut expr = self.parse_multiplicative()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Punct("+")) => ArithOp::Add,
+                Some(Token::Punct("-")) => ArithOp::Sub,
+                _ => return Ok(expr),
+            };
+            if expr.is_predicate() {
+                return Err(self.error());
+            }
+            self.pos += 1;
+            let right = self.parse_value(Self::parse_multiplicative)?;
+            expr = Expr::Arith(op, Box::new(expr), Box::new(right));
+        }
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Expr, EvalError> {
+        let mut expr = self.parse_unary()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Punct("*")) => ArithOp::Mul,
+                Some(Token::Punct("/")) => ArithOp::Div,
+                Some(Token::Punct("%")) => ArithOp::Mod,
+                _ => return Ok(expr),
+            };
+            if expr.is_predicate() {
+                return Err(self.error());
+            }
+            self.pos += 1;
+            let right = self.parse_value(Self::parse_unary)?;
+            expr = Expr::Arith(op, Box::new(expr), Box::new(right));
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, EvalError> {
+        let op = if self.consume_punct("+") {
+            UnaryOp::Plus
+        } else if self.consume_punct("-") {
+            UnaryOp::Minus
+        } else {
+            return self.parse_accessors();
+        };
+        let expr = self.parse_value(Self::parse_unary)?;
+        Ok(Expr::Unary(op, Box::new(expr)))
+    }
+
+    fn parse_accessors(&mut self) -> Result<Expr, EvalError> {
+        let mut expr = self.parse_primary()?;
+        loop {
+            match self.peek() {
+                Some(Token::Punct(".")) => {
+                    self.pos += 1;
+                    expr = self.parse_dot_accessor(expr)?;
+                }
+                Some(Token::Punct("[")) => {
+                    self.pos += 1;
+                    expr = self.parse_subscripts(expr)?;
+                }
+                Some(Token::Punct("?")) => {
+                    self.pos += 1;
+                    self.expect_punct("(")?;
+                    self.filter_depth += 1;
+                    let predicate = self.parse_predicate(Self::parse_or)?;
+                    self.filter_depth -= 1;
+                    self.expect_punct(")")?;
+                    expr = Expr::Filter(Box::new(expr), Box::new(predicate));
+                }
+                _ => return Ok(expr),
+            }
+            // Accessors cannot be applied to predicates, but we only know
+            // that an expression is one once it has been parsed.
+            if let Expr::Member(inner, _)
+            | Expr::WildcardMember(inner)
+            | Expr::Element(inner, _)
+            | Expr::WildcardElement(inner)
+            | Expr::Descendants { expr: inner, .. }
+            | Expr::Filter(inner, _)
+            | Expr::Method(inner, _) = &expr
+            {
+                if inner.is_predicate() {
+                    return Err(self.error());
+                }
+            }
+        }
+    }
+
+    fn parse_dot_accessor(&mut self, expr: Expr) -> Result<Expr, EvalError> {
+        let expr = Box::new(expr);
+        match self.next() {
+            Some(Token::Punct("*")) => Ok(Expr::WildcardMember(expr)),
+            Some(Token::Punct("**")) => {
+                let (min, max) = if self.consume_punct("{") {
+                    let Some(min) = self.parse_level()? else {
+                        self.pos -= 1;
+                        return Err(self.error());
+                    };
+                    let max = if self.consume_keyword("to") {
+                        self.parse_level()?
+                    } else {
+                        Some(min)
+                    };
+                    self.expect_punct("}")?;
+                    (min, max)
+                } else {
+                    (0, None)
+                };
+                Ok(Expr::Descendants { expr, min, max })
+            }
+            Some(Token::String(key)) => Ok(Expr::Member(expr, key)),
+            Some(Token::Ident(name)) => {
+                if !self.consume_punct("(") {
+                    return Ok(Expr::Member(expr, name));
+                }
+                match Method::from_name(&name) {
+                    Some(method) => {
+                        self.expect_punct(")")?;
+                        Ok(Expr::Method(expr, method))
+                    }
+                    None => {
+                        self.pos -= 2;
+                        Err(self.error())
+                    }
+                }
+            }
+            _ => {
+                self.pos -= 1;
+                Err(self.error())
+            }
+        }
+    }
+
+    /// Parses a level of a recursive accessor, where `None` denotes `last`,
+    /// the deepest level.
+    fn parse_level(&mut self) -> Result<Option<u32>, EvalError> {
+        match self.next() {
+            Some(Token::Ident(ident)) if ident == "last" => Ok(None),
+            Some(Token::Number(n)) => {
+                let mut cx = numeric::cx_datum();
+                match cx.try_into_u32(n) {
+                    Ok(level) if n.exponent() >= 0 => Ok(Some(level)),
+                    _ => {
+                        self.pos -= 1;
+                        Err(self.error())
+                    }
+                }
+            }
+            _ => {
+                self.pos -= 1;
+                Err(self.error())
+            }
+        }
+    }
+
+    fn parse_subscripts(&mut self, expr: Expr) -> Result<Expr, EvalError> {
+        if self.consume_punct("*") {
+            self.expect_punct("]")?;
+            return Ok(Expr::WildcardElement(Box::new(expr)));
+        }
+        self.subscript_depth += 1;
+        let mut subscripts = vec![];
+        loop {
+            let from = self.parse_value(Self::parse_additive)?;
+            if self.consume_keyword("to") {
+                let to = self.parse_value(Self::parse_additive)?;
+                subscripts.push(Subscript::Range(from, to));
+            } else {
+                subscripts.push(Subscript::Index(from));
+            }
+            if !self.consume_punct(",") {
+                break;
+            }
+        }
+        self.subscript_depth -= 1;
+        self.expect_punct("]")?;
+        Ok(Expr::Element(Box::new(expr), subscripts))
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, EvalError> {
+        let Some(token) = self.next() else {
+            return Err(self.error());
+        };
+        match token {
+            Token::Root => Ok(Expr::Root),
+            Token::Current if self.filter_depth > 0 => Ok(Expr::Current),
+            Token::Current => Err(EvalError::JsonPathError(
+                "@ is not allowed in root expressions".into(),
+            )),
+            Token::Variable(name) => Ok(Expr::Variable(name)),
+            Token::String(s) => Ok(Expr::Literal(Literal::String(s))),
+            Token::Number(n) => Ok(Expr::Literal(Literal::Number(n))),
+            Token::Ident(ident) => match ident.as_str() {
+                "null" => Ok(Expr::Literal(Literal::Null)),
+                "true" => Ok(Expr::Literal(Literal::Bool(true))),
+                "false" => Ok(Expr::Literal(Literal::Bool(false))),
+                "last" if self.subscript_depth > 0 => Ok(Expr::Last),
+                "last" => Err(EvalError::JsonPathError(
+                    "LAST is allowed only in array subscripts".into(),
+                )),
+                "exists" => {
+                    self.expect_punct("(")?;
+                    let expr = self.parse_value(Self::parse_or)?;
+                    self.expect_punct(")")?;
+                    Ok(Expr::Exists(Box::new(expr)))
+                }
+                _ => {
+                    self.pos -= 1;
+                    Err(self.error())
+                }
+            },
+            Token::Punct("(") => {
+                let expr = self.parse_or()?;
+                self.expect_punct(")")?;
+                Ok(expr)
+            }
+            Token::Punct(_) => {
+                self.pos -= 1;
+                Err(self.error())
+            }
+        }
+    }
+}
+
+/// The result of a predicate, following SQL's three-valued logic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Truth {
+    True,
+    False,
+    Unknown,
+}
+
+impl From<bool> for Truth {
+    fn from(b: bool) -> Truth {
+        if b {
+            Truth::True
+        } else {
+            Truth::False
+        }
+    }
+}
+
+/// Errors raised during evaluation, which are suppressed by the `silent`
+/// argument and within filters.
+type EvalResult<T> = Result<T, String>;
+
+struct Evaluator<'a> {
+    strict: bool,
+    root: Datum<'a>,
+    vars: DatumMap<'a>,
+    temp_storage: &'a RowArena,
+}
+
+impl<'a> Evaluator<'a> {
+    /// Evaluates `expr` with `current` bound to `@` and `last` to the index
+    /// of the last element of the innermost enclosing subscripted array,
+    /// appending the resulting items to `out`.
+    fn eval(
+        &self,
+        expr: &Expr,
+        current: Datum<'a>,
+        last: Option<i64>,
+        out: &mut Vec<Datum<'a>>,
+    ) -> EvalResult<()> {
+        match expr {
+            Expr::Root => out.push(self.root),
+            Expr::Current => out.push(current),
+            Expr::Last => {
+                let last = last.ok_or("LAST is allowed only in array subscripts")?;
+                out.push(Datum::from(Numeric::from(last)));
+            }
+            Expr::Variable(name) => {
+                let (_, value) = self
+                    .vars
+                    .iter()
+                    .find(|(key, _)| *key == name.as_str())
+                    .ok_or_else(|| format!("could not find jsonpath variable \"{}\"", name))?;
+                out.push(value);
+            }
+            Expr::Literal(literal) => out.push(match literal {
+                Literal::Null => Datum::JsonNull,
+                Literal::Bool(b) => Datum::from(*b),
+                Literal::Number(n) => Datum::from(*n),
+                Literal::String(s) => Datum::String(self.temp_storage.push_string(s.clone())),
+            }),
+            Expr::Member(expr, key) => {
+                for item in self.eval_unwrapped(expr, current, last)? {
+                    match item {
+                        Datum::Map(map) => match map.iter().find(|(k, _)| *k == key.as_str()) {
+                            Some((_, value)) => out.push(value),
+                            None if self.strict => {
+                                return Err(format!("JSON object does not contain key \"{}\"", key))
+                            }
+                            None => {}
+                        },
+                        _ if self.strict => {
+                            return Err(
+                                "jsonpath member accessor can only be applied to an object".into()
+                            )
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Expr::WildcardMember(expr) => {
+                for item in self.eval_unwrapped(expr, current, last)? {
+                    match item {
+                        Datum::Map(map) => out.extend(map.iter().map(|(_, value)| value)),
+                        _ if self.strict => {
+                            return Err("jsonpath wildcard member accessor can only be applied \
+                                        to an object"
+                                .into())
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Expr::Element(expr, subscripts) => {
+                for item in self.eval_items(expr, current, last)? {
+                    let elements: Vec<_> = match item {
+                        Datum::List(list) => list.iter().collect(),
+                        _ if self.strict => {
+                            return Err(
+                                "jsonpath array accessor can only be applied to an array".into()
+                            )
+                        }
+                        item => vec![item],
+                    };
+                    let len = i64::try_from(elements.len()).expect("array length fits in i64");
+                    let last = Some(len - 1);
+                    for subscript in subscripts {
+                        let (from, to) = match subscript {
+                            Subscript::Index(index) => {
+                                let index = self.eval_subscript(index, current, last)?;
+                                (index, index)
+                            }
+                            Subscript::Range(from, to) => (
+                                self.eval_subscript(from, current, last)?,
+                                self.eval_subscript(to, current, last)?,
+                            ),
+                        };
+                        if self.strict && (from < 0 || from > to || to >= len) {
+                            return Err("jsonpath array subscript is out of bounds".into());
+                        }
+                        let from = from.max(0);
+                        let to = to.min(len - 1);
+                        if from <= to {
+                            let from = usize::try_from(from).expect("clamped to zero");
+                            let to = usize::try_from(to).expect("from is non-negative");
+                            out.extend(&elements[from..=to]);
+                        }
+                    }
+                }
+            }
+            Expr::WildcardElement(expr) => {
+                for item in self.eval_items(expr, current, last)? {
+                    match item {
+                        Datum::List(list) => out.extend(list.iter()),
+                        _ if self.strict => {
+                            return Err("jsonpath wildcard array accessor can only be applied \
+                                        to an array"
+                                .into())
+                        }
+                        item => out.push(item),
+                    }
+                }
+            }
+            Expr::Descendants { expr, min, max } => {
+                fn descend<'a>(
+                    item: Datum<'a>,
+                    level: u32,
+                    min: u32,
+                    max: Option<u32>,
+                    out: &mut Vec<Datum<'a>>,
+                ) {
+                    if level >= min {
+                        out.push(item);
+                    }
+                    if let Some(max) = max {
+                        if level >= max {
+                            return;
+                        }
+                    }
+                    match item {
+                        Datum::List(list) => {
+                            for element in list.iter() {
+                                descend(element, level + 1, min, max, out);
+                            }
+                        }
+                        Datum::Map(map) => {
+                            for (_, value) in map.iter() {
+                                descend(value, level + 1, min, max, out);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                for item in self.eval_items(expr, current, last)? {
+                    descend(item, 0, *min, *max, out);
+                }
+            }
+            Expr::Filter(expr, predicate) => {
+                for item in self.eval_unwrapped(expr, current, last)? {
+                    if self.eval_predicate(predicate, item, last) == Truth::True {
+                        out.push(item);
+                    }
+                }
+            }
+            Expr::Method(expr, method) => {
+                let items = match method {
+                    Method::Type | Method::Size => self.eval_items(expr, current, last)?,
+                    _ => self.eval_unwrapped(expr, current, last)?,
+                };
+                for item in items {
+                    self.eval_method(*method, item, out)?;
+                }
+            }
+            Expr::Unary(op, expr) => {
+                for item in self.eval_unwrapped(expr, current, last)? {
+                    let Datum::Numeric(n) = item else {
+                        return Err(format!(
+                            "operand of unary jsonpath operator {} is not a numeric value",
+                            op
+                        ));
+                    };
+                    let mut n = n.0;
+                    if let UnaryOp::Minus = op {
+                        numeric::cx_datum().neg(&mut n);
+                        numeric::munge_numeric(&mut n).map_err(|e| e.to_string())?;
+                    }
+                    out.push(Datum::from(n));
+                }
+            }
+            Expr::Arith(op, left, right) => {
+                let operand = |expr: &Expr, side: &str| -> EvalResult<Numeric> {
+                    match self.eval_unwrapped(expr, current, last)?.as_slice() {
+                        [Datum::Numeric(n)] => Ok(n.0),
+                        _ => Err(format!(
+                            "{} operand of jsonpath operator {} is not a single numeric value",
+                            side, op
+                        )),
+                    }
+                };
+                let left = operand(left, "left")?;
+                let right = operand(right, "right")?;
+                out.push(Datum::from(arith(*op, left, right)?));
+            }
+            Expr::Compare(..)
+            | Expr::And(..)
+            | Expr::Or(..)
+            | Expr::Not(..)
+            | Expr::Exists(..)
+            | Expr::IsUnknown(..)
+            | Expr::StartsWith(..)
+            | Expr::LikeRegex(..) => out.push(match self.eval_predicate(expr, current, last) {
+                Truth::True => Datum::True,
+                Truth::False => Datum::False,
+                Truth::Unknown => Datum::JsonNull,
+            }),
+        }
+        Ok(())
+    }
+
+    fn eval_items(
+        &self,
+        expr: &Expr,
+        current: Datum<'a>,
+        last: Option<i64>,
+    ) -> EvalResult<Vec<Datum<'a>>> {
+        let mut items = vec![];
+        self.eval(expr, current, last, &mut items)?;
+        Ok(items)
+    }
+
+    /// Like [`Evaluator::eval_items`], but in lax mode replaces each array in
+    /// the result with its elements.
+    fn eval_unwrapped(
+        &self,
+        expr: &Expr,
+        current: Datum<'a>,
+        last: Option<i64>,
+    ) -> EvalResult<Vec<Datum<'a>>> {
+        let items = self.eval_items(expr, current, last)?;
+        if self.strict {
+            return Ok(items);
+        }
+        let mut unwrapped = vec![];
+        for item in items {
+            match item {
+                Datum::List(list) => unwrapped.extend(list.iter()),
+                item => unwrapped.push(item),
+            }
+        }
+        Ok(unwrapped)
+    }
+
+    fn eval_subscript(
+        &self,
+        expr: &Expr,
+        current: Datum<'a>,
+        last: Option<i64>,
+    ) -> EvalResult<i64> {
+        match self.eval_items(expr, current, last)?.as_slice() {
+            [Datum::Numeric(n)] => {
+                let mut n = n.0;
+                let mut cx = numeric::cx_datum();
+                cx.set_rounding(Rounding::Down);
+                cx.round(&mut n);
+                cx.try_into_i32(n)
+                    .map(i64::from)
+                    .map_err(|_| "jsonpath array subscript is out of integer range".into())
+            }
+            _ => Err("jsonpath array subscript is not a single numeric value".into()),
+        }
+    }
+
+    fn eval_method(
+        &self,
+        method: Method,
+        item: Datum<'a>,
+        out: &mut Vec<Datum<'a>>,
+    ) -> EvalResult<()> {
+        let numeric_only = || {
+            format!(
+                "jsonpath item method {} can only be applied to a numeric value",
+                method
+            )
+        };
+        match method {
+            Method::Type => out.push(Datum::String(match item {
+                Datum::JsonNull => "null",
+                Datum::True | Datum::False => "boolean",
+                Datum::Numeric(_) => "number",
+                Datum::String(_) => "string",
+                Datum::List(_) => "array",
+                Datum::Map(_) => "object",
+                _ => unreachable!("{item:?} is not a jsonb datum"),
+            })),
+            Method::Size => {
+                let size = match item {
+                    Datum::List(list) => list.iter().count(),
+                    _ if self.strict => {
+                        return Err(format!(
+                            "jsonpath item method {} can only be applied to an array",
+                            method
+                        ))
+                    }
+                    _ => 1,
+                };
+                out.push(Datum::from(Numeric::from(u64::cast_from(size))));
+            }
+            Method::Double => match item {
+                Datum::Numeric(_) => out.push(item),
+                Datum::String(s) => match strconv::parse_numeric(s) {
+                    Ok(n) if n.0.is_finite() => out.push(Datum::from(n.0)),
+                    _ => {
+                        return Err(format!(
+                            "string argument of jsonpath item method {} is not a valid \
+                             representation of a double precision number",
+                            method
+                        ))
+                    }
+                },
+                _ => {
+                    return Err(format!(
+                        "jsonpath item method {} can only be applied to a string or numeric value",
+                        method
+                    ))
+                }
+            },
+            Method::Ceiling | Method::Floor => {
+                let Datum::Numeric(n) = item else {
+                    return Err(numeric_only());
+                };
+                let mut n = n.0;
+                if n.exponent() < 0 {
+                    let mut cx = numeric::cx_datum();
+                    cx.set_rounding(match method {
+                        Method::Ceiling => Rounding::Ceiling,
+                        _ => Rounding::Floor,
+                    });
+                    cx.round(&mut n);
+                    numeric::munge_numeric(&mut n).map_err(|e| e.to_string())?;
+                }
+                out.push(Datum::from(n));
+            }
+            Method::Abs => {
+                let Datum::Numeric(n) = item else {
+                    return Err(numeric_only());
+                };
+                let mut n = n.0;
+                numeric::cx_datum().abs(&mut n);
+                out.push(Datum::from(n));
+            }
+            Method::KeyValue => {
+                let Datum::Map(map) = item else {
+                    return Err(format!(
+                        "jsonpath item method {} can only be applied to an object",
+                        method
+                    ));
+                };
+                for (key, value) in map.iter() {
+                    out.push(self.temp_storage.make_datum(|packer| {
+                        packer.push_dict([("key", Datum::String(key)), ("value", value)])
+                    }));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn eval_predicate(&self, expr: &Expr, current: Datum<'a>, last: Option<i64>) -> Truth {
+        match expr {
+            Expr::And(left, right) => match self.eval_predicate(left, current, last) {
+                Truth::False => Truth::False,
+                left => match (left, self.eval_predicate(right, current, last)) {
+                    (_, Truth::False) => Truth::False,
+                    (Truth::True, Truth::True) => Truth::True,
+                    _ => Truth::Unknown,
+                },
+            },
+            Expr::Or(left, right) => match self.eval_predicate(left, current, last) {
+                Truth::True => Truth::True,
+                left => match (left, self.eval_predicate(right, current, last)) {
+                    (_, Truth::True) => Truth::True,
+                    (Truth::False, Truth::False) => Truth::False,
+                    _ => Truth::Unknown,
+                },
+            },
+            Expr::Not(expr) => match self.eval_predicate(expr, current, last) {
+                Truth::True => Truth::False,
+                Truth::False => Truth::True,
+                Truth::Unknown => Truth::Unknown,
+            },
+            Expr::IsUnknown(expr) => {
+                Truth::from(self.eval_predicate(expr, current, last) == Truth::Unknown)
+            }
+            Expr::Exists(expr) => match self.eval_items(expr, current, last) {
+                Ok(items) => Truth::from(!items.is_empty()),
+                Err(_) => Truth::Unknown,
+            },
+            Expr::Compare(op, left, right) => {
+                let (Ok(left), Ok(right)) = (
+                    self.eval_unwrapped(left, current, last),
+                    self.eval_unwrapped(right, current, last),
+                ) else {
+                    return Truth::Unknown;
+                };
+                self.any(
+                    left.iter()
+                        .flat_map(|l| right.iter().map(move |r| compare(*op, *l, *r))),
+                )
+            }
+            Expr::StartsWith(expr, prefix) => {
+                let (Ok(items), Ok(prefix)) = (
+                    self.eval_unwrapped(expr, current, last),
+                    self.eval_unwrapped(prefix, current, last),
+                ) else {
+                    return Truth::Unknown;
+                };
+                let [Datum::String(prefix)] = prefix.as_slice() else {
+                    return Truth::Unknown;
+                };
+                self.any(items.into_iter().map(|item| match item {
+                    Datum::String(s) => Truth::from(s.starts_with(prefix)),
+                    _ => Truth::Unknown,
+                }))
+            }
+            Expr::LikeRegex(expr, regex) => {
+                let Ok(items) = self.eval_unwrapped(expr, current, last) else {
+                    return Truth::Unknown;
+                };
+                self.any(items.into_iter().map(|item| match item {
+                    Datum::String(s) => Truth::from(regex.is_match(s)),
+                    _ => Truth::Unknown,
+                }))
+            }
+            _ => unreachable!("parser only accepts predicates here"),
+        }
+    }
+
+    /// Combines the results of an existential predicate over each item or
+    /// pair of items. It is true if any result is true, except that in strict
+    /// mode any unknown result makes it unknown.
+    fn any(&self, results: impl Iterator<Item = Truth>) -> Truth {
+        let mut found = false;
+        let mut unknown = false;
+        for result in results {
+            match result {
+                Truth::True if self.strict => found = true,
+                Truth::True => return Truth::True,
+                Truth::Unknown if self.strict => return Truth::Unknown,
+                Truth::Unknown => unknown = true,
+                Truth::False => {}
+            }
+        }
+        if found {
+            Truth::True
+        } else if unknown {
+            Truth::Unknown
+        } else {
+            Truth::False
+        }
+    }
+}
+
+/// Compares two items. Only scalars of the same type are comparable, except
+/// that `null` equals only itself.
+fn compare(op: CmpOp, left: Datum, right: Datum) -> Truth {
+    let ordering = match (left, right) {
+        (Datum::JsonNull, Datum::JsonNull) => Ordering::Equal,
+        (Datum::JsonNull, _) | (_, Datum::JsonNull) => return Truth::from(op == CmpOp::NotEq),
+        (Datum::True | Datum::False, Datum::True | Datum::False) => {
+            left.unwrap_bool().cmp(&right.unwrap_bool())
+        }
+        (Datum::Numeric(left), Datum::Numeric(right)) => left.cmp(&right),
+        (Datum::String(left), Datum::String(right)) => left.cmp(right),
+        _ => return Truth::Unknown,
+    };
+    Truth::from(op.test(ordering))
+}
+
+fn arith(op: ArithOp, mut left: Numeric, right: Numeric) -> EvalResult<Numeric> {
+    let mut cx = numeric::cx_datum();
+    match op {
+        ArithOp::Add => cx.add(&mut left, &right),
+        ArithOp::Sub => cx.sub(&mut left, &right),
+        ArithOp::Mul => cx.mul(&mut left, &right),
+        ArithOp::Div | ArithOp::Mod if right.is_zero() => {
+            return Err(EvalError::DivisionByZero.to_string())
+        }
+        ArithOp::Div => cx.div(&mut left, &right),
+        ArithOp::Mod => cx.rem(&mut left, &right),
+    }
+    if cx.status().overflow() {
+        return Err(EvalError::FloatOverflow.to_string());
+    }
+    numeric::munge_numeric(&mut left).map_err(|e| e.to_string())?;
+    Ok(left)
+}
@@ -24,6 +24,8 @@ mod relation;
 mod scalar;
 
 pub mod explain;
+pub mod hll;
+pub mod jsonpath;
 pub mod refresh_schedule;
 pub mod virtual_syntax;
 pub mod visit;
@@ -36,16 +38,16 @@ pub use linear::{
     memoize_expr, MapFilterProject, ProtoMapFilterProject, ProtoMfpPlan, ProtoSafeMfpPlan,
 };
 pub use relation::func::{
-    AggregateFunc, AnalyzedRegex, CaptureGroupDesc, LagLeadType, NaiveOneByOneAggr, OneByOneAggr,
-    TableFunc,
+    interpolate_percentile, percentile_ranks, AggregateFunc, AnalyzedRegex, CaptureGroupDesc,
+    LagLeadType, NaiveOneByOneAggr, OneByOneAggr, TableFunc,
 };
 pub use relation::join_input_mapper::JoinInputMapper;
 pub use relation::{
     canonicalize, compare_columns, non_nullable_columns, AccessStrategy, AggregateExpr,
-    CollectionPlan, ColumnOrder, JoinImplementation, JoinInputCharacteristics, LetRecLimit,
-    MirRelationExpr, ProtoAggregateExpr, ProtoAggregateFunc, ProtoColumnOrder,
-    ProtoRowSetFinishing, ProtoTableFunc, RowSetFinishing, WindowFrame, WindowFrameBound,
-    WindowFrameUnits, RECURSION_LIMIT,
+    CollectionPlan, ColumnOrder, JoinHints, JoinImplementation, JoinImplementationHint,
+    JoinInputCharacteristics, LetRecLimit, MirRelationExpr, ProtoAggregateExpr, ProtoAggregateFunc,
+    ProtoColumnOrder, ProtoRowSetFinishing, ProtoTableFunc, RowSetFinishing, WindowFrame,
+    WindowFrameBound, WindowFrameUnits, RECURSION_LIMIT,
 };
 pub use scalar::func::{self, BinaryFunc, UnaryFunc, UnmaterializableFunc, VariadicFunc};
 pub use scalar::{
@@ -18,7 +18,7 @@ use chrono::{DateTime, NaiveDateTime, Utc};
 use dec::OrderedDecimal;
 use itertools::Itertools;
 use mz_lowertest::MzReflect;
-use mz_ore::cast::CastFrom;
+use mz_ore::cast::{CastFrom, CastLossy};
 
 use mz_ore::soft_assert_or_log;
 use mz_ore::str::separated;
@@ -39,6 +39,8 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 
 use crate::explain::{HumanizedExpr, HumanizerMode};
+use crate::hll::{self, Sketch};
+use crate::jsonpath::{self, JsonPath};
 use crate::relation::proto_aggregate_func::{self, ProtoColumnOrders};
 use crate::relation::proto_table_func::ProtoTabletizedScalar;
 use crate::relation::{
@@ -227,6 +229,270 @@ where
     Datum::String(temp_storage.push_string(s))
 }
 
+/// Pairs each distinct datum with the number of times it occurs.
+fn consolidate_datums<'a, I>(datums: I) -> Vec<(Datum<'a>, usize)>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let mut datums: Vec<_> = datums.into_iter().collect();
+    datums.sort();
+    datums
+        .into_iter()
+        .dedup_with_count()
+        .map(|(count, datum)| (datum, count))
+        .collect()
+}
+
+/// Returns the datum at the zero-based `rank` among `values`, which pair
+/// distinct datums with their multiplicities, when ranked in ascending order
+/// (or descending order if `desc`). `rank` must be less than the sum of the
+/// multiplicities.
+///
+/// This is a weighted quickselect: rather than expanding the multiplicities
+/// and sorting, it repeatedly partitions the distinct datums around their
+/// median, which takes time linear in the number of distinct datums.
+fn select_by_rank<'a>(
+    mut values: &mut [(Datum<'a>, usize)],
+    mut rank: usize,
+    desc: bool,
+) -> Datum<'a> {
+    let cmp = move |a: &(Datum<'a>, usize), b: &(Datum<'a>, usize)| {
+        if desc {
+            b.0.cmp(&a.0)
+        } else {
+            a.0.cmp(&b.0)
+        }
+    };
+    loop {
+        let mid = values.len() / 2;
+        let (lower, pivot, upper) = { values }.select_nth_unstable_by(mid, cmp);
+        let (pivot, pivot_count) = *pivot;
+        let lower_count: usize = lower.iter().map(|(_, count)| count).sum();
+        if rank < lower_count {
+            values = lower;
+        } else if rank < lower_count + pivot_count {
+            return pivot;
+        } else {
+            rank -= lower_count + pivot_count;
+            values = upper;
+        }
+    }
+}
+
+/// Returns the zero-based ranks of the values that determine the percentile at
+/// `fraction` of `total` values, when ranked in the order of the aggregate, and
+/// the weight of the second value. `percentile_cont` interpolates between the
+/// two values with [`interpolate_percentile`], while for `percentile_disc` the
+/// ranks coincide.
+pub fn percentile_ranks(cont: bool, total: usize, fraction: f64) -> (usize, usize, f64) {
+    if cont {
+        let position = fraction * f64::cast_lossy(total - 1);
+        let first_rank = position.floor();
+        (
+            usize::cast_lossy(first_rank),
+            usize::cast_lossy(position.ceil()),
+            position - first_rank,
+        )
+    } else {
+        let position = usize::cast_lossy((fraction * f64::cast_lossy(total)).ceil());
+        let rank = max(position, 1) - 1;
+        (rank, rank, 0.0)
+    }
+}
+
+/// Interpolates the continuous percentile between the values at the ranks
+/// returned by [`percentile_ranks`].
+pub fn interpolate_percentile(first: f64, second: f64, weight: f64) -> f64 {
+    first + (second - first) * weight
+}
+
+/// Computes the continuous percentile at `fraction` of `total` values by
+/// interpolating between the values at the adjacent ranks.
+fn percentile_cont<'a>(
+    values: &mut [(Datum<'a>, usize)],
+    total: usize,
+    fraction: f64,
+    desc: bool,
+) -> Datum<'a> {
+    let (first_rank, second_rank, weight) = percentile_ranks(true, total, fraction);
+    let first = select_by_rank(values, first_rank, desc).unwrap_float64();
+    let second = select_by_rank(values, second_rank, desc).unwrap_float64();
+    Datum::from(interpolate_percentile(first, second, weight))
+}
+
+/// Computes the discrete percentile at `fraction` of `total` values, which is
+/// the first value whose position in the ranking equals or exceeds `fraction`.
+fn percentile_disc<'a>(
+    values: &mut [(Datum<'a>, usize)],
+    total: usize,
+    fraction: f64,
+    desc: bool,
+) -> Datum<'a> {
+    let (rank, _, _) = percentile_ranks(false, total, fraction);
+    select_by_rank(values, rank, desc)
+}
+
+/// Evaluates `percentile_cont` or `percentile_disc`, as computed by `select`,
+/// over `Datum::List`s of (value, fraction) paired with their multiplicities.
+/// If `multiple`, the fraction is an array, and the result is an array of the
+/// same shape.
+fn percentile<'a, I>(
+    datums: I,
+    temp_storage: &'a RowArena,
+    desc: bool,
+    multiple: bool,
+    select: fn(&mut [(Datum<'a>, usize)], usize, f64, bool) -> Datum<'a>,
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = (Datum<'a>, usize)>,
+{
+    // The fraction is evaluated once per group, so it is the same in every
+    // record of the group.
+    let mut fraction = Datum::Null;
+    let mut total = 0;
+    let mut values: Vec<_> = datums
+        .into_iter()
+        .filter_map(|(d, count)| {
+            if d.is_null() {
+                return None;
+            }
+            let mut fields = d.unwrap_list().iter();
+            let value = fields.next().unwrap();
+            fraction = fields.next().unwrap();
+            if value.is_null() || count == 0 {
+                return None;
+            }
+            total += count;
+            Some((value, count))
+        })
+        .collect();
+    if total == 0 {
+        return Datum::Null;
+    }
+
+    let mut eval = |fraction: Datum| match fraction {
+        Datum::Float64(fraction) if (0.0..=1.0).contains(&*fraction) => {
+            select(&mut values, total, *fraction, desc)
+        }
+        _ => Datum::Null,
+    };
+    if !multiple {
+        eval(fraction)
+    } else if fraction.is_null() {
+        Datum::Null
+    } else {
+        let fractions = fraction.unwrap_array();
+        let dims: Vec<_> = fractions.dims().into_iter().collect();
+        let results: Vec<_> = fractions.elements().iter().map(&mut eval).collect();
+        temp_storage.make_datum(|packer| {
+            packer
+                .push_array(&dims, results)
+                .expect("results have the same shape as the fractions");
+        })
+    }
+}
+
+/// Computes the most frequent of the non-null `datums`, which must be distinct
+/// and are paired with their multiplicities.
+fn mode<'a, I>(datums: I, desc: bool) -> Datum<'a>
+where
+    I: IntoIterator<Item = (Datum<'a>, usize)>,
+{
+    let mut best: Option<(Datum, usize)> = None;
+    for (datum, count) in datums {
+        if datum.is_null() || count == 0 {
+            continue;
+        }
+        let better = match best {
+            None => true,
+            Some((best_datum, best_count)) => {
+                count > best_count
+                    || (count == best_count
+                        && if desc {
+                            datum > best_datum
+                        } else {
+                            datum < best_datum
+                        })
+            }
+        };
+        if better {
+            best = Some((datum, count));
+        }
+    }
+    best.map_or(Datum::Null, |(datum, _)| datum)
+}
+
+/// Builds a HyperLogLog sketch from the register and rank pairs produced by
+/// [`hll::register_of`], ignoring nulls. Returns `None` if all inputs are null.
+fn build_sketch<'a, I>(datums: I, precision: u8) -> Option<Sketch>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let mut sketch = None;
+    for datum in datums {
+        if !datum.is_null() {
+            sketch
+                .get_or_insert_with(|| Sketch::new(precision))
+                .insert(datum.unwrap_int32());
+        }
+    }
+    sketch
+}
+
+fn approx_count_distinct<'a, I>(datums: I, precision: u8) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let estimate = build_sketch(datums, precision).map_or(0, |sketch| sketch.estimate());
+    Datum::Int64(estimate)
+}
+
+fn hll_sketch<'a, I>(datums: I, temp_storage: &'a RowArena, precision: u8) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    match build_sketch(datums, precision) {
+        Some(sketch) => Datum::Bytes(temp_storage.push_bytes(sketch.to_bytes())),
+        None => Datum::Null,
+    }
+}
+
+fn hll_union<'a, I>(datums: I, temp_storage: &'a RowArena) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let mut union: Option<Sketch> = None;
+    for datum in datums {
+        if datum.is_null() {
+            continue;
+        }
+        let sketch = Sketch::from_bytes(datum.unwrap_bytes())
+            .expect("sketches are validated by UnaryFunc::HllValidate");
+        union = Some(match union.take() {
+            Some(mut union) => {
+                union.merge(&sketch);
+                union
+            }
+            None => sketch,
+        });
+    }
+    match union {
+        Some(sketch) => Datum::Bytes(temp_storage.push_bytes(sketch.to_bytes())),
+        None => Datum::Null,
+    }
+}
+
+/// Writes the options of an ordered-set aggregate that differ from the
+/// defaults, as in `percentile_cont[desc, multiple]`.
+fn fmt_ordered_set_options(f: &mut fmt::Formatter, desc: bool, multiple: bool) -> fmt::Result {
+    match (desc, multiple) {
+        (false, false) => Ok(()),
+        (true, false) => f.write_str("[desc]"),
+        (false, true) => f.write_str("[multiple]"),
+        (true, true) => f.write_str("[desc, multiple]"),
+    }
+}
+
 fn jsonb_agg<'a, I>(datums: I, temp_storage: &'a RowArena, order_by: &[ColumnOrder]) -> Datum<'a>
 where
     I: IntoIterator<Item = Datum<'a>>,
@@ -1292,6 +1558,46 @@ pub enum AggregateFunc {
     StringAgg {
         order_by: Vec<ColumnOrder>,
     },
+    /// Computes the continuous percentile of `Datum::List`s whose first
+    /// element is a `Float64` value and whose second element is the requested
+    /// fraction, or, if `multiple`, an array of fractions. The values are
+    /// ranked in descending order if `desc`.
+    ///
+    /// Null values are ignored. The result is null if there are no non-null
+    /// values, or for any fraction that is null. Fractions that are not
+    /// between 0 and 1 are rejected by `UnaryFunc::CheckPercentileFraction`
+    /// before they reach the aggregate.
+    PercentileCont {
+        desc: bool,
+        multiple: bool,
+    },
+    /// Like `PercentileCont`, but returns the first value whose position in
+    /// the ranking equals or exceeds the fraction, rather than interpolating
+    /// between adjacent values. The values may be of any type.
+    PercentileDisc {
+        desc: bool,
+        multiple: bool,
+    },
+    /// Computes the most frequent non-null value. Ties are broken in favor of
+    /// the value that ranks first, in descending order if `desc`.
+    Mode {
+        desc: bool,
+    },
+    /// Estimates the number of distinct values from the HyperLogLog register
+    /// and rank pairs of its input, as produced by `UnaryFunc::HllRegister`
+    /// with the same precision. Nulls are ignored.
+    ApproxCountDistinct {
+        precision: u8,
+    },
+    /// Like `ApproxCountDistinct`, but returns the HyperLogLog sketch as a
+    /// `Datum::Bytes`, or null if there are no non-null inputs.
+    HllSketch {
+        precision: u8,
+    },
+    /// Merges `Datum::Bytes` HyperLogLog sketches, as produced by `HllSketch`
+    /// and validated by `UnaryFunc::HllValidate`. The result has the smallest
+    /// precision of its inputs, and is null if all of its inputs are null.
+    HllUnion,
     RowNumber {
         order_by: Vec<ColumnOrder>,
     },
@@ -1409,6 +1715,22 @@ impl Arbitrary for AggregateFunc {
             vec(proptest_any::<ColumnOrder>(), 1..4)
                 .prop_map(|order_by| AggregateFunc::StringAgg { order_by })
                 .boxed(),
+            (proptest_any::<bool>(), proptest_any::<bool>())
+                .prop_map(|(desc, multiple)| AggregateFunc::PercentileCont { desc, multiple })
+                .boxed(),
+            (proptest_any::<bool>(), proptest_any::<bool>())
+                .prop_map(|(desc, multiple)| AggregateFunc::PercentileDisc { desc, multiple })
+                .boxed(),
+            proptest_any::<bool>()
+                .prop_map(|desc| AggregateFunc::Mode { desc })
+                .boxed(),
+            (hll::MIN_PRECISION..=hll::MAX_PRECISION)
+                .prop_map(|precision| AggregateFunc::ApproxCountDistinct { precision })
+                .boxed(),
+            (hll::MIN_PRECISION..=hll::MAX_PRECISION)
+                .prop_map(|precision| AggregateFunc::HllSketch { precision })
+                .boxed(),
+            Just(AggregateFunc::HllUnion).boxed(),
             vec(proptest_any::<ColumnOrder>(), 1..4)
                 .prop_map(|order_by| AggregateFunc::RowNumber { order_by })
                 .boxed(),
@@ -1526,6 +1848,24 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
                 AggregateFunc::ArrayConcat { order_by } => Kind::ArrayConcat(order_by.into_proto()),
                 AggregateFunc::ListConcat { order_by } => Kind::ListConcat(order_by.into_proto()),
                 AggregateFunc::StringAgg { order_by } => Kind::StringAgg(order_by.into_proto()),
+                AggregateFunc::PercentileCont { desc, multiple } => {
+                    Kind::PercentileCont(proto_aggregate_func::ProtoPercentile {
+                        desc: *desc,
+                        multiple: *multiple,
+                    })
+                }
+                AggregateFunc::PercentileDisc { desc, multiple } => {
+                    Kind::PercentileDisc(proto_aggregate_func::ProtoPercentile {
+                        desc: *desc,
+                        multiple: *multiple,
+                    })
+                }
+                AggregateFunc::Mode { desc } => Kind::Mode(*desc),
+                AggregateFunc::ApproxCountDistinct { precision } => {
+                    Kind::ApproxCountDistinct(precision.into_proto())
+                }
+                AggregateFunc::HllSketch { precision } => Kind::HllSketch(precision.into_proto()),
+                AggregateFunc::HllUnion => Kind::HllUnion(()),
                 AggregateFunc::RowNumber { order_by } => Kind::RowNumber(order_by.into_proto()),
                 AggregateFunc::Rank { order_by } => Kind::Rank(order_by.into_proto()),
                 AggregateFunc::DenseRank { order_by } => Kind::DenseRank(order_by.into_proto()),
@@ -1642,6 +1982,22 @@ impl RustType<ProtoAggregateFunc> for AggregateFunc {
             Kind::StringAgg(order_by) => AggregateFunc::StringAgg {
                 order_by: order_by.into_rust()?,
             },
+            Kind::PercentileCont(pp) => AggregateFunc::PercentileCont {
+                desc: pp.desc,
+                multiple: pp.multiple,
+            },
+            Kind::PercentileDisc(pp) => AggregateFunc::PercentileDisc {
+                desc: pp.desc,
+                multiple: pp.multiple,
+            },
+            Kind::Mode(desc) => AggregateFunc::Mode { desc },
+            Kind::ApproxCountDistinct(precision) => AggregateFunc::ApproxCountDistinct {
+                precision: precision.into_rust()?,
+            },
+            Kind::HllSketch(precision) => AggregateFunc::HllSketch {
+                precision: precision.into_rust()?,
+            },
+            Kind::HllUnion(()) => AggregateFunc::HllUnion,
             Kind::RowNumber(order_by) => AggregateFunc::RowNumber {
                 order_by: order_by.into_rust()?,
             },
@@ -1769,6 +2125,26 @@ impl AggregateFunc {
             AggregateFunc::ArrayConcat { order_by } => array_concat(datums, temp_storage, order_by),
             AggregateFunc::ListConcat { order_by } => list_concat(datums, temp_storage, order_by),
             AggregateFunc::StringAgg { order_by } => string_agg(datums, temp_storage, order_by),
+            AggregateFunc::PercentileCont { desc, multiple } => percentile(
+                consolidate_datums(datums),
+                temp_storage,
+                *desc,
+                *multiple,
+                percentile_cont,
+            ),
+            AggregateFunc::PercentileDisc { desc, multiple } => percentile(
+                consolidate_datums(datums),
+                temp_storage,
+                *desc,
+                *multiple,
+                percentile_disc,
+            ),
+            AggregateFunc::Mode { desc } => mode(consolidate_datums(datums), *desc),
+            AggregateFunc::ApproxCountDistinct { precision } => {
+                approx_count_distinct(datums, *precision)
+            }
+            AggregateFunc::HllSketch { precision } => hll_sketch(datums, temp_storage, *precision),
+            AggregateFunc::HllUnion => hll_union(datums, temp_storage),
             AggregateFunc::RowNumber { order_by } => row_number(datums, temp_storage, order_by),
             AggregateFunc::Rank { order_by } => rank(datums, temp_storage, order_by),
             AggregateFunc::DenseRank { order_by } => dense_rank(datums, temp_storage, order_by),
@@ -1824,11 +2200,53 @@ impl AggregateFunc {
         }
     }
 
+    /// Like `eval_fast_window_agg`, but it's given distinct datums paired with
+    /// their multiplicities, as presented by a consolidated arrangement.
+    ///
+    /// HyperLogLog aggregates ignore the multiplicities. All other aggregates
+    /// see each datum repeated according to its multiplicity.
+    pub fn eval_with_multiplicities<'a, I, W>(
+        &self,
+        datums: I,
+        temp_storage: &'a RowArena,
+    ) -> Datum<'a>
+    where
+        I: IntoIterator<Item = (Datum<'a>, usize)>,
+        W: OneByOneAggr,
+    {
+        match self {
+            // Sketches are insensitive to multiplicities.
+            AggregateFunc::ApproxCountDistinct { .. }
+            | AggregateFunc::HllSketch { .. }
+            | AggregateFunc::HllUnion => {
+                self.eval(datums.into_iter().map(|(datum, _)| datum), temp_storage)
+            }
+            _ => self.eval_fast_window_agg::<_, W>(
+                datums
+                    .into_iter()
+                    .flat_map(|(datum, count)| iter::repeat(datum).take(count)),
+                temp_storage,
+            ),
+        }
+    }
+
+    /// Reports whether this is an ordered-set aggregate, which ranks its
+    /// input, like `percentile_cont`.
+    pub fn is_ordered_set(&self) -> bool {
+        matches!(
+            self,
+            AggregateFunc::PercentileCont { .. }
+                | AggregateFunc::PercentileDisc { .. }
+                | AggregateFunc::Mode { .. }
+        )
+    }
+
     /// Returns the output of the aggregation function when applied on an empty
     /// input relation.
     pub fn default(&self) -> Datum<'static> {
         match self {
             AggregateFunc::Count => Datum::Int64(0),
+            AggregateFunc::ApproxCountDistinct { .. } => Datum::Int64(0),
             AggregateFunc::Any => Datum::False,
             AggregateFunc::All => Datum::True,
             AggregateFunc::Dummy => Datum::Dummy,
@@ -1890,6 +2308,18 @@ impl AggregateFunc {
                 }
             }
             AggregateFunc::StringAgg { .. } => ScalarType::String,
+            AggregateFunc::ApproxCountDistinct { .. } => ScalarType::Int64,
+            AggregateFunc::HllSketch { .. } | AggregateFunc::HllUnion => ScalarType::Bytes,
+            AggregateFunc::PercentileCont { multiple, .. }
+            | AggregateFunc::PercentileDisc { multiple, .. } => {
+                // The input is a (value, fraction) Record.
+                let value_type = input_type.scalar_type.unwrap_record_element_type()[0].clone();
+                if *multiple {
+                    ScalarType::Array(Box::new(value_type))
+                } else {
+                    value_type
+                }
+            }
             AggregateFunc::RowNumber { .. } => {
                 AggregateFunc::output_type_ranking_window_funcs(&input_type, "?row_number?")
             }
@@ -1999,7 +2429,10 @@ impl AggregateFunc {
         // Count never produces null, and other aggregations only produce
         // null in the presence of null inputs.
         let nullable = match self {
-            AggregateFunc::Count => false,
+            AggregateFunc::Count | AggregateFunc::ApproxCountDistinct { .. } => false,
+            // Percentiles are null when there are no non-null values or when
+            // the fraction is out of range, regardless of the input Record.
+            AggregateFunc::PercentileCont { .. } | AggregateFunc::PercentileDisc { .. } => true,
             // Use the nullability of the underlying column being aggregated, not the Records wrapping it
             AggregateFunc::StringAgg { .. } => match input_type.scalar_type {
                 // The outer Record wraps the input in the first position, and any ORDER BY expressions afterwards
@@ -2140,6 +2573,25 @@ fn jsonb_array_elements<'a>(
     })
 }
 
+fn jsonb_path_query<'a>(
+    datums: &[Datum<'a>],
+    path: Option<&JsonPath>,
+    temp_storage: &'a RowArena,
+) -> Result<impl Iterator<Item = (Row, Diff)> + 'a, EvalError> {
+    let items = jsonpath::query(
+        datums[0],
+        path,
+        datums[1].unwrap_str(),
+        datums[2],
+        datums[3].unwrap_bool(),
+        temp_storage,
+    )?;
+    Ok(items
+        .unwrap_or_default()
+        .into_iter()
+        .map(|item| (Row::pack_slice(&[item]), 1)))
+}
+
 fn regexp_extract(a: Datum, r: &AnalyzedRegex) -> Option<(Row, Diff)> {
     let r = r.inner();
     let a = a.unwrap_str();
@@ -2360,6 +2812,25 @@ where
                 let order_by = order_by.iter().map(|col| self.child(col));
                 write!(f, "string_agg[order_by=[{}]]", separated(", ", order_by))
             }
+            AggregateFunc::PercentileCont { desc, multiple } => {
+                f.write_str("percentile_cont")?;
+                fmt_ordered_set_options(f, *desc, *multiple)
+            }
+            AggregateFunc::PercentileDisc { desc, multiple } => {
+                f.write_str("percentile_disc")?;
+                fmt_ordered_set_options(f, *desc, *multiple)
+            }
+            AggregateFunc::Mode { desc } => {
+                f.write_str("mode")?;
+                fmt_ordered_set_options(f, *desc, false)
+            }
+            AggregateFunc::ApproxCountDistinct { precision } => {
+                write!(f, "approx_count_distinct[precision={}]", precision)
+            }
+            AggregateFunc::HllSketch { precision } => {
+                write!(f, "hll_sketch[precision={}]", precision)
+            }
+            AggregateFunc::HllUnion => f.write_str("hll_union_agg"),
             AggregateFunc::RowNumber { order_by } => {
                 let order_by = order_by.iter().map(|col| self.child(col));
                 write!(f, "row_number[order_by=[{}]]", separated(", ", order_by))
@@ -2623,6 +3094,10 @@ pub enum TableFunc {
     JsonbArrayElements {
         stringify: bool,
     },
+    JsonbPathQuery {
+        #[proptest(strategy = "jsonpath::any_json_path()")]
+        path: Option<JsonPath>,
+    },
     RegexpExtract(AnalyzedRegex),
     CsvExtract(usize),
     GenerateSeriesInt32,
@@ -2667,6 +3142,7 @@ impl RustType<ProtoTableFunc> for TableFunc {
                 TableFunc::JsonbEach { stringify } => Kind::JsonbEach(*stringify),
                 TableFunc::JsonbObjectKeys => Kind::JsonbObjectKeys(()),
                 TableFunc::JsonbArrayElements { stringify } => Kind::JsonbArrayElements(*stringify),
+                TableFunc::JsonbPathQuery { path } => Kind::JsonbPathQuery(path.into_proto()),
                 TableFunc::RegexpExtract(x) => Kind::RegexpExtract(x.into_proto()),
                 TableFunc::CsvExtract(x) => Kind::CsvExtract(x.into_proto()),
                 TableFunc::GenerateSeriesInt32 => Kind::GenerateSeriesInt32(()),
@@ -2705,6 +3181,9 @@ impl RustType<ProtoTableFunc> for TableFunc {
             Kind::JsonbEach(stringify) => TableFunc::JsonbEach { stringify },
             Kind::JsonbObjectKeys(()) => TableFunc::JsonbObjectKeys,
             Kind::JsonbArrayElements(stringify) => TableFunc::JsonbArrayElements { stringify },
+            Kind::JsonbPathQuery(path) => TableFunc::JsonbPathQuery {
+                path: path.into_rust()?,
+            },
             Kind::RegexpExtract(x) => TableFunc::RegexpExtract(x.into_rust()?),
             Kind::CsvExtract(x) => TableFunc::CsvExtract(x.into_rust()?),
             Kind::GenerateSeriesInt32(()) => TableFunc::GenerateSeriesInt32,
@@ -2757,6 +3236,11 @@ impl TableFunc {
                 temp_storage,
                 *stringify,
             ))),
+            TableFunc::JsonbPathQuery { path } => Ok(Box::new(jsonb_path_query(
+                datums,
+                path.as_ref(),
+                temp_storage,
+            )?)),
             TableFunc::RegexpExtract(a) => Ok(Box::new(regexp_extract(datums[0], a).into_iter())),
             TableFunc::CsvExtract(n_cols) => Ok(Box::new(csv_extract(datums[0], *n_cols))),
             TableFunc::GenerateSeriesInt32 => {
@@ -2867,6 +3351,11 @@ impl TableFunc {
                 let keys = vec![];
                 (column_types, keys)
             }
+            TableFunc::JsonbPathQuery { .. } => {
+                let column_types = vec![ScalarType::Jsonb.nullable(false)];
+                let keys = vec![];
+                (column_types, keys)
+            }
             TableFunc::RegexpExtract(a) => {
                 let column_types = a
                     .capture_groups_iter()
@@ -2955,6 +3444,7 @@ impl TableFunc {
             TableFunc::JsonbEach { .. } => 2,
             TableFunc::JsonbObjectKeys => 1,
             TableFunc::JsonbArrayElements { .. } => 1,
+            TableFunc::JsonbPathQuery { .. } => 1,
             TableFunc::RegexpExtract(a) => a.capture_groups_len(),
             TableFunc::CsvExtract(n_cols) => *n_cols,
             TableFunc::GenerateSeriesInt32 => 1,
@@ -2978,6 +3468,7 @@ impl TableFunc {
             | TableFunc::JsonbEach { .. }
             | TableFunc::JsonbObjectKeys
             | TableFunc::JsonbArrayElements { .. }
+            | TableFunc::JsonbPathQuery { .. }
             | TableFunc::GenerateSeriesInt32
             | TableFunc::GenerateSeriesInt64
             | TableFunc::GenerateSeriesTimestamp
@@ -3004,6 +3495,7 @@ impl TableFunc {
             TableFunc::JsonbEach { .. } => true,
             TableFunc::JsonbObjectKeys => true,
             TableFunc::JsonbArrayElements { .. } => true,
+            TableFunc::JsonbPathQuery { .. } => true,
             TableFunc::RegexpExtract(_) => true,
             TableFunc::CsvExtract(_) => true,
             TableFunc::GenerateSeriesInt32 => true,
@@ -3029,6 +3521,7 @@ impl fmt::Display for TableFunc {
             TableFunc::JsonbEach { .. } => f.write_str("jsonb_each"),
             TableFunc::JsonbObjectKeys => f.write_str("jsonb_object_keys"),
             TableFunc::JsonbArrayElements { .. } => f.write_str("jsonb_array_elements"),
+            TableFunc::JsonbPathQuery { .. } => f.write_str("jsonb_path_query"),
             TableFunc::RegexpExtract(a) => write!(f, "regexp_extract({:?}, _)", a.0),
             TableFunc::CsvExtract(n_cols) => write!(f, "csv_extract({}, _)", n_cols),
             TableFunc::GenerateSeriesInt32 => f.write_str("generate_series"),
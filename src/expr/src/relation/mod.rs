@@ -218,6 +218,9 @@ pub enum MirRelationExpr {
         /// Join implementation information.
         #[serde(default)]
         implementation: JoinImplementation,
+        /// Hints that override the choices made when determining `implementation`.
+        #[serde(default)]
+        hints: JoinHints,
     },
     /// Group a dataflow by some columns and aggregate over each group
     ///
@@ -1255,9 +1258,20 @@ impl MirRelationExpr {
             inputs,
             equivalences,
             implementation: JoinImplementation::Unimplemented,
+            hints: JoinHints::default(),
         }
     }
 
+    /// Replaces the hints of a join operator.
+    ///
+    /// Has no effect on other operators.
+    pub fn with_join_hints(mut self, new_hints: JoinHints) -> Self {
+        if let MirRelationExpr::Join { hints, .. } = &mut self {
+            *hints = new_hints;
+        }
+        self
+    }
+
     /// Perform a key-wise reduction / aggregation.
     ///
     /// The `group_key` argument indicates columns in the input collection that should
@@ -2410,11 +2424,36 @@ impl AggregateExpr {
         }
     }
 
+    /// Reports whether [`AggregateExpr::on_unique`] can express this
+    /// aggregate over a single input row.
+    ///
+    /// Window aggregates have a unique form exactly when the aggregate they
+    /// wrap has one.
+    pub fn has_unique_form(&self) -> bool {
+        Self::func_has_unique_form(&self.func)
+    }
+
+    fn func_has_unique_form(func: &AggregateFunc) -> bool {
+        match func {
+            AggregateFunc::PercentileCont { multiple: true, .. }
+            | AggregateFunc::PercentileDisc { multiple: true, .. }
+            | AggregateFunc::HllSketch { .. }
+            | AggregateFunc::HllUnion => false,
+            AggregateFunc::WindowAggregate {
+                wrapped_aggregate, ..
+            } => Self::func_has_unique_form(wrapped_aggregate),
+            _ => true,
+        }
+    }
+
     /// Extracts unique input from aggregate type
+    ///
+    /// Panics if [`AggregateExpr::has_unique_form`] is false.
     pub fn on_unique(&self, input_type: &[ColumnType]) -> MirScalarExpr {
         match &self.func {
-            // Count is one if non-null, and zero if null.
-            AggregateFunc::Count => self
+            // Count is one if non-null, and zero if null. The same holds for
+            // the approximate count of distinct values.
+            AggregateFunc::Count | AggregateFunc::ApproxCountDistinct { .. } => self
                 .expr
                 .clone()
                 .call_unary(UnaryFunc::IsNull(crate::func::IsNull))
@@ -2510,6 +2549,45 @@ impl AggregateExpr {
                 .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)))
                 .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0))),
 
+            // The mode of a single value is that value.
+            AggregateFunc::Mode { .. } => self.expr.clone(),
+
+            // Percentiles take a (value, fraction) record. The percentile of a
+            // single value is that value, unless the fraction is null. Fractions
+            // out of range have already errored in `CheckPercentileFraction`.
+            AggregateFunc::PercentileCont {
+                multiple: false, ..
+            }
+            | AggregateFunc::PercentileDisc {
+                multiple: false, ..
+            } => {
+                let value = self
+                    .expr
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(0)));
+                let fraction = self
+                    .expr
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(scalar_func::RecordGet(1)));
+                fraction.call_is_null().if_then_else(
+                    MirScalarExpr::literal_null(self.typ(input_type).scalar_type),
+                    value,
+                )
+            }
+
+            // There is no scalar expression that maps each fraction of an array
+            // to the single value. `has_unique_form` reports these, including
+            // when they are wrapped in a window aggregate, so callers never ask.
+            AggregateFunc::PercentileCont { multiple: true, .. }
+            | AggregateFunc::PercentileDisc { multiple: true, .. } => {
+                unreachable!("percentiles of multiple fractions have no unique-input form")
+            }
+
+            // Building a sketch requires evaluating the aggregate.
+            AggregateFunc::HllSketch { .. } | AggregateFunc::HllUnion => {
+                unreachable!("HyperLogLog sketches have no unique-input form")
+            }
+
             // ListConcat and ArrayConcat take a single level of records and output a list containing exactly 1 element
             AggregateFunc::ListConcat { .. } | AggregateFunc::ArrayConcat { .. } => self
                 .expr
@@ -2923,6 +3001,63 @@ impl JoinImplementation {
     }
 }
 
+/// User-provided hints that override the choices `JoinImplementation` would otherwise make for
+/// a join.
+///
+/// Join inputs are referred to by the global collections they read, rather than by position,
+/// so that the hints survive transforms that fuse or reorder join inputs.
+#[derive(
+    Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct JoinHints {
+    /// Collections in the order in which they should be joined. Inputs that do not read any of
+    /// these collections are ordered after them.
+    pub order: Vec<GlobalId>,
+    /// The join implementation that should be used.
+    pub implementation: Option<JoinImplementationHint>,
+}
+
+impl JoinHints {
+    /// Returns `true` iff no hint is present.
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty() && self.implementation.is_none()
+    }
+
+    /// Merges the hints of a join that is being fused into the join owning `self`.
+    ///
+    /// The hints of `self` take precedence.
+    pub fn merge(&mut self, other: &JoinHints) {
+        for id in other.order.iter() {
+            if !self.order.contains(id) {
+                self.order.push(*id);
+            }
+        }
+        if self.implementation.is_none() {
+            self.implementation = other.implementation;
+        }
+    }
+}
+
+/// The join implementation requested by a [`JoinHints`].
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub enum JoinImplementationHint {
+    /// Plan a linear differential join.
+    Differential,
+    /// Plan a delta join.
+    Delta,
+}
+
+impl fmt::Display for JoinImplementationHint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Differential => f.write_str("differential"),
+            Self::Delta => f.write_str("delta"),
+        }
+    }
+}
+
 /// Characteristics of a join order candidate collection.
 ///
 /// A candidate is described by a collection and a key, and may have various liabilities.
@@ -22,13 +22,13 @@ use ::encoding::label::encoding_from_whatwg_label;
 use ::encoding::DecoderTrap;
 use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
 use chrono_tz::{OffsetComponents, OffsetName, Tz};
-use dec::OrderedDecimal;
+use dec::{OrderedDecimal, Rounding};
 use fallible_iterator::FallibleIterator;
 use hmac::{Hmac, Mac};
 use itertools::Itertools;
 use md5::{Digest, Md5};
 use mz_lowertest::MzReflect;
-use mz_ore::cast::{self, CastFrom, ReinterpretCast};
+use mz_ore::cast::{self, CastFrom, CastLossy, ReinterpretCast};
 use mz_ore::fmt::FormatBuffer;
 use mz_ore::lex::LexBuf;
 use mz_ore::option::OptionExt;
@@ -59,6 +59,7 @@ use sha1::Sha1;
 use sha2::{Sha224, Sha256, Sha384, Sha512};
 use subtle::ConstantTimeEq;
 
+use crate::jsonpath::{self, JsonPath};
 use crate::scalar::func::format::DateTimeFormat;
 use crate::scalar::{
     ProtoBinaryFunc, ProtoUnaryFunc, ProtoUnmaterializableFunc, ProtoVariadicFunc,
@@ -1985,6 +1986,57 @@ fn mz_acl_item_contains_privilege(a: Datum<'_>, b: Datum<'_>) -> Result<Datum<'s
     Ok(contains.into())
 }
 
+fn inet_contained_by<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_inet().contained_by(&b.unwrap_inet()))
+}
+
+fn inet_contained_by_or_equals<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_inet().contained_by_or_equals(&b.unwrap_inet()))
+}
+
+fn inet_contains<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(b.unwrap_inet().contained_by(&a.unwrap_inet()))
+}
+
+fn inet_contains_or_equals<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(b.unwrap_inet().contained_by_or_equals(&a.unwrap_inet()))
+}
+
+fn inet_overlaps<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_inet().overlaps(&b.unwrap_inet()))
+}
+
+/// Sets the netmask length of an `inet` or `cidr` value. A length of `-1`
+/// means the maximum length for the address family. For `cidr` values, the
+/// bits to the right of the new netmask are zeroed.
+fn set_masklen<'a>(a: Datum<'a>, b: Datum<'a>, cidr: bool) -> Result<Datum<'a>, EvalError> {
+    let inet = a.unwrap_inet();
+    let prefix_len = match b.unwrap_int32() {
+        -1 => inet.max_prefix_len(),
+        n => u8::try_from(n)
+            .map_err(|_| EvalError::InvalidParameterValue(format!("invalid mask length: {}", n)))?,
+    };
+    let inet = inet
+        .with_prefix_len(prefix_len)
+        .map_err(|e| EvalError::InvalidParameterValue(e.to_string()))?;
+    Ok(Datum::Inet(if cidr { inet.network() } else { inet }))
+}
+
+/// Merges two HyperLogLog sketches. See [`crate::hll::Sketch::merge`].
+fn hll_union<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let decode = |d: Datum| {
+        crate::hll::Sketch::from_bytes(d.unwrap_bytes())
+            .map_err(|e| EvalError::InvalidParameterValue(e.to_string()))
+    };
+    let mut sketch = decode(a)?;
+    sketch.merge(&decode(b)?);
+    Ok(Datum::Bytes(temp_storage.push_bytes(sketch.to_bytes())))
+}
+
 // transliterated from postgres/src/backend/utils/adt/misc.c
 fn parse_ident<'a>(
     a: Datum<'a>,
@@ -2341,6 +2393,14 @@ pub enum BinaryFunc {
     MzAclItemContainsPrivilege,
     ParseIdent,
     PrettySql,
+    InetContainedBy,
+    InetContainedByOrEquals,
+    InetContains,
+    InetContainsOrEquals,
+    InetOverlaps,
+    SetMasklenInet,
+    SetMasklenCidr,
+    HllUnion,
 }
 
 impl BinaryFunc {
@@ -2606,6 +2666,14 @@ impl BinaryFunc {
             BinaryFunc::MzAclItemContainsPrivilege => mz_acl_item_contains_privilege(a, b),
             BinaryFunc::ParseIdent => parse_ident(a, b, temp_storage),
             BinaryFunc::PrettySql => pretty_sql(a, b, temp_storage),
+            BinaryFunc::InetContainedBy => Ok(inet_contained_by(a, b)),
+            BinaryFunc::InetContainedByOrEquals => Ok(inet_contained_by_or_equals(a, b)),
+            BinaryFunc::InetContains => Ok(inet_contains(a, b)),
+            BinaryFunc::InetContainsOrEquals => Ok(inet_contains_or_equals(a, b)),
+            BinaryFunc::InetOverlaps => Ok(inet_overlaps(a, b)),
+            BinaryFunc::SetMasklenInet => set_masklen(a, b, false),
+            BinaryFunc::SetMasklenCidr => set_masklen(a, b, true),
+            BinaryFunc::HllUnion => hll_union(a, b, temp_storage),
         }
     }
 
@@ -2794,6 +2862,17 @@ impl BinaryFunc {
 
             ParseIdent => ScalarType::Array(Box::new(ScalarType::String)).nullable(in_nullable),
             PrettySql => ScalarType::String.nullable(in_nullable),
+
+            InetContainedBy
+            | InetContainedByOrEquals
+            | InetContains
+            | InetContainsOrEquals
+            | InetOverlaps => ScalarType::Bool.nullable(in_nullable),
+
+            SetMasklenInet => ScalarType::Inet.nullable(in_nullable),
+            SetMasklenCidr => ScalarType::Cidr.nullable(in_nullable),
+
+            HllUnion => ScalarType::Bytes.nullable(in_nullable),
         }
     }
 
@@ -2994,7 +3073,15 @@ impl BinaryFunc {
             | UuidGenerateV5
             | MzAclItemContainsPrivilege
             | ParseIdent
-            | PrettySql => false,
+            | PrettySql
+            | InetContainedBy
+            | InetContainedByOrEquals
+            | InetContains
+            | InetContainsOrEquals
+            | InetOverlaps
+            | SetMasklenInet
+            | SetMasklenCidr
+            | HllUnion => false,
 
             JsonbGetInt64 { .. }
             | JsonbGetString { .. }
@@ -3145,7 +3232,12 @@ impl BinaryFunc {
             | RangeAdjacent
             | RangeUnion
             | RangeIntersection
-            | RangeDifference => true,
+            | RangeDifference
+            | InetContainedBy
+            | InetContainedByOrEquals
+            | InetContains
+            | InetContainsOrEquals
+            | InetOverlaps => true,
             ToCharTimestamp
             | ToCharTimestampTz
             | AgeTimestamp
@@ -3198,7 +3290,10 @@ impl BinaryFunc {
             | ConstantTimeEqBytes
             | ConstantTimeEqString
             | ParseIdent
-            | PrettySql => false,
+            | PrettySql
+            | SetMasklenInet
+            | SetMasklenCidr
+            | HllUnion => false,
         }
     }
 
@@ -3422,6 +3517,14 @@ impl BinaryFunc {
             BinaryFunc::ParseIdent => (false, false),
             BinaryFunc::ConstantTimeEqBytes | BinaryFunc::ConstantTimeEqString => (false, false),
             BinaryFunc::PrettySql => (false, false),
+            BinaryFunc::InetContainedBy
+            | BinaryFunc::InetContainedByOrEquals
+            | BinaryFunc::InetContains
+            | BinaryFunc::InetContainsOrEquals
+            | BinaryFunc::InetOverlaps
+            | BinaryFunc::SetMasklenInet
+            | BinaryFunc::SetMasklenCidr
+            | BinaryFunc::HllUnion => (false, false),
         }
     }
 }
@@ -3629,6 +3732,13 @@ impl fmt::Display for BinaryFunc {
             BinaryFunc::MzAclItemContainsPrivilege => f.write_str("mz_aclitem_contains_privilege"),
             BinaryFunc::ParseIdent => f.write_str("parse_ident"),
             BinaryFunc::PrettySql => f.write_str("pretty_sql"),
+            BinaryFunc::InetContainedBy => f.write_str("<<"),
+            BinaryFunc::InetContainedByOrEquals => f.write_str("<<="),
+            BinaryFunc::InetContains => f.write_str(">>"),
+            BinaryFunc::InetContainsOrEquals => f.write_str(">>="),
+            BinaryFunc::InetOverlaps => f.write_str("&&"),
+            BinaryFunc::SetMasklenInet | BinaryFunc::SetMasklenCidr => f.write_str("set_masklen"),
+            BinaryFunc::HllUnion => f.write_str("hll_union"),
         }
     }
 }
@@ -3842,6 +3952,14 @@ impl Arbitrary for BinaryFunc {
             Just(BinaryFunc::RangeIntersection).boxed(),
             Just(BinaryFunc::RangeDifference).boxed(),
             Just(BinaryFunc::ParseIdent).boxed(),
+            Just(BinaryFunc::InetContainedBy).boxed(),
+            Just(BinaryFunc::InetContainedByOrEquals).boxed(),
+            Just(BinaryFunc::InetContains).boxed(),
+            Just(BinaryFunc::InetContainsOrEquals).boxed(),
+            Just(BinaryFunc::InetOverlaps).boxed(),
+            Just(BinaryFunc::SetMasklenInet).boxed(),
+            Just(BinaryFunc::SetMasklenCidr).boxed(),
+            Just(BinaryFunc::HllUnion).boxed(),
         ])
     }
 }
@@ -4041,6 +4159,14 @@ impl RustType<ProtoBinaryFunc> for BinaryFunc {
             BinaryFunc::ConstantTimeEqBytes => ConstantTimeEqBytes(()),
             BinaryFunc::ConstantTimeEqString => ConstantTimeEqString(()),
             BinaryFunc::PrettySql => PrettySql(()),
+            BinaryFunc::InetContainedBy => InetContainedBy(()),
+            BinaryFunc::InetContainedByOrEquals => InetContainedByOrEquals(()),
+            BinaryFunc::InetContains => InetContains(()),
+            BinaryFunc::InetContainsOrEquals => InetContainsOrEquals(()),
+            BinaryFunc::InetOverlaps => InetOverlaps(()),
+            BinaryFunc::SetMasklenInet => SetMasklenInet(()),
+            BinaryFunc::SetMasklenCidr => SetMasklenCidr(()),
+            BinaryFunc::HllUnion => HllUnion(()),
         };
         ProtoBinaryFunc { kind: Some(kind) }
     }
@@ -4244,6 +4370,14 @@ impl RustType<ProtoBinaryFunc> for BinaryFunc {
                 ConstantTimeEqBytes(()) => Ok(BinaryFunc::ConstantTimeEqBytes),
                 ConstantTimeEqString(()) => Ok(BinaryFunc::ConstantTimeEqString),
                 PrettySql(()) => Ok(BinaryFunc::PrettySql),
+                InetContainedBy(()) => Ok(BinaryFunc::InetContainedBy),
+                InetContainedByOrEquals(()) => Ok(BinaryFunc::InetContainedByOrEquals),
+                InetContains(()) => Ok(BinaryFunc::InetContains),
+                InetContainsOrEquals(()) => Ok(BinaryFunc::InetContainsOrEquals),
+                InetOverlaps(()) => Ok(BinaryFunc::InetOverlaps),
+                SetMasklenInet(()) => Ok(BinaryFunc::SetMasklenInet),
+                SetMasklenCidr(()) => Ok(BinaryFunc::SetMasklenCidr),
+                HllUnion(()) => Ok(BinaryFunc::HllUnion),
             }
         } else {
             Err(TryFromProtoError::missing_field("ProtoBinaryFunc::kind"))
@@ -4623,6 +4757,23 @@ derive_unary!(
     CastMapToString,
     CastInt2VectorToString,
     CastRangeToString,
+    CastStringToEnum,
+    CastEnumToString,
+    CastStringToInet,
+    CastStringToCidr,
+    CastStringToMacAddr,
+    CastInetToString,
+    CastCidrToString,
+    CastMacAddrToString,
+    CastCidrToInet,
+    CastInetToCidr,
+    Host,
+    Masklen,
+    Network,
+    HllRegister,
+    HllCardinality,
+    HllValidate,
+    CheckPercentileFraction,
     CeilFloat32,
     CeilFloat64,
     CeilNumeric,
@@ -5023,6 +5174,27 @@ impl Arbitrary for UnaryFunc {
             CastMapToString::arbitrary().prop_map_into().boxed(),
             CastInt2VectorToString::arbitrary().prop_map_into().boxed(),
             CastRangeToString::arbitrary().prop_map_into().boxed(),
+            any::<ScalarType>()
+                .prop_map(|return_ty| UnaryFunc::CastStringToEnum(CastStringToEnum { return_ty }))
+                .boxed(),
+            CastEnumToString::arbitrary().prop_map_into().boxed(),
+            CastStringToInet::arbitrary().prop_map_into().boxed(),
+            CastStringToCidr::arbitrary().prop_map_into().boxed(),
+            CastStringToMacAddr::arbitrary().prop_map_into().boxed(),
+            CastInetToString::arbitrary().prop_map_into().boxed(),
+            CastCidrToString::arbitrary().prop_map_into().boxed(),
+            CastMacAddrToString::arbitrary().prop_map_into().boxed(),
+            CastCidrToInet::arbitrary().prop_map_into().boxed(),
+            CastInetToCidr::arbitrary().prop_map_into().boxed(),
+            Host::arbitrary().prop_map_into().boxed(),
+            Masklen::arbitrary().prop_map_into().boxed(),
+            Network::arbitrary().prop_map_into().boxed(),
+            (crate::hll::MIN_PRECISION..=crate::hll::MAX_PRECISION)
+                .prop_map(|precision| UnaryFunc::HllRegister(HllRegister { precision }))
+                .boxed(),
+            HllCardinality::arbitrary().prop_map_into().boxed(),
+            HllValidate::arbitrary().prop_map_into().boxed(),
+            CheckPercentileFraction::arbitrary().prop_map_into().boxed(),
             CeilFloat32::arbitrary().prop_map_into().boxed(),
             CeilFloat64::arbitrary().prop_map_into().boxed(),
             CeilNumeric::arbitrary().prop_map_into().boxed(),
@@ -5410,6 +5582,25 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
             UnaryFunc::CastMapToString(func) => CastMapToString(func.ty.into_proto()),
             UnaryFunc::CastInt2VectorToString(_) => CastInt2VectorToString(()),
             UnaryFunc::CastRangeToString(func) => CastRangeToString(func.ty.into_proto()),
+            UnaryFunc::CastStringToEnum(func) => CastStringToEnum(func.return_ty.into_proto()),
+            UnaryFunc::CastEnumToString(func) => CastEnumToString(ProtoCastEnumToString {
+                labels: func.labels.clone(),
+            }),
+            UnaryFunc::CastStringToInet(_) => CastStringToInet(()),
+            UnaryFunc::CastStringToCidr(_) => CastStringToCidr(()),
+            UnaryFunc::CastStringToMacAddr(_) => CastStringToMacAddr(()),
+            UnaryFunc::CastInetToString(_) => CastInetToString(()),
+            UnaryFunc::CastCidrToString(_) => CastCidrToString(()),
+            UnaryFunc::CastMacAddrToString(_) => CastMacAddrToString(()),
+            UnaryFunc::CastCidrToInet(_) => CastCidrToInet(()),
+            UnaryFunc::CastInetToCidr(_) => CastInetToCidr(()),
+            UnaryFunc::Host(_) => Host(()),
+            UnaryFunc::Masklen(_) => Masklen(()),
+            UnaryFunc::Network(_) => Network(()),
+            UnaryFunc::HllRegister(func) => HllRegister(func.precision.into_proto()),
+            UnaryFunc::HllCardinality(_) => HllCardinality(()),
+            UnaryFunc::HllValidate(_) => HllValidate(()),
+            UnaryFunc::CheckPercentileFraction(_) => CheckPercentileFraction(()),
             UnaryFunc::CeilFloat32(_) => CeilFloat32(()),
             UnaryFunc::CeilFloat64(_) => CeilFloat64(()),
             UnaryFunc::CeilNumeric(_) => CeilNumeric(()),
@@ -5865,6 +6056,32 @@ impl RustType<ProtoUnaryFunc> for UnaryFunc {
                     ty: ty.into_rust()?,
                 }
                 .into()),
+                CastStringToEnum(return_ty) => Ok(impls::CastStringToEnum {
+                    return_ty: return_ty.into_rust()?,
+                }
+                .into()),
+                CastEnumToString(func) => Ok(impls::CastEnumToString {
+                    labels: func.labels,
+                }
+                .into()),
+                CastStringToInet(_) => Ok(impls::CastStringToInet.into()),
+                CastStringToCidr(_) => Ok(impls::CastStringToCidr.into()),
+                CastStringToMacAddr(_) => Ok(impls::CastStringToMacAddr.into()),
+                CastInetToString(_) => Ok(impls::CastInetToString.into()),
+                CastCidrToString(_) => Ok(impls::CastCidrToString.into()),
+                CastMacAddrToString(_) => Ok(impls::CastMacAddrToString.into()),
+                CastCidrToInet(_) => Ok(impls::CastCidrToInet.into()),
+                CastInetToCidr(_) => Ok(impls::CastInetToCidr.into()),
+                Host(_) => Ok(impls::Host.into()),
+                Masklen(_) => Ok(impls::Masklen.into()),
+                Network(_) => Ok(impls::Network.into()),
+                HllRegister(precision) => Ok(impls::HllRegister {
+                    precision: precision.into_rust()?,
+                }
+                .into()),
+                HllCardinality(_) => Ok(impls::HllCardinality.into()),
+                HllValidate(_) => Ok(impls::HllValidate.into()),
+                CheckPercentileFraction(_) => Ok(impls::CheckPercentileFraction.into()),
                 CeilFloat32(_) => Ok(impls::CeilFloat32.into()),
                 CeilFloat64(_) => Ok(impls::CeilFloat64.into()),
                 CeilNumeric(_) => Ok(impls::CeilNumeric.into()),
@@ -6471,6 +6688,68 @@ fn jsonb_build_object<'a>(
     })
 }
 
+/// Evaluates the path in `datums[1]`, or `path` if it was parsed when the call
+/// was planned, against the `jsonb` value in `datums[0]`, with the variables
+/// in `datums[2]` and the `silent` flag in `datums[3]`.
+fn jsonb_path_query<'a>(
+    datums: &[Datum<'a>],
+    path: Option<&JsonPath>,
+    temp_storage: &'a RowArena,
+) -> Result<Option<Vec<Datum<'a>>>, EvalError> {
+    jsonpath::query(
+        datums[0],
+        path,
+        datums[1].unwrap_str(),
+        datums[2],
+        datums[3].unwrap_bool(),
+        temp_storage,
+    )
+}
+
+fn jsonb_path_exists<'a>(
+    datums: &[Datum<'a>],
+    path: Option<&JsonPath>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    match jsonb_path_query(datums, path, temp_storage)? {
+        Some(items) => Ok(Datum::from(!items.is_empty())),
+        None => Ok(Datum::Null),
+    }
+}
+
+fn jsonb_path_match<'a>(
+    datums: &[Datum<'a>],
+    path: Option<&JsonPath>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    match jsonb_path_query(datums, path, temp_storage)?.as_deref() {
+        None | Some([Datum::JsonNull]) => Ok(Datum::Null),
+        Some([item @ (Datum::True | Datum::False)]) => Ok(*item),
+        Some(_) if datums[3].unwrap_bool() => Ok(Datum::Null),
+        Some(_) => Err(EvalError::JsonPathError(
+            "single boolean result is expected".into(),
+        )),
+    }
+}
+
+fn jsonb_path_query_array<'a>(
+    datums: &[Datum<'a>],
+    path: Option<&JsonPath>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let items = jsonb_path_query(datums, path, temp_storage)?.unwrap_or_default();
+    Ok(temp_storage.make_datum(|packer| packer.push_list(items)))
+}
+
+fn jsonb_path_query_first<'a>(
+    datums: &[Datum<'a>],
+    path: Option<&JsonPath>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let items = jsonb_path_query(datums, path, temp_storage)?.unwrap_or_default();
+    Ok(items.first().copied().unwrap_or(Datum::Null))
+}
+
 fn map_build<'a>(datums: &[Datum<'a>], temp_storage: &'a RowArena) -> Datum<'a> {
     // Collect into a `BTreeMap` to provide the same semantics as it.
     let map: std::collections::BTreeMap<&str, _> = datums
@@ -6630,6 +6909,9 @@ where
         }
         Jsonb => Ok(strconv::format_jsonb(buf, JsonbRef::from_datum(d))),
         Uuid => Ok(strconv::format_uuid(buf, d.unwrap_uuid())),
+        Inet => Ok(strconv::format_inet(buf, d.unwrap_inet())),
+        Cidr => Ok(strconv::format_cidr(buf, d.unwrap_inet())),
+        MacAddr => Ok(strconv::format_macaddr(buf, d.unwrap_macaddr())),
         Record { fields, .. } => {
             let mut fields = fields.iter();
             strconv::format_record(buf, &d.unwrap_list(), |buf, d| {
@@ -6676,6 +6958,10 @@ where
             None => Ok::<_, EvalError>(buf.write_null()),
         }),
         MzAclItem => Ok(strconv::format_mz_acl_item(buf, d.unwrap_mz_acl_item())),
+        Enum { labels, .. } => Ok(strconv::format_string(
+            buf,
+            &labels[usize::cast_from(d.unwrap_uint32())],
+        )),
     }
 }
 
@@ -6893,6 +7179,107 @@ fn make_timestamp<'a>(datums: &[Datum<'a>]) -> Result<Datum<'a>, EvalError> {
     Ok(timestamp.try_into()?)
 }
 
+/// Returns the `width_bucket` bucket for operands that lie outside of the range
+/// between `low` and `high`, or `None` if `operand` lies within it.
+fn width_bucket_outside<T: PartialOrd>(
+    operand: &T,
+    low: &T,
+    high: &T,
+    count: i32,
+) -> Result<Option<i32>, EvalError> {
+    let overflow = || {
+        count
+            .checked_add(1)
+            .ok_or_else(|| EvalError::Int32OutOfRange((i64::from(count) + 1).to_string()))
+    };
+    match low.partial_cmp(high) {
+        Some(Ordering::Less) if operand < low => Ok(Some(0)),
+        Some(Ordering::Less) if operand >= high => overflow().map(Some),
+        Some(Ordering::Greater) if operand > low => Ok(Some(0)),
+        Some(Ordering::Greater) if operand <= high => overflow().map(Some),
+        Some(Ordering::Less | Ordering::Greater) => Ok(None),
+        _ => Err(EvalError::InvalidParameterValue(
+            "lower bound cannot equal upper bound".into(),
+        )),
+    }
+}
+
+fn width_bucket_float64<'a>(datums: &[Datum<'a>]) -> Result<Datum<'a>, EvalError> {
+    let operand = datums[0].unwrap_float64();
+    let low = datums[1].unwrap_float64();
+    let high = datums[2].unwrap_float64();
+    let count = datums[3].unwrap_int32();
+    if count <= 0 {
+        return Err(EvalError::InvalidParameterValue(
+            "count must be greater than zero".into(),
+        ));
+    }
+    if operand.is_nan() || low.is_nan() || high.is_nan() {
+        return Err(EvalError::InvalidParameterValue(
+            "operand, lower bound, and upper bound cannot be NaN".into(),
+        ));
+    }
+    if !low.is_finite() || !high.is_finite() {
+        return Err(EvalError::InvalidParameterValue(
+            "lower and upper bounds must be finite".into(),
+        ));
+    }
+    let bucket = match width_bucket_outside(&operand, &low, &high, count)? {
+        Some(bucket) => bucket,
+        None => {
+            // Halve everything if the width of the range overflows.
+            let fraction = if (high - low).is_finite() {
+                (operand - low) / (high - low)
+            } else {
+                (operand / 2.0 - low / 2.0) / (high / 2.0 - low / 2.0)
+            };
+            // The product can round up to `count` for operands just below the
+            // upper bound, which would put them in the overflow bucket.
+            let bucket =
+                i64::cast_lossy((f64::from(count) * fraction).floor()).min(i64::from(count) - 1);
+            i32::try_from(bucket + 1).expect("bucket is at most count")
+        }
+    };
+    Ok(Datum::from(bucket))
+}
+
+fn width_bucket_numeric<'a>(datums: &[Datum<'a>]) -> Result<Datum<'a>, EvalError> {
+    let operand = datums[0].unwrap_numeric();
+    let low = datums[1].unwrap_numeric();
+    let high = datums[2].unwrap_numeric();
+    let count = datums[3].unwrap_int32();
+    if count <= 0 {
+        return Err(EvalError::InvalidParameterValue(
+            "count must be greater than zero".into(),
+        ));
+    }
+    if operand.0.is_nan() || low.0.is_nan() || high.0.is_nan() {
+        return Err(EvalError::InvalidParameterValue(
+            "operand, lower bound, and upper bound cannot be NaN".into(),
+        ));
+    }
+    let bucket = match width_bucket_outside(&operand, &low, &high, count)? {
+        Some(bucket) => bucket,
+        None => {
+            let mut cx = numeric::cx_datum();
+            let mut offset = operand.0;
+            cx.sub(&mut offset, &low.0);
+            let mut width = high.0;
+            cx.sub(&mut width, &low.0);
+            let mut bucket = Numeric::from(count);
+            cx.mul(&mut bucket, &offset);
+            cx.div(&mut bucket, &width);
+            cx.set_rounding(Rounding::Floor);
+            cx.round(&mut bucket);
+            let bucket = cx
+                .try_into_i32(bucket)
+                .expect("bucket is between zero and count");
+            bucket.min(count - 1) + 1
+        }
+    };
+    Ok(Datum::from(bucket))
+}
+
 fn position<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
     let substring: &'a str = a.unwrap_str();
     let string = b.unwrap_str();
@@ -7525,6 +7912,20 @@ pub enum VariadicFunc {
     TimezoneTime,
     RegexpSplitToArray,
     RegexpReplace,
+    JsonbPathExists {
+        path: Option<JsonPath>,
+    },
+    JsonbPathMatch {
+        path: Option<JsonPath>,
+    },
+    JsonbPathQueryArray {
+        path: Option<JsonPath>,
+    },
+    JsonbPathQueryFirst {
+        path: Option<JsonPath>,
+    },
+    WidthBucketFloat64,
+    WidthBucketNumeric,
 }
 
 impl VariadicFunc {
@@ -7635,6 +8036,20 @@ impl VariadicFunc {
                 };
                 regexp_replace(ds[0], ds[1], ds[2], flags, temp_storage)
             }
+            VariadicFunc::JsonbPathExists { path } => {
+                jsonb_path_exists(&ds, path.as_ref(), temp_storage)
+            }
+            VariadicFunc::JsonbPathMatch { path } => {
+                jsonb_path_match(&ds, path.as_ref(), temp_storage)
+            }
+            VariadicFunc::JsonbPathQueryArray { path } => {
+                jsonb_path_query_array(&ds, path.as_ref(), temp_storage)
+            }
+            VariadicFunc::JsonbPathQueryFirst { path } => {
+                jsonb_path_query_first(&ds, path.as_ref(), temp_storage)
+            }
+            VariadicFunc::WidthBucketFloat64 => width_bucket_float64(&ds),
+            VariadicFunc::WidthBucketNumeric => width_bucket_numeric(&ds),
         }
     }
 
@@ -7681,7 +8096,13 @@ impl VariadicFunc {
             | VariadicFunc::ArrayFill { .. }
             | VariadicFunc::TimezoneTime
             | VariadicFunc::RegexpSplitToArray
-            | VariadicFunc::RegexpReplace => false,
+            | VariadicFunc::RegexpReplace
+            | VariadicFunc::JsonbPathExists { .. }
+            | VariadicFunc::JsonbPathMatch { .. }
+            | VariadicFunc::JsonbPathQueryArray { .. }
+            | VariadicFunc::JsonbPathQueryFirst { .. }
+            | VariadicFunc::WidthBucketFloat64
+            | VariadicFunc::WidthBucketNumeric => false,
         }
     }
 
@@ -7786,6 +8207,10 @@ impl VariadicFunc {
                 ScalarType::Array(Box::new(ScalarType::String)).nullable(in_nullable)
             }
             RegexpReplace => ScalarType::String.nullable(in_nullable),
+            JsonbPathExists { .. } | JsonbPathMatch { .. } => ScalarType::Bool.nullable(true),
+            JsonbPathQueryArray { .. } => ScalarType::Jsonb.nullable(in_nullable),
+            JsonbPathQueryFirst { .. } => ScalarType::Jsonb.nullable(true),
+            WidthBucketFloat64 | WidthBucketNumeric => ScalarType::Int32.nullable(in_nullable),
         }
     }
 
@@ -7857,14 +8282,20 @@ impl VariadicFunc {
             | ArrayFill { .. }
             | TimezoneTime
             | RegexpSplitToArray
-            | RegexpReplace => false,
+            | RegexpReplace
+            | JsonbPathQueryArray { .. }
+            | WidthBucketFloat64
+            | WidthBucketNumeric => false,
             Coalesce
             | Greatest
             | Least
             | MakeTimestamp
             | ArrayIndex { .. }
             | ListIndex
-            | RegexpMatch => true,
+            | RegexpMatch
+            | JsonbPathExists { .. }
+            | JsonbPathMatch { .. }
+            | JsonbPathQueryFirst { .. } => true,
         }
     }
 
@@ -7963,7 +8394,13 @@ impl VariadicFunc {
             | VariadicFunc::DateDiffTime
             | VariadicFunc::TimezoneTime
             | VariadicFunc::RegexpSplitToArray
-            | VariadicFunc::RegexpReplace => false,
+            | VariadicFunc::RegexpReplace
+            | VariadicFunc::JsonbPathExists { .. }
+            | VariadicFunc::JsonbPathMatch { .. }
+            | VariadicFunc::JsonbPathQueryArray { .. }
+            | VariadicFunc::JsonbPathQueryFirst { .. }
+            | VariadicFunc::WidthBucketFloat64
+            | VariadicFunc::WidthBucketNumeric => false,
         }
     }
 }
@@ -8021,6 +8458,13 @@ impl fmt::Display for VariadicFunc {
             VariadicFunc::TimezoneTime => f.write_str("timezonet"),
             VariadicFunc::RegexpSplitToArray => f.write_str("regexp_split_to_array"),
             VariadicFunc::RegexpReplace => f.write_str("regexp_replace"),
+            VariadicFunc::JsonbPathExists { .. } => f.write_str("jsonb_path_exists"),
+            VariadicFunc::JsonbPathMatch { .. } => f.write_str("jsonb_path_match"),
+            VariadicFunc::JsonbPathQueryArray { .. } => f.write_str("jsonb_path_query_array"),
+            VariadicFunc::JsonbPathQueryFirst { .. } => f.write_str("jsonb_path_query_first"),
+            VariadicFunc::WidthBucketFloat64 | VariadicFunc::WidthBucketNumeric => {
+                f.write_str("width_bucket")
+            }
         }
     }
 }
@@ -8091,6 +8535,20 @@ impl Arbitrary for VariadicFunc {
             ScalarType::arbitrary()
                 .prop_map(|elem_type| VariadicFunc::ArrayFill { elem_type })
                 .boxed(),
+            jsonpath::any_json_path()
+                .prop_map(|path| VariadicFunc::JsonbPathExists { path })
+                .boxed(),
+            jsonpath::any_json_path()
+                .prop_map(|path| VariadicFunc::JsonbPathMatch { path })
+                .boxed(),
+            jsonpath::any_json_path()
+                .prop_map(|path| VariadicFunc::JsonbPathQueryArray { path })
+                .boxed(),
+            jsonpath::any_json_path()
+                .prop_map(|path| VariadicFunc::JsonbPathQueryFirst { path })
+                .boxed(),
+            Just(VariadicFunc::WidthBucketFloat64).boxed(),
+            Just(VariadicFunc::WidthBucketNumeric).boxed(),
         ])
     }
 }
@@ -8143,6 +8601,12 @@ impl RustType<ProtoVariadicFunc> for VariadicFunc {
             VariadicFunc::TimezoneTime => TimezoneTime(()),
             VariadicFunc::RegexpSplitToArray => RegexpSplitToArray(()),
             VariadicFunc::RegexpReplace => RegexpReplace(()),
+            VariadicFunc::JsonbPathExists { path } => JsonbPathExists(path.into_proto()),
+            VariadicFunc::JsonbPathMatch { path } => JsonbPathMatch(path.into_proto()),
+            VariadicFunc::JsonbPathQueryArray { path } => JsonbPathQueryArray(path.into_proto()),
+            VariadicFunc::JsonbPathQueryFirst { path } => JsonbPathQueryFirst(path.into_proto()),
+            VariadicFunc::WidthBucketFloat64 => WidthBucketFloat64(()),
+            VariadicFunc::WidthBucketNumeric => WidthBucketNumeric(()),
         };
         ProtoVariadicFunc { kind: Some(kind) }
     }
@@ -8209,6 +8673,20 @@ impl RustType<ProtoVariadicFunc> for VariadicFunc {
                 TimezoneTime(()) => Ok(VariadicFunc::TimezoneTime),
                 RegexpSplitToArray(()) => Ok(VariadicFunc::RegexpSplitToArray),
                 RegexpReplace(()) => Ok(VariadicFunc::RegexpReplace),
+                JsonbPathExists(path) => Ok(VariadicFunc::JsonbPathExists {
+                    path: path.into_rust()?,
+                }),
+                JsonbPathMatch(path) => Ok(VariadicFunc::JsonbPathMatch {
+                    path: path.into_rust()?,
+                }),
+                JsonbPathQueryArray(path) => Ok(VariadicFunc::JsonbPathQueryArray {
+                    path: path.into_rust()?,
+                }),
+                JsonbPathQueryFirst(path) => Ok(VariadicFunc::JsonbPathQueryFirst {
+                    path: path.into_rust()?,
+                }),
+                WidthBucketFloat64(()) => Ok(VariadicFunc::WidthBucketFloat64),
+                WidthBucketNumeric(()) => Ok(VariadicFunc::WidthBucketNumeric),
             }
         } else {
             Err(TryFromProtoError::missing_field(
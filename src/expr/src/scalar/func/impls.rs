@@ -13,8 +13,10 @@ mod byte;
 mod char;
 mod date;
 mod datum;
+mod enums;
 mod float32;
 mod float64;
+mod hll;
 mod int16;
 mod int2vector;
 mod int32;
@@ -25,6 +27,7 @@ mod list;
 mod map;
 mod mz_acl_item;
 mod mz_timestamp;
+mod network;
 mod numeric;
 mod oid;
 mod pg_legacy_char;
@@ -46,8 +49,10 @@ pub use crate::scalar::func::impls::byte::*;
 pub use crate::scalar::func::impls::char::*;
 pub use crate::scalar::func::impls::date::*;
 pub use crate::scalar::func::impls::datum::*;
+pub use crate::scalar::func::impls::enums::*;
 pub use crate::scalar::func::impls::float32::*;
 pub use crate::scalar::func::impls::float64::*;
+pub use crate::scalar::func::impls::hll::*;
 pub use crate::scalar::func::impls::int16::*;
 pub use crate::scalar::func::impls::int2vector::*;
 pub use crate::scalar::func::impls::int32::*;
@@ -58,6 +63,7 @@ pub use crate::scalar::func::impls::list::*;
 pub use crate::scalar::func::impls::map::*;
 pub use crate::scalar::func::impls::mz_acl_item::*;
 pub use crate::scalar::func::impls::mz_timestamp::*;
+pub use crate::scalar::func::impls::network::*;
 pub use crate::scalar::func::impls::numeric::*;
 pub use crate::scalar::func::impls::oid::*;
 pub use crate::scalar::func::impls::pg_legacy_char::*;
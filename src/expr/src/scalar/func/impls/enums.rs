@@ -0,0 +1,106 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::fmt;
+
+use mz_lowertest::MzReflect;
+use mz_ore::cast::CastFrom;
+use mz_ore::str::StrExt;
+use mz_repr::{ColumnType, ScalarType};
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+use crate::scalar::func::EagerUnaryFunc;
+use crate::EvalError;
+
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct CastEnumToString {
+    /// The labels of the enum type, in declaration order.
+    pub labels: Vec<String>,
+}
+
+impl<'a> EagerUnaryFunc<'a> for CastEnumToString {
+    type Input = u32;
+    type Output = Result<String, EvalError>;
+
+    fn call(&self, a: u32) -> Result<String, EvalError> {
+        match self.labels.get(usize::cast_from(a)) {
+            Some(label) => Ok(label.clone()),
+            None => Err(EvalError::Internal(format!(
+                "invalid ordinal {} for enum with {} labels",
+                a,
+                self.labels.len()
+            ))),
+        }
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        ScalarType::String.nullable(input.nullable)
+    }
+
+    fn preserves_uniqueness(&self) -> bool {
+        true
+    }
+}
+
+impl fmt::Display for CastEnumToString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("enum_to_text")
+    }
+}
+
+#[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect)]
+pub struct CastStringToEnum {
+    /// The target enum type.
+    pub return_ty: ScalarType,
+}
+
+impl<'a> EagerUnaryFunc<'a> for CastStringToEnum {
+    type Input = &'a str;
+    type Output = Result<u32, EvalError>;
+
+    fn call(&self, a: &'a str) -> Result<u32, EvalError> {
+        let labels = match &self.return_ty {
+            ScalarType::Enum { labels, .. } => labels,
+            ty => unreachable!("CastStringToEnum to non-enum type {ty:?}"),
+        };
+        match labels.iter().position(|l| l == a) {
+            Some(position) => Ok(u32::try_from(position).expect("enum labels fit in u32")),
+            None => Err(EvalError::InvalidParameterValue(format!(
+                "invalid input value for enum: {}",
+                a.quoted()
+            ))),
+        }
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        self.return_ty.clone().nullable(input.nullable)
+    }
+
+    fn preserves_uniqueness(&self) -> bool {
+        true
+    }
+
+    fn inverse(&self) -> Option<crate::UnaryFunc> {
+        match &self.return_ty {
+            ScalarType::Enum { labels, .. } => to_unary!(super::CastEnumToString {
+                labels: labels.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CastStringToEnum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("text_to_enum")
+    }
+}
@@ -14,7 +14,8 @@ use mz_lowertest::MzReflect;
 use mz_ore::cast::TryCastFrom;
 use mz_repr::adt::numeric::{self, Numeric, NumericMaxScale};
 use mz_repr::adt::timestamp::CheckedTimestamp;
-use mz_repr::{strconv, ColumnType, ScalarType};
+use mz_repr::{strconv, ColumnType, Datum, ScalarType};
+use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
 
 use crate::scalar::func::EagerUnaryFunc;
@@ -494,3 +495,55 @@ sqlfunc!(
         }
     }
 );
+
+/// Checks that the fraction of `percentile_cont` or `percentile_disc`, or each
+/// element of an array of fractions, is between 0 and 1.
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct CheckPercentileFraction;
+
+impl<'a> EagerUnaryFunc<'a> for CheckPercentileFraction {
+    type Input = Datum<'a>;
+    type Output = Result<Datum<'a>, EvalError>;
+
+    fn call(&self, a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+        let check = |fraction: Datum| match fraction {
+            Datum::Float64(f) if !(0.0..=1.0).contains(&*f) => {
+                let mut buf = String::new();
+                strconv::format_float64(&mut buf, *f);
+                Err(EvalError::InvalidParameterValue(format!(
+                    "percentile value {buf} is not between 0 and 1"
+                )))
+            }
+            _ => Ok(()),
+        };
+        match a {
+            Datum::Array(fractions) => fractions.elements().iter().try_for_each(check)?,
+            fraction => check(fraction)?,
+        }
+        Ok(a)
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        input
+    }
+
+    fn propagates_nulls(&self) -> bool {
+        true
+    }
+
+    fn introduces_nulls(&self) -> bool {
+        false
+    }
+
+    fn preserves_uniqueness(&self) -> bool {
+        true
+    }
+}
+
+impl fmt::Display for CheckPercentileFraction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("check_percentile_fraction")
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::fmt;
+
+use mz_lowertest::MzReflect;
+use mz_repr::{ColumnType, Datum, ScalarType};
+use serde::{Deserialize, Serialize};
+
+use crate::hll::{self, Sketch};
+use crate::scalar::func::EagerUnaryFunc;
+use crate::EvalError;
+
+/// Maps a value to its HyperLogLog register and rank, as described in
+/// [`hll::register_of`].
+#[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect)]
+pub struct HllRegister {
+    pub precision: u8,
+}
+
+impl<'a> EagerUnaryFunc<'a> for HllRegister {
+    type Input = Datum<'a>;
+    type Output = Option<i32>;
+
+    fn call(&self, a: Datum<'a>) -> Option<i32> {
+        if a.is_null() {
+            None
+        } else {
+            Some(hll::register_of(a, self.precision))
+        }
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        ScalarType::Int32.nullable(input.nullable)
+    }
+
+    fn propagates_nulls(&self) -> bool {
+        true
+    }
+
+    fn introduces_nulls(&self) -> bool {
+        false
+    }
+}
+
+impl fmt::Display for HllRegister {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "hll_register[{}]", self.precision)
+    }
+}
+
+sqlfunc!(
+    fn hll_cardinality<'a>(a: &'a [u8]) -> Result<i64, EvalError> {
+        Sketch::from_bytes(a)
+            .map(|sketch| sketch.estimate())
+            .map_err(|e| EvalError::InvalidParameterValue(e.to_string()))
+    }
+);
+
+sqlfunc!(
+    fn hll_validate<'a>(a: &'a [u8]) -> Result<&'a [u8], EvalError> {
+        Sketch::from_bytes(a)
+            .map(|_| a)
+            .map_err(|e| EvalError::InvalidParameterValue(e.to_string()))
+    }
+);
@@ -19,6 +19,7 @@ use mz_repr::adt::char::{format_str_trim, Char};
 use mz_repr::adt::date::Date;
 use mz_repr::adt::interval::Interval;
 use mz_repr::adt::jsonb::Jsonb;
+use mz_repr::adt::network::{Inet, MacAddr};
 use mz_repr::adt::numeric::{self, Numeric, NumericMaxScale};
 use mz_repr::adt::pg_legacy_name::PgLegacyName;
 use mz_repr::adt::regex::Regex;
@@ -300,6 +301,52 @@ sqlfunc!(
     }
 );
 
+sqlfunc!(
+    #[sqlname = "text_to_inet"]
+    #[preserves_uniqueness = false]
+    #[inverse = to_unary!(super::CastInetToString)]
+    fn cast_string_to_inet<'a>(a: &'a str) -> Result<Inet, EvalError> {
+        strconv::parse_inet(a).err_into()
+    }
+);
+
+#[derive(
+    Arbitrary, Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect,
+)]
+pub struct CastStringToCidr;
+
+impl<'a> EagerUnaryFunc<'a> for CastStringToCidr {
+    type Input = &'a str;
+    type Output = Result<Inet, EvalError>;
+
+    fn call(&self, a: &'a str) -> Result<Inet, EvalError> {
+        strconv::parse_cidr(a).err_into()
+    }
+
+    fn output_type(&self, input: ColumnType) -> ColumnType {
+        ScalarType::Cidr.nullable(input.nullable)
+    }
+
+    fn inverse(&self) -> Option<crate::UnaryFunc> {
+        to_unary!(super::CastCidrToString)
+    }
+}
+
+impl fmt::Display for CastStringToCidr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("text_to_cidr")
+    }
+}
+
+sqlfunc!(
+    #[sqlname = "text_to_macaddr"]
+    #[preserves_uniqueness = false]
+    #[inverse = to_unary!(super::CastMacAddrToString)]
+    fn cast_string_to_mac_addr<'a>(a: &'a str) -> Result<MacAddr, EvalError> {
+        strconv::parse_macaddr(a).err_into()
+    }
+);
+
 #[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, MzReflect)]
 pub struct CastStringToArray {
     // Target array's type.
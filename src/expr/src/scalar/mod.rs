@@ -2467,6 +2467,7 @@ pub enum EvalError {
     AclArrayNullElement,
     MzAclArrayNullElement,
     PrettyError(String),
+    JsonPathError(String),
 }
 
 impl fmt::Display for EvalError {
@@ -2569,6 +2570,7 @@ impl fmt::Display for EvalError {
             }
             EvalError::Parse(e) => e.fmt(f),
             EvalError::PrettyError(e) => e.fmt(f),
+            EvalError::JsonPathError(e) => e.fmt(f),
             EvalError::ParseHex(e) => e.fmt(f),
             EvalError::Internal(s) => write!(f, "internal error: {}", s),
             EvalError::InfinityOutOfDomain(s) => {
@@ -2864,6 +2866,7 @@ impl RustType<ProtoEvalError> for EvalError {
             EvalError::UnterminatedLikeEscapeSequence => UnterminatedLikeEscapeSequence(()),
             EvalError::Parse(error) => Parse(error.into_proto()),
             EvalError::PrettyError(error) => PrettyError(error.into_proto()),
+            EvalError::JsonPathError(error) => JsonPathError(error.into_proto()),
             EvalError::ParseHex(error) => ParseHex(error.into_proto()),
             EvalError::Internal(v) => Internal(v.clone()),
             EvalError::InfinityOutOfDomain(v) => InfinityOutOfDomain(v.clone()),
@@ -3044,6 +3047,7 @@ impl RustType<ProtoEvalError> for EvalError {
                 MzAclArrayNullElement(()) => Ok(EvalError::MzAclArrayNullElement),
                 InvalidIanaTimezoneId(s) => Ok(EvalError::InvalidIanaTimezoneId(s)),
                 PrettyError(s) => Ok(EvalError::PrettyError(s)),
+                JsonPathError(s) => Ok(EvalError::JsonPathError(s)),
             },
             None => Err(TryFromProtoError::missing_field("ProtoEvalError::kind")),
         }
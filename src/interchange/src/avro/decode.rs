@@ -16,8 +16,8 @@ use anyhow::{Context, Error};
 use mz_avro::error::{DecodeError, Error as AvroError};
 use mz_avro::{
     define_unexpected, give_value, AvroArrayAccess, AvroDecode, AvroDeserializer, AvroMapAccess,
-    AvroRead, AvroRecordAccess, GeneralDeserializer, StatefulAvroDecodable, ValueDecoder,
-    ValueOrReader,
+    AvroRead, AvroRecordAccess, BlockIter, GeneralDeserializer, Schema, StatefulAvroDecodable,
+    ValueDecoder, ValueOrReader,
 };
 use mz_ore::error::ErrorExt;
 use mz_repr::adt::date::Date;
@@ -29,7 +29,7 @@ use ordered_float::OrderedFloat;
 use tracing::trace;
 use uuid::Uuid;
 
-use crate::avro::ConfluentAvroResolver;
+use crate::avro::{parse_schema, ConfluentAvroResolver};
 
 /// Manages decoding of Avro-encoded bytes.
 #[derive(Debug)]
@@ -42,9 +42,11 @@ pub struct Decoder {
 
 #[cfg(test)]
 mod tests {
+    use mz_avro::types::Value;
+    use mz_avro::{Codec, Writer};
     use mz_repr::{Datum, Row};
 
-    use crate::avro::Decoder;
+    use crate::avro::{parse_schema, Decoder, OcfDecoder};
 
     #[mz_ore::test(tokio::test)]
     async fn test_error_followed_by_success() {
@@ -65,6 +67,44 @@ mod tests {
             Row::pack([Datum::Int32(0), Datum::Int32(0)])
         );
     }
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function `deflateInit2_` on OS `linux`
+    fn test_ocf_schema_resolution() {
+        let writer_schema = r#"{
+"type": "record",
+"name": "test",
+"fields": [{"name": "f1", "type": "int"}, {"name": "f2", "type": "string"}]
+}"#;
+        let reader_schema = r#"{
+"type": "record",
+"name": "test",
+"fields": [{"name": "f1", "type": "long"}, {"name": "f3", "type": "string", "default": "x"}]
+}"#;
+        let mut writer =
+            Writer::with_codec(parse_schema(writer_schema).unwrap(), vec![], Codec::Deflate);
+        for (f1, f2) in [(1, "a"), (2, "b")] {
+            writer
+                .append(Value::Record(vec![
+                    ("f1".into(), Value::Int(f1)),
+                    ("f2".into(), Value::String(f2.into())),
+                ]))
+                .unwrap();
+        }
+        writer.flush().unwrap();
+        let bytes = writer.into_inner();
+
+        let mut decoder = OcfDecoder::new(reader_schema, "Test".to_string()).unwrap();
+        assert_eq!(
+            decoder.decode(&bytes).unwrap(),
+            vec![
+                Row::pack([Datum::Int64(1), Datum::String("x")]),
+                Row::pack([Datum::Int64(2), Datum::String("x")]),
+            ]
+        );
+        // A blob that is not an object container file is an error.
+        assert!(decoder.decode(&[0, 0]).is_err());
+    }
 }
 
 impl Decoder {
@@ -134,6 +174,68 @@ impl Decoder {
     }
 }
 
+/// Manages decoding of Avro object container files.
+///
+/// Unlike [`Decoder`], which decodes a single datum per message, every message
+/// handed to an `OcfDecoder` is an entire object container file that embeds the
+/// schema it was written with and contains any number of records.
+#[derive(Debug)]
+pub struct OcfDecoder {
+    reader_schema: Schema,
+    debug_name: String,
+    buf1: Vec<u8>,
+    row_buf: Row,
+}
+
+impl OcfDecoder {
+    /// Creates a new `OcfDecoder`.
+    ///
+    /// The writer schema embedded in each file is resolved against the provided
+    /// reader schema, which determines the shape of the decoded rows.
+    pub fn new(reader_schema: &str, debug_name: String) -> anyhow::Result<OcfDecoder> {
+        Ok(OcfDecoder {
+            reader_schema: parse_schema(reader_schema)?,
+            debug_name,
+            buf1: vec![],
+            row_buf: Row::default(),
+        })
+    }
+
+    /// Decodes every record of the object container file in `bytes` into a
+    /// `Row`.
+    pub fn decode(&mut self, bytes: &[u8]) -> Result<Vec<Row>, Error> {
+        let blocks = BlockIter::with_schema(&self.reader_schema, bytes)
+            .context("unable to read object container file header")?;
+        let resolved_schema = blocks.schema().clone();
+
+        let mut rows = vec![];
+        for block in blocks {
+            let block = block.context("unable to read object container file block")?;
+            let mut block_bytes = &block.bytes[..];
+            for _ in 0..block.len {
+                let mut packer = self.row_buf.packer();
+                let dec = AvroFlatDecoder {
+                    packer: &mut packer,
+                    buf: &mut self.buf1,
+                    is_top: true,
+                };
+                let dsr = GeneralDeserializer {
+                    schema: resolved_schema.top_node(),
+                };
+                dsr.deserialize(&mut block_bytes, dec)
+                    .with_context(|| format!("unable to decode row {}", rows.len()))?;
+                trace!(
+                    "[customer-data] Decoded row {:?} in {}",
+                    self.row_buf,
+                    self.debug_name
+                );
+                rows.push(self.row_buf.clone());
+            }
+        }
+        Ok(rows)
+    }
+}
+
 pub struct AvroStringDecoder<'a> {
     pub buf: &'a mut Vec<u8>,
 }
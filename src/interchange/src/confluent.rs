@@ -52,6 +52,10 @@ pub fn extract_avro_header(buf: &[u8]) -> Result<(i32, &[u8])> {
     extract_schema_id(buf, "avro")
 }
 
+pub fn extract_json_header(buf: &[u8]) -> Result<(i32, &[u8])> {
+    extract_schema_id(buf, "json")
+}
+
 pub fn extract_protobuf_header(buf: &[u8]) -> Result<(i32, &[u8])> {
     let (schema_id, buf) = extract_schema_id(buf, "protobuf")?;
 
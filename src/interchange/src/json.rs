@@ -10,6 +10,7 @@
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt;
 
+use mz_ore::cast::CastFrom;
 use mz_repr::adt::array::ArrayDimension;
 use mz_repr::adt::char;
 use mz_repr::adt::jsonb::JsonbRef;
@@ -258,6 +259,12 @@ impl ToJson for TypedDatum<'_> {
                 json!(datum.unwrap_range().to_string())
             }
             ScalarType::MzAclItem => json!(datum.unwrap_mz_acl_item().to_string()),
+            ScalarType::Enum { labels, .. } => {
+                json!(labels[usize::cast_from(datum.unwrap_uint32())])
+            }
+            ScalarType::Inet => json!(datum.unwrap_inet().to_string()),
+            ScalarType::Cidr => json!(datum.unwrap_inet().to_cidr_string()),
+            ScalarType::MacAddr => json!(datum.unwrap_macaddr().to_string()),
         };
         // We don't need to recurse into map or object here because those already recursively call
         // .json() with the number policy to generate the member Values.
@@ -422,6 +429,8 @@ fn build_row_schema_field_type(
         // https://debezium.io/documentation/reference/stable/connectors/postgresql.html
         ScalarType::Range { .. } => json!("string"),
         ScalarType::MzAclItem => json!("string"),
+        ScalarType::Enum { .. } => json!("string"),
+        ScalarType::Inet | ScalarType::Cidr | ScalarType::MacAddr => json!("string"),
     };
     if typ.nullable {
         // Should be revisited if we ever support a different kind of union scheme.
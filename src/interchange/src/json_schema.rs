@@ -0,0 +1,547 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Decoding of JSON documents into the typed columns described by a JSON
+//! Schema.
+//!
+//! JSON Schema is a validation language rather than a type system, so only
+//! the parts of it that describe the shape of a document are used to derive
+//! column types: objects with `properties` become records, arrays with
+//! `items` become lists, and well-known string `format`s like `date-time`
+//! become the corresponding temporal types. Subschemas that cannot be mapped
+//! onto a single SQL type are represented as `jsonb`.
+
+use std::collections::BTreeSet;
+
+use anyhow::{anyhow, bail, Context};
+use mz_ore::str::StrExt;
+use mz_repr::adt::jsonb::JsonbPacker;
+use mz_repr::{strconv, ColumnName, ColumnType, Datum, Row, RowPacker, ScalarType};
+use serde_json::{Map, Value};
+
+/// A decoded description of the shape of the documents matched by a JSON
+/// Schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedSchema {
+    fields: Vec<Field>,
+    columns: Vec<(ColumnName, ColumnType)>,
+}
+
+impl DecodedSchema {
+    /// Derives the shape of the documents matched by the JSON Schema in
+    /// `schema`.
+    ///
+    /// The schema must describe an object with `properties`, each of which
+    /// becomes a column.
+    pub fn from_json_str(schema: &str) -> Result<Self, anyhow::Error> {
+        let root: Value = serde_json::from_str(schema).context("parsing JSON schema")?;
+        let mut deriver = Deriver {
+            root: &root,
+            seen_refs: BTreeSet::new(),
+        };
+        let fields = match deriver.derive(&root)?.ty {
+            JsonType::Record(fields) => fields,
+            _ => bail!("JSON schema must describe an object with properties"),
+        };
+        let columns = fields
+            .iter()
+            .map(|f| (ColumnName::from(&*f.name), f.column_type()))
+            .collect();
+        Ok(DecodedSchema { fields, columns })
+    }
+
+    /// Describes the columns in the documents.
+    ///
+    /// In other words, the return value describes the shape of the rows that
+    /// will be produced by a [`Decoder`] constructed from this
+    /// `DecodedSchema`.
+    pub fn columns(&self) -> &[(ColumnName, ColumnType)] {
+        &self.columns
+    }
+}
+
+/// Decodes JSON documents into rows that match a [`DecodedSchema`].
+#[derive(Debug)]
+pub struct Decoder {
+    schema: DecodedSchema,
+    row: Row,
+    confluent_wire_format: bool,
+}
+
+impl Decoder {
+    /// Constructs a decoder for the documents described by `schema`.
+    pub fn new(schema: DecodedSchema, confluent_wire_format: bool) -> Self {
+        Decoder {
+            schema,
+            row: Row::default(),
+            confluent_wire_format,
+        }
+    }
+
+    /// Decodes the JSON document in `bytes` into a [`Row`].
+    ///
+    /// Documents that do not match the schema, e.g. because a property has
+    /// the wrong type or a required property is missing, produce an error.
+    pub fn decode(&mut self, mut bytes: &[u8]) -> Result<Row, anyhow::Error> {
+        if self.confluent_wire_format {
+            // Like for Protobuf, we ignore the schema that the document was
+            // written with and instead validate it against the schema we
+            // know about.
+            let (_schema_id, adjusted_bytes) = crate::confluent::extract_json_header(bytes)?;
+            bytes = adjusted_bytes;
+        }
+        let value: Value = serde_json::from_slice(bytes).context("parsing JSON")?;
+        let object = match &value {
+            Value::Object(object) => object,
+            _ => bail!("expected object at document root, found {}", kind(&value)),
+        };
+        let mut packer = self.row.packer();
+        pack_fields(&mut packer, &self.schema.fields, object, "")?;
+        Ok(self.row.clone())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Field {
+    name: String,
+    ty: JsonType,
+    /// Whether the property must be present in the document.
+    required: bool,
+    /// Whether the property may be `null`.
+    allows_null: bool,
+}
+
+impl Field {
+    fn column_type(&self) -> ColumnType {
+        self.ty
+            .scalar_type()
+            .nullable(!self.required || self.allows_null)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum JsonType {
+    Bool,
+    Int64,
+    Float64,
+    String,
+    TimestampTz,
+    Date,
+    Time,
+    Uuid,
+    List {
+        element: Box<JsonType>,
+        allows_null: bool,
+    },
+    Record(Vec<Field>),
+    Jsonb,
+}
+
+impl JsonType {
+    fn scalar_type(&self) -> ScalarType {
+        match self {
+            JsonType::Bool => ScalarType::Bool,
+            JsonType::Int64 => ScalarType::Int64,
+            JsonType::Float64 => ScalarType::Float64,
+            JsonType::String => ScalarType::String,
+            JsonType::TimestampTz => ScalarType::TimestampTz { precision: None },
+            JsonType::Date => ScalarType::Date,
+            JsonType::Time => ScalarType::Time,
+            JsonType::Uuid => ScalarType::Uuid,
+            JsonType::List { element, .. } => ScalarType::List {
+                element_type: Box::new(element.scalar_type()),
+                custom_id: None,
+            },
+            JsonType::Record(fields) => ScalarType::Record {
+                fields: fields
+                    .iter()
+                    .map(|f| (ColumnName::from(&*f.name), f.column_type()))
+                    .collect(),
+                custom_id: None,
+            },
+            JsonType::Jsonb => ScalarType::Jsonb,
+        }
+    }
+}
+
+/// The type of a subschema and whether it allows `null`.
+struct Derived {
+    ty: JsonType,
+    allows_null: bool,
+}
+
+struct Deriver<'a> {
+    root: &'a Value,
+    /// The `$ref`s that are currently being derived, to detect recursive
+    /// schemas.
+    seen_refs: BTreeSet<&'a str>,
+}
+
+impl<'a> Deriver<'a> {
+    fn derive(&mut self, schema: &'a Value) -> Result<Derived, anyhow::Error> {
+        let schema = match schema {
+            // The `true` schema matches any document.
+            Value::Bool(true) => {
+                return Ok(Derived {
+                    ty: JsonType::Jsonb,
+                    allows_null: true,
+                })
+            }
+            Value::Bool(false) => bail!("JSON schema false matches no documents"),
+            Value::Object(schema) => schema,
+            _ => bail!("JSON schema must be an object or a boolean"),
+        };
+
+        if let Some(reference) = schema.get("$ref") {
+            return self.derive_ref(reference);
+        }
+
+        let types = match schema.get("type") {
+            Some(Value::String(ty)) => vec![ty.as_str()],
+            Some(Value::Array(types)) => types
+                .iter()
+                .map(|ty| {
+                    ty.as_str()
+                        .ok_or_else(|| anyhow!("JSON schema type must be a string"))
+                })
+                .collect::<Result<_, _>>()?,
+            Some(_) => bail!("JSON schema type must be a string or an array of strings"),
+            None => return self.derive_untyped(schema),
+        };
+        let allows_null = types.contains(&"null");
+        let ty = match types.iter().filter(|ty| **ty != "null").collect::<Vec<_>>()[..] {
+            [ty] => self.derive_type(ty, schema)?,
+            [] => bail!("JSON schemas that only match null are not supported"),
+            // Properties that can hold several types of values have no
+            // corresponding SQL type.
+            _ => JsonType::Jsonb,
+        };
+        Ok(Derived { ty, allows_null })
+    }
+
+    fn derive_ref(&mut self, reference: &'a Value) -> Result<Derived, anyhow::Error> {
+        let reference = reference
+            .as_str()
+            .ok_or_else(|| anyhow!("JSON schema $ref must be a string"))?;
+        let pointer = reference.strip_prefix('#').ok_or_else(|| {
+            anyhow!(
+                "JSON schema $ref {} does not refer to the same schema",
+                reference.quoted()
+            )
+        })?;
+        let schema = self.root.pointer(pointer).ok_or_else(|| {
+            anyhow!(
+                "JSON schema $ref {} does not refer to a subschema",
+                reference.quoted()
+            )
+        })?;
+        if !self.seen_refs.insert(reference) {
+            bail!(
+                "recursive JSON schemas are not supported: {}",
+                reference.quoted()
+            );
+        }
+        let derived = self.derive(schema)?;
+        self.seen_refs.remove(reference);
+        Ok(derived)
+    }
+
+    /// Derives the type of a subschema without a `type` keyword.
+    fn derive_untyped(&mut self, schema: &'a Map<String, Value>) -> Result<Derived, anyhow::Error> {
+        // A union of a single schema with `null` is the usual way to describe
+        // an optional value, e.g. `{"anyOf": [{"type": "null"}, {...}]}`.
+        if let Some(Value::Array(branches)) = schema.get("anyOf").or_else(|| schema.get("oneOf")) {
+            let is_null = |s: &Value| s.get("type") == Some(&Value::String("null".into()));
+            let allows_null = branches.iter().any(is_null);
+            let branches: Vec<_> = branches.iter().filter(|s| !is_null(*s)).collect();
+            if let [branch] = branches[..] {
+                let derived = self.derive(branch)?;
+                return Ok(Derived {
+                    ty: derived.ty,
+                    allows_null: allows_null || derived.allows_null,
+                });
+            }
+        }
+
+        let ty = if schema.contains_key("properties") {
+            self.derive_type("object", schema)?
+        } else if schema.contains_key("items") {
+            self.derive_type("array", schema)?
+        } else {
+            match schema.get("enum") {
+                Some(Value::Array(values)) if values.iter().all(|v| v.is_string()) => {
+                    JsonType::String
+                }
+                _ => JsonType::Jsonb,
+            }
+        };
+        let allows_null = matches!(ty, JsonType::Jsonb);
+        Ok(Derived { ty, allows_null })
+    }
+
+    fn derive_type(
+        &mut self,
+        ty: &str,
+        schema: &'a Map<String, Value>,
+    ) -> Result<JsonType, anyhow::Error> {
+        let ty = match ty {
+            "boolean" => JsonType::Bool,
+            "integer" => JsonType::Int64,
+            "number" => JsonType::Float64,
+            "string" => match schema.get("format").and_then(|f| f.as_str()) {
+                Some("date-time") => JsonType::TimestampTz,
+                Some("date") => JsonType::Date,
+                Some("time") => JsonType::Time,
+                Some("uuid") => JsonType::Uuid,
+                _ => JsonType::String,
+            },
+            "array" => match schema.get("items") {
+                // Tuple validation describes heterogeneous arrays.
+                Some(items @ (Value::Object(_) | Value::Bool(_))) => {
+                    let element = self.derive(items)?;
+                    JsonType::List {
+                        element: Box::new(element.ty),
+                        allows_null: element.allows_null,
+                    }
+                }
+                Some(_) => JsonType::Jsonb,
+                None => JsonType::List {
+                    element: Box::new(JsonType::Jsonb),
+                    allows_null: true,
+                },
+            },
+            "object" => {
+                let properties = match schema.get("properties") {
+                    Some(Value::Object(properties)) if !properties.is_empty() => properties,
+                    Some(Value::Object(_)) | None => return Ok(JsonType::Jsonb),
+                    Some(_) => bail!("JSON schema properties must be an object"),
+                };
+                let required: BTreeSet<_> = match schema.get("required") {
+                    Some(Value::Array(required)) => {
+                        required.iter().filter_map(|r| r.as_str()).collect()
+                    }
+                    _ => BTreeSet::new(),
+                };
+                let mut fields = Vec::with_capacity(properties.len());
+                for (name, property) in properties {
+                    let derived = self
+                        .derive(property)
+                        .with_context(|| format!("deriving type of property {}", name.quoted()))?;
+                    fields.push(Field {
+                        name: name.clone(),
+                        ty: derived.ty,
+                        required: required.contains(name.as_str()),
+                        allows_null: derived.allows_null,
+                    });
+                }
+                JsonType::Record(fields)
+            }
+            ty => bail!("unknown JSON schema type {}", ty.quoted()),
+        };
+        Ok(ty)
+    }
+}
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn pack_fields(
+    packer: &mut RowPacker,
+    fields: &[Field],
+    object: &Map<String, Value>,
+    path: &str,
+) -> Result<(), anyhow::Error> {
+    for field in fields {
+        let path = format!("{path}/{}", field.name);
+        match object.get(&field.name) {
+            None if field.required => bail!("missing required property at {path}"),
+            None => packer.push(Datum::Null),
+            Some(Value::Null) if !field.allows_null && !matches!(field.ty, JsonType::Jsonb) => {
+                bail!("unexpected null at {path}")
+            }
+            Some(value) => pack_value(packer, &field.ty, value, &path)?,
+        }
+    }
+    Ok(())
+}
+
+fn pack_value(
+    packer: &mut RowPacker,
+    ty: &JsonType,
+    value: &Value,
+    path: &str,
+) -> Result<(), anyhow::Error> {
+    let mismatch = |expected: &str| anyhow!("expected {expected} at {path}, found {}", kind(value));
+    let invalid = |e: strconv::ParseError| anyhow!("invalid value at {path}: {e}");
+    match (ty, value) {
+        (JsonType::Jsonb, value) => JsonbPacker::new(packer).pack_serde_json(value.clone())?,
+        (_, Value::Null) => packer.push(Datum::Null),
+        (JsonType::Bool, Value::Bool(b)) => packer.push(Datum::from(*b)),
+        (JsonType::Int64, Value::Number(n)) => {
+            let n = n
+                .as_i64()
+                .ok_or_else(|| anyhow!("expected 64-bit integer at {path}, found {n}"))?;
+            packer.push(Datum::Int64(n));
+        }
+        (JsonType::Float64, Value::Number(n)) => {
+            let n = n.as_f64().ok_or_else(|| mismatch("number"))?;
+            packer.push(Datum::Float64(n.into()));
+        }
+        (JsonType::String, Value::String(s)) => packer.push(Datum::String(s)),
+        (JsonType::TimestampTz, Value::String(s)) => {
+            packer.push(Datum::TimestampTz(
+                strconv::parse_timestamptz(s).map_err(invalid)?,
+            ));
+        }
+        (JsonType::Date, Value::String(s)) => {
+            packer.push(Datum::Date(strconv::parse_date(s).map_err(invalid)?));
+        }
+        (JsonType::Time, Value::String(s)) => {
+            packer.push(Datum::Time(strconv::parse_time(s).map_err(invalid)?));
+        }
+        (JsonType::Uuid, Value::String(s)) => {
+            packer.push(Datum::Uuid(strconv::parse_uuid(s).map_err(invalid)?));
+        }
+        (
+            JsonType::List {
+                element,
+                allows_null,
+            },
+            Value::Array(values),
+        ) => packer.push_list_with(|packer| {
+            for (i, value) in values.iter().enumerate() {
+                let path = format!("{path}/{i}");
+                if value.is_null() && !allows_null && !matches!(**element, JsonType::Jsonb) {
+                    bail!("unexpected null at {path}");
+                }
+                pack_value(packer, element, value, &path)?;
+            }
+            Ok::<_, anyhow::Error>(())
+        })?,
+        (JsonType::Record(fields), Value::Object(object)) => {
+            packer.push_list_with(|packer| pack_fields(packer, fields, object, path))?
+        }
+        (JsonType::Bool, _) => bail!(mismatch("boolean")),
+        (JsonType::Int64, _) => bail!(mismatch("integer")),
+        (JsonType::Float64, _) => bail!(mismatch("number")),
+        (
+            JsonType::String
+            | JsonType::TimestampTz
+            | JsonType::Date
+            | JsonType::Time
+            | JsonType::Uuid,
+            _,
+        ) => bail!(mismatch("string")),
+        (JsonType::List { .. }, _) => bail!(mismatch("array")),
+        (JsonType::Record(_), _) => bail!(mismatch("object")),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r##"{
+        "type": "object",
+        "properties": {
+            "id": {"type": "integer"},
+            "at": {"type": "string", "format": "date-time"},
+            "tags": {"type": "array", "items": {"type": "string"}},
+            "owner": {"$ref": "#/definitions/owner"},
+            "extra": {}
+        },
+        "required": ["id", "at"],
+        "definitions": {
+            "owner": {
+                "type": ["object", "null"],
+                "properties": {"name": {"type": "string"}},
+                "required": ["name"]
+            }
+        }
+    }"##;
+
+    #[mz_ore::test]
+    fn test_columns() {
+        let schema = DecodedSchema::from_json_str(SCHEMA).unwrap();
+        let owner = ScalarType::Record {
+            fields: vec![("name".into(), ScalarType::String.nullable(false))],
+            custom_id: None,
+        };
+        let tags = ScalarType::List {
+            element_type: Box::new(ScalarType::String),
+            custom_id: None,
+        };
+        assert_eq!(
+            schema.columns(),
+            [
+                ("id".into(), ScalarType::Int64.nullable(false)),
+                (
+                    "at".into(),
+                    ScalarType::TimestampTz { precision: None }.nullable(false)
+                ),
+                ("tags".into(), tags.nullable(true)),
+                ("owner".into(), owner.nullable(true)),
+                ("extra".into(), ScalarType::Jsonb.nullable(true)),
+            ]
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_recursive_schema() {
+        let schema = r##"{
+            "type": "object",
+            "properties": {"child": {"$ref": "#"}}
+        }"##;
+        assert!(DecodedSchema::from_json_str(schema).is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_decode() {
+        let schema = DecodedSchema::from_json_str(SCHEMA).unwrap();
+        let mut decoder = Decoder::new(schema, false);
+
+        let row = decoder
+            .decode(br#"{"id": 1, "at": "2024-01-01T00:00:00Z", "owner": {"name": "a"}}"#)
+            .unwrap();
+        let mut datums = row.iter();
+        assert_eq!(datums.next(), Some(Datum::Int64(1)));
+        assert!(matches!(datums.next(), Some(Datum::TimestampTz(_))));
+        assert_eq!(datums.next(), Some(Datum::Null));
+        assert_eq!(
+            datums
+                .next()
+                .unwrap()
+                .unwrap_list()
+                .iter()
+                .collect::<Vec<_>>(),
+            [Datum::String("a")]
+        );
+        assert_eq!(datums.next(), Some(Datum::Null));
+
+        let err = decoder
+            .decode(br#"{"id": "1", "at": "2024-01-01T00:00:00Z"}"#)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "expected integer at /id, found string");
+        let err = decoder
+            .decode(br#"{"id": 1, "at": "2024-01-01T00:00:00Z", "owner": {}}"#)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "missing required property at /owner/name");
+        let err = decoder.decode(br#"{"id": 1}"#).unwrap_err();
+        assert_eq!(err.to_string(), "missing required property at /at");
+    }
+}
@@ -16,4 +16,5 @@ pub mod confluent;
 pub mod encode;
 pub mod envelopes;
 pub mod json;
+pub mod json_schema;
 pub mod protobuf;
@@ -17,6 +17,12 @@ use prost_reflect::{
     ReflectMessage, Value,
 };
 
+mod encode;
+
+pub use crate::protobuf::encode::{
+    ProtobufEncoder, ProtobufSchemaGenerator, ProtobufSchemaOptions,
+};
+
 /// A decoded description of the schema of a Protobuf message.
 #[derive(Debug, PartialEq)]
 pub struct DecodedDescriptors {
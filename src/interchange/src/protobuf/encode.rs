@@ -0,0 +1,772 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Generation of Protobuf schemas for relations, and encoding of rows as
+//! messages of those schemas.
+//!
+//! The generated schemas use `proto3` syntax and contain exactly one top-level
+//! message, so that encoded messages can always be framed with the message
+//! index `0` of the Confluent wire format. Columns map to fields numbered in
+//! column order, records map to nested messages, and lists and arrays map to
+//! `repeated` fields. Types without a natural Protobuf counterpart (e.g.
+//! `numeric` or `interval`) are encoded as strings in their text format.
+//!
+//! Protobuf can't represent every SQL value exactly: `NULL` elements of lists,
+//! arrays and maps are encoded as the default value of their type, multi
+//! dimensional arrays are flattened, and a `NULL` list is indistinguishable
+//! from an empty one.
+
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::fmt::{self, Write};
+
+use anyhow::bail;
+use byteorder::{NetworkEndian, WriteBytesExt};
+use mz_ore::cast::CastFrom;
+use mz_repr::adt::char::format_str_pad;
+use mz_repr::adt::jsonb::JsonbRef;
+use mz_repr::{ColumnName, ColumnType, Datum, RelationDesc, Row, ScalarType};
+use prost::encoding::{encode_key, encode_varint, WireType};
+
+use crate::encode::{column_names_and_types, Encode};
+use crate::envelopes;
+
+/// The package of all generated schemas.
+const PACKAGE: &str = "com.materialize.sink";
+/// The well-known type used to encode timestamps.
+const TIMESTAMP_TYPE: &str = "google.protobuf.Timestamp";
+const TIMESTAMP_IMPORT: &str = "google/protobuf/timestamp.proto";
+
+#[derive(Debug, Default)]
+pub struct ProtobufSchemaOptions {
+    /// Whether the value is wrapped in a Debezium-style envelope.
+    pub is_debezium: bool,
+}
+
+/// Generates the Protobuf schemas of the keys and values of a sink.
+#[derive(Debug, Clone)]
+pub struct ProtobufSchemaGenerator {
+    key: Option<Message>,
+    value: Message,
+}
+
+impl ProtobufSchemaGenerator {
+    pub fn new(
+        key_desc: Option<RelationDesc>,
+        value_desc: RelationDesc,
+        options: ProtobufSchemaOptions,
+    ) -> Result<Self, anyhow::Error> {
+        let mut value_columns = column_names_and_types(value_desc);
+        if options.is_debezium {
+            value_columns = envelopes::dbz_envelope(value_columns);
+        }
+        let value = Message::new("Value".into(), &value_columns)?;
+        let key = match key_desc {
+            Some(desc) => Some(Message::new("Key".into(), &column_names_and_types(desc))?),
+            None => None,
+        };
+        Ok(ProtobufSchemaGenerator { key, value })
+    }
+
+    /// Returns the `.proto` file describing keys, if the sink has a key.
+    pub fn key_schema(&self) -> Option<String> {
+        self.key.as_ref().map(|key| key.schema_file())
+    }
+
+    /// Returns the `.proto` file describing values.
+    pub fn value_schema(&self) -> String {
+        self.value.schema_file()
+    }
+}
+
+/// Manages encoding of Protobuf-encoded bytes, using the Confluent wire format.
+pub struct ProtobufEncoder {
+    schema_generator: ProtobufSchemaGenerator,
+    key_schema_id: Option<i32>,
+    value_schema_id: i32,
+}
+
+impl fmt::Debug for ProtobufEncoder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ProtobufEncoder")
+            .field("value_schema", &self.schema_generator.value_schema())
+            .finish()
+    }
+}
+
+impl ProtobufEncoder {
+    pub fn new(
+        schema_generator: ProtobufSchemaGenerator,
+        key_schema_id: Option<i32>,
+        value_schema_id: i32,
+    ) -> Self {
+        ProtobufEncoder {
+            schema_generator,
+            key_schema_id,
+            value_schema_id,
+        }
+    }
+}
+
+impl Encode for ProtobufEncoder {
+    fn get_format_name(&self) -> &str {
+        "protobuf"
+    }
+
+    fn encode_key_unchecked(&self, row: Row) -> Vec<u8> {
+        let message = self
+            .schema_generator
+            .key
+            .as_ref()
+            .expect("key schema must exist");
+        let schema_id = self.key_schema_id.expect("key schema must exist");
+        encode_message_unchecked(schema_id, row, message)
+    }
+
+    fn encode_value_unchecked(&self, row: Row) -> Vec<u8> {
+        encode_message_unchecked(self.value_schema_id, row, &self.schema_generator.value)
+    }
+}
+
+fn encode_message_unchecked(schema_id: i32, row: Row, message: &Message) -> Vec<u8> {
+    let mut buf = vec![];
+    // The first byte is a magic byte (0) that indicates the Confluent
+    // serialization format version, and the next four bytes are a 32-bit
+    // schema ID. Protobuf messages are then followed by the path of the
+    // message in the schema, where `0` is a shorthand for the first top-level
+    // message.
+    //
+    // https://docs.confluent.io/current/schema-registry/docs/serializer-formatter.html#wire-format
+    buf.write_u8(0).expect("writing to vec cannot fail");
+    buf.write_i32::<NetworkEndian>(schema_id)
+        .expect("writing to vec cannot fail");
+    buf.write_u8(0).expect("writing to vec cannot fail");
+    message.encode(row.iter(), &mut buf);
+    buf
+}
+
+/// A Protobuf scalar type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scalar {
+    Bool,
+    Int32,
+    Int64,
+    Uint32,
+    Uint64,
+    Float,
+    Double,
+    String,
+    Bytes,
+}
+
+impl Scalar {
+    fn name(&self) -> &'static str {
+        match self {
+            Scalar::Bool => "bool",
+            Scalar::Int32 => "int32",
+            Scalar::Int64 => "int64",
+            Scalar::Uint32 => "uint32",
+            Scalar::Uint64 => "uint64",
+            Scalar::Float => "float",
+            Scalar::Double => "double",
+            Scalar::String => "string",
+            Scalar::Bytes => "bytes",
+        }
+    }
+
+    fn wire_type(&self) -> WireType {
+        match self {
+            Scalar::Bool | Scalar::Int32 | Scalar::Int64 | Scalar::Uint32 | Scalar::Uint64 => {
+                WireType::Varint
+            }
+            Scalar::Float => WireType::ThirtyTwoBit,
+            Scalar::Double => WireType::SixtyFourBit,
+            Scalar::String | Scalar::Bytes => WireType::LengthDelimited,
+        }
+    }
+
+    /// Reports whether repeated fields of this type use the packed encoding.
+    fn is_packed(&self) -> bool {
+        self.wire_type() != WireType::LengthDelimited
+    }
+
+    /// Encodes `datum`, of type `typ`, without a field key. `NULL` is encoded
+    /// as the default value of the type.
+    fn encode(&self, datum: Datum, typ: &ScalarType, buf: &mut Vec<u8>) {
+        if datum.is_null() {
+            match self.wire_type() {
+                WireType::ThirtyTwoBit => buf.extend_from_slice(&[0; 4]),
+                WireType::SixtyFourBit => buf.extend_from_slice(&[0; 8]),
+                _ => encode_varint(0, buf),
+            }
+            return;
+        }
+        match self {
+            Scalar::Bool => encode_varint(u64::from(datum.unwrap_bool()), buf),
+            // Negative integers are sign extended to 64 bits, as mandated by
+            // the Protobuf encoding of `int32`.
+            Scalar::Int32 => {
+                let i = match typ {
+                    ScalarType::Int16 => i64::from(datum.unwrap_int16()),
+                    _ => i64::from(datum.unwrap_int32()),
+                };
+                encode_varint(u64::from_ne_bytes(i.to_ne_bytes()), buf)
+            }
+            Scalar::Int64 => {
+                let i = datum.unwrap_int64();
+                encode_varint(u64::from_ne_bytes(i.to_ne_bytes()), buf)
+            }
+            Scalar::Uint32 => {
+                let u = match typ {
+                    ScalarType::PgLegacyChar => u32::from(datum.unwrap_uint8()),
+                    ScalarType::UInt16 => u32::from(datum.unwrap_uint16()),
+                    _ => datum.unwrap_uint32(),
+                };
+                encode_varint(u64::from(u), buf)
+            }
+            Scalar::Uint64 => {
+                let u = match typ {
+                    ScalarType::MzTimestamp => u64::from(datum.unwrap_mz_timestamp()),
+                    _ => datum.unwrap_uint64(),
+                };
+                encode_varint(u, buf)
+            }
+            Scalar::Float => buf.extend_from_slice(&datum.unwrap_float32().to_le_bytes()),
+            Scalar::Double => buf.extend_from_slice(&datum.unwrap_float64().to_le_bytes()),
+            Scalar::String => {
+                let s = text(datum, typ);
+                encode_varint(u64::cast_from(s.len()), buf);
+                buf.extend_from_slice(s.as_bytes());
+            }
+            Scalar::Bytes => {
+                let b = datum.unwrap_bytes();
+                encode_varint(u64::cast_from(b.len()), buf);
+                buf.extend_from_slice(b);
+            }
+        }
+    }
+}
+
+/// Returns the text representation of a datum encoded as a Protobuf string.
+fn text<'a>(datum: Datum<'a>, typ: &ScalarType) -> Cow<'a, str> {
+    match typ {
+        ScalarType::String | ScalarType::VarChar { .. } | ScalarType::PgLegacyName => {
+            Cow::Borrowed(datum.unwrap_str())
+        }
+        ScalarType::Char { length } => Cow::Owned(format_str_pad(datum.unwrap_str(), *length)),
+        ScalarType::Jsonb => Cow::Owned(JsonbRef::from_datum(datum).to_string()),
+        ScalarType::Numeric { .. } => {
+            Cow::Owned(datum.unwrap_numeric().0.to_standard_notation_string())
+        }
+        ScalarType::Date => Cow::Owned(datum.unwrap_date().to_string()),
+        ScalarType::Time => Cow::Owned(datum.unwrap_time().to_string()),
+        ScalarType::Interval => Cow::Owned(datum.unwrap_interval().to_string()),
+        ScalarType::Uuid => Cow::Owned(datum.unwrap_uuid().to_string()),
+        ScalarType::Range { .. } => Cow::Owned(datum.unwrap_range().to_string()),
+        ScalarType::AclItem => Cow::Owned(datum.unwrap_acl_item().to_string()),
+        ScalarType::MzAclItem => Cow::Owned(datum.unwrap_mz_acl_item().to_string()),
+        ScalarType::Enum { labels, .. } => {
+            Cow::Owned(labels[usize::cast_from(datum.unwrap_uint32())].clone())
+        }
+        ScalarType::Inet => Cow::Owned(datum.unwrap_inet().to_string()),
+        ScalarType::Cidr => Cow::Owned(datum.unwrap_inet().to_cidr_string()),
+        ScalarType::MacAddr => Cow::Owned(datum.unwrap_macaddr().to_string()),
+        _ => unreachable!("{typ:?} is not encoded as a string"),
+    }
+}
+
+/// The type of the values of a field.
+#[derive(Debug, Clone)]
+enum Kind {
+    Scalar(Scalar),
+    /// A `google.protobuf.Timestamp`.
+    Timestamp,
+    /// A message nested in the parent message, identified by its index.
+    Message(usize),
+}
+
+impl Kind {
+    /// Determines the kind of the values of `field_name`, declaring a nested
+    /// message in `nested` if needed.
+    fn new(
+        nested: &mut Vec<Message>,
+        field_name: &str,
+        typ: &ScalarType,
+    ) -> Result<Self, anyhow::Error> {
+        let scalar = match typ {
+            ScalarType::Bool => Scalar::Bool,
+            ScalarType::Int16 | ScalarType::Int32 => Scalar::Int32,
+            ScalarType::Int64 => Scalar::Int64,
+            ScalarType::PgLegacyChar
+            | ScalarType::UInt16
+            | ScalarType::UInt32
+            | ScalarType::Oid
+            | ScalarType::RegClass
+            | ScalarType::RegProc
+            | ScalarType::RegType => Scalar::Uint32,
+            ScalarType::UInt64 | ScalarType::MzTimestamp => Scalar::Uint64,
+            ScalarType::Float32 => Scalar::Float,
+            ScalarType::Float64 => Scalar::Double,
+            ScalarType::Bytes => Scalar::Bytes,
+            ScalarType::String
+            | ScalarType::VarChar { .. }
+            | ScalarType::Char { .. }
+            | ScalarType::PgLegacyName
+            | ScalarType::Jsonb
+            | ScalarType::Numeric { .. }
+            | ScalarType::Date
+            | ScalarType::Time
+            | ScalarType::Interval
+            | ScalarType::Uuid
+            | ScalarType::Range { .. }
+            | ScalarType::AclItem
+            | ScalarType::MzAclItem
+            | ScalarType::Enum { .. }
+            | ScalarType::Inet
+            | ScalarType::Cidr
+            | ScalarType::MacAddr => Scalar::String,
+            ScalarType::Timestamp { .. } | ScalarType::TimestampTz { .. } => {
+                return Ok(Kind::Timestamp)
+            }
+            ScalarType::Record { fields, .. } => {
+                let taken: BTreeSet<_> = nested.iter().map(|m| m.name.clone()).collect();
+                let name = unique_name(message_name(field_name), &taken);
+                let message = Message::new(name, fields)?;
+                nested.push(message);
+                return Ok(Kind::Message(nested.len() - 1));
+            }
+            ScalarType::List { .. }
+            | ScalarType::Array(_)
+            | ScalarType::Int2Vector
+            | ScalarType::Map { .. } => {
+                bail!("Protobuf sinks do not support nested collections: field {field_name}")
+            }
+        };
+        Ok(Kind::Scalar(scalar))
+    }
+}
+
+/// How many values a field holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Label {
+    /// A singular field that is omitted when `NULL`.
+    Singular,
+    /// A singular scalar field with explicit presence, which distinguishes
+    /// `NULL` from the default value.
+    Optional,
+    Repeated,
+    /// A `map<string, V>` field.
+    Map,
+}
+
+#[derive(Debug, Clone)]
+struct Field {
+    name: String,
+    number: u32,
+    label: Label,
+    kind: Kind,
+    /// The SQL type of the values of the field, i.e. the element type of
+    /// repeated and map fields.
+    typ: ScalarType,
+}
+
+#[derive(Debug, Clone)]
+struct Message {
+    name: String,
+    fields: Vec<Field>,
+    nested: Vec<Message>,
+}
+
+impl Message {
+    fn new(name: String, columns: &[(ColumnName, ColumnType)]) -> Result<Self, anyhow::Error> {
+        let mut fields = vec![];
+        let mut nested = vec![];
+        let mut taken = BTreeSet::new();
+        for ((name, typ), number) in columns.iter().zip(1..) {
+            let name = unique_name(field_name(name.as_str()), &taken);
+            taken.insert(name.clone());
+            let (label, typ) = match &typ.scalar_type {
+                ScalarType::List { element_type, .. } | ScalarType::Array(element_type) => {
+                    (Label::Repeated, (**element_type).clone())
+                }
+                ScalarType::Int2Vector => (Label::Repeated, ScalarType::Int16),
+                ScalarType::Map { value_type, .. } => (Label::Map, (**value_type).clone()),
+                scalar_type => {
+                    let label = match scalar_type {
+                        ScalarType::Record { .. }
+                        | ScalarType::Timestamp { .. }
+                        | ScalarType::TimestampTz { .. } => Label::Singular,
+                        _ if typ.nullable => Label::Optional,
+                        _ => Label::Singular,
+                    };
+                    (label, scalar_type.clone())
+                }
+            };
+            let kind = Kind::new(&mut nested, &name, &typ)?;
+            fields.push(Field {
+                name,
+                number,
+                label,
+                kind,
+                typ,
+            });
+        }
+        Ok(Message {
+            name,
+            fields,
+            nested,
+        })
+    }
+
+    fn uses_timestamps(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|f| matches!(f.kind, Kind::Timestamp))
+            || self.nested.iter().any(|m| m.uses_timestamps())
+    }
+
+    fn type_name(&self, kind: &Kind) -> &str {
+        match kind {
+            Kind::Scalar(scalar) => scalar.name(),
+            Kind::Timestamp => TIMESTAMP_TYPE,
+            Kind::Message(i) => &self.nested[*i].name,
+        }
+    }
+
+    /// Renders a `.proto` file that declares this message.
+    fn schema_file(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "syntax = \"proto3\";").unwrap();
+        writeln!(out).unwrap();
+        writeln!(out, "package {PACKAGE};").unwrap();
+        writeln!(out).unwrap();
+        if self.uses_timestamps() {
+            writeln!(out, "import \"{TIMESTAMP_IMPORT}\";").unwrap();
+            writeln!(out).unwrap();
+        }
+        self.render(&mut out, 0);
+        out
+    }
+
+    fn render(&self, out: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth);
+        writeln!(out, "{indent}message {} {{", self.name).unwrap();
+        for field in &self.fields {
+            let typ = self.type_name(&field.kind);
+            let typ = match field.label {
+                Label::Singular => Cow::Borrowed(typ),
+                Label::Optional => Cow::Owned(format!("optional {typ}")),
+                Label::Repeated => Cow::Owned(format!("repeated {typ}")),
+                Label::Map => Cow::Owned(format!("map<string, {typ}>")),
+            };
+            writeln!(out, "{indent}  {typ} {} = {};", field.name, field.number).unwrap();
+        }
+        for message in &self.nested {
+            writeln!(out).unwrap();
+            message.render(out, depth + 1);
+        }
+        writeln!(out, "{indent}}}").unwrap();
+    }
+
+    /// Encodes `datums`, one per field, as an instance of this message.
+    fn encode<'a>(&self, datums: impl IntoIterator<Item = Datum<'a>>, buf: &mut Vec<u8>) {
+        for (field, datum) in self.fields.iter().zip(datums) {
+            if datum.is_null() {
+                continue;
+            }
+            match field.label {
+                Label::Singular | Label::Optional => {
+                    self.encode_value(field.number, &field.kind, &field.typ, datum, buf)
+                }
+                Label::Repeated => {
+                    let elements: Vec<_> = match datum {
+                        Datum::List(list) => list.iter().collect(),
+                        Datum::Array(array) => array.elements().iter().collect(),
+                        _ => unreachable!("repeated fields are lists or arrays"),
+                    };
+                    match &field.kind {
+                        Kind::Scalar(scalar) if scalar.is_packed() => {
+                            if elements.is_empty() {
+                                continue;
+                            }
+                            let mut packed = vec![];
+                            for element in elements {
+                                scalar.encode(element, &field.typ, &mut packed);
+                            }
+                            encode_key(field.number, WireType::LengthDelimited, buf);
+                            encode_varint(u64::cast_from(packed.len()), buf);
+                            buf.extend_from_slice(&packed);
+                        }
+                        kind => {
+                            for element in elements {
+                                self.encode_value(field.number, kind, &field.typ, element, buf);
+                            }
+                        }
+                    }
+                }
+                Label::Map => {
+                    // Map entries are encoded as repeated messages with the
+                    // key in field 1 and the value in field 2.
+                    let mut entry = vec![];
+                    for (key, value) in datum.unwrap_map().iter() {
+                        entry.clear();
+                        self.encode_value(
+                            1,
+                            &Kind::Scalar(Scalar::String),
+                            &ScalarType::String,
+                            Datum::String(key),
+                            &mut entry,
+                        );
+                        self.encode_value(2, &field.kind, &field.typ, value, &mut entry);
+                        encode_key(field.number, WireType::LengthDelimited, buf);
+                        encode_varint(u64::cast_from(entry.len()), buf);
+                        buf.extend_from_slice(&entry);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Encodes a single value of a field, including its key.
+    fn encode_value(
+        &self,
+        number: u32,
+        kind: &Kind,
+        typ: &ScalarType,
+        datum: Datum,
+        buf: &mut Vec<u8>,
+    ) {
+        match kind {
+            Kind::Scalar(scalar) => {
+                encode_key(number, scalar.wire_type(), buf);
+                scalar.encode(datum, typ, buf);
+            }
+            Kind::Timestamp => {
+                let mut message = vec![];
+                if !datum.is_null() {
+                    let naive = match typ {
+                        ScalarType::TimestampTz { .. } => datum.unwrap_timestamptz().to_naive(),
+                        _ => datum.unwrap_timestamp().to_naive(),
+                    };
+                    let seconds = naive.timestamp();
+                    let nanos = naive.timestamp_subsec_nanos();
+                    encode_key(1, WireType::Varint, &mut message);
+                    encode_varint(u64::from_ne_bytes(seconds.to_ne_bytes()), &mut message);
+                    encode_key(2, WireType::Varint, &mut message);
+                    encode_varint(u64::from(nanos), &mut message);
+                }
+                encode_key(number, WireType::LengthDelimited, buf);
+                encode_varint(u64::cast_from(message.len()), buf);
+                buf.extend_from_slice(&message);
+            }
+            Kind::Message(i) => {
+                let mut message = vec![];
+                if !datum.is_null() {
+                    self.nested[*i].encode(datum.unwrap_list().iter(), &mut message);
+                }
+                encode_key(number, WireType::LengthDelimited, buf);
+                encode_varint(u64::cast_from(message.len()), buf);
+                buf.extend_from_slice(&message);
+            }
+        }
+    }
+}
+
+/// Converts a column name into a valid Protobuf field name.
+fn field_name(name: &str) -> String {
+    let mut out: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !out.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        out.insert(0, '_');
+    }
+    out
+}
+
+/// Converts a field name into the name of the nested message that describes
+/// its values, e.g. `order_line` to `OrderLine`.
+fn message_name(field_name: &str) -> String {
+    let mut out = String::new();
+    for part in field_name.split('_').filter(|part| !part.is_empty()) {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            out.push(first.to_ascii_uppercase());
+            out.extend(chars);
+        }
+    }
+    if !out.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        out.insert_str(0, "Record");
+    }
+    out
+}
+
+/// Appends a numeric suffix to `name` until it is not in `taken`.
+fn unique_name(name: String, taken: &BTreeSet<String>) -> String {
+    if !taken.contains(&name) {
+        return name;
+    }
+    (1..)
+        .map(|i| format!("{name}_{i}"))
+        .find(|candidate| !taken.contains(candidate))
+        .expect("infinite candidates")
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::adt::timestamp::CheckedTimestamp;
+    use mz_repr::{Datum, RelationDesc, Row, ScalarType};
+
+    use super::*;
+
+    fn desc() -> RelationDesc {
+        RelationDesc::empty()
+            .with_column("id", ScalarType::Int32.nullable(false))
+            .with_column("name", ScalarType::String.nullable(true))
+            .with_column(
+                "tags",
+                ScalarType::List {
+                    element_type: Box::new(ScalarType::Int64),
+                    custom_id: None,
+                }
+                .nullable(false),
+            )
+            .with_column(
+                "line item",
+                ScalarType::Record {
+                    fields: vec![(
+                        "at".into(),
+                        ScalarType::Timestamp { precision: None }.nullable(true),
+                    )],
+                    custom_id: None,
+                }
+                .nullable(true),
+            )
+    }
+
+    #[mz_ore::test]
+    fn test_schema() {
+        let generator = ProtobufSchemaGenerator::new(
+            Some(RelationDesc::empty().with_column("id", ScalarType::Int32.nullable(false))),
+            desc(),
+            ProtobufSchemaOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            generator.key_schema().unwrap(),
+            "syntax = \"proto3\";
+
+package com.materialize.sink;
+
+message Key {
+  int32 id = 1;
+}
+"
+        );
+        assert_eq!(
+            generator.value_schema(),
+            "syntax = \"proto3\";
+
+package com.materialize.sink;
+
+import \"google/protobuf/timestamp.proto\";
+
+message Value {
+  int32 id = 1;
+  optional string name = 2;
+  repeated int64 tags = 3;
+  LineItem line_item = 4;
+
+  message LineItem {
+    google.protobuf.Timestamp at = 1;
+  }
+}
+"
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_debezium_schema() {
+        let generator = ProtobufSchemaGenerator::new(
+            None,
+            RelationDesc::empty().with_column("a", ScalarType::Bool.nullable(false)),
+            ProtobufSchemaOptions { is_debezium: true },
+        )
+        .unwrap();
+        assert_eq!(generator.key_schema(), None);
+        assert_eq!(
+            generator.value_schema(),
+            "syntax = \"proto3\";
+
+package com.materialize.sink;
+
+message Value {
+  Before before = 1;
+  After after = 2;
+
+  message Before {
+    bool a = 1;
+  }
+
+  message After {
+    bool a = 1;
+  }
+}
+"
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_nested_collections() {
+        let desc = RelationDesc::empty().with_column(
+            "a",
+            ScalarType::List {
+                element_type: Box::new(ScalarType::List {
+                    element_type: Box::new(ScalarType::Int32),
+                    custom_id: None,
+                }),
+                custom_id: None,
+            }
+            .nullable(false),
+        );
+        assert!(
+            ProtobufSchemaGenerator::new(None, desc, ProtobufSchemaOptions::default()).is_err()
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_encode() {
+        let generator =
+            ProtobufSchemaGenerator::new(None, desc(), ProtobufSchemaOptions::default()).unwrap();
+        let encoder = ProtobufEncoder::new(generator, None, 7);
+
+        let at = CheckedTimestamp::from_timestamplike(
+            chrono::NaiveDateTime::from_timestamp_opt(1, 500).unwrap(),
+        )
+        .unwrap();
+        let mut row = Row::default();
+        let mut packer = row.packer();
+        packer.push(Datum::Int32(-1));
+        packer.push(Datum::Null);
+        packer.push_list([Datum::Int64(1), Datum::Int64(300)]);
+        packer.push_list([Datum::Timestamp(at)]);
+
+        let expected: &[u8] = &[
+            // Confluent header with schema id 7 and message index 0.
+            0, 0, 0, 0, 7, 0, //
+            // id = -1, sign extended to 64 bits.
+            0x08, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01, //
+            // tags = [1, 300], packed.
+            0x1a, 0x03, 0x01, 0xac, 0x02, //
+            // line_item = { at = { seconds: 1, nanos: 500 } }.
+            0x22, 0x07, 0x0a, 0x05, 0x08, 0x01, 0x10, 0xf4, 0x03,
+        ];
+        assert_eq!(encoder.encode_value_unchecked(row), expected);
+    }
+}
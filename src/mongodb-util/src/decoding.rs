@@ -0,0 +1,74 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use bson::{Bson, Document};
+use mz_repr::adt::jsonb::JsonbPacker;
+use mz_repr::RowPacker;
+
+use crate::MongoDbError;
+
+/// Packs `value` as a `jsonb` datum.
+///
+/// BSON values are converted to relaxed Extended JSON, so types without a
+/// JSON equivalent, like object IDs and dates, become objects such as
+/// `{"$oid": "..."}`.
+///
+/// See: <https://www.mongodb.com/docs/manual/reference/mongodb-extended-json/>
+pub fn pack_bson(
+    packer: &mut RowPacker,
+    collection: &str,
+    value: Bson,
+) -> Result<(), MongoDbError> {
+    JsonbPacker::new(packer)
+        .pack_serde_json(value.into_relaxed_extjson())
+        .map_err(|e| MongoDbError::ValueDecodeError {
+            collection: collection.to_string(),
+            error: e.to_string(),
+        })?;
+    Ok(())
+}
+
+/// Returns the `_id` of `document`, which every document stored in a
+/// collection has.
+pub fn document_id(collection: &str, document: &Document) -> Result<Bson, MongoDbError> {
+    document
+        .get("_id")
+        .cloned()
+        .ok_or_else(|| MongoDbError::ValueDecodeError {
+            collection: collection.to_string(),
+            error: "document has no _id".to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use bson::{doc, oid::ObjectId};
+    use mz_repr::adt::jsonb::JsonbRef;
+    use mz_repr::Row;
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn pack_extended_json() {
+        let oid = ObjectId::parse_str("65553f1000000000000000aa").unwrap();
+        let document = doc! { "_id": oid, "n": 1_i64, "tags": ["a", "b"] };
+
+        let mut row = Row::default();
+        pack_bson(&mut row.packer(), "c", Bson::Document(document)).unwrap();
+        let json = JsonbRef::from_datum(row.unpack_first()).to_serde_json();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "_id": {"$oid": "65553f1000000000000000aa"},
+                "n": 1,
+                "tags": ["a", "b"],
+            })
+        );
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! MongoDB utility library.
+
+mod tunnel;
+pub use tunnel::{Config, MongoDbClient, TunnelConfig};
+
+pub mod resume_token;
+pub use resume_token::{ResumeToken, StartPoint};
+
+pub mod schemas;
+pub use schemas::{
+    collection_info, ensure_pre_and_post_images, get_cluster_time, CollectionInfo,
+    CollectionRequest,
+};
+
+pub mod snapshot;
+pub use snapshot::SnapshotCursor;
+
+pub mod decoding;
+pub use decoding::{document_id, pack_bson};
+
+#[derive(Debug, thiserror::Error)]
+pub enum MongoDbError {
+    #[error("error setting up ssh: {0}")]
+    Ssh(#[source] anyhow::Error),
+    #[error("invalid resume token: {0}")]
+    InvalidResumeToken(String),
+    #[error("change stream pre- and post-images are not enabled for collections: {}", collections.join(", "))]
+    PreAndPostImagesDisabled { collections: Vec<String> },
+    #[error("MongoDB server did not report a cluster time; change streams require a replica set or sharded cluster")]
+    NoClusterTime,
+    #[error("error decoding document of collection '{collection}': {error}")]
+    ValueDecodeError { collection: String, error: String },
+    /// Any other error we bail on.
+    #[error(transparent)]
+    Generic(#[from] anyhow::Error),
+    /// A MongoDB driver error.
+    #[error(transparent)]
+    MongoDb(#[from] mongodb::error::Error),
+}
@@ -0,0 +1,268 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Resume tokens of MongoDB change streams.
+//!
+//! Every event of a change stream carries a resume token whose `_data` field
+//! is a hex-encoded `KeyString`. The first component of that `KeyString` is
+//! the cluster time of the operation, so comparing the decoded bytes of two
+//! tokens orders them in the same way as the events of the change stream.
+//! This lets us use resume tokens directly as the timestamps of a dataflow.
+//!
+//! See: <https://www.mongodb.com/docs/manual/changeStreams/#resume-tokens>
+
+use std::fmt;
+
+use bson::{doc, Bson};
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+use timely::order::{PartialOrder, TotalOrder};
+use timely::progress::timestamp::{PathSummary, Refines, Timestamp};
+
+use crate::MongoDbError;
+
+/// The `KeyString` type byte of a BSON timestamp, which starts every resume
+/// token.
+const TIMESTAMP_TYPE: u8 = 0x82;
+
+/// The length of a resume token that holds nothing but a cluster time.
+const CLUSTER_TIME_LEN: usize = 9;
+
+/// The decoded `_data` of a MongoDB change stream resume token.
+///
+/// When used as a timestamp, a resume token represents the change event that
+/// carries it. When used in a frontier, it represents the smallest token that
+/// we might still see an event for, which need not be the token of an actual
+/// event. See [`ResumeToken::start_point`] for how a frontier is turned back
+/// into a position of a change stream.
+#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Arbitrary)]
+pub struct ResumeToken(Vec<u8>);
+
+/// The position at which to (re)open a change stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StartPoint {
+    /// Start with the first event at or after the given cluster time.
+    AtOperationTime(bson::Timestamp),
+    /// Start with the first event after the one that carried the token.
+    AfterToken(mongodb::change_stream::event::ResumeToken),
+}
+
+impl ResumeToken {
+    /// Creates a resume token from its decoded bytes.
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        ResumeToken(bytes)
+    }
+
+    /// Returns the decoded bytes of this token.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Decodes the token of a change stream event.
+    pub fn from_event_token(
+        token: &mongodb::change_stream::event::ResumeToken,
+    ) -> Result<Self, MongoDbError> {
+        let invalid = || MongoDbError::InvalidResumeToken(format!("{token:?}"));
+        let doc = match bson::to_bson(token).map_err(|_| invalid())? {
+            Bson::Document(doc) => doc,
+            _ => return Err(invalid()),
+        };
+        let data = doc.get_str("_data").map_err(|_| invalid())?;
+        let bytes = hex::decode(data).map_err(|_| invalid())?;
+        Ok(ResumeToken(bytes))
+    }
+
+    /// Encodes this token into a token that a change stream accepts in its
+    /// `resumeAfter` option.
+    fn to_event_token(bytes: &[u8]) -> mongodb::change_stream::event::ResumeToken {
+        let doc = doc! { "_data": hex::encode_upper(bytes) };
+        bson::from_bson(Bson::Document(doc)).expect("resume tokens are documents")
+    }
+
+    /// Returns the smallest token that is greater than the tokens of all
+    /// events at or before `cluster_time`.
+    pub fn after_cluster_time(cluster_time: bson::Timestamp) -> Option<Self> {
+        let next = match cluster_time.increment.checked_add(1) {
+            Some(increment) => bson::Timestamp {
+                time: cluster_time.time,
+                increment,
+            },
+            None => bson::Timestamp {
+                time: cluster_time.time.checked_add(1)?,
+                increment: 0,
+            },
+        };
+        Some(Self::at_cluster_time(next))
+    }
+
+    /// Returns the smallest token of any event at or after `cluster_time`.
+    pub fn at_cluster_time(cluster_time: bson::Timestamp) -> Self {
+        let mut bytes = Vec::with_capacity(CLUSTER_TIME_LEN);
+        bytes.push(TIMESTAMP_TYPE);
+        bytes.extend_from_slice(&cluster_time.time.to_be_bytes());
+        bytes.extend_from_slice(&cluster_time.increment.to_be_bytes());
+        ResumeToken(bytes)
+    }
+
+    /// Returns the smallest token that is greater than this one.
+    pub fn successor(&self) -> Self {
+        let mut bytes = self.0.clone();
+        bytes.push(0);
+        ResumeToken(bytes)
+    }
+
+    /// Returns the cluster time encoded in this token, if any.
+    pub fn cluster_time(&self) -> Option<bson::Timestamp> {
+        match self.0.get(..CLUSTER_TIME_LEN)? {
+            [TIMESTAMP_TYPE, t0, t1, t2, t3, i0, i1, i2, i3] => Some(bson::Timestamp {
+                time: u32::from_be_bytes([*t0, *t1, *t2, *t3]),
+                increment: u32::from_be_bytes([*i0, *i1, *i2, *i3]),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the position of a change stream whose first event is the first
+    /// one with a token not less than this token, when this token is used as
+    /// a frontier.
+    ///
+    /// Returns `None` for the minimum token, from which no change stream can
+    /// be opened. Frontiers are only ever made up of tokens produced by
+    /// [`ResumeToken::at_cluster_time`], [`ResumeToken::after_cluster_time`]
+    /// or [`ResumeToken::successor`], for which this function is defined.
+    pub fn start_point(&self) -> Result<Option<StartPoint>, MongoDbError> {
+        if self.0.is_empty() {
+            return Ok(None);
+        }
+        if self.0.len() == CLUSTER_TIME_LEN {
+            if let Some(cluster_time) = self.cluster_time() {
+                return Ok(Some(StartPoint::AtOperationTime(cluster_time)));
+            }
+        }
+        match self.0.split_last() {
+            Some((0, token)) => Ok(Some(StartPoint::AfterToken(Self::to_event_token(token)))),
+            _ => Err(MongoDbError::InvalidResumeToken(self.to_string())),
+        }
+    }
+
+    /// Returns a lossy `u64` approximation of this token that preserves its
+    /// order, for use in progress statistics.
+    pub fn abbreviate(&self) -> u64 {
+        self.cluster_time()
+            .map(|ts| (u64::from(ts.time) << 32) | u64::from(ts.increment))
+            .unwrap_or(0)
+    }
+}
+
+impl fmt::Display for ResumeToken {
+    /// Formats the token the way MongoDB prints the `_data` of a token.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hex::encode_upper(&self.0))
+    }
+}
+
+impl Timestamp for ResumeToken {
+    // No need to describe complex summaries
+    type Summary = ();
+
+    fn minimum() -> Self {
+        ResumeToken(vec![])
+    }
+}
+
+impl TotalOrder for ResumeToken {}
+
+impl PartialOrder for ResumeToken {
+    fn less_equal(&self, other: &Self) -> bool {
+        self <= other
+    }
+}
+
+impl PathSummary<ResumeToken> for () {
+    fn results_in(&self, src: &ResumeToken) -> Option<ResumeToken> {
+        Some(src.clone())
+    }
+
+    fn followed_by(&self, _other: &Self) -> Option<Self> {
+        Some(())
+    }
+}
+
+impl Refines<()> for ResumeToken {
+    fn to_inner(_other: ()) -> Self {
+        Self::minimum()
+    }
+
+    fn to_outer(self) -> () {}
+
+    fn summarize(_path: Self::Summary) -> <() as Timestamp>::Summary {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A token in the `KeyString` format of an event at cluster time
+    // (1700000000, 1).
+    const EVENT_TOKEN: &str = "826553F100000000012B022C0100296E5A1004A2D6C1E0D3C24B6B8F6F1E5A6C3A4E9A46645F6964006465534C00A7A2C1A2E3D4F50004";
+
+    #[mz_ore::test]
+    fn cluster_time_roundtrip() {
+        let ts = bson::Timestamp {
+            time: 1700000000,
+            increment: 1,
+        };
+        let bytes = hex::decode(EVENT_TOKEN).unwrap();
+        let token = ResumeToken::from_bytes(bytes);
+        assert_eq!(token.cluster_time(), Some(ts));
+        assert_eq!(token.to_string(), EVENT_TOKEN);
+
+        let at = ResumeToken::at_cluster_time(ts);
+        let after = ResumeToken::after_cluster_time(ts).unwrap();
+        assert!(at < token);
+        assert!(token < after);
+        assert!(token < token.successor());
+        assert!(token.successor() < after);
+        assert_eq!(
+            at.start_point().unwrap(),
+            Some(StartPoint::AtOperationTime(ts))
+        );
+        assert_eq!(ResumeToken::minimum().start_point().unwrap(), None);
+    }
+
+    #[mz_ore::test]
+    fn successor_resumes_after_token() {
+        let token = ResumeToken::from_bytes(hex::decode(EVENT_TOKEN).unwrap());
+        let Some(StartPoint::AfterToken(event_token)) = token.successor().start_point().unwrap()
+        else {
+            panic!("expected a resume token");
+        };
+        assert_eq!(ResumeToken::from_event_token(&event_token).unwrap(), token);
+    }
+
+    #[mz_ore::test]
+    fn after_cluster_time_overflow() {
+        let ts = bson::Timestamp {
+            time: 7,
+            increment: u32::MAX,
+        };
+        assert_eq!(
+            ResumeToken::after_cluster_time(ts).unwrap().cluster_time(),
+            Some(bson::Timestamp {
+                time: 8,
+                increment: 0
+            })
+        );
+        let ts = bson::Timestamp {
+            time: u32::MAX,
+            increment: u32::MAX,
+        };
+        assert_eq!(ResumeToken::after_cluster_time(ts), None);
+    }
+}
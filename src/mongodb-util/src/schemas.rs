@@ -0,0 +1,101 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use bson::doc;
+use futures::TryStreamExt;
+use mongodb::results::CollectionType;
+use mongodb::Database;
+
+use crate::MongoDbError;
+
+/// Request for collections from MongoDB.
+///
+/// Views, time series collections and system collections are never returned,
+/// as they cannot be watched by a change stream.
+pub enum CollectionRequest<'a> {
+    /// Request all collections in the database
+    All,
+    /// Request the specified collections
+    Collections(Vec<&'a str>),
+}
+
+/// A collection of a MongoDB database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollectionInfo {
+    /// The name of the collection.
+    pub name: String,
+    /// Whether the collection has `changeStreamPreAndPostImages` enabled.
+    ///
+    /// Change streams can only report the previous version of updated and
+    /// deleted documents if this is enabled, which we require to be able to
+    /// retract the previous version.
+    pub pre_and_post_images: bool,
+}
+
+/// Returns the collections of `db` that match `request`, ordered by name.
+pub async fn collection_info(
+    db: &Database,
+    request: &CollectionRequest<'_>,
+) -> Result<Vec<CollectionInfo>, MongoDbError> {
+    let specs: Vec<_> = db.list_collections(None, None).await?.try_collect().await?;
+
+    let mut collections = vec![];
+    for spec in specs {
+        if spec.collection_type != CollectionType::Collection || spec.name.starts_with("system.") {
+            continue;
+        }
+        let matches = match request {
+            CollectionRequest::All => true,
+            CollectionRequest::Collections(names) => names.contains(&spec.name.as_str()),
+        };
+        if !matches {
+            continue;
+        }
+        let pre_and_post_images = spec
+            .options
+            .change_stream_pre_and_post_images
+            .map_or(false, |o| o.enabled);
+        collections.push(CollectionInfo {
+            name: spec.name,
+            pre_and_post_images,
+        });
+    }
+
+    collections.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(collections)
+}
+
+/// Returns an error listing the `collections` that do not have change stream
+/// pre- and post-images enabled, if any.
+pub fn ensure_pre_and_post_images(collections: &[CollectionInfo]) -> Result<(), MongoDbError> {
+    let disabled: Vec<_> = collections
+        .iter()
+        .filter(|c| !c.pre_and_post_images)
+        .map(|c| c.name.clone())
+        .collect();
+    if disabled.is_empty() {
+        Ok(())
+    } else {
+        Err(MongoDbError::PreAndPostImagesDisabled {
+            collections: disabled,
+        })
+    }
+}
+
+/// Returns the latest cluster time that `db` has observed, which is the
+/// operation time of a no-op command.
+///
+/// Only replica sets and sharded clusters report an operation time. Those are
+/// also the only deployments that support change streams.
+pub async fn get_cluster_time(db: &Database) -> Result<bson::Timestamp, MongoDbError> {
+    let reply = db.run_command(doc! { "ping": 1 }, None).await?;
+    reply
+        .get_timestamp("operationTime")
+        .map_err(|_| MongoDbError::NoClusterTime)
+}
@@ -0,0 +1,86 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Consistent snapshots of MongoDB collections.
+//!
+//! Reads with the `snapshot` read concern can be pinned to a cluster time
+//! with `atClusterTime`, which lets us read several collections as of the
+//! cluster time at which a change stream is opened. The driver does not
+//! expose `atClusterTime` on its `find` helpers, so we issue the `find` and
+//! `getMore` commands ourselves.
+//!
+//! The cluster time must be within the server's snapshot history window
+//! (`minSnapshotHistoryWindowInSeconds`, 5 minutes by default) when a
+//! collection starts being read, or the read fails with `SnapshotTooOld`.
+//!
+//! See: <https://www.mongodb.com/docs/manual/reference/read-concern-snapshot/>
+
+use anyhow::Context;
+use bson::{doc, Document};
+use mongodb::Database;
+
+use crate::MongoDbError;
+
+/// A cursor over the documents of a collection as of a cluster time.
+pub struct SnapshotCursor<'a> {
+    db: &'a Database,
+    collection: String,
+    cluster_time: bson::Timestamp,
+    /// The ID of the server-side cursor, `None` before the first batch and
+    /// `Some(0)` once the cursor is exhausted.
+    cursor_id: Option<i64>,
+}
+
+impl<'a> SnapshotCursor<'a> {
+    pub fn new(db: &'a Database, collection: String, cluster_time: bson::Timestamp) -> Self {
+        SnapshotCursor {
+            db,
+            collection,
+            cluster_time,
+            cursor_id: None,
+        }
+    }
+
+    /// Returns the next batch of documents, or `None` once all documents have
+    /// been returned.
+    pub async fn next_batch(&mut self) -> Result<Option<Vec<Document>>, MongoDbError> {
+        let (command, batch_field) = match self.cursor_id {
+            None => (
+                doc! {
+                    "find": &self.collection,
+                    "readConcern": {
+                        "level": "snapshot",
+                        "atClusterTime": self.cluster_time,
+                    },
+                },
+                "firstBatch",
+            ),
+            Some(0) => return Ok(None),
+            Some(cursor_id) => (
+                doc! { "getMore": cursor_id, "collection": &self.collection },
+                "nextBatch",
+            ),
+        };
+        let reply = self.db.run_command(command, None).await?;
+        let cursor = reply
+            .get_document("cursor")
+            .context("reply has no cursor")?;
+        self.cursor_id = Some(cursor.get_i64("id").context("cursor has no id")?);
+        let batch = cursor
+            .get_array(batch_field)
+            .with_context(|| format!("cursor has no {batch_field}"))?
+            .iter()
+            .map(|doc| match doc {
+                bson::Bson::Document(doc) => Ok(doc.clone()),
+                other => Err(anyhow::anyhow!("expected document, found {other}").into()),
+            })
+            .collect::<Result<_, MongoDbError>>()?;
+        Ok(Some(batch))
+    }
+}
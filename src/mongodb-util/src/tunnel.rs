@@ -0,0 +1,193 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::ops::Deref;
+
+use mongodb::options::{ClientOptions, Credential, ServerAddress};
+use mongodb::{Client, Database};
+use mz_repr::GlobalId;
+use mz_ssh_util::tunnel::{SshTimeoutConfig, SshTunnelConfig};
+use mz_ssh_util::tunnel_manager::{ManagedSshTunnelHandle, SshTunnelManager};
+use tracing::{info, warn};
+
+use crate::MongoDbError;
+
+/// Configures an optional tunnel for use when connecting to a MongoDB
+/// deployment.
+#[derive(Debug, PartialEq, Clone)]
+pub enum TunnelConfig {
+    /// Establish a direct TCP connection to the database host.
+    Direct,
+    /// Establish a TCP connection to the database via an SSH tunnel.
+    /// This means first establishing an SSH connection to a bastion host,
+    /// and then opening a separate connection from that host to the database.
+    /// This is commonly referred by vendors as a "direct SSH tunnel", in
+    /// opposition to "reverse SSH tunnel", which is currently unsupported.
+    Ssh { config: SshTunnelConfig },
+    /// Establish a TCP connection to the database via an AWS PrivateLink
+    /// service.
+    AwsPrivatelink {
+        /// The ID of the AWS PrivateLink service.
+        connection_id: GlobalId,
+    },
+}
+
+/// A MongoDB client with an optional SSH tunnel handle.
+///
+/// This wrapper is intended to be used in place of `mongodb::Client` to
+/// keep the SSH tunnel alive for the lifecycle of the connection by holding
+/// a reference to the tunnel handle.
+#[derive(Debug)]
+pub struct MongoDbClient {
+    client: Client,
+    database: String,
+    _ssh_tunnel_handle: Option<ManagedSshTunnelHandle>,
+}
+
+impl Deref for MongoDbClient {
+    type Target = Client;
+
+    fn deref(&self) -> &Self::Target {
+        &self.client
+    }
+}
+
+impl MongoDbClient {
+    /// Returns a handle to the database that the connection points at.
+    pub fn db(&self) -> Database {
+        self.client.database(&self.database)
+    }
+}
+
+/// Configuration for MongoDB connections.
+///
+/// The driver always connects directly to the configured host, rather than
+/// discovering the other members of a replica set, as those are usually not
+/// reachable through a tunnel. Change streams and snapshot reads can be
+/// served by any data-bearing member.
+///
+/// TLS is not yet supported.
+#[derive(Clone, Debug)]
+pub struct Config {
+    host: String,
+    port: u16,
+    user: String,
+    password: String,
+    database: String,
+    tunnel: TunnelConfig,
+    ssh_timeout_config: SshTimeoutConfig,
+}
+
+impl Config {
+    pub fn new(
+        host: String,
+        port: u16,
+        user: String,
+        password: String,
+        database: String,
+        tunnel: TunnelConfig,
+        ssh_timeout_config: SshTimeoutConfig,
+    ) -> Self {
+        Self {
+            host,
+            port,
+            user,
+            password,
+            database,
+            tunnel,
+            ssh_timeout_config,
+        }
+    }
+
+    pub async fn connect(
+        &self,
+        task_name: &str,
+        ssh_tunnel_manager: &SshTunnelManager,
+    ) -> Result<MongoDbClient, MongoDbError> {
+        let address = format!("mongodb://{}:{}", self.host, self.port);
+        info!(%task_name, %address, "connecting");
+        match self.connect_internal(task_name, ssh_tunnel_manager).await {
+            Ok(t) => {
+                info!(%task_name, %address, "connected");
+                Ok(t)
+            }
+            Err(e) => {
+                warn!(%task_name, %address, "connection failed: {e:#}");
+                Err(e)
+            }
+        }
+    }
+
+    async fn connect_internal(
+        &self,
+        task_name: &str,
+        ssh_tunnel_manager: &SshTunnelManager,
+    ) -> Result<MongoDbClient, MongoDbError> {
+        let (address, ssh_tunnel_handle) = match &self.tunnel {
+            TunnelConfig::Direct => (
+                ServerAddress::Tcp {
+                    host: self.host.clone(),
+                    port: Some(self.port),
+                },
+                None,
+            ),
+            TunnelConfig::Ssh { config } => {
+                let tunnel = ssh_tunnel_manager
+                    .connect(
+                        config.clone(),
+                        &self.host,
+                        self.port,
+                        self.ssh_timeout_config,
+                    )
+                    .await
+                    .map_err(MongoDbError::Ssh)?;
+                // Point the driver at the local end of the tunnel instead of
+                // the upstream server.
+                let local_addr = tunnel.local_addr();
+                let address = ServerAddress::Tcp {
+                    host: local_addr.ip().to_string(),
+                    port: Some(local_addr.port()),
+                };
+                (address, Some(tunnel))
+            }
+            TunnelConfig::AwsPrivatelink { connection_id } => (
+                ServerAddress::Tcp {
+                    host: mz_cloud_resources::vpc_endpoint_name(*connection_id),
+                    port: Some(self.port),
+                },
+                None,
+            ),
+        };
+
+        let mut credential = Credential::default();
+        credential.username = Some(self.user.clone());
+        credential.password = Some(self.password.clone());
+
+        let mut options = ClientOptions::default();
+        options.hosts = vec![address];
+        options.credential = Some(credential);
+        options.direct_connection = Some(true);
+        options.app_name = Some(task_name.to_string());
+
+        let client = Client::with_options(options)?;
+
+        // The driver connects lazily, so issue a command to surface
+        // connection and authentication errors right away.
+        client
+            .database(&self.database)
+            .run_command(bson::doc! { "ping": 1 }, None)
+            .await?;
+
+        Ok(MongoDbClient {
+            client,
+            database: self.database.clone(),
+            _ssh_tunnel_handle: ssh_tunnel_handle,
+        })
+    }
+}
@@ -180,6 +180,38 @@ fn val_to_datum<'a>(
                     }
                 }
             }
+            ScalarType::Enum { labels, .. } => {
+                let idx = match value {
+                    // Enum values in query responses are provided as their label.
+                    Value::Bytes(data) => {
+                        let data = std::str::from_utf8(&data)?;
+                        labels
+                            .iter()
+                            .position(|l| l == data)
+                            .ok_or(anyhow::anyhow!(
+                                "received invalid enum value: {} for column {}",
+                                data,
+                                col_desc.name
+                            ))?
+                    }
+                    // Enum types are provided as 1-indexed integers in the replication
+                    // stream.
+                    Value::Int(val) => usize::try_from(val)
+                        .ok()
+                        .and_then(|val| val.checked_sub(1))
+                        .filter(|idx| *idx < labels.len())
+                        .ok_or(anyhow::anyhow!(
+                            "received invalid enum value: {} for column {}",
+                            val,
+                            col_desc.name
+                        ))?,
+                    _ => Err(anyhow::anyhow!(
+                        "received unexpected value for enum type: {:?}",
+                        value
+                    ))?,
+                };
+                Datum::UInt32(u32::try_from(idx)?)
+            }
             ScalarType::Jsonb => {
                 if let Value::Bytes(data) = value {
                     let j = mz_repr::adt::jsonb::Jsonb::from_slice(&data).map_err(|e| {
@@ -395,6 +395,18 @@ fn parse_data_type(
             Ok(ScalarType::Bytes)
         }
         "json" => Ok(ScalarType::Jsonb),
+        "enum" => Ok(ScalarType::Enum {
+            labels: enum_vals_from_column_type(info.column_type.as_str()).map_err(|_| {
+                UnsupportedDataType {
+                    column_type: info.column_type.clone(),
+                    qualified_table_name: format!("{:?}.{:?}", schema_name, table_name),
+                    column_name: info.column_name.clone(),
+                    intended_type: None,
+                }
+            })?,
+            custom_id: None,
+            custom_oid: None,
+        }),
         _ => Err(UnsupportedDataType {
             column_type: info.column_type.clone(),
             qualified_table_name: format!("{:?}.{:?}", schema_name, table_name),
@@ -41,12 +41,8 @@ pub fn encode_copy_row_binary(
 
     out.extend(count.to_be_bytes());
     let mut buf = BytesMut::new();
-    for (field, typ) in row
-        .iter()
-        .zip(&typ.column_types)
-        .map(|(datum, typ)| (mz_pgrepr::Value::from_datum(datum, &typ.scalar_type), typ))
-    {
-        match field {
+    for (datum, typ) in row.iter().zip(&typ.column_types) {
+        match mz_pgrepr::Value::from_datum(datum, &typ.scalar_type)? {
             None => out.extend(NULL_BYTES),
             Some(field) => {
                 buf.clear();
@@ -76,7 +72,10 @@ pub fn encode_copy_row_text(
 ) -> Result<(), io::Error> {
     let null = null.as_bytes();
     let mut buf = BytesMut::new();
-    for (idx, field) in mz_pgrepr::values_from_row(row, typ).into_iter().enumerate() {
+    for (idx, field) in mz_pgrepr::values_from_row(row, typ)?
+        .into_iter()
+        .enumerate()
+    {
         if idx > 0 {
             out.push(delimiter);
         }
@@ -116,7 +115,10 @@ pub fn encode_copy_row_csv(
     let null = null.as_bytes();
     let is_special = |c: &u8| *c == delim || *c == quote || *c == b'\r' || *c == b'\n';
     let mut buf = BytesMut::new();
-    for (idx, field) in mz_pgrepr::values_from_row(row, typ).into_iter().enumerate() {
+    for (idx, field) in mz_pgrepr::values_from_row(row, typ)?
+        .into_iter()
+        .enumerate()
+    {
         if idx > 0 {
             out.push(delim);
         }
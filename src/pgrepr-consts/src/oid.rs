@@ -31,12 +31,16 @@ pub const TYPE_BYTEA_ARRAY_OID: u32 = 1001;
 pub const TYPE_BYTEA_OID: u32 = 17;
 pub const TYPE_CHAR_ARRAY_OID: u32 = 1002;
 pub const TYPE_CHAR_OID: u32 = 18;
+pub const TYPE_CIDR_ARRAY_OID: u32 = 651;
+pub const TYPE_CIDR_OID: u32 = 650;
 pub const TYPE_DATE_ARRAY_OID: u32 = 1182;
 pub const TYPE_DATE_OID: u32 = 1082;
 pub const TYPE_FLOAT4_ARRAY_OID: u32 = 1021;
 pub const TYPE_FLOAT4_OID: u32 = 700;
 pub const TYPE_FLOAT8_ARRAY_OID: u32 = 1022;
 pub const TYPE_FLOAT8_OID: u32 = 701;
+pub const TYPE_INET_ARRAY_OID: u32 = 1041;
+pub const TYPE_INET_OID: u32 = 869;
 pub const TYPE_INT2_ARRAY_OID: u32 = 1005;
 pub const TYPE_INT2_OID: u32 = 21;
 pub const TYPE_INT2_VECTOR_ARRAY_OID: u32 = 1006;
@@ -50,6 +54,8 @@ pub const TYPE_INTERVAL_OID: u32 = 1186;
 pub const TYPE_JSONB_ARRAY_OID: u32 = 3807;
 pub const TYPE_JSONB_OID: u32 = 3802;
 pub const TYPE_LIST_OID_OID: u32 = 16_384;
+pub const TYPE_MACADDR_ARRAY_OID: u32 = 1040;
+pub const TYPE_MACADDR_OID: u32 = 829;
 pub const TYPE_NAME_ARRAY_OID: u32 = 1003;
 pub const TYPE_NAME_OID: u32 = 19;
 pub const TYPE_NUMERIC_ARRAY_OID: u32 = 1231;
@@ -80,6 +86,7 @@ pub const TYPE_INT4RANGE_OID: u32 = 3904;
 pub const TYPE_INT4RANGE_ARRAY_OID: u32 = 3905;
 pub const TYPE_ANYRANGE_OID: u32 = 3831;
 pub const TYPE_ANYCOMPATIBLERANGE_OID: u32 = 5080;
+pub const TYPE_ANYENUM_OID: u32 = 3500;
 pub const TYPE_INT8RANGE_OID: u32 = 3926;
 pub const TYPE_INT8RANGE_ARRAY_OID: u32 = 3927;
 pub const TYPE_DATERANGE_OID: u32 = 3912;
@@ -668,3 +675,31 @@ pub const VIEW_MZ_NOTICES_OID: u32 = 16941;
 pub const VIEW_MZ_NOTICES_REDACTED_OID: u32 = 16942;
 pub const INDEX_MZ_NOTICES_IND_OID: u32 = 16943;
 pub const ROLE_PUBLIC_OID: u32 = 16944;
+pub const TABLE_MZ_ENUM_TYPES_OID: u32 = 16945;
+pub const FUNC_APPROX_COUNT_DISTINCT_OID: u32 = 16946;
+pub const FUNC_APPROX_COUNT_DISTINCT_PRECISION_OID: u32 = 16947;
+pub const FUNC_HLL_SKETCH_OID: u32 = 16948;
+pub const FUNC_HLL_SKETCH_PRECISION_OID: u32 = 16949;
+pub const FUNC_HLL_UNION_AGG_OID: u32 = 16950;
+pub const FUNC_HLL_UNION_OID: u32 = 16951;
+pub const FUNC_HLL_CARDINALITY_OID: u32 = 16952;
+pub const FUNC_JSONB_PATH_EXISTS_OID: u32 = 16953;
+pub const FUNC_JSONB_PATH_EXISTS_VARS_OID: u32 = 16954;
+pub const FUNC_JSONB_PATH_EXISTS_SILENT_OID: u32 = 16955;
+pub const FUNC_JSONB_PATH_MATCH_OID: u32 = 16956;
+pub const FUNC_JSONB_PATH_MATCH_VARS_OID: u32 = 16957;
+pub const FUNC_JSONB_PATH_MATCH_SILENT_OID: u32 = 16958;
+pub const FUNC_JSONB_PATH_QUERY_OID: u32 = 16959;
+pub const FUNC_JSONB_PATH_QUERY_VARS_OID: u32 = 16960;
+pub const FUNC_JSONB_PATH_QUERY_SILENT_OID: u32 = 16961;
+pub const FUNC_JSONB_PATH_QUERY_ARRAY_OID: u32 = 16962;
+pub const FUNC_JSONB_PATH_QUERY_ARRAY_VARS_OID: u32 = 16963;
+pub const FUNC_JSONB_PATH_QUERY_ARRAY_SILENT_OID: u32 = 16964;
+pub const FUNC_JSONB_PATH_QUERY_FIRST_OID: u32 = 16965;
+pub const FUNC_JSONB_PATH_QUERY_FIRST_VARS_OID: u32 = 16966;
+pub const FUNC_JSONB_PATH_QUERY_FIRST_SILENT_OID: u32 = 16967;
+pub const OP_JSONB_PATH_EXISTS_OID: u32 = 16968;
+pub const OP_JSONB_PATH_MATCH_OID: u32 = 16969;
+pub const FUNC_MZ_FLOAT8_PROMOTION_OID: u32 = 16970;
+pub const LOG_MZ_LIR_MAPPING_PER_WORKER_OID: u32 = 16971;
+pub const VIEW_MZ_LIR_MAPPING_OID: u32 = 16972;
@@ -29,6 +29,7 @@ pub use types::{
 };
 pub use value::interval::Interval;
 pub use value::jsonb::Jsonb;
+pub use value::network::{Inet, MacAddr};
 pub use value::numeric::Numeric;
 pub use value::record::Record;
 pub use value::unsigned::{UInt2, UInt4, UInt8};
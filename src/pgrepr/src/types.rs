@@ -142,6 +142,12 @@ pub enum Type {
     },
     /// A universally unique identifier.
     Uuid,
+    /// An IPv4 or IPv6 host address, with an optional netmask.
+    Inet,
+    /// An IPv4 or IPv6 network.
+    Cidr,
+    /// A MAC address.
+    MacAddr,
     /// A function name.
     RegProc,
     /// A type name.
@@ -163,6 +169,13 @@ pub enum Type {
     /// A list of privileges granted to a user that uses [`mz_repr::adt::system::Oid`]s for role
     /// references. This type is used primarily for compatibility with PostgreSQL.
     AclItem,
+    /// A value of an enumerated type.
+    Enum {
+        /// The labels of the enumerated type, in declaration order.
+        labels: Vec<String>,
+        /// The OID of the enumerated type's catalog entry, if it has one.
+        oid: Option<u32>,
+    },
 }
 
 /// An unpacked [`typmod`](Type::typmod) for a [`Type`].
@@ -569,6 +582,9 @@ impl Type {
             postgres_types::Type::TIMESTAMP => Type::Timestamp { precision: None },
             postgres_types::Type::TIMESTAMPTZ => Type::TimestampTz { precision: None },
             postgres_types::Type::UUID => Type::Uuid,
+            postgres_types::Type::INET => Type::Inet,
+            postgres_types::Type::CIDR => Type::Cidr,
+            postgres_types::Type::MACADDR => Type::MacAddr,
             postgres_types::Type::REGCLASS => Type::RegClass,
             postgres_types::Type::REGPROC => Type::RegProc,
             postgres_types::Type::REGTYPE => Type::RegType,
@@ -606,6 +622,9 @@ impl Type {
                 Type::Array(Box::new(Type::TimestampTz { precision: None }))
             }
             postgres_types::Type::UUID_ARRAY => Type::Array(Box::new(Type::Uuid)),
+            postgres_types::Type::INET_ARRAY => Type::Array(Box::new(Type::Inet)),
+            postgres_types::Type::CIDR_ARRAY => Type::Array(Box::new(Type::Cidr)),
+            postgres_types::Type::MACADDR_ARRAY => Type::Array(Box::new(Type::MacAddr)),
             postgres_types::Type::VARCHAR_ARRAY => {
                 Type::Array(Box::new(Type::VarChar { max_length: None }))
             }
@@ -731,6 +750,9 @@ impl Type {
                 Type::Timestamp { .. } => &postgres_types::Type::TIMESTAMP_ARRAY,
                 Type::TimestampTz { .. } => &postgres_types::Type::TIMESTAMPTZ_ARRAY,
                 Type::Uuid => &postgres_types::Type::UUID_ARRAY,
+                Type::Inet => &postgres_types::Type::INET_ARRAY,
+                Type::Cidr => &postgres_types::Type::CIDR_ARRAY,
+                Type::MacAddr => &postgres_types::Type::MACADDR_ARRAY,
                 Type::RegClass => &postgres_types::Type::REGCLASS_ARRAY,
                 Type::RegProc => &postgres_types::Type::REGPROC_ARRAY,
                 Type::RegType => &postgres_types::Type::REGTYPE_ARRAY,
@@ -746,6 +768,7 @@ impl Type {
                     _ => unreachable!(),
                 },
                 Type::MzAclItem => &MZ_ACL_ITEM_ARRAY,
+                Type::Enum { .. } => unreachable!(),
             },
             Type::Bool => &postgres_types::Type::BOOL,
             Type::Bytea => &postgres_types::Type::BYTEA,
@@ -776,6 +799,9 @@ impl Type {
             Type::Timestamp { .. } => &postgres_types::Type::TIMESTAMP,
             Type::TimestampTz { .. } => &postgres_types::Type::TIMESTAMPTZ,
             Type::Uuid => &postgres_types::Type::UUID,
+            Type::Inet => &postgres_types::Type::INET,
+            Type::Cidr => &postgres_types::Type::CIDR,
+            Type::MacAddr => &postgres_types::Type::MACADDR,
             Type::RegClass => &postgres_types::Type::REGCLASS,
             Type::RegProc => &postgres_types::Type::REGPROC,
             Type::RegType => &postgres_types::Type::REGTYPE,
@@ -791,6 +817,7 @@ impl Type {
                 t => unreachable!("{t:?} is not a range element type"),
             },
             Type::MzAclItem => &MZ_ACL_ITEM,
+            Type::Enum { .. } => &postgres_types::Type::ANYENUM,
         }
     }
 
@@ -855,7 +882,12 @@ impl Type {
     ///
     /// [OID]: https://www.postgresql.org/docs/current/datatype-oid.html
     pub fn oid(&self) -> u32 {
-        self.inner().oid()
+        match self {
+            // Values of named enumerated types have the type of their catalog
+            // entry. Anonymous ones only have the pseudo-type.
+            Type::Enum { oid: Some(oid), .. } => *oid,
+            _ => self.inner().oid(),
+        }
     }
 
     /// Returns the constraint on the type, if any.
@@ -919,10 +951,14 @@ impl Type {
             | Type::Timestamp { precision: None }
             | Type::TimestampTz { precision: None }
             | Type::Uuid
+            | Type::Inet
+            | Type::Cidr
+            | Type::MacAddr
             | Type::MzTimestamp
             | Type::VarChar { max_length: None }
             | Type::Range { .. }
-            | Type::MzAclItem => None,
+            | Type::MzAclItem
+            | Type::Enum { .. } => None,
         }
     }
 
@@ -960,6 +996,9 @@ impl Type {
             Type::Timestamp { .. } => 8,
             Type::TimestampTz { .. } => 8,
             Type::Uuid => 16,
+            Type::Inet => -1,
+            Type::Cidr => -1,
+            Type::MacAddr => 6,
             Type::RegClass => 4,
             Type::RegProc => 4,
             Type::RegType => 4,
@@ -972,6 +1011,7 @@ impl Type {
             Type::Range { .. } => -1,
             Type::MzAclItem => MzAclItem::binary_size().try_into().expect("must fit"),
             Type::AclItem => AclItem::binary_size().try_into().expect("must fit"),
+            Type::Enum { .. } => 4,
         }
     }
 
@@ -1095,6 +1135,9 @@ impl TryFrom<&Type> for ScalarType {
                 },
             }),
             Type::Uuid => Ok(ScalarType::Uuid),
+            Type::Inet => Ok(ScalarType::Inet),
+            Type::Cidr => Ok(ScalarType::Cidr),
+            Type::MacAddr => Ok(ScalarType::MacAddr),
             Type::RegClass => Ok(ScalarType::RegClass),
             Type::RegProc => Ok(ScalarType::RegProc),
             Type::RegType => Ok(ScalarType::RegType),
@@ -1104,6 +1147,11 @@ impl TryFrom<&Type> for ScalarType {
                 element_type: Box::new(TryFrom::try_from(&**element_type)?),
             }),
             Type::MzAclItem => Ok(ScalarType::MzAclItem),
+            Type::Enum { labels, oid } => Ok(ScalarType::Enum {
+                labels: labels.clone(),
+                custom_id: None,
+                custom_oid: *oid,
+            }),
         }
     }
 }
@@ -1254,6 +1302,9 @@ impl From<&ScalarType> for Type {
                 precision: (*precision).map(TimestampPrecision::from),
             },
             ScalarType::Uuid => Type::Uuid,
+            ScalarType::Inet => Type::Inet,
+            ScalarType::Cidr => Type::Cidr,
+            ScalarType::MacAddr => Type::MacAddr,
             ScalarType::Numeric { max_scale } => Type::Numeric {
                 constraints: Some(NumericConstraints {
                     max_precision: i32::from(NUMERIC_DATUM_MAX_PRECISION),
@@ -1272,6 +1323,12 @@ impl From<&ScalarType> for Type {
                 element_type: Box::new(From::from(&**element_type)),
             },
             ScalarType::MzAclItem => Type::MzAclItem,
+            ScalarType::Enum {
+                labels, custom_oid, ..
+            } => Type::Enum {
+                labels: labels.clone(),
+                oid: *custom_oid,
+            },
         }
     }
 }
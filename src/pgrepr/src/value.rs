@@ -13,7 +13,7 @@ use std::{io, str};
 
 use bytes::{BufMut, BytesMut};
 use chrono::{DateTime, NaiveDateTime, NaiveTime, Utc};
-use mz_ore::cast::ReinterpretCast;
+use mz_ore::cast::{CastFrom, ReinterpretCast};
 use mz_pgwire_common::Format;
 use mz_repr::adt::array::ArrayDimension;
 use mz_repr::adt::char;
@@ -29,10 +29,11 @@ use postgres_types::{FromSql, IsNull, ToSql, Type as PgType};
 use uuid::Uuid;
 
 use crate::types::{UINT2, UINT4, UINT8};
-use crate::{Interval, Jsonb, Numeric, Type, UInt2, UInt4, UInt8};
+use crate::{Inet, Interval, Jsonb, MacAddr, Numeric, Type, UInt2, UInt4, UInt8};
 
 pub mod interval;
 pub mod jsonb;
+pub mod network;
 pub mod numeric;
 pub mod record;
 pub mod unsigned;
@@ -116,6 +117,14 @@ pub enum Value {
     /// A list of privileges granted to a user that uses [`mz_repr::adt::system::Oid`]s for role
     /// references. This type is used primarily for compatibility with PostgreSQL.
     AclItem(AclItem),
+    /// A label of an enumerated type.
+    Enum(String),
+    /// An IPv4 or IPv6 host address, with an optional netmask.
+    Inet(mz_repr::adt::network::Inet),
+    /// An IPv4 or IPv6 network.
+    Cidr(mz_repr::adt::network::Inet),
+    /// A MAC address.
+    MacAddr(mz_repr::adt::network::MacAddr),
 }
 
 impl Value {
@@ -123,8 +132,11 @@ impl Value {
     ///
     /// The conversion happens in the obvious manner, except that `Datum::Null`
     /// is converted to `None` to align with how PostgreSQL handles NULL.
-    pub fn from_datum(datum: Datum, typ: &ScalarType) -> Option<Value> {
-        match (datum, typ) {
+    ///
+    /// Returns an error if the datum is an enum ordinal that does not name one
+    /// of the enum's labels.
+    pub fn from_datum(datum: Datum, typ: &ScalarType) -> Result<Option<Value>, io::Error> {
+        let value = match (datum, typ) {
             (Datum::Null, _) => None,
             (Datum::True, ScalarType::Bool) => Some(Value::Bool(true)),
             (Datum::False, ScalarType::Bool) => Some(Value::Bool(false)),
@@ -138,6 +150,19 @@ impl Value {
             (Datum::UInt32(oid), ScalarType::RegProc) => Some(Value::Oid(oid)),
             (Datum::UInt32(oid), ScalarType::RegType) => Some(Value::Oid(oid)),
             (Datum::UInt32(u), ScalarType::UInt32) => Some(Value::UInt4(UInt4(u))),
+            (Datum::UInt32(i), ScalarType::Enum { labels, .. }) => {
+                let label = labels.get(usize::cast_from(i)).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "invalid ordinal {} for enum with {} labels",
+                            i,
+                            labels.len()
+                        ),
+                    )
+                })?;
+                Some(Value::Enum(label.clone()))
+            }
             (Datum::UInt64(u), ScalarType::UInt64) => Some(Value::UInt8(UInt8(u))),
             (Datum::Float32(f), ScalarType::Float32) => Some(Value::Float4(*f)),
             (Datum::Float64(f), ScalarType::Float64) => Some(Value::Float8(*f)),
@@ -145,6 +170,9 @@ impl Value {
             (Datum::MzTimestamp(t), ScalarType::MzTimestamp) => Some(Value::MzTimestamp(t)),
             (Datum::MzAclItem(mai), ScalarType::MzAclItem) => Some(Value::MzAclItem(mai)),
             (Datum::AclItem(ai), ScalarType::AclItem) => Some(Value::AclItem(ai)),
+            (Datum::Inet(i), ScalarType::Inet) => Some(Value::Inet(i)),
+            (Datum::Inet(i), ScalarType::Cidr) => Some(Value::Cidr(i)),
+            (Datum::MacAddr(m), ScalarType::MacAddr) => Some(Value::MacAddr(m)),
             (Datum::Date(d), ScalarType::Date) => Some(Value::Date(d)),
             (Datum::Time(t), ScalarType::Time) => Some(Value::Time(t)),
             (Datum::Timestamp(ts), ScalarType::Timestamp { .. }) => Some(Value::Timestamp(ts)),
@@ -169,7 +197,7 @@ impl Value {
                     .elements()
                     .iter()
                     .map(|elem| Value::from_datum(elem, elem_type))
-                    .collect();
+                    .collect::<Result<_, _>>()?;
                 Some(Value::Array { dims, elements })
             }
             (Datum::Array(array), ScalarType::Int2Vector) => {
@@ -179,14 +207,14 @@ impl Value {
                     .elements()
                     .iter()
                     .map(|elem| Value::from_datum(elem, &ScalarType::Int16))
-                    .collect();
+                    .collect::<Result<_, _>>()?;
                 Some(Value::Int2Vector { elements })
             }
             (Datum::List(list), ScalarType::List { element_type, .. }) => {
                 let elements = list
                     .iter()
                     .map(|elem| Value::from_datum(elem, element_type))
-                    .collect();
+                    .collect::<Result<_, _>>()?;
                 Some(Value::List(elements))
             }
             (Datum::List(record), ScalarType::Record { fields, .. }) => {
@@ -194,27 +222,28 @@ impl Value {
                     .iter()
                     .zip(fields)
                     .map(|(e, (_name, ty))| Value::from_datum(e, &ty.scalar_type))
-                    .collect();
+                    .collect::<Result<_, _>>()?;
                 Some(Value::Record(fields))
             }
             (Datum::Map(dict), ScalarType::Map { value_type, .. }) => {
                 let entries = dict
                     .iter()
-                    .map(|(k, v)| (k.to_owned(), Value::from_datum(v, value_type)))
-                    .collect();
+                    .map(|(k, v)| Ok((k.to_owned(), Value::from_datum(v, value_type)?)))
+                    .collect::<Result<_, io::Error>>()?;
                 Some(Value::Map(entries))
             }
             (Datum::Range(range), ScalarType::Range { element_type }) => {
-                let value_range = range.into_bounds(|b| {
-                    Box::new(
-                        Value::from_datum(b.datum(), element_type)
+                let value_range = range.try_into_bounds(|b| {
+                    Ok::<_, io::Error>(Box::new(
+                        Value::from_datum(b.datum(), element_type)?
                             .expect("RangeBounds never contain Datum::Null"),
-                    )
-                });
+                    ))
+                })?;
                 Some(Value::Range(value_range))
             }
             _ => panic!("can't serialize {}::{:?}", datum, typ),
-        }
+        };
+        Ok(value)
     }
 
     /// Converts a Materialize datum from this value.
@@ -313,6 +342,19 @@ impl Value {
             }
             Value::MzAclItem(mz_acl_item) => Datum::MzAclItem(mz_acl_item),
             Value::AclItem(acl_item) => Datum::AclItem(acl_item),
+            Value::Inet(inet) | Value::Cidr(inet) => Datum::Inet(inet),
+            Value::MacAddr(mac) => Datum::MacAddr(mac),
+            Value::Enum(label) => {
+                let labels = match typ {
+                    Type::Enum { labels, .. } => labels,
+                    _ => panic!("Value::Enum should have type Type::Enum. Found {:?}", typ),
+                };
+                let position = labels
+                    .iter()
+                    .position(|l| *l == label)
+                    .expect("Value::Enum label validated during decoding");
+                Datum::UInt32(u32::try_from(position).expect("enum has at most u32::MAX labels"))
+            }
         }
     }
 
@@ -381,9 +423,11 @@ impl Value {
                 Some(elem) => Ok(elem.encode_text(buf.nonnull_buffer())),
             })
             .expect("provided closure never fails"),
-            Value::Text(s) | Value::VarChar(s) | Value::BpChar(s) | Value::Name(s) => {
-                strconv::format_string(buf, s)
-            }
+            Value::Text(s)
+            | Value::VarChar(s)
+            | Value::BpChar(s)
+            | Value::Name(s)
+            | Value::Enum(s) => strconv::format_string(buf, s),
             Value::Time(t) => strconv::format_time(buf, *t),
             Value::Timestamp(ts) => strconv::format_timestamp(buf, ts),
             Value::TimestampTz(ts) => strconv::format_timestamptz(buf, ts),
@@ -397,6 +441,9 @@ impl Value {
             .expect("provided closure never fails"),
             Value::MzAclItem(mz_acl_item) => strconv::format_mz_acl_item(buf, *mz_acl_item),
             Value::AclItem(acl_item) => strconv::format_acl_item(buf, *acl_item),
+            Value::Inet(inet) => strconv::format_inet(buf, *inet),
+            Value::Cidr(inet) => strconv::format_cidr(buf, *inet),
+            Value::MacAddr(mac) => strconv::format_macaddr(buf, *mac),
         }
     }
 
@@ -535,6 +582,14 @@ impl Value {
                 Ok(postgres_types::IsNull::No)
             }
             Value::AclItem(_) => Err("aclitem has no binary encoding".into()),
+            // The binary encoding of an enum value is its label.
+            Value::Enum(label) => {
+                buf.extend_from_slice(label.as_bytes());
+                Ok(postgres_types::IsNull::No)
+            }
+            Value::Inet(inet) => Inet(*inet).to_sql(&PgType::INET, buf),
+            Value::Cidr(inet) => Inet(*inet).to_sql(&PgType::CIDR, buf),
+            Value::MacAddr(mac) => MacAddr(*mac).to_sql(&PgType::MACADDR, buf),
         }
         .expect("encode_binary should never trigger a to_sql failure");
         if let IsNull::Yes = is_null {
@@ -627,6 +682,10 @@ impl Value {
             })?),
             Type::MzAclItem => Value::MzAclItem(strconv::parse_mz_acl_item(s)?),
             Type::AclItem => Value::AclItem(strconv::parse_acl_item(s)?),
+            Type::Enum { labels, .. } => Value::Enum(decode_enum_label(labels, s)?),
+            Type::Inet => Value::Inet(strconv::parse_inet(s)?),
+            Type::Cidr => Value::Cidr(strconv::parse_cidr(s)?),
+            Type::MacAddr => Value::MacAddr(strconv::parse_macaddr(s)?),
         })
     }
 
@@ -697,10 +756,26 @@ impl Value {
                 Ok(Value::MzAclItem(mz_acl_item))
             }
             Type::AclItem => Err("aclitem has no binary encoding".into()),
+            Type::Enum { labels, .. } => {
+                let s = str::from_utf8(raw)?;
+                Ok(Value::Enum(decode_enum_label(labels, s)?))
+            }
+            Type::Inet => Inet::from_sql(ty.inner(), raw).map(|inet| Value::Inet(inet.0)),
+            Type::Cidr => Inet::from_sql(ty.inner(), raw).map(|inet| Value::Cidr(inet.0)),
+            Type::MacAddr => MacAddr::from_sql(ty.inner(), raw).map(|mac| Value::MacAddr(mac.0)),
         }
     }
 }
 
+/// Validates that `s` is one of the `labels` of an enumerated type.
+fn decode_enum_label(labels: &[String], s: &str) -> Result<String, Box<dyn Error + Sync + Send>> {
+    if labels.iter().any(|l| l == s) {
+        Ok(s.to_owned())
+    } else {
+        Err(format!("invalid input value for enum: \"{}\"", s).into())
+    }
+}
+
 fn encode_element(buf: &mut BytesMut, elem: Option<&Value>, ty: &Type) -> Result<(), io::Error> {
     match elem {
         None => buf.put_i32(-1),
@@ -728,7 +803,7 @@ fn pg_len(what: &str, len: usize) -> Result<i32, io::Error> {
 ///
 /// Calling this function is equivalent to mapping [`Value::from_datum`] over
 /// every datum in `row`.
-pub fn values_from_row(row: &Row, typ: &RelationType) -> Vec<Option<Value>> {
+pub fn values_from_row(row: &Row, typ: &RelationType) -> Result<Vec<Option<Value>>, io::Error> {
     row.iter()
         .zip(typ.column_types.iter())
         .map(|(col, typ)| Value::from_datum(col, &typ.scalar_type))
@@ -762,4 +837,23 @@ mod tests {
             "invalid input syntax for type array: Specifying array lower bounds is not supported: \"[0:0]={t}\"".to_string()
         );
     }
+
+    #[mz_ore::test]
+    fn enum_ordinal_out_of_range() {
+        let typ = ScalarType::Enum {
+            labels: vec!["a".into(), "b".into()],
+            custom_id: None,
+            custom_oid: None,
+        };
+        assert!(matches!(
+            Value::from_datum(Datum::UInt32(1), &typ),
+            Ok(Some(Value::Enum(label))) if label == "b"
+        ));
+        assert_eq!(
+            Value::from_datum(Datum::UInt32(2), &typ)
+                .unwrap_err()
+                .to_string(),
+            "invalid ordinal 2 for enum with 2 labels"
+        );
+    }
 }
@@ -0,0 +1,117 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::error::Error;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use bytes::{BufMut, BytesMut};
+use mz_repr::adt::network;
+use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+/// PostgreSQL's on-the-wire identifier for IPv4 addresses.
+///
+/// See: <https://github.com/postgres/postgres/blob/REL_16_0/src/include/utils/inet.h#L39-L40>
+const PGSQL_AF_INET: u8 = 2;
+/// PostgreSQL's on-the-wire identifier for IPv6 addresses.
+const PGSQL_AF_INET6: u8 = 3;
+
+/// A wrapper for [`network::Inet`] that can be serialized to and deserialized
+/// from the PostgreSQL binary format of the `inet` and `cidr` types.
+#[derive(Debug, Clone, Copy)]
+pub struct Inet(pub network::Inet);
+
+impl<'a> FromSql<'a> for Inet {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Inet, Box<dyn Error + Sync + Send>> {
+        let [family, prefix_len, _is_cidr, len, addr @ ..] = raw else {
+            return Err("invalid network address length".into());
+        };
+        if usize::from(*len) != addr.len() {
+            return Err("invalid network address length".into());
+        }
+        let addr = match *family {
+            PGSQL_AF_INET => IpAddr::V4(Ipv4Addr::from(<[u8; 4]>::try_from(addr)?)),
+            PGSQL_AF_INET6 => IpAddr::V6(Ipv6Addr::from(<[u8; 16]>::try_from(addr)?)),
+            _ => return Err(format!("invalid address family in external \"{ty}\" value").into()),
+        };
+        let inet = network::Inet::new(addr, *prefix_len)?;
+        if *ty == Type::CIDR && !inet.is_network() {
+            return Err("invalid external \"cidr\" value".into());
+        }
+        Ok(Inet(inet))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::INET | Type::CIDR)
+    }
+}
+
+impl ToSql for Inet {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+        let family = match self.0.addr() {
+            IpAddr::V4(_) => PGSQL_AF_INET,
+            IpAddr::V6(_) => PGSQL_AF_INET6,
+        };
+        out.put_u8(family);
+        out.put_u8(self.0.prefix_len());
+        out.put_u8(u8::from(*ty == Type::CIDR));
+        match self.0.addr() {
+            IpAddr::V4(addr) => {
+                out.put_u8(4);
+                out.put_slice(&addr.octets());
+            }
+            IpAddr::V6(addr) => {
+                out.put_u8(16);
+                out.put_slice(&addr.octets());
+            }
+        }
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::INET | Type::CIDR)
+    }
+
+    to_sql_checked!();
+}
+
+/// A wrapper for [`network::MacAddr`] that can be serialized to and
+/// deserialized from the PostgreSQL binary format of the `macaddr` type.
+#[derive(Debug, Clone, Copy)]
+pub struct MacAddr(pub network::MacAddr);
+
+impl<'a> FromSql<'a> for MacAddr {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<MacAddr, Box<dyn Error + Sync + Send>> {
+        Ok(MacAddr(network::MacAddr::decode_binary(raw)?))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::MACADDR
+    }
+}
+
+impl ToSql for MacAddr {
+    fn to_sql(
+        &self,
+        _: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+        out.put_slice(&self.0.bytes());
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::MACADDR
+    }
+
+    to_sql_checked!();
+}
@@ -1678,6 +1678,7 @@ where
             | ExecuteResponse::CreatedSource { .. }
             | ExecuteResponse::CreatedTable { .. }
             | ExecuteResponse::CreatedType
+            | ExecuteResponse::CreatedFunction
             | ExecuteResponse::CreatedView { .. }
             | ExecuteResponse::CreatedViews { .. }
             | ExecuteResponse::Comment
@@ -1835,10 +1836,22 @@ where
 
                     // Drain panics if it's > len, so cap it.
                     let drain_rows = cmp::min(want_rows, batch_rows.len());
-                    self.send_all(batch_rows.drain(..drain_rows).map(|row| {
-                        BackendMessage::DataRow(mz_pgrepr::values_from_row(&row, row_desc.typ()))
-                    }))
-                    .await?;
+                    let values = match batch_rows
+                        .drain(..drain_rows)
+                        .map(|row| mz_pgrepr::values_from_row(&row, row_desc.typ()))
+                        .collect::<Result<Vec<_>, _>>()
+                    {
+                        Ok(values) => values,
+                        Err(e) => {
+                            let msg = format!("internal error: {}", e);
+                            return self
+                                .error(ErrorResponse::error(SqlState::INTERNAL_ERROR, msg.clone()))
+                                .await
+                                .map(|state| (state, SendRowsEndedReason::Errored { error: msg }));
+                        }
+                    };
+                    self.send_all(values.into_iter().map(BackendMessage::DataRow))
+                        .await?;
                     total_sent_rows += drain_rows;
                     want_rows -= drain_rows;
                     // If we have sent the number of requested rows, put the remainder of the batch
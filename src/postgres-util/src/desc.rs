@@ -144,6 +144,10 @@ pub struct PostgresColumnDesc {
     pub type_mod: i32,
     /// True if the column lacks a `NOT NULL` constraint.
     pub nullable: bool,
+    /// If the column's type is an enum, the type's labels in sort order.
+    pub enum_labels: Option<Vec<String>>,
+    /// If the column's type is an enum, the type's name.
+    pub enum_name: Option<String>,
 }
 
 impl PostgresColumnDesc {
@@ -158,6 +162,7 @@ impl PostgresColumnDesc {
             && self.col_num == other.col_num
             && self.type_oid == other.type_oid
             && self.type_mod == other.type_mod
+            && self.enum_labels == other.enum_labels
             // Columns are compatible if:
             // - self is nullable; introducing a not null constraint doesn't
             //   change this column's behavior.
@@ -174,6 +179,13 @@ impl RustType<ProtoPostgresColumnDesc> for PostgresColumnDesc {
             type_oid: self.type_oid,
             type_mod: self.type_mod,
             nullable: self.nullable,
+            enum_labels: self
+                .enum_labels
+                .as_ref()
+                .map(|labels| ProtoPostgresEnumLabels {
+                    labels: labels.clone(),
+                }),
+            enum_name: self.enum_name.clone(),
         }
     }
 
@@ -189,6 +201,8 @@ impl RustType<ProtoPostgresColumnDesc> for PostgresColumnDesc {
             type_oid: proto.type_oid,
             type_mod: proto.type_mod,
             nullable: proto.nullable,
+            enum_labels: proto.enum_labels.map(|e| e.labels),
+            enum_name: proto.enum_name,
         })
     }
 }
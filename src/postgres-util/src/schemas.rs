@@ -94,8 +94,18 @@ pub async fn publication_info(
                         a.attnum AS colnum,
                         a.atttypmod AS typmod,
                         a.attnotnull AS not_null,
-                        b.oid IS NOT NULL AS primary_key
+                        b.oid IS NOT NULL AS primary_key,
+                        CASE WHEN t.typtype = 'e' THEN
+                            ARRAY(
+                                SELECT e.enumlabel::pg_catalog.text
+                                FROM pg_catalog.pg_enum e
+                                WHERE e.enumtypid = t.oid
+                                ORDER BY e.enumsortorder
+                            )
+                        END AS enum_labels,
+                        CASE WHEN t.typtype = 'e' THEN t.typname::pg_catalog.text END AS enum_name
                     FROM pg_catalog.pg_attribute a
+                    JOIN pg_catalog.pg_type t ON a.atttypid = t.oid
                     LEFT JOIN pg_catalog.pg_constraint b
                         ON a.attrelid = b.conrelid
                         AND b.contype = 'p'
@@ -118,12 +128,16 @@ pub async fn publication_info(
                     .expect("non-negative values");
                 let type_mod: i32 = row.get("typmod");
                 let not_null: bool = row.get("not_null");
+                let enum_labels: Option<Vec<String>> = row.get("enum_labels");
+                let enum_name: Option<String> = row.get("enum_name");
                 Ok(PostgresColumnDesc {
                     name,
                     col_num,
                     type_oid,
                     type_mod,
                     nullable: !not_null,
+                    enum_labels,
+                    enum_name,
                 })
             })
             .collect::<Result<Vec<_>, PostgresError>>()?;
@@ -73,6 +73,8 @@ pub enum TryFromProtoError {
     InvalidBitFlags(String),
     /// Failed to deserialize a LIKE/ILIKE pattern.
     LikePatternDeserializationError(String),
+    /// Failed to deserialize a SQL/JSON path.
+    JsonPathDeserializationError(String),
 }
 
 impl TryFromProtoError {
@@ -164,6 +166,11 @@ impl std::fmt::Display for TryFromProtoError {
                 "Protobuf deserialization failed for a LIKE/ILIKE pattern: `{}`",
                 inner_error
             ),
+            JsonPathDeserializationError(inner_error) => write!(
+                f,
+                "Protobuf deserialization failed for a SQL/JSON path: `{}`",
+                inner_error
+            ),
         }
     }
 }
@@ -198,6 +205,7 @@ impl std::error::Error for TryFromProtoError {
             InvalidUrl(error) => Some(error),
             InvalidBitFlags(_) => None,
             LikePatternDeserializationError(_) => None,
+            JsonPathDeserializationError(_) => None,
         }
     }
 }
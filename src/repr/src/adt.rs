@@ -24,6 +24,7 @@ pub mod datetime;
 pub mod interval;
 pub mod jsonb;
 pub mod mz_acl_item;
+pub mod network;
 pub mod numeric;
 pub mod pg_legacy_name;
 pub mod range;
@@ -0,0 +1,588 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Network address abstract data types.
+//!
+//! [`Inet`] backs both the `inet` and `cidr` SQL types, which share a
+//! representation and differ only in whether host bits to the right of the
+//! netmask may be set. [`MacAddr`] backs the `macaddr` SQL type.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Error};
+use proptest::arbitrary::Arbitrary;
+use proptest::prelude::any;
+use proptest::strategy::{BoxedStrategy, Strategy};
+use serde::{Deserialize, Serialize};
+
+/// The maximum netmask length of an IPv4 address.
+pub const IPV4_MAX_PREFIX_LEN: u8 = 32;
+/// The maximum netmask length of an IPv6 address.
+pub const IPV6_MAX_PREFIX_LEN: u8 = 128;
+
+const FAMILY_V4: u8 = 4;
+const FAMILY_V6: u8 = 6;
+
+/// An IPv4 or IPv6 host address with an optional netmask.
+///
+/// Modeled after the `inet` type in PostgreSQL. Values of the `cidr` type use
+/// the same representation, but are guaranteed to have no bits set to the
+/// right of the netmask.
+///
+/// See: <https://github.com/postgres/postgres/blob/REL_16_0/src/include/utils/inet.h>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Inet {
+    addr: IpAddr,
+    prefix_len: u8,
+}
+
+impl Inet {
+    /// Constructs a new `Inet`, returning an error if `prefix_len` is too
+    /// large for the address family of `addr`.
+    pub fn new(addr: IpAddr, prefix_len: u8) -> Result<Inet, Error> {
+        if prefix_len > max_prefix_len(&addr) {
+            bail!("invalid mask length: {prefix_len}");
+        }
+        Ok(Inet { addr, prefix_len })
+    }
+
+    /// Constructs a new `Inet` whose netmask covers the entire address.
+    pub fn from_addr(addr: IpAddr) -> Inet {
+        Inet {
+            prefix_len: max_prefix_len(&addr),
+            addr,
+        }
+    }
+
+    /// Returns the address, ignoring the netmask.
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Returns the length of the netmask in bits.
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns the maximum netmask length for this address's family.
+    pub fn max_prefix_len(&self) -> u8 {
+        max_prefix_len(&self.addr)
+    }
+
+    /// Returns the PostgreSQL family number of the address, i.e. `4` or `6`.
+    pub fn family(&self) -> u8 {
+        match self.addr {
+            IpAddr::V4(_) => FAMILY_V4,
+            IpAddr::V6(_) => FAMILY_V6,
+        }
+    }
+
+    /// Returns a copy of this address with a netmask of `prefix_len` bits.
+    pub fn with_prefix_len(&self, prefix_len: u8) -> Result<Inet, Error> {
+        Inet::new(self.addr, prefix_len)
+    }
+
+    /// Returns the network part of this address, i.e. this address with all
+    /// bits to the right of the netmask cleared.
+    pub fn network(&self) -> Inet {
+        let bits = self.bits() & prefix_mask(self.prefix_len);
+        Inet {
+            addr: self.addr_from_bits(bits),
+            prefix_len: self.prefix_len,
+        }
+    }
+
+    /// Reports whether this address has no bits set to the right of its
+    /// netmask, i.e. whether it is a valid `cidr` value.
+    pub fn is_network(&self) -> bool {
+        self.bits() & !prefix_mask(self.prefix_len) == 0
+    }
+
+    /// Reports whether `self` is contained within or equal to `other`, i.e.
+    /// the `<<=` operator.
+    pub fn contained_by_or_equals(&self, other: &Inet) -> bool {
+        self.family() == other.family()
+            && self.prefix_len >= other.prefix_len
+            && self.bits_eq(other, other.prefix_len)
+    }
+
+    /// Reports whether `self` is strictly contained within `other`, i.e. the
+    /// `<<` operator.
+    pub fn contained_by(&self, other: &Inet) -> bool {
+        self.family() == other.family()
+            && self.prefix_len > other.prefix_len
+            && self.bits_eq(other, other.prefix_len)
+    }
+
+    /// Reports whether either of `self` or `other` contains or equals the
+    /// other, i.e. the `&&` operator.
+    pub fn overlaps(&self, other: &Inet) -> bool {
+        self.family() == other.family()
+            && self.bits_eq(other, std::cmp::min(self.prefix_len, other.prefix_len))
+    }
+
+    /// Formats the address in the output format of the `cidr` type, which,
+    /// unlike that of `inet`, always includes the netmask length.
+    pub fn to_cidr_string(&self) -> String {
+        format!("{}/{}", self.addr, self.prefix_len)
+    }
+
+    /// Parses an address in the format accepted by the `cidr` type.
+    ///
+    /// Unlike `inet`, IPv4 networks may be abbreviated by omitting trailing
+    /// zero octets (e.g. `10/8` or `192.168`). If no netmask length is
+    /// specified for an abbreviated network, it is derived from the number of
+    /// octets present and the address's classful network size.
+    pub fn parse_cidr(s: &str) -> Result<Inet, Error> {
+        let (addr, prefix_len) = split_prefix_len(s)?;
+        let inet = match addr.parse::<IpAddr>() {
+            Ok(addr) => Inet::new(addr, prefix_len.unwrap_or_else(|| max_prefix_len(&addr)))?,
+            Err(_) => {
+                let (octets, len) = parse_abbreviated_ipv4(addr)?;
+                let prefix_len = match prefix_len {
+                    Some(prefix_len) => prefix_len,
+                    None => {
+                        let classful = match octets[0] {
+                            0..=127 => 8,
+                            128..=191 => 16,
+                            192..=223 => 24,
+                            _ => 8,
+                        };
+                        std::cmp::max(classful, len * 8)
+                    }
+                };
+                Inet::new(IpAddr::V4(Ipv4Addr::from(octets)), prefix_len)?
+            }
+        };
+        if !inet.is_network() {
+            bail!("value has bits set to right of mask");
+        }
+        Ok(inet)
+    }
+
+    /// Returns the number of bytes [`Inet::encode_binary`] produces.
+    pub const fn binary_size() -> usize {
+        2 + 16
+    }
+
+    /// Encodes the address as a family byte, a netmask length byte and 16
+    /// address bytes. IPv4 addresses occupy the first four address bytes.
+    pub fn encode_binary(&self) -> Vec<u8> {
+        let mut res = vec![0; Self::binary_size()];
+        res[0] = self.family();
+        res[1] = self.prefix_len;
+        match self.addr {
+            IpAddr::V4(addr) => res[2..6].copy_from_slice(&addr.octets()),
+            IpAddr::V6(addr) => res[2..].copy_from_slice(&addr.octets()),
+        }
+        res
+    }
+
+    /// Decodes an address produced by [`Inet::encode_binary`].
+    pub fn decode_binary(raw: &[u8]) -> Result<Inet, Error> {
+        if raw.len() != Self::binary_size() {
+            bail!(
+                "invalid binary size, expecting {}, found {}",
+                Self::binary_size(),
+                raw.len()
+            );
+        }
+        let addr = match raw[0] {
+            FAMILY_V4 => {
+                let octets: [u8; 4] = raw[2..6].try_into().expect("known size");
+                IpAddr::V4(Ipv4Addr::from(octets))
+            }
+            FAMILY_V6 => {
+                let octets: [u8; 16] = raw[2..].try_into().expect("known size");
+                IpAddr::V6(Ipv6Addr::from(octets))
+            }
+            family => bail!("invalid address family: {family}"),
+        };
+        Inet::new(addr, raw[1])
+    }
+
+    /// Returns the address as a big-endian bit string left-aligned in a
+    /// `u128`, so that IPv4 and IPv6 netmasks can be applied uniformly.
+    fn bits(&self) -> u128 {
+        match self.addr {
+            IpAddr::V4(addr) => u128::from(u32::from(addr)) << 96,
+            IpAddr::V6(addr) => u128::from(addr),
+        }
+    }
+
+    fn addr_from_bits(&self, bits: u128) -> IpAddr {
+        match self.addr {
+            IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::from(u32::try_from(bits >> 96).expect("fits"))),
+            IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::from(bits)),
+        }
+    }
+
+    /// Reports whether the first `n` bits of `self` and `other` are equal.
+    fn bits_eq(&self, other: &Inet, n: u8) -> bool {
+        let mask = prefix_mask(n);
+        self.bits() & mask == other.bits() & mask
+    }
+}
+
+/// Orders addresses the same way as PostgreSQL: first by family, then by the
+/// network part of the address, then by netmask length, and finally by the
+/// full address.
+///
+/// Comparing the network parts of the addresses (rather than the common
+/// prefix PostgreSQL compares) yields the same order, and makes it possible
+/// to order addresses by their binary encoding in persist.
+impl Ord for Inet {
+    fn cmp(&self, other: &Inet) -> Ordering {
+        self.family()
+            .cmp(&other.family())
+            .then_with(|| {
+                let lhs = self.bits() & prefix_mask(self.prefix_len);
+                let rhs = other.bits() & prefix_mask(other.prefix_len);
+                lhs.cmp(&rhs)
+            })
+            .then_with(|| self.prefix_len.cmp(&other.prefix_len))
+            .then_with(|| self.bits().cmp(&other.bits()))
+    }
+}
+
+impl PartialOrd for Inet {
+    fn partial_cmp(&self, other: &Inet) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl FromStr for Inet {
+    type Err = Error;
+
+    /// Parses an address in the format accepted by the `inet` type.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (addr, prefix_len) = split_prefix_len(s)?;
+        match addr.parse::<IpAddr>() {
+            Ok(addr) => match prefix_len {
+                Some(prefix_len) => Inet::new(addr, prefix_len),
+                None => Ok(Inet::from_addr(addr)),
+            },
+            Err(e) => match prefix_len {
+                // Abbreviated IPv4 networks are only allowed with an explicit
+                // netmask, e.g. `10/8`.
+                Some(prefix_len) => {
+                    let (octets, _) = parse_abbreviated_ipv4(addr)?;
+                    Inet::new(IpAddr::V4(Ipv4Addr::from(octets)), prefix_len)
+                }
+                None => Err(e.into()),
+            },
+        }
+    }
+}
+
+/// Formats the address in the format used by the `inet` type, which omits the
+/// netmask length if the netmask covers the entire address.
+impl fmt::Display for Inet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.prefix_len == self.max_prefix_len() {
+            write!(f, "{}", self.addr)
+        } else {
+            write!(f, "{}/{}", self.addr, self.prefix_len)
+        }
+    }
+}
+
+impl Arbitrary for Inet {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Inet>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        let v4 = (any::<[u8; 4]>(), 0..=IPV4_MAX_PREFIX_LEN).prop_map(|(octets, prefix_len)| {
+            Inet::new(IpAddr::V4(Ipv4Addr::from(octets)), prefix_len).expect("valid prefix")
+        });
+        let v6 = (any::<[u8; 16]>(), 0..=IPV6_MAX_PREFIX_LEN).prop_map(|(octets, prefix_len)| {
+            Inet::new(IpAddr::V6(Ipv6Addr::from(octets)), prefix_len).expect("valid prefix")
+        });
+        v4.boxed().prop_union(v6.boxed()).boxed()
+    }
+}
+
+fn max_prefix_len(addr: &IpAddr) -> u8 {
+    match addr {
+        IpAddr::V4(_) => IPV4_MAX_PREFIX_LEN,
+        IpAddr::V6(_) => IPV6_MAX_PREFIX_LEN,
+    }
+}
+
+/// Returns a mask with the leftmost `n` bits of a `u128` set.
+fn prefix_mask(n: u8) -> u128 {
+    match n {
+        0 => 0,
+        n => u128::MAX << (128 - u32::from(n)),
+    }
+}
+
+/// Splits `s` into an address and an optional netmask length.
+fn split_prefix_len(s: &str) -> Result<(&str, Option<u8>), Error> {
+    match s.split_once('/') {
+        None => Ok((s, None)),
+        Some((addr, prefix_len)) => {
+            let prefix_len = prefix_len
+                .parse()
+                .map_err(|_| anyhow!("invalid mask length: {prefix_len}"))?;
+            Ok((addr, Some(prefix_len)))
+        }
+    }
+}
+
+/// Parses an IPv4 address in which trailing zero octets may be omitted,
+/// returning the address and the number of octets present.
+fn parse_abbreviated_ipv4(s: &str) -> Result<([u8; 4], u8), Error> {
+    let mut octets = [0; 4];
+    let mut len = 0;
+    for part in s.split('.') {
+        if len == 4 || part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+            bail!("invalid IP address");
+        }
+        octets[len] = part.parse().map_err(|_| anyhow!("invalid IP address"))?;
+        len += 1;
+    }
+    Ok((octets, u8::try_from(len).expect("at most 4")))
+}
+
+/// A 6 byte MAC address.
+///
+/// Modeled after the `macaddr` type in PostgreSQL.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Default,
+)]
+pub struct MacAddr([u8; 6]);
+
+impl MacAddr {
+    /// Constructs a new `MacAddr` from its bytes.
+    pub fn new(bytes: [u8; 6]) -> MacAddr {
+        MacAddr(bytes)
+    }
+
+    /// Returns the bytes of the address.
+    pub fn bytes(&self) -> [u8; 6] {
+        self.0
+    }
+
+    pub const fn binary_size() -> usize {
+        6
+    }
+
+    pub fn decode_binary(raw: &[u8]) -> Result<MacAddr, Error> {
+        let bytes = raw.try_into().map_err(|_| {
+            anyhow!(
+                "invalid binary size, expecting {}, found {}",
+                Self::binary_size(),
+                raw.len()
+            )
+        })?;
+        Ok(MacAddr(bytes))
+    }
+}
+
+impl FromStr for MacAddr {
+    type Err = Error;
+
+    /// Parses any of the formats accepted by PostgreSQL, e.g.
+    /// `08:00:2b:01:02:03`, `08-00-2b-01-02-03`, `08002b:010203`,
+    /// `08002b-010203`, `0800.2b01.0203`, `0800-2b01-0203` and
+    /// `08002b010203`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let groups: Vec<&str> = s.split(|c| c == ':' || c == '-' || c == '.').collect();
+        // Each format uses a single separator character, and its groups all
+        // have the same number of hex digits.
+        let separators: Vec<char> = s.chars().filter(|c| matches!(c, ':' | '-' | '.')).collect();
+        let group_len = match groups.len() {
+            1 => 12,
+            2 => 6,
+            3 => 4,
+            6 => 2,
+            _ => bail!("invalid MAC address"),
+        };
+        if separators.windows(2).any(|w| w[0] != w[1])
+            || (groups.len() == 3 && separators.first() == Some(&':'))
+            || (groups.len() != 3 && separators.first() == Some(&'.'))
+        {
+            bail!("invalid MAC address");
+        }
+        let mut hex = String::with_capacity(12);
+        for group in groups {
+            if group.len() != group_len || !group.bytes().all(|b| b.is_ascii_hexdigit()) {
+                bail!("invalid MAC address");
+            }
+            hex.push_str(group);
+        }
+        let mut bytes = [0; 6];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).expect("validated hex digits");
+        }
+        Ok(MacAddr(bytes))
+    }
+}
+
+impl fmt::Display for MacAddr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let [a, b, c, d, e, g] = self.0;
+        write!(f, "{a:02x}:{b:02x}:{c:02x}:{d:02x}:{e:02x}:{g:02x}")
+    }
+}
+
+impl Arbitrary for MacAddr {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<MacAddr>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        any::<[u8; 6]>().prop_map(MacAddr).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_inet_parse_and_format() {
+        let cases = [
+            ("192.168.1.5", "192.168.1.5", "192.168.1.5/32"),
+            ("192.168.1.5/24", "192.168.1.5/24", "192.168.1.5/24"),
+            ("10/8", "10.0.0.0/8", "10.0.0.0/8"),
+            ("::1", "::1", "::1/128"),
+            ("2001:db8::/32", "2001:db8::/32", "2001:db8::/32"),
+        ];
+        for (input, inet, cidr) in cases {
+            let parsed: Inet = input.parse().unwrap();
+            assert_eq!(parsed.to_string(), inet);
+            assert_eq!(parsed.to_cidr_string(), cidr);
+        }
+
+        for input in [
+            "",
+            "192.168.1",
+            "192.168.1.5/33",
+            "::1/129",
+            "1.2.3.4.5/8",
+            "x",
+        ] {
+            assert!(input.parse::<Inet>().is_err(), "{input}");
+        }
+    }
+
+    #[mz_ore::test]
+    fn test_cidr_parse() {
+        let cases = [
+            ("192.168.100.128/25", "192.168.100.128/25"),
+            ("192.168/24", "192.168.0.0/24"),
+            ("192.168.1", "192.168.1.0/24"),
+            ("128.1", "128.1.0.0/16"),
+            ("10", "10.0.0.0/8"),
+            ("10.1.2.3", "10.1.2.3/32"),
+            ("2001:4f8:3:ba::/64", "2001:4f8:3:ba::/64"),
+        ];
+        for (input, expected) in cases {
+            let parsed = Inet::parse_cidr(input).unwrap();
+            assert_eq!(parsed.to_cidr_string(), expected, "{input}");
+        }
+
+        assert!(Inet::parse_cidr("192.168.1.2/24").is_err());
+    }
+
+    #[mz_ore::test]
+    fn test_inet_containment() {
+        let inet = |s: &str| s.parse::<Inet>().unwrap();
+        assert!(inet("192.168.1.5").contained_by(&inet("192.168.1/24")));
+        assert!(!inet("192.168.1/24").contained_by(&inet("192.168.1/24")));
+        assert!(inet("192.168.1/24").contained_by_or_equals(&inet("192.168.1/24")));
+        assert!(!inet("192.168.2.5").contained_by(&inet("192.168.1/24")));
+        assert!(inet("192.168.1/24").overlaps(&inet("192.168.1.5")));
+        assert!(!inet("::1").overlaps(&inet("0.0.0.0/0")));
+        assert_eq!(inet("192.168.1.5/24").network(), inet("192.168.1.0/24"));
+    }
+
+    #[mz_ore::test]
+    fn test_inet_ord() {
+        let inet = |s: &str| s.parse::<Inet>().unwrap();
+        let mut addrs = vec![
+            inet("::1"),
+            inet("10.0.0.0/16"),
+            inet("10.1.0.0/8"),
+            inet("10.0.0.1"),
+            inet("10.0.0.0/8"),
+            inet("9.255.255.255"),
+        ];
+        addrs.sort();
+        let addrs: Vec<_> = addrs.iter().map(|a| a.to_string()).collect();
+        assert_eq!(
+            addrs,
+            [
+                "9.255.255.255",
+                "10.0.0.0/8",
+                "10.1.0.0/8",
+                "10.0.0.0/16",
+                "10.0.0.1",
+                "::1"
+            ]
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_macaddr_parse_and_format() {
+        for input in [
+            "08:00:2b:01:02:03",
+            "08-00-2b-01-02-03",
+            "08002b:010203",
+            "08002b-010203",
+            "0800.2b01.0203",
+            "0800-2b01-0203",
+            "08002b010203",
+            "08:00:2B:01:02:03",
+        ] {
+            let parsed: MacAddr = input.parse().unwrap();
+            assert_eq!(parsed.to_string(), "08:00:2b:01:02:03", "{input}");
+        }
+
+        for input in [
+            "",
+            "08:00:2b:01:02",
+            "08:00-2b:01:02:03",
+            "0800:2b01:0203",
+            "zz:00:2b:01:02:03",
+        ] {
+            assert!(input.parse::<MacAddr>().is_err(), "{input}");
+        }
+    }
+
+    proptest! {
+        #[mz_ore::test]
+        #[cfg_attr(miri, ignore)] // slow
+        fn proptest_inet_binary_encoding_roundtrip(inet: Inet) {
+            let encoded = inet.encode_binary();
+            let decoded = Inet::decode_binary(&encoded).unwrap();
+            assert_eq!(inet, decoded);
+        }
+
+        #[mz_ore::test]
+        #[cfg_attr(miri, ignore)] // slow
+        fn proptest_inet_str_roundtrip(inet: Inet) {
+            let decoded: Inet = inet.to_string().parse().unwrap();
+            assert_eq!(inet, decoded);
+        }
+
+        #[mz_ore::test]
+        #[cfg_attr(miri, ignore)] // slow
+        fn proptest_macaddr_str_roundtrip(mac: MacAddr) {
+            let decoded: MacAddr = mac.to_string().parse().unwrap();
+            assert_eq!(mac, decoded);
+        }
+    }
+}
@@ -268,6 +268,28 @@ impl<D> Range<D> {
                 }),
         }
     }
+
+    /// Like [`Range::into_bounds`], but `conv` may fail, in which case its
+    /// first error is returned.
+    pub fn try_into_bounds<F, O, E>(self, conv: F) -> Result<Range<O>, E>
+    where
+        F: Fn(D) -> Result<O, E>,
+    {
+        let inner = match self.inner {
+            None => None,
+            Some(RangeInner { lower, upper }) => Some(RangeInner {
+                lower: RangeLowerBound {
+                    inclusive: lower.inclusive,
+                    bound: lower.bound.map(&conv).transpose()?,
+                },
+                upper: RangeUpperBound {
+                    inclusive: upper.inclusive,
+                    bound: upper.bound.map(&conv).transpose()?,
+                },
+            }),
+        };
+        Ok(Range { inner })
+    }
 }
 
 /// Range implementations meant to work with `Range<Datum>` and `Range<DatumNested>`.
@@ -35,6 +35,7 @@ use crate::adt::array::{
 use crate::adt::date::Date;
 use crate::adt::interval::Interval;
 use crate::adt::mz_acl_item::{AclItem, MzAclItem};
+use crate::adt::network::{Inet, MacAddr};
 use crate::adt::numeric;
 use crate::adt::numeric::Numeric;
 use crate::adt::range::{
@@ -506,6 +507,8 @@ enum Tag {
     UInt64_48,
     UInt64_56,
     UInt64_64,
+    Inet,
+    MacAddr,
 }
 
 impl Tag {
@@ -1019,6 +1022,13 @@ pub unsafe fn read_datum<'a>(data: &'a [u8], offset: &mut usize) -> Datum<'a> {
                 .expect("invalid aclitem");
             Datum::AclItem(acl_item)
         }
+        Tag::Inet => {
+            const N: usize = Inet::binary_size();
+            let inet =
+                Inet::decode_binary(&read_byte_array::<N>(data, offset)).expect("invalid inet");
+            Datum::Inet(inet)
+        }
+        Tag::MacAddr => Datum::MacAddr(MacAddr::new(read_byte_array(data, offset))),
     }
 }
 
@@ -1387,6 +1397,14 @@ where
             data.push(Tag::AclItem.into());
             data.extend_from_slice(&acl_item.encode_binary());
         }
+        Datum::Inet(inet) => {
+            data.push(Tag::Inet.into());
+            data.extend_from_slice(&inet.encode_binary());
+        }
+        Datum::MacAddr(mac) => {
+            data.push(Tag::MacAddr.into());
+            data.extend_from_slice(&mac.bytes());
+        }
     }
 }
 
@@ -1499,6 +1517,8 @@ pub fn datum_size(datum: &Datum) -> usize {
         }
         Datum::MzAclItem(_) => 1 + MzAclItem::binary_size(),
         Datum::AclItem(_) => 1 + AclItem::binary_size(),
+        Datum::Inet(_) => 1 + Inet::binary_size(),
+        Datum::MacAddr(_) => 1 + MacAddr::binary_size(),
     }
 }
 
@@ -31,6 +31,7 @@ use uuid::Uuid;
 use crate::adt::array::ArrayDimension;
 use crate::adt::date::{Date, ProtoDate};
 use crate::adt::jsonb::Jsonb;
+use crate::adt::network::{Inet, MacAddr};
 use crate::adt::numeric::Numeric;
 use crate::adt::range::{Range, RangeInner, RangeLowerBound, RangeUpperBound};
 use crate::row::proto_datum::DatumType;
@@ -123,8 +124,12 @@ impl ColumnType {
             (true, Int64) => Some(f.call::<Option<i64>>()),
             (false, UInt16) => Some(f.call::<u16>()),
             (true, UInt16) => Some(f.call::<Option<u16>>()),
-            (false, UInt32 | Oid | RegClass | RegProc | RegType) => Some(f.call::<u32>()),
-            (true, UInt32 | Oid | RegClass | RegProc | RegType) => Some(f.call::<Option<u32>>()),
+            (false, UInt32 | Oid | RegClass | RegProc | RegType | Enum { .. }) => {
+                Some(f.call::<u32>())
+            }
+            (true, UInt32 | Oid | RegClass | RegProc | RegType | Enum { .. }) => {
+                Some(f.call::<Option<u32>>())
+            }
             (false, UInt64) => Some(f.call::<u64>()),
             (true, UInt64) => Some(f.call::<Option<u64>>()),
             (false, Float32) => Some(f.call::<f32>()),
@@ -149,7 +154,15 @@ impl ColumnType {
             (true, MzTimestamp) => Some(f.call::<Option<crate::Timestamp>>()),
             (
                 _,
-                Numeric { .. } | Time | Timestamp { .. } | TimestampTz { .. } | Interval | Uuid,
+                Numeric { .. }
+                | Time
+                | Timestamp { .. }
+                | TimestampTz { .. }
+                | Interval
+                | Uuid
+                | Inet
+                | Cidr
+                | MacAddr,
             ) => {
                 if *nullable {
                     Some(f.call::<NullableProtoDatumToPersist>())
@@ -868,6 +881,8 @@ impl<'a> From<Datum<'a>> for ProtoDatum {
             })),
             Datum::MzAclItem(x) => DatumType::MzAclItem(x.into_proto()),
             Datum::AclItem(x) => DatumType::AclItem(x.into_proto()),
+            Datum::Inet(x) => DatumType::Inet(x.encode_binary()),
+            Datum::MacAddr(x) => DatumType::MacAddr(x.bytes().to_vec()),
         };
         ProtoDatum {
             datum_type: Some(datum_type),
@@ -1017,6 +1032,14 @@ impl RowPacker<'_> {
             }
             Some(DatumType::MzAclItem(x)) => self.push(Datum::MzAclItem(x.clone().into_rust()?)),
             Some(DatumType::AclItem(x)) => self.push(Datum::AclItem(x.clone().into_rust()?)),
+            Some(DatumType::Inet(x)) => {
+                let inet = Inet::decode_binary(x).map_err(|err| err.to_string())?;
+                self.push(Datum::Inet(inet));
+            }
+            Some(DatumType::MacAddr(x)) => {
+                let mac = MacAddr::decode_binary(x).map_err(|err| err.to_string())?;
+                self.push(Datum::MacAddr(mac));
+            }
             None => return Err("unknown datum type".into()),
         };
         Ok(())
@@ -1124,6 +1147,8 @@ mod tests {
             Datum::from(Numeric::nan()),
             Datum::JsonNull,
             Datum::Uuid(Uuid::from_u128(30)),
+            Datum::Inet("192.168.1.5/24".parse().unwrap()),
+            Datum::MacAddr("08:00:2b:01:02:03".parse().unwrap()),
             Datum::Dummy,
             Datum::Null,
         ]);
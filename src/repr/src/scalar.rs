@@ -10,6 +10,7 @@
 use std::fmt::{self, Debug, Write};
 use std::hash::Hash;
 use std::iter;
+use std::net::{Ipv4Addr, Ipv6Addr};
 use std::ops::Add;
 
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
@@ -17,6 +18,7 @@ use dec::OrderedDecimal;
 use enum_kinds::EnumKind;
 use itertools::Itertools;
 use mz_lowertest::MzReflect;
+use mz_ore::cast::CastFrom;
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use once_cell::sync::Lazy;
 use ordered_float::OrderedFloat;
@@ -31,6 +33,7 @@ use crate::adt::date::Date;
 use crate::adt::interval::Interval;
 use crate::adt::jsonb::{Jsonb, JsonbRef};
 use crate::adt::mz_acl_item::{AclItem, AclMode, MzAclItem};
+use crate::adt::network::{Inet, MacAddr};
 use crate::adt::numeric::{Numeric, NumericMaxScale};
 use crate::adt::pg_legacy_name::PgLegacyName;
 use crate::adt::range::{Range, RangeLowerBound, RangeUpperBound};
@@ -130,6 +133,11 @@ pub enum Datum<'a> {
     /// A list of privileges granted to a user that uses [`Oid`]s for role references.
     /// This type is used primarily for compatibility with PostgreSQL.
     AclItem(AclItem),
+    /// An IPv4 or IPv6 host address with a netmask. Used by both the `inet`
+    /// and the `cidr` types.
+    Inet(Inet),
+    /// A MAC address.
+    MacAddr(MacAddr),
     /// A placeholder value.
     ///
     /// Dummy values are never meant to be observed. Many operations on `Datum`
@@ -807,6 +815,32 @@ impl<'a> Datum<'a> {
         }
     }
 
+    /// Unwraps the inet value within this datum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the datum is not [`Datum::Inet`].
+    #[track_caller]
+    pub fn unwrap_inet(&self) -> Inet {
+        match self {
+            Datum::Inet(inet) => *inet,
+            _ => panic!("Datum::unwrap_inet called on {:?}", self),
+        }
+    }
+
+    /// Unwraps the macaddr value within this datum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the datum is not [`Datum::MacAddr`].
+    #[track_caller]
+    pub fn unwrap_macaddr(&self) -> MacAddr {
+        match self {
+            Datum::MacAddr(mac) => *mac,
+            _ => panic!("Datum::unwrap_macaddr called on {:?}", self),
+        }
+    }
+
     /// Unwraps the array value within this datum.
     ///
     /// # Panics
@@ -959,6 +993,9 @@ impl<'a> Datum<'a> {
                     (Datum::UInt32(_), ScalarType::RegProc) => true,
                     (Datum::UInt32(_), ScalarType::RegType) => true,
                     (Datum::UInt32(_), ScalarType::UInt32) => true,
+                    (Datum::UInt32(i), ScalarType::Enum { labels, .. }) => {
+                        usize::cast_from(*i) < labels.len()
+                    }
                     (Datum::UInt32(_), _) => false,
                     (Datum::UInt64(_), ScalarType::UInt64) => true,
                     (Datum::UInt64(_), _) => false,
@@ -1036,6 +1073,11 @@ impl<'a> Datum<'a> {
                     (Datum::MzAclItem(_), _) => false,
                     (Datum::AclItem(_), ScalarType::AclItem) => true,
                     (Datum::AclItem(_), _) => false,
+                    (Datum::Inet(_), ScalarType::Inet) => true,
+                    (Datum::Inet(inet), ScalarType::Cidr) => inet.is_network(),
+                    (Datum::Inet(_), _) => false,
+                    (Datum::MacAddr(_), ScalarType::MacAddr) => true,
+                    (Datum::MacAddr(_), _) => false,
                 }
             }
         }
@@ -1261,6 +1303,20 @@ impl<'a> From<MzAclItem> for Datum<'a> {
     }
 }
 
+impl<'a> From<Inet> for Datum<'a> {
+    #[inline]
+    fn from(inet: Inet) -> Self {
+        Datum::Inet(inet)
+    }
+}
+
+impl<'a> From<MacAddr> for Datum<'a> {
+    #[inline]
+    fn from(mac: MacAddr) -> Self {
+        Datum::MacAddr(mac)
+    }
+}
+
 impl<'a, T> From<Option<T>> for Datum<'a>
 where
     Datum<'a>: From<T>,
@@ -1361,6 +1417,8 @@ impl fmt::Display for Datum<'_> {
             Datum::Range(i) => write!(f, "{}", i),
             Datum::MzAclItem(mz_acl_item) => write!(f, "{mz_acl_item}"),
             Datum::AclItem(acl_item) => write!(f, "{acl_item}"),
+            Datum::Inet(inet) => write!(f, "{inet}"),
+            Datum::MacAddr(mac) => write!(f, "{mac}"),
         }
     }
 }
@@ -1510,6 +1568,25 @@ pub enum ScalarType {
     MzAclItem,
     /// The type of [`Datum::AclItem`]
     AclItem,
+    /// An IPv4 or IPv6 host address with a netmask. The type of
+    /// [`Datum::Inet`].
+    Inet,
+    /// An IPv4 or IPv6 network. Also represented as [`Datum::Inet`], but
+    /// with no bits set to the right of the netmask.
+    Cidr,
+    /// The type of [`Datum::MacAddr`].
+    MacAddr,
+    /// A user-defined enumerated type.
+    ///
+    /// Values are stored as [`Datum::UInt32`] holding the index of the value's
+    /// label in `labels`, so that values sort in declaration order.
+    Enum {
+        labels: Vec<String>,
+        custom_id: Option<GlobalId>,
+        /// The OID of the type's catalog entry, which clients see as the
+        /// type of its values.
+        custom_oid: Option<u32>,
+    },
 }
 
 impl RustType<ProtoRecordField> for (ColumnName, ColumnType) {
@@ -1602,6 +1679,18 @@ impl RustType<ProtoScalarType> for ScalarType {
                 })),
                 ScalarType::MzAclItem => MzAclItem(()),
                 ScalarType::AclItem => AclItem(()),
+                ScalarType::Inet => Inet(()),
+                ScalarType::Cidr => Cidr(()),
+                ScalarType::MacAddr => MacAddr(()),
+                ScalarType::Enum {
+                    labels,
+                    custom_id,
+                    custom_oid,
+                } => Enum(ProtoEnum {
+                    labels: labels.clone(),
+                    custom_id: custom_id.map(|id| id.into_proto()),
+                    custom_oid: *custom_oid,
+                }),
             }),
         }
     }
@@ -1665,6 +1754,7 @@ impl RustType<ProtoScalarType> for ScalarType {
                         .into_rust_if_some("ProtoList::element_type")?,
                 ),
                 custom_id: x.custom_id.map(|id| id.into_rust().unwrap()),
+                custom_oid: x.custom_oid,
             }),
             Record(x) => Ok(ScalarType::Record {
                 custom_id: x.custom_id.map(|id| id.into_rust().unwrap()),
@@ -1688,6 +1778,13 @@ impl RustType<ProtoScalarType> for ScalarType {
             }),
             MzAclItem(()) => Ok(ScalarType::MzAclItem),
             AclItem(()) => Ok(ScalarType::AclItem),
+            Inet(()) => Ok(ScalarType::Inet),
+            Cidr(()) => Ok(ScalarType::Cidr),
+            MacAddr(()) => Ok(ScalarType::MacAddr),
+            Enum(x) => Ok(ScalarType::Enum {
+                labels: x.labels,
+                custom_id: x.custom_id.map(|id| id.into_rust().unwrap()),
+            }),
         }
     }
 }
@@ -2482,6 +2579,60 @@ impl<'a, E> DatumType<'a, E> for AclItem {
     }
 }
 
+impl AsColumnType for Inet {
+    fn as_column_type() -> ColumnType {
+        ScalarType::Inet.nullable(false)
+    }
+}
+
+impl<'a, E> DatumType<'a, E> for Inet {
+    fn nullable() -> bool {
+        false
+    }
+
+    fn fallible() -> bool {
+        false
+    }
+
+    fn try_from_result(res: Result<Datum<'a>, E>) -> Result<Self, Result<Datum<'a>, E>> {
+        match res {
+            Ok(Datum::Inet(inet)) => Ok(inet),
+            _ => Err(res),
+        }
+    }
+
+    fn into_result(self, _temp_storage: &'a RowArena) -> Result<Datum<'a>, E> {
+        Ok(Datum::Inet(self))
+    }
+}
+
+impl AsColumnType for MacAddr {
+    fn as_column_type() -> ColumnType {
+        ScalarType::MacAddr.nullable(false)
+    }
+}
+
+impl<'a, E> DatumType<'a, E> for MacAddr {
+    fn nullable() -> bool {
+        false
+    }
+
+    fn fallible() -> bool {
+        false
+    }
+
+    fn try_from_result(res: Result<Datum<'a>, E>) -> Result<Self, Result<Datum<'a>, E>> {
+        match res {
+            Ok(Datum::MacAddr(mac)) => Ok(mac),
+            _ => Err(res),
+        }
+    }
+
+    fn into_result(self, _temp_storage: &'a RowArena) -> Result<Datum<'a>, E> {
+        Ok(Datum::MacAddr(self))
+    }
+}
+
 impl AsColumnType for CheckedTimestamp<NaiveDateTime> {
     fn as_column_type() -> ColumnType {
         ScalarType::Timestamp { precision: None }.nullable(false)
@@ -2778,6 +2929,18 @@ impl ScalarType {
         }
     }
 
+    /// Returns the labels of an enum type, in declaration order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called on anything other than a [`ScalarType::Enum`].
+    pub fn unwrap_enum_labels(&self) -> &[String] {
+        match self {
+            ScalarType::Enum { labels, .. } => labels,
+            _ => panic!("ScalarType::unwrap_enum_labels called on {:?}", self),
+        }
+    }
+
     /// Returns a "near match" of `self`, which are types that are implicitly
     /// castable from `self` and offer a means to leverage Materialize's type
     /// system to achieve more reasonable approaches to unifying types.
@@ -2849,6 +3012,7 @@ impl ScalarType {
                         .map(|(_, t)| t)
                         .any(|t| t.scalar_type.is_custom_type())
             }
+            Enum { custom_id, .. } => custom_id.is_some(),
             _ => false,
         }
     }
@@ -2922,6 +3086,18 @@ impl ScalarType {
                                 && a.1.scalar_type.eq_inner(&b.1.scalar_type, structure_only)
                         })
             }
+            (
+                Enum {
+                    labels: labels_a,
+                    custom_id: id_a,
+                    ..
+                },
+                Enum {
+                    labels: labels_b,
+                    custom_id: id_b,
+                    ..
+                },
+            ) => (id_a == id_b || structure_only) && labels_a == labels_b,
             (s, o) => ScalarBaseType::from(s) == ScalarBaseType::from(o),
         }
     }
@@ -3280,6 +3456,32 @@ impl ScalarType {
         });
         // aclitem has no binary encoding so we can't test it here.
         static ACLITEM: Lazy<Row> = Lazy::new(|| Row::pack_slice(&[]));
+        // Which ordinals are valid depends on the enum's labels.
+        static ENUM: Lazy<Row> = Lazy::new(|| Row::pack_slice(&[]));
+        static INET: Lazy<Row> = Lazy::new(|| {
+            Row::pack_slice(&[
+                Datum::Inet(Inet::from_addr(Ipv4Addr::UNSPECIFIED.into())),
+                Datum::Inet(Inet::new(Ipv4Addr::BROADCAST.into(), 0).unwrap()),
+                Datum::Inet(Inet::new(Ipv4Addr::new(192, 168, 1, 5).into(), 24).unwrap()),
+                Datum::Inet(Inet::from_addr(Ipv6Addr::UNSPECIFIED.into())),
+                Datum::Inet(Inet::new(Ipv6Addr::LOCALHOST.into(), 64).unwrap()),
+            ])
+        });
+        static CIDR: Lazy<Row> = Lazy::new(|| {
+            Row::pack_slice(&[
+                Datum::Inet(Inet::new(Ipv4Addr::UNSPECIFIED.into(), 0).unwrap()),
+                Datum::Inet(Inet::from_addr(Ipv4Addr::BROADCAST.into())),
+                Datum::Inet(Inet::new(Ipv4Addr::new(192, 168, 1, 0).into(), 24).unwrap()),
+                Datum::Inet(Inet::new(Ipv6Addr::UNSPECIFIED.into(), 0).unwrap()),
+                Datum::Inet(Inet::from_addr(Ipv6Addr::LOCALHOST.into())),
+            ])
+        });
+        static MACADDR: Lazy<Row> = Lazy::new(|| {
+            Row::pack_slice(&[
+                Datum::MacAddr(MacAddr::new([0; 6])),
+                Datum::MacAddr(MacAddr::new([255; 6])),
+            ])
+        });
 
         match self {
             ScalarType::Bool => (*BOOL).iter(),
@@ -3318,6 +3520,10 @@ impl ScalarType {
             ScalarType::Range { .. } => (*RANGE).iter(),
             ScalarType::MzAclItem { .. } => (*MZACLITEM).iter(),
             ScalarType::AclItem { .. } => (*ACLITEM).iter(),
+            ScalarType::Enum { .. } => (*ENUM).iter(),
+            ScalarType::Inet => (*INET).iter(),
+            ScalarType::Cidr => (*CIDR).iter(),
+            ScalarType::MacAddr => (*MACADDR).iter(),
         }
     }
 
@@ -3374,6 +3580,9 @@ impl ScalarType {
             ScalarType::Int2Vector,
             ScalarType::MzTimestamp,
             ScalarType::MzAclItem,
+            ScalarType::Inet,
+            ScalarType::Cidr,
+            ScalarType::MacAddr,
             // TODO: Fill in some variants of these.
             /*
             ScalarType::AclItem,
@@ -3434,7 +3643,10 @@ impl ScalarType {
             | ScalarType::Int2Vector
             | ScalarType::MzTimestamp
             | ScalarType::Range { .. }
-            | ScalarType::MzAclItem { .. }) => Ok(t),
+            | ScalarType::MzAclItem { .. }
+            | ScalarType::Inet
+            | ScalarType::Cidr
+            | ScalarType::MacAddr) => Ok(t),
 
             ScalarType::Array(elem) => Ok(elem.array_of_self_elem_type()?),
 
@@ -3442,7 +3654,9 @@ impl ScalarType {
             t @ (ScalarType::Char { .. }
             // not sensible to put in arrays
             | ScalarType::Map { .. }
-            | ScalarType::List { .. }) => Err(t),
+            | ScalarType::List { .. }
+            // enum array types are not yet supported
+            | ScalarType::Enum { .. }) => Err(t),
         }
     }
 }
@@ -3490,6 +3704,20 @@ impl Arbitrary for ScalarType {
             Just(ScalarType::RegType).boxed(),
             Just(ScalarType::RegClass).boxed(),
             Just(ScalarType::Int2Vector).boxed(),
+            Just(ScalarType::Inet).boxed(),
+            Just(ScalarType::Cidr).boxed(),
+            Just(ScalarType::MacAddr).boxed(),
+            (
+                prop::collection::vec(any::<String>(), 0..5),
+                any::<Option<GlobalId>>(),
+                any::<Option<u32>>(),
+            )
+                .prop_map(|(labels, custom_id, custom_oid)| ScalarType::Enum {
+                    labels,
+                    custom_id,
+                    custom_oid,
+                })
+                .boxed(),
         ]);
 
         leaf.prop_recursive(
@@ -3606,6 +3834,8 @@ pub enum PropDatum {
 
     JsonNull,
     Uuid(Uuid),
+    Inet(Inet),
+    MacAddr(MacAddr),
     Dummy,
 }
 
@@ -3640,6 +3870,8 @@ pub fn arb_datum() -> BoxedStrategy<PropDatum> {
         ".*".prop_map(PropDatum::String).boxed(),
         Just(PropDatum::JsonNull).boxed(),
         Just(PropDatum::Uuid(Uuid::nil())).boxed(),
+        any::<Inet>().prop_map(PropDatum::Inet).boxed(),
+        any::<MacAddr>().prop_map(PropDatum::MacAddr).boxed(),
         arb_range().prop_map(PropDatum::Range).boxed(),
         Just(PropDatum::Dummy).boxed(),
     ]);
@@ -3938,6 +4170,8 @@ impl<'a> From<&'a PropDatum> for Datum<'a> {
             }
             JsonNull => Datum::JsonNull,
             Uuid(u) => Datum::from(*u),
+            Inet(i) => Datum::from(*i),
+            MacAddr(m) => Datum::from(*m),
             Dummy => Datum::Dummy,
         }
     }
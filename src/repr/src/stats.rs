@@ -9,6 +9,7 @@
 
 use std::collections::BTreeMap;
 
+use mz_ore::cast::CastLossy;
 use mz_persist_types::columnar::{ColumnGet, Data};
 use mz_persist_types::dyn_struct::ValidityRef;
 use mz_persist_types::stats::{JsonMapElementStats, JsonStats, PrimitiveStats};
@@ -166,6 +167,117 @@ impl<'a> JsonDatums<'a> {
     }
 }
 
+/// Statistics about the contents of a collection that are available to the
+/// optimizer, e.g., derived from the statistics that persist keeps about the
+/// parts of a shard.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CollectionStatistics {
+    /// An estimate of the number of rows in the collection.
+    pub cardinality: usize,
+    /// Statistics about the values of individual columns, keyed by column
+    /// index. Columns without statistics are absent.
+    pub columns: BTreeMap<usize, ColumnStatistics>,
+}
+
+/// Statistics about the values of a single column of a collection.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ColumnStatistics {
+    /// The number of null values in the column.
+    pub null_count: usize,
+    /// An estimate of the number of distinct non-null values in the column.
+    pub distinct_count: usize,
+    /// The smallest non-null value in the column, if any, packed as a
+    /// single-datum row.
+    min: Option<Row>,
+    /// The largest non-null value in the column, if any, packed as a
+    /// single-datum row.
+    max: Option<Row>,
+}
+
+impl ColumnStatistics {
+    /// Creates statistics for a column with `null_count` null values and
+    /// `distinct_count` distinct non-null values between `min` and `max`.
+    ///
+    /// For integer columns, `distinct_count` is capped at the number of values
+    /// between `min` and `max`.
+    pub fn new(
+        null_count: usize,
+        distinct_count: usize,
+        min: Option<Datum>,
+        max: Option<Datum>,
+    ) -> Self {
+        let distinct_count = match (min.and_then(as_integer), max.and_then(as_integer)) {
+            (Some(min), Some(max)) => {
+                let width = usize::try_from(max - min + 1).unwrap_or(usize::MAX);
+                distinct_count.min(width)
+            }
+            _ => distinct_count,
+        };
+        ColumnStatistics {
+            null_count,
+            distinct_count,
+            min: min.map(|min| Row::pack_slice(&[min])),
+            max: max.map(|max| Row::pack_slice(&[max])),
+        }
+    }
+
+    /// The smallest non-null value in the column, if known.
+    pub fn min(&self) -> Option<Datum> {
+        self.min.as_ref().map(|min| min.unpack_first())
+    }
+
+    /// The largest non-null value in the column, if known.
+    pub fn max(&self) -> Option<Datum> {
+        self.max.as_ref().map(|max| max.unpack_first())
+    }
+
+    /// Estimates the fraction of non-null values in the column that are
+    /// smaller than `datum`, assuming that the values are spread uniformly
+    /// between the minimum and the maximum.
+    ///
+    /// Returns `None` for non-numeric columns and if the bounds are unknown.
+    pub fn fraction_below(&self, datum: Datum) -> Option<f64> {
+        let min = as_f64(self.min()?)?;
+        let max = as_f64(self.max()?)?;
+        let value = as_f64(datum)?;
+        if value <= min {
+            Some(0.0)
+        } else if value > max {
+            Some(1.0)
+        } else {
+            Some((value - min) / (max - min))
+        }
+    }
+}
+
+fn as_integer(datum: Datum) -> Option<i128> {
+    match datum {
+        Datum::Int16(n) => Some(n.into()),
+        Datum::Int32(n) => Some(n.into()),
+        Datum::Int64(n) => Some(n.into()),
+        Datum::UInt8(n) => Some(n.into()),
+        Datum::UInt16(n) => Some(n.into()),
+        Datum::UInt32(n) => Some(n.into()),
+        Datum::UInt64(n) => Some(n.into()),
+        _ => None,
+    }
+}
+
+fn as_f64(datum: Datum) -> Option<f64> {
+    match datum {
+        Datum::Float32(n) => Some(n.into_inner().into()),
+        Datum::Float64(n) => Some(n.into_inner()),
+        Datum::Int16(n) => Some(n.into()),
+        Datum::Int32(n) => Some(n.into()),
+        Datum::Int64(n) => Some(f64::cast_lossy(n)),
+        Datum::UInt8(n) => Some(n.into()),
+        Datum::UInt16(n) => Some(n.into()),
+        Datum::UInt32(n) => Some(n.into()),
+        Datum::UInt64(n) => Some(f64::cast_lossy(n)),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use mz_persist_types::codec_impls::UnitSchema;
@@ -178,6 +290,7 @@ mod tests {
     use proptest::prelude::*;
 
     use crate::row::encoding::is_no_stats_type;
+    use crate::stats::ColumnStatistics;
     use crate::{Datum, DatumToPersist, DatumToPersistFn, RelationDesc, Row, RowArena, ScalarType};
 
     fn datum_stats_roundtrip_trim<'a>(
@@ -265,4 +378,31 @@ mod tests {
             scalar_type_stats_roundtrip_trim(scalar_type)
         });
     }
+
+    #[mz_ore::test]
+    fn column_statistics() {
+        // The distinct count of integer columns is capped at the width of their range.
+        let stats =
+            ColumnStatistics::new(2, 10_000, Some(Datum::Int32(1)), Some(Datum::Int32(200)));
+        assert_eq!(stats.distinct_count, 200);
+        assert_eq!(stats.min(), Some(Datum::Int32(1)));
+        assert_eq!(stats.max(), Some(Datum::Int32(200)));
+
+        assert_eq!(stats.fraction_below(Datum::Int32(-5)), Some(0.0));
+        assert_eq!(stats.fraction_below(Datum::Int32(1)), Some(0.0));
+        assert_eq!(stats.fraction_below(Datum::Int32(200)), Some(1.0));
+        assert_eq!(stats.fraction_below(Datum::Int32(500)), Some(1.0));
+        let half = stats.fraction_below(Datum::Int32(100)).unwrap();
+        assert!(0.49 < half && half < 0.51, "unexpected fraction {half}");
+
+        // Other columns keep their distinct count, and have no fractions.
+        let stats = ColumnStatistics::new(0, 7, Some(Datum::String("a")), Some(Datum::String("b")));
+        assert_eq!(stats.distinct_count, 7);
+        assert_eq!(stats.fraction_below(Datum::String("aa")), None);
+
+        // Columns with only nulls have no bounds.
+        let stats = ColumnStatistics::new(3, 0, None, None);
+        assert_eq!(stats.min(), None);
+        assert_eq!(stats.fraction_below(Datum::Int32(1)), None);
+    }
 }
@@ -55,6 +55,7 @@ use crate::adt::datetime::{self, DateTimeField, ParsedDateTime};
 use crate::adt::interval::Interval;
 use crate::adt::jsonb::{Jsonb, JsonbRef};
 use crate::adt::mz_acl_item::{AclItem, MzAclItem};
+use crate::adt::network::{Inet, MacAddr};
 use crate::adt::numeric::{self, Numeric, NUMERIC_DATUM_MAX_PRECISION};
 use crate::adt::pg_legacy_name::NAME_MAX_BYTES;
 use crate::adt::range::{Range, RangeBound, RangeInner};
@@ -740,6 +741,51 @@ where
     Nestable::Yes
 }
 
+/// Parses an [`Inet`] from `s` using the input format of the `inet` type.
+pub fn parse_inet(s: &str) -> Result<Inet, ParseError> {
+    s.trim()
+        .parse()
+        .map_err(|e| ParseError::invalid_input_syntax("inet", s).with_details(e))
+}
+
+/// Writes an [`Inet`] to `buf` using the output format of the `inet` type.
+pub fn format_inet<F>(buf: &mut F, inet: Inet) -> Nestable
+where
+    F: FormatBuffer,
+{
+    write!(buf, "{}", inet);
+    Nestable::Yes
+}
+
+/// Parses an [`Inet`] from `s` using the input format of the `cidr` type.
+pub fn parse_cidr(s: &str) -> Result<Inet, ParseError> {
+    Inet::parse_cidr(s.trim())
+        .map_err(|e| ParseError::invalid_input_syntax("cidr", s).with_details(e))
+}
+
+/// Writes an [`Inet`] to `buf` using the output format of the `cidr` type.
+pub fn format_cidr<F>(buf: &mut F, inet: Inet) -> Nestable
+where
+    F: FormatBuffer,
+{
+    buf.write_str(&inet.to_cidr_string());
+    Nestable::Yes
+}
+
+pub fn parse_macaddr(s: &str) -> Result<MacAddr, ParseError> {
+    s.trim()
+        .parse()
+        .map_err(|e| ParseError::invalid_input_syntax("macaddr", s).with_details(e))
+}
+
+pub fn format_macaddr<F>(buf: &mut F, mac: MacAddr) -> Nestable
+where
+    F: FormatBuffer,
+{
+    write!(buf, "{}", mac);
+    Nestable::Yes
+}
+
 fn format_nanos_to_micros<F>(buf: &mut F, nanos: u32)
 where
     F: FormatBuffer,
@@ -317,6 +317,24 @@ impl<T: AstInfo> AstDisplay for CsrConnectionProtobuf<T> {
 }
 impl_display_t!(CsrConnectionProtobuf);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CsrConnectionJson<T: AstInfo> {
+    pub connection: CsrConnection<T>,
+    pub seed: Option<CsrSeedJson>,
+}
+
+impl<T: AstInfo> AstDisplay for CsrConnectionJson<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("USING CONFLUENT SCHEMA REGISTRY ");
+        f.write_node(&self.connection);
+        if let Some(seed) = &self.seed {
+            f.write_str(" ");
+            f.write_node(seed);
+        }
+    }
+}
+impl_display_t!(CsrConnectionJson);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CsrSeedAvro {
     pub key_schema: Option<String>,
@@ -338,6 +356,27 @@ impl AstDisplay for CsrSeedAvro {
 }
 impl_display!(CsrSeedAvro);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CsrSeedJson {
+    pub key_schema: Option<String>,
+    pub value_schema: String,
+}
+
+impl AstDisplay for CsrSeedJson {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SEED");
+        if let Some(key_schema) = &self.key_schema {
+            f.write_str(" KEY SCHEMA '");
+            f.write_node(&display::escape_single_quote_string(key_schema));
+            f.write_str("'");
+        }
+        f.write_str(" VALUE SCHEMA '");
+        f.write_node(&display::escape_single_quote_string(&self.value_schema));
+        f.write_str("'");
+    }
+}
+impl_display!(CsrSeedJson);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CsrSeedProtobuf {
     pub key: Option<CsrSeedProtobufSchema>,
@@ -404,6 +443,11 @@ impl_display_t!(CreateSourceFormat);
 pub enum Format<T: AstInfo> {
     Bytes,
     Avro(AvroSchema<T>),
+    /// `AVRO OCF USING SCHEMA '...'`: every message is an Avro object
+    /// container file whose records are resolved against `schema`.
+    AvroOcf {
+        schema: Schema,
+    },
     Protobuf(ProtobufSchema<T>),
     Regex(String),
     Csv {
@@ -413,7 +457,13 @@ pub enum Format<T: AstInfo> {
     Json {
         array: bool,
     },
+    /// `JSON USING CONFLUENT SCHEMA REGISTRY ...`: JSON documents whose
+    /// shape is described by a JSON Schema in the schema registry.
+    JsonCsr {
+        csr_connection: CsrConnectionJson<T>,
+    },
     Text,
+    Parquet,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -616,6 +666,10 @@ impl<T: AstInfo> AstDisplay for Format<T> {
                 f.write_str("AVRO ");
                 f.write_node(inner);
             }
+            Self::AvroOcf { schema } => {
+                f.write_str("AVRO OCF USING ");
+                schema.fmt(f);
+            }
             Self::Protobuf(inner) => {
                 f.write_str("PROTOBUF ");
                 f.write_node(inner);
@@ -641,7 +695,12 @@ impl<T: AstInfo> AstDisplay for Format<T> {
                     f.write_str(" ARRAY");
                 }
             }
+            Self::JsonCsr { csr_connection } => {
+                f.write_str("JSON ");
+                f.write_node(csr_connection);
+            }
             Self::Text => f.write_str("TEXT"),
+            Self::Parquet => f.write_str("PARQUET"),
         }
     }
 }
@@ -661,6 +720,7 @@ pub enum ConnectionOptionName {
     Brokers,
     Database,
     Endpoint,
+    HmacSecret,
     Host,
     Password,
     Port,
@@ -692,6 +752,7 @@ impl AstDisplay for ConnectionOptionName {
             ConnectionOptionName::Brokers => "BROKERS",
             ConnectionOptionName::Database => "DATABASE",
             ConnectionOptionName::Endpoint => "ENDPOINT",
+            ConnectionOptionName::HmacSecret => "HMAC SECRET",
             ConnectionOptionName::Host => "HOST",
             ConnectionOptionName::Password => "PASSWORD",
             ConnectionOptionName::Port => "PORT",
@@ -745,6 +806,9 @@ pub enum CreateConnectionType {
     Postgres,
     Ssh,
     MySql,
+    Http,
+    SqlServer,
+    MongoDb,
 }
 
 impl AstDisplay for CreateConnectionType {
@@ -771,6 +835,15 @@ impl AstDisplay for CreateConnectionType {
             Self::MySql => {
                 f.write_str("MYSQL");
             }
+            Self::Http => {
+                f.write_str("HTTP");
+            }
+            Self::SqlServer => {
+                f.write_str("SQL SERVER");
+            }
+            Self::MongoDb => {
+                f.write_str("MONGODB");
+            }
         }
     }
 }
@@ -856,6 +929,7 @@ pub enum KafkaSinkConfigOptionName {
     Topic,
     TransactionalIdPrefix,
     LegacyIds,
+    PartitionBy,
 }
 
 impl AstDisplay for KafkaSinkConfigOptionName {
@@ -866,6 +940,7 @@ impl AstDisplay for KafkaSinkConfigOptionName {
             KafkaSinkConfigOptionName::Topic => "TOPIC",
             KafkaSinkConfigOptionName::TransactionalIdPrefix => "TRANSACTIONAL ID PREFIX",
             KafkaSinkConfigOptionName::LegacyIds => "LEGACY IDS",
+            KafkaSinkConfigOptionName::PartitionBy => "PARTITION BY",
         })
     }
 }
@@ -888,6 +963,41 @@ impl<T: AstInfo> AstDisplay for KafkaSinkConfigOption<T> {
 }
 impl_display_t!(KafkaSinkConfigOption);
 
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PostgresSinkConfigOptionName {
+    ProgressTable,
+    Schema,
+    Table,
+}
+
+impl AstDisplay for PostgresSinkConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            PostgresSinkConfigOptionName::ProgressTable => "PROGRESS TABLE",
+            PostgresSinkConfigOptionName::Schema => "SCHEMA",
+            PostgresSinkConfigOptionName::Table => "TABLE",
+        })
+    }
+}
+impl_display!(PostgresSinkConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PostgresSinkConfigOption<T: AstInfo> {
+    pub name: PostgresSinkConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for PostgresSinkConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(PostgresSinkConfigOption);
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum PgConfigOptionName {
     /// Hex encoded string of binary serialization of
@@ -968,6 +1078,111 @@ impl<T: AstInfo> AstDisplay for MySqlConfigOption<T> {
 }
 impl_display_t!(MySqlConfigOption);
 
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SqlServerConfigOptionName {
+    /// Hex encoded string of binary serialization of
+    /// `mz_storage_types::sources::sql_server::SqlServerSourceDetails`
+    Details,
+}
+
+impl AstDisplay for SqlServerConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            SqlServerConfigOptionName::Details => "DETAILS",
+        })
+    }
+}
+impl_display!(SqlServerConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// An option in a `FROM SQL SERVER CONNECTION ...` statement.
+pub struct SqlServerConfigOption<T: AstInfo> {
+    pub name: SqlServerConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for SqlServerConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(SqlServerConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MongoDbConfigOptionName {
+    /// Hex encoded string of binary serialization of
+    /// `mz_storage_types::sources::mongodb::MongoDbSourceDetails`
+    Details,
+}
+
+impl AstDisplay for MongoDbConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            MongoDbConfigOptionName::Details => "DETAILS",
+        })
+    }
+}
+impl_display!(MongoDbConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// An option in a `FROM MONGODB CONNECTION ...` statement.
+pub struct MongoDbConfigOption<T: AstInfo> {
+    pub name: MongoDbConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for MongoDbConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(MongoDbConfigOption);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum S3ConfigOptionName {
+    /// The `s3://bucket/prefix` URL of the objects to ingest
+    Prefix,
+    /// How often to list the prefix for new objects. Objects are ingested in key order, so new
+    /// objects with keys before those of already ingested objects are not ingested.
+    PollInterval,
+}
+
+impl AstDisplay for S3ConfigOptionName {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str(match self {
+            S3ConfigOptionName::Prefix => "PREFIX",
+            S3ConfigOptionName::PollInterval => "POLL INTERVAL",
+        })
+    }
+}
+impl_display!(S3ConfigOptionName);
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// An option in a `FROM S3 CONNECTION ...` statement.
+pub struct S3ConfigOption<T: AstInfo> {
+    pub name: S3ConfigOptionName,
+    pub value: Option<WithOptionValue<T>>,
+}
+
+impl<T: AstInfo> AstDisplay for S3ConfigOption<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        if let Some(v) = &self.value {
+            f.write_str(" = ");
+            f.write_node(v);
+        }
+    }
+}
+impl_display_t!(S3ConfigOption);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CreateSourceConnection<T: AstInfo> {
     Kafka {
@@ -982,6 +1197,18 @@ pub enum CreateSourceConnection<T: AstInfo> {
         connection: T::ItemName,
         options: Vec<MySqlConfigOption<T>>,
     },
+    S3 {
+        connection: T::ItemName,
+        options: Vec<S3ConfigOption<T>>,
+    },
+    SqlServer {
+        connection: T::ItemName,
+        options: Vec<SqlServerConfigOption<T>>,
+    },
+    MongoDb {
+        connection: T::ItemName,
+        options: Vec<MongoDbConfigOption<T>>,
+    },
     LoadGenerator {
         generator: LoadGenerator,
         options: Vec<LoadGeneratorOption<T>>,
@@ -1027,6 +1254,42 @@ impl<T: AstInfo> AstDisplay for CreateSourceConnection<T> {
                     f.write_str(")");
                 }
             }
+            CreateSourceConnection::S3 {
+                connection,
+                options,
+            } => {
+                f.write_str("S3 CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
+            CreateSourceConnection::SqlServer {
+                connection,
+                options,
+            } => {
+                f.write_str("SQL SERVER CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
+            CreateSourceConnection::MongoDb {
+                connection,
+                options,
+            } => {
+                f.write_str("MONGODB CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+            }
             CreateSourceConnection::LoadGenerator { generator, options } => {
                 f.write_str("LOAD GENERATOR ");
                 f.write_node(generator);
@@ -1105,6 +1368,16 @@ pub enum CreateSinkConnection<T: AstInfo> {
         connection: T::ItemName,
         options: Vec<KafkaSinkConfigOption<T>>,
         key: Option<KafkaSinkKey>,
+        headers: Option<Ident>,
+    },
+    Http {
+        connection: T::ItemName,
+        key: Option<KafkaSinkKey>,
+    },
+    Postgres {
+        connection: T::ItemName,
+        options: Vec<PostgresSinkConfigOption<T>>,
+        key: Option<KafkaSinkKey>,
     },
 }
 
@@ -1115,6 +1388,7 @@ impl<T: AstInfo> AstDisplay for CreateSinkConnection<T> {
                 connection,
                 options,
                 key,
+                headers,
             } => {
                 f.write_str("KAFKA CONNECTION ");
                 f.write_node(connection);
@@ -1126,6 +1400,33 @@ impl<T: AstInfo> AstDisplay for CreateSinkConnection<T> {
                 if let Some(key) = key.as_ref() {
                     f.write_node(key);
                 }
+                if let Some(headers) = headers {
+                    f.write_str(" HEADERS ");
+                    f.write_node(headers);
+                }
+            }
+            CreateSinkConnection::Http { connection, key } => {
+                f.write_str("HTTP CONNECTION ");
+                f.write_node(connection);
+                if let Some(key) = key.as_ref() {
+                    f.write_node(key);
+                }
+            }
+            CreateSinkConnection::Postgres {
+                connection,
+                options,
+                key,
+            } => {
+                f.write_str("POSTGRES CONNECTION ");
+                f.write_node(connection);
+                if !options.is_empty() {
+                    f.write_str(" (");
+                    f.write_node(&display::comma_separated(options));
+                    f.write_str(")");
+                }
+                if let Some(key) = key.as_ref() {
+                    f.write_node(key);
+                }
             }
         }
     }
@@ -131,6 +131,12 @@ pub enum Expr<T: AstInfo> {
         l_expr: Box<Expr<T>>,
         r_expr: Box<Expr<T>>,
     },
+    /// `GROUPING(<expr>, ...)`
+    ///
+    /// While GROUPING has the same syntax as a function call, it reports
+    /// which of its arguments are part of the grouping set that produced the
+    /// current row, rather than computing a value from its arguments.
+    Grouping(Vec<Expr<T>>),
     /// Nested expression e.g. `(foo > bar)` or `(1)`
     Nested(Box<Expr<T>>),
     /// A row constructor like `ROW(<expr>...)` or `(<expr>, <expr>...)`.
@@ -338,6 +344,7 @@ impl<T: AstInfo> AstDisplay for Expr<T> {
                         | Expr::Collate { .. }
                         | Expr::HomogenizingFunction { .. }
                         | Expr::NullIf { .. }
+                        | Expr::Grouping(_)
                 );
                 if needs_wrap {
                     f.write_str('(');
@@ -365,6 +372,11 @@ impl<T: AstInfo> AstDisplay for Expr<T> {
                 f.write_node(&display::comma_separated(&[l_expr, r_expr]));
                 f.write_str(")");
             }
+            Expr::Grouping(exprs) => {
+                f.write_str("GROUPING(");
+                f.write_node(&display::comma_separated(exprs));
+                f.write_str(")");
+            }
             Expr::Nested(ast) => {
                 f.write_str("(");
                 f.write_node(&ast);
@@ -853,6 +865,16 @@ impl<T: AstInfo> AstDisplay for Function<T> {
         }
         f.write_node(&self.args);
         f.write_str(")");
+        if let FunctionArgs::Args {
+            order_by,
+            within_group: true,
+            ..
+        } = &self.args
+        {
+            f.write_str(" WITHIN GROUP (ORDER BY ");
+            f.write_node(&display::comma_separated(order_by));
+            f.write_str(")");
+        }
         if let Some(filter) = &self.filter {
             f.write_str(" FILTER (WHERE ");
             f.write_node(&filter);
@@ -874,6 +896,10 @@ pub enum FunctionArgs<T: AstInfo> {
     Args {
         args: Vec<Expr<T>>,
         order_by: Vec<OrderByExpr<T>>,
+        /// Whether `order_by` was specified as `WITHIN GROUP (ORDER BY ...)`,
+        /// as for ordered-set aggregates like `percentile_cont`. In that case
+        /// the `ORDER BY` expressions are themselves aggregated arguments.
+        within_group: bool,
     },
 }
 
@@ -882,6 +908,7 @@ impl<T: AstInfo> FunctionArgs<T> {
         Self::Args {
             args,
             order_by: vec![],
+            within_group: false,
         }
     }
 
@@ -922,9 +949,15 @@ impl<T: AstInfo> AstDisplay for FunctionArgs<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
             FunctionArgs::Star => f.write_str("*"),
-            FunctionArgs::Args { args, order_by } => {
+            FunctionArgs::Args {
+                args,
+                order_by,
+                within_group,
+            } => {
                 f.write_node(&display::comma_separated(args));
-                if !order_by.is_empty() {
+                // `WITHIN GROUP` is printed after the closing parenthesis by
+                // `Function`.
+                if !order_by.is_empty() && !within_group {
                     f.write_str(" ORDER BY ");
                     f.write_node(&display::comma_separated(order_by));
                 }
@@ -193,6 +193,8 @@ pub enum SelectOptionName {
     AggregateInputGroupSize,
     DistinctOnInputGroupSize,
     LimitInputGroupSize,
+    JoinOrder,
+    JoinImplementation,
 }
 
 impl AstDisplay for SelectOptionName {
@@ -202,6 +204,8 @@ impl AstDisplay for SelectOptionName {
             SelectOptionName::AggregateInputGroupSize => "AGGREGATE INPUT GROUP SIZE",
             SelectOptionName::DistinctOnInputGroupSize => "DISTINCT ON INPUT GROUP SIZE",
             SelectOptionName::LimitInputGroupSize => "LIMIT INPUT GROUP SIZE",
+            SelectOptionName::JoinOrder => "JOIN ORDER",
+            SelectOptionName::JoinImplementation => "JOIN IMPLEMENTATION",
         })
     }
 }
@@ -236,7 +240,7 @@ pub struct Select<T: AstInfo> {
     /// WHERE
     pub selection: Option<Expr<T>>,
     /// GROUP BY
-    pub group_by: Vec<Expr<T>>,
+    pub group_by: Vec<GroupingElement<T>>,
     /// HAVING
     pub having: Option<Expr<T>>,
     /// OPTION
@@ -296,6 +300,78 @@ impl<T: AstInfo> Select<T> {
     }
 }
 
+/// An element of a `GROUP BY` clause.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum GroupingElement<T: AstInfo> {
+    /// A single grouping expression, e.g. `a`.
+    Expr(Expr<T>),
+    /// A parenthesized list of grouping expressions, e.g. `(a, b)` or the
+    /// empty grouping set `()`.
+    ///
+    /// Outside of `GROUPING SETS`, a parenthesized list of two or more
+    /// expressions is parsed as a row constructor instead.
+    Set(Vec<Expr<T>>),
+    /// `ROLLUP (<exprs>, ...)`, where each item is a single expression or a
+    /// parenthesized list of expressions.
+    Rollup(Vec<Vec<Expr<T>>>),
+    /// `CUBE (<exprs>, ...)`, where each item is a single expression or a
+    /// parenthesized list of expressions.
+    Cube(Vec<Vec<Expr<T>>>),
+    /// `GROUPING SETS (<element>, ...)`
+    GroupingSets(Vec<GroupingElement<T>>),
+}
+
+impl<T: AstInfo> GroupingElement<T> {
+    /// Reports whether this element is a plain grouping expression.
+    pub fn is_expr(&self) -> bool {
+        matches!(self, GroupingElement::Expr(_))
+    }
+}
+
+impl<T: AstInfo> AstDisplay for GroupingElement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        fn fmt_items<T: AstInfo, W: fmt::Write>(f: &mut AstFormatter<W>, items: &[Vec<Expr<T>>]) {
+            f.write_str("(");
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ");
+                }
+                if let [expr] = item.as_slice() {
+                    f.write_node(expr);
+                } else {
+                    f.write_str("(");
+                    f.write_node(&display::comma_separated(item));
+                    f.write_str(")");
+                }
+            }
+            f.write_str(")");
+        }
+
+        match self {
+            GroupingElement::Expr(expr) => f.write_node(expr),
+            GroupingElement::Set(exprs) => {
+                f.write_str("(");
+                f.write_node(&display::comma_separated(exprs));
+                f.write_str(")");
+            }
+            GroupingElement::Rollup(items) => {
+                f.write_str("ROLLUP ");
+                fmt_items(f, items);
+            }
+            GroupingElement::Cube(items) => {
+                f.write_str("CUBE ");
+                fmt_items(f, items);
+            }
+            GroupingElement::GroupingSets(elements) => {
+                f.write_str("GROUPING SETS (");
+                f.write_node(&display::comma_separated(elements));
+                f.write_str(")");
+            }
+        }
+    }
+}
+impl_display_t!(GroupingElement);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Distinct<T: AstInfo> {
     EntireRow,
@@ -319,11 +395,15 @@ impl<T: AstInfo> AstDisplay for Distinct<T> {
 /// A block of common table expressions (CTEs).
 ///
 /// The block can either be entirely "simple" (traditional SQL `WITH` block),
-/// or "mutually recursive", which introduce their bindings before the block
-/// and may result in mutually recursive definitions.
+/// "recursive" (standard SQL `WITH RECURSIVE` block), or "mutually recursive",
+/// which introduce their bindings before the block and may result in mutually
+/// recursive definitions.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum CteBlock<T: AstInfo> {
     Simple(Vec<Cte<T>>),
+    /// A `WITH RECURSIVE` block, in which each CTE may refer to itself in
+    /// addition to the CTEs that precede it.
+    Recursive(Vec<Cte<T>>),
     MutuallyRecursive(MutRecBlock<T>),
 }
 
@@ -341,7 +421,7 @@ impl<T: AstInfo> CteBlock<T> {
     /// True if there are no bindings in the block.
     pub fn is_empty(&self) -> bool {
         match self {
-            CteBlock::Simple(list) => list.is_empty(),
+            CteBlock::Simple(list) | CteBlock::Recursive(list) => list.is_empty(),
             CteBlock::MutuallyRecursive(list) => list.ctes.is_empty(),
         }
     }
@@ -349,7 +429,7 @@ impl<T: AstInfo> CteBlock<T> {
     pub fn bound_identifiers(&self) -> impl Iterator<Item = &Ident> {
         let mut names = Vec::new();
         match self {
-            CteBlock::Simple(list) => {
+            CteBlock::Simple(list) | CteBlock::Recursive(list) => {
                 for cte in list.iter() {
                     names.push(&cte.alias.name);
                 }
@@ -372,6 +452,10 @@ impl<T: AstInfo> AstDisplay for CteBlock<T> {
                     f.write_str("WITH ");
                     f.write_node(&display::comma_separated(list));
                 }
+                CteBlock::Recursive(list) => {
+                    f.write_str("WITH RECURSIVE ");
+                    f.write_node(&display::comma_separated(list));
+                }
                 CteBlock::MutuallyRecursive(MutRecBlock { options, ctes }) => {
                     f.write_str("WITH MUTUALLY RECURSIVE ");
                     if !options.is_empty() {
@@ -56,6 +56,7 @@ pub enum Statement<T: AstInfo> {
     CreateTable(CreateTableStatement<T>),
     CreateIndex(CreateIndexStatement<T>),
     CreateType(CreateTypeStatement<T>),
+    CreateFunction(CreateFunctionStatement<T>),
     CreateRole(CreateRoleStatement),
     CreateCluster(CreateClusterStatement<T>),
     CreateClusterReplica(CreateClusterReplicaStatement<T>),
@@ -87,6 +88,7 @@ pub enum Statement<T: AstInfo> {
     Subscribe(SubscribeStatement<T>),
     ExplainPlan(ExplainPlanStatement<T>),
     ExplainPushdown(ExplainPushdownStatement<T>),
+    ExplainAnalyze(ExplainAnalyzeStatement<T>),
     ExplainTimestamp(ExplainTimestampStatement<T>),
     ExplainSinkSchema(ExplainSinkSchemaStatement<T>),
     Declare(DeclareStatement<T>),
@@ -128,6 +130,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::CreateRole(stmt) => f.write_node(stmt),
             Statement::CreateSecret(stmt) => f.write_node(stmt),
             Statement::CreateType(stmt) => f.write_node(stmt),
+            Statement::CreateFunction(stmt) => f.write_node(stmt),
             Statement::CreateCluster(stmt) => f.write_node(stmt),
             Statement::CreateClusterReplica(stmt) => f.write_node(stmt),
             Statement::AlterCluster(stmt) => f.write_node(stmt),
@@ -157,6 +160,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::Subscribe(stmt) => f.write_node(stmt),
             Statement::ExplainPlan(stmt) => f.write_node(stmt),
             Statement::ExplainPushdown(stmt) => f.write_node(stmt),
+            Statement::ExplainAnalyze(stmt) => f.write_node(stmt),
             Statement::ExplainTimestamp(stmt) => f.write_node(stmt),
             Statement::ExplainSinkSchema(stmt) => f.write_node(stmt),
             Statement::Declare(stmt) => f.write_node(stmt),
@@ -199,6 +203,7 @@ pub fn statement_kind_label_value(kind: StatementKind) -> &'static str {
         StatementKind::CreateTable => "create_table",
         StatementKind::CreateIndex => "create_index",
         StatementKind::CreateType => "create_type",
+        StatementKind::CreateFunction => "create_function",
         StatementKind::CreateRole => "create_role",
         StatementKind::CreateCluster => "create_cluster",
         StatementKind::CreateClusterReplica => "create_cluster_replica",
@@ -230,6 +235,7 @@ pub fn statement_kind_label_value(kind: StatementKind) -> &'static str {
         StatementKind::Subscribe => "subscribe",
         StatementKind::ExplainPlan => "explain_plan",
         StatementKind::ExplainPushdown => "explain_pushdown",
+        StatementKind::ExplainAnalyze => "explain_analyze",
         StatementKind::ExplainTimestamp => "explain_timestamp",
         StatementKind::ExplainSinkSchema => "explain_sink_schema",
         StatementKind::Declare => "declare",
@@ -277,6 +283,8 @@ pub struct InsertStatement<T: AstInfo> {
     pub columns: Vec<Ident>,
     /// A SQL query that specifies what to insert.
     pub source: InsertSource<T>,
+    /// ON CONFLICT
+    pub on_conflict: Option<OnConflict<T>>,
     /// RETURNING
     pub returning: Vec<SelectItem<T>>,
 }
@@ -292,6 +300,10 @@ impl<T: AstInfo> AstDisplay for InsertStatement<T> {
         }
         f.write_str(" ");
         f.write_node(&self.source);
+        if let Some(on_conflict) = &self.on_conflict {
+            f.write_str(" ");
+            f.write_node(on_conflict);
+        }
         if !self.returning.is_empty() {
             f.write_str(" RETURNING ");
             f.write_node(&display::comma_separated(&self.returning));
@@ -1668,11 +1680,124 @@ impl<T: AstInfo> AstDisplay for CreateTypeStatement<T> {
                 }
                 f.write_str(")");
             }
+            CreateTypeAs::Enum { labels } => {
+                f.write_str(&self.as_type);
+                f.write_str("(");
+                let labels: Vec<_> = labels
+                    .iter()
+                    .map(|l| display::escaped_string_literal(l))
+                    .collect();
+                f.write_node(&display::comma_separated(&labels));
+                f.write_str(")");
+            }
         };
     }
 }
 impl_display_t!(CreateTypeStatement);
 
+/// `CREATE FUNCTION ... LANGUAGE SQL`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CreateFunctionStatement<T: AstInfo> {
+    /// Name of the created function.
+    pub name: UnresolvedItemName,
+    /// Whether `OR REPLACE` was specified.
+    pub if_exists: IfExistsBehavior,
+    /// The function's parameters.
+    pub params: Vec<FunctionParameter<T>>,
+    /// What the function returns.
+    pub returns: FunctionReturns<T>,
+    /// The query that computes the function's result.
+    pub body: Query<T>,
+}
+
+impl<T: AstInfo> AstDisplay for CreateFunctionStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("CREATE");
+        if self.if_exists == IfExistsBehavior::Replace {
+            f.write_str(" OR REPLACE");
+        }
+        f.write_str(" FUNCTION ");
+        f.write_node(&self.name);
+        f.write_str("(");
+        f.write_node(&display::comma_separated(&self.params));
+        f.write_str(") RETURNS ");
+        f.write_node(&self.returns);
+        f.write_str(" LANGUAGE SQL AS ");
+
+        // The body is printed as a string literal, using dollar quoting
+        // whenever the body's text permits it.
+        let mut body = String::new();
+        f.with_buf(&mut body).write_node(&self.body);
+        if body.contains("$$") || body.ends_with('$') {
+            f.write_node(&display::escaped_string_literal(&body));
+        } else {
+            f.write_str("$$");
+            f.write_str(body);
+            f.write_str("$$");
+        }
+    }
+}
+impl_display_t!(CreateFunctionStatement);
+
+/// A parameter in a `CREATE FUNCTION` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FunctionParameter<T: AstInfo> {
+    /// The parameter's name, if any.
+    pub name: Option<Ident>,
+    /// The parameter's type.
+    pub data_type: T::DataType,
+}
+
+impl<T: AstInfo> AstDisplay for FunctionParameter<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        if let Some(name) = &self.name {
+            f.write_node(name);
+            f.write_str(" ");
+        }
+        f.write_node(&self.data_type);
+    }
+}
+impl_display_t!(FunctionParameter);
+
+/// The `RETURNS` clause of a `CREATE FUNCTION` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FunctionReturns<T: AstInfo> {
+    /// `RETURNS <type>`
+    Scalar(T::DataType),
+    /// `RETURNS TABLE (<name> <type>, ...)`
+    Table(Vec<FunctionTableColumn<T>>),
+}
+
+impl<T: AstInfo> AstDisplay for FunctionReturns<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            FunctionReturns::Scalar(data_type) => f.write_node(data_type),
+            FunctionReturns::Table(columns) => {
+                f.write_str("TABLE (");
+                f.write_node(&display::comma_separated(columns));
+                f.write_str(")");
+            }
+        }
+    }
+}
+impl_display_t!(FunctionReturns);
+
+/// A column in the `RETURNS TABLE` clause of a `CREATE FUNCTION` statement.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FunctionTableColumn<T: AstInfo> {
+    pub name: Ident,
+    pub data_type: T::DataType,
+}
+
+impl<T: AstInfo> AstDisplay for FunctionTableColumn<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_node(&self.name);
+        f.write_str(" ");
+        f.write_node(&self.data_type);
+    }
+}
+impl_display_t!(FunctionTableColumn);
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ClusterOptionName {
     /// The `AVAILABILITY ZONES [[=] '[' <values> ']' ]` option.
@@ -1950,6 +2075,9 @@ pub enum CreateTypeAs<T: AstInfo> {
     Record {
         column_defs: Vec<ColumnDef<T>>,
     },
+    Enum {
+        labels: Vec<String>,
+    },
 }
 
 impl<T: AstInfo> AstDisplay for CreateTypeAs<T> {
@@ -1958,6 +2086,7 @@ impl<T: AstInfo> AstDisplay for CreateTypeAs<T> {
             CreateTypeAs::List { .. } => f.write_str("LIST "),
             CreateTypeAs::Map { .. } => f.write_str("MAP "),
             CreateTypeAs::Record { .. } => f.write_str("RECORD "),
+            CreateTypeAs::Enum { .. } => f.write_str("ENUM "),
         }
     }
 }
@@ -2898,6 +3027,20 @@ impl<T: AstInfo> AstDisplay for ShowCreateConnectionStatement<T> {
     }
 }
 
+/// `SHOW CREATE FUNCTION <function>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ShowCreateFunctionStatement<T: AstInfo> {
+    pub function_name: T::ItemName,
+}
+
+impl<T: AstInfo> AstDisplay for ShowCreateFunctionStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("SHOW CREATE FUNCTION ");
+        f.write_node(&self.function_name);
+    }
+}
+impl_display_t!(ShowCreateFunctionStatement);
+
 /// `{ BEGIN [ TRANSACTION | WORK ] | START TRANSACTION } ...`
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct StartTransactionStatement {
@@ -3162,6 +3305,48 @@ impl<T: AstInfo> AstDisplay for ExplainPushdownStatement<T> {
 }
 impl_display_t!(ExplainPushdownStatement);
 
+/// `EXPLAIN ANALYZE ...`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExplainAnalyzeStatement<T: AstInfo> {
+    /// The statistics to report. If empty, all statistics are reported.
+    pub properties: Vec<ExplainAnalyzeProperty>,
+    pub format: ExplainFormat,
+    pub explainee: Explainee<T>,
+}
+
+impl<T: AstInfo> AstDisplay for ExplainAnalyzeStatement<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("EXPLAIN ANALYZE ");
+        if !self.properties.is_empty() {
+            f.write_node(&display::comma_separated(&self.properties));
+            f.write_str(" ");
+        }
+        f.write_str("AS ");
+        f.write_node(&self.format);
+        f.write_str(" FOR ");
+        f.write_node(&self.explainee);
+    }
+}
+impl_display_t!(ExplainAnalyzeStatement);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ExplainAnalyzeProperty {
+    /// Arrangement records, batches, and sizes.
+    Memory,
+    /// Elapsed operator time.
+    Cpu,
+}
+
+impl AstDisplay for ExplainAnalyzeProperty {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            Self::Memory => f.write_str("MEMORY"),
+            Self::Cpu => f.write_str("CPU"),
+        }
+    }
+}
+impl_display!(ExplainAnalyzeProperty);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ExplainTimestampStatement<T: AstInfo> {
     pub format: ExplainFormat,
@@ -3194,6 +3379,59 @@ impl<T: AstInfo> AstDisplay for InsertSource<T> {
 }
 impl_display_t!(InsertSource);
 
+/// `ON CONFLICT [(<columns>)] <action>`, as used in `INSERT`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OnConflict<T: AstInfo> {
+    /// The columns that identify a conflicting row, if specified.
+    pub target: Option<Vec<Ident>>,
+    /// What to do with a conflicting row.
+    pub action: OnConflictAction<T>,
+}
+
+impl<T: AstInfo> AstDisplay for OnConflict<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ON CONFLICT ");
+        if let Some(target) = &self.target {
+            f.write_str("(");
+            f.write_node(&display::comma_separated(target));
+            f.write_str(") ");
+        }
+        f.write_node(&self.action);
+    }
+}
+impl_display_t!(OnConflict);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OnConflictAction<T: AstInfo> {
+    /// `DO NOTHING`
+    DoNothing,
+    /// `DO UPDATE SET <assignments> [WHERE <selection>]`
+    DoUpdate {
+        assignments: Vec<Assignment<T>>,
+        selection: Option<Expr<T>>,
+    },
+}
+
+impl<T: AstInfo> AstDisplay for OnConflictAction<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            OnConflictAction::DoNothing => f.write_str("DO NOTHING"),
+            OnConflictAction::DoUpdate {
+                assignments,
+                selection,
+            } => {
+                f.write_str("DO UPDATE SET ");
+                f.write_node(&display::comma_separated(assignments));
+                if let Some(selection) = selection {
+                    f.write_str(" WHERE ");
+                    f.write_node(selection);
+                }
+            }
+        }
+    }
+}
+impl_display_t!(OnConflictAction);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Copy)]
 pub enum ObjectType {
     Table,
@@ -3310,6 +3548,7 @@ pub enum WithOptionValue<T: AstInfo> {
     Item(T::ItemName),
     UnresolvedItemName(UnresolvedItemName),
     Sequence(Vec<WithOptionValue<T>>),
+    Expr(Expr<T>),
     // Special cases.
     ClusterReplicas(Vec<ReplicaDefinition<T>>),
     ConnectionKafkaBroker(KafkaBroker<T>),
@@ -3327,7 +3566,8 @@ impl<T: AstInfo> AstDisplay for WithOptionValue<T> {
                 WithOptionValue::Value(_)
                 | WithOptionValue::Sequence(_)
                 | WithOptionValue::RetainHistoryFor(_)
-                | WithOptionValue::Refresh(_) => {
+                | WithOptionValue::Refresh(_)
+                | WithOptionValue::Expr(_) => {
                     // These are redact-aware.
                 }
                 WithOptionValue::DataType(_)
@@ -3379,6 +3619,7 @@ impl<T: AstInfo> AstDisplay for WithOptionValue<T> {
                 f.write_node(value);
             }
             WithOptionValue::Refresh(opt) => f.write_node(opt),
+            WithOptionValue::Expr(expr) => f.write_node(expr),
         }
     }
 }
@@ -4027,6 +4268,7 @@ pub enum ShowStatement<T: AstInfo> {
     ShowCreateSink(ShowCreateSinkStatement<T>),
     ShowCreateIndex(ShowCreateIndexStatement<T>),
     ShowCreateConnection(ShowCreateConnectionStatement<T>),
+    ShowCreateFunction(ShowCreateFunctionStatement<T>),
     ShowVariable(ShowVariableStatement),
     InspectShard(InspectShardStatement),
 }
@@ -4043,6 +4285,7 @@ impl<T: AstInfo> AstDisplay for ShowStatement<T> {
             ShowStatement::ShowCreateSink(stmt) => f.write_node(stmt),
             ShowStatement::ShowCreateIndex(stmt) => f.write_node(stmt),
             ShowStatement::ShowCreateConnection(stmt) => f.write_node(stmt),
+            ShowStatement::ShowCreateFunction(stmt) => f.write_node(stmt),
             ShowStatement::ShowVariable(stmt) => f.write_node(stmt),
             ShowStatement::InspectShard(stmt) => f.write_node(stmt),
         }
@@ -120,6 +120,11 @@ where
     pub fn new(buf: W, mode: FormatMode) -> Self {
         AstFormatter { buf, mode }
     }
+
+    /// Returns a formatter that writes to `buf` in the same mode as this one.
+    pub fn with_buf<V: fmt::Write>(&self, buf: V) -> AstFormatter<V> {
+        AstFormatter::new(buf, self.mode)
+    }
 }
 
 // AstDisplay is an alternative to fmt::Display to be used for formatting ASTs. It permits
@@ -187,6 +187,8 @@ pub enum RawDataType {
         key_type: Box<RawDataType>,
         value_type: Box<RawDataType>,
     },
+    /// Anonymous enum, e.g. `enum('a', 'b')`
+    Enum(Vec<String>),
     /// Types who don't embed other types, e.g. INT
     Other {
         name: RawItemName,
@@ -216,6 +218,15 @@ impl AstDisplay for RawDataType {
                 f.write_node(&value_type);
                 f.write_str("]");
             }
+            RawDataType::Enum(labels) => {
+                let labels: Vec<_> = labels
+                    .iter()
+                    .map(|l| display::escaped_string_literal(l))
+                    .collect();
+                f.write_str("enum(");
+                f.write_node(&display::comma_separated(&labels));
+                f.write_str(")");
+            }
             RawDataType::Other { name, typ_mod } => {
                 f.write_node(name);
                 if typ_mod.len() > 0 {
@@ -568,6 +568,9 @@ impl<'a> Parser<'a> {
             }
             Token::Keyword(LEAST) => self.parse_homogenizing_function(HomogenizingFunction::Least),
             Token::Keyword(NULLIF) => self.parse_nullif_expr(),
+            Token::Keyword(GROUPING) if self.peek_token() == Some(Token::LParen) => {
+                self.parse_grouping_expr()
+            }
             Token::Keyword(EXISTS) => self.parse_exists_expr(),
             Token::Keyword(EXTRACT) => self.parse_extract_expr(),
             Token::Keyword(INTERVAL) => {
@@ -739,6 +742,38 @@ impl<'a> Parser<'a> {
             ));
         }
 
+        let args = if self.peek_keywords(&[WITHIN, GROUP]) {
+            let pos = self.peek_pos();
+            self.expect_keywords(&[WITHIN, GROUP])?;
+            self.expect_token(&Token::LParen)?;
+            self.expect_keywords(&[ORDER, BY])?;
+            let within_group = self.parse_comma_separated(Parser::parse_order_by_expr)?;
+            self.expect_token(&Token::RParen)?;
+            if distinct {
+                return Err(self.error(pos, "cannot use DISTINCT with WITHIN GROUP".into()));
+            }
+            match args {
+                FunctionArgs::Args { args, order_by, .. } if order_by.is_empty() => {
+                    FunctionArgs::Args {
+                        args,
+                        order_by: within_group,
+                        within_group: true,
+                    }
+                }
+                FunctionArgs::Args { .. } => {
+                    return Err(self.error(
+                        pos,
+                        "cannot use multiple ORDER BY clauses with WITHIN GROUP".into(),
+                    ))
+                }
+                FunctionArgs::Star => {
+                    return Err(self.error(pos, "cannot use * with WITHIN GROUP".into()))
+                }
+            }
+        } else {
+            args
+        };
+
         let filter = if self.parse_keyword(FILTER) {
             self.expect_token(&Token::LParen)?;
             self.expect_keyword(WHERE)?;
@@ -909,6 +944,13 @@ impl<'a> Parser<'a> {
         Ok(Expr::NullIf { l_expr, r_expr })
     }
 
+    fn parse_grouping_expr(&mut self) -> Result<Expr<Raw>, ParserError> {
+        self.expect_token(&Token::LParen)?;
+        let exprs = self.parse_comma_separated(Parser::parse_expr)?;
+        self.expect_token(&Token::RParen)?;
+        Ok(Expr::Grouping(exprs))
+    }
+
     // Parse calls to extract(), which can take the form:
     // - extract(field from 'interval')
     fn parse_extract_expr(&mut self) -> Result<Expr<Raw>, ParserError> {
@@ -1850,6 +1892,9 @@ impl<'a> Parser<'a> {
         } else if self.peek_keyword(CONNECTION) {
             self.parse_create_connection()
                 .map_parser_err(StatementKind::CreateConnection)
+        } else if self.peek_keyword(FUNCTION) || self.peek_keywords(&[OR, REPLACE, FUNCTION]) {
+            self.parse_create_function()
+                .map_parser_err(StatementKind::CreateFunction)
         } else if self.peek_keywords(&[MATERIALIZED, VIEW])
             || self.peek_keywords(&[OR, REPLACE, MATERIALIZED, VIEW])
         {
@@ -1875,7 +1920,7 @@ impl<'a> Parser<'a> {
             } else {
                 self.expected(
                     self.peek_pos(),
-                    "DATABASE, SCHEMA, ROLE, TYPE, INDEX, SINK, SOURCE, TABLE, SECRET, [OR REPLACE] [TEMPORARY] VIEW, or [OR REPLACE] MATERIALIZED VIEW after CREATE",
+                    "DATABASE, SCHEMA, ROLE, TYPE, INDEX, SINK, SOURCE, TABLE, SECRET, [OR REPLACE] FUNCTION, [OR REPLACE] [TEMPORARY] VIEW, or [OR REPLACE] MATERIALIZED VIEW after CREATE",
                     self.peek_token(),
                 ).map_no_statement_parser_err()
             }
@@ -1903,7 +1948,13 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_format(&mut self) -> Result<Format<Raw>, ParserError> {
-        let format = if self.parse_keyword(AVRO) {
+        let format = if self.parse_keywords(&[AVRO, OCF]) {
+            self.expect_keywords(&[USING, SCHEMA])?;
+            let schema = Schema {
+                schema: self.parse_literal_string()?,
+            };
+            Format::AvroOcf { schema }
+        } else if self.parse_keyword(AVRO) {
             self.expect_keyword(USING)?;
             Format::Avro(self.parse_avro_schema()?)
         } else if self.parse_keyword(PROTOBUF) {
@@ -1933,16 +1984,23 @@ impl<'a> Parser<'a> {
             };
             Format::Csv { columns, delimiter }
         } else if self.parse_keyword(JSON) {
-            let array = self.parse_keyword(ARRAY);
-            Format::Json { array }
+            if self.parse_keywords(&[USING, CONFLUENT, SCHEMA, REGISTRY]) {
+                let csr_connection = self.parse_csr_connection_json()?;
+                Format::JsonCsr { csr_connection }
+            } else {
+                let array = self.parse_keyword(ARRAY);
+                Format::Json { array }
+            }
         } else if self.parse_keyword(TEXT) {
             Format::Text
         } else if self.parse_keyword(BYTES) {
             Format::Bytes
+        } else if self.parse_keyword(PARQUET) {
+            Format::Parquet
         } else {
             return self.expected(
                 self.peek_pos(),
-                "AVRO, PROTOBUF, REGEX, CSV, JSON, TEXT, or BYTES",
+                "AVRO, PROTOBUF, REGEX, CSV, JSON, TEXT, BYTES, or PARQUET",
                 self.peek_token(),
             );
         };
@@ -2174,6 +2232,28 @@ impl<'a> Parser<'a> {
         Ok(CsrConnectionProtobuf { connection, seed })
     }
 
+    fn parse_csr_connection_json(&mut self) -> Result<CsrConnectionJson<Raw>, ParserError> {
+        let connection = self.parse_csr_connection_reference()?;
+        let seed = if self.parse_keyword(SEED) {
+            let key_schema = if self.parse_keyword(KEY) {
+                self.expect_keyword(SCHEMA)?;
+                Some(self.parse_literal_string()?)
+            } else {
+                None
+            };
+            self.expect_keywords(&[VALUE, SCHEMA])?;
+            let value_schema = self.parse_literal_string()?;
+            Some(CsrSeedJson {
+                key_schema,
+                value_schema,
+            })
+        } else {
+            None
+        };
+
+        Ok(CsrConnectionJson { connection, seed })
+    }
+
     fn parse_source_envelope(&mut self) -> Result<SourceEnvelope, ParserError> {
         let envelope = if self.parse_keyword(NONE) {
             SourceEnvelope::None
@@ -2220,28 +2300,35 @@ impl<'a> Parser<'a> {
             TO => true,
             _ => unreachable!(),
         };
-        let connection_type =
-            match self.expect_one_of_keywords(&[AWS, KAFKA, CONFLUENT, POSTGRES, SSH, MYSQL])? {
-                AWS => {
-                    if self.parse_keyword(PRIVATELINK) {
-                        CreateConnectionType::AwsPrivatelink
-                    } else {
-                        CreateConnectionType::Aws
-                    }
-                }
-                KAFKA => CreateConnectionType::Kafka,
-                CONFLUENT => {
-                    self.expect_keywords(&[SCHEMA, REGISTRY])?;
-                    CreateConnectionType::Csr
-                }
-                POSTGRES => CreateConnectionType::Postgres,
-                SSH => {
-                    self.expect_keyword(TUNNEL)?;
-                    CreateConnectionType::Ssh
+        let connection_type = match self.expect_one_of_keywords(&[
+            AWS, KAFKA, CONFLUENT, POSTGRES, SSH, MYSQL, HTTP, SQL, MONGODB,
+        ])? {
+            AWS => {
+                if self.parse_keyword(PRIVATELINK) {
+                    CreateConnectionType::AwsPrivatelink
+                } else {
+                    CreateConnectionType::Aws
                 }
-                MYSQL => CreateConnectionType::MySql,
-                _ => unreachable!(),
-            };
+            }
+            KAFKA => CreateConnectionType::Kafka,
+            CONFLUENT => {
+                self.expect_keywords(&[SCHEMA, REGISTRY])?;
+                CreateConnectionType::Csr
+            }
+            POSTGRES => CreateConnectionType::Postgres,
+            SSH => {
+                self.expect_keyword(TUNNEL)?;
+                CreateConnectionType::Ssh
+            }
+            MYSQL => CreateConnectionType::MySql,
+            HTTP => CreateConnectionType::Http,
+            SQL => {
+                self.expect_keyword(SERVER)?;
+                CreateConnectionType::SqlServer
+            }
+            MONGODB => CreateConnectionType::MongoDb,
+            _ => unreachable!(),
+        };
         if expect_paren {
             self.expect_token(&Token::LParen)?;
         }
@@ -2399,6 +2486,7 @@ impl<'a> Parser<'a> {
             TOPIC,
             LEGACY,
             TRANSACTIONAL,
+            PARTITION,
         ])? {
             COMPRESSION => {
                 self.expect_keyword(TYPE)?;
@@ -2417,6 +2505,14 @@ impl<'a> Parser<'a> {
                 self.expect_keywords(&[IDS])?;
                 KafkaSinkConfigOptionName::LegacyIds
             }
+            PARTITION => {
+                self.expect_keyword(BY)?;
+                let _ = self.consume_token(&Token::Eq);
+                return Ok(KafkaSinkConfigOption {
+                    name: KafkaSinkConfigOptionName::PartitionBy,
+                    value: Some(WithOptionValue::Expr(self.parse_expr()?)),
+                });
+            }
             _ => unreachable!(),
         };
         Ok(KafkaSinkConfigOption {
@@ -2425,6 +2521,24 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_postgres_sink_config_option(
+        &mut self,
+    ) -> Result<PostgresSinkConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[PROGRESS, SCHEMA, TABLE])? {
+            PROGRESS => {
+                self.expect_keyword(TABLE)?;
+                PostgresSinkConfigOptionName::ProgressTable
+            }
+            SCHEMA => PostgresSinkConfigOptionName::Schema,
+            TABLE => PostgresSinkConfigOptionName::Table,
+            _ => unreachable!(),
+        };
+        Ok(PostgresSinkConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
     fn parse_connection_option_name(&mut self) -> Result<ConnectionOptionName, ParserError> {
         Ok(
             match self.expect_one_of_keywords(&[
@@ -2436,6 +2550,7 @@ impl<'a> Parser<'a> {
                 BROKERS,
                 DATABASE,
                 ENDPOINT,
+                HMAC,
                 HOST,
                 PASSWORD,
                 PORT,
@@ -2480,6 +2595,10 @@ impl<'a> Parser<'a> {
                 BROKERS => ConnectionOptionName::Brokers,
                 DATABASE => ConnectionOptionName::Database,
                 ENDPOINT => ConnectionOptionName::Endpoint,
+                HMAC => {
+                    self.expect_keyword(SECRET)?;
+                    ConnectionOptionName::HmacSecret
+                }
                 HOST => ConnectionOptionName::Host,
                 PASSWORD => ConnectionOptionName::Password,
                 PORT => ConnectionOptionName::Port,
@@ -3016,7 +3135,7 @@ impl<'a> Parser<'a> {
     fn parse_create_source_connection(
         &mut self,
     ) -> Result<CreateSourceConnection<Raw>, ParserError> {
-        match self.expect_one_of_keywords(&[KAFKA, POSTGRES, MYSQL, LOAD])? {
+        match self.expect_one_of_keywords(&[KAFKA, POSTGRES, MYSQL, S3, SQL, MONGODB, LOAD])? {
             POSTGRES => {
                 self.expect_keyword(CONNECTION)?;
                 let connection = self.parse_raw_name()?;
@@ -3052,6 +3171,59 @@ impl<'a> Parser<'a> {
                     options,
                 })
             }
+            S3 => {
+                self.expect_keyword(CONNECTION)?;
+                let connection = self.parse_raw_name()?;
+
+                let options = if self.consume_token(&Token::LParen) {
+                    let options = self.parse_comma_separated(Parser::parse_s3_connection_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+
+                Ok(CreateSourceConnection::S3 {
+                    connection,
+                    options,
+                })
+            }
+            SQL => {
+                self.expect_keywords(&[SERVER, CONNECTION])?;
+                let connection = self.parse_raw_name()?;
+
+                let options = if self.consume_token(&Token::LParen) {
+                    let options =
+                        self.parse_comma_separated(Parser::parse_sql_server_connection_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+
+                Ok(CreateSourceConnection::SqlServer {
+                    connection,
+                    options,
+                })
+            }
+            MONGODB => {
+                self.expect_keyword(CONNECTION)?;
+                let connection = self.parse_raw_name()?;
+
+                let options = if self.consume_token(&Token::LParen) {
+                    let options =
+                        self.parse_comma_separated(Parser::parse_mongodb_connection_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
+
+                Ok(CreateSourceConnection::MongoDb {
+                    connection,
+                    options,
+                })
+            }
             KAFKA => {
                 self.expect_keyword(CONNECTION)?;
                 let connection = self.parse_raw_name()?;
@@ -3181,6 +3353,47 @@ impl<'a> Parser<'a> {
         }
     }
 
+    fn parse_s3_connection_option(&mut self) -> Result<S3ConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[PREFIX, POLL])? {
+            PREFIX => S3ConfigOptionName::Prefix,
+            POLL => {
+                self.expect_keyword(INTERVAL)?;
+                S3ConfigOptionName::PollInterval
+            }
+            _ => unreachable!(),
+        };
+
+        let _ = self.consume_token(&Token::Eq);
+        Ok(S3ConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_sql_server_connection_option(
+        &mut self,
+    ) -> Result<SqlServerConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[DETAILS])? {
+            DETAILS => SqlServerConfigOptionName::Details,
+            _ => unreachable!(),
+        };
+        Ok(SqlServerConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
+    fn parse_mongodb_connection_option(&mut self) -> Result<MongoDbConfigOption<Raw>, ParserError> {
+        let name = match self.expect_one_of_keywords(&[DETAILS])? {
+            DETAILS => MongoDbConfigOptionName::Details,
+            _ => unreachable!(),
+        };
+        Ok(MongoDbConfigOption {
+            name,
+            value: self.parse_optional_option_value()?,
+        })
+    }
+
     fn parse_load_generator_option(&mut self) -> Result<LoadGeneratorOption<Raw>, ParserError> {
         let name = match self.expect_one_of_keywords(&[SCALE, TICK, MAX])? {
             SCALE => {
@@ -3206,46 +3419,91 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_create_sink_connection(&mut self) -> Result<CreateSinkConnection<Raw>, ParserError> {
-        self.expect_keyword(KAFKA)?;
-        self.expect_keyword(CONNECTION)?;
+        match self.expect_one_of_keywords(&[KAFKA, HTTP, POSTGRES])? {
+            KAFKA => {
+                self.expect_keyword(CONNECTION)?;
 
-        let connection = self.parse_raw_name()?;
+                let connection = self.parse_raw_name()?;
 
-        let options = if self.consume_token(&Token::LParen) {
-            let options = self.parse_comma_separated(Parser::parse_kafka_sink_config_option)?;
-            self.expect_token(&Token::RParen)?;
-            options
-        } else {
-            vec![]
-        };
+                let options = if self.consume_token(&Token::LParen) {
+                    let options =
+                        self.parse_comma_separated(Parser::parse_kafka_sink_config_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
+                };
 
-        // one token of lookahead:
-        // * `KEY (` means we're parsing a list of columns for the key
-        // * `KEY FORMAT` means there is no key, we'll parse a KeyValueFormat later
-        let key =
-            if self.peek_keyword(KEY) && self.peek_nth_token(1) != Some(Token::Keyword(FORMAT)) {
-                let _ = self.expect_keyword(KEY);
-                let key_columns = self.parse_parenthesized_column_list(Mandatory)?;
+                let key = self.parse_sink_key()?;
 
-                let not_enforced = if self.peek_keywords(&[NOT, ENFORCED]) {
-                    let _ = self.expect_keywords(&[NOT, ENFORCED])?;
-                    true
+                let headers = if self.parse_keyword(HEADERS) {
+                    Some(self.parse_identifier()?)
                 } else {
-                    false
+                    None
+                };
+
+                Ok(CreateSinkConnection::Kafka {
+                    connection,
+                    options,
+                    key,
+                    headers,
+                })
+            }
+            HTTP => {
+                self.expect_keyword(CONNECTION)?;
+
+                let connection = self.parse_raw_name()?;
+                let key = self.parse_sink_key()?;
+
+                Ok(CreateSinkConnection::Http { connection, key })
+            }
+            POSTGRES => {
+                self.expect_keyword(CONNECTION)?;
+
+                let connection = self.parse_raw_name()?;
+
+                let options = if self.consume_token(&Token::LParen) {
+                    let options =
+                        self.parse_comma_separated(Parser::parse_postgres_sink_config_option)?;
+                    self.expect_token(&Token::RParen)?;
+                    options
+                } else {
+                    vec![]
                 };
-                Some(KafkaSinkKey {
-                    key_columns,
-                    not_enforced,
+
+                let key = self.parse_sink_key()?;
+
+                Ok(CreateSinkConnection::Postgres {
+                    connection,
+                    options,
+                    key,
                 })
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn parse_sink_key(&mut self) -> Result<Option<KafkaSinkKey>, ParserError> {
+        // one token of lookahead:
+        // * `KEY (` means we're parsing a list of columns for the key
+        // * `KEY FORMAT` means there is no key, we'll parse a KeyValueFormat later
+        if self.peek_keyword(KEY) && self.peek_nth_token(1) != Some(Token::Keyword(FORMAT)) {
+            let _ = self.expect_keyword(KEY);
+            let key_columns = self.parse_parenthesized_column_list(Mandatory)?;
+
+            let not_enforced = if self.peek_keywords(&[NOT, ENFORCED]) {
+                let _ = self.expect_keywords(&[NOT, ENFORCED])?;
+                true
             } else {
-                None
+                false
             };
-
-        Ok(CreateSinkConnection::Kafka {
-            connection,
-            options,
-            key,
-        })
+            Ok(Some(KafkaSinkKey {
+                key_columns,
+                not_enforced,
+            }))
+        } else {
+            Ok(None)
+        }
     }
 
     fn parse_create_view(&mut self) -> Result<Statement<Raw>, ParserError> {
@@ -3577,7 +3835,7 @@ impl<'a> Parser<'a> {
         let name = self.parse_item_name()?;
         self.expect_keyword(AS)?;
 
-        match self.parse_one_of_keywords(&[LIST, MAP]) {
+        match self.parse_one_of_keywords(&[LIST, MAP, ENUM]) {
             Some(LIST) => {
                 self.expect_token(&Token::LParen)?;
                 let options = self.parse_comma_separated(Parser::parse_create_type_list_option)?;
@@ -3596,6 +3854,19 @@ impl<'a> Parser<'a> {
                     as_type: CreateTypeAs::Map { options },
                 }))
             }
+            Some(ENUM) => {
+                self.expect_token(&Token::LParen)?;
+                let labels = if self.peek_token() == Some(Token::RParen) {
+                    vec![]
+                } else {
+                    self.parse_comma_separated(Parser::parse_literal_string)?
+                };
+                self.expect_token(&Token::RParen)?;
+                Ok(Statement::CreateType(CreateTypeStatement {
+                    name,
+                    as_type: CreateTypeAs::Enum { labels },
+                }))
+            }
             None => {
                 let column_defs = self.parse_composite_type_definition()?;
 
@@ -3608,6 +3879,114 @@ impl<'a> Parser<'a> {
         }
     }
 
+    fn parse_create_function(&mut self) -> Result<Statement<Raw>, ParserError> {
+        let if_exists = if self.parse_keyword(OR) {
+            self.expect_keyword(REPLACE)?;
+            IfExistsBehavior::Replace
+        } else {
+            IfExistsBehavior::Error
+        };
+        self.expect_keyword(FUNCTION)?;
+        let name = self.parse_item_name()?;
+
+        self.expect_token(&Token::LParen)?;
+        let params = if self.consume_token(&Token::RParen) {
+            vec![]
+        } else {
+            let params = self.parse_comma_separated(Parser::parse_function_parameter)?;
+            self.expect_token(&Token::RParen)?;
+            params
+        };
+
+        self.expect_keyword(RETURNS)?;
+        let returns = if self.parse_keyword(TABLE) {
+            self.expect_token(&Token::LParen)?;
+            let columns = self.parse_comma_separated(|parser| {
+                Ok(FunctionTableColumn {
+                    name: parser.parse_identifier()?,
+                    data_type: parser.parse_data_type()?,
+                })
+            })?;
+            self.expect_token(&Token::RParen)?;
+            FunctionReturns::Table(columns)
+        } else {
+            FunctionReturns::Scalar(self.parse_data_type()?)
+        };
+
+        // Like PostgreSQL, accept the `LANGUAGE` and `AS` clauses in either
+        // order.
+        let mut language = false;
+        let mut body = None;
+        loop {
+            if !language && self.parse_keyword(LANGUAGE) {
+                if !self.parse_keyword(SQL) {
+                    return parser_err!(
+                        self,
+                        self.peek_pos(),
+                        "CREATE FUNCTION only supports LANGUAGE SQL"
+                    );
+                }
+                language = true;
+            } else if body.is_none() && self.parse_keyword(AS) {
+                let pos = self.peek_pos();
+                let sql = self.parse_literal_string()?;
+                body = Some(self.parse_function_body(pos, &sql)?);
+            } else {
+                break;
+            }
+        }
+        if !language {
+            return self.expected(self.peek_pos(), "LANGUAGE", self.peek_token());
+        }
+        let Some(body) = body else {
+            return self.expected(self.peek_pos(), "AS", self.peek_token());
+        };
+
+        Ok(Statement::CreateFunction(CreateFunctionStatement {
+            name,
+            if_exists,
+            params,
+            returns,
+            body,
+        }))
+    }
+
+    fn parse_function_parameter(&mut self) -> Result<FunctionParameter<Raw>, ParserError> {
+        // The parameter name is optional, so first try to parse the parameter
+        // as an unnamed one. Types can span several tokens (e.g. `double
+        // precision`), so the parameter is unnamed only if the type is
+        // followed by the end of the parameter.
+        let index = self.index;
+        let data_type = self.parse_data_type()?;
+        if matches!(self.peek_token(), Some(Token::Comma | Token::RParen)) {
+            return Ok(FunctionParameter {
+                name: None,
+                data_type,
+            });
+        }
+        self.index = index;
+        Ok(FunctionParameter {
+            name: Some(self.parse_identifier()?),
+            data_type: self.parse_data_type()?,
+        })
+    }
+
+    /// Parses the body of a SQL function from `sql`, the contents of the
+    /// string literal at `pos`.
+    fn parse_function_body(&self, pos: usize, sql: &str) -> Result<Query<Raw>, ParserError> {
+        let err = |e: ParserError| self.error(pos, format!("invalid function body: {}", e.message));
+        let tokens = lexer::lex(sql).map_err(|e| err(e.into()))?;
+        let mut parser = Parser::new(sql, tokens);
+        let query = parser.parse_query().map_err(err)?;
+        let _ = parser.consume_token(&Token::Semicolon);
+        if parser.next_token().is_some() {
+            return Err(err(
+                parser.error(parser.peek_prev_pos(), "extra token after query".into())
+            ));
+        }
+        Ok(query)
+    }
+
     fn parse_create_type_list_option(&mut self) -> Result<CreateTypeListOption<Raw>, ParserError> {
         self.expect_keywords(&[ELEMENT, TYPE])?;
         let name = CreateTypeListOptionName::ElementType;
@@ -3955,6 +4334,7 @@ impl<'a> Parser<'a> {
             | ObjectType::Sink
             | ObjectType::Index
             | ObjectType::Type
+            | ObjectType::Func
             | ObjectType::Secret
             | ObjectType::Connection => {
                 let names = self.parse_comma_separated(|parser| {
@@ -3971,7 +4351,7 @@ impl<'a> Parser<'a> {
                     cascade,
                 }))
             }
-            ObjectType::Func | ObjectType::Subsource => parser_err!(
+            ObjectType::Subsource => parser_err!(
                 self,
                 self.peek_prev_pos(),
                 format!("Unsupported DROP on {object_type}")
@@ -5587,6 +5967,14 @@ impl<'a> Parser<'a> {
                     return self.parse_map();
                 }
 
+                // Anonymous enums, e.g. `enum('a', 'b')`
+                ENUM if self.peek_token() == Some(Token::LParen) => {
+                    self.expect_token(&Token::LParen)?;
+                    let labels = self.parse_comma_separated(Parser::parse_literal_string)?;
+                    self.expect_token(&Token::RParen)?;
+                    RawDataType::Enum(labels)
+                }
+
                 // Misc.
                 BOOLEAN => other(ident!("bool")),
                 BYTES => other(ident!("bytea")),
@@ -5980,8 +6368,9 @@ impl<'a> Parser<'a> {
                         options,
                         ctes: parser.parse_comma_separated(Parser::parse_cte_mut_rec)?,
                     })
+                } else if parser.parse_keyword(RECURSIVE) {
+                    CteBlock::Recursive(parser.parse_comma_separated(Parser::parse_cte)?)
                 } else {
-                    // TODO: optional RECURSIVE
                     CteBlock::Simple(parser.parse_comma_separated(Parser::parse_cte)?)
                 }
             } else {
@@ -6327,7 +6716,7 @@ impl<'a> Parser<'a> {
         };
 
         let group_by = if self.parse_keywords(&[GROUP, BY]) {
-            self.parse_comma_separated(Parser::parse_expr)?
+            self.parse_comma_separated(Parser::parse_grouping_element)?
         } else {
             vec![]
         };
@@ -6358,26 +6747,79 @@ impl<'a> Parser<'a> {
         })
     }
 
+    /// Parses an element of a `GROUP BY` clause.
+    fn parse_grouping_element(&mut self) -> Result<GroupingElement<Raw>, ParserError> {
+        if self.parse_keywords(&[GROUPING, SETS]) {
+            self.expect_token(&Token::LParen)?;
+            let elements = self.parse_comma_separated(|parser| {
+                // Within `GROUPING SETS`, a parenthesized list of expressions
+                // is a grouping set rather than a row constructor.
+                Ok(match parser.parse_grouping_element()? {
+                    GroupingElement::Expr(Expr::Row { exprs }) => GroupingElement::Set(exprs),
+                    GroupingElement::Expr(Expr::Nested(expr)) => GroupingElement::Set(vec![*expr]),
+                    element => element,
+                })
+            })?;
+            self.expect_token(&Token::RParen)?;
+            Ok(GroupingElement::GroupingSets(elements))
+        } else if self.peek_keyword(ROLLUP) && self.peek_nth_token(1) == Some(Token::LParen) {
+            self.expect_keyword(ROLLUP)?;
+            Ok(GroupingElement::Rollup(self.parse_grouping_items()?))
+        } else if self.peek_keyword(CUBE) && self.peek_nth_token(1) == Some(Token::LParen) {
+            self.expect_keyword(CUBE)?;
+            Ok(GroupingElement::Cube(self.parse_grouping_items()?))
+        } else if self.peek_token() == Some(Token::LParen)
+            && self.peek_nth_token(1) == Some(Token::RParen)
+        {
+            self.expect_token(&Token::LParen)?;
+            self.expect_token(&Token::RParen)?;
+            Ok(GroupingElement::Set(vec![]))
+        } else {
+            Ok(GroupingElement::Expr(self.parse_expr()?))
+        }
+    }
+
+    /// Parses the parenthesized items of `ROLLUP` or `CUBE`, each of which is
+    /// a single expression or a parenthesized list of expressions.
+    fn parse_grouping_items(&mut self) -> Result<Vec<Vec<Expr<Raw>>>, ParserError> {
+        self.expect_token(&Token::LParen)?;
+        let items = self.parse_comma_separated(|parser| {
+            Ok(match parser.parse_expr()? {
+                Expr::Row { exprs } => exprs,
+                Expr::Nested(expr) => vec![*expr],
+                expr => vec![expr],
+            })
+        })?;
+        self.expect_token(&Token::RParen)?;
+        Ok(items)
+    }
+
     fn parse_select_option(&mut self) -> Result<SelectOption<Raw>, ParserError> {
-        let name = match self.expect_one_of_keywords(&[EXPECTED, AGGREGATE, DISTINCT, LIMIT])? {
-            EXPECTED => {
-                self.expect_keywords(&[GROUP, SIZE])?;
-                SelectOptionName::ExpectedGroupSize
-            }
-            AGGREGATE => {
-                self.expect_keywords(&[INPUT, GROUP, SIZE])?;
-                SelectOptionName::AggregateInputGroupSize
-            }
-            DISTINCT => {
-                self.expect_keywords(&[ON, INPUT, GROUP, SIZE])?;
-                SelectOptionName::DistinctOnInputGroupSize
-            }
-            LIMIT => {
-                self.expect_keywords(&[INPUT, GROUP, SIZE])?;
-                SelectOptionName::LimitInputGroupSize
-            }
-            _ => unreachable!(),
-        };
+        let name =
+            match self.expect_one_of_keywords(&[EXPECTED, AGGREGATE, DISTINCT, LIMIT, JOIN])? {
+                EXPECTED => {
+                    self.expect_keywords(&[GROUP, SIZE])?;
+                    SelectOptionName::ExpectedGroupSize
+                }
+                AGGREGATE => {
+                    self.expect_keywords(&[INPUT, GROUP, SIZE])?;
+                    SelectOptionName::AggregateInputGroupSize
+                }
+                DISTINCT => {
+                    self.expect_keywords(&[ON, INPUT, GROUP, SIZE])?;
+                    SelectOptionName::DistinctOnInputGroupSize
+                }
+                LIMIT => {
+                    self.expect_keywords(&[INPUT, GROUP, SIZE])?;
+                    SelectOptionName::LimitInputGroupSize
+                }
+                JOIN => match self.expect_one_of_keywords(&[ORDER, IMPLEMENTATION])? {
+                    ORDER => SelectOptionName::JoinOrder,
+                    IMPLEMENTATION => SelectOptionName::JoinImplementation,
+                    _ => unreachable!(),
+                },
+                _ => unreachable!(),
+            };
         Ok(SelectOption {
             name,
             value: self.parse_optional_option_value()?,
@@ -6643,6 +7085,12 @@ impl<'a> Parser<'a> {
                     connection_name: self.parse_raw_name()?,
                 },
             ))
+        } else if self.parse_keywords(&[CREATE, FUNCTION]) {
+            Ok(ShowStatement::ShowCreateFunction(
+                ShowCreateFunctionStatement {
+                    function_name: self.parse_raw_name()?,
+                },
+            ))
         } else {
             let variable = if self.parse_keywords(&[TRANSACTION, ISOLATION, LEVEL]) {
                 ident!("transaction_isolation")
@@ -6964,15 +7412,48 @@ impl<'a> Parser<'a> {
         } else {
             InsertSource::Query(self.parse_query()?)
         };
+        let on_conflict = if self.parse_keywords(&[ON, CONFLICT]) {
+            Some(self.parse_on_conflict()?)
+        } else {
+            None
+        };
         let returning = self.parse_returning()?;
         Ok(Statement::Insert(InsertStatement {
             table_name,
             columns,
             source,
+            on_conflict,
             returning,
         }))
     }
 
+    fn parse_on_conflict(&mut self) -> Result<OnConflict<Raw>, ParserError> {
+        let target = if self.peek_token() == Some(Token::LParen) {
+            Some(self.parse_parenthesized_column_list(Mandatory)?)
+        } else {
+            None
+        };
+        self.expect_keyword(DO)?;
+        let action = match self.expect_one_of_keywords(&[NOTHING, UPDATE])? {
+            NOTHING => OnConflictAction::DoNothing,
+            UPDATE => {
+                self.expect_keyword(SET)?;
+                let assignments = self.parse_comma_separated(Parser::parse_assignment)?;
+                let selection = if self.parse_keyword(WHERE) {
+                    Some(self.parse_expr()?)
+                } else {
+                    None
+                };
+                OnConflictAction::DoUpdate {
+                    assignments,
+                    selection,
+                }
+            }
+            _ => unreachable!(),
+        };
+        Ok(OnConflict { target, action })
+    }
+
     fn parse_returning(&mut self) -> Result<Vec<SelectItem<Raw>>, ParserError> {
         Ok(if self.parse_keyword(RETURNING) {
             self.parse_comma_separated(Parser::parse_select_item)?
@@ -7035,7 +7516,11 @@ impl<'a> Parser<'a> {
                 vec![]
             };
             self.expect_token(&Token::RParen)?;
-            Ok(FunctionArgs::Args { args, order_by })
+            Ok(FunctionArgs::Args {
+                args,
+                order_by,
+                within_group: false,
+            })
         }
     }
 
@@ -7302,6 +7787,9 @@ impl<'a> Parser<'a> {
         } else if self.parse_keywords(&[FILTER, PUSHDOWN]) {
             self.parse_explain_pushdown()
                 .map_parser_err(StatementKind::ExplainPushdown)
+        } else if self.parse_keyword(ANALYZE) {
+            self.parse_explain_analyze()
+                .map_parser_err(StatementKind::ExplainAnalyze)
         } else if self.peek_keyword(KEY) || self.peek_keyword(VALUE) {
             self.parse_explain_schema()
                 .map_parser_err(StatementKind::ExplainSinkSchema)
@@ -7488,6 +7976,49 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    /// Parse an `EXPLAIN ANALYZE` statement, assuming that the `EXPLAIN
+    /// ANALYZE` tokens have already been consumed.
+    fn parse_explain_analyze(&mut self) -> Result<Statement<Raw>, ParserError> {
+        let properties = if self.peek_keyword(MEMORY) || self.peek_keyword(CPU) {
+            self.parse_comma_separated(|parser| {
+                match parser.expect_one_of_keywords(&[MEMORY, CPU])? {
+                    MEMORY => Ok(ExplainAnalyzeProperty::Memory),
+                    CPU => Ok(ExplainAnalyzeProperty::Cpu),
+                    _ => unreachable!(),
+                }
+            })?
+        } else {
+            vec![]
+        };
+
+        let format = if self.parse_keyword(AS) {
+            match self.expect_one_of_keywords(&[TEXT, JSON])? {
+                TEXT => ExplainFormat::Text,
+                JSON => ExplainFormat::Json,
+                _ => unreachable!(),
+            }
+        } else {
+            ExplainFormat::Text
+        };
+
+        self.expect_keyword(FOR)?;
+
+        let explainee = match self.expect_one_of_keywords(&[INDEX, MATERIALIZED])? {
+            INDEX => Explainee::Index(self.parse_raw_name()?),
+            MATERIALIZED => {
+                self.expect_keyword(VIEW)?;
+                Explainee::MaterializedView(self.parse_raw_name()?)
+            }
+            _ => unreachable!(),
+        };
+
+        Ok(Statement::ExplainAnalyze(ExplainAnalyzeStatement {
+            properties,
+            format,
+            explainee,
+        }))
+    }
+
     /// Parse an `EXPLAIN TIMESTAMP` statement, assuming that the `EXPLAIN
     /// TIMESTAMP` tokens have already been consumed.
     fn parse_explain_timestamp(&mut self) -> Result<Statement<Raw>, ParserError> {
@@ -216,6 +216,9 @@ pub(crate) fn doc_insert<T: AstInfo>(v: &InsertStatement<T>) -> RcDoc {
         _ => doc_display(&v.source, "insert source"),
     };
     let mut doc = intersperse_line_nest([intersperse_line_nest(first), sources]);
+    if let Some(on_conflict) = &v.on_conflict {
+        doc = nest(doc, doc_display_pass(on_conflict));
+    }
     if !v.returning.is_empty() {
         doc = nest(
             doc,
@@ -256,6 +259,9 @@ fn doc_query<T: AstInfo>(v: &Query<T>) -> RcDoc {
     if !v.ctes.is_empty() {
         match &v.ctes {
             CteBlock::Simple(ctes) => docs.push(title_comma_separate("WITH", doc_cte, ctes)),
+            CteBlock::Recursive(ctes) => {
+                docs.push(title_comma_separate("WITH RECURSIVE", doc_cte, ctes))
+            }
             CteBlock::MutuallyRecursive(mutrec) => {
                 let mut doc = RcDoc::text("WITH MUTUALLY RECURSIVE");
                 if !mutrec.options.is_empty() {
@@ -447,7 +453,11 @@ fn doc_select<T: AstInfo>(v: &Select<T>) -> RcDoc {
         docs.push(nest_title("WHERE", doc_expr(selection)));
     }
     if !v.group_by.is_empty() {
-        docs.push(title_comma_separate("GROUP BY", doc_expr, &v.group_by));
+        docs.push(title_comma_separate(
+            "GROUP BY",
+            doc_grouping_element,
+            &v.group_by,
+        ));
     }
     if let Some(having) = &v.having {
         docs.push(nest_title("HAVING", doc_expr(having)));
@@ -525,6 +535,7 @@ pub fn doc_expr<T: AstInfo>(v: &Expr<T>) -> RcDoc {
                     | Expr::Collate { .. }
                     | Expr::HomogenizingFunction { .. }
                     | Expr::NullIf { .. }
+                    | Expr::Grouping(_)
             );
             let mut doc = doc_expr(expr);
             if needs_wrap {
@@ -616,6 +627,7 @@ pub fn doc_expr<T: AstInfo>(v: &Expr<T>) -> RcDoc {
         Expr::HomogenizingFunction { function, exprs } => {
             bracket(format!("{function}("), comma_separate(doc_expr, exprs), ")")
         }
+        Expr::Grouping(exprs) => bracket("GROUPING(", comma_separate(doc_expr, exprs), ")"),
         Expr::ArraySubquery(s) => bracket("ARRAY(", doc_query(s), ")"),
         Expr::ListSubquery(s) => bracket("LIST(", doc_query(s), ")"),
         Expr::Array(exprs) => bracket("ARRAY[", comma_separate(doc_expr, exprs), "]"),
@@ -625,10 +637,17 @@ pub fn doc_expr<T: AstInfo>(v: &Expr<T>) -> RcDoc {
     .group()
 }
 
+fn doc_grouping_element<T: AstInfo>(v: &GroupingElement<T>) -> RcDoc {
+    match v {
+        GroupingElement::Expr(expr) => doc_expr(expr),
+        _ => doc_display_pass(v),
+    }
+}
+
 fn doc_function<T: AstInfo>(v: &Function<T>) -> RcDoc {
     match &v.args {
         FunctionArgs::Star => doc_display_pass(v),
-        FunctionArgs::Args { args, order_by } => {
+        FunctionArgs::Args { args, order_by, .. } => {
             if args.is_empty() {
                 // Nullary, don't allow newline between parens, so just delegate.
                 return doc_display_pass(v);
@@ -0,0 +1,445 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Helpers for reading SQL Server change data capture (CDC) tables.
+//!
+//! When CDC is enabled for a table, the SQL Server Agent asynchronously copies
+//! every change from the transaction log into a change table associated with
+//! a "capture instance". Each change is tagged with the log sequence number
+//! (LSN) of the commit record of the transaction that made it, which gives us a
+//! total order over all changes in the database.
+//!
+//! See: <https://learn.microsoft.com/en-us/sql/relational-databases/track-changes/about-change-data-capture-sql-server>
+
+use std::fmt;
+use std::str::FromStr;
+
+use futures::stream::{BoxStream, StreamExt};
+use itertools::Itertools;
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+use tiberius::{Client, Row};
+use timely::order::{PartialOrder, TotalOrder};
+use timely::progress::timestamp::{PathSummary, Refines, Timestamp};
+use tokio::net::TcpStream;
+use tokio_util::compat::Compat;
+
+use crate::{quote_identifier, SqlServerError, SqlServerTableDesc};
+
+/// A SQL Server log sequence number.
+///
+/// LSNs are 10 byte values made up of the sequence number of the virtual log
+/// file (VLF), the offset of the log block within that file and the slot of
+/// the record within that block. Their byte representation, as returned by
+/// the CDC functions, compares in the same order as the LSNs themselves.
+///
+/// When used in a frontier, an LSN represents the next LSN that we might see a
+/// change for. When used as a timestamp, it represents the commit LSN of the
+/// transaction that produced a change.
+#[derive(
+    Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize, Arbitrary,
+)]
+pub struct Lsn {
+    /// The sequence number of the virtual log file.
+    pub vlf_id: u32,
+    /// The offset of the log block within the virtual log file.
+    pub block_id: u32,
+    /// The slot of the log record within the log block.
+    pub record_id: u16,
+}
+
+/// An error parsing an [`Lsn`].
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("invalid LSN '{0}'")]
+pub struct LsnParseError(String);
+
+impl Lsn {
+    /// The number of bytes in the binary representation of an LSN.
+    pub const SIZE: usize = 10;
+
+    /// The largest possible LSN.
+    pub const MAX: Lsn = Lsn {
+        vlf_id: u32::MAX,
+        block_id: u32::MAX,
+        record_id: u16::MAX,
+    };
+
+    /// Decodes an LSN from its `binary(10)` representation.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Lsn, LsnParseError> {
+        let bytes: [u8; Self::SIZE] = bytes
+            .try_into()
+            .map_err(|_| LsnParseError(format!("{bytes:02X?}")))?;
+        let [a, b, c, d, e, f, g, h, i, j] = bytes;
+        Ok(Lsn {
+            vlf_id: u32::from_be_bytes([a, b, c, d]),
+            block_id: u32::from_be_bytes([e, f, g, h]),
+            record_id: u16::from_be_bytes([i, j]),
+        })
+    }
+
+    /// Encodes this LSN into its `binary(10)` representation.
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0; Self::SIZE];
+        bytes[0..4].copy_from_slice(&self.vlf_id.to_be_bytes());
+        bytes[4..8].copy_from_slice(&self.block_id.to_be_bytes());
+        bytes[8..10].copy_from_slice(&self.record_id.to_be_bytes());
+        bytes
+    }
+
+    /// Returns the smallest LSN that is greater than this one, or `None` if
+    /// this is [`Lsn::MAX`].
+    ///
+    /// This mirrors `sys.fn_cdc_increment_lsn`.
+    pub fn increment(&self) -> Option<Lsn> {
+        if let Some(record_id) = self.record_id.checked_add(1) {
+            return Some(Lsn { record_id, ..*self });
+        }
+        if let Some(block_id) = self.block_id.checked_add(1) {
+            return Some(Lsn {
+                vlf_id: self.vlf_id,
+                block_id,
+                record_id: 0,
+            });
+        }
+        let vlf_id = self.vlf_id.checked_add(1)?;
+        Some(Lsn {
+            vlf_id,
+            block_id: 0,
+            record_id: 0,
+        })
+    }
+
+    /// Returns a lossy `u64` approximation of this LSN that preserves its
+    /// order, for use in progress statistics.
+    pub fn abbreviate(&self) -> u64 {
+        (u64::from(self.vlf_id) << 32) | u64::from(self.block_id)
+    }
+}
+
+impl fmt::Display for Lsn {
+    /// Formats the LSN the way SQL Server does in its dynamic management
+    /// views, e.g. `0000002A:00000CF8:0003`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:08X}:{:08X}:{:04X}",
+            self.vlf_id, self.block_id, self.record_id
+        )
+    }
+}
+
+impl FromStr for Lsn {
+    type Err = LsnParseError;
+
+    /// Parses an LSN in the format produced by its `Display` implementation.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || LsnParseError(s.to_string());
+        let (vlf_id, block_id, record_id) = s.split(':').collect_tuple().ok_or_else(err)?;
+        Ok(Lsn {
+            vlf_id: u32::from_str_radix(vlf_id, 16).map_err(|_| err())?,
+            block_id: u32::from_str_radix(block_id, 16).map_err(|_| err())?,
+            record_id: u16::from_str_radix(record_id, 16).map_err(|_| err())?,
+        })
+    }
+}
+
+impl Timestamp for Lsn {
+    // No need to describe complex summaries
+    type Summary = ();
+
+    fn minimum() -> Self {
+        Lsn {
+            vlf_id: 0,
+            block_id: 0,
+            record_id: 0,
+        }
+    }
+}
+
+impl TotalOrder for Lsn {}
+
+impl PartialOrder for Lsn {
+    fn less_equal(&self, other: &Self) -> bool {
+        self <= other
+    }
+}
+
+impl PathSummary<Lsn> for () {
+    fn results_in(&self, src: &Lsn) -> Option<Lsn> {
+        Some(*src)
+    }
+
+    fn followed_by(&self, _other: &Self) -> Option<Self> {
+        Some(())
+    }
+}
+
+impl Refines<()> for Lsn {
+    fn to_inner(_other: ()) -> Self {
+        Self::minimum()
+    }
+
+    fn to_outer(self) -> () {}
+
+    fn summarize(_path: Self::Summary) -> <() as Timestamp>::Summary {}
+}
+
+/// The kind of change recorded by a row of a change table, as reported by its
+/// `__$operation` column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CdcOperation {
+    Delete,
+    Insert,
+    /// The contents of a row before an update.
+    UpdateOld,
+    /// The contents of a row after an update.
+    UpdateNew,
+}
+
+impl CdcOperation {
+    fn from_code(code: i32) -> Result<Self, SqlServerError> {
+        match code {
+            1 => Ok(CdcOperation::Delete),
+            2 => Ok(CdcOperation::Insert),
+            3 => Ok(CdcOperation::UpdateOld),
+            4 => Ok(CdcOperation::UpdateNew),
+            other => Err(anyhow::anyhow!("unknown CDC operation code {other}").into()),
+        }
+    }
+
+    /// The differential `diff` that this operation corresponds to.
+    pub fn diff(&self) -> i64 {
+        match self {
+            CdcOperation::Delete | CdcOperation::UpdateOld => -1,
+            CdcOperation::Insert | CdcOperation::UpdateNew => 1,
+        }
+    }
+}
+
+/// A single row of a change table.
+#[derive(Debug)]
+pub struct CdcChange {
+    /// The commit LSN of the transaction that made the change.
+    pub lsn: Lsn,
+    pub operation: CdcOperation,
+    /// The captured columns of the changed row, starting at
+    /// [`CdcChange::DATA_OFFSET`].
+    pub row: Row,
+}
+
+impl CdcChange {
+    /// The index of the first captured column in [`CdcChange::row`].
+    pub const DATA_OFFSET: usize = 2;
+}
+
+fn lsn_from_row(row: &Row, idx: usize) -> Result<Option<Lsn>, SqlServerError> {
+    match row.try_get::<&[u8], _>(idx)? {
+        None => Ok(None),
+        Some(bytes) => Ok(Some(Lsn::from_bytes(bytes)?)),
+    }
+}
+
+/// Returns the largest LSN that the capture process has copied into the
+/// change tables of the database, or `None` if nothing has been captured yet.
+///
+/// Every change with an LSN at or below this value is present in the change
+/// tables, which makes it a safe upper bound for reading changes.
+pub async fn get_max_lsn(
+    client: &mut Client<Compat<TcpStream>>,
+) -> Result<Option<Lsn>, SqlServerError> {
+    let row = client
+        .simple_query("SELECT sys.fn_cdc_get_max_lsn()")
+        .await?
+        .into_row()
+        .await?;
+    match row {
+        Some(row) => lsn_from_row(&row, 0),
+        None => Ok(None),
+    }
+}
+
+/// Returns the smallest LSN still available in the change table of
+/// `capture_instance`, or `None` if the capture instance does not exist.
+///
+/// Changes below this LSN have been removed by the CDC cleanup job.
+pub async fn get_min_lsn(
+    client: &mut Client<Compat<TcpStream>>,
+    capture_instance: &str,
+) -> Result<Option<Lsn>, SqlServerError> {
+    let row = client
+        .query("SELECT sys.fn_cdc_get_min_lsn(@P1)", &[&capture_instance])
+        .await?
+        .into_row()
+        .await?;
+    match row {
+        // `fn_cdc_get_min_lsn` returns an all-zero LSN for unknown capture
+        // instances.
+        Some(row) => Ok(lsn_from_row(&row, 0)?.filter(|lsn| *lsn != Lsn::minimum())),
+        None => Ok(None),
+    }
+}
+
+/// Returns the LSN of the last record written to the transaction log of the
+/// current database.
+///
+/// Unlike [`get_max_lsn`], this does not depend on the progress of the
+/// capture process. Any transaction that committed before this function is
+/// called has a commit LSN at or below the returned value.
+pub async fn get_log_end_lsn(
+    client: &mut Client<Compat<TcpStream>>,
+) -> Result<Lsn, SqlServerError> {
+    let row = client
+        .simple_query("SELECT log_end_lsn FROM sys.dm_db_log_stats(DB_ID())")
+        .await?
+        .into_row()
+        .await?;
+    let lsn = row
+        .as_ref()
+        .and_then(|row| row.get::<&str, _>(0))
+        .ok_or_else(|| anyhow::anyhow!("sys.dm_db_log_stats did not return a log end LSN"))?;
+    Ok(lsn.parse()?)
+}
+
+/// Returns whether change data capture is enabled for the current database.
+pub async fn is_cdc_enabled(
+    client: &mut Client<Compat<TcpStream>>,
+) -> Result<bool, SqlServerError> {
+    let row = client
+        .simple_query("SELECT is_cdc_enabled FROM sys.databases WHERE database_id = DB_ID()")
+        .await?
+        .into_row()
+        .await?;
+    Ok(row.and_then(|row| row.get::<bool, _>(0)).unwrap_or(false))
+}
+
+/// Streams all changes to `table` with a commit LSN in `[from, to]`, in the
+/// order in which they were made.
+///
+/// Updates are reported as an [`CdcOperation::UpdateOld`] change followed by
+/// an [`CdcOperation::UpdateNew`] change. Callers must ensure that `from` is
+/// at or above the minimum LSN of the table's capture instance, otherwise
+/// SQL Server reports an error.
+pub async fn get_changes<'a>(
+    client: &'a mut Client<Compat<TcpStream>>,
+    table: &SqlServerTableDesc,
+    from: Lsn,
+    to: Lsn,
+) -> Result<BoxStream<'a, Result<CdcChange, SqlServerError>>, SqlServerError> {
+    let function = quote_identifier(&format!(
+        "fn_cdc_get_all_changes_{}",
+        table.capture_instance
+    ));
+    let columns = table
+        .columns
+        .iter()
+        .map(|c| quote_identifier(&c.name))
+        .join(", ");
+    let query = format!(
+        "SELECT __$start_lsn, __$operation, {columns} \
+         FROM cdc.{function}(@P1, @P2, N'all update old') \
+         ORDER BY __$start_lsn, __$seqval, __$operation"
+    );
+    let from = from.to_bytes().to_vec();
+    let to = to.to_bytes().to_vec();
+    let stream = client.query(query, &[&from, &to]).await?.into_row_stream();
+
+    Ok(stream
+        .map(|row| {
+            let row = row?;
+            let lsn = lsn_from_row(&row, 0)?
+                .ok_or_else(|| anyhow::anyhow!("change row without __$start_lsn"))?;
+            let operation = row
+                .try_get::<i32, _>(1)?
+                .ok_or_else(|| anyhow::anyhow!("change row without __$operation"))?;
+            Ok(CdcChange {
+                lsn,
+                operation: CdcOperation::from_code(operation)?,
+                row,
+            })
+        })
+        .boxed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn lsn_roundtrip() {
+        let lsn = Lsn {
+            vlf_id: 0x2A,
+            block_id: 0xCF8,
+            record_id: 3,
+        };
+        let bytes = lsn.to_bytes();
+        assert_eq!(bytes, [0, 0, 0, 0x2A, 0, 0, 0x0C, 0xF8, 0, 3]);
+        assert_eq!(Lsn::from_bytes(&bytes).unwrap(), lsn);
+        assert_eq!(lsn.to_string(), "0000002A:00000CF8:0003");
+        assert_eq!("0000002A:00000CF8:0003".parse::<Lsn>().unwrap(), lsn);
+
+        assert!(Lsn::from_bytes(&bytes[1..]).is_err());
+        assert!("0000002A:00000CF8".parse::<Lsn>().is_err());
+    }
+
+    #[mz_ore::test]
+    fn lsn_order_matches_bytes() {
+        let lsns = [
+            Lsn {
+                vlf_id: 1,
+                block_id: 0,
+                record_id: u16::MAX,
+            },
+            Lsn {
+                vlf_id: 1,
+                block_id: 1,
+                record_id: 0,
+            },
+            Lsn {
+                vlf_id: 2,
+                block_id: 0,
+                record_id: 0,
+            },
+        ];
+        for (a, b) in lsns.iter().tuple_windows() {
+            assert!(a < b);
+            assert!(a.to_bytes() < b.to_bytes());
+            assert!(a.abbreviate() <= b.abbreviate());
+        }
+    }
+
+    #[mz_ore::test]
+    fn lsn_increment() {
+        let lsn = Lsn {
+            vlf_id: 1,
+            block_id: 7,
+            record_id: 2,
+        };
+        assert_eq!(
+            lsn.increment(),
+            Some(Lsn {
+                record_id: 3,
+                ..lsn
+            })
+        );
+        let lsn = Lsn {
+            vlf_id: 1,
+            block_id: u32::MAX,
+            record_id: u16::MAX,
+        };
+        assert_eq!(
+            lsn.increment(),
+            Some(Lsn {
+                vlf_id: 2,
+                block_id: 0,
+                record_id: 0,
+            })
+        );
+        assert_eq!(Lsn::MAX.increment(), None);
+    }
+}
@@ -0,0 +1,177 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use itertools::{EitherOrBoth, Itertools};
+use tiberius::{time, ColumnData};
+
+use mz_repr::adt::date::Date;
+use mz_repr::adt::numeric::Numeric;
+use mz_repr::adt::timestamp::CheckedTimestamp;
+use mz_repr::{Datum, Row};
+
+use crate::{SqlServerColumnDesc, SqlServerError, SqlServerTableDesc};
+
+/// Packs the columns of `row`, starting at index `offset`, into
+/// `row_container` according to `table_desc`.
+pub fn pack_sql_server_row(
+    row_container: &mut Row,
+    row: tiberius::Row,
+    offset: usize,
+    table_desc: &SqlServerTableDesc,
+) -> Result<Row, SqlServerError> {
+    let mut packer = row_container.packer();
+
+    for values in table_desc
+        .columns
+        .iter()
+        .zip_longest(row.into_iter().skip(offset))
+    {
+        let (col_desc, value) = match values {
+            EitherOrBoth::Both(col_desc, value) => (col_desc, value),
+            EitherOrBoth::Left(col_desc) => Err(SqlServerError::ValueDecodeError {
+                column_name: col_desc.name.clone(),
+                qualified_table_name: format!("{}.{}", table_desc.schema_name, table_desc.name),
+                error: "extra column description".to_string(),
+            })?,
+            EitherOrBoth::Right(_) => Err(SqlServerError::ValueDecodeError {
+                column_name: "<unknown>".to_string(),
+                qualified_table_name: format!("{}.{}", table_desc.schema_name, table_desc.name),
+                error: "received more columns than described".to_string(),
+            })?,
+        };
+        match val_to_datum(&value, col_desc) {
+            Ok(datum) => packer.push(datum),
+            Err(err) => Err(SqlServerError::ValueDecodeError {
+                column_name: col_desc.name.clone(),
+                qualified_table_name: format!("{}.{}", table_desc.schema_name, table_desc.name),
+                error: err.to_string(),
+            })?,
+        }
+    }
+
+    Ok(row_container.clone())
+}
+
+fn val_to_datum<'a>(
+    value: &'a ColumnData<'static>,
+    col_desc: &SqlServerColumnDesc,
+) -> Result<Datum<'a>, anyhow::Error> {
+    let datum = match value {
+        ColumnData::Bit(Some(v)) => Datum::from(*v),
+        ColumnData::U8(Some(v)) => Datum::from(i16::from(*v)),
+        ColumnData::I16(Some(v)) => Datum::from(*v),
+        ColumnData::I32(Some(v)) => Datum::from(*v),
+        ColumnData::I64(Some(v)) => Datum::from(*v),
+        ColumnData::F32(Some(v)) => Datum::from(*v),
+        ColumnData::F64(Some(v)) => Datum::from(*v),
+        ColumnData::String(Some(v)) => Datum::from(v.as_ref()),
+        ColumnData::Binary(Some(v)) => Datum::from(v.as_ref()),
+        ColumnData::Guid(Some(v)) => Datum::from(*v),
+        ColumnData::Numeric(Some(v)) => {
+            // `tiberius::numeric::Numeric` displays with exactly its scale,
+            // which round trips through our own parser.
+            Datum::from(Numeric::from_str(&v.to_string())?)
+        }
+        ColumnData::Date(Some(v)) => Datum::from(Date::try_from(decode_date(v)?)?),
+        ColumnData::Time(Some(v)) => Datum::from(decode_time(v)?),
+        ColumnData::DateTime2(Some(v)) => {
+            let ts = NaiveDateTime::new(decode_date(&v.date())?, decode_time(&v.time())?);
+            Datum::from(CheckedTimestamp::try_from(ts)?)
+        }
+        ColumnData::DateTimeOffset(Some(v)) => {
+            // The date and time of a `datetimeoffset` are transmitted in UTC,
+            // alongside the offset of the original value.
+            let dt2 = v.datetime2();
+            let ts = NaiveDateTime::new(decode_date(&dt2.date())?, decode_time(&dt2.time())?);
+            Datum::from(CheckedTimestamp::try_from(DateTime::<Utc>::from_utc(
+                ts, Utc,
+            ))?)
+        }
+        ColumnData::DateTime(Some(v)) => {
+            // `datetime` counts days since 1900-01-01 and the time of day in
+            // units of 1/300th of a second.
+            let nanos = i64::from(v.seconds_fragments()) * 1_000_000_000 / 300;
+            let ts = sql_server_epoch()
+                + Duration::days(i64::from(v.days()))
+                + Duration::microseconds(nanos / 1_000);
+            Datum::from(CheckedTimestamp::try_from(ts)?)
+        }
+        ColumnData::SmallDateTime(Some(v)) => {
+            // `smalldatetime` counts days since 1900-01-01 and the time of
+            // day in minutes.
+            let ts = sql_server_epoch()
+                + Duration::days(i64::from(v.days()))
+                + Duration::minutes(i64::from(v.seconds_fragments()));
+            Datum::from(CheckedTimestamp::try_from(ts)?)
+        }
+        ColumnData::Bit(None)
+        | ColumnData::U8(None)
+        | ColumnData::I16(None)
+        | ColumnData::I32(None)
+        | ColumnData::I64(None)
+        | ColumnData::F32(None)
+        | ColumnData::F64(None)
+        | ColumnData::String(None)
+        | ColumnData::Binary(None)
+        | ColumnData::Guid(None)
+        | ColumnData::Numeric(None)
+        | ColumnData::Date(None)
+        | ColumnData::Time(None)
+        | ColumnData::DateTime2(None)
+        | ColumnData::DateTimeOffset(None)
+        | ColumnData::DateTime(None)
+        | ColumnData::SmallDateTime(None) => {
+            if !col_desc.column_type.nullable {
+                anyhow::bail!("received a null value in a non-null column");
+            }
+            Datum::Null
+        }
+        value => anyhow::bail!(
+            "received unexpected value for type {}: {:?}",
+            col_desc.data_type,
+            value
+        ),
+    };
+    Ok(datum)
+}
+
+/// The epoch of the `datetime` and `smalldatetime` types.
+fn sql_server_epoch() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(1900, 1, 1)
+        .expect("valid date")
+        .and_hms_opt(0, 0, 0)
+        .expect("valid time")
+}
+
+/// Decodes a date, which SQL Server represents as the number of days since
+/// 0001-01-01.
+fn decode_date(date: &time::Date) -> Result<NaiveDate, anyhow::Error> {
+    let days = i32::try_from(date.days())?;
+    NaiveDate::from_num_days_from_ce_opt(days + 1)
+        .ok_or_else(|| anyhow::anyhow!("date out of range: {} days", date.days()))
+}
+
+/// Decodes a time of day, which SQL Server represents as a number of
+/// increments of `10^-scale` seconds since midnight.
+///
+/// SQL Server supports up to 100ns precision, while Materialize only supports
+/// microseconds, so any additional precision is truncated.
+fn decode_time(time: &time::Time) -> Result<NaiveTime, anyhow::Error> {
+    let nanos_per_increment = 10u64
+        .checked_pow(9u32.saturating_sub(u32::from(time.scale())))
+        .ok_or_else(|| anyhow::anyhow!("invalid time scale {}", time.scale()))?;
+    let nanos = time.increments() * nanos_per_increment;
+    let secs = u32::try_from(nanos / 1_000_000_000)?;
+    let micros = u32::try_from((nanos % 1_000_000_000) / 1_000)?;
+    NaiveTime::from_num_seconds_from_midnight_opt(secs, micros * 1_000)
+        .ok_or_else(|| anyhow::anyhow!("time out of range: {} increments", time.increments()))
+}
@@ -0,0 +1,210 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::BTreeSet;
+
+use anyhow::bail;
+use proptest::prelude::any;
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+use mz_proto::{IntoRustIfSome, RustType, TryFromProtoError};
+use mz_repr::ColumnType;
+
+include!(concat!(env!("OUT_DIR"), "/mz_sql_server_util.rs"));
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub struct SqlServerTableDesc {
+    /// The schema the table belongs to.
+    pub schema_name: String,
+    /// The name of the table.
+    pub name: String,
+    /// The CDC capture instance whose change table tracks this table.
+    ///
+    /// A table can have at most two capture instances at a time. We always
+    /// track the most recently created one.
+    pub capture_instance: String,
+    /// Columns for the table, in the order they appear in the change table.
+    ///
+    /// Only columns captured by `capture_instance` are present.
+    #[proptest(strategy = "proptest::collection::vec(any::<SqlServerColumnDesc>(), 0..4)")]
+    pub columns: Vec<SqlServerColumnDesc>,
+    /// Applicable keys for this table (i.e. primary key and unique
+    /// constraints) whose columns are all captured.
+    #[proptest(strategy = "proptest::collection::btree_set(any::<SqlServerKeyDesc>(), 0..4)")]
+    pub keys: BTreeSet<SqlServerKeyDesc>,
+}
+
+impl RustType<ProtoSqlServerTableDesc> for SqlServerTableDesc {
+    fn into_proto(&self) -> ProtoSqlServerTableDesc {
+        ProtoSqlServerTableDesc {
+            schema_name: self.schema_name.clone(),
+            name: self.name.clone(),
+            capture_instance: self.capture_instance.clone(),
+            columns: self.columns.iter().map(|c| c.into_proto()).collect(),
+            keys: self.keys.iter().map(|c| c.into_proto()).collect(),
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerTableDesc) -> Result<Self, TryFromProtoError> {
+        Ok(Self {
+            schema_name: proto.schema_name,
+            name: proto.name,
+            capture_instance: proto.capture_instance,
+            columns: proto
+                .columns
+                .into_iter()
+                .map(SqlServerColumnDesc::from_proto)
+                .collect::<Result<_, _>>()?,
+            keys: proto
+                .keys
+                .into_iter()
+                .map(SqlServerKeyDesc::from_proto)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl SqlServerTableDesc {
+    /// Determines if two `SqlServerTableDesc` are compatible with one another
+    /// in a way that Materialize can handle.
+    ///
+    /// Change tables have a fixed shape for the lifetime of their capture
+    /// instance, so this currently means that the values are equal except
+    /// that `other` may have keys that `self` does not know about.
+    pub fn determine_compatibility(&self, other: &SqlServerTableDesc) -> Result<(), anyhow::Error> {
+        if self == other {
+            return Ok(());
+        }
+
+        if self.schema_name != other.schema_name || self.name != other.name {
+            bail!(
+                "table name mismatch: self: {}.{}, other: {}.{}",
+                self.schema_name,
+                self.name,
+                other.schema_name,
+                other.name
+            );
+        }
+
+        if self.capture_instance != other.capture_instance {
+            bail!(
+                "capture instance for table {}.{} changed from {} to {}",
+                self.schema_name,
+                self.name,
+                self.capture_instance,
+                other.capture_instance
+            );
+        }
+
+        if self.columns.len() != other.columns.len() {
+            bail!(
+                "columns in table {}.{} have been altered",
+                self.schema_name,
+                self.name
+            );
+        }
+        for (self_column, other_column) in self.columns.iter().zip(other.columns.iter()) {
+            if !self_column.is_compatible(other_column) {
+                bail!(
+                    "column {} in table {}.{} has been altered",
+                    self_column.name,
+                    self.schema_name,
+                    self.name
+                );
+            }
+        }
+
+        if self.keys.difference(&other.keys).next().is_some() {
+            bail!(
+                "keys in table {}.{} have been altered: self: {:?}, other: {:?}",
+                self.schema_name,
+                self.name,
+                self.keys,
+                other.keys
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub struct SqlServerColumnDesc {
+    /// The name of the column.
+    pub name: String,
+    /// The intended data type of this column within Materialize.
+    pub column_type: ColumnType,
+    /// The name of the column's data type in SQL Server, e.g. `nvarchar`.
+    pub data_type: String,
+}
+
+impl SqlServerColumnDesc {
+    /// Determines if two `SqlServerColumnDesc` are compatible with one another
+    /// in a way that Materialize can handle.
+    fn is_compatible(&self, other: &SqlServerColumnDesc) -> bool {
+        self.name == other.name
+            && self.data_type == other.data_type
+            && self.column_type.scalar_type == other.column_type.scalar_type
+            // Columns are compatible if:
+            // - self is nullable; introducing a not null constraint doesn't
+            //   change this column's behavior.
+            // - self and other are both not nullable
+            && (self.column_type.nullable || !other.column_type.nullable)
+    }
+}
+
+impl RustType<ProtoSqlServerColumnDesc> for SqlServerColumnDesc {
+    fn into_proto(&self) -> ProtoSqlServerColumnDesc {
+        ProtoSqlServerColumnDesc {
+            name: self.name.clone(),
+            column_type: Some(self.column_type.into_proto()),
+            data_type: self.data_type.clone(),
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerColumnDesc) -> Result<Self, TryFromProtoError> {
+        Ok(Self {
+            name: proto.name,
+            column_type: proto
+                .column_type
+                .into_rust_if_some("ProtoSqlServerColumnDesc::column_type")?,
+            data_type: proto.data_type,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Ord, PartialOrd, Arbitrary)]
+pub struct SqlServerKeyDesc {
+    /// The name of the index or constraint.
+    pub name: String,
+    /// Whether or not this key is the primary key.
+    pub is_primary: bool,
+    /// The columns that make up the key.
+    #[proptest(strategy = "proptest::collection::vec(any::<String>(), 0..4)")]
+    pub columns: Vec<String>,
+}
+
+impl RustType<ProtoSqlServerKeyDesc> for SqlServerKeyDesc {
+    fn into_proto(&self) -> ProtoSqlServerKeyDesc {
+        ProtoSqlServerKeyDesc {
+            name: self.name.clone(),
+            is_primary: self.is_primary,
+            columns: self.columns.clone(),
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerKeyDesc) -> Result<Self, TryFromProtoError> {
+        Ok(Self {
+            name: proto.name,
+            is_primary: proto.is_primary,
+            columns: proto.columns,
+        })
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! SQL Server utility library.
+
+mod tunnel;
+pub use tunnel::{Config, SqlServerClient, TunnelConfig};
+
+mod desc;
+pub use desc::{
+    ProtoSqlServerColumnDesc, ProtoSqlServerKeyDesc, ProtoSqlServerTableDesc, SqlServerColumnDesc,
+    SqlServerKeyDesc, SqlServerTableDesc,
+};
+
+pub mod cdc;
+pub use cdc::{
+    get_changes, get_log_end_lsn, get_max_lsn, get_min_lsn, is_cdc_enabled, CdcChange,
+    CdcOperation, Lsn, LsnParseError,
+};
+
+pub mod schemas;
+pub use schemas::{schema_info, SchemaRequest};
+
+pub mod decoding;
+pub use decoding::pack_sql_server_row;
+
+/// Quotes `ident` as a SQL Server delimited identifier, e.g. `[my table]`.
+pub fn quote_identifier(ident: &str) -> String {
+    format!("[{}]", ident.replace(']', "]]"))
+}
+
+#[derive(Debug, Clone)]
+pub struct UnsupportedDataType {
+    pub column_type: String,
+    pub qualified_table_name: String,
+    pub column_name: String,
+}
+
+impl std::fmt::Display for UnsupportedDataType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "'{}.{}' of type '{}'",
+            self.qualified_table_name, self.column_name, self.column_type
+        )
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SqlServerError {
+    #[error("error creating sql server connection with config: {0}")]
+    InvalidClientConfig(String),
+    #[error("error setting up ssh: {0}")]
+    Ssh(#[source] anyhow::Error),
+    #[error("error decoding value for '{qualified_table_name}' column '{column_name}': {error}")]
+    ValueDecodeError {
+        column_name: String,
+        qualified_table_name: String,
+        error: String,
+    },
+    #[error("unsupported data types: {columns:?}")]
+    UnsupportedDataTypes { columns: Vec<UnsupportedDataType> },
+    #[error("invalid LSN returned by sql server: {0}")]
+    InvalidLsn(#[from] LsnParseError),
+    #[error("change data capture is not enabled for database '{0}'")]
+    CdcNotEnabled(String),
+    /// Any other error we bail on.
+    #[error(transparent)]
+    Generic(#[from] anyhow::Error),
+    /// An I/O error while establishing the connection.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// A tiberius error.
+    #[error(transparent)]
+    SqlServer(#[from] tiberius::error::Error),
+}
@@ -0,0 +1,249 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use mz_repr::adt::numeric::{NumericMaxScale, NUMERIC_DATUM_MAX_PRECISION};
+use mz_repr::{ColumnType, ScalarType};
+use tiberius::{Client, Row};
+use tokio::net::TcpStream;
+use tokio_util::compat::Compat;
+
+use crate::desc::{SqlServerColumnDesc, SqlServerKeyDesc, SqlServerTableDesc};
+use crate::{SqlServerError, UnsupportedDataType};
+
+/// Request for table schemas from SQL Server.
+///
+/// Only tables with change data capture enabled are ever returned.
+pub enum SchemaRequest<'a> {
+    /// Request schemas for all CDC-enabled tables in the database
+    All,
+    /// Request schemas for all CDC-enabled tables in the specified schemas
+    Schemas(Vec<&'a str>),
+    /// Request schemas for all specified tables, specified as (schema_name, table_name)
+    Tables(Vec<(&'a str, &'a str)>),
+}
+
+impl SchemaRequest<'_> {
+    fn matches(&self, schema_name: &str, table_name: &str) -> bool {
+        match self {
+            SchemaRequest::All => true,
+            SchemaRequest::Schemas(schemas) => schemas.contains(&schema_name),
+            SchemaRequest::Tables(tables) => tables.contains(&(schema_name, table_name)),
+        }
+    }
+}
+
+/// A row of `cdc.change_tables`, joined with the name of its source table.
+struct CaptureInstance {
+    schema_name: String,
+    table_name: String,
+    capture_instance: String,
+    /// The object ID of the table being captured.
+    source_object_id: i32,
+    /// The object ID of the change table.
+    object_id: i32,
+}
+
+fn get_string(row: &Row, idx: usize) -> Result<String, SqlServerError> {
+    row.try_get::<&str, _>(idx)?
+        .map(str::to_string)
+        .ok_or_else(|| anyhow::anyhow!("unexpected NULL in column {idx}").into())
+}
+
+fn get_i32(row: &Row, idx: usize) -> Result<i32, SqlServerError> {
+    row.try_get::<i32, _>(idx)?
+        .ok_or_else(|| anyhow::anyhow!("unexpected NULL in column {idx}").into())
+}
+
+/// Retrieve the tables and column descriptions for the CDC-enabled tables
+/// matching `schema_request`.
+///
+/// A table may have up to two capture instances at a time, which happens
+/// while users migrate it to a new schema. We always describe the most
+/// recently created one.
+pub async fn schema_info(
+    client: &mut Client<Compat<TcpStream>>,
+    schema_request: &SchemaRequest<'_>,
+) -> Result<Vec<SqlServerTableDesc>, SqlServerError> {
+    let rows = client
+        .simple_query(
+            "SELECT s.name, t.name, ct.capture_instance, ct.source_object_id, ct.object_id
+            FROM cdc.change_tables AS ct
+            JOIN sys.tables AS t ON t.object_id = ct.source_object_id
+            JOIN sys.schemas AS s ON s.schema_id = t.schema_id
+            ORDER BY s.name, t.name, ct.create_date",
+        )
+        .await?
+        .into_first_result()
+        .await?;
+
+    // Later capture instances overwrite earlier ones, since the rows are
+    // ordered by their creation date.
+    let mut instances = BTreeMap::new();
+    for row in rows {
+        let instance = CaptureInstance {
+            schema_name: get_string(&row, 0)?,
+            table_name: get_string(&row, 1)?,
+            capture_instance: get_string(&row, 2)?,
+            source_object_id: get_i32(&row, 3)?,
+            object_id: get_i32(&row, 4)?,
+        };
+        if schema_request.matches(&instance.schema_name, &instance.table_name) {
+            instances.insert(
+                (instance.schema_name.clone(), instance.table_name.clone()),
+                instance,
+            );
+        }
+    }
+
+    let mut tables = vec![];
+    let mut error_cols = vec![];
+    for instance in instances.into_values() {
+        // NOTE: It's important that we order by column_ordinal since we rely
+        // on this as the order of the columns returned by the CDC functions.
+        let column_rows = client
+            .query(
+                "SELECT cc.column_name, ty.name, c.is_nullable, c.precision, c.scale
+                FROM cdc.captured_columns AS cc
+                JOIN sys.columns AS c ON c.object_id = @P1 AND c.column_id = cc.column_id
+                JOIN sys.types AS ty ON ty.user_type_id = c.system_type_id
+                WHERE cc.object_id = @P2
+                ORDER BY cc.column_ordinal",
+                &[&instance.source_object_id, &instance.object_id],
+            )
+            .await?
+            .into_first_result()
+            .await?;
+
+        let mut columns = Vec::with_capacity(column_rows.len());
+        for row in column_rows {
+            let name = get_string(&row, 0)?;
+            let data_type = get_string(&row, 1)?;
+            let nullable = row.try_get::<bool, _>(2)?.unwrap_or(true);
+            let precision = row.try_get::<u8, _>(3)?.unwrap_or(0);
+            let scale = row.try_get::<u8, _>(4)?.unwrap_or(0);
+
+            match parse_data_type(&data_type, precision, scale) {
+                Some(scalar_type) => columns.push(SqlServerColumnDesc {
+                    name,
+                    column_type: ColumnType {
+                        scalar_type,
+                        nullable,
+                    },
+                    data_type,
+                }),
+                None => error_cols.push(UnsupportedDataType {
+                    column_type: data_type,
+                    qualified_table_name: format!(
+                        "{}.{}",
+                        instance.schema_name, instance.table_name
+                    ),
+                    column_name: name,
+                }),
+            }
+        }
+
+        // Query for the primary key and unique constraints of the table.
+        // Filtered indexes only enforce uniqueness for a subset of rows, so
+        // they are not keys.
+        let index_rows = client
+            .query(
+                "SELECT i.name, i.is_primary_key, c.name
+                FROM sys.indexes AS i
+                JOIN sys.index_columns AS ic
+                    ON ic.object_id = i.object_id AND ic.index_id = i.index_id
+                JOIN sys.columns AS c
+                    ON c.object_id = ic.object_id AND c.column_id = ic.column_id
+                WHERE i.object_id = @P1
+                    AND i.is_unique = 1
+                    AND i.has_filter = 0
+                    AND ic.is_included_column = 0
+                ORDER BY i.index_id, ic.key_ordinal",
+                &[&instance.source_object_id],
+            )
+            .await?
+            .into_first_result()
+            .await?;
+
+        let mut indices: BTreeMap<String, (bool, Vec<String>)> = BTreeMap::new();
+        for row in index_rows {
+            let index_name = get_string(&row, 0)?;
+            let is_primary = row.try_get::<bool, _>(1)?.unwrap_or(false);
+            let column = get_string(&row, 2)?;
+            indices
+                .entry(index_name)
+                .or_insert_with(|| (is_primary, vec![]))
+                .1
+                .push(column);
+        }
+        // A key is only useful to us if the change table captures all of its
+        // columns.
+        let captured: BTreeSet<_> = columns.iter().map(|c| c.name.as_str()).collect();
+        let keys = indices
+            .into_iter()
+            .filter(|(_, (_, key_columns))| {
+                key_columns.iter().all(|c| captured.contains(c.as_str()))
+            })
+            .map(|(name, (is_primary, columns))| SqlServerKeyDesc {
+                name,
+                is_primary,
+                columns,
+            })
+            .collect();
+
+        tables.push(SqlServerTableDesc {
+            schema_name: instance.schema_name,
+            name: instance.table_name,
+            capture_instance: instance.capture_instance,
+            columns,
+            keys,
+        });
+    }
+
+    if !error_cols.is_empty() {
+        Err(SqlServerError::UnsupportedDataTypes {
+            columns: error_cols,
+        })?;
+    }
+    Ok(tables)
+}
+
+/// Maps a SQL Server data type to the type it is represented as in
+/// Materialize, or `None` if the type is unsupported.
+///
+/// See: <https://learn.microsoft.com/en-us/sql/t-sql/data-types/data-types-transact-sql>
+fn parse_data_type(data_type: &str, precision: u8, scale: u8) -> Option<ScalarType> {
+    let scalar_type = match data_type {
+        "bit" => ScalarType::Bool,
+        // `tinyint` is unsigned in SQL Server and ranges from 0 to 255.
+        "tinyint" | "smallint" => ScalarType::Int16,
+        "int" => ScalarType::Int32,
+        "bigint" => ScalarType::Int64,
+        "real" => ScalarType::Float32,
+        "float" => ScalarType::Float64,
+        "decimal" | "numeric" => {
+            if precision > NUMERIC_DATUM_MAX_PRECISION {
+                return None;
+            }
+            ScalarType::Numeric {
+                max_scale: Some(NumericMaxScale::try_from(i64::from(scale)).ok()?),
+            }
+        }
+        "char" | "varchar" | "text" | "nchar" | "nvarchar" | "ntext" => ScalarType::String,
+        "binary" | "varbinary" | "image" => ScalarType::Bytes,
+        "date" => ScalarType::Date,
+        "time" => ScalarType::Time,
+        "datetime" | "datetime2" | "smalldatetime" => ScalarType::Timestamp { precision: None },
+        "datetimeoffset" => ScalarType::TimestampTz { precision: None },
+        "uniqueidentifier" => ScalarType::Uuid,
+        _ => return None,
+    };
+    Some(scalar_type)
+}
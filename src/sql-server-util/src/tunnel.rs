@@ -0,0 +1,175 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::ops::{Deref, DerefMut};
+
+use mz_repr::GlobalId;
+use mz_ssh_util::tunnel::{SshTimeoutConfig, SshTunnelConfig};
+use mz_ssh_util::tunnel_manager::{ManagedSshTunnelHandle, SshTunnelManager};
+use tiberius::Client;
+use tokio::net::TcpStream;
+use tokio_util::compat::{Compat, TokioAsyncWriteCompatExt};
+use tracing::{info, warn};
+
+use crate::SqlServerError;
+
+/// Configures an optional tunnel for use when connecting to a SQL Server
+/// database.
+#[derive(Debug, PartialEq, Clone)]
+pub enum TunnelConfig {
+    /// Establish a direct TCP connection to the database host.
+    Direct,
+    /// Establish a TCP connection to the database via an SSH tunnel.
+    /// This means first establishing an SSH connection to a bastion host,
+    /// and then opening a separate connection from that host to the database.
+    /// This is commonly referred by vendors as a "direct SSH tunnel", in
+    /// opposition to "reverse SSH tunnel", which is currently unsupported.
+    Ssh { config: SshTunnelConfig },
+    /// Establish a TCP connection to the database via an AWS PrivateLink
+    /// service.
+    AwsPrivatelink {
+        /// The ID of the AWS PrivateLink service.
+        connection_id: GlobalId,
+    },
+}
+
+/// A SQL Server client with an optional SSH tunnel handle.
+///
+/// This wrapper is intended to be used in place of `tiberius::Client` to
+/// keep the SSH tunnel alive for the lifecycle of the connection by holding
+/// a reference to the tunnel handle.
+#[derive(Debug)]
+pub struct SqlServerClient {
+    client: Client<Compat<TcpStream>>,
+    _ssh_tunnel_handle: Option<ManagedSshTunnelHandle>,
+}
+
+impl Deref for SqlServerClient {
+    type Target = Client<Compat<TcpStream>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.client
+    }
+}
+
+impl DerefMut for SqlServerClient {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.client
+    }
+}
+
+impl SqlServerClient {
+    pub async fn close(self) -> Result<(), SqlServerError> {
+        let SqlServerClient {
+            client,
+            _ssh_tunnel_handle,
+        } = self;
+        client.close().await?;
+        drop(_ssh_tunnel_handle);
+        Ok(())
+    }
+}
+
+/// Configuration for SQL Server connections.
+///
+/// This wraps [`tiberius::Config`] to allow the configuration of a
+/// tunnel via a [`TunnelConfig`]. The host and port are tracked separately
+/// because the TCP stream handed to `tiberius` may point at a tunnel rather
+/// than at the server itself.
+#[derive(Clone, Debug)]
+pub struct Config {
+    inner: tiberius::Config,
+    host: String,
+    port: u16,
+    tunnel: TunnelConfig,
+    ssh_timeout_config: SshTimeoutConfig,
+}
+
+impl Config {
+    pub fn new(
+        host: String,
+        port: u16,
+        mut inner: tiberius::Config,
+        tunnel: TunnelConfig,
+        ssh_timeout_config: SshTimeoutConfig,
+    ) -> Self {
+        inner.host(&host);
+        inner.port(port);
+        Self {
+            inner,
+            host,
+            port,
+            tunnel,
+            ssh_timeout_config,
+        }
+    }
+
+    pub async fn connect(
+        &self,
+        task_name: &str,
+        ssh_tunnel_manager: &SshTunnelManager,
+    ) -> Result<SqlServerClient, SqlServerError> {
+        let address = format!("sqlserver://{}:{}", self.host, self.port);
+        info!(%task_name, %address, "connecting");
+        match self.connect_internal(ssh_tunnel_manager).await {
+            Ok(t) => {
+                info!(%task_name, %address, "connected");
+                Ok(t)
+            }
+            Err(e) => {
+                warn!(%task_name, %address, "connection failed: {e:#}");
+                Err(e)
+            }
+        }
+    }
+
+    async fn connect_internal(
+        &self,
+        ssh_tunnel_manager: &SshTunnelManager,
+    ) -> Result<SqlServerClient, SqlServerError> {
+        let (tcp, ssh_tunnel_handle) = match &self.tunnel {
+            TunnelConfig::Direct => (
+                TcpStream::connect((self.host.as_str(), self.port)).await?,
+                None,
+            ),
+            TunnelConfig::Ssh { config } => {
+                let tunnel = ssh_tunnel_manager
+                    .connect(
+                        config.clone(),
+                        &self.host,
+                        self.port,
+                        self.ssh_timeout_config,
+                    )
+                    .await
+                    .map_err(SqlServerError::Ssh)?;
+                // Point the actual TCP connection at the local end of the
+                // tunnel instead of the upstream server.
+                let tcp = TcpStream::connect(tunnel.local_addr()).await?;
+                (tcp, Some(tunnel))
+            }
+            TunnelConfig::AwsPrivatelink { connection_id } => {
+                let privatelink_host = mz_cloud_resources::vpc_endpoint_name(*connection_id);
+                (
+                    TcpStream::connect((privatelink_host.as_str(), self.port)).await?,
+                    None,
+                )
+            }
+        };
+        tcp.set_nodelay(true)?;
+
+        // `tiberius` is runtime agnostic and expects a stream implementing the
+        // `futures` I/O traits, so we wrap Tokio's stream with `compat_write`.
+        let client = Client::connect(self.inner.clone(), tcp.compat_write()).await?;
+
+        Ok(SqlServerClient {
+            client,
+            _ssh_tunnel_handle: ssh_tunnel_handle,
+        })
+    }
+}
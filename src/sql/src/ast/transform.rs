@@ -17,10 +17,11 @@ use mz_repr::GlobalId;
 use crate::ast::visit::{self, Visit};
 use crate::ast::visit_mut::{self, VisitMut};
 use crate::ast::{
-    AstInfo, CreateConnectionStatement, CreateIndexStatement, CreateMaterializedViewStatement,
-    CreateSecretStatement, CreateSinkStatement, CreateSourceStatement, CreateSubsourceStatement,
-    CreateTableStatement, CreateViewStatement, CreateWebhookSourceStatement, Expr, Ident, Query,
-    Raw, RawItemName, Statement, UnresolvedItemName, ViewDefinition,
+    AstInfo, CreateConnectionStatement, CreateFunctionStatement, CreateIndexStatement,
+    CreateMaterializedViewStatement, CreateSecretStatement, CreateSinkStatement,
+    CreateSourceStatement, CreateSubsourceStatement, CreateTableStatement, CreateViewStatement,
+    CreateWebhookSourceStatement, Expr, Ident, Query, Raw, RawItemName, Statement,
+    UnresolvedItemName, ViewDefinition,
 };
 use crate::names::FullItemName;
 
@@ -45,6 +46,7 @@ pub fn create_stmt_rename_schema_refs(
         | stmt @ Statement::CreateTable(_)
         | stmt @ Statement::CreateIndex(_)
         | stmt @ Statement::CreateType(_)
+        | stmt @ Statement::CreateFunction(_)
         | stmt @ Statement::CreateSecret(_) => {
             let mut visitor = CreateSqlRewriteSchema {
                 database,
@@ -200,7 +202,8 @@ pub fn create_stmt_rename_refs(
             definition: ViewDefinition { query, .. },
             ..
         })
-        | Statement::CreateMaterializedView(CreateMaterializedViewStatement { query, .. }) => {
+        | Statement::CreateMaterializedView(CreateMaterializedViewStatement { query, .. })
+        | Statement::CreateFunction(CreateFunctionStatement { body: query, .. }) => {
             rewrite_query(from_name, to_item_name, query)?;
         }
         Statement::CreateSource(_)
@@ -40,7 +40,7 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::func::Func;
+use crate::func::{Func, SqlFunction};
 use crate::names::{
     Aug, CommentObjectId, DatabaseId, FullItemName, FullSchemaName, ObjectId, PartialItemName,
     QualifiedItemName, QualifiedSchemaName, ResolvedDatabaseSpecifier, ResolvedIds, SchemaId,
@@ -577,7 +577,18 @@ pub trait CatalogItem {
     ///
     /// If the catalog item is not of a type that produces functions (i.e.,
     /// anything other than a function), it returns an error.
-    fn func(&self) -> Result<&'static Func, CatalogError>;
+    fn func(&self) -> Result<&Func, CatalogError>;
+
+    /// Returns the definition of the user-defined function, or `None` if the
+    /// catalog item is not a user-defined function.
+    fn sql_function(&self) -> Option<&SqlFunction>;
+
+    /// Returns the IDs of the user-defined functions that share this
+    /// function's name, including this function itself.
+    ///
+    /// If the catalog item is not a user-defined function, returns an empty
+    /// slice.
+    fn function_overloads(&self) -> &[GlobalId];
 
     /// Returns the resolved source connection.
     ///
@@ -840,6 +851,13 @@ pub enum CatalogType<T: TypeReference> {
     VarChar,
     Int2Vector,
     MzAclItem,
+    Inet,
+    Cidr,
+    MacAddr,
+    Enum {
+        /// The labels of the enum, in declaration order.
+        labels: Vec<String>,
+    },
 }
 
 impl CatalogType<IdReference> {
@@ -15,12 +15,15 @@ use std::collections::BTreeMap;
 use std::fmt;
 
 use itertools::Itertools;
-use mz_expr::func;
+use mz_expr::jsonpath::JsonPath;
+use mz_expr::{func, hll};
 use mz_ore::collections::CollectionExt;
 use mz_ore::str::StrExt;
 use mz_pgrepr::oid;
 use mz_repr::role_id::RoleId;
-use mz_repr::{ColumnName, ColumnType, Datum, RelationType, Row, ScalarBaseType, ScalarType};
+use mz_repr::{
+    ColumnName, ColumnType, Datum, DatumMap, RelationType, Row, ScalarBaseType, ScalarType,
+};
 use once_cell::sync::Lazy;
 
 use crate::ast::{SelectStatement, Statement};
@@ -77,7 +80,8 @@ impl TypeCategory {
             | ScalarType::Bytes
             | ScalarType::Jsonb
             | ScalarType::Uuid
-            | ScalarType::MzAclItem => Self::UserDefined,
+            | ScalarType::MzAclItem
+            | ScalarType::MacAddr => Self::UserDefined,
             ScalarType::Date
             | ScalarType::Time
             | ScalarType::Timestamp { .. }
@@ -96,6 +100,7 @@ impl TypeCategory {
             | ScalarType::RegType
             | ScalarType::Numeric { .. } => Self::Numeric,
             ScalarType::Interval => Self::Timespan,
+            ScalarType::Inet | ScalarType::Cidr => Self::NetworkAddress,
             ScalarType::List { .. } => Self::List,
             ScalarType::PgLegacyChar
             | ScalarType::PgLegacyName
@@ -112,6 +117,7 @@ impl TypeCategory {
             ScalarType::Map { .. } => Self::Pseudo,
             ScalarType::MzTimestamp => Self::Numeric,
             ScalarType::Range { .. } => Self::Range,
+            ScalarType::Enum { .. } => Self::Enum,
         }
     }
 
@@ -132,6 +138,7 @@ impl TypeCategory {
             | ParamType::MapAnyCompatible
             | ParamType::RecordAny => Self::Pseudo,
             ParamType::RangeAnyCompatible | ParamType::RangeAny => Self::Range,
+            ParamType::EnumAny => Self::Enum,
             ParamType::Plain(t) => Self::from_type(t),
         }
     }
@@ -151,7 +158,8 @@ impl TypeCategory {
             | CatalogType::Bytes
             | CatalogType::Jsonb
             | CatalogType::Uuid
-            | CatalogType::MzAclItem => Self::UserDefined,
+            | CatalogType::MzAclItem
+            | CatalogType::MacAddr => Self::UserDefined,
             CatalogType::Date
             | CatalogType::Time
             | CatalogType::Timestamp
@@ -170,6 +178,7 @@ impl TypeCategory {
             | CatalogType::RegType
             | CatalogType::Numeric { .. } => Self::Numeric,
             CatalogType::Interval => Self::Timespan,
+            CatalogType::Inet | CatalogType::Cidr => Self::NetworkAddress,
             CatalogType::List { .. } => Self::List,
             CatalogType::PgLegacyChar
             | CatalogType::PgLegacyName
@@ -180,6 +189,7 @@ impl TypeCategory {
             CatalogType::Map { .. } | CatalogType::Pseudo => Self::Pseudo,
             CatalogType::MzTimestamp => Self::String,
             CatalogType::Range { .. } => Self::Range,
+            CatalogType::Enum { .. } => Self::Enum,
         }
     }
 
@@ -198,13 +208,13 @@ impl TypeCategory {
             | Self::Enum
             | Self::Geometric
             | Self::List
-            | Self::NetworkAddress
             | Self::Pseudo
             | Self::Range
             | Self::Unknown
             | Self::UserDefined => None,
             Self::Boolean => Some(ScalarType::Bool),
             Self::DateTime => Some(ScalarType::TimestampTz { precision: None }),
+            Self::NetworkAddress => Some(ScalarType::Inet),
             Self::Numeric => Some(ScalarType::Float64),
             Self::String => Some(ScalarType::String),
             Self::Timespan => Some(ScalarType::Interval),
@@ -309,7 +319,8 @@ impl<R> Operation<R> {
     /// Builds an operation that takes two arguments and an order_by.
     ///
     /// If returning an aggregate function, it should return `true` for
-    /// [`AggregateFunc::is_order_sensitive`].
+    /// [`AggregateFunc::is_order_sensitive`] or
+    /// [`AggregateFunc::is_ordered_set`].
     fn binary_ordered<F>(f: F) -> Operation<R>
     where
         F: Fn(&ExprContext, HirScalarExpr, HirScalarExpr, Vec<ColumnOrder>) -> Result<R, PlanError>
@@ -726,6 +737,9 @@ pub enum ParamType {
     /// this type into generating non-existent range types (e.g. ranges of
     /// floats) that will panic.
     RangeAnyCompatible,
+    /// A pseudotype permitting any enum type, requiring other "Any"-type
+    /// parameters to be of the same type.
+    EnumAny,
     /// A psuedotype indicating that the function is only meant to be called
     /// internally by the database system.
     Internal,
@@ -743,6 +757,7 @@ impl ParamType {
             ListAny | ListAnyCompatible => matches!(t, List { .. }),
             MapAny | MapAnyCompatible => matches!(t, Map { .. }),
             RangeAny | RangeAnyCompatible => matches!(t, Range { .. }),
+            EnumAny => matches!(t, Enum { .. }),
             NonVecAny | NonVecAnyCompatible => !t.is_vec(),
             Internal => false,
             Plain(to) => typeconv::can_cast(ecx, CastContext::Implicit, t, to),
@@ -798,7 +813,8 @@ impl ParamType {
             // `PolymorphicCompatClass::StructuralEq`.
             | RecordAny
             | RangeAny
-            | RangeAnyCompatible => true,
+            | RangeAnyCompatible
+            | EnumAny => true,
             Any | Internal | Plain(_)  => false,
         }
     }
@@ -829,6 +845,7 @@ impl ParamType {
             ParamType::RecordAny => "record",
             ParamType::RangeAny => "anyrange",
             ParamType::RangeAnyCompatible => "anycompatiblerange",
+            ParamType::EnumAny => "anyenum",
         }
     }
 }
@@ -893,6 +910,9 @@ impl From<ScalarBaseType> for ParamType {
             Int2Vector => ScalarType::Int2Vector,
             MzTimestamp => ScalarType::MzTimestamp,
             MzAclItem => ScalarType::MzAclItem,
+            Inet => ScalarType::Inet,
+            Cidr => ScalarType::Cidr,
+            MacAddr => ScalarType::MacAddr,
         };
         ParamType::Plain(s)
     }
@@ -1311,7 +1331,7 @@ impl TryFrom<&ParamType> for PolymorphicCompatClass {
         use ParamType::*;
 
         Ok(match param {
-            AnyElement | ArrayAny | ListAny | MapAny | NonVecAny | RangeAny => {
+            AnyElement | ArrayAny | ListAny | MapAny | NonVecAny | RangeAny | EnumAny => {
                 PolymorphicCompatClass::Any
             }
             ArrayAnyCompatible | AnyCompatible | RangeAnyCompatible | NonVecAnyCompatible => {
@@ -1384,7 +1404,7 @@ impl PolymorphicSolution {
 
         self.seen.push(match param {
             // These represent the keys of their respective compatibility classes.
-            AnyElement | AnyCompatible | ListAnyCompatible |  MapAnyCompatible | NonVecAny | RecordAny => seen,
+            AnyElement | AnyCompatible | ListAnyCompatible |  MapAnyCompatible | NonVecAny | RecordAny | EnumAny => seen,
             MapAny => seen.map(|array| array.unwrap_map_value_type().clone()),
             ListAny => seen.map(|array| array.unwrap_list_element_type().clone()),
             ArrayAny | ArrayAnyCompatible => seen.map(|array| array.unwrap_array_element_type().clone()),
@@ -1504,9 +1524,8 @@ impl PolymorphicSolution {
         );
 
         match param {
-            AnyElement | AnyCompatible | ListAnyCompatible | MapAnyCompatible | NonVecAny => {
-                self.key.clone()
-            }
+            AnyElement | AnyCompatible | ListAnyCompatible | MapAnyCompatible | NonVecAny
+            | EnumAny => self.key.clone(),
             ArrayAny | ArrayAnyCompatible => self
                 .key
                 .as_ref()
@@ -1650,6 +1669,111 @@ pub struct TableFuncPlan {
     pub column_names: Vec<ColumnName>,
 }
 
+/// A user-defined function whose body is written in SQL.
+///
+/// The body is planned once, when the function is created, with
+/// [`HirScalarExpr::Parameter`]s standing in for the function's arguments.
+/// Calls to the function inline the body, splicing in the arguments of the
+/// call, much like the built-in functions defined via `sql_impl_func`.
+#[derive(Debug, Clone)]
+pub struct SqlFunction {
+    /// The types of the function's parameters.
+    pub param_types: Vec<ScalarType>,
+    /// The planned body of the function.
+    pub body: SqlFunctionBody,
+}
+
+#[derive(Debug, Clone)]
+pub enum SqlFunctionBody {
+    /// The function returns a single value of type `return_type`.
+    Scalar {
+        expr: HirScalarExpr,
+        return_type: ScalarType,
+    },
+    /// The function returns a set of rows with the named columns.
+    Table {
+        expr: HirRelationExpr,
+        column_names: Vec<ColumnName>,
+    },
+}
+
+impl SqlFunction {
+    /// Returns the [`Func`] that inlines this function at its call sites.
+    ///
+    /// User-defined functions are identified by the OID of their catalog
+    /// entry, so the implementation is not assigned an OID of its own.
+    pub fn func(&self) -> Func {
+        Self::overloaded_func([self])
+    }
+
+    /// Returns the [`Func`] for a name shared by `functions`, which must
+    /// differ in their parameter types.
+    ///
+    /// Calls select among the implementations of the overloads like they do
+    /// for built-in functions. The overloads must either all be scalar or all
+    /// be table functions.
+    pub fn overloaded_func<'a>(functions: impl IntoIterator<Item = &'a SqlFunction>) -> Func {
+        let mut scalar_impls = vec![];
+        let mut table_impls = vec![];
+        for function in functions {
+            let params = ParamList::Exact(
+                function
+                    .param_types
+                    .iter()
+                    .cloned()
+                    .map(ParamType::Plain)
+                    .collect(),
+            );
+            match &function.body {
+                SqlFunctionBody::Scalar { expr, return_type } => {
+                    let expr = expr.clone();
+                    scalar_impls.push(FuncImpl {
+                        oid: 0,
+                        params,
+                        return_type: ReturnType::scalar(return_type.clone().into()),
+                        op: Operation::variadic(move |_ecx, args| {
+                            let mut out = expr.clone();
+                            out.splice_parameters(&args, 0);
+                            Ok(out)
+                        }),
+                    });
+                }
+                SqlFunctionBody::Table { expr, column_names } => {
+                    let expr = expr.clone();
+                    let column_names = column_names.clone();
+                    table_impls.push(FuncImpl {
+                        oid: 0,
+                        params,
+                        return_type: ReturnType::set_of(ParamType::RecordAny),
+                        op: Operation::variadic(move |_ecx, args| {
+                            let mut expr = expr.clone();
+                            expr.splice_parameters(&args, 0);
+                            Ok(TableFuncPlan {
+                                expr,
+                                column_names: column_names.clone(),
+                            })
+                        }),
+                    });
+                }
+            }
+        }
+        if table_impls.is_empty() {
+            Func::Scalar(scalar_impls)
+        } else {
+            assert!(
+                scalar_impls.is_empty(),
+                "scalar and table functions cannot overload each other"
+            );
+            Func::Table(table_impls)
+        }
+    }
+
+    /// Reports whether this is a table function.
+    pub fn returns_set(&self) -> bool {
+        matches!(self.body, SqlFunctionBody::Table { .. })
+    }
+}
+
 #[derive(Debug)]
 pub enum Func {
     Scalar(Vec<FuncImpl<HirScalarExpr>>),
@@ -1930,6 +2054,15 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
         "cot" => Scalar {
             params!(Float64) => UnaryFunc::Cot(func::Cot) => Float64, 1607;
         },
+        "corr" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("corr")) => Float64, 2829;
+        },
+        "covar_pop" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("covar_pop")) => Float64, 2827;
+        },
+        "covar_samp" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("covar_samp")) => Float64, 2828;
+        },
         "current_schema" => Scalar {
             // TODO: this should be `name`. This is tricky in Materialize
             // because `name` truncates to 63 characters but Materialize does
@@ -2085,6 +2218,9 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
             params!(String, String, String) => VariadicFunc::HmacString => Bytes, oid::FUNC_PG_HMAC_STRING;
             params!(Bytes, Bytes, String) => VariadicFunc::HmacBytes => Bytes, oid::FUNC_PG_HMAC_BYTES;
         },
+        "host" => Scalar {
+            params!(Inet) => UnaryFunc::Host(func::Host) => String, 699;
+        },
         "int4range" => Scalar {
             params!(Int32, Int32) => Operation::variadic(|_ecx, mut exprs| {
                 exprs.push(HirScalarExpr::literal(Datum::String("[)"), ScalarType::String));
@@ -2144,6 +2280,26 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
                 })
             }) => Jsonb, 3273;
         },
+        "jsonb_path_exists" => Scalar {
+            params!(Jsonb, String) => jsonb_path(|path| VariadicFunc::JsonbPathExists { path }, false) => Bool, oid::FUNC_JSONB_PATH_EXISTS_OID;
+            params!(Jsonb, String, Jsonb) => jsonb_path(|path| VariadicFunc::JsonbPathExists { path }, false) => Bool, oid::FUNC_JSONB_PATH_EXISTS_VARS_OID;
+            params!(Jsonb, String, Jsonb, Bool) => jsonb_path(|path| VariadicFunc::JsonbPathExists { path }, false) => Bool, oid::FUNC_JSONB_PATH_EXISTS_SILENT_OID;
+        },
+        "jsonb_path_match" => Scalar {
+            params!(Jsonb, String) => jsonb_path(|path| VariadicFunc::JsonbPathMatch { path }, false) => Bool, oid::FUNC_JSONB_PATH_MATCH_OID;
+            params!(Jsonb, String, Jsonb) => jsonb_path(|path| VariadicFunc::JsonbPathMatch { path }, false) => Bool, oid::FUNC_JSONB_PATH_MATCH_VARS_OID;
+            params!(Jsonb, String, Jsonb, Bool) => jsonb_path(|path| VariadicFunc::JsonbPathMatch { path }, false) => Bool, oid::FUNC_JSONB_PATH_MATCH_SILENT_OID;
+        },
+        "jsonb_path_query_array" => Scalar {
+            params!(Jsonb, String) => jsonb_path(|path| VariadicFunc::JsonbPathQueryArray { path }, false) => Jsonb, oid::FUNC_JSONB_PATH_QUERY_ARRAY_OID;
+            params!(Jsonb, String, Jsonb) => jsonb_path(|path| VariadicFunc::JsonbPathQueryArray { path }, false) => Jsonb, oid::FUNC_JSONB_PATH_QUERY_ARRAY_VARS_OID;
+            params!(Jsonb, String, Jsonb, Bool) => jsonb_path(|path| VariadicFunc::JsonbPathQueryArray { path }, false) => Jsonb, oid::FUNC_JSONB_PATH_QUERY_ARRAY_SILENT_OID;
+        },
+        "jsonb_path_query_first" => Scalar {
+            params!(Jsonb, String) => jsonb_path(|path| VariadicFunc::JsonbPathQueryFirst { path }, false) => Jsonb, oid::FUNC_JSONB_PATH_QUERY_FIRST_OID;
+            params!(Jsonb, String, Jsonb) => jsonb_path(|path| VariadicFunc::JsonbPathQueryFirst { path }, false) => Jsonb, oid::FUNC_JSONB_PATH_QUERY_FIRST_VARS_OID;
+            params!(Jsonb, String, Jsonb, Bool) => jsonb_path(|path| VariadicFunc::JsonbPathQueryFirst { path }, false) => Jsonb, oid::FUNC_JSONB_PATH_QUERY_FIRST_SILENT_OID;
+        },
         "jsonb_pretty" => Scalar {
             params!(Jsonb) => UnaryFunc::JsonbPretty(func::JsonbPretty) => String, 3306;
         },
@@ -2211,6 +2367,9 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
         "make_timestamp" => Scalar {
             params!(Int64, Int64, Int64, Int64, Int64, Float64) => VariadicFunc::MakeTimestamp => Timestamp, 3461;
         },
+        "masklen" => Scalar {
+            params!(Inet) => UnaryFunc::Masklen(func::Masklen) => Int32, 697;
+        },
         "md5" => Scalar {
             params!(String) => Operation::unary(move |_ecx, input| {
                 let algorithm = HirScalarExpr::literal(Datum::String("md5"), ScalarType::String);
@@ -2232,6 +2391,9 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
             params!(UInt32, UInt32) => Operation::nullary(|_ecx| catalog_name_only!("mod")) => UInt32, oid::FUNC_MOD_UINT32_OID;
             params!(UInt64, UInt64) => Operation::nullary(|_ecx| catalog_name_only!("mod")) => UInt64, oid::FUNC_MOD_UINT64_OID;
         },
+        "network" => Scalar {
+            params!(Inet) => UnaryFunc::Network(func::Network) => Cidr, 683;
+        },
         "now" => Scalar {
             params!() => UnmaterializableFunc::CurrentTimestamp => TimestampTz, 1299;
         },
@@ -2501,6 +2663,33 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
         "repeat" => Scalar {
             params!(String, Int32) => BinaryFunc::RepeatString => String, 1622;
         },
+        "regr_avgx" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_avgx")) => Float64, 2822;
+        },
+        "regr_avgy" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_avgy")) => Float64, 2823;
+        },
+        "regr_count" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_count")) => Int64, 2818;
+        },
+        "regr_intercept" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_intercept")) => Float64, 2826;
+        },
+        "regr_r2" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_r2")) => Float64, 2824;
+        },
+        "regr_slope" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_slope")) => Float64, 2825;
+        },
+        "regr_sxx" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_sxx")) => Float64, 2819;
+        },
+        "regr_sxy" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_sxy")) => Float64, 2821;
+        },
+        "regr_syy" => Scalar {
+            params!(Float64, Float64) => Operation::nullary(|_ecx| catalog_name_only!("regr_syy")) => Float64, 2820;
+        },
         "regexp_match" => Scalar {
             params!(String, String) => VariadicFunc::RegexpMatch => ScalarType::Array(Box::new(ScalarType::String)), 3396;
             params!(String, String, String) => VariadicFunc::RegexpMatch => ScalarType::Array(Box::new(ScalarType::String)), 3397;
@@ -2521,6 +2710,10 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
             params!(String) => UnaryFunc::TrimTrailingWhitespace(func::TrimTrailingWhitespace) => String, 882;
             params!(String, String) => BinaryFunc::TrimTrailing => String, 876;
         },
+        "set_masklen" => Scalar {
+            params!(Inet, Int32) => BinaryFunc::SetMasklenInet => Inet, 605;
+            params!(Cidr, Int32) => BinaryFunc::SetMasklenCidr => Cidr, 635;
+        },
         "sha224" => Scalar {
             params!(Bytes) => digest("sha224") => Bytes, 3419;
         },
@@ -2750,6 +2943,10 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
         "version" => Scalar {
             params!() => UnmaterializableFunc::Version => String, 89;
         },
+        "width_bucket" => Scalar {
+            params!(Float64, Float64, Float64, Int32) => VariadicFunc::WidthBucketFloat64 => Int32, 320;
+            params!(Numeric, Numeric, Numeric, Int32) => VariadicFunc::WidthBucketNumeric => Int32, 2170;
+        },
 
         // Internal conversion stubs.
         "aclitemin" => Scalar {
@@ -2779,6 +2976,9 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
         "anynonarray_in" => Scalar {
             params!(String) => Operation::variadic(|_ecx, _exprs| bail_unsupported!("anynonarray_in")) => NonVecAny, 2777;
         },
+        "anyenum_in" => Scalar {
+            params!(String) => Operation::variadic(|_ecx, _exprs| bail_unsupported!("anyenum_in")) => EnumAny, 3504;
+        },
         "anyrange_in" => Scalar {
             params!(String, Oid, Int32) => Operation::variadic(|_ecx, _exprs| bail_unsupported!("anyrange_in")) => RangeAny, 3832;
         },
@@ -2798,6 +2998,9 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
         "charin" => Scalar {
             params!(String) => Operation::variadic(|_ecx, _exprs| bail_unsupported!("charin")) => PgLegacyChar, 1245;
         },
+        "cidr_in" => Scalar {
+            params!(String) => Operation::variadic(|_ecx, _exprs| bail_unsupported!("cidr_in")) => Cidr, 1267;
+        },
         "date_in" => Scalar {
             params!(String) => Operation::variadic(|_ecx, _exprs| bail_unsupported!("date_in")) => Date, 1084;
         },
@@ -2819,6 +3022,9 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
         "int8in" => Scalar {
             params!(String) => Operation::variadic(|_ecx, _exprs| bail_unsupported!("int8in")) => Int64, 460;
         },
+        "inet_in" => Scalar {
+            params!(String) => Operation::variadic(|_ecx, _exprs| bail_unsupported!("inet_in")) => Inet, 910;
+        },
         "internal_in" => Scalar {
             params!(String) => Operation::variadic(|_ecx, _exprs| bail_unsupported!("internal_in")) => Internal, 2304;
         },
@@ -2831,6 +3037,9 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
         "namein" => Scalar {
             params!(String) => Operation::variadic(|_ecx, _exprs| bail_unsupported!("namein")) => PgLegacyName, 34;
         },
+        "macaddr_in" => Scalar {
+            params!(String) => Operation::variadic(|_ecx, _exprs| bail_unsupported!("macaddr_in")) => MacAddr, 436;
+        },
         "numeric_in" => Scalar {
             params!(String, Oid, Int32) => Operation::variadic(|_ecx, _exprs| bail_unsupported!("numeric_in")) => Numeric, 1701;
         },
@@ -2888,6 +3097,9 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
         "charrecv" => Scalar {
             params!(Internal) => Operation::nullary(|_ecx| catalog_name_only!("charrecv")) => PgLegacyChar, 2434;
         },
+        "cidr_recv" => Scalar {
+            params!(Internal) => Operation::nullary(|_ecx| catalog_name_only!("cidr_recv")) => Cidr, 2498;
+        },
         "date_recv" => Scalar {
             params!(Internal) => Operation::nullary(|_ecx| catalog_name_only!("date_recv")) => Date, 2468;
         },
@@ -2903,12 +3115,18 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
         "int8recv" => Scalar {
             params!(Internal) => Operation::nullary(|_ecx| catalog_name_only!("int8recv")) => Int64, 2408;
         },
+        "inet_recv" => Scalar {
+            params!(Internal) => Operation::nullary(|_ecx| catalog_name_only!("inet_recv")) => Inet, 2496;
+        },
         "interval_recv" => Scalar {
             params!(Internal) => Operation::nullary(|_ecx| catalog_name_only!("interval_recv")) => Interval, 2478;
         },
         "jsonb_recv" => Scalar {
             params!(Internal) => Operation::nullary(|_ecx| catalog_name_only!("jsonb_recv")) => Jsonb, 3805;
         },
+        "macaddr_recv" => Scalar {
+            params!(Internal) => Operation::nullary(|_ecx| catalog_name_only!("macaddr_recv")) => MacAddr, 2494;
+        },
         "namerecv" => Scalar {
             params!(Internal) => Operation::nullary(|_ecx| catalog_name_only!("namerecv")) => PgLegacyName, 2422;
         },
@@ -3090,6 +3308,22 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
                 Ok((e, AggregateFunc::JsonbObjectAgg { order_by }))
             }) => Jsonb, 3270;
         },
+        "mode" => Aggregate {
+            params!(AnyElement) => Operation::unary_ordered(|_ecx, e, order_by| {
+                let desc = order_by.first().map_or(false, |o| o.desc);
+                Ok((e, AggregateFunc::Mode { desc }))
+            }) => AnyElement, 3984;
+        },
+        "percentile_cont" => Aggregate {
+            params!(Float64, Float64) => percentile(true, false) => Float64, 3974;
+            params!(ScalarType::Array(Box::new(ScalarType::Float64)), Float64) => percentile(true, true)
+                => ScalarType::Array(Box::new(ScalarType::Float64)), 3980;
+        },
+        "percentile_disc" => Aggregate {
+            params!(Float64, AnyElement) => percentile(false, false) => AnyElement, 3972;
+            params!(ScalarType::Array(Box::new(ScalarType::Float64)), NonVecAny) => percentile(false, true)
+                => ArrayAny, 3978;
+        },
         "string_agg" => Aggregate {
             params!(String, String) => Operation::binary_ordered(|_ecx, value, sep, order_by| {
                 let e = HirScalarExpr::CallVariadic {
@@ -3329,6 +3563,11 @@ pub static PG_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
                 })
             }) => ReturnType::set_of(String.into()), 3931;
         },
+        "jsonb_path_query" => Table {
+            params!(Jsonb, String) => jsonb_path_query() => ReturnType::set_of(Jsonb.into()), oid::FUNC_JSONB_PATH_QUERY_OID;
+            params!(Jsonb, String, Jsonb) => jsonb_path_query() => ReturnType::set_of(Jsonb.into()), oid::FUNC_JSONB_PATH_QUERY_VARS_OID;
+            params!(Jsonb, String, Jsonb, Bool) => jsonb_path_query() => ReturnType::set_of(Jsonb.into()), oid::FUNC_JSONB_PATH_QUERY_SILENT_OID;
+        },
         // Note that these implementations' input to `generate_series` is
         // contrived to match Flink's expected values. There are other,
         // equally valid windows we could generate.
@@ -3442,6 +3681,14 @@ pub static MZ_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
             params!(Bytes, Bytes) => BinaryFunc::ConstantTimeEqBytes => Bool, oid::FUNC_CONSTANT_TIME_EQ_BYTES_OID;
             params!(String, String) => BinaryFunc::ConstantTimeEqString => Bool, oid::FUNC_CONSTANT_TIME_EQ_STRING_OID;
         },
+        "approx_count_distinct" => Aggregate {
+            params!(Any) => Operation::unary(|_ecx, e| {
+                hll_aggregate(e, None, |precision| AggregateFunc::ApproxCountDistinct { precision })
+            }) => Int64, oid::FUNC_APPROX_COUNT_DISTINCT_OID;
+            params!(Any, Int64) => Operation::binary(|_ecx, e, precision| {
+                hll_aggregate(e, Some(precision), |precision| AggregateFunc::ApproxCountDistinct { precision })
+            }) => Int64, oid::FUNC_APPROX_COUNT_DISTINCT_PRECISION_OID;
+        },
         // Note: this is the original version of the AVG(...) function, as it existed prior to
         // v0.66. We updated the internal type promotion used when summing values to increase
         // precision, but objects (e.g. materialized views) that already used the AVG(...) function
@@ -3606,6 +3853,27 @@ pub static MZ_CATALOG_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|
             params!(String, String) => sql_impl_func("has_type_privilege(current_user, $1, $2)") => Bool, 3142;
             params!(Oid, String) => sql_impl_func("has_type_privilege(current_user, $1, $2)") => Bool, 3143;
         },
+        "hll_cardinality" => Scalar {
+            params!(Bytes) => UnaryFunc::HllCardinality(func::HllCardinality) => Int64, oid::FUNC_HLL_CARDINALITY_OID;
+        },
+        "hll_sketch" => Aggregate {
+            params!(Any) => Operation::unary(|_ecx, e| {
+                hll_aggregate(e, None, |precision| AggregateFunc::HllSketch { precision })
+            }) => Bytes, oid::FUNC_HLL_SKETCH_OID;
+            params!(Any, Int64) => Operation::binary(|_ecx, e, precision| {
+                hll_aggregate(e, Some(precision), |precision| AggregateFunc::HllSketch { precision })
+            }) => Bytes, oid::FUNC_HLL_SKETCH_PRECISION_OID;
+        },
+        "hll_union" => Scalar {
+            params!(Bytes, Bytes) => BinaryFunc::HllUnion => Bytes, oid::FUNC_HLL_UNION_OID;
+        },
+        "hll_union_agg" => Aggregate {
+            // Validating the sketches before aggregating them reports invalid
+            // sketches through the error path of the reduction.
+            params!(Bytes) => Operation::unary(|_ecx, e| {
+                Ok((e.call_unary(UnaryFunc::HllValidate(func::HllValidate)), AggregateFunc::HllUnion))
+            }) => Bytes, oid::FUNC_HLL_UNION_AGG_OID;
+        },
         "list_agg" => Aggregate {
             params!(Any) => Operation::unary_ordered(|ecx, e, order_by| {
                 if let ScalarType::Char {.. }  = ecx.scalar_type(&e) {
@@ -4302,6 +4570,11 @@ pub static MZ_UNSAFE_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(||
                 )
             }) => Numeric, oid::FUNC_MZ_AVG_PROMOTION_NUMERIC_OID;
         },
+        "mz_float8_promotion" => Scalar {
+            // Implicitly casts its argument to `double precision`, like the
+            // parameters of the two-argument statistical aggregates.
+            params!(Float64) => Operation::identity() => Float64, oid::FUNC_MZ_FLOAT8_PROMOTION_OID;
+        },
         "mz_error_if_null" => Scalar {
             // If the first argument is NULL, returns an EvalError::Internal whose error
             // message is the second argument.
@@ -4316,6 +4589,109 @@ pub static MZ_UNSAFE_BUILTINS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(||
     }
 });
 
+/// Builds the operation for the ordered-set aggregates `percentile_cont` (if
+/// `cont`) and `percentile_disc`. The direct argument is the fraction, or an
+/// array of fractions if `multiple`, and the aggregated argument is the `WITHIN
+/// GROUP` expression. Fractions that are not between 0 and 1 are errors.
+fn percentile(cont: bool, multiple: bool) -> Operation<(HirScalarExpr, AggregateFunc)> {
+    Operation::binary_ordered(move |_ecx, fraction, value, order_by| {
+        let desc = order_by.first().map_or(false, |o| o.desc);
+        let fraction = fraction.call_unary(UnaryFunc::CheckPercentileFraction(
+            func::CheckPercentileFraction,
+        ));
+        let e = HirScalarExpr::CallVariadic {
+            func: VariadicFunc::RecordCreate {
+                field_names: vec![ColumnName::from("value"), ColumnName::from("fraction")],
+            },
+            exprs: vec![value, fraction],
+        };
+        let func = if cont {
+            AggregateFunc::PercentileCont { desc, multiple }
+        } else {
+            AggregateFunc::PercentileDisc { desc, multiple }
+        };
+        Ok((e, func))
+    })
+}
+
+/// Plans an aggregate over the HyperLogLog registers of `value`, as computed
+/// for the given precision, which must be a constant if specified.
+fn hll_aggregate(
+    value: HirScalarExpr,
+    precision: Option<HirScalarExpr>,
+    aggregate: fn(u8) -> AggregateFunc,
+) -> Result<(HirScalarExpr, AggregateFunc), PlanError> {
+    let precision = match precision {
+        None => hll::DEFAULT_PRECISION,
+        Some(precision) => match precision
+            .into_literal_int64()
+            .and_then(|p| u8::try_from(p).ok())
+        {
+            Some(p) if (hll::MIN_PRECISION..=hll::MAX_PRECISION).contains(&p) => p,
+            _ => sql_bail!(
+                "HyperLogLog precision must be an integer literal between {} and {}",
+                hll::MIN_PRECISION,
+                hll::MAX_PRECISION
+            ),
+        },
+    };
+    let e = value.call_unary(UnaryFunc::HllRegister(func::HllRegister { precision }));
+    Ok((e, aggregate(precision)))
+}
+
+/// Fills in the optional `vars` and `silent` arguments of the SQL/JSON path
+/// functions, which default to an empty object and to `silent`.
+fn jsonb_path_args(mut exprs: Vec<HirScalarExpr>, silent: bool) -> Vec<HirScalarExpr> {
+    if exprs.len() < 3 {
+        exprs.push(HirScalarExpr::literal(
+            Datum::Map(DatumMap::empty()),
+            ScalarType::Jsonb,
+        ));
+    }
+    if exprs.len() < 4 {
+        exprs.push(HirScalarExpr::literal(
+            Datum::from(silent),
+            ScalarType::Bool,
+        ));
+    }
+    exprs
+}
+
+/// Parses the path argument of a SQL/JSON path function if it is a constant,
+/// so that it is not parsed again each time the function is evaluated.
+fn jsonb_path_literal(path: &HirScalarExpr) -> Result<Option<JsonPath>, PlanError> {
+    match path.as_literal() {
+        Some(Datum::String(path)) => Ok(Some(JsonPath::parse(path)?)),
+        _ => Ok(None),
+    }
+}
+
+fn jsonb_path(
+    func: fn(Option<JsonPath>) -> VariadicFunc,
+    silent: bool,
+) -> Operation<HirScalarExpr> {
+    Operation::variadic(move |_ecx, exprs| {
+        let path = jsonb_path_literal(&exprs[1])?;
+        Ok(HirScalarExpr::CallVariadic {
+            func: func(path),
+            exprs: jsonb_path_args(exprs, silent),
+        })
+    })
+}
+
+fn jsonb_path_query() -> Operation<TableFuncPlan> {
+    Operation::variadic(|_ecx, exprs| {
+        let path = jsonb_path_literal(&exprs[1])?;
+        Ok(TableFuncPlan {
+            expr: HirRelationExpr::CallTable {
+                func: TableFunc::JsonbPathQuery { path },
+                exprs: jsonb_path_args(exprs, false),
+            },
+            column_names: vec!["jsonb_path_query".into()],
+        })
+    })
+}
+
 fn digest(algorithm: &'static str) -> Operation<HirScalarExpr> {
     Operation::unary(move |_ecx, input| {
         let algorithm = HirScalarExpr::literal(Datum::String(algorithm), ScalarType::String);
@@ -4515,6 +4891,10 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
             params!(UInt32, UInt32) => BitShiftLeftUInt32 => UInt32, oid::FUNC_SHIFT_LEFT_UINT32;
             params!(UInt64, UInt32) => BitShiftLeftUInt64 => UInt64, oid::FUNC_SHIFT_LEFT_UINT64;
             params!(RangeAny, RangeAny) => RangeBefore => Bool, 3893;
+            params!(Inet, Inet) => InetContainedBy => Bool, 931;
+        },
+        "<<=" => Scalar {
+            params!(Inet, Inet) => InetContainedByOrEquals => Bool, 932;
         },
         ">>" => Scalar {
             params!(Int16, Int32) => BitShiftRightInt16 => Int16, 1879;
@@ -4524,6 +4904,10 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
             params!(UInt32, UInt32) => BitShiftRightUInt32 => UInt32, oid::FUNC_SHIFT_RIGHT_UINT32;
             params!(UInt64, UInt32) => BitShiftRightUInt64 => UInt64, oid::FUNC_SHIFT_RIGHT_UINT64;
             params!(RangeAny, RangeAny) => RangeAfter => Bool, 3894;
+            params!(Inet, Inet) => InetContains => Bool, 933;
+        },
+        ">>=" => Scalar {
+            params!(Inet, Inet) => InetContainsOrEquals => Bool, 934;
         },
 
         // ILIKE
@@ -4736,8 +5120,15 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
         "?|" => Scalar {
             params!(MapAny, ScalarType::Array(Box::new(ScalarType::String))) => MapContainsAnyKeys => Bool, oid::OP_CONTAINS_ANY_KEYS_MAP_OID;
         },
+        "@?" => Scalar {
+            params!(Jsonb, String) => jsonb_path(|path| VariadicFunc::JsonbPathExists { path }, true) => Bool, oid::OP_JSONB_PATH_EXISTS_OID;
+        },
+        "@@" => Scalar {
+            params!(Jsonb, String) => jsonb_path(|path| VariadicFunc::JsonbPathMatch { path }, true) => Bool, oid::OP_JSONB_PATH_MATCH_OID;
+        },
         "&&" => Scalar {
             params!(RangeAny, RangeAny) => BinaryFunc::RangeOverlaps => Bool, 3888;
+            params!(Inet, Inet) => BinaryFunc::InetOverlaps => Bool, 3552;
         },
         "&<" => Scalar {
             params!(RangeAny, RangeAny) => BinaryFunc::RangeOverleft => Bool, 3895;
@@ -4767,6 +5158,8 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::Lt => Bool, 2062;
             params!(TimestampTz, TimestampTz) => BinaryFunc::Lt => Bool, 1322;
             params!(Uuid, Uuid) => BinaryFunc::Lt => Bool, 2974;
+            params!(Inet, Inet) => BinaryFunc::Lt => Bool, 1203;
+            params!(MacAddr, MacAddr) => BinaryFunc::Lt => Bool, 1222;
             params!(Interval, Interval) => BinaryFunc::Lt => Bool, 1332;
             params!(Bytes, Bytes) => BinaryFunc::Lt => Bool, 1957;
             params!(String, String) => BinaryFunc::Lt => Bool, 664;
@@ -4778,6 +5171,7 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
             params!(RecordAny, RecordAny) => BinaryFunc::Lt => Bool, 2990;
             params!(MzTimestamp, MzTimestamp)=>BinaryFunc::Lt =>Bool, oid::FUNC_MZ_TIMESTAMP_LT_MZ_TIMESTAMP_OID;
             params!(RangeAny, RangeAny) => BinaryFunc::Lt => Bool, 3884;
+            params!(EnumAny, EnumAny) => BinaryFunc::Lt => Bool, 3518;
         },
         "<=" => Scalar {
             params!(Numeric, Numeric) => BinaryFunc::Lte => Bool, 1755;
@@ -4796,6 +5190,8 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::Lte => Bool, 2063;
             params!(TimestampTz, TimestampTz) => BinaryFunc::Lte => Bool, 1323;
             params!(Uuid, Uuid) => BinaryFunc::Lte => Bool, 2976;
+            params!(Inet, Inet) => BinaryFunc::Lte => Bool, 1204;
+            params!(MacAddr, MacAddr) => BinaryFunc::Lte => Bool, 1223;
             params!(Interval, Interval) => BinaryFunc::Lte => Bool, 1333;
             params!(Bytes, Bytes) => BinaryFunc::Lte => Bool, 1958;
             params!(String, String) => BinaryFunc::Lte => Bool, 665;
@@ -4807,6 +5203,7 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
             params!(RecordAny, RecordAny) => BinaryFunc::Lte => Bool, 2992;
             params!(MzTimestamp, MzTimestamp)=>BinaryFunc::Lte =>Bool, oid::FUNC_MZ_TIMESTAMP_LTE_MZ_TIMESTAMP_OID;
             params!(RangeAny, RangeAny) => BinaryFunc::Lte => Bool, 3885;
+            params!(EnumAny, EnumAny) => BinaryFunc::Lte => Bool, 3520;
         },
         ">" => Scalar {
             params!(Numeric, Numeric) => BinaryFunc::Gt => Bool, 1756;
@@ -4825,6 +5222,8 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::Gt => Bool, 2064;
             params!(TimestampTz, TimestampTz) => BinaryFunc::Gt => Bool, 1324;
             params!(Uuid, Uuid) => BinaryFunc::Gt => Bool, 2975;
+            params!(Inet, Inet) => BinaryFunc::Gt => Bool, 1205;
+            params!(MacAddr, MacAddr) => BinaryFunc::Gt => Bool, 1224;
             params!(Interval, Interval) => BinaryFunc::Gt => Bool, 1334;
             params!(Bytes, Bytes) => BinaryFunc::Gt => Bool, 1959;
             params!(String, String) => BinaryFunc::Gt => Bool, 666;
@@ -4836,6 +5235,7 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
             params!(RecordAny, RecordAny) => BinaryFunc::Gt => Bool, 2991;
             params!(MzTimestamp, MzTimestamp)=>BinaryFunc::Gt =>Bool, oid::FUNC_MZ_TIMESTAMP_GT_MZ_TIMESTAMP_OID;
             params!(RangeAny, RangeAny) => BinaryFunc::Gt => Bool, 3887;
+            params!(EnumAny, EnumAny) => BinaryFunc::Gt => Bool, 3519;
         },
         ">=" => Scalar {
             params!(Numeric, Numeric) => BinaryFunc::Gte => Bool, 1757;
@@ -4854,6 +5254,8 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::Gte => Bool, 2065;
             params!(TimestampTz, TimestampTz) => BinaryFunc::Gte => Bool, 1325;
             params!(Uuid, Uuid) => BinaryFunc::Gte => Bool, 2977;
+            params!(Inet, Inet) => BinaryFunc::Gte => Bool, 1206;
+            params!(MacAddr, MacAddr) => BinaryFunc::Gte => Bool, 1225;
             params!(Interval, Interval) => BinaryFunc::Gte => Bool, 1335;
             params!(Bytes, Bytes) => BinaryFunc::Gte => Bool, 1960;
             params!(String, String) => BinaryFunc::Gte => Bool, 667;
@@ -4865,6 +5267,7 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
             params!(RecordAny, RecordAny) => BinaryFunc::Gte => Bool, 2993;
             params!(MzTimestamp, MzTimestamp)=>BinaryFunc::Gte =>Bool, oid::FUNC_MZ_TIMESTAMP_GTE_MZ_TIMESTAMP_OID;
             params!(RangeAny, RangeAny) => BinaryFunc::Gte => Bool, 3886;
+            params!(EnumAny, EnumAny) => BinaryFunc::Gte => Bool, 3521;
         },
         // Warning!
         // - If you are writing functions here that do not simply use
@@ -4892,6 +5295,8 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::Eq => Bool, 2060;
             params!(TimestampTz, TimestampTz) => BinaryFunc::Eq => Bool, 1320;
             params!(Uuid, Uuid) => BinaryFunc::Eq => Bool, 2972;
+            params!(Inet, Inet) => BinaryFunc::Eq => Bool, 1201;
+            params!(MacAddr, MacAddr) => BinaryFunc::Eq => Bool, 1220;
             params!(Interval, Interval) => BinaryFunc::Eq => Bool, 1330;
             params!(Bytes, Bytes) => BinaryFunc::Eq => Bool, 1955;
             params!(String, String) => BinaryFunc::Eq => Bool, 98;
@@ -4904,6 +5309,7 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
             params!(RecordAny, RecordAny) => BinaryFunc::Eq => Bool, 2988;
             params!(MzTimestamp, MzTimestamp) => BinaryFunc::Eq => Bool, oid::FUNC_MZ_TIMESTAMP_EQ_MZ_TIMESTAMP_OID;
             params!(RangeAny, RangeAny) => BinaryFunc::Eq => Bool, 3882;
+            params!(EnumAny, EnumAny) => BinaryFunc::Eq => Bool, 3516;
             params!(MzAclItem, MzAclItem) => BinaryFunc::Eq => Bool, oid::FUNC_MZ_ACL_ITEM_EQ_MZ_ACL_ITEM_OID;
             params!(AclItem, AclItem) => BinaryFunc::Eq => Bool, 974;
         },
@@ -4924,6 +5330,8 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
             params!(Timestamp, Timestamp) => BinaryFunc::NotEq => Bool, 2061;
             params!(TimestampTz, TimestampTz) => BinaryFunc::NotEq => Bool, 1321;
             params!(Uuid, Uuid) => BinaryFunc::NotEq => Bool, 2973;
+            params!(Inet, Inet) => BinaryFunc::NotEq => Bool, 1202;
+            params!(MacAddr, MacAddr) => BinaryFunc::NotEq => Bool, 1221;
             params!(Interval, Interval) => BinaryFunc::NotEq => Bool, 1331;
             params!(Bytes, Bytes) => BinaryFunc::NotEq => Bool, 1956;
             params!(String, String) => BinaryFunc::NotEq => Bool, 531;
@@ -4935,6 +5343,7 @@ pub static OP_IMPLS: Lazy<BTreeMap<&'static str, Func>> = Lazy::new(|| {
             params!(RecordAny, RecordAny) => BinaryFunc::NotEq => Bool, 2989;
             params!(MzTimestamp, MzTimestamp) => BinaryFunc::NotEq => Bool, oid::FUNC_MZ_TIMESTAMP_NOT_EQ_MZ_TIMESTAMP_OID;
             params!(RangeAny, RangeAny) => BinaryFunc::NotEq => Bool, 3883;
+            params!(EnumAny, EnumAny) => BinaryFunc::NotEq => Bool, 3517;
             params!(MzAclItem, MzAclItem) => BinaryFunc::NotEq => Bool, oid::FUNC_MZ_ACL_ITEM_NOT_EQ_MZ_ACL_ITEM_OID;
         }
     }
@@ -23,7 +23,7 @@ use rdkafka::consumer::{BaseConsumer, Consumer, ConsumerContext};
 use rdkafka::{Offset, TopicPartitionList};
 use tokio::time::Duration;
 
-use crate::ast::Value;
+use crate::ast::{Expr, Value};
 use crate::names::Aug;
 use crate::normalize::generate_extracted_config;
 use crate::plan::with_options::{ImpliedValue, TryFromValue};
@@ -52,7 +52,8 @@ generate_extracted_config!(
     (ProgressGroupIdPrefix, String),
     (Topic, String),
     (TransactionalIdPrefix, String),
-    (LegacyIds, bool)
+    (LegacyIds, bool),
+    (PartitionBy, Expr<Aug>)
 );
 
 impl TryFromValue<Value> for KafkaSinkCompressionType {
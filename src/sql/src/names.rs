@@ -35,7 +35,8 @@ use crate::ast::visit_mut::VisitMut;
 use crate::ast::{
     self, AstInfo, Cte, CteBlock, CteMutRec, DocOnIdentifier, GrantTargetSpecification,
     GrantTargetSpecificationInner, Ident, MutRecBlock, ObjectType, Query, Raw, RawClusterName,
-    RawDataType, RawItemName, Statement, UnresolvedItemName, UnresolvedObjectName,
+    RawDataType, RawItemName, ShowCreateFunctionStatement, Statement, UnresolvedItemName,
+    UnresolvedObjectName,
 };
 use crate::catalog::{
     CatalogError, CatalogItem, CatalogItemType, CatalogType, CatalogTypeDetails, SessionCatalog,
@@ -621,6 +622,7 @@ pub enum ResolvedDataType {
         key_type: Box<ResolvedDataType>,
         value_type: Box<ResolvedDataType>,
     },
+    AnonymousEnum(Vec<String>),
     Named {
         id: GlobalId,
         qualifiers: ItemQualifiers,
@@ -648,6 +650,15 @@ impl AstDisplay for ResolvedDataType {
                 value_type.fmt(f);
                 f.write_str("]");
             }
+            ResolvedDataType::AnonymousEnum(labels) => {
+                let labels: Vec<_> = labels
+                    .iter()
+                    .map(|l| ast::display::escaped_string_literal(l))
+                    .collect();
+                f.write_str("enum(");
+                f.write_node(&ast::display::comma_separated(&labels));
+                f.write_str(")");
+            }
             ResolvedDataType::Named {
                 id,
                 full_name,
@@ -702,6 +713,9 @@ impl ResolvedDataType {
                 res += &value_type.unqualified_item_name();
                 res += "]";
             }
+            ResolvedDataType::AnonymousEnum(_) => {
+                res += "enum";
+            }
             ResolvedDataType::Named { full_name, .. } => {
                 res += &full_name.item;
             }
@@ -730,6 +744,9 @@ impl ResolvedDataType {
                 res += &value_type.human_readable_name();
                 res += "]";
             }
+            ResolvedDataType::AnonymousEnum(_) => {
+                res += "enum";
+            }
             ResolvedDataType::Named { full_name, .. } => {
                 if let RawDatabaseSpecifier::Name(database) = &full_name.database {
                     res += database;
@@ -1219,7 +1236,9 @@ impl<'a> NameResolver<'a> {
             RawDataType::Array(elem_type) => {
                 let name = elem_type.to_string();
                 match self.resolve_data_type(*elem_type)? {
-                    ResolvedDataType::AnonymousList(_) | ResolvedDataType::AnonymousMap { .. } => {
+                    ResolvedDataType::AnonymousList(_)
+                    | ResolvedDataType::AnonymousMap { .. }
+                    | ResolvedDataType::AnonymousEnum(_) => {
                         sql_bail!("type \"{}[]\" does not exist", name)
                     }
                     ResolvedDataType::Named { id, modifiers, .. } => {
@@ -1271,6 +1290,7 @@ impl<'a> NameResolver<'a> {
                     value_type: Box::new(value_type),
                 })
             }
+            RawDataType::Enum(labels) => Ok(ResolvedDataType::AnonymousEnum(labels)),
             RawDataType::Other { name, typ_mod } => {
                 let (full_name, item) = match name {
                     RawItemName::Name(name) => {
@@ -1366,6 +1386,9 @@ impl<'a> NameResolver<'a> {
         match r {
             Ok(item) => {
                 self.ids.insert(item.id());
+                // A call to an overloaded function can resolve to any of its
+                // overloads.
+                self.ids.extend(item.function_overloads());
                 let print_id = !matches!(
                     item.item_type(),
                     CatalogItemType::Func | CatalogItemType::Type
@@ -1496,6 +1519,28 @@ impl<'a> Fold<Raw, Aug> for NameResolver<'a> {
                 }
                 CteBlock::Simple(result_ctes)
             }
+            CteBlock::Recursive(ctes) => {
+                let mut result_ctes = Vec::<Cte<Aug>>::new();
+
+                let initial_id = self.ctes.len();
+
+                for (offset, cte) in ctes.into_iter().enumerate() {
+                    let cte_name = normalize::ident(cte.alias.name.clone());
+                    let local_id = LocalId::new(u64::cast_from(initial_id + offset));
+
+                    // Unlike in a simple block, the name is bound before its
+                    // definition is resolved, so that it can refer to itself.
+                    let shadowed_id = self.ctes.insert(cte_name.clone(), local_id);
+                    shadowed_cte_ids.push((cte_name, shadowed_id));
+
+                    result_ctes.push(Cte {
+                        alias: cte.alias,
+                        id: local_id,
+                        query: self.fold_query(cte.query),
+                    });
+                }
+                CteBlock::Recursive(result_ctes)
+            }
             CteBlock::MutuallyRecursive(MutRecBlock { options, ctes }) => {
                 let mut result_ctes = Vec::<CteMutRec<Aug>>::new();
 
@@ -2003,6 +2048,23 @@ impl<'a> Fold<Raw, Aug> for NameResolver<'a> {
         }
     }
 
+    fn fold_show_create_function_statement(
+        &mut self,
+        node: ShowCreateFunctionStatement<Raw>,
+    ) -> ShowCreateFunctionStatement<Aug> {
+        ShowCreateFunctionStatement {
+            function_name: self.resolve_item_name(
+                node.function_name,
+                // `SHOW CREATE FUNCTION ...` can only refer to function names.
+                ItemResolutionConfig {
+                    functions: true,
+                    types: false,
+                    relations: false,
+                },
+            ),
+        }
+    }
+
     fn fold_expr(&mut self, node: Expr<Raw>) -> Expr<Aug> {
         // Exprs can be recursive, so need the ability to grow the stack.
         mz_ore::stack::maybe_grow(|| mz_sql_parser::ast::fold::fold_expr(self, node))
@@ -2119,7 +2181,48 @@ impl Fold<Aug, Aug> for TransientResolver<'_> {
         node
     }
     fn fold_data_type(&mut self, node: <Aug as AstInfo>::DataType) -> <Aug as AstInfo>::DataType {
-        node
+        match node {
+            ResolvedDataType::AnonymousList(element_type) => {
+                ResolvedDataType::AnonymousList(Box::new(self.fold_data_type(*element_type)))
+            }
+            ResolvedDataType::AnonymousMap {
+                key_type,
+                value_type,
+            } => ResolvedDataType::AnonymousMap {
+                key_type: Box::new(self.fold_data_type(*key_type)),
+                value_type: Box::new(self.fold_data_type(*value_type)),
+            },
+            ResolvedDataType::Named {
+                id,
+                qualifiers,
+                full_name,
+                modifiers,
+                print_id,
+            } => {
+                let ResolvedItemName::Item {
+                    id,
+                    qualifiers,
+                    full_name,
+                    print_id,
+                } = self.fold_item_name(ResolvedItemName::Item {
+                    id,
+                    qualifiers,
+                    full_name,
+                    print_id,
+                })
+                else {
+                    unreachable!("folding preserves the item name variant")
+                };
+                ResolvedDataType::Named {
+                    id,
+                    qualifiers,
+                    full_name,
+                    modifiers,
+                    print_id,
+                }
+            }
+            other => other,
+        }
     }
     fn fold_database_name(
         &mut self,
@@ -2185,6 +2288,7 @@ impl<'ast> Visit<'ast, Aug> for DependencyVisitor {
                 self.visit_data_type(key_type);
                 self.visit_data_type(value_type);
             }
+            ResolvedDataType::AnonymousEnum(_) => {}
             ResolvedDataType::Named { id, .. } => {
                 self.ids.insert(*id);
             }
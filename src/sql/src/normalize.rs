@@ -21,11 +21,12 @@ use mz_repr::{ColumnName, GlobalId};
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::visit_mut::{self, VisitMut};
 use mz_sql_parser::ast::{
-    CreateConnectionStatement, CreateIndexStatement, CreateMaterializedViewStatement,
-    CreateSecretStatement, CreateSinkStatement, CreateSourceStatement, CreateSubsourceStatement,
-    CreateTableStatement, CreateTypeStatement, CreateViewStatement, CreateWebhookSourceStatement,
-    CteBlock, Function, FunctionArgs, Ident, IfExistsBehavior, MutRecBlock, Op, Query, Statement,
-    TableFactor, UnresolvedItemName, UnresolvedSchemaName, Value, ViewDefinition,
+    CreateConnectionStatement, CreateFunctionStatement, CreateIndexStatement,
+    CreateMaterializedViewStatement, CreateSecretStatement, CreateSinkStatement,
+    CreateSourceStatement, CreateSubsourceStatement, CreateTableStatement, CreateTypeStatement,
+    CreateViewStatement, CreateWebhookSourceStatement, CteBlock, Function, FunctionArgs, Ident,
+    IfExistsBehavior, MutRecBlock, Op, Query, Statement, TableFactor, UnresolvedItemName,
+    UnresolvedSchemaName, Value, ViewDefinition,
 };
 
 use crate::names::{Aug, FullItemName, PartialItemName, PartialSchemaName, RawDatabaseSpecifier};
@@ -201,7 +202,7 @@ pub fn create_statement(
         fn visit_query_mut(&mut self, query: &'ast mut Query<Aug>) {
             let n = self.ctes.len();
             match &query.ctes {
-                CteBlock::Simple(ctes) => {
+                CteBlock::Simple(ctes) | CteBlock::Recursive(ctes) => {
                     for cte in ctes.iter() {
                         self.ctes.push(cte.alias.name.clone());
                     }
@@ -219,7 +220,7 @@ pub fn create_statement(
         fn visit_function_mut(&mut self, func: &'ast mut Function<Aug>) {
             match &mut func.args {
                 FunctionArgs::Star => (),
-                FunctionArgs::Args { args, order_by } => {
+                FunctionArgs::Args { args, order_by, .. } => {
                     for arg in args {
                         self.visit_expr_mut(arg);
                     }
@@ -418,6 +419,25 @@ pub fn create_statement(
                 return Err(err);
             }
         }
+        Statement::CreateFunction(CreateFunctionStatement {
+            name,
+            if_exists,
+            params,
+            returns,
+            body,
+        }) => {
+            *name = allocate_name(name)?;
+            let mut normalizer = QueryNormalizer::new();
+            for param in params {
+                normalizer.visit_function_parameter_mut(param);
+            }
+            normalizer.visit_function_returns_mut(returns);
+            normalizer.visit_query_mut(body);
+            if let Some(err) = normalizer.err {
+                return Err(err);
+            }
+            *if_exists = IfExistsBehavior::Error;
+        }
         Statement::CreateSecret(CreateSecretStatement {
             name,
             if_not_exists,
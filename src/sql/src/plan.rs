@@ -49,7 +49,7 @@ use mz_sql_parser::ast::{
     TransactionIsolationLevel, TransactionMode, WithOptionValue,
 };
 use mz_storage_types::connections::inline::ReferencedConnection;
-use mz_storage_types::sinks::{SinkEnvelope, StorageSinkConnection};
+use mz_storage_types::sinks::{S3SinkFormat, SinkEnvelope, StorageSinkConnection};
 use mz_storage_types::sources::{SourceDesc, Timeline};
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
@@ -62,6 +62,7 @@ use crate::catalog::{
     CatalogType, DefaultPrivilegeAclItem, DefaultPrivilegeObject, IdReference, ObjectType,
     RoleAttributes,
 };
+use crate::func::SqlFunction;
 use crate::names::{
     Aug, CommentObjectId, FullItemName, ObjectId, QualifiedItemName, ResolvedDatabaseSpecifier,
     ResolvedIds, SchemaSpecifier, SystemObjectId,
@@ -121,6 +122,7 @@ pub enum Plan {
     CreateMaterializedView(CreateMaterializedViewPlan),
     CreateIndex(CreateIndexPlan),
     CreateType(CreateTypePlan),
+    CreateFunction(CreateFunctionPlan),
     Comment(CommentPlan),
     DiscardTemp,
     DiscardAll,
@@ -253,6 +255,7 @@ impl Plan {
             | StatementKind::CreateWebhookSource => &[PlanKind::CreateSource],
             StatementKind::CreateTable => &[PlanKind::CreateTable],
             StatementKind::CreateType => &[PlanKind::CreateType],
+            StatementKind::CreateFunction => &[PlanKind::CreateFunction],
             StatementKind::CreateView => &[PlanKind::CreateView],
             StatementKind::Deallocate => &[PlanKind::Deallocate],
             StatementKind::Declare => &[PlanKind::Declare],
@@ -263,6 +266,7 @@ impl Plan {
             StatementKind::Execute => &[PlanKind::Execute],
             StatementKind::ExplainPlan => &[PlanKind::ExplainPlan],
             StatementKind::ExplainPushdown => &[PlanKind::ExplainPushdown],
+            StatementKind::ExplainAnalyze => &[PlanKind::Select],
             StatementKind::ExplainTimestamp => &[PlanKind::ExplainTimestamp],
             StatementKind::ExplainSinkSchema => &[PlanKind::ExplainSinkSchema],
             StatementKind::Fetch => &[PlanKind::Fetch],
@@ -312,6 +316,7 @@ impl Plan {
             Plan::CreateMaterializedView(_) => "create materialized view",
             Plan::CreateIndex(_) => "create index",
             Plan::CreateType(_) => "create type",
+            Plan::CreateFunction(_) => "create function",
             Plan::Comment(_) => "comment",
             Plan::DiscardTemp => "discard temp",
             Plan::DiscardAll => "discard all",
@@ -413,6 +418,7 @@ impl Plan {
                 MutationKind::Insert => "insert into select",
                 MutationKind::Update => "update",
                 MutationKind::Delete => "delete",
+                MutationKind::Upsert => "insert on conflict",
             },
             Plan::Prepare(_) => "prepare",
             Plan::Execute(_) => "execute",
@@ -646,6 +652,14 @@ pub struct CreateTypePlan {
     pub typ: Type,
 }
 
+#[derive(Debug)]
+pub struct CreateFunctionPlan {
+    pub name: QualifiedItemName,
+    pub function: Function,
+    /// The ID of the function that this function is replacing, if any.
+    pub replace: Option<GlobalId>,
+}
+
 #[derive(Debug)]
 pub struct DropObjectsPlan {
     /// The IDs of only the objects directly referenced in the `DROP` statement.
@@ -794,7 +808,7 @@ pub struct CopyToPlan {
     pub connection: mz_storage_types::connections::Connection<ReferencedConnection>,
     /// The ID of the connection.
     pub connection_id: GlobalId,
-    pub format_params: CopyFormatParams<'static>,
+    pub format: S3SinkFormat,
     pub max_file_size: u64,
 }
 
@@ -924,7 +938,16 @@ pub struct ExplainTimestampPlan {
 #[derive(Debug)]
 pub struct ExplainSinkSchemaPlan {
     pub sink_from: GlobalId,
-    pub json_schema: String,
+    pub schema: SinkSchema,
+}
+
+/// A schema generated for the keys or values of a sink.
+#[derive(Debug)]
+pub enum SinkSchema {
+    /// An Avro schema, in its JSON representation.
+    Avro(String),
+    /// A Protobuf schema, as the text of a `.proto` file.
+    Protobuf(String),
 }
 
 #[derive(Debug)]
@@ -1434,6 +1457,12 @@ pub struct Type {
     pub inner: CatalogType<IdReference>,
 }
 
+#[derive(Clone, Debug)]
+pub struct Function {
+    pub create_sql: String,
+    pub inner: SqlFunction,
+}
+
 /// Specifies when a `Peek` or `Subscribe` should occur.
 #[derive(Deserialize, Clone, Debug, PartialEq)]
 pub enum QueryWhen {
@@ -1510,6 +1539,10 @@ pub enum MutationKind {
     Insert,
     Update,
     Delete,
+    /// `INSERT ... ON CONFLICT DO UPDATE`. Rows produced by the read carry a
+    /// trailing boolean column that says whether the row is inserted (`true`)
+    /// or retracted (`false`).
+    Upsert,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -1517,6 +1550,7 @@ pub enum CopyFormat {
     Text,
     Csv,
     Binary,
+    Parquet,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -16,6 +16,7 @@ use std::{fmt, io};
 
 use itertools::Itertools;
 use mz_expr::EvalError;
+use mz_mongodb_util::MongoDbError;
 use mz_mysql_util::MySqlError;
 use mz_ore::error::ErrorExt;
 use mz_ore::stack::RecursionLimitError;
@@ -30,6 +31,7 @@ use mz_repr::{strconv, ColumnName, GlobalId};
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::{IdentError, UnresolvedItemName};
 use mz_sql_parser::parser::{ParserError, ParserStatementError};
+use mz_sql_server_util::SqlServerError;
 
 use crate::catalog::{
     CatalogError, CatalogItemType, ErrorMessageObjectDescription, SystemObjectType,
@@ -40,7 +42,8 @@ use crate::plan::scope::ScopeItem;
 use crate::plan::ObjectType;
 use crate::pure::error::{
     CsrPurificationError, KafkaSinkPurificationError, KafkaSourcePurificationError,
-    LoadGeneratorSourcePurificationError, MySqlSourcePurificationError, PgSourcePurificationError,
+    LoadGeneratorSourcePurificationError, MongoDbSourcePurificationError,
+    MySqlSourcePurificationError, PgSourcePurificationError, SqlServerSourcePurificationError,
 };
 use crate::session::vars::VarError;
 
@@ -148,6 +151,10 @@ pub enum PlanError {
     AlterViewOnMaterializedView(String),
     ShowCreateViewOnMaterializedView(String),
     ExplainViewOnMaterializedView(String),
+    ExplainAnalyzeOnOtherCluster {
+        name: String,
+        cluster: String,
+    },
     UnacceptableTimelineName(String),
     FetchingCsrSchemaFailed {
         schema_lookup: String,
@@ -159,6 +166,12 @@ pub enum PlanError {
     MySqlConnectionErr {
         cause: Arc<MySqlError>,
     },
+    SqlServerConnectionErr {
+        cause: Arc<SqlServerError>,
+    },
+    MongoDbConnectionErr {
+        cause: Arc<MongoDbError>,
+    },
     SubsourceNameConflict {
         name: UnresolvedItemName,
         upstream_references: Vec<UnresolvedItemName>,
@@ -210,6 +223,9 @@ pub enum PlanError {
         name: String,
         item_type: CatalogItemType,
     },
+    UpstreamEnumTypeConflict {
+        name: String,
+    },
     ManagedCluster {
         cluster_name: String,
     },
@@ -239,6 +255,8 @@ pub enum PlanError {
     LoadGeneratorSourcePurification(LoadGeneratorSourcePurificationError),
     CsrPurification(CsrPurificationError),
     MySqlSourcePurification(MySqlSourcePurificationError),
+    SqlServerSourcePurification(SqlServerSourcePurificationError),
+    MongoDbSourcePurification(MongoDbSourcePurificationError),
     MissingName(CatalogItemType),
     InvalidRefreshAt,
     InvalidRefreshEveryAlignedTo,
@@ -305,6 +323,8 @@ impl PlanError {
             Self::PgSourcePurification(e) => e.detail(),
             Self::KafkaSourcePurification(e) => e.detail(),
             Self::LoadGeneratorSourcePurification(e) => e.detail(),
+            Self::SqlServerSourcePurification(e) => e.detail(),
+            Self::MongoDbSourcePurification(e) => e.detail(),
             Self::CsrPurification(e) => e.detail(),
             Self::KafkaSinkPurification(e) => e.detail(),
             Self::CreateReplicaFailStorageObjects { current_replica_count: current, internal_replica_count: internal, hypothetical_replica_count: target } => {
@@ -359,9 +379,16 @@ impl PlanError {
             Self::ExplainViewOnMaterializedView(_) => {
                 Some("Use EXPLAIN [...] MATERIALIZED VIEW to explain a materialized view.".into())
             }
+            Self::ExplainAnalyzeOnOtherCluster { cluster, .. } => {
+                Some(format!("Use SET cluster = {cluster} to run EXPLAIN ANALYZE on the cluster maintaining the object."))
+            }
             Self::UnacceptableTimelineName(_) => {
                 Some("The prefix \"mz_\" is reserved for system timelines.".into())
             }
+            Self::UpstreamEnumTypeConflict { .. } => Some(
+                "Drop or rename the existing object, or use TEXT COLUMNS to ingest the column as text."
+                    .into(),
+            ),
             Self::PostgresConnectionErr { cause } => {
                 if let Some(cause) = cause.source() {
                     if let Some(cause) = cause.downcast_ref::<io::Error>() {
@@ -408,6 +435,8 @@ impl PlanError {
             Self::PgSourcePurification(e) => e.hint(),
             Self::KafkaSourcePurification(e) => e.hint(),
             Self::LoadGeneratorSourcePurification(e) => e.hint(),
+            Self::SqlServerSourcePurification(e) => e.hint(),
+            Self::MongoDbSourcePurification(e) => e.hint(),
             Self::CsrPurification(e) => e.hint(),
             Self::KafkaSinkPurification(e) => e.hint(),
             Self::UnknownColumn { table, similar, .. } => {
@@ -553,6 +582,9 @@ impl fmt::Display for PlanError {
             | Self::AlterViewOnMaterializedView(name)
             | Self::ShowCreateViewOnMaterializedView(name)
             | Self::ExplainViewOnMaterializedView(name) => write!(f, "{name} is not a view"),
+            Self::ExplainAnalyzeOnOtherCluster { name, cluster } => {
+                write!(f, "{name} is maintained on cluster {cluster}, not the active cluster")
+            }
             Self::FetchingCsrSchemaFailed { schema_lookup, .. } => {
                 write!(f, "failed to fetch schema {schema_lookup} from schema registry")
             }
@@ -562,6 +594,12 @@ impl fmt::Display for PlanError {
             Self::MySqlConnectionErr { cause } => {
                 write!(f, "failed to connect to MySQL database: {}", cause)
             }
+            Self::SqlServerConnectionErr { cause } => {
+                write!(f, "failed to connect to SQL Server database: {}", cause)
+            }
+            Self::MongoDbConnectionErr { cause } => {
+                write!(f, "failed to connect to MongoDB database: {}", cause)
+            }
             Self::SubsourceNameConflict {
                 name , upstream_references: _,
             } => {
@@ -629,6 +667,11 @@ impl fmt::Display for PlanError {
             Self::InvalidPrivatelinkAvailabilityZone { name, ..} => write!(f, "invalid AWS PrivateLink availability zone {}", name.quoted()),
             Self::InvalidSchemaName => write!(f, "no schema has been selected to create in"),
             Self::ItemAlreadyExists { name, item_type } => write!(f, "{item_type} {} already exists", name.quoted()),
+            Self::UpstreamEnumTypeConflict { name } => write!(
+                f,
+                "cannot map upstream enum onto {}: an object of that name already exists that is not an enum with the same labels",
+                name.quoted()
+            ),
             Self::ManagedCluster {cluster_name} => write!(f, "cannot modify managed cluster {cluster_name}"),
             Self::InvalidKeysInSubscribeEnvelopeUpsert => {
                 write!(f, "invalid keys in SUBSCRIBE ENVELOPE UPSERT (KEY (..))")
@@ -669,6 +712,8 @@ impl fmt::Display for PlanError {
             Self::KafkaSinkPurification(e) => write!(f, "KAFKA sink validation: {}", e),
             Self::CsrPurification(e) => write!(f, "CONFLUENT SCHEMA REGISTRY validation: {}", e),
             Self::MySqlSourcePurification(e) => write!(f, "MYSQL source validation: {}", e),
+            Self::SqlServerSourcePurification(e) => write!(f, "SQL SERVER source validation: {}", e),
+            Self::MongoDbSourcePurification(e) => write!(f, "MONGODB source validation: {}", e),
             Self::MangedReplicaName(name) => {
                 write!(f, "{name} is reserved for replicas of managed clusters")
             }
@@ -805,6 +850,18 @@ impl From<MySqlError> for PlanError {
     }
 }
 
+impl From<SqlServerError> for PlanError {
+    fn from(e: SqlServerError) -> PlanError {
+        PlanError::SqlServerConnectionErr { cause: Arc::new(e) }
+    }
+}
+
+impl From<MongoDbError> for PlanError {
+    fn from(e: MongoDbError) -> PlanError {
+        PlanError::MongoDbConnectionErr { cause: Arc::new(e) }
+    }
+}
+
 impl From<VarError> for PlanError {
     fn from(e: VarError) -> Self {
         PlanError::VarError(e)
@@ -847,6 +904,18 @@ impl From<MySqlSourcePurificationError> for PlanError {
     }
 }
 
+impl From<SqlServerSourcePurificationError> for PlanError {
+    fn from(e: SqlServerSourcePurificationError) -> Self {
+        PlanError::SqlServerSourcePurification(e)
+    }
+}
+
+impl From<MongoDbSourcePurificationError> for PlanError {
+    fn from(e: MongoDbSourcePurificationError) -> Self {
+        PlanError::MongoDbSourcePurification(e)
+    }
+}
+
 impl From<IdentError> for PlanError {
     fn from(e: IdentError) -> Self {
         PlanError::InvalidIdent(e)
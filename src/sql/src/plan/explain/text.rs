@@ -186,6 +186,7 @@ impl HirRelationExpr {
                 right,
                 on,
                 kind,
+                hints: _,
             } => {
                 if on.is_literal_true() && kind == &JoinKind::Inner {
                     write!(f, "{}CrossJoin", ctx.indent)?;
@@ -19,7 +19,7 @@ use std::{fmt, mem};
 use itertools::Itertools;
 use mz_expr::virtual_syntax::{AlgExcept, Except, IR};
 use mz_expr::visit::{Visit, VisitChildren};
-use mz_expr::{func, CollectionPlan, Id, LetRecLimit, RowSetFinishing};
+use mz_expr::{func, CollectionPlan, Id, JoinHints, LetRecLimit, RowSetFinishing};
 // these happen to be unchanged at the moment, but there might be additions later
 use mz_expr::AggregateFunc::WindowAggregate;
 pub use mz_expr::{
@@ -142,6 +142,8 @@ pub enum HirRelationExpr {
         right: Box<HirRelationExpr>,
         on: HirScalarExpr,
         kind: JoinKind,
+        /// User-provided hints for the implementation of the join.
+        hints: JoinHints,
     },
     /// Unlike MirRelationExpr, when `key` is empty AND `input` is empty this returns
     /// a single row with the aggregates evaluated over empty groups, rather than returning zero
@@ -1035,6 +1037,35 @@ pub enum AggregateFunc {
     StringAgg {
         order_by: Vec<ColumnOrder>,
     },
+    /// Computes the continuous percentile of `Datum::List`s whose first
+    /// element is a `Float64` value and whose second element is the requested
+    /// fraction, or an array of fractions if `multiple`.
+    PercentileCont {
+        desc: bool,
+        multiple: bool,
+    },
+    /// Computes the discrete percentile of `Datum::List`s whose first element
+    /// is the value and whose second element is the requested fraction, or an
+    /// array of fractions if `multiple`.
+    PercentileDisc {
+        desc: bool,
+        multiple: bool,
+    },
+    /// Computes the most frequent non-null input value.
+    Mode {
+        desc: bool,
+    },
+    /// Estimates the number of distinct values from their HyperLogLog
+    /// registers, as computed by `UnaryFunc::HllRegister`.
+    ApproxCountDistinct {
+        precision: u8,
+    },
+    /// Builds a HyperLogLog sketch from the registers of its input values.
+    HllSketch {
+        precision: u8,
+    },
+    /// Merges HyperLogLog sketches.
+    HllUnion,
     /// Accumulates any number of `Datum::Dummy`s into `Datum::Dummy`.
     ///
     /// Useful for removing an expensive aggregation while maintaining the shape
@@ -1108,6 +1139,20 @@ impl AggregateFunc {
                 mz_expr::AggregateFunc::ListConcat { order_by }
             }
             AggregateFunc::StringAgg { order_by } => mz_expr::AggregateFunc::StringAgg { order_by },
+            AggregateFunc::PercentileCont { desc, multiple } => {
+                mz_expr::AggregateFunc::PercentileCont { desc, multiple }
+            }
+            AggregateFunc::PercentileDisc { desc, multiple } => {
+                mz_expr::AggregateFunc::PercentileDisc { desc, multiple }
+            }
+            AggregateFunc::Mode { desc } => mz_expr::AggregateFunc::Mode { desc },
+            AggregateFunc::ApproxCountDistinct { precision } => {
+                mz_expr::AggregateFunc::ApproxCountDistinct { precision }
+            }
+            AggregateFunc::HllSketch { precision } => {
+                mz_expr::AggregateFunc::HllSketch { precision }
+            }
+            AggregateFunc::HllUnion => mz_expr::AggregateFunc::HllUnion,
             AggregateFunc::Dummy => mz_expr::AggregateFunc::Dummy,
         }
     }
@@ -1138,6 +1183,8 @@ impl AggregateFunc {
             AggregateFunc::JsonbAgg { .. } => ScalarType::Jsonb,
             AggregateFunc::JsonbObjectAgg { .. } => ScalarType::Jsonb,
             AggregateFunc::StringAgg { .. } => ScalarType::String,
+            AggregateFunc::ApproxCountDistinct { .. } => ScalarType::Int64,
+            AggregateFunc::HllSketch { .. } | AggregateFunc::HllUnion => ScalarType::Bytes,
             AggregateFunc::SumInt16 | AggregateFunc::SumInt32 => ScalarType::Int64,
             AggregateFunc::SumInt64 => ScalarType::Numeric {
                 max_scale: Some(NumericMaxScale::ZERO),
@@ -1157,10 +1204,26 @@ impl AggregateFunc {
                     _ => unreachable!(),
                 }
             }
+            AggregateFunc::PercentileCont { multiple, .. }
+            | AggregateFunc::PercentileDisc { multiple, .. } => {
+                // The input is a (value, fraction) Record.
+                let value_type = match input_type.scalar_type {
+                    ScalarType::Record { fields, .. } => fields[0].1.scalar_type.clone(),
+                    _ => unreachable!(),
+                };
+                if *multiple {
+                    ScalarType::Array(Box::new(value_type))
+                } else {
+                    value_type
+                }
+            }
             _ => input_type.scalar_type,
         };
         // max/min/sum return null on empty sets
-        let nullable = !matches!(self, AggregateFunc::Count);
+        let nullable = !matches!(
+            self,
+            AggregateFunc::Count | AggregateFunc::ApproxCountDistinct { .. }
+        );
         scalar_type.nullable(nullable)
     }
 
@@ -1176,6 +1239,16 @@ impl AggregateFunc {
                 | StringAgg { .. }
         )
     }
+
+    /// Reports whether this is an ordered-set aggregate, which must be called
+    /// with `WITHIN GROUP (ORDER BY ...)`.
+    pub fn is_ordered_set(&self) -> bool {
+        use AggregateFunc::*;
+        matches!(
+            self,
+            PercentileCont { .. } | PercentileDisc { .. } | Mode { .. }
+        )
+    }
 }
 
 impl HirRelationExpr {
@@ -1487,6 +1560,7 @@ impl HirRelationExpr {
                 right: Box::new(right),
                 on,
                 kind,
+                hints: JoinHints::default(),
             }
         }
     }
@@ -2003,6 +2077,7 @@ impl VisitChildren<Self> for HirRelationExpr {
                 right,
                 on: _,
                 kind: _,
+                hints: _,
             } => {
                 f(left);
                 f(right);
@@ -2090,6 +2165,7 @@ impl VisitChildren<Self> for HirRelationExpr {
                 right,
                 on: _,
                 kind: _,
+                hints: _,
             } => {
                 f(left);
                 f(right);
@@ -2177,6 +2253,7 @@ impl VisitChildren<Self> for HirRelationExpr {
                 right,
                 on: _,
                 kind: _,
+                hints: _,
             } => {
                 f(left)?;
                 f(right)?;
@@ -2265,6 +2342,7 @@ impl VisitChildren<Self> for HirRelationExpr {
                 right,
                 on: _,
                 kind: _,
+                hints: _,
             } => {
                 f(left)?;
                 f(right)?;
@@ -2348,6 +2426,7 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
                 right: _,
                 on,
                 kind: _,
+                hints: _,
             } => f(on),
             Reduce {
                 input: _,
@@ -2424,6 +2503,7 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
                 right: _,
                 on,
                 kind: _,
+                hints: _,
             } => f(on),
             Reduce {
                 input: _,
@@ -2497,6 +2577,7 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
                 right: _,
                 on,
                 kind: _,
+                hints: _,
             } => f(on)?,
             Reduce {
                 input: _,
@@ -2571,6 +2652,7 @@ impl VisitChildren<HirScalarExpr> for HirRelationExpr {
                 right: _,
                 on,
                 kind: _,
+                hints: _,
             } => f(on)?,
             Reduce {
                 input: _,
@@ -524,6 +524,7 @@ impl HirRelationExpr {
                     right,
                     on,
                     kind,
+                    hints: _,
                 } if right.is_correlated() => {
                     // A correlated join is a join in which the right expression has
                     // access to the columns in the left expression. It turns out
@@ -585,6 +586,7 @@ impl HirRelationExpr {
                     right,
                     on,
                     kind,
+                    hints,
                 } => {
                     if config.enable_variadic_left_join_lowering {
                         // Attempt to extract a stack of left joins.
@@ -596,6 +598,7 @@ impl HirRelationExpr {
                                 right,
                                 on,
                                 kind: JoinKind::LeftOuter,
+                                ..
                             } = &**left_test
                             {
                                 rights.push((&**right, on));
@@ -651,6 +654,7 @@ impl HirRelationExpr {
                                 vec![get_left.clone(), get_right.clone()],
                                 (0..oa).map(|i| vec![(0, i), (1, i)]).collect(),
                             )
+                            .with_join_hints(hints)
                             // Project away the repeated copy of get_outer's columns.
                             .project(
                                 (0..(oa + la))
@@ -40,7 +40,10 @@ use std::{iter, mem};
 
 use itertools::Itertools;
 use mz_expr::virtual_syntax::AlgExcept;
-use mz_expr::{func as expr_func, Id, LetRecLimit, LocalId, MirScalarExpr, RowSetFinishing};
+use mz_expr::{
+    func as expr_func, Id, JoinHints, JoinImplementationHint, LetRecLimit, LocalId, MirScalarExpr,
+    RowSetFinishing,
+};
 use mz_ore::collections::CollectionExt;
 use mz_ore::option::FallibleMapExt;
 use mz_ore::stack::{CheckedRecursion, RecursionGuard};
@@ -59,8 +62,9 @@ use mz_sql_parser::ast::visit_mut::{self, VisitMut};
 use mz_sql_parser::ast::{
     visit, AsOf, Assignment, AstInfo, CreateWebhookSourceBody, CreateWebhookSourceCheck,
     CreateWebhookSourceHeader, CreateWebhookSourceSecret, CteBlock, DeleteStatement, Distinct,
-    Expr, Function, FunctionArgs, HomogenizingFunction, Ident, InsertSource, IsExprConstruct, Join,
-    JoinConstraint, JoinOperator, Limit, MutRecBlock, MutRecBlockOption, MutRecBlockOptionName,
+    Expr, Function, FunctionArgs, FunctionParameter, FunctionReturns, GroupingElement,
+    HomogenizingFunction, Ident, InsertSource, IsExprConstruct, Join, JoinConstraint, JoinOperator,
+    Limit, MutRecBlock, MutRecBlockOption, MutRecBlockOptionName, OnConflict, OnConflictAction,
     OrderByExpr, Query, Select, SelectItem, SelectOption, SelectOptionName, SetExpr, SetOperator,
     ShowStatement, SubscriptPosition, TableAlias, TableFactor, TableWithJoins, UnresolvedItemName,
     UpdateStatement, Value, Values, WindowFrame, WindowFrameBound, WindowFrameUnits, WindowSpec,
@@ -69,7 +73,7 @@ use mz_sql_parser::ident;
 use uuid::Uuid;
 
 use crate::catalog::{CatalogItemType, CatalogType, SessionCatalog};
-use crate::func::{self, Func, FuncSpec};
+use crate::func::{self, Func, FuncSpec, SqlFunction, SqlFunctionBody};
 use crate::names::{
     Aug, FullItemName, PartialItemName, ResolvedDataType, ResolvedItemName, SchemaSpecifier,
 };
@@ -202,12 +206,14 @@ pub fn plan_insert_query(
     table_name: ResolvedItemName,
     columns: Vec<Ident>,
     source: InsertSource<Aug>,
+    on_conflict: Option<OnConflict<Aug>>,
     returning: Vec<SelectItem<Aug>>,
 ) -> Result<
     (
         GlobalId,
         HirRelationExpr,
         PlannedRootQuery<Vec<HirScalarExpr>>,
+        bool,
     ),
     PlanError,
 > {
@@ -352,6 +358,14 @@ pub fn plan_insert_query(
         }
     }
 
+    let values = expr.map(map_exprs).project(project_key);
+    let (values, upsert) = match on_conflict {
+        Some(on_conflict) => {
+            plan_insert_on_conflict(&qcx, table_name.clone(), &desc, values, on_conflict)?
+        }
+        None => (values, false),
+    };
+
     let returning = {
         let (scope, typ) = if let ResolvedItemName::Item { full_name, .. } = table_name {
             let desc = table.desc(&full_name)?;
@@ -403,11 +417,246 @@ pub fn plan_insert_query(
         }
     };
 
-    Ok((
-        table.id(),
-        expr.map(map_exprs).project(project_key),
-        returning,
-    ))
+    Ok((table.id(), values, returning, upsert))
+}
+
+/// Plans the `ON CONFLICT` clause of an `INSERT` into `table_name`, given the
+/// rows proposed for insertion, `values`, in table column order.
+///
+/// `DO NOTHING` removes the proposed rows that conflict with an existing row
+/// or with one another, and so remains a plain insert. `DO UPDATE` produces
+/// the read portion of a read-then-write, and the returned boolean is `true`:
+/// each output row carries a trailing boolean column that is `true` for rows
+/// to insert and `false` for rows to retract. Proposed rows that share a key
+/// produce an output row whose trailing column is `NULL`, which the write
+/// rejects, as PostgreSQL does.
+fn plan_insert_on_conflict(
+    qcx: &QueryContext,
+    table_name: ResolvedItemName,
+    desc: &RelationDesc,
+    values: HirRelationExpr,
+    OnConflict { target, action }: OnConflict<Aug>,
+) -> Result<(HirRelationExpr, bool), PlanError> {
+    let arity = desc.arity();
+
+    // Determine the keys that arbitrate conflicts.
+    let keys = match target {
+        Some(target) => {
+            let mut columns = BTreeSet::new();
+            for c in target {
+                let name = normalize::column_name(c);
+                match desc.get_by_name(&name) {
+                    Some((idx, _)) => {
+                        columns.insert(idx);
+                    }
+                    None => sql_bail!("column {} does not exist", name.as_str().quoted()),
+                }
+            }
+            match desc
+                .typ()
+                .keys
+                .iter()
+                .find(|key| key.iter().copied().collect::<BTreeSet<_>>() == columns)
+            {
+                Some(key) => vec![key.clone()],
+                None => sql_bail!(
+                    "there is no unique or exclusion constraint matching the ON CONFLICT specification"
+                ),
+            }
+        }
+        None => {
+            if let OnConflictAction::DoUpdate { .. } = action {
+                sql_bail!(
+                    "ON CONFLICT DO UPDATE requires inference specification or constraint name"
+                );
+            }
+            desc.typ().keys.clone()
+        }
+    };
+
+    // Tests whether the columns of `key` at `level` equal those at level 0.
+    let key_matches = |key: &[usize], level: usize| {
+        HirScalarExpr::variadic_and(
+            key.iter()
+                .map(|k| {
+                    HirScalarExpr::Column(ColumnRef { level, column: *k })
+                        .call_binary(HirScalarExpr::column(*k), BinaryFunc::Eq)
+                })
+                .collect(),
+        )
+    };
+    // Tests whether the proposed row conflicts with an existing row.
+    let conflicts = |keys: &[Vec<usize>]| -> Result<HirScalarExpr, PlanError> {
+        let (get, _scope) = qcx.resolve_table_name(table_name.clone())?;
+        let on = HirScalarExpr::variadic_or(keys.iter().map(|key| key_matches(key, 1)).collect());
+        Ok(get.filter(vec![on]).exists())
+    };
+
+    match action {
+        OnConflictAction::DoNothing => {
+            if keys.is_empty() {
+                return Ok((values, false));
+            }
+            let mut values = values.filter(vec![conflicts(&keys)?.not()]);
+            // Of the proposed rows that conflict with one another, only insert
+            // the first in an arbitrary but deterministic order.
+            let order_key: Vec<_> = (0..arity)
+                .map(|column| ColumnOrder {
+                    column,
+                    desc: false,
+                    nulls_last: true,
+                })
+                .collect();
+            for key in keys {
+                values = HirRelationExpr::top_k(
+                    values,
+                    key,
+                    order_key.clone(),
+                    Some(HirScalarExpr::literal(Datum::Int64(1), ScalarType::Int64)),
+                    0,
+                    None,
+                );
+            }
+            Ok((values, false))
+        }
+        OnConflictAction::DoUpdate {
+            assignments,
+            selection,
+        } => {
+            let key = keys.into_element();
+
+            // Pair each existing row with the proposed row that conflicts with
+            // it. The proposed row is visible as `excluded`.
+            let (get, table_scope) = qcx.resolve_table_name(table_name.clone())?;
+            let excluded_scope = Scope::from_source(
+                Some(PartialItemName {
+                    database: None,
+                    schema: None,
+                    item: "excluded".into(),
+                }),
+                desc.iter_names().cloned(),
+            );
+            let scope = table_scope.product(excluded_scope)?;
+            let on = HirScalarExpr::variadic_and(
+                key.iter()
+                    .map(|k| {
+                        HirScalarExpr::column(*k)
+                            .call_binary(HirScalarExpr::column(arity + *k), BinaryFunc::Eq)
+                    })
+                    .collect(),
+            );
+            let mut conflicting = get.join(values.clone(), on, JoinKind::Inner);
+            let relation_type = qcx.relation_type(&conflicting);
+
+            if let Some(expr) = selection {
+                let ecx = &ExprContext {
+                    qcx,
+                    name: "WHERE clause",
+                    scope: &scope,
+                    relation_type: &relation_type,
+                    allow_aggregates: false,
+                    allow_subqueries: true,
+                    allow_parameters: true,
+                    allow_windows: false,
+                };
+                let expr = plan_expr(ecx, &expr)?.type_as(ecx, &ScalarType::Bool)?;
+                conflicting = conflicting.filter(vec![expr]);
+            }
+
+            let mut sets = BTreeMap::new();
+            for Assignment { id, value } in assignments {
+                // Get the index and type of the column.
+                let name = normalize::column_name(id);
+                match desc.get_by_name(&name) {
+                    Some((idx, typ)) => {
+                        let ecx = &ExprContext {
+                            qcx,
+                            name: "SET clause",
+                            scope: &scope,
+                            relation_type: &relation_type,
+                            allow_aggregates: false,
+                            allow_subqueries: false,
+                            allow_parameters: true,
+                            allow_windows: false,
+                        };
+                        let expr = plan_expr(ecx, &value)?.cast_to(
+                            ecx,
+                            CastContext::Assignment,
+                            &typ.scalar_type,
+                        )?;
+
+                        if sets.insert(idx, expr).is_some() {
+                            sql_bail!("column {} set twice", name)
+                        }
+                    }
+                    None => sql_bail!("unknown column {}", name),
+                };
+            }
+
+            // Retract the existing rows...
+            let retractions = conflicting
+                .clone()
+                .map(vec![HirScalarExpr::literal_false()])
+                .project((0..arity).chain(iter::once(2 * arity)).collect());
+
+            // ...and insert their updated versions.
+            let mut map_exprs = vec![];
+            let mut project_key = Vec::with_capacity(arity + 1);
+            for idx in 0..arity {
+                match sets.remove(&idx) {
+                    Some(expr) => {
+                        project_key.push(2 * arity + map_exprs.len());
+                        map_exprs.push(expr);
+                    }
+                    None => project_key.push(idx),
+                }
+            }
+            project_key.push(2 * arity + map_exprs.len());
+            map_exprs.push(HirScalarExpr::literal_true());
+            let updates = conflicting.map(map_exprs).project(project_key);
+
+            // Proposed rows without a conflict are inserted as is.
+            let inserts = values
+                .clone()
+                .filter(vec![conflicts(&[key.clone()])?.not()])
+                .map(vec![HirScalarExpr::literal_true()]);
+
+            // Proposed rows that share a key with one another cannot all be
+            // applied.
+            let key_arity = key.len();
+            let duplicates = values
+                .filter(
+                    key.iter()
+                        .map(|k| HirScalarExpr::column(*k).call_is_null().not())
+                        .collect(),
+                )
+                .reduce(
+                    key,
+                    vec![AggregateExpr {
+                        func: AggregateFunc::Count,
+                        expr: Box::new(HirScalarExpr::literal_true()),
+                        distinct: false,
+                    }],
+                    None,
+                )
+                .filter(vec![HirScalarExpr::column(key_arity).call_binary(
+                    HirScalarExpr::literal(Datum::Int64(1), ScalarType::Int64),
+                    BinaryFunc::Gt,
+                )])
+                .map(
+                    desc.iter_types()
+                        .map(|typ| HirScalarExpr::literal_null(typ.scalar_type.clone()))
+                        .chain(iter::once(HirScalarExpr::literal_null(ScalarType::Bool)))
+                        .collect(),
+                )
+                .project((key_arity + 1..key_arity + 2 + arity).collect());
+
+            Ok((
+                retractions.union(updates).union(inserts).union(duplicates),
+                true,
+            ))
+        }
+    }
 }
 
 pub fn plan_copy_from(
@@ -945,6 +1194,156 @@ pub fn plan_secret_as(
     Ok(expr)
 }
 
+/// Plans the parameters, return type and body of a `CREATE FUNCTION`
+/// statement.
+///
+/// The body is planned with [`HirScalarExpr::Parameter`]s standing in for the
+/// function's arguments, which the body may refer to either by position (`$1`)
+/// or, if the parameter is named, by name. Column references take precedence
+/// over parameter names, as in PostgreSQL.
+pub fn plan_sql_function(
+    scx: &StatementContext,
+    params: Vec<FunctionParameter<Aug>>,
+    returns: FunctionReturns<Aug>,
+    mut body: Query<Aug>,
+) -> Result<SqlFunction, PlanError> {
+    let mut param_types = vec![];
+    let mut param_names = BTreeMap::new();
+    for (i, param) in params.into_iter().enumerate() {
+        if let Some(name) = param.name {
+            let name = normalize::column_name(name);
+            if param_names.insert(name.clone(), i + 1).is_some() {
+                sql_bail!(
+                    "parameter name {} used more than once",
+                    name.as_str().quoted()
+                );
+            }
+        }
+        param_types.push(scalar_type_from_sql(scx, &param.data_type)?);
+    }
+
+    // Reconstruct a statement context where the parameter types are bound to
+    // the types of the function's parameters.
+    let mut scx = scx.clone();
+    scx.param_types = RefCell::new(
+        param_types
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(i, ty)| (i + 1, ty))
+            .collect(),
+    );
+    scx.function_param_names = param_names;
+    let scx = &scx;
+
+    let body = match returns {
+        FunctionReturns::Scalar(data_type) => {
+            let return_type = scalar_type_from_sql(scx, &data_type)?;
+            transform_ast::transform(scx, &mut body)?;
+            // A body of the form `SELECT <expr>` is planned as the expression
+            // itself, which spares callers a subquery. Any other body is
+            // planned as a scalar subquery.
+            let simple_expr = match &body {
+                Query {
+                    ctes,
+                    body: SetExpr::Select(select),
+                    order_by,
+                    limit: None,
+                    offset: None,
+                } if ctes.is_empty()
+                    && order_by.is_empty()
+                    && select.distinct.is_none()
+                    && select.from.is_empty()
+                    && select.selection.is_none()
+                    && select.group_by.is_empty()
+                    && select.having.is_none()
+                    && select.options.is_empty() =>
+                {
+                    match select.projection.as_slice() {
+                        [SelectItem::Expr { expr, alias: _ }] => Some(expr.clone()),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            let expr = simple_expr.unwrap_or_else(|| Expr::Subquery(Box::new(body)));
+            let qcx = QueryContext::root(scx, QueryLifetime::View);
+            let ecx = &ExprContext {
+                qcx: &qcx,
+                name: "function body",
+                scope: &Scope::empty(),
+                relation_type: &RelationType::empty(),
+                allow_aggregates: false,
+                allow_subqueries: true,
+                allow_parameters: true,
+                allow_windows: false,
+            };
+            let expr = typeconv::plan_coerce(ecx, plan_expr(ecx, &expr)?, &return_type)?;
+            let expr_type = ecx.scalar_type(&expr);
+            let expr = typeconv::plan_cast(ecx, CastContext::Assignment, expr, &return_type)
+                .map_err(|_| {
+                    sql_err!(
+                        "return type mismatch in function declared to return {}: body returns {}",
+                        ecx.humanize_scalar_type(&return_type),
+                        ecx.humanize_scalar_type(&expr_type),
+                    )
+                })?;
+            SqlFunctionBody::Scalar { expr, return_type }
+        }
+        FunctionReturns::Table(columns) => {
+            let mut column_names = vec![];
+            let mut column_types = vec![];
+            for column in columns {
+                column_names.push(normalize::column_name(column.name));
+                column_types.push(scalar_type_from_sql(scx, &column.data_type)?);
+            }
+            if let Some(dup) = column_names.iter().duplicates().next() {
+                sql_bail!("column {} specified more than once", dup.as_str().quoted());
+            }
+
+            let PlannedRootQuery {
+                expr,
+                desc,
+                finishing,
+                scope: _,
+            } = plan_root_query(scx, body, QueryLifetime::View)?;
+            // We get back a trivial finishing, see comment in `plan_view`.
+            assert!(finishing.is_trivial(expr.arity()));
+            if desc.arity() != column_types.len() {
+                sql_bail!(
+                    "return type mismatch in function declared to return {} columns: body returns {}",
+                    column_types.len(),
+                    desc.arity(),
+                );
+            }
+            let qcx = QueryContext::root(scx, QueryLifetime::View);
+            let expr = cast_relation(&qcx, CastContext::Assignment, expr, &column_types)
+                .map_err(|e| {
+                    sql_err!(
+                        "return type mismatch in function: column {} is declared as {} but body returns {}",
+                        column_names[e.column].as_str().quoted(),
+                        qcx.humanize_scalar_type(&e.target_type),
+                        qcx.humanize_scalar_type(&e.source_type),
+                    )
+                })?;
+            SqlFunctionBody::Table { expr, column_names }
+        }
+    };
+
+    // Referring to a parameter beyond those the function declares would
+    // otherwise silently add a parameter to the function.
+    if let Some(n) = scx
+        .param_types
+        .borrow()
+        .keys()
+        .find(|n| **n > param_types.len())
+    {
+        return Err(PlanError::UnknownParameter(*n));
+    }
+
+    Ok(SqlFunction { param_types, body })
+}
+
 /// Plans an expression in the CHECK position of a `CREATE SOURCE ... FROM WEBHOOK`.
 pub fn plan_webhook_validate_using(
     scx: &StatementContext,
@@ -1203,6 +1602,61 @@ pub fn plan_index_exprs<'a>(
     Ok(out)
 }
 
+/// Plans the `PARTITION BY` expression of a Kafka sink.
+///
+/// The expression is planned against `desc`, the relation written by the sink,
+/// and is cast to `uint8`. If the sink has a key, the expression may only
+/// refer to key columns and the returned expression refers to the columns of
+/// the key, in the order given by `key_indices`.
+pub fn plan_sink_partition_by(
+    scx: &StatementContext,
+    desc: &RelationDesc,
+    key_indices: Option<&[usize]>,
+    mut expr: Expr<Aug>,
+) -> Result<mz_expr::MirScalarExpr, PlanError> {
+    let scope = Scope::from_source(None, desc.iter_names());
+    let qcx = QueryContext::root(scx, QueryLifetime::Index);
+
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name: "PARTITION BY",
+        scope: &scope,
+        relation_type: desc.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_parameters: false,
+        allow_windows: false,
+    };
+    transform_ast::transform(scx, &mut expr)?;
+    let expr = plan_expr(ecx, &expr)?.type_as_any(ecx)?;
+    let source_type = ecx.scalar_type(&expr);
+    let expr = match typeconv::plan_cast(ecx, CastContext::Assignment, expr, &ScalarType::UInt64) {
+        Ok(expr) => expr,
+        Err(_) => sql_bail!(
+            "PARTITION BY expression must be of type uint8, but is of type {}",
+            qcx.humanize_scalar_type(&source_type)
+        ),
+    };
+    let mut expr = expr.lower_uncorrelated()?;
+    if expr.contains_unmaterializable() || expr.contains_temporal() {
+        sql_bail!("PARTITION BY expression cannot call unmaterializable functions");
+    }
+    expr.reduce(&desc.typ().column_types);
+
+    if let Some(key_indices) = key_indices {
+        let key_positions: BTreeMap<_, _> = key_indices
+            .iter()
+            .enumerate()
+            .map(|(position, index)| (*index, position))
+            .collect();
+        if !expr.support().iter().all(|c| key_positions.contains_key(c)) {
+            sql_bail!("PARTITION BY expression can only refer to columns in the sink's key");
+        }
+        expr.permute_map(&key_positions);
+    }
+    Ok(expr)
+}
+
 fn plan_expr_or_col_index(ecx: &ExprContext, e: &Expr<Aug>) -> Result<HirScalarExpr, PlanError> {
     match check_col_index(ecx.name, e, ecx.relation_type.column_types.len())? {
         Some(column) => Ok(HirScalarExpr::column(column)),
@@ -1342,58 +1796,69 @@ fn plan_query_inner(qcx: &mut QueryContext, q: &Query<Aug>) -> Result<PlannedQue
         }
     };
 
-    // Both introduce `Let` bindings atop `result` and re-install shadowed bindings.
-    match &q.ctes {
-        CteBlock::Simple(_) => {
-            for (id, value, shadowed_val) in cte_bindings.into_iter().rev() {
-                if let Some(cte) = qcx.ctes.remove(&id) {
-                    planned_query.expr = HirRelationExpr::Let {
-                        name: cte.name,
-                        id: id.clone(),
-                        value: Box::new(value),
-                        body: Box::new(planned_query.expr),
-                    };
-                }
-                if let Some(shadowed_val) = shadowed_val {
-                    qcx.ctes.insert(id, shadowed_val);
-                }
+    // All introduce `Let` bindings atop `result` and re-install shadowed bindings.
+    // A `WITH RECURSIVE` block is planned as a simple block if none of its CTEs
+    // refer to themselves, and as a mutually recursive block without a
+    // recursion limit otherwise.
+    let is_recursive = match &q.ctes {
+        CteBlock::Simple(_) => false,
+        CteBlock::Recursive(ctes) => ctes
+            .iter()
+            .any(|cte| cte_references(cte.id, |v| v.visit_query(&cte.query)) > 0),
+        CteBlock::MutuallyRecursive(_) => true,
+    };
+    if !is_recursive {
+        for (id, value, shadowed_val) in cte_bindings.into_iter().rev() {
+            if let Some(cte) = qcx.ctes.remove(&id) {
+                planned_query.expr = HirRelationExpr::Let {
+                    name: cte.name,
+                    id: id.clone(),
+                    value: Box::new(value),
+                    body: Box::new(planned_query.expr),
+                };
+            }
+            if let Some(shadowed_val) = shadowed_val {
+                qcx.ctes.insert(id, shadowed_val);
             }
         }
-        CteBlock::MutuallyRecursive(MutRecBlock { options, ctes: _ }) => {
-            let MutRecBlockOptionExtracted {
-                recursion_limit,
-                return_at_recursion_limit,
-                error_at_recursion_limit,
-                seen: _,
-            } = MutRecBlockOptionExtracted::try_from(options.clone())?;
-            let limit = match (recursion_limit, return_at_recursion_limit, error_at_recursion_limit) {
-                (None, None, None) => None,
-                (Some(max_iters), None, None) => Some((max_iters, LetRecLimit::RETURN_AT_LIMIT_DEFAULT)),
-                (None, Some(max_iters), None) => Some((max_iters, true)),
-                (None, None, Some(max_iters)) => Some((max_iters, false)),
-                _ => {
-                    return Err(InvalidWmrRecursionLimit("More than one recursion limit given. Please give at most one of RECURSION LIMIT, ERROR AT RECURSION LIMIT, RETURN AT RECURSION LIMIT.".to_owned()));
-                }
-            }.try_map(|(max_iters, return_at_limit)| Ok::<LetRecLimit, PlanError>(LetRecLimit {
-                max_iters: NonZeroU64::new(*max_iters).ok_or(InvalidWmrRecursionLimit("Recursion limit has to be greater than 0.".to_owned()))?,
-                return_at_limit: *return_at_limit,
-            }))?;
-
-            let mut bindings = Vec::new();
-            for (id, value, shadowed_val) in cte_bindings.into_iter() {
-                if let Some(cte) = qcx.ctes.remove(&id) {
-                    bindings.push((cte.name, id, value, cte.desc.typ().clone()));
-                }
-                if let Some(shadowed_val) = shadowed_val {
-                    qcx.ctes.insert(id, shadowed_val);
-                }
+    } else {
+        let options = match &q.ctes {
+            CteBlock::MutuallyRecursive(MutRecBlock { options, ctes: _ }) => options.clone(),
+            CteBlock::Simple(_) | CteBlock::Recursive(_) => vec![],
+        };
+        let MutRecBlockOptionExtracted {
+            recursion_limit,
+            return_at_recursion_limit,
+            error_at_recursion_limit,
+            seen: _,
+        } = MutRecBlockOptionExtracted::try_from(options)?;
+        let limit = match (recursion_limit, return_at_recursion_limit, error_at_recursion_limit) {
+            (None, None, None) => None,
+            (Some(max_iters), None, None) => Some((max_iters, LetRecLimit::RETURN_AT_LIMIT_DEFAULT)),
+            (None, Some(max_iters), None) => Some((max_iters, true)),
+            (None, None, Some(max_iters)) => Some((max_iters, false)),
+            _ => {
+                return Err(InvalidWmrRecursionLimit("More than one recursion limit given. Please give at most one of RECURSION LIMIT, ERROR AT RECURSION LIMIT, RETURN AT RECURSION LIMIT.".to_owned()));
             }
-            if !bindings.is_empty() {
-                planned_query.expr = HirRelationExpr::LetRec {
-                    limit,
-                    bindings,
-                    body: Box::new(planned_query.expr),
-                }
+        }.try_map(|(max_iters, return_at_limit)| Ok::<LetRecLimit, PlanError>(LetRecLimit {
+            max_iters: NonZeroU64::new(*max_iters).ok_or(InvalidWmrRecursionLimit("Recursion limit has to be greater than 0.".to_owned()))?,
+            return_at_limit: *return_at_limit,
+        }))?;
+
+        let mut bindings = Vec::new();
+        for (id, value, shadowed_val) in cte_bindings.into_iter() {
+            if let Some(cte) = qcx.ctes.remove(&id) {
+                bindings.push((cte.name, id, value, cte.desc.typ().clone()));
+            }
+            if let Some(shadowed_val) = shadowed_val {
+                qcx.ctes.insert(id, shadowed_val);
+            }
+        }
+        if !bindings.is_empty() {
+            planned_query.expr = HirRelationExpr::LetRec {
+                limit,
+                bindings,
+                body: Box::new(planned_query.expr),
             }
         }
     }
@@ -1455,6 +1920,149 @@ pub fn plan_ctes(
                 result.push((cte.id, val, shadowed));
             }
         }
+        CteBlock::Recursive(ctes) => {
+            for cte in ctes.iter() {
+                let cte_name = normalize::ident(cte.alias.name.clone());
+                if cte_references(cte.id, |v| v.visit_query(&cte.query)) == 0 {
+                    // Plan CTEs that don't refer to themselves as in a simple block.
+                    let (val, scope) = plan_nested_query(qcx, &cte.query)?;
+                    let typ = qcx.relation_type(&val);
+                    let mut desc = RelationDesc::new(typ, scope.column_names());
+                    plan_utils::maybe_rename_columns(
+                        format!("CTE {}", cte.alias.name),
+                        &mut desc,
+                        &cte.alias.columns,
+                    )?;
+                    let shadowed = qcx.ctes.insert(
+                        cte.id,
+                        CteDesc {
+                            name: cte_name,
+                            desc,
+                        },
+                    );
+                    result.push((cte.id, val, shadowed));
+                    continue;
+                }
+
+                // A recursive CTE must have the form `non-recursive-term UNION
+                // [ALL] recursive-term`, where only the recursive term refers to
+                // the CTE, and does so exactly once. Evaluating the whole query
+                // to a fixpoint then produces the same result as the iterative
+                // evaluation prescribed by the standard, including for the
+                // duplicate elimination of `UNION`.
+                let non_recursive_term = match &cte.query {
+                    Query {
+                        ctes,
+                        body:
+                            SetExpr::SetOperation {
+                                op: SetOperator::Union,
+                                all: _,
+                                left,
+                                right,
+                            },
+                        order_by,
+                        limit,
+                        offset,
+                    } if ctes.is_empty() => {
+                        if !order_by.is_empty() {
+                            sql_bail!("ORDER BY in a recursive query is not implemented");
+                        }
+                        if limit.is_some() {
+                            sql_bail!("LIMIT in a recursive query is not implemented");
+                        }
+                        if offset.is_some() {
+                            sql_bail!("OFFSET in a recursive query is not implemented");
+                        }
+                        if cte_references(cte.id, |v| v.visit_set_expr(left)) > 0 {
+                            sql_bail!(
+                                "recursive reference to query {} must not appear within its non-recursive term",
+                                cte_name.quoted()
+                            );
+                        }
+                        if cte_references(cte.id, |v| v.visit_set_expr(right)) > 1 {
+                            sql_bail!(
+                                "recursive reference to query {} must not appear more than once",
+                                cte_name.quoted()
+                            );
+                        }
+                        check_recursive_term(qcx.scx, cte.id, &cte_name, right)?;
+                        left
+                    }
+                    _ => sql_bail!(
+                        "recursive query {} does not have the form non-recursive-term UNION [ALL] recursive-term",
+                        cte_name.quoted()
+                    ),
+                };
+
+                // The non-recursive term determines the column names and types
+                // of the CTE.
+                let (val, scope) = plan_nested_query(
+                    qcx,
+                    &Query {
+                        ctes: CteBlock::empty(),
+                        body: (**non_recursive_term).clone(),
+                        order_by: vec![],
+                        limit: None,
+                        offset: None,
+                    },
+                )?;
+                let typ = RelationType::new(
+                    qcx.relation_type(&val)
+                        .column_types
+                        .into_iter()
+                        .map(|typ| typ.nullable(true))
+                        .collect(),
+                );
+                let mut desc = RelationDesc::new(typ, scope.column_names());
+                plan_utils::maybe_rename_columns(
+                    format!("CTE {}", cte.alias.name),
+                    &mut desc,
+                    &cte.alias.columns,
+                )?;
+                let shadowed = qcx.ctes.insert(
+                    cte.id,
+                    CteDesc {
+                        name: cte_name.clone(),
+                        desc,
+                    },
+                );
+
+                // Plan the whole query, with the CTE in scope, and check that
+                // it agrees with the types of the non-recursive term.
+                let (val, _scope) = plan_nested_query(qcx, &cte.query)?;
+                let proposed_typ = qcx.ctes[&cte.id].desc.typ();
+                let derived_typ = qcx.relation_type(&val);
+                let val = match cast_relation(
+                    qcx,
+                    CastContext::Implicit,
+                    val,
+                    proposed_typ.column_types.iter().map(|c| &c.scalar_type),
+                ) {
+                    Ok(val) => val,
+                    Err(_) => {
+                        let (i, proposed, derived) = proposed_typ
+                            .column_types
+                            .iter()
+                            .zip(derived_typ.column_types.iter())
+                            .enumerate()
+                            .find(|(_i, (proposed, derived))| {
+                                !proposed.scalar_type.base_eq(&derived.scalar_type)
+                            })
+                            .map(|(i, (proposed, derived))| (i, proposed, derived))
+                            .expect("cast only fails on mismatched types");
+                        sql_bail!(
+                            "recursive query {} column {} has type {} in non-recursive term but type {} overall",
+                            cte_name.quoted(),
+                            i + 1,
+                            qcx.humanize_scalar_type(&proposed.scalar_type),
+                            qcx.humanize_scalar_type(&derived.scalar_type),
+                        );
+                    }
+                };
+
+                result.push((cte.id, val, shadowed));
+            }
+        }
         CteBlock::MutuallyRecursive(MutRecBlock { options: _, ctes }) => {
             // Insert column types into `qcx.ctes` first for recursive bindings.
             for cte in ctes.iter() {
@@ -1550,6 +2158,184 @@ pub fn plan_ctes(
     Ok(result)
 }
 
+/// Checks that the recursive term of the recursive CTE `id` refers to the CTE
+/// only where PostgreSQL allows it, and does not use the constructs PostgreSQL
+/// rejects in a recursive term.
+///
+/// The reference must appear in a `FROM` clause, possibly within a derived
+/// table, but not within a subquery expression, on the nullable side of an
+/// outer join, or within `EXCEPT` or `INTERSECT`. The `SELECT` containing it
+/// must not aggregate or use `DISTINCT`, and no query around it may use
+/// `ORDER BY`, `LIMIT` or `OFFSET`.
+fn check_recursive_term(
+    scx: &StatementContext,
+    id: LocalId,
+    cte_name: &str,
+    term: &SetExpr<Aug>,
+) -> Result<(), PlanError> {
+    if cte_references(id, |v| v.visit_set_expr(term)) == 0 {
+        return Ok(());
+    }
+    match term {
+        SetExpr::SetOperation {
+            op: SetOperator::Union,
+            all: _,
+            left,
+            right,
+        } => {
+            check_recursive_term(scx, id, cte_name, left)?;
+            check_recursive_term(scx, id, cte_name, right)
+        }
+        SetExpr::SetOperation { op, .. } => sql_bail!(
+            "recursive reference to query {} must not appear within {}",
+            cte_name.quoted(),
+            op
+        ),
+        SetExpr::Query(query) => check_recursive_query(scx, id, cte_name, query),
+        SetExpr::Select(select) => {
+            let mut from_references = 0;
+            for table in &select.from {
+                from_references += check_recursive_join(scx, id, cte_name, table)?;
+            }
+            if cte_references(id, |v| v.visit_select(select)) > from_references {
+                sql_bail!(
+                    "recursive reference to query {} must not appear within a subquery",
+                    cte_name.quoted()
+                );
+            }
+            if select.distinct.is_some() {
+                sql_bail!("DISTINCT is not allowed in a recursive query's recursive term");
+            }
+            let has_aggregates = {
+                let mut visitor = AggregateTableFuncVisitor::new(scx);
+                visitor.visit_select_mut(&mut (**select).clone());
+                // Errors are reported when the term is planned.
+                visitor
+                    .into_result()
+                    .map_or(false, |(aggs, _)| !aggs.is_empty())
+            };
+            if has_aggregates || !select.group_by.is_empty() || select.having.is_some() {
+                sql_bail!(
+                    "aggregate functions are not allowed in a recursive query's recursive term"
+                );
+            }
+            Ok(())
+        }
+        SetExpr::Values(_) | SetExpr::Show(_) | SetExpr::Table(_) => Ok(()),
+    }
+}
+
+/// Like [`check_recursive_term`], for a parenthesized query or derived table
+/// within the recursive term.
+fn check_recursive_query(
+    scx: &StatementContext,
+    id: LocalId,
+    cte_name: &str,
+    query: &Query<Aug>,
+) -> Result<(), PlanError> {
+    let Query {
+        ctes,
+        body,
+        order_by,
+        limit,
+        offset,
+    } = query;
+    if cte_references(id, |v| v.visit_query(query)) == 0 {
+        return Ok(());
+    }
+    if !order_by.is_empty() {
+        sql_bail!("ORDER BY in a recursive query is not implemented");
+    }
+    if limit.is_some() {
+        sql_bail!("LIMIT in a recursive query is not implemented");
+    }
+    if offset.is_some() {
+        sql_bail!("OFFSET in a recursive query is not implemented");
+    }
+    if cte_references(id, |v| v.visit_cte_block(ctes)) > 0 {
+        sql_bail!(
+            "recursive reference to query {} must not appear within a subquery",
+            cte_name.quoted()
+        );
+    }
+    check_recursive_term(scx, id, cte_name, body)
+}
+
+/// Checks the references to the recursive CTE `id` in the tables of `table`,
+/// and returns their number.
+fn check_recursive_join(
+    scx: &StatementContext,
+    id: LocalId,
+    cte_name: &str,
+    table: &TableWithJoins<Aug>,
+) -> Result<usize, PlanError> {
+    let references = |factor: &TableFactor<Aug>| -> Result<usize, PlanError> {
+        match factor {
+            TableFactor::Table {
+                name: ResolvedItemName::Cte { id: cte_id, .. },
+                alias: _,
+            } if *cte_id == id => Ok(1),
+            TableFactor::Derived {
+                lateral: _,
+                subquery,
+                alias: _,
+            } => {
+                check_recursive_query(scx, id, cte_name, subquery)?;
+                Ok(cte_references(id, |v| v.visit_query(subquery)))
+            }
+            TableFactor::NestedJoin { join, alias: _ } => {
+                check_recursive_join(scx, id, cte_name, join)
+            }
+            _ => Ok(0),
+        }
+    };
+
+    let mut left = references(&table.relation)?;
+    for join in &table.joins {
+        let right = references(&join.relation)?;
+        let nullable_reference = match join.join_operator {
+            JoinOperator::LeftOuter(_) => right > 0,
+            JoinOperator::RightOuter(_) => left > 0,
+            JoinOperator::FullOuter(_) => left > 0 || right > 0,
+            JoinOperator::Inner(_) | JoinOperator::CrossJoin => false,
+        };
+        if nullable_reference {
+            sql_bail!(
+                "recursive reference to query {} must not appear within an outer join",
+                cte_name.quoted()
+            );
+        }
+        left += right;
+    }
+    Ok(left)
+}
+
+/// Counts the references to the CTE with the given ID among the AST nodes
+/// visited by `f`.
+fn cte_references<F>(id: LocalId, f: F) -> usize
+where
+    F: FnOnce(&mut CteReferenceCounter),
+{
+    let mut visitor = CteReferenceCounter { id, count: 0 };
+    f(&mut visitor);
+    visitor.count
+}
+
+struct CteReferenceCounter {
+    id: LocalId,
+    count: usize,
+}
+
+impl Visit<'_, Aug> for CteReferenceCounter {
+    fn visit_item_name(&mut self, item_name: &ResolvedItemName) {
+        if let ResolvedItemName::Cte { id, .. } = item_name {
+            if *id == self.id {
+                self.count += 1;
+            }
+        }
+    }
+}
+
 pub fn plan_nested_query(
     qcx: &mut QueryContext,
     q: &Query<Aug>,
@@ -1780,6 +2566,10 @@ fn plan_set_expr(
                     show::plan_show_create_connection(qcx.scx, stmt.clone())?,
                     show::describe_show_create_connection(qcx.scx, stmt)?,
                 ),
+                ShowStatement::ShowCreateFunction(stmt) => to_hirscope(
+                    show::plan_show_create_function(qcx.scx, stmt.clone())?,
+                    show::describe_show_create_function(qcx.scx, stmt)?,
+                ),
                 ShowStatement::ShowCreateIndex(stmt) => to_hirscope(
                     show::plan_show_create_index(qcx.scx, stmt.clone())?,
                     show::describe_show_create_index(qcx.scx, stmt)?,
@@ -1984,9 +2774,154 @@ generate_extracted_config!(
     (ExpectedGroupSize, u64),
     (AggregateInputGroupSize, u64),
     (DistinctOnInputGroupSize, u64),
-    (LimitInputGroupSize, u64)
+    (LimitInputGroupSize, u64),
+    (JoinOrder, Vec<Ident>),
+    (JoinImplementation, Ident)
 );
 
+/// Plans the `JOIN ORDER` and `JOIN IMPLEMENTATION` options of a `SELECT`.
+///
+/// `JOIN ORDER` lists relations of the FROM clause by their alias, or by their
+/// name if they have no alias. The optimizer identifies join inputs by the
+/// catalog item they read, so each listed relation must directly reference a
+/// catalog item that is read only once in the FROM clause.
+fn plan_join_hints(
+    qcx: &QueryContext,
+    from: &[TableWithJoins<Aug>],
+    select_option_extracted: &SelectOptionExtracted,
+) -> Result<JoinHints, PlanError> {
+    let SelectOptionExtracted {
+        join_order,
+        join_implementation,
+        ..
+    } = select_option_extracted;
+    if join_order.is_none() && join_implementation.is_none() {
+        return Ok(JoinHints::default());
+    }
+    qcx.scx.require_feature_flag(&vars::ENABLE_JOIN_HINTS)?;
+
+    let implementation = match join_implementation {
+        None => None,
+        Some(implementation) => match normalize::ident_ref(implementation) {
+            "differential" => Some(JoinImplementationHint::Differential),
+            "delta" => Some(JoinImplementationHint::Delta),
+            other => sql_bail!(
+                "invalid JOIN IMPLEMENTATION {}: expected DIFFERENTIAL or DELTA",
+                other.quoted()
+            ),
+        },
+    };
+
+    let mut relations = Vec::new();
+    for twj in from {
+        collect_join_hint_relations(twj, &mut relations);
+    }
+    let mut order = Vec::new();
+    for name in join_order.iter().flatten() {
+        let name = normalize::ident(name.clone());
+        let mut matches = relations.iter().filter(|(n, _)| *n == name);
+        let id = match (matches.next(), matches.next()) {
+            (Some((_, Some(id))), None) => *id,
+            (Some((_, None)), None) => sql_bail!(
+                "JOIN ORDER can only refer to relations from the catalog, but {} is not",
+                name.quoted()
+            ),
+            (Some(_), Some(_)) => sql_bail!("JOIN ORDER reference {} is ambiguous", name.quoted()),
+            (None, _) => sql_bail!(
+                "JOIN ORDER refers to {}, which is not in the FROM clause",
+                name.quoted()
+            ),
+        };
+        if relations
+            .iter()
+            .filter(|(_, other)| *other == Some(id))
+            .count()
+            > 1
+        {
+            sql_bail!(
+                "JOIN ORDER cannot refer to {}, because it is read more than once in the FROM clause",
+                name.quoted()
+            );
+        }
+        if order.contains(&id) {
+            sql_bail!("{} appears more than once in JOIN ORDER", name.quoted());
+        }
+        order.push(id);
+    }
+
+    Ok(JoinHints {
+        order,
+        implementation,
+    })
+}
+
+/// Collects the names by which the relations of `twj` can be referred to in a
+/// `JOIN ORDER` hint, along with the catalog item each of them reads, if any.
+fn collect_join_hint_relations(
+    twj: &TableWithJoins<Aug>,
+    relations: &mut Vec<(String, Option<GlobalId>)>,
+) {
+    for factor in std::iter::once(&twj.relation).chain(twj.joins.iter().map(|j| &j.relation)) {
+        match factor {
+            TableFactor::Table { name, alias } => {
+                let id = match name {
+                    ResolvedItemName::Item { id, .. } => Some(*id),
+                    ResolvedItemName::Cte { .. } | ResolvedItemName::Error => None,
+                };
+                let name = match (alias, name) {
+                    (Some(alias), _) => normalize::ident(alias.name.clone()),
+                    (None, ResolvedItemName::Item { full_name, .. }) => full_name.item.clone(),
+                    (None, ResolvedItemName::Cte { name, .. }) => name.clone(),
+                    (None, ResolvedItemName::Error) => continue,
+                };
+                relations.push((name, id));
+            }
+            TableFactor::NestedJoin { join, alias: None } => {
+                collect_join_hint_relations(join, relations);
+            }
+            TableFactor::NestedJoin {
+                alias: Some(alias), ..
+            }
+            | TableFactor::Function {
+                alias: Some(alias), ..
+            }
+            | TableFactor::RowsFrom {
+                alias: Some(alias), ..
+            }
+            | TableFactor::Derived {
+                alias: Some(alias), ..
+            } => relations.push((normalize::ident(alias.name.clone()), None)),
+            TableFactor::Function { alias: None, .. }
+            | TableFactor::RowsFrom { alias: None, .. }
+            | TableFactor::Derived { alias: None, .. } => {}
+        }
+    }
+}
+
+/// Attaches `new_hints` to the outermost join of `expr`, which is the planned
+/// FROM clause of a `SELECT`.
+fn attach_join_hints(expr: &mut HirRelationExpr, new_hints: JoinHints) -> Result<(), PlanError> {
+    match expr {
+        HirRelationExpr::Join {
+            kind: JoinKind::Inner,
+            hints,
+            ..
+        } => {
+            *hints = new_hints;
+            Ok(())
+        }
+        HirRelationExpr::Join { .. } => {
+            sql_bail!("join hints are only supported for inner joins")
+        }
+        // Joins with `USING` or `NATURAL` constraints are wrapped in a
+        // projection of the joined columns.
+        HirRelationExpr::Map { input, .. }
+        | HirRelationExpr::Project { input, .. }
+        | HirRelationExpr::Filter { input, .. } => attach_join_hints(input, new_hints),
+        _ => sql_bail!("join hints require a join in the FROM clause"),
+    }
+}
+
 /// Plans a SELECT query. The SELECT query may contain an intrusive ORDER BY clause.
 ///
 /// Normally, the ORDER BY clause occurs after the columns specified in the
@@ -2016,6 +2951,7 @@ fn plan_view_select(
 
     // Extract query options.
     let select_option_extracted = SelectOptionExtracted::try_from(s.options.clone())?;
+    let join_hints = plan_join_hints(qcx, &s.from, &select_option_extracted)?;
     let group_size_hints = GroupSizeHints::try_from(select_option_extracted)?;
 
     // Step 1. Handle FROM clause, including joins.
@@ -2035,6 +2971,9 @@ fn plan_view_select(
                 },
             )
         })?;
+    if !join_hints.is_empty() {
+        attach_join_hints(&mut relation_expr, join_hints)?;
+    }
 
     // Step 2. Handle WHERE clause.
     if let Some(selection) = &s.selection {
@@ -2102,6 +3041,17 @@ fn plan_view_select(
     // Step 5. Handle GROUP BY clause.
     // This will also plan the aggregates gathered in Step 3.
     let (mut group_scope, select_all_mapping) = {
+        // Expand `GROUPING SETS`, `ROLLUP` and `CUBE` into the list of
+        // grouping sets they describe. A `GROUP BY` clause consisting only of
+        // plain expressions describes exactly one grouping set.
+        let grouping_sets = expand_grouping_sets(&s.group_by)?;
+        let mut grouping_exprs: Vec<&Expr<Aug>> = vec![];
+        for expr in grouping_sets.iter().flatten() {
+            if !grouping_exprs.contains(expr) {
+                grouping_exprs.push(*expr);
+            }
+        }
+
         // Compute GROUP BY expressions.
         let ecx = &ExprContext {
             qcx,
@@ -2118,8 +3068,10 @@ fn plan_view_select(
         let mut group_hir_exprs = vec![];
         let mut group_scope = Scope::empty();
         let mut select_all_mapping = BTreeMap::new();
+        // For each of `grouping_exprs`, the index of its column in `group_key`.
+        let mut grouping_expr_columns = vec![];
 
-        for group_expr in &s.group_by {
+        for group_expr in &grouping_exprs {
             let (group_expr, expr) = plan_group_by_expr(ecx, group_expr, &projection)?;
             let new_column = group_key.len();
 
@@ -2129,6 +3081,11 @@ fn plan_view_select(
                 // next AST expression to its set
                 if let Some(existing_scope_item) = group_exprs.get_mut(&expr) {
                     existing_scope_item.exprs.insert(group_expr.clone());
+                    let column = group_hir_exprs
+                        .iter()
+                        .position(|e| *e == expr)
+                        .expect("group expression already planned");
+                    grouping_expr_columns.push(column);
                     continue;
                 }
             }
@@ -2154,6 +3111,7 @@ fn plan_view_select(
                 scope_item.exprs.insert(group_expr);
             }
 
+            grouping_expr_columns.push(new_column);
             group_key.push(from_scope.len() + group_exprs.len());
             group_hir_exprs.push(expr.clone());
             group_exprs.insert(expr, scope_item);
@@ -2166,6 +3124,104 @@ fn plan_view_select(
             }
         }
 
+        // Determine which group key columns participate in each grouping set.
+        let grouping_set_columns: Vec<BTreeSet<usize>> = grouping_sets
+            .iter()
+            .map(|set| {
+                set.iter()
+                    .map(|expr| {
+                        let i = grouping_exprs
+                            .iter()
+                            .position(|e| e == expr)
+                            .expect("grouping expression collected above");
+                        grouping_expr_columns[i]
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // With more than one grouping set, all grouping sets are computed by a
+        // single reduction over a shared input. Each input row is replicated
+        // once per grouping set, tagged with the set's ID, and the group key
+        // columns that are not part of that grouping set are nulled out. The
+        // grouping set ID then becomes an additional column of the group key,
+        // which immediately follows the other group key columns in the output.
+        let grouping_set_id = if grouping_sets.len() > 1 {
+            let column = group_scope.len();
+            let mut scope_item = ScopeItem::empty();
+            scope_item.allow_unqualified_references = false;
+            group_scope.items.push(scope_item);
+            Some(column)
+        } else {
+            None
+        };
+
+        // Plan `GROUPING` operations, which are computed from the grouping set
+        // ID once the aggregation is complete.
+        let grouping_funcs = {
+            let mut visitor = GroupingFuncCollector::default();
+            visitor.visit_select(&s);
+            for o in order_by_exprs.iter() {
+                visitor.visit_order_by_expr(o);
+            }
+            visitor.into_result()
+        };
+        let mut grouping_func_exprs = vec![];
+        for grouping_func in &grouping_funcs {
+            let Expr::Grouping(args) = grouping_func else {
+                unreachable!("GroupingFuncCollector only collects GROUPING operations");
+            };
+            if args.len() > 31 {
+                sql_bail!("GROUPING must have fewer than 32 arguments");
+            }
+            let mut arg_columns = vec![];
+            for arg in args {
+                let arg = plan_expr(ecx, arg)?.type_as_any(ecx)?;
+                match group_hir_exprs.iter().position(|e| *e == arg) {
+                    Some(column) => arg_columns.push(column),
+                    None => sql_bail!(
+                        "arguments to GROUPING must be grouping expressions of the associated query level"
+                    ),
+                }
+            }
+            // Bit `i`, counting from the least significant bit, is set when
+            // the `i`th argument from the right is not part of the grouping
+            // set that produced the row.
+            let values: Vec<i32> = grouping_set_columns
+                .iter()
+                .map(|columns| {
+                    arg_columns.iter().fold(0, |acc, column| {
+                        (acc << 1) | i32::from(!columns.contains(column))
+                    })
+                })
+                .collect();
+            let mut expr = HirScalarExpr::literal(
+                Datum::Int32(*values.last().expect("at least one grouping set")),
+                ScalarType::Int32,
+            );
+            if let Some(grouping_set_id) = grouping_set_id {
+                for (id, value) in values.iter().enumerate().rev().skip(1) {
+                    if *value != values[values.len() - 1] {
+                        expr = HirScalarExpr::If {
+                            cond: Box::new(HirScalarExpr::column(grouping_set_id).call_binary(
+                                HirScalarExpr::literal(
+                                    Datum::Int32(i32::try_from(id).expect("bounded")),
+                                    ScalarType::Int32,
+                                ),
+                                BinaryFunc::Eq,
+                            )),
+                            then: Box::new(HirScalarExpr::literal(
+                                Datum::Int32(*value),
+                                ScalarType::Int32,
+                            )),
+                            els: Box::new(expr),
+                        };
+                    }
+                }
+            }
+            grouping_func_exprs.push(expr);
+        }
+
         // Plan aggregates.
         let ecx = &ExprContext {
             qcx,
@@ -2189,13 +3245,116 @@ fn plan_view_select(
                 .items
                 .push(ScopeItem::from_expr(Expr::Function(sql_function.clone())));
         }
-        if !agg_exprs.is_empty() || !group_key.is_empty() || s.having.is_some() {
+        if !agg_exprs.is_empty() || !s.group_by.is_empty() || s.having.is_some() {
             // apply GROUP BY / aggregates
-            relation_expr = relation_expr.map(group_hir_exprs).reduce(
-                group_key,
-                agg_exprs,
-                group_size_hints.aggregate_input_group_size,
-            );
+            relation_expr = relation_expr.map(group_hir_exprs);
+            if grouping_set_id.is_some() {
+                let input_arity = from_scope.len() + group_key.len();
+                let column_types = qcx.relation_type(&relation_expr).column_types;
+                // Empty grouping sets are planned as separate global
+                // reductions, so that they produce a row even when the input
+                // is empty, just like a query without a `GROUP BY` clause.
+                let (empty_ids, grouped_ids): (Vec<_>, Vec<_>) =
+                    (0..grouping_sets.len()).partition(|id| grouping_set_columns[*id].is_empty());
+                let grand_totals: Vec<_> = empty_ids
+                    .into_iter()
+                    .map(|id| {
+                        let mut outputs: Vec<_> =
+                            (agg_exprs.len()..agg_exprs.len() + group_key.len() + 1).collect();
+                        outputs.extend(0..agg_exprs.len());
+                        relation_expr
+                            .clone()
+                            .reduce(
+                                vec![],
+                                agg_exprs.clone(),
+                                group_size_hints.aggregate_input_group_size,
+                            )
+                            .map(
+                                group_key
+                                    .iter()
+                                    .map(|key| {
+                                        HirScalarExpr::literal_null(
+                                            column_types[*key].scalar_type.clone(),
+                                        )
+                                    })
+                                    .chain(iter::once(HirScalarExpr::literal(
+                                        Datum::Int32(i32::try_from(id).expect("bounded")),
+                                        ScalarType::Int32,
+                                    )))
+                                    .collect(),
+                            )
+                            .project(outputs)
+                    })
+                    .collect();
+                let grouped = if grouped_ids.is_empty() {
+                    None
+                } else {
+                    let grouping_set_ids = grouped_ids
+                        .iter()
+                        .map(|id| vec![Datum::Int32(i32::try_from(*id).expect("bounded"))])
+                        .collect();
+                    let mut grouped = relation_expr.join(
+                        HirRelationExpr::constant(
+                            grouping_set_ids,
+                            RelationType::new(vec![ScalarType::Int32.nullable(false)]),
+                        ),
+                        HirScalarExpr::literal_true(),
+                        JoinKind::Inner,
+                    );
+                    let mut nulled_keys = vec![];
+                    for (i, key) in group_key.iter().enumerate() {
+                        let member_ids: Vec<_> = grouped_ids
+                            .iter()
+                            .filter(|id| grouping_set_columns[**id].contains(&i))
+                            .map(|id| {
+                                HirScalarExpr::column(input_arity).call_binary(
+                                    HirScalarExpr::literal(
+                                        Datum::Int32(i32::try_from(*id).expect("bounded")),
+                                        ScalarType::Int32,
+                                    ),
+                                    BinaryFunc::Eq,
+                                )
+                            })
+                            .collect();
+                        let key_expr = if member_ids.len() == grouped_ids.len() {
+                            HirScalarExpr::column(*key)
+                        } else {
+                            HirScalarExpr::If {
+                                cond: Box::new(HirScalarExpr::variadic_or(member_ids)),
+                                then: Box::new(HirScalarExpr::column(*key)),
+                                els: Box::new(HirScalarExpr::literal_null(
+                                    column_types[*key].scalar_type.clone(),
+                                )),
+                            }
+                        };
+                        nulled_keys.push(key_expr);
+                    }
+                    let group_key = (input_arity + 1..input_arity + 1 + nulled_keys.len())
+                        .chain(iter::once(input_arity))
+                        .collect();
+                    grouped = grouped.map(nulled_keys).reduce(
+                        group_key,
+                        agg_exprs.clone(),
+                        group_size_hints.aggregate_input_group_size,
+                    );
+                    Some(grouped)
+                };
+                relation_expr = grouped
+                    .into_iter()
+                    .chain(grand_totals)
+                    .reduce(|acc, expr| acc.union(expr))
+                    .expect("at least two grouping sets");
+            } else {
+                relation_expr = relation_expr.reduce(
+                    group_key,
+                    agg_exprs,
+                    group_size_hints.aggregate_input_group_size,
+                );
+            }
+            relation_expr = relation_expr.map(grouping_func_exprs);
+            group_scope
+                .items
+                .extend(grouping_funcs.into_iter().map(ScopeItem::from_expr));
 
             // For every old column that wasn't a group key, add a scope item
             // that errors when referenced. We can't simply drop these items
@@ -2588,6 +3747,78 @@ fn plan_group_by_expr<'a>(
     }
 }
 
+/// The maximum number of grouping sets that a `GROUP BY` clause may expand to.
+/// This matches PostgreSQL.
+const MAX_GROUPING_SETS: usize = 4096;
+
+/// Expands the elements of a `GROUP BY` clause into the grouping sets they
+/// describe.
+///
+/// The grouping sets of a list of elements are the cross product of the
+/// grouping sets of each element, so a `GROUP BY` clause that consists only of
+/// plain expressions describes exactly one grouping set.
+fn expand_grouping_sets(
+    elements: &[GroupingElement<Aug>],
+) -> Result<Vec<Vec<&Expr<Aug>>>, PlanError> {
+    let mut sets = vec![vec![]];
+    for element in elements {
+        let element_sets = expand_grouping_element(element)?;
+        if sets.len() * element_sets.len() > MAX_GROUPING_SETS {
+            sql_bail!("too many grouping sets present");
+        }
+        sets = sets
+            .iter()
+            .cartesian_product(&element_sets)
+            .map(|(l, r)| l.iter().chain(r).copied().collect())
+            .collect();
+    }
+    Ok(sets)
+}
+
+/// Expands a single element of a `GROUP BY` clause into the grouping sets it
+/// describes.
+fn expand_grouping_element(
+    element: &GroupingElement<Aug>,
+) -> Result<Vec<Vec<&Expr<Aug>>>, PlanError> {
+    let sets = match element {
+        GroupingElement::Expr(expr) => vec![vec![expr]],
+        GroupingElement::Set(exprs) => vec![exprs.iter().collect()],
+        // `ROLLUP (a, b, c)` is `GROUPING SETS ((a, b, c), (a, b), (a), ())`.
+        GroupingElement::Rollup(items) => (0..=items.len())
+            .rev()
+            .map(|n| items[..n].iter().flatten().collect())
+            .collect(),
+        // `CUBE (a, b)` is `GROUPING SETS ((a, b), (a), (b), ())`.
+        GroupingElement::Cube(items) => {
+            if items.len() > 12 {
+                sql_bail!("CUBE is limited to 12 elements");
+            }
+            (0..1usize << items.len())
+                .rev()
+                .map(|mask| {
+                    items
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _item)| mask & (1 << (items.len() - 1 - i)) != 0)
+                        .flat_map(|(_i, item)| item)
+                        .collect()
+                })
+                .collect()
+        }
+        GroupingElement::GroupingSets(elements) => {
+            let mut sets = vec![];
+            for element in elements {
+                sets.extend(expand_grouping_element(element)?);
+                if sets.len() > MAX_GROUPING_SETS {
+                    sql_bail!("too many grouping sets present");
+                }
+            }
+            sets
+        }
+    };
+    Ok(sets)
+}
+
 /// Plans a slice of `ORDER BY` expressions.
 ///
 /// See `plan_order_by_or_distinct_expr` for details on the `output_columns`
@@ -2986,7 +4217,7 @@ fn plan_table_function_internal(
 
     let scalar_args = match args {
         FunctionArgs::Star => sql_bail!("{} does not accept * as an argument", name),
-        FunctionArgs::Args { args, order_by } => {
+        FunctionArgs::Args { args, order_by, .. } => {
             if !order_by.is_empty() {
                 sql_bail!(
                     "ORDER BY specified, but {} is not an aggregate function",
@@ -3201,6 +4432,7 @@ fn invent_column_name(
                 NameQuality::High,
             )),
             Expr::NullIf { .. } => Some(("nullif".into(), NameQuality::High)),
+            Expr::Grouping(_) => Some(("grouping".into(), NameQuality::High)),
             Expr::Array { .. } => Some(("array".into(), NameQuality::High)),
             Expr::List { .. } => Some(("list".into(), NameQuality::High)),
             Expr::Cast { expr, data_type } => match invent(ecx, expr, table_func_names) {
@@ -3701,6 +4933,14 @@ fn plan_expr_inner<'a>(
             &Some(Box::new(*l_expr.clone())),
         )?
         .into()),
+        // `GROUPING` operations are planned alongside the `GROUP BY` clause
+        // and are resolved via the scope above.
+        Expr::Grouping(_) => {
+            if !ecx.allow_aggregates {
+                sql_bail!("grouping operations are not allowed in {}", ecx.name);
+            }
+            sql_bail!("grouping operations must refer to the query level that contains them")
+        }
         Expr::FieldAccess { expr, field } => plan_field_access(ecx, expr, field),
         Expr::WildcardAccess(expr) => plan_expr(ecx, expr),
         Expr::Subscript { expr, positions } => plan_subscript(ecx, expr, positions),
@@ -4639,10 +5879,17 @@ fn plan_aggregate_common(
     // rules to all aggregates, not just `count`, since we may one day support
     // user-defined aggregates, including user-defined aggregates that take no
     // parameters.
-    let (args, order_by) = match &args {
-        FunctionArgs::Star => (vec![], vec![]),
-        FunctionArgs::Args { args, order_by } => {
-            if args.is_empty() {
+    let (args, order_by, within_group) = match &args {
+        FunctionArgs::Star => (vec![], vec![], false),
+        FunctionArgs::Args {
+            args,
+            order_by,
+            within_group,
+        } => {
+            // Ordered-set aggregates, like `mode`, may have no direct
+            // arguments, as their aggregated arguments are specified by
+            // `WITHIN GROUP`.
+            if args.is_empty() && !within_group {
                 sql_bail!(
                     "{}(*) must be used to call a parameterless aggregate function",
                     ecx.qcx
@@ -4652,13 +5899,68 @@ fn plan_aggregate_common(
                 );
             }
             let args = plan_exprs(ecx, args)?;
-            (args, order_by.clone())
+            (args, order_by.clone(), *within_group)
+        }
+    };
+
+    let (order_by_exprs, mut expr, func) = if within_group {
+        // The `WITHIN GROUP (ORDER BY ...)` expressions of an ordered-set
+        // aggregate are its aggregated arguments, and are passed to the
+        // function after its direct arguments. The direct arguments (e.g. the
+        // fraction of `percentile_cont`) are evaluated once per group, so they
+        // must not refer to the rows being aggregated.
+        for arg in &args {
+            let mut seen_inner = false;
+            if let CoercibleScalarExpr::Coerced(arg) = arg {
+                #[allow(deprecated)]
+                arg.visit_columns(0, &mut |depth, col| seen_inner |= col.level == depth);
+            }
+            if seen_inner {
+                sql_bail!(
+                    "direct arguments of ordered-set aggregate {} must not reference input columns",
+                    ecx.qcx
+                        .scx
+                        .humanize_resolved_name(name)
+                        .expect("name actually resolved")
+                );
+            }
         }
+        let mut args = args;
+        args.extend(plan_exprs(
+            ecx,
+            &order_by.iter().map(|obe| &obe.expr).collect::<Vec<_>>(),
+        )?);
+        let col_orders = order_by
+            .iter()
+            .enumerate()
+            .map(|(i, obe)| resolve_desc_and_nulls_last(obe, i))
+            .collect();
+        let (expr, func) = func::select_impl(ecx, FuncSpec::Func(name), impls, args, col_orders)?;
+        (vec![], expr, func)
+    } else {
+        let (order_by_exprs, col_orders) = plan_function_order_by(ecx, &order_by)?;
+        let (expr, func) = func::select_impl(ecx, FuncSpec::Func(name), impls, args, col_orders)?;
+        (order_by_exprs, expr, func)
     };
 
-    let (order_by_exprs, col_orders) = plan_function_order_by(ecx, &order_by)?;
+    if within_group && !func.is_ordered_set() {
+        sql_bail!(
+            "{} is not an ordered-set aggregate, so it cannot have WITHIN GROUP",
+            ecx.qcx
+                .scx
+                .humanize_resolved_name(name)
+                .expect("name actually resolved")
+        );
+    } else if !within_group && func.is_ordered_set() {
+        sql_bail!(
+            "WITHIN GROUP is required for ordered-set aggregate {}",
+            ecx.qcx
+                .scx
+                .humanize_resolved_name(name)
+                .expect("name actually resolved")
+        );
+    }
 
-    let (mut expr, func) = func::select_impl(ecx, FuncSpec::Func(name), impls, args, col_orders)?;
     if let Some(filter) = &filter {
         // If a filter is present, as in
         //
@@ -4748,12 +6050,17 @@ fn plan_identifier(ecx: &ExprContext, names: &[Ident]) -> Result<HirScalarExpr,
         },
     )?;
     match items.as_slice() {
-        // The name doesn't refer to a table either. Return an error.
-        [] => Err(PlanError::UnknownColumn {
-            table: None,
-            column: col_name,
-            similar: similar_names,
-        }),
+        // The name doesn't refer to a table either. If we're planning the body
+        // of a SQL function, it might refer to one of the function's
+        // parameters. Otherwise, return an error.
+        [] => match ecx.qcx.scx.function_param_names.get(&col_name) {
+            Some(n) => Ok(HirScalarExpr::Parameter(*n)),
+            None => Err(PlanError::UnknownColumn {
+                table: None,
+                column: col_name,
+                similar: similar_names,
+            }),
+        },
         // The name refers to a table that is the result of a function that
         // returned a single column. Per PostgreSQL, this is a special case
         // that returns the value directly.
@@ -4970,6 +6277,10 @@ fn plan_function<'a>(
                     bail_unsupported!("DISTINCT in window aggregates");
                 }
 
+                if aggregate_expr.func.is_ordered_set() {
+                    sql_bail!("OVER is not supported for ordered-set aggregate {}", name);
+                }
+
                 return Ok(HirScalarExpr::Windowing(WindowExpr {
                     func: WindowExprType::Aggregate(AggregateWindowExpr {
                         aggregate_expr,
@@ -5016,7 +6327,7 @@ fn plan_function<'a>(
                     .expect("already resolved")
             )
         }
-        FunctionArgs::Args { args, order_by } => {
+        FunctionArgs::Args { args, order_by, .. } => {
             if !order_by.is_empty() {
                 sql_bail!(
                     "ORDER BY specified, but {} is not an aggregate function",
@@ -5039,11 +6350,11 @@ pub const IGNORE_NULLS_ERROR_MSG: &str =
 /// Resolves the name to a set of function implementations.
 ///
 /// If the name does not specify a known built-in function, returns an error.
-pub fn resolve_func(
-    ecx: &ExprContext,
+pub fn resolve_func<'a>(
+    ecx: &'a ExprContext,
     name: &ResolvedItemName,
     args: &mz_sql_parser::ast::FunctionArgs<Aug>,
-) -> Result<&'static Func, PlanError> {
+) -> Result<&'a Func, PlanError> {
     if let Ok(i) = ecx.qcx.scx.get_item_by_resolved_name(name) {
         if let Ok(f) = i.func() {
             return Ok(f);
@@ -5054,7 +6365,7 @@ pub fn resolve_func(
     // message.
     let cexprs = match args {
         mz_sql_parser::ast::FunctionArgs::Star => vec![],
-        mz_sql_parser::ast::FunctionArgs::Args { args, order_by } => {
+        mz_sql_parser::ast::FunctionArgs::Args { args, order_by, .. } => {
             if !order_by.is_empty() {
                 sql_bail!(
                     "ORDER BY specified, but {} is not an aggregate function",
@@ -5241,7 +6552,7 @@ fn plan_window_function_non_aggr<'a>(
         FunctionArgs::Star => {
             sql_bail!("* argument is invalid with non-aggregate function {}", name)
         }
-        FunctionArgs::Args { args, order_by } => {
+        FunctionArgs::Args { args, order_by, .. } => {
             if !order_by.is_empty() {
                 sql_bail!(
                     "ORDER BY specified, but {} is not an aggregate function",
@@ -5450,6 +6761,14 @@ pub fn scalar_type_from_sql(
                 custom_id: None,
             })
         }
+        ResolvedDataType::AnonymousEnum(labels) => {
+            validate_enum_labels(labels)?;
+            Ok(ScalarType::Enum {
+                labels: labels.clone(),
+                custom_id: None,
+                custom_oid: None,
+            })
+        }
         ResolvedDataType::Named { id, modifiers, .. } => {
             scalar_type_from_catalog(scx.catalog, *id, modifiers)
         }
@@ -5457,6 +6776,28 @@ pub fn scalar_type_from_sql(
     }
 }
 
+/// The maximum length, in bytes, of an enum label. Matches PostgreSQL's
+/// `NAMEDATALEN - 1`.
+const MAX_ENUM_LABEL_LEN: usize = 63;
+
+/// Validates the labels of an enum type, as provided by the user.
+pub(crate) fn validate_enum_labels(labels: &[String]) -> Result<(), PlanError> {
+    let mut seen = BTreeSet::new();
+    for label in labels {
+        if label.len() > MAX_ENUM_LABEL_LEN {
+            sql_bail!(
+                "invalid enum label {}: labels must be {} bytes or less",
+                label.quoted(),
+                MAX_ENUM_LABEL_LEN
+            );
+        }
+        if !seen.insert(label) {
+            sql_bail!("enum label {} used more than once", label.quoted());
+        }
+    }
+    Ok(())
+}
+
 pub fn scalar_type_from_catalog(
     catalog: &dyn SessionCatalog,
     id: GlobalId,
@@ -5649,6 +6990,14 @@ pub fn scalar_type_from_catalog(
                 CatalogType::Uuid => Ok(ScalarType::Uuid),
                 CatalogType::Int2Vector => Ok(ScalarType::Int2Vector),
                 CatalogType::MzAclItem => Ok(ScalarType::MzAclItem),
+                CatalogType::Inet => Ok(ScalarType::Inet),
+                CatalogType::Cidr => Ok(ScalarType::Cidr),
+                CatalogType::MacAddr => Ok(ScalarType::MacAddr),
+                CatalogType::Enum { labels } => Ok(ScalarType::Enum {
+                    labels: labels.clone(),
+                    custom_id: Some(id),
+                    custom_oid: Some(entry.oid()),
+                }),
                 CatalogType::Numeric => unreachable!("handled above"),
                 CatalogType::Char => unreachable!("handled above"),
                 CatalogType::VarChar => unreachable!("handled above"),
@@ -5864,6 +7213,35 @@ impl Visit<'_, Aug> for WindowFuncCollector {
     }
 }
 
+#[derive(Default)]
+struct GroupingFuncCollector {
+    grouping_funcs: Vec<Expr<Aug>>,
+}
+
+impl GroupingFuncCollector {
+    fn into_result(self) -> Vec<Expr<Aug>> {
+        // Dedup while preserving the order.
+        let mut seen = BTreeSet::new();
+        self.grouping_funcs
+            .into_iter()
+            .filter(move |expr| seen.insert(expr.clone()))
+            .collect()
+    }
+}
+
+impl Visit<'_, Aug> for GroupingFuncCollector {
+    fn visit_expr(&mut self, expr: &Expr<Aug>) {
+        if let Expr::Grouping(_) = expr {
+            self.grouping_funcs.push(expr.clone());
+        }
+        visit::visit_expr(self, expr);
+    }
+
+    fn visit_query(&mut self, _query: &Query<Aug>) {
+        // Don't go into subqueries. Those will be handled by their own `plan_view_select`.
+    }
+}
+
 /// Specifies how long a query will live.
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 pub enum QueryLifetime {
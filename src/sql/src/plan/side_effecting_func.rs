@@ -159,7 +159,12 @@ fn extract_sef_call(
         expr:
             Expr::Function(Function {
                 name,
-                args: FunctionArgs::Args { args, order_by },
+                args:
+                    FunctionArgs::Args {
+                        args,
+                        order_by,
+                        within_group: false,
+                    },
                 filter: None,
                 over: None,
                 distinct: false,
@@ -14,7 +14,7 @@
 use std::cell::RefCell;
 use std::collections::{BTreeMap, BTreeSet};
 
-use mz_repr::{ColumnType, GlobalId, RelationDesc, ScalarType};
+use mz_repr::{ColumnName, ColumnType, GlobalId, RelationDesc, ScalarType};
 use mz_sql_parser::ast::{
     ColumnDef, ConnectionDefaultAwsPrivatelink, CreateMaterializedViewStatement, RawItemName,
     ShowStatement, StatementKind, TableConstraint, UnresolvedDatabaseName, UnresolvedSchemaName,
@@ -49,6 +49,7 @@ mod validate;
 
 use crate::session::vars;
 pub(crate) use ddl::PgConfigOptionExtracted;
+pub(crate) use ddl::PostgresSinkConfigOptionExtracted;
 use mz_controller_types::ClusterId;
 use mz_pgrepr::oid::{FIRST_MATERIALIZE_OID, FIRST_USER_OID};
 use mz_repr::role_id::RoleId;
@@ -115,6 +116,7 @@ pub fn describe(
         catalog,
         param_types: RefCell::new(param_types),
         ambiguous_columns: RefCell::new(false),
+        function_param_names: BTreeMap::new(),
     };
 
     let desc = match stmt {
@@ -147,6 +149,7 @@ pub fn describe(
         Statement::CreateSubsource(stmt) => ddl::describe_create_subsource(&scx, stmt)?,
         Statement::CreateTable(stmt) => ddl::describe_create_table(&scx, stmt)?,
         Statement::CreateType(stmt) => ddl::describe_create_type(&scx, stmt)?,
+        Statement::CreateFunction(stmt) => ddl::describe_create_function(&scx, stmt)?,
         Statement::CreateView(stmt) => ddl::describe_create_view(&scx, stmt)?,
         Statement::CreateMaterializedView(stmt) => {
             ddl::describe_create_materialized_view(&scx, stmt)?
@@ -172,6 +175,9 @@ pub fn describe(
         Statement::Show(ShowStatement::ShowCreateConnection(stmt)) => {
             show::describe_show_create_connection(&scx, stmt)?
         }
+        Statement::Show(ShowStatement::ShowCreateFunction(stmt)) => {
+            show::describe_show_create_function(&scx, stmt)?
+        }
         Statement::Show(ShowStatement::ShowCreateIndex(stmt)) => {
             show::describe_show_create_index(&scx, stmt)?
         }
@@ -213,6 +219,7 @@ pub fn describe(
         Statement::Delete(stmt) => dml::describe_delete(&scx, stmt)?,
         Statement::ExplainPlan(stmt) => dml::describe_explain_plan(&scx, stmt)?,
         Statement::ExplainPushdown(stmt) => dml::describe_explain_pushdown(&scx, stmt)?,
+        Statement::ExplainAnalyze(stmt) => dml::describe_explain_analyze(&scx, stmt)?,
         Statement::ExplainTimestamp(stmt) => dml::describe_explain_timestamp(&scx, stmt)?,
         Statement::ExplainSinkSchema(stmt) => dml::describe_explain_schema(&scx, stmt)?,
         Statement::Insert(stmt) => dml::describe_insert(&scx, stmt)?,
@@ -280,6 +287,7 @@ pub fn plan(
         catalog,
         param_types: RefCell::new(param_types),
         ambiguous_columns: RefCell::new(false),
+        function_param_names: BTreeMap::new(),
     };
 
     if resolved_ids
@@ -326,6 +334,7 @@ pub fn plan(
         Statement::CreateSubsource(stmt) => ddl::plan_create_subsource(scx, stmt),
         Statement::CreateTable(stmt) => ddl::plan_create_table(scx, stmt),
         Statement::CreateType(stmt) => ddl::plan_create_type(scx, stmt),
+        Statement::CreateFunction(stmt) => ddl::plan_create_function(scx, stmt),
         Statement::CreateView(stmt) => ddl::plan_create_view(scx, stmt, params),
         Statement::CreateMaterializedView(stmt) => {
             ddl::plan_create_materialized_view(scx, stmt, params)
@@ -347,6 +356,7 @@ pub fn plan(
         Statement::Delete(stmt) => dml::plan_delete(scx, stmt, params),
         Statement::ExplainPlan(stmt) => dml::plan_explain_plan(scx, stmt, params),
         Statement::ExplainPushdown(stmt) => dml::plan_explain_pushdown(scx, stmt, params),
+        Statement::ExplainAnalyze(stmt) => dml::plan_explain_analyze(scx, stmt),
         Statement::ExplainTimestamp(stmt) => dml::plan_explain_timestamp(scx, stmt, params),
         Statement::ExplainSinkSchema(stmt) => dml::plan_explain_schema(scx, stmt),
         Statement::Insert(stmt) => dml::plan_insert(scx, stmt, params),
@@ -359,6 +369,9 @@ pub fn plan(
         Statement::Show(ShowStatement::ShowCreateConnection(stmt)) => {
             show::plan_show_create_connection(scx, stmt).map(Plan::ShowCreate)
         }
+        Statement::Show(ShowStatement::ShowCreateFunction(stmt)) => {
+            show::plan_show_create_function(scx, stmt).map(Plan::ShowCreate)
+        }
         Statement::Show(ShowStatement::ShowCreateIndex(stmt)) => {
             show::plan_show_create_index(scx, stmt).map(Plan::ShowCreate)
         }
@@ -469,6 +482,9 @@ pub struct StatementContext<'a> {
     /// Whether the statement contains an expression that can make the exact column list
     /// ambiguous. For example `NATURAL JOIN` or `SELECT *`. This is filled in as planning occurs.
     pub ambiguous_columns: RefCell<bool>,
+    /// The positions of the named parameters of the SQL function whose body is
+    /// being planned, if any.
+    pub function_param_names: BTreeMap<ColumnName, usize>,
 }
 
 impl<'a> StatementContext<'a> {
@@ -481,6 +497,7 @@ impl<'a> StatementContext<'a> {
             catalog,
             param_types: Default::default(),
             ambiguous_columns: RefCell::new(false),
+            function_param_names: BTreeMap::new(),
         }
     }
 
@@ -763,6 +780,11 @@ impl<'a> StatementContext<'a> {
             mz_pgrepr::Type::TimeTz { precision } => *precision = None,
             mz_pgrepr::Type::Timestamp { precision } => *precision = None,
             mz_pgrepr::Type::TimestampTz { precision } => *precision = None,
+            // Enums are user-defined types, which purification resolves
+            // through the catalog instead.
+            mz_pgrepr::Type::Enum { labels, .. } => {
+                sql_bail!("internal error, unexpected user type: enum {labels:?}")
+            }
             _ => (),
         }
         // NOTE(benesch): this *looks* gross, but it is
@@ -15,6 +15,7 @@
 use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::fmt::Write;
 use std::iter;
+use std::str::FromStr;
 use std::time::Duration;
 
 use itertools::{Either, Itertools};
@@ -25,6 +26,7 @@ use mz_controller_types::{
 use mz_expr::refresh_schedule::{RefreshEvery, RefreshSchedule};
 use mz_expr::{CollectionPlan, UnmaterializableFunc};
 use mz_interchange::avro::{AvroSchemaGenerator, AvroSchemaOptions, DocTarget};
+use mz_interchange::protobuf::{ProtobufSchemaGenerator, ProtobufSchemaOptions};
 use mz_ore::cast::{CastFrom, TryCastFrom};
 use mz_ore::collections::HashSet;
 use mz_ore::soft_panic_or_log;
@@ -50,22 +52,25 @@ use mz_sql_parser::ast::{
     ClusterOptionName, ColumnOption, CommentObjectType, CommentStatement,
     CreateClusterReplicaStatement, CreateClusterStatement, CreateConnectionOption,
     CreateConnectionOptionName, CreateConnectionStatement, CreateConnectionType,
-    CreateDatabaseStatement, CreateIndexStatement, CreateMaterializedViewStatement,
-    CreateRoleStatement, CreateSchemaStatement, CreateSecretStatement, CreateSinkConnection,
-    CreateSinkOption, CreateSinkOptionName, CreateSinkStatement, CreateSourceConnection,
-    CreateSourceFormat, CreateSourceOption, CreateSourceOptionName, CreateSourceStatement,
-    CreateSubsourceOption, CreateSubsourceOptionName, CreateSubsourceStatement,
-    CreateTableStatement, CreateTypeAs, CreateTypeListOption, CreateTypeListOptionName,
-    CreateTypeMapOption, CreateTypeMapOptionName, CreateTypeStatement, CreateViewStatement,
-    CreateWebhookSourceStatement, CsrConfigOption, CsrConfigOptionName, CsrConnection,
-    CsrConnectionAvro, CsrConnectionProtobuf, CsrSeedProtobuf, CsvColumns, DeferredItemName,
+    CreateDatabaseStatement, CreateFunctionStatement, CreateIndexStatement,
+    CreateMaterializedViewStatement, CreateRoleStatement, CreateSchemaStatement,
+    CreateSecretStatement, CreateSinkConnection, CreateSinkOption, CreateSinkOptionName,
+    CreateSinkStatement, CreateSourceConnection, CreateSourceFormat, CreateSourceOption,
+    CreateSourceOptionName, CreateSourceStatement, CreateSubsourceOption,
+    CreateSubsourceOptionName, CreateSubsourceStatement, CreateTableStatement, CreateTypeAs,
+    CreateTypeListOption, CreateTypeListOptionName, CreateTypeMapOption, CreateTypeMapOptionName,
+    CreateTypeStatement, CreateViewStatement, CreateWebhookSourceStatement, CsrConfigOption,
+    CsrConfigOptionName, CsrConnection, CsrConnectionAvro, CsrConnectionJson,
+    CsrConnectionProtobuf, CsrSeedJson, CsrSeedProtobuf, CsvColumns, DeferredItemName,
     DocOnIdentifier, DocOnSchema, DropObjectsStatement, DropOwnedStatement, Expr, Format, Ident,
     IfExistsBehavior, IndexOption, IndexOptionName, KafkaSinkConfigOption, KeyConstraint,
     LoadGeneratorOption, LoadGeneratorOptionName, MaterializedViewOption,
-    MaterializedViewOptionName, MySqlConfigOption, MySqlConfigOptionName, PgConfigOption,
-    PgConfigOptionName, ProtobufSchema, QualifiedReplica, ReferencedSubsources,
-    RefreshAtOptionValue, RefreshEveryOptionValue, RefreshOptionValue, ReplicaDefinition,
-    ReplicaOption, ReplicaOptionName, RoleAttribute, SetRoleVar, SourceIncludeMetadata, Statement,
+    MaterializedViewOptionName, MongoDbConfigOption, MongoDbConfigOptionName, MySqlConfigOption,
+    MySqlConfigOptionName, PgConfigOption, PgConfigOptionName, PostgresSinkConfigOption,
+    ProtobufSchema, QualifiedReplica, ReferencedSubsources, RefreshAtOptionValue,
+    RefreshEveryOptionValue, RefreshOptionValue, ReplicaDefinition, ReplicaOption,
+    ReplicaOptionName, RoleAttribute, S3ConfigOption, S3ConfigOptionName, SetRoleVar,
+    SourceIncludeMetadata, SqlServerConfigOption, SqlServerConfigOptionName, Statement,
     TableConstraint, TableOption, TableOptionName, UnresolvedDatabaseName, UnresolvedItemName,
     UnresolvedObjectName, UnresolvedSchemaName, Value, ViewDefinition,
 };
@@ -73,17 +78,21 @@ use mz_sql_parser::ident;
 use mz_storage_types::connections::inline::{ConnectionAccess, ReferencedConnection};
 use mz_storage_types::connections::Connection;
 use mz_storage_types::sinks::{
-    KafkaIdStyle, KafkaSinkConnection, KafkaSinkFormat, SinkEnvelope, StorageSinkConnection,
+    HttpSinkConnection, KafkaIdStyle, KafkaSinkConnection, KafkaSinkFormat, PostgresSinkConnection,
+    SinkEnvelope, StorageSinkConnection,
 };
 use mz_storage_types::sources::encoding::{
-    included_column_desc, AvroEncoding, ColumnSpec, CsvEncoding, DataEncoding, ProtobufEncoding,
-    RegexEncoding, SourceDataEncoding,
+    included_column_desc, AvroEncoding, AvroOcfEncoding, ColumnSpec, CsvEncoding, DataEncoding,
+    JsonSchemaEncoding, ProtobufEncoding, RegexEncoding, SourceDataEncoding,
 };
 use mz_storage_types::sources::envelope::{
     KeyEnvelope, SourceEnvelope, UnplannedSourceEnvelope, UpsertStyle,
 };
 use mz_storage_types::sources::kafka::{KafkaMetadataKind, KafkaSourceConnection};
 use mz_storage_types::sources::load_generator::{LoadGenerator, LoadGeneratorSourceConnection};
+use mz_storage_types::sources::mongodb::{
+    MongoDbSourceConnection, MongoDbSourceDetails, ProtoMongoDbSourceDetails,
+};
 use mz_storage_types::sources::mysql::{
     MySqlSourceConnection, MySqlSourceDetails, ProtoMySqlSourceDetails,
 };
@@ -91,6 +100,10 @@ use mz_storage_types::sources::postgres::{
     PostgresSourceConnection, PostgresSourcePublicationDetails,
     ProtoPostgresSourcePublicationDetails,
 };
+use mz_storage_types::sources::s3::{S3ObjectFormat, S3SourceConnection};
+use mz_storage_types::sources::sql_server::{
+    ProtoSqlServerSourceDetails, SqlServerSourceConnection, SqlServerSourceDetails,
+};
 use mz_storage_types::sources::{GenericSourceConnection, SourceConnection, SourceDesc, Timeline};
 use prost::Message;
 
@@ -101,14 +114,16 @@ use crate::catalog::{
 };
 use crate::kafka_util::{KafkaSinkConfigOptionExtracted, KafkaSourceConfigOptionExtracted};
 use crate::names::{
-    Aug, CommentObjectId, DatabaseId, ObjectId, PartialItemName, QualifiedItemName,
+    self, Aug, CommentObjectId, DatabaseId, ObjectId, PartialItemName, QualifiedItemName,
     RawDatabaseSpecifier, ResolvedClusterName, ResolvedColumnName, ResolvedDataType,
     ResolvedDatabaseSpecifier, ResolvedItemName, SchemaSpecifier, SystemObjectId,
 };
 use crate::normalize::{self, ident};
 use crate::plan::error::PlanError;
 use crate::plan::expr::ColumnRef;
-use crate::plan::query::{plan_expr, scalar_type_from_catalog, ExprContext, QueryLifetime};
+use crate::plan::query::{
+    plan_expr, scalar_type_from_catalog, validate_enum_labels, ExprContext, QueryLifetime,
+};
 use crate::plan::scope::Scope;
 use crate::plan::statement::ddl::connection::{INALTERABLE_OPTIONS, MUTUALLY_EXCLUSIVE_SETS};
 use crate::plan::statement::{scl, StatementContext, StatementDesc};
@@ -123,13 +138,13 @@ use crate::plan::{
     AlterSystemResetPlan, AlterSystemSetPlan, CommentPlan, ComputeReplicaConfig,
     ComputeReplicaIntrospectionConfig, CreateClusterManagedPlan, CreateClusterPlan,
     CreateClusterReplicaPlan, CreateClusterUnmanagedPlan, CreateClusterVariant,
-    CreateConnectionPlan, CreateDatabasePlan, CreateIndexPlan, CreateMaterializedViewPlan,
-    CreateRolePlan, CreateSchemaPlan, CreateSecretPlan, CreateSinkPlan, CreateSourcePlan,
-    CreateTablePlan, CreateTypePlan, CreateViewPlan, DataSourceDesc, DropObjectsPlan,
-    DropOwnedPlan, FullItemName, HirScalarExpr, Index, Ingestion, MaterializedView, Params, Plan,
-    PlanClusterOption, PlanNotice, QueryContext, ReplicaConfig, Secret, Sink, Source, Table, Type,
-    VariableValue, View, WebhookBodyFormat, WebhookHeaderFilters, WebhookHeaders,
-    WebhookValidation,
+    CreateConnectionPlan, CreateDatabasePlan, CreateFunctionPlan, CreateIndexPlan,
+    CreateMaterializedViewPlan, CreateRolePlan, CreateSchemaPlan, CreateSecretPlan, CreateSinkPlan,
+    CreateSourcePlan, CreateTablePlan, CreateTypePlan, CreateViewPlan, DataSourceDesc,
+    DropObjectsPlan, DropOwnedPlan, FullItemName, Function, HirScalarExpr, Index, Ingestion,
+    MaterializedView, Params, Plan, PlanClusterOption, PlanNotice, QueryContext, ReplicaConfig,
+    Secret, Sink, Source, Table, Type, VariableValue, View, WebhookBodyFormat,
+    WebhookHeaderFilters, WebhookHeaders, WebhookValidation,
 };
 use crate::session::vars;
 use crate::session::vars::ENABLE_REFRESH_EVERY_MVS;
@@ -433,6 +448,12 @@ generate_extracted_config!(
     (IgnoreColumns, Vec::<UnresolvedItemName>, Default(vec![]))
 );
 
+generate_extracted_config!(S3ConfigOption, (Prefix, String), (PollInterval, Duration));
+
+generate_extracted_config!(SqlServerConfigOption, (Details, String));
+
+generate_extracted_config!(MongoDbConfigOption, (Details, String));
+
 pub fn plan_create_webhook_source(
     scx: &StatementContext,
     mut stmt: CreateWebhookSourceStatement<Aug>,
@@ -870,19 +891,29 @@ pub fn plan_create_source(
                 // column and casts it to the appropriate target type
                 let mut column_casts = vec![];
                 for (i, column) in table.columns.iter().enumerate() {
-                    let ty = match text_cols.get(&Oid(table.oid)) {
+                    let scalar_type = match (text_cols.get(&Oid(table.oid)), &column.enum_labels) {
                         // Treat the column as text if it was referenced in
                         // `TEXT COLUMNS`. This is the only place we need to
                         // perform this logic; even if the type is unsupported,
                         // we'll be able to ingest its values as text in
                         // storage.
-                        Some(names) if names.contains(&column.name) => mz_pgrepr::Type::Text,
+                        (Some(names), _) if names.contains(&column.name) => ScalarType::String,
+                        // The subsource column has a catalog enum type with
+                        // the same labels, and thus the same representation.
+                        (_, Some(labels)) => ScalarType::Enum {
+                            labels: labels.clone(),
+                            custom_id: None,
+                            custom_oid: None,
+                        },
                         _ => {
                             match mz_pgrepr::Type::from_oid_and_typmod(
                                 column.type_oid,
                                 column.type_mod,
                             ) {
-                                Ok(t) => t,
+                                Ok(t) => {
+                                    let data_type = scx.resolve_type(t)?;
+                                    query::scalar_type_from_sql(scx, &data_type)?
+                                }
                                 // If this reference survived purification, we
                                 // do not expect it to be from a table that the
                                 // user will consume., i.e. expect this table to
@@ -914,9 +945,6 @@ pub fn plan_create_source(
                         }
                     };
 
-                    let data_type = scx.resolve_type(ty)?;
-                    let scalar_type = query::scalar_type_from_sql(scx, &data_type)?;
-
                     let col_expr = HirScalarExpr::Column(ColumnRef {
                         level: 0,
                         column: i,
@@ -1051,6 +1079,180 @@ pub fn plan_create_source(
 
             (connection, Some(available_subsources))
         }
+        CreateSourceConnection::SqlServer {
+            connection,
+            options,
+        } => {
+            scx.require_feature_flag(&vars::ENABLE_SQL_SERVER_SOURCE)?;
+
+            let connection_item = scx.get_item_by_resolved_name(connection)?;
+            let sql_server_connection = match connection_item.connection()? {
+                Connection::SqlServer(connection) => connection,
+                _ => sql_bail!(
+                    "{} is not a SQL Server connection",
+                    scx.catalog.resolve_full_name(connection_item.name())
+                ),
+            };
+            let SqlServerConfigOptionExtracted { details, seen: _ } = options.clone().try_into()?;
+
+            let details = details
+                .as_ref()
+                .ok_or_else(|| sql_err!("internal error: SQL Server source missing details"))?;
+            let details = hex::decode(details).map_err(|e| sql_err!("{}", e))?;
+            let details =
+                ProtoSqlServerSourceDetails::decode(&*details).map_err(|e| sql_err!("{}", e))?;
+            let details =
+                SqlServerSourceDetails::from_proto(details).map_err(|e| sql_err!("{}", e))?;
+
+            let mut available_subsources = BTreeMap::new();
+
+            for (index, table) in details.tables.iter().enumerate() {
+                let name = FullItemName {
+                    // SQL Server tables live inside the database named by the connection.
+                    database: RawDatabaseSpecifier::Name(sql_server_connection.database.clone()),
+                    schema: table.schema_name.clone(),
+                    item: table.name.clone(),
+                };
+                // The zero-th output is the main output
+                available_subsources.insert(name, index + 1);
+            }
+
+            let connection =
+                GenericSourceConnection::<ReferencedConnection>::from(SqlServerSourceConnection {
+                    connection: connection_item.id(),
+                    connection_id: connection_item.id(),
+                    details,
+                });
+
+            (connection, Some(available_subsources))
+        }
+        CreateSourceConnection::MongoDb {
+            connection,
+            options,
+        } => {
+            scx.require_feature_flag(&vars::ENABLE_MONGODB_SOURCE)?;
+
+            let connection_item = scx.get_item_by_resolved_name(connection)?;
+            let mongodb_connection = match connection_item.connection()? {
+                Connection::MongoDb(connection) => connection,
+                _ => sql_bail!(
+                    "{} is not a MongoDB connection",
+                    scx.catalog.resolve_full_name(connection_item.name())
+                ),
+            };
+            let MongoDbConfigOptionExtracted { details, seen: _ } = options.clone().try_into()?;
+
+            let details = details
+                .as_ref()
+                .ok_or_else(|| sql_err!("internal error: MongoDB source missing details"))?;
+            let details = hex::decode(details).map_err(|e| sql_err!("{}", e))?;
+            let details =
+                ProtoMongoDbSourceDetails::decode(&*details).map_err(|e| sql_err!("{}", e))?;
+            let details =
+                MongoDbSourceDetails::from_proto(details).map_err(|e| sql_err!("{}", e))?;
+
+            // Without subsources, all collections are ingested into the
+            // source itself.
+            let available_subsources = details.subsources.then(|| {
+                let mut available_subsources = BTreeMap::new();
+                for (index, collection) in details.collections.iter().enumerate() {
+                    let name = FullItemName {
+                        // MongoDB has no concept of schemas, so we use 'mongodb' as the
+                        // database name, like we do for MySQL, and the upstream database as
+                        // the schema name.
+                        database: RawDatabaseSpecifier::Name("mongodb".to_string()),
+                        schema: mongodb_connection.database.clone(),
+                        item: collection.clone(),
+                    };
+                    // The zero-th output is the main output
+                    available_subsources.insert(name, index + 1);
+                }
+                available_subsources
+            });
+
+            let connection =
+                GenericSourceConnection::<ReferencedConnection>::from(MongoDbSourceConnection {
+                    connection: connection_item.id(),
+                    connection_id: connection_item.id(),
+                    details,
+                });
+
+            (connection, available_subsources)
+        }
+        CreateSourceConnection::S3 {
+            connection,
+            options,
+        } => {
+            scx.require_feature_flag(&vars::ENABLE_S3_SOURCE)?;
+
+            let connection_item = scx.get_item_by_resolved_name(connection)?;
+            if !matches!(connection_item.connection()?, Connection::Aws(_)) {
+                sql_bail!(
+                    "{} is not an AWS connection",
+                    scx.catalog.resolve_full_name(connection_item.name())
+                )
+            }
+
+            let S3ConfigOptionExtracted {
+                prefix,
+                poll_interval,
+                seen: _,
+            } = options.clone().try_into()?;
+
+            let Some(prefix) = prefix else {
+                sql_bail!("PREFIX option is required");
+            };
+            let uri = match http::Uri::from_str(&prefix) {
+                Ok(uri) if uri.scheme_str() == Some("s3") => uri,
+                Ok(_) => sql_bail!("PREFIX must be an 's3://...' url"),
+                Err(e) => sql_bail!("could not parse PREFIX url: {}", e),
+            };
+            let Some(bucket) = uri.host() else {
+                sql_bail!("PREFIX url must include a bucket");
+            };
+            let prefix = uri.path().trim_start_matches('/').to_string();
+
+            if poll_interval == Some(Duration::ZERO) {
+                sql_bail!("POLL INTERVAL must be greater than zero");
+            }
+
+            if !matches!(envelope, ast::SourceEnvelope::None) {
+                bail_unsupported!(format!("ENVELOPE {} with S3 sources", envelope));
+            }
+
+            let object_format = match format {
+                None => S3ObjectFormat::Lines { header: false },
+                Some(CreateSourceFormat::Bare(Format::Parquet)) => S3ObjectFormat::Parquet,
+                Some(CreateSourceFormat::Bare(Format::Csv {
+                    columns: CsvColumns::Header { .. },
+                    ..
+                })) => S3ObjectFormat::Lines { header: true },
+                Some(CreateSourceFormat::Bare(
+                    Format::Bytes
+                    | Format::Text
+                    | Format::Json { .. }
+                    | Format::Regex(_)
+                    | Format::Csv { .. },
+                )) => S3ObjectFormat::Lines { header: false },
+                Some(CreateSourceFormat::Bare(format)) => {
+                    bail_unsupported!(format!("FORMAT {} with S3 sources", format))
+                }
+                Some(CreateSourceFormat::KeyValue { .. }) => {
+                    sql_bail!("S3 sources do not support KEY FORMAT")
+                }
+            };
+
+            let connection = GenericSourceConnection::from(S3SourceConnection {
+                connection: connection_item.id(),
+                connection_id: connection_item.id(),
+                bucket: bucket.to_string(),
+                prefix,
+                format: object_format,
+                poll_interval,
+            });
+
+            (connection, None)
+        }
         CreateSourceConnection::LoadGenerator { generator, options } => {
             let (load_generator, available_subsources) =
                 load_generator_ast_to_generator(generator, options)?;
@@ -1155,7 +1357,14 @@ pub fn plan_create_source(
         seen: _,
     } = CreateSourceOptionExtracted::try_from(with_options.clone())?;
 
-    let encoding = match format {
+    let mut encoding = match format {
+        // Parquet files carry their own schema and are decoded by the S3
+        // source itself.
+        Some(CreateSourceFormat::Bare(Format::Parquet))
+            if matches!(external_connection, GenericSourceConnection::S3(_)) =>
+        {
+            None
+        }
         Some(format) => Some(get_encoding(scx, format, &envelope)?),
         None => None,
     };
@@ -1201,6 +1410,16 @@ pub fn plan_create_source(
         None => (None, external_connection.value_desc()),
     };
 
+    // S3 sources skip the header line of every object themselves, so the
+    // header names are only used to name the columns. The decoder must not
+    // treat the first line it sees as a header.
+    if let (GenericSourceConnection::S3(_), Some(encoding)) = (&external_connection, &mut encoding)
+    {
+        if let DataEncoding::Csv(CsvEncoding { columns, .. }) = &mut encoding.value {
+            *columns = ColumnSpec::Count(columns.arity());
+        }
+    }
+
     let mut key_envelope = get_key_envelope(include_metadata, encoding.as_ref())?;
 
     match (&envelope, &key_envelope) {
@@ -1761,6 +1980,17 @@ fn get_encoding(
         sql_bail!("ENVELOPE [DEBEZIUM] UPSERT requires that KEY FORMAT be specified");
     };
 
+    // Every Avro object container file decodes into any number of rows, so it
+    // can neither act as a key nor be used with envelopes that rely on keys.
+    if matches!(encoding.key, Some(DataEncoding::AvroOcf(_))) {
+        bail_unsupported!("KEY FORMAT AVRO OCF");
+    }
+    if matches!(encoding.value, DataEncoding::AvroOcf(_))
+        && !matches!(envelope, ast::SourceEnvelope::None)
+    {
+        bail_unsupported!(format!("ENVELOPE {} with FORMAT AVRO OCF", envelope));
+    }
+
     Ok(encoding)
 }
 
@@ -1891,6 +2121,14 @@ fn get_encoding_inner(
                 })
             }
         }
+        Format::AvroOcf {
+            schema: ast::Schema { schema },
+        } => {
+            scx.require_feature_flag(&vars::ENABLE_AVRO_OCF_FORMAT)?;
+            DataEncoding::AvroOcf(AvroOcfEncoding {
+                schema: schema.clone(),
+            })
+        }
         Format::Protobuf(schema) => match schema {
             ProtobufSchema::Csr {
                 csr_connection:
@@ -1979,7 +2217,61 @@ fn get_encoding_inner(
         }
         Format::Json { array: false } => DataEncoding::Json,
         Format::Json { array: true } => bail_unsupported!("JSON ARRAY format in sources"),
+        Format::JsonCsr {
+            csr_connection:
+                CsrConnectionJson {
+                    connection:
+                        CsrConnection {
+                            connection,
+                            options,
+                        },
+                    seed,
+                },
+        } => {
+            scx.require_feature_flag(&vars::ENABLE_JSON_SCHEMA_FORMAT)?;
+            let item = scx.get_item_by_resolved_name(connection)?;
+            match item.connection()? {
+                Connection::Csr(_) => {}
+                _ => {
+                    sql_bail!(
+                        "{} is not a schema registry connection",
+                        scx.catalog
+                            .resolve_full_name(item.name())
+                            .to_string()
+                            .quoted()
+                    )
+                }
+            };
+
+            if !options.is_empty() {
+                sql_bail!("JSON CSR connections do not support any options");
+            }
+
+            let Some(CsrSeedJson {
+                key_schema,
+                value_schema,
+            }) = seed
+            else {
+                unreachable!("CSR seed resolution should already have been called: JSON")
+            };
+
+            let value = DataEncoding::JsonSchema(JsonSchemaEncoding {
+                schema: value_schema.clone(),
+                confluent_wire_format: true,
+            });
+            if let Some(key_schema) = key_schema {
+                return Ok(SourceDataEncoding {
+                    key: Some(DataEncoding::JsonSchema(JsonSchemaEncoding {
+                        schema: key_schema.clone(),
+                        confluent_wire_format: true,
+                    })),
+                    value,
+                });
+            }
+            value
+        }
         Format::Text => DataEncoding::Text,
+        Format::Parquet => bail_unsupported!("FORMAT PARQUET with non-S3 sources"),
     };
     Ok(SourceDataEncoding { key: None, value })
 }
@@ -2022,7 +2314,9 @@ fn get_unnamed_key_envelope(
     let is_composite = match key {
         DataEncoding::Bytes | DataEncoding::Json | DataEncoding::Text => false,
         DataEncoding::Avro(_)
+        | DataEncoding::AvroOcf(_)
         | DataEncoding::Csv(_)
+        | DataEncoding::JsonSchema(_)
         | DataEncoding::Protobuf(_)
         | DataEncoding::Regex { .. } => true,
     };
@@ -2532,7 +2826,9 @@ pub fn plan_create_sink(
     let from = scx.get_item_by_resolved_name(&from)?;
     let desc = from.desc(&scx.catalog.resolve_full_name(from.name()))?;
     let key_indices = match &connection {
-        CreateSinkConnection::Kafka { key, .. } => {
+        CreateSinkConnection::Kafka { key, .. }
+        | CreateSinkConnection::Http { key, .. }
+        | CreateSinkConnection::Postgres { key, .. } => {
             if let Some(key) = key.clone() {
                 let key_columns = key
                     .key_columns
@@ -2616,11 +2912,13 @@ pub fn plan_create_sink(
         CreateSinkConnection::Kafka {
             connection,
             options,
+            headers,
             ..
         } => kafka_sink_builder(
             scx,
             connection,
             options,
+            headers,
             format,
             relation_key_indices,
             key_desc_and_indices,
@@ -2628,6 +2926,28 @@ pub fn plan_create_sink(
             envelope,
             from.id(),
         )?,
+        CreateSinkConnection::Http { connection, .. } => http_sink_builder(
+            scx,
+            connection,
+            format,
+            relation_key_indices,
+            key_desc_and_indices,
+            desc.into_owned(),
+        )?,
+        CreateSinkConnection::Postgres {
+            connection,
+            options,
+            ..
+        } => postgres_sink_builder(
+            scx,
+            connection,
+            options,
+            format,
+            relation_key_indices,
+            key_desc_and_indices,
+            desc.into_owned(),
+            envelope,
+        )?,
     };
 
     let CreateSinkOptionExtracted { snapshot, seen: _ } = with_options.try_into()?;
@@ -2772,6 +3092,7 @@ fn kafka_sink_builder(
     scx: &StatementContext,
     connection: ResolvedItemName,
     options: Vec<KafkaSinkConfigOption<Aug>>,
+    headers: Option<Ident>,
     format: Option<Format<Aug>>,
     relation_key_indices: Option<Vec<usize>>,
     key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
@@ -2796,6 +3117,7 @@ fn kafka_sink_builder(
         progress_group_id_prefix,
         transactional_id_prefix,
         legacy_ids,
+        partition_by,
         seen: _,
     }: KafkaSinkConfigOptionExtracted = options.try_into()?;
 
@@ -2817,6 +3139,61 @@ fn kafka_sink_builder(
 
     let topic_name = topic.ok_or_else(|| sql_err!("KAFKA CONNECTION must specify TOPIC"))?;
 
+    let headers_index = match headers {
+        Some(headers) => {
+            scx.require_feature_flag(&vars::ENABLE_KAFKA_SINK_HEADERS)?;
+            let headers = normalize::column_name(headers);
+            let Some((index, typ)) = value_desc.get_by_name(&headers) else {
+                sql_bail!("HEADERS column ({}) is unknown", headers);
+            };
+            // Headers are either a map from header names to values, or a list
+            // of name-value records when names may repeat.
+            let is_header_value =
+                |typ: &ScalarType| matches!(typ, ScalarType::String | ScalarType::Bytes);
+            let valid = match &typ.scalar_type {
+                ScalarType::Map { value_type, .. } => is_header_value(value_type),
+                ScalarType::List { element_type, .. } => match &**element_type {
+                    ScalarType::Record { fields, .. } => match &fields[..] {
+                        [(key_name, key_type), (value_name, value_type)] => {
+                            key_name.as_str() == "key"
+                                && key_type.scalar_type == ScalarType::String
+                                && value_name.as_str() == "value"
+                                && is_header_value(&value_type.scalar_type)
+                        }
+                        _ => false,
+                    },
+                    _ => false,
+                },
+                _ => false,
+            };
+            if !valid {
+                sql_bail!(
+                    "HEADERS column must have type map[text => text], map[text => bytea], or a \
+                     list of records with fields key text and value text or bytea, but has type {}",
+                    scx.humanize_column_type(typ)
+                );
+            }
+            Some(index)
+        }
+        None => None,
+    };
+
+    let partition_by = match partition_by {
+        Some(partition_by) => {
+            scx.require_feature_flag(&vars::ENABLE_KAFKA_SINK_PARTITION_BY)?;
+            let key_indices = key_desc_and_indices
+                .as_ref()
+                .map(|(_desc, indices)| indices.as_slice());
+            Some(query::plan_sink_partition_by(
+                scx,
+                &value_desc,
+                key_indices,
+                partition_by,
+            )?)
+        }
+        None => None,
+    };
+
     let format = match format {
         Some(Format::Avro(AvroSchema::Csr {
             csr_connection:
@@ -2905,6 +3282,55 @@ fn kafka_sink_builder(
                 csr_connection,
             }
         }
+        Some(Format::Protobuf(ProtobufSchema::Csr {
+            csr_connection:
+                CsrConnectionProtobuf {
+                    connection:
+                        CsrConnection {
+                            connection,
+                            options,
+                        },
+                    seed,
+                },
+        })) => {
+            if seed.is_some() {
+                sql_bail!("SEED option does not make sense with sinks");
+            }
+            if !options.is_empty() {
+                sql_bail!("CONFLUENT SCHEMA REGISTRY options are not supported for PROTOBUF sinks");
+            }
+
+            let item = scx.get_item_by_resolved_name(&connection)?;
+            let csr_connection = match item.connection()? {
+                Connection::Csr(_) => item.id(),
+                _ => {
+                    sql_bail!(
+                        "{} is not a schema registry connection",
+                        scx.catalog
+                            .resolve_full_name(item.name())
+                            .to_string()
+                            .quoted()
+                    )
+                }
+            };
+
+            let options = ProtobufSchemaOptions {
+                is_debezium: matches!(envelope, SinkEnvelope::Debezium),
+            };
+            let schema_generator = ProtobufSchemaGenerator::new(
+                key_desc_and_indices
+                    .as_ref()
+                    .map(|(desc, _indices)| desc.clone()),
+                value_desc.clone(),
+                options,
+            )?;
+
+            KafkaSinkFormat::Protobuf {
+                key_schema: schema_generator.key_schema(),
+                value_schema: schema_generator.value_schema(),
+                csr_connection,
+            }
+        }
         Some(Format::Json { array: false }) => KafkaSinkFormat::Json,
         Some(Format::Json { array: true }) => bail_unsupported!("JSON ARRAY format in sinks"),
         Some(format) => bail_unsupported!(format!("sink format {:?}", format)),
@@ -2919,12 +3345,114 @@ fn kafka_sink_builder(
         relation_key_indices,
         key_desc_and_indices,
         value_desc,
+        headers_index,
+        partition_by,
         compression_type,
         progress_group_id,
         transactional_id,
     }))
 }
 
+fn http_sink_builder(
+    scx: &StatementContext,
+    connection: ResolvedItemName,
+    format: Option<Format<Aug>>,
+    relation_key_indices: Option<Vec<usize>>,
+    key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    value_desc: RelationDesc,
+) -> Result<StorageSinkConnection<ReferencedConnection>, PlanError> {
+    scx.require_feature_flag(&vars::ENABLE_HTTP_SINK)?;
+
+    // Get HTTP connection.
+    let connection_item = scx.get_item_by_resolved_name(&connection)?;
+    let connection_id = connection_item.id();
+    match connection_item.connection()? {
+        Connection::Http(_) => (),
+        _ => sql_bail!(
+            "{} is not an http connection",
+            scx.catalog.resolve_full_name(connection_item.name())
+        ),
+    };
+
+    match format {
+        Some(Format::Json { array: false }) => (),
+        Some(Format::Json { array: true }) => bail_unsupported!("JSON ARRAY format in sinks"),
+        Some(format) => bail_unsupported!(format!("HTTP sink format {:?}", format)),
+        None => sql_bail!("HTTP CONNECTION sinks require FORMAT JSON"),
+    }
+
+    Ok(StorageSinkConnection::Http(HttpSinkConnection {
+        connection_id,
+        connection: connection_id,
+        relation_key_indices,
+        key_desc_and_indices,
+        value_desc,
+    }))
+}
+
+generate_extracted_config!(
+    PostgresSinkConfigOption,
+    (ProgressTable, String, Default("mz_sink_progress")),
+    (Schema, String, Default("public")),
+    (Table, String)
+);
+
+fn postgres_sink_builder(
+    scx: &StatementContext,
+    connection: ResolvedItemName,
+    options: Vec<PostgresSinkConfigOption<Aug>>,
+    format: Option<Format<Aug>>,
+    relation_key_indices: Option<Vec<usize>>,
+    key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    value_desc: RelationDesc,
+    envelope: SinkEnvelope,
+) -> Result<StorageSinkConnection<ReferencedConnection>, PlanError> {
+    scx.require_feature_flag(&vars::ENABLE_POSTGRES_SINK)?;
+
+    // Get Postgres connection.
+    let connection_item = scx.get_item_by_resolved_name(&connection)?;
+    let connection_id = connection_item.id();
+    match connection_item.connection()? {
+        Connection::Postgres(_) => (),
+        _ => sql_bail!(
+            "{} is not a postgres connection",
+            scx.catalog.resolve_full_name(connection_item.name())
+        ),
+    };
+
+    let PostgresSinkConfigOptionExtracted {
+        progress_table,
+        schema,
+        table,
+        seen: _,
+    } = options.try_into()?;
+
+    let table = table.ok_or_else(|| sql_err!("TABLE option is required"))?;
+    if table == progress_table {
+        sql_bail!("TABLE and PROGRESS TABLE must be different tables");
+    }
+
+    if format.is_some() {
+        sql_bail!("POSTGRES CONNECTION sinks do not support a FORMAT clause");
+    }
+    if envelope != SinkEnvelope::Upsert {
+        bail_unsupported!("POSTGRES CONNECTION sinks with envelopes other than UPSERT");
+    }
+    // Upsert sinks always have a key, see `plan_create_sink`.
+    let key_desc_and_indices = key_desc_and_indices.expect("upsert sinks have a key");
+
+    Ok(StorageSinkConnection::Postgres(PostgresSinkConnection {
+        connection_id,
+        connection: connection_id,
+        schema,
+        table,
+        progress_table,
+        key_desc_and_indices,
+        relation_key_indices,
+        value_desc,
+    }))
+}
+
 pub fn describe_create_index(
     _: &StatementContext,
     _: CreateIndexStatement<Aug>,
@@ -3180,6 +3708,10 @@ pub fn plan_create_type(
             }
             CatalogType::Record { fields }
         }
+        CreateTypeAs::Enum { labels } => {
+            validate_enum_labels(&labels)?;
+            CatalogType::Enum { labels }
+        }
     };
 
     let name = scx.allocate_qualified_name(normalize::unresolved_item_name(name)?)?;
@@ -3204,6 +3736,124 @@ pub fn plan_create_type(
     }))
 }
 
+pub fn describe_create_function(
+    _: &StatementContext,
+    _: CreateFunctionStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_create_function(
+    scx: &StatementContext,
+    stmt: CreateFunctionStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    scx.require_feature_flag(&vars::ENABLE_CREATE_FUNCTION)?;
+    let create_sql = normalize::create_statement(scx, Statement::CreateFunction(stmt.clone()))?;
+    let dependencies = names::visit_dependencies(&stmt);
+    let CreateFunctionStatement {
+        name,
+        if_exists,
+        params,
+        returns,
+        body,
+    } = stmt;
+
+    let inner = query::plan_sql_function(scx, params, returns, body)?;
+
+    let name = scx.allocate_qualified_name(normalize::unresolved_item_name(name)?)?;
+
+    // Check for an object in the catalog with this same name. Functions share
+    // a namespace with relations, so either conflicts. Functions of the same
+    // name are overloads, and conflict only if their parameter types match.
+    let full_name = scx.catalog.resolve_full_name(&name);
+    let partial_name = PartialItemName::from(full_name.clone());
+    let mut replaced = None;
+    if let Ok(item) = scx.catalog.resolve_function(&partial_name) {
+        if item.function_overloads().is_empty() {
+            return Err(PlanError::ItemAlreadyExists {
+                name: full_name.to_string(),
+                item_type: item.item_type(),
+            });
+        }
+        for id in item.function_overloads() {
+            let overload = scx.catalog.get_item(id);
+            let function = overload
+                .sql_function()
+                .expect("overloads are user-defined functions");
+            // Table functions and scalar functions are planned differently
+            // at their call sites, so they cannot share a name.
+            if function.returns_set() != inner.returns_set() {
+                let kind = |returns_set| if returns_set { "table" } else { "scalar" };
+                sql_bail!(
+                    "cannot create {} function {}: {} function with the same name already exists",
+                    kind(inner.returns_set()),
+                    full_name,
+                    kind(function.returns_set()),
+                );
+            }
+            let same_params = function.param_types.len() == inner.param_types.len()
+                && function
+                    .param_types
+                    .iter()
+                    .zip_eq(&inner.param_types)
+                    .all(|(a, b)| a.base_eq(b));
+            if same_params {
+                if if_exists != IfExistsBehavior::Replace {
+                    return Err(PlanError::ItemAlreadyExists {
+                        name: full_name.to_string(),
+                        item_type: overload.item_type(),
+                    });
+                }
+                replaced = Some(overload);
+            }
+        }
+    } else if let Ok(item) = scx.catalog.resolve_item(&partial_name) {
+        return Err(PlanError::ItemAlreadyExists {
+            name: full_name.to_string(),
+            item_type: item.item_type(),
+        });
+    }
+
+    let replace = match replaced {
+        Some(item) => {
+            // The body has already been inlined into the new definition, but
+            // the new definition would still record a dependency on the
+            // function it replaces.
+            if dependencies.0.contains(&item.id()) {
+                sql_bail!(
+                    "cannot replace function {0}: depended upon by new {0} definition",
+                    full_name
+                );
+            }
+            // Like `DROP FUNCTION` without `CASCADE`, replacing a function
+            // requires that no other object depends on it.
+            let dep = item
+                .used_by()
+                .iter()
+                .map(|id| scx.catalog.get_item(id))
+                .find(|dep| dependency_prevents_drop(ObjectType::Func, *dep));
+            if let Some(dep) = dep {
+                return Err(PlanError::DependentObjectsStillExist {
+                    object_type: item.item_type().to_string(),
+                    object_name: scx.catalog.minimal_qualification(item.name()).to_string(),
+                    dependents: vec![(
+                        dep.item_type().to_string(),
+                        scx.catalog.minimal_qualification(dep.name()).to_string(),
+                    )],
+                });
+            }
+            Some(item.id())
+        }
+        None => None,
+    };
+
+    Ok(Plan::CreateFunction(CreateFunctionPlan {
+        name,
+        function: Function { create_sql, inner },
+        replace,
+    }))
+}
+
 generate_extracted_config!(CreateTypeListOption, (ElementType, ResolvedDataType));
 
 generate_extracted_config!(
@@ -3777,6 +4427,12 @@ pub fn plan_create_connection(
         scx.require_feature_flag(&vars::ENABLE_AWS_CONNECTION)?;
     } else if let Connection::MySql(_) = &connection {
         scx.require_feature_flag(&vars::ENABLE_MYSQL_SOURCE)?;
+    } else if let Connection::Http(_) = &connection {
+        scx.require_feature_flag(&vars::ENABLE_HTTP_SINK)?;
+    } else if let Connection::SqlServer(_) = &connection {
+        scx.require_feature_flag(&vars::ENABLE_SQL_SERVER_SOURCE)?;
+    } else if let Connection::MongoDb(_) = &connection {
+        scx.require_feature_flag(&vars::ENABLE_MONGODB_SOURCE)?;
     }
     let name = scx.allocate_qualified_name(normalize::unresolved_item_name(name)?)?;
 
@@ -3852,11 +4508,6 @@ pub fn plan_drop_objects(
         cascade,
     }: DropObjectsStatement,
 ) -> Result<Plan, PlanError> {
-    assert_ne!(
-        object_type,
-        mz_sql_parser::ast::ObjectType::Func,
-        "rejected in parser"
-    );
     let object_type = object_type.into();
 
     let mut referenced_ids = Vec::new();
@@ -3883,7 +4534,19 @@ pub fn plan_drop_objects(
             }
         };
         match id {
-            Some(id) => referenced_ids.push(id),
+            Some(id) => {
+                // Dropping a function drops all of its overloads.
+                if let ObjectId::Item(item_id) = id {
+                    let overloads = scx.catalog.get_item(&item_id).function_overloads();
+                    referenced_ids.extend(
+                        overloads
+                            .iter()
+                            .filter(|overload| **overload != item_id)
+                            .map(|overload| ObjectId::Item(*overload)),
+                    );
+                }
+                referenced_ids.push(id);
+            }
             None => scx.catalog.add_notice(PlanNotice::ObjectDoesNotExist {
                 name: name.to_ast_string(),
                 object_type,
@@ -4093,10 +4756,12 @@ fn plan_drop_item_inner(
             if !cascade {
                 let entry_id = catalog_item.id();
                 // When this item gets dropped it will also drop its subsources, so we need to check the
-                // users of those
+                // users of those. Similarly, dropping a function drops all of its overloads.
+                let overloads = catalog_item.function_overloads();
                 let mut dropped_items = catalog_item
                     .subsources()
                     .iter()
+                    .chain(overloads.iter().filter(|id| **id != entry_id))
                     .map(|id| scx.catalog.get_item(id))
                     .collect_vec();
                 dropped_items.push(catalog_item);
@@ -4106,8 +4771,9 @@ fn plan_drop_item_inner(
                         // The catalog_entry we're trying to drop will appear in the used_by list of
                         // its subsources so we need to exclude it from cascade checking since it
                         // will be dropped. Similarly, if we're dropping a subsource, the primary
-                        // source will show up in its dependents but should not prevent the drop.
-                        if id == &entry_id || Some(id) == primary_source {
+                        // source will show up in its dependents but should not prevent the drop,
+                        // and neither should overloads that call each other.
+                        if id == &entry_id || Some(id) == primary_source || overloads.contains(id) {
                             continue;
                         }
 
@@ -5217,6 +5883,9 @@ pub fn plan_alter_connection(
         Connection::Postgres(_) => CreateConnectionType::Postgres,
         Connection::Ssh(_) => CreateConnectionType::Ssh,
         Connection::MySql(_) => CreateConnectionType::MySql,
+        Connection::Http(_) => CreateConnectionType::Http,
+        Connection::SqlServer(_) => CreateConnectionType::SqlServer,
+        Connection::MongoDb(_) => CreateConnectionType::MongoDb,
     };
 
     // Collect all options irrespective of action taken on them.
@@ -5615,8 +6284,10 @@ pub fn plan_comment(
             }
         }
         CommentObjectType::Type { ty } => match ty {
-            ResolvedDataType::AnonymousList(_) | ResolvedDataType::AnonymousMap { .. } => {
-                sql_bail!("cannot comment on anonymous list or map type");
+            ResolvedDataType::AnonymousList(_)
+            | ResolvedDataType::AnonymousMap { .. }
+            | ResolvedDataType::AnonymousEnum(_) => {
+                sql_bail!("cannot comment on anonymous list, map or enum type");
             }
             ResolvedDataType::Named { id, modifiers, .. } => {
                 if !modifiers.is_empty() {
@@ -5746,6 +6417,7 @@ pub(crate) fn resolve_item_or_type<'a>(
     let name = normalize::unresolved_item_name(name)?;
     let catalog_item = match object_type {
         ObjectType::Type => scx.catalog.resolve_type(&name),
+        ObjectType::Func => scx.catalog.resolve_function(&name),
         _ => scx.catalog.resolve_item(&name),
     };
 
@@ -24,9 +24,10 @@ use mz_sql_parser::ast::{
 use mz_storage_types::connections::aws::{AwsAssumeRole, AwsAuth, AwsConnection, AwsCredentials};
 use mz_storage_types::connections::inline::ReferencedConnection;
 use mz_storage_types::connections::{
-    AwsPrivatelink, AwsPrivatelinkConnection, CsrConnection, CsrConnectionHttpAuth,
-    KafkaConnection, KafkaSaslConfig, KafkaTlsConfig, MySqlConnection, MySqlSslMode,
-    PostgresConnection, SshConnection, SshTunnel, StringOrSecret, TlsIdentity, Tunnel,
+    AwsPrivatelink, AwsPrivatelinkConnection, CsrConnection, CsrConnectionHttpAuth, HttpConnection,
+    KafkaConnection, KafkaSaslConfig, KafkaTlsConfig, MongoDbConnection, MySqlConnection,
+    MySqlSslMode, PostgresConnection, SqlServerConnection, SshConnection, SshTunnel,
+    StringOrSecret, TlsIdentity, Tunnel,
 };
 
 use crate::names::Aug;
@@ -46,6 +47,7 @@ generate_extracted_config!(
     (Brokers, Vec<KafkaBroker<Aug>>),
     (Database, String),
     (Endpoint, String),
+    (HmacSecret, with_options::Secret),
     (Host, String),
     (Password, with_options::Secret),
     (Port, u16),
@@ -147,6 +149,25 @@ pub(super) fn validate_options_per_connection_type(
             SslMode,
             User,
         ],
+        CreateConnectionType::Http => &[HmacSecret, Url],
+        CreateConnectionType::SqlServer => &[
+            AwsPrivatelink,
+            Database,
+            Host,
+            Password,
+            Port,
+            SshTunnel,
+            User,
+        ],
+        CreateConnectionType::MongoDb => &[
+            AwsPrivatelink,
+            Database,
+            Host,
+            Password,
+            Port,
+            SshTunnel,
+            User,
+        ],
     };
 
     for o in permitted_options {
@@ -440,6 +461,80 @@ impl ConnectionOptionExtracted {
                         .ok_or_else(|| sql_err!("USER option is required"))?,
                 })
             }
+            CreateConnectionType::Http => {
+                scx.require_feature_flag(&crate::session::vars::ENABLE_HTTP_SINK)?;
+
+                let url: reqwest::Url = match self.url {
+                    Some(url) => url
+                        .parse()
+                        .map_err(|e| sql_err!("parsing HTTP connection url: {e}"))?,
+                    None => sql_bail!("invalid CONNECTION: must specify URL"),
+                };
+                if !matches!(url.scheme(), "http" | "https") {
+                    sql_bail!("invalid CONNECTION: URL scheme must be http or https");
+                }
+                let _ = url
+                    .host_str()
+                    .ok_or_else(|| sql_err!("invalid CONNECTION: URL must specify domain name"))?;
+
+                Connection::Http(HttpConnection {
+                    url,
+                    hmac_secret: self.hmac_secret.map(|secret| secret.into()),
+                })
+            }
+            CreateConnectionType::SqlServer => {
+                scx.require_feature_flag(&crate::session::vars::ENABLE_SQL_SERVER_SOURCE)?;
+
+                if let Some(privatelink) = self.aws_privatelink.as_ref() {
+                    if privatelink.port.is_some() {
+                        sql_bail!("invalid CONNECTION: PORT in AWS PRIVATELINK is only supported for kafka")
+                    }
+                }
+                let tunnel = scx.build_tunnel_definition(self.ssh_tunnel, self.aws_privatelink)?;
+
+                Connection::SqlServer(SqlServerConnection {
+                    host: self
+                        .host
+                        .ok_or_else(|| sql_err!("HOST option is required"))?,
+                    port: self.port.unwrap_or(1433_u16),
+                    database: self
+                        .database
+                        .ok_or_else(|| sql_err!("DATABASE option is required"))?,
+                    user: self
+                        .user
+                        .ok_or_else(|| sql_err!("USER option is required"))?,
+                    password: self.password.map(|password| password.into()),
+                    tunnel,
+                })
+            }
+            CreateConnectionType::MongoDb => {
+                scx.require_feature_flag(&crate::session::vars::ENABLE_MONGODB_SOURCE)?;
+
+                if let Some(privatelink) = self.aws_privatelink.as_ref() {
+                    if privatelink.port.is_some() {
+                        sql_bail!("invalid CONNECTION: PORT in AWS PRIVATELINK is only supported for kafka")
+                    }
+                }
+                let tunnel = scx.build_tunnel_definition(self.ssh_tunnel, self.aws_privatelink)?;
+
+                Connection::MongoDb(MongoDbConnection {
+                    host: self
+                        .host
+                        .ok_or_else(|| sql_err!("HOST option is required"))?,
+                    port: self.port.unwrap_or(27017_u16),
+                    database: self
+                        .database
+                        .ok_or_else(|| sql_err!("DATABASE option is required"))?,
+                    user: self
+                        .user
+                        .ok_or_else(|| sql_err!("USER option is required"))?,
+                    password: self
+                        .password
+                        .ok_or_else(|| sql_err!("PASSWORD option is required"))?
+                        .into(),
+                    tunnel,
+                })
+            }
         };
 
         Ok(connection)
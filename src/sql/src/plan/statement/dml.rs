@@ -27,12 +27,15 @@ use mz_repr::explain::{ExplainConfig, ExplainFormat};
 use mz_repr::optimize::OptimizerFeatureOverrides;
 use mz_repr::{Datum, GlobalId, RelationDesc, ScalarType};
 use mz_sql_parser::ast::{
-    CteBlock, ExplainPlanOption, ExplainPlanOptionName, ExplainPushdownStatement,
-    ExplainSinkSchemaFor, ExplainSinkSchemaStatement, ExplainTimestampStatement, Expr,
-    IfExistsBehavior, OrderByExpr, SetExpr, SubscribeOutput, UnresolvedItemName,
+    CteBlock, ExplainAnalyzeProperty, ExplainAnalyzeStatement, ExplainPlanOption,
+    ExplainPlanOptionName, ExplainPushdownStatement, ExplainSinkSchemaFor,
+    ExplainSinkSchemaStatement, ExplainTimestampStatement, Expr, IfExistsBehavior, OrderByExpr,
+    SetExpr, SubscribeOutput, UnresolvedItemName,
 };
 use mz_sql_parser::ident;
-use mz_storage_types::sinks::{KafkaSinkConnection, KafkaSinkFormat, StorageSinkConnection};
+use mz_storage_types::sinks::{
+    KafkaSinkConnection, KafkaSinkFormat, S3SinkFormat, StorageSinkConnection,
+};
 
 use crate::ast::display::AstDisplay;
 use crate::ast::{
@@ -46,11 +49,12 @@ use crate::names::{Aug, ResolvedItemName};
 use crate::normalize;
 use crate::plan::query::{plan_expr, plan_up_to, ExprContext, QueryLifetime};
 use crate::plan::scope::Scope;
+use crate::plan::statement::show::ShowSelect;
 use crate::plan::statement::{ddl, StatementContext, StatementDesc};
 use crate::plan::with_options::{self, TryFromValue};
 use crate::plan::{
     self, side_effecting_func, transform_ast, CopyToPlan, CreateSinkPlan, ExplainPushdownPlan,
-    ExplainSinkSchemaPlan, ExplainTimestampPlan,
+    ExplainSinkSchemaPlan, ExplainTimestampPlan, SinkSchema,
 };
 use crate::plan::{
     query, CopyFormat, CopyFromPlan, ExplainPlanPlan, InsertPlan, MutationKind, Params, Plan,
@@ -70,10 +74,12 @@ pub fn describe_insert(
         table_name,
         columns,
         source,
+        on_conflict,
         returning,
     }: InsertStatement<Aug>,
 ) -> Result<StatementDesc, PlanError> {
-    let (_, _, returning) = query::plan_insert_query(scx, table_name, columns, source, returning)?;
+    let (_, _, returning, _) =
+        query::plan_insert_query(scx, table_name, columns, source, on_conflict, returning)?;
     let desc = if returning.expr.is_empty() {
         None
     } else {
@@ -88,12 +94,13 @@ pub fn plan_insert(
         table_name,
         columns,
         source,
+        on_conflict,
         returning,
     }: InsertStatement<Aug>,
     params: &Params,
 ) -> Result<Plan, PlanError> {
-    let (id, mut expr, returning) =
-        query::plan_insert_query(scx, table_name, columns, source, returning)?;
+    let (id, mut expr, returning, upsert) =
+        query::plan_insert_query(scx, table_name, columns, source, on_conflict, returning)?;
     expr.bind_parameters(params)?;
     let returning = returning
         .expr
@@ -101,6 +108,28 @@ pub fn plan_insert(
         .map(|expr| expr.lower_uncorrelated())
         .collect::<Result<Vec<_>, _>>()?;
 
+    if upsert {
+        if expr.contains_temporal()? {
+            bail_unsupported!("calls to mz_now in write statements");
+        }
+        // The rows to write carry a trailing column that says whether they
+        // are inserted or retracted.
+        let finishing = RowSetFinishing {
+            order_by: vec![],
+            limit: None,
+            offset: 0,
+            project: (0..expr.arity()).collect(),
+        };
+        return Ok(Plan::ReadThenWrite(ReadThenWritePlan {
+            id,
+            selection: expr,
+            finishing,
+            assignments: BTreeMap::new(),
+            kind: MutationKind::Upsert,
+            returning,
+        }));
+    }
+
     Ok(Plan::Insert(InsertPlan {
         id,
         values: expr,
@@ -310,6 +339,13 @@ pub fn describe_explain_pushdown(
     )
 }
 
+pub fn describe_explain_analyze(
+    scx: &StatementContext,
+    statement: ExplainAnalyzeStatement<Aug>,
+) -> Result<StatementDesc, PlanError> {
+    explain_analyze(scx, statement)?.describe()
+}
+
 pub fn describe_explain_timestamp(
     scx: &StatementContext,
     ExplainTimestampStatement { select, .. }: ExplainTimestampStatement<Aug>,
@@ -588,15 +624,28 @@ pub fn plan_explain_schema(
 
     match ddl::plan_create_sink(scx, statement)? {
         Plan::CreateSink(CreateSinkPlan { sink, .. }) => match sink.connection {
-            StorageSinkConnection::Kafka(KafkaSinkConnection {
-                format:
+            StorageSinkConnection::Kafka(KafkaSinkConnection { format, .. }) => {
+                let (key_schema, value_schema) = match format {
                     KafkaSinkFormat::Avro {
                         key_schema,
                         value_schema,
                         ..
-                    },
-                ..
-            }) => {
+                    } => (
+                        key_schema.map(SinkSchema::Avro),
+                        SinkSchema::Avro(value_schema),
+                    ),
+                    KafkaSinkFormat::Protobuf {
+                        key_schema,
+                        value_schema,
+                        ..
+                    } => (
+                        key_schema.map(SinkSchema::Protobuf),
+                        SinkSchema::Protobuf(value_schema),
+                    ),
+                    KafkaSinkFormat::Json => bail_unsupported!(
+                        "EXPLAIN SCHEMA is only available for Kafka sinks with Avro or Protobuf schemas"
+                    ),
+                };
                 let schema = match schema_for {
                     ExplainSinkSchemaFor::Key => {
                         key_schema.ok_or_else(|| sql_err!("CREATE SINK does not have a key"))?
@@ -606,11 +655,11 @@ pub fn plan_explain_schema(
 
                 Ok(Plan::ExplainSinkSchema(ExplainSinkSchemaPlan {
                     sink_from: sink.from,
-                    json_schema: schema,
+                    schema,
                 }))
             }
             _ => bail_unsupported!(
-                "EXPLAIN SCHEMA is only available for Kafka sinks with Avro schemas"
+                "EXPLAIN SCHEMA is only available for Kafka sinks with Avro or Protobuf schemas"
             ),
         },
         _ => unreachable!("plan_create_sink returns a CreateSinkPlan"),
@@ -627,6 +676,166 @@ pub fn plan_explain_pushdown(
     Ok(Plan::ExplainPushdown(ExplainPushdownPlan { explainee }))
 }
 
+pub fn plan_explain_analyze(
+    scx: &StatementContext,
+    statement: ExplainAnalyzeStatement<Aug>,
+) -> Result<Plan, PlanError> {
+    scx.require_feature_flag(&vars::ENABLE_EXPLAIN_ANALYZE)?;
+    explain_analyze(scx, statement)?.plan()
+}
+
+/// Rewrites an `EXPLAIN ANALYZE` statement into a query that attributes the arrangement sizes and
+/// the elapsed time of dataflow operators to the LIR nodes that rendered them.
+///
+/// The LIR mapping is a per-replica introspection relation, so the query must run on the cluster
+/// maintaining the explained object.
+fn explain_analyze<'a>(
+    scx: &'a StatementContext,
+    ExplainAnalyzeStatement {
+        properties,
+        format,
+        explainee,
+    }: ExplainAnalyzeStatement<Aug>,
+) -> Result<ShowSelect<'a>, PlanError> {
+    let item = match &explainee {
+        Explainee::Index(name) => {
+            let item = scx.get_item_by_resolved_name(name)?;
+            let item_type = item.item_type();
+            if item_type != CatalogItemType::Index {
+                sql_bail!("Expected {name} to be an index, not a {item_type}");
+            }
+            item
+        }
+        Explainee::MaterializedView(name) => {
+            let item = scx.get_item_by_resolved_name(name)?;
+            let item_type = item.item_type();
+            if item_type != CatalogItemType::MaterializedView {
+                sql_bail!("Expected {name} to be a materialized view, not a {item_type}");
+            }
+            item
+        }
+        _ => sql_bail!("EXPLAIN ANALYZE is only supported for indexes and materialized views"),
+    };
+
+    let cluster_id = item
+        .cluster_id()
+        .expect("indexes and materialized views are installed on clusters");
+    let cluster = scx.catalog.get_cluster(cluster_id);
+    if cluster.name() != scx.catalog.active_cluster() {
+        let name = scx.catalog.resolve_full_name(item.name()).to_string();
+        return Err(PlanError::ExplainAnalyzeOnOtherCluster {
+            name,
+            cluster: cluster.name().to_string(),
+        });
+    }
+
+    let memory = properties.is_empty() || properties.contains(&ExplainAnalyzeProperty::Memory);
+    let cpu = properties.is_empty() || properties.contains(&ExplainAnalyzeProperty::Cpu);
+
+    let mut ctes = vec![format!(
+        "lir AS (
+            SELECT lir_id, parent_lir_id, nesting, operator, operator_id_start, operator_id_end
+            FROM mz_internal.mz_lir_mapping
+            WHERE export_id = '{}'
+        )",
+        item.id()
+    )];
+    let mut joins = String::new();
+    let mut columns = vec![(
+        "operator",
+        "repeat(' ', 2 * lir.nesting::int4) || lir.operator",
+    )];
+    let mut fields = vec![
+        ("lir_id", "lir.lir_id::int8"),
+        ("parent_lir_id", "lir.parent_lir_id::int8"),
+        ("nesting", "lir.nesting::int4"),
+        ("operator", "lir.operator"),
+    ];
+
+    if memory {
+        ctes.push(
+            "memory AS (
+                SELECT
+                    lir.lir_id,
+                    sum(a.records) AS records,
+                    sum(a.batches) AS batches,
+                    sum(a.size) AS size
+                FROM lir
+                JOIN mz_internal.mz_arrangement_sizes a
+                    ON a.operator_id >= lir.operator_id_start
+                    AND a.operator_id < lir.operator_id_end
+                GROUP BY lir.lir_id
+            )"
+            .into(),
+        );
+        joins.push_str(" LEFT JOIN memory ON lir.lir_id = memory.lir_id");
+        columns.extend([
+            ("records", "memory.records"),
+            ("batches", "memory.batches"),
+            ("size", "memory.size"),
+        ]);
+        fields.extend([
+            ("records", "memory.records"),
+            ("batches", "memory.batches"),
+            ("size", "memory.size"),
+        ]);
+    }
+
+    if cpu {
+        // Only leaf operators are considered, as the elapsed time of a scope includes the elapsed
+        // time of the operators it contains.
+        ctes.push(
+            "cpu AS (
+                SELECT lir.lir_id, sum(e.elapsed_ns) AS elapsed_ns
+                FROM lir
+                JOIN mz_internal.mz_scheduling_elapsed e
+                    ON e.id >= lir.operator_id_start
+                    AND e.id < lir.operator_id_end
+                WHERE NOT EXISTS (
+                    SELECT 1 FROM mz_internal.mz_dataflow_operator_parents p
+                    WHERE p.parent_id = e.id
+                )
+                GROUP BY lir.lir_id
+            )"
+            .into(),
+        );
+        joins.push_str(" LEFT JOIN cpu ON lir.lir_id = cpu.lir_id");
+        columns.push((
+            "elapsed",
+            "INTERVAL '1 microsecond' * (cpu.elapsed_ns::float8 / 1000)",
+        ));
+        fields.push(("elapsed_ns", "cpu.elapsed_ns"));
+    }
+
+    let ctes = ctes.join(", ");
+    match format {
+        mz_sql_parser::ast::ExplainFormat::Text => {
+            let projection: Vec<_> = columns.iter().map(|(name, _)| *name).collect();
+            let columns = columns
+                .iter()
+                .map(|(name, expr)| format!("{expr} AS {name}"))
+                .join(", ");
+            let query = format!("WITH {ctes} SELECT {columns}, lir.lir_id FROM lir{joins}");
+            ShowSelect::new(scx, query, None, Some("lir_id DESC"), Some(&projection))
+        }
+        mz_sql_parser::ast::ExplainFormat::Json => {
+            let fields = fields
+                .iter()
+                .map(|(name, expr)| format!("'{name}', {expr}"))
+                .join(", ");
+            let query = format!(
+                "WITH {ctes}
+                SELECT jsonb_agg(jsonb_build_object({fields}) ORDER BY lir.lir_id DESC) AS analysis
+                FROM lir{joins}"
+            );
+            ShowSelect::new(scx, query, None, None, None)
+        }
+        mz_sql_parser::ast::ExplainFormat::Dot => {
+            sql_bail!("EXPLAIN ANALYZE does not support the DOT format")
+        }
+    }
+}
+
 pub fn plan_explain_timestamp(
     scx: &StatementContext,
     ExplainTimestampStatement { format, select }: ExplainTimestampStatement<Aug>,
@@ -964,15 +1173,19 @@ fn plan_copy_to(
         _ => sql_bail!("only AWS CONNECTION is supported for COPY ... TO <expr>"),
     }
 
-    if format != CopyFormat::Csv {
-        sql_bail!("only CSV format is supported for COPY ... TO <expr>");
-    }
-
-    // TODO(mouli): Get these from sql options
-    let format_params = CopyFormatParams::Csv(
-        CopyCsvFormatParams::try_new(None, None, None, None, None)
-            .map_err(|e| sql_err!("{}", e))?,
-    );
+    let format = match format {
+        CopyFormat::Csv => {
+            // TODO(mouli): Get these from sql options
+            S3SinkFormat::PgCopy(CopyFormatParams::Csv(
+                CopyCsvFormatParams::try_new(None, None, None, None, None)
+                    .map_err(|e| sql_err!("{}", e))?,
+            ))
+        }
+        CopyFormat::Parquet => S3SinkFormat::Parquet,
+        CopyFormat::Text | CopyFormat::Binary => {
+            sql_bail!("only CSV and PARQUET formats are supported for COPY ... TO <expr>")
+        }
+    };
 
     // Converting the to expr to a HirScalarExpr
     let mut to_expr = to.clone();
@@ -1001,7 +1214,7 @@ fn plan_copy_to(
         to,
         connection: connection.to_owned(),
         connection_id: conn_id,
-        format_params,
+        format,
         max_file_size: options.max_file_size.as_bytes(),
     }))
 }
@@ -1060,6 +1273,7 @@ fn plan_copy_from(
             )
         }
         CopyFormat::Binary => bail_unsupported!("FORMAT BINARY"),
+        CopyFormat::Parquet => bail_unsupported!("FORMAT PARQUET"),
     };
 
     let (id, _, columns) = query::plan_copy_from(scx, table_name, columns)?;
@@ -1096,6 +1310,7 @@ pub fn plan_copy(
         "text" => CopyFormat::Text,
         "csv" => CopyFormat::Csv,
         "binary" => CopyFormat::Binary,
+        "parquet" => CopyFormat::Parquet,
         _ => sql_bail!("unknown FORMAT: {}", options.format),
     };
     if let CopyDirection::To = direction {
@@ -1107,6 +1322,9 @@ pub fn plan_copy(
         }
     }
     match (&direction, &target) {
+        (CopyDirection::To, CopyTarget::Stdout) if format == CopyFormat::Parquet => {
+            bail_unsupported!("COPY TO STDOUT with FORMAT PARQUET")
+        }
         (CopyDirection::To, CopyTarget::Stdout) => match relation {
             CopyRelation::Table { .. } => sql_bail!("table with COPY TO unsupported"),
             CopyRelation::Select(stmt) => {
@@ -59,6 +59,9 @@ where
 //   * Rewrites the suite of standard deviation and variance functions in a
 //     manner similar to `avg`.
 //
+//   * Rewrites the two-argument statistical aggregates, like `corr` and
+//     `regr_slope`, in terms of the same sums as the variance functions.
+//
 // TODO(sploiselle): rewrite these in terms of func::sql_op!
 struct FuncRewriter<'a> {
     scx: &'a StatementContext<'a>,
@@ -129,6 +132,7 @@ impl<'a> FuncRewriter<'a> {
             args: FunctionArgs::Args {
                 args: vec![expr],
                 order_by,
+                within_group: false,
             },
             filter,
             over,
@@ -266,6 +270,11 @@ impl<'a> FuncRewriter<'a> {
                 count
             },
         );
+        Self::plan_nonnegative(result)
+    }
+
+    // Clamps a sum of squares computed by the "textbook" algorithm to zero.
+    fn plan_nonnegative(result: Expr<Aug>) -> Expr<Aug> {
         // Result is _basically_ what we want, except
         // that due to numerical inaccuracy, it might be a negative
         // number very close to zero when it should mathematically be zero.
@@ -311,6 +320,125 @@ impl<'a> FuncRewriter<'a> {
             )
     }
 
+    fn plan_regr(
+        &mut self,
+        name: &str,
+        y: Expr<Aug>,
+        x: Expr<Aug>,
+        filter: Option<Box<Expr<Aug>>>,
+        distinct: bool,
+        over: Option<WindowSpec<Aug>>,
+    ) -> Expr<Aug> {
+        // The two-argument statistical aggregates only consider rows where
+        // both `y` and `x` are non-NULL. They are computed from the same
+        // "textbook" sums as `plan_variance`, so that each of them is
+        // maintained incrementally as a handful of `sum`s and a `count`:
+        //
+        //     sxx = sum(x²) - sum(x)² / count
+        //     syy = sum(y²) - sum(y)² / count
+        //     sxy = sum(x * y) - sum(x) * sum(y) / count
+        //
+        // Removing duplicate pairs cannot be expressed with these sums, so
+        // `DISTINCT` is not supported.
+        if distinct && self.status.is_ok() {
+            self.status = Err(PlanError::Unsupported {
+                feature: format!("{}(DISTINCT ...)", name),
+                issue_no: None,
+            });
+        }
+        let promote = |expr: Expr<Aug>| {
+            expr.call_unary(
+                self.scx
+                    .dangerous_resolve_name(vec![MZ_UNSAFE_SCHEMA, "mz_float8_promotion"]),
+            )
+        };
+        let (y, x) = (promote(y), promote(x));
+        let mask = |expr: Expr<Aug>, other: Expr<Aug>| Expr::Case {
+            operand: None,
+            conditions: vec![Expr::IsExpr {
+                expr: Box::new(other),
+                construct: IsExprConstruct::Null,
+                negated: false,
+            }],
+            results: vec![Expr::null()],
+            else_result: Some(Box::new(expr)),
+        };
+        let (y, x) = (mask(y.clone(), x.clone()), mask(x, y));
+        let mut sum = |expr: Expr<Aug>| {
+            self.plan_agg(
+                self.scx
+                    .dangerous_resolve_name(vec![PG_CATALOG_SCHEMA, "sum"]),
+                expr,
+                vec![],
+                filter.clone(),
+                false,
+                over.clone(),
+            )
+        };
+        let sum_x = sum(x.clone());
+        let sum_y = sum(y.clone());
+        let sum_xx = sum(x.clone().multiply(x.clone()));
+        let sum_yy = sum(y.clone().multiply(y.clone()));
+        let sum_xy = sum(x.clone().multiply(y));
+        let count = self.plan_agg(
+            self.scx
+                .dangerous_resolve_name(vec![PG_CATALOG_SCHEMA, "count"]),
+            x,
+            vec![],
+            filter,
+            false,
+            over,
+        );
+        let sxx = Self::plan_nonnegative(sum_xx.minus(Self::plan_divide(
+            sum_x.clone().multiply(sum_x.clone()),
+            count.clone(),
+        )));
+        let syy = Self::plan_nonnegative(sum_yy.minus(Self::plan_divide(
+            sum_y.clone().multiply(sum_y.clone()),
+            count.clone(),
+        )));
+        let sxy = sum_xy.minus(Self::plan_divide(
+            sum_x.clone().multiply(sum_y.clone()),
+            count.clone(),
+        ));
+        match name {
+            "regr_count" => count,
+            "regr_avgx" => Self::plan_divide(sum_x, count),
+            "regr_avgy" => Self::plan_divide(sum_y, count),
+            "regr_sxx" => sxx,
+            "regr_syy" => syy,
+            "regr_sxy" => sxy,
+            "covar_pop" => Self::plan_divide(sxy, count),
+            "covar_samp" => Self::plan_divide(sxy, count.minus(Expr::number("1"))),
+            "regr_slope" => Self::plan_divide(sxy, sxx),
+            "regr_intercept" => Self::plan_divide(
+                sum_y.minus(sum_x.multiply(Self::plan_divide(sxy, sxx))),
+                count,
+            ),
+            "corr" => Self::plan_divide(
+                sxy,
+                sxx.multiply(syy).call_unary(
+                    self.scx
+                        .dangerous_resolve_name(vec![PG_CATALOG_SCHEMA, "sqrt"]),
+                ),
+            ),
+            // If `y` is constant but `x` is not, the regression line fits
+            // perfectly.
+            "regr_r2" => Expr::Case {
+                operand: None,
+                conditions: vec![
+                    sxx.clone().equals(Expr::number("0")),
+                    syy.clone().equals(Expr::number("0")),
+                ],
+                results: vec![Expr::null(), Expr::number("1")],
+                else_result: Some(Box::new(
+                    sxy.clone().multiply(sxy).divide(sxx.multiply(syy)),
+                )),
+            },
+            _ => unreachable!("{} is not a statistical aggregate", name),
+        }
+    }
+
     fn plan_bool_and(
         &mut self,
         expr: Expr<Aug>,
@@ -375,7 +503,12 @@ impl<'a> FuncRewriter<'a> {
     fn rewrite_function(&mut self, func: &Function<Aug>) -> Option<(Ident, Expr<Aug>)> {
         if let Function {
             name,
-            args: FunctionArgs::Args { args, order_by: _ },
+            args:
+                FunctionArgs::Args {
+                    args,
+                    order_by: _,
+                    within_group: false,
+                },
             filter,
             distinct,
             over,
@@ -429,6 +562,11 @@ impl<'a> FuncRewriter<'a> {
                 let (lhs, rhs) = (args[0].clone(), args[1].clone());
                 match name.as_str() {
                     "mod" => lhs.modulo(rhs),
+                    "corr" | "covar_pop" | "covar_samp" | "regr_avgx" | "regr_avgy"
+                    | "regr_count" | "regr_intercept" | "regr_r2" | "regr_slope" | "regr_sxx"
+                    | "regr_sxy" | "regr_syy" => {
+                        self.plan_regr(&name, lhs, rhs, filter, distinct, over)
+                    }
                     "pow" => Expr::call(
                         self.scx
                             .dangerous_resolve_name(vec![PG_CATALOG_SCHEMA, "power"]),
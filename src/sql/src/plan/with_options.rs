@@ -15,7 +15,8 @@ use mz_repr::adt::interval::Interval;
 use mz_repr::bytes::ByteSize;
 use mz_repr::{strconv, GlobalId};
 use mz_sql_parser::ast::{
-    ConnectionDefaultAwsPrivatelink, Ident, KafkaBroker, RefreshOptionValue, ReplicaDefinition,
+    ConnectionDefaultAwsPrivatelink, Expr, Ident, KafkaBroker, RefreshOptionValue,
+    ReplicaDefinition,
 };
 use mz_storage_types::connections::StringOrSecret;
 use serde::{Deserialize, Serialize};
@@ -467,7 +468,8 @@ impl<V: TryFromValue<Value>, T: AstInfo + std::fmt::Debug> TryFromValue<WithOpti
             | WithOptionValue::ClusterReplicas(_)
             | WithOptionValue::ConnectionKafkaBroker(_)
             | WithOptionValue::ConnectionAwsPrivatelink(_)
-            | WithOptionValue::Refresh(_) => sql_bail!(
+            | WithOptionValue::Refresh(_)
+            | WithOptionValue::Expr(_) => sql_bail!(
                 "incompatible value types: cannot convert {} to {}",
                 match v {
                     // The first few are unreachable because they are handled at the top of the outer match.
@@ -483,6 +485,7 @@ impl<V: TryFromValue<Value>, T: AstInfo + std::fmt::Debug> TryFromValue<WithOpti
                     WithOptionValue::ConnectionKafkaBroker(_) => "connection kafka brokers",
                     WithOptionValue::ConnectionAwsPrivatelink(_) => "connection kafka brokers",
                     WithOptionValue::Refresh(_) => "refresh option values",
+                    WithOptionValue::Expr(_) => "expressions",
                 },
                 V::name()
             ),
@@ -570,6 +573,26 @@ impl ImpliedValue for RefreshOptionValue<Aug> {
     }
 }
 
+impl TryFromValue<WithOptionValue<Aug>> for Expr<Aug> {
+    fn try_from_value(v: WithOptionValue<Aug>) -> Result<Self, PlanError> {
+        if let WithOptionValue::Expr(e) = v {
+            Ok(e)
+        } else {
+            sql_bail!("cannot use value `{}` for an expression", v)
+        }
+    }
+
+    fn name() -> String {
+        "expression".to_string()
+    }
+}
+
+impl ImpliedValue for Expr<Aug> {
+    fn implied_value() -> Result<Self, PlanError> {
+        sql_bail!("must provide an expression")
+    }
+}
+
 impl TryFromValue<WithOptionValue<Aug>> for ConnectionDefaultAwsPrivatelink<Aug> {
     fn try_from_value(v: WithOptionValue<Aug>) -> Result<Self, PlanError> {
         if let WithOptionValue::ConnectionAwsPrivatelink(r) = v {
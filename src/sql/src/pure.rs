@@ -32,20 +32,24 @@ use mz_sql_parser::ast::visit_mut::{visit_expr_mut, VisitMut};
 use mz_sql_parser::ast::{
     AlterSourceAction, AlterSourceAddSubsourceOptionName, AlterSourceStatement, AvroDocOn,
     CreateMaterializedViewStatement, CreateSinkConnection, CreateSinkStatement,
-    CreateSubsourceOption, CreateSubsourceOptionName, CsrConfigOption, CsrConfigOptionName,
-    CsrConnection, CsrSeedAvro, CsrSeedProtobuf, CsrSeedProtobufSchema, DeferredItemName,
-    DocOnIdentifier, DocOnSchema, Expr, Function, FunctionArgs, Ident, KafkaSourceConfigOption,
+    CreateSubsourceOption, CreateSubsourceOptionName, CreateTypeAs, CreateTypeStatement,
+    CsrConfigOption, CsrConfigOptionName, CsrConnection, CsrConnectionJson, CsrSeedAvro,
+    CsrSeedJson, CsrSeedProtobuf, CsrSeedProtobufSchema, DeferredItemName, DocOnIdentifier,
+    DocOnSchema, Expr, Function, FunctionArgs, Ident, KafkaSourceConfigOption,
     KafkaSourceConfigOptionName, MaterializedViewOption, MaterializedViewOptionName,
-    MySqlConfigOption, MySqlConfigOptionName, PgConfigOption, PgConfigOptionName, RawItemName,
-    ReaderSchemaSelectionStrategy, RefreshAtOptionValue, RefreshEveryOptionValue,
-    RefreshOptionValue, SourceEnvelope, Statement, UnresolvedItemName,
+    MongoDbConfigOption, MongoDbConfigOptionName, MySqlConfigOption, MySqlConfigOptionName,
+    PgConfigOption, PgConfigOptionName, RawItemName, ReaderSchemaSelectionStrategy,
+    RefreshAtOptionValue, RefreshEveryOptionValue, RefreshOptionValue, SourceEnvelope,
+    SqlServerConfigOption, SqlServerConfigOptionName, Statement, UnresolvedItemName,
 };
 use mz_storage_types::configuration::StorageConfiguration;
 use mz_storage_types::connections::inline::IntoInlineConnection;
 use mz_storage_types::connections::Connection;
 use mz_storage_types::errors::ContextCreationError;
+use mz_storage_types::sources::mongodb::MongoDbSourceDetails;
 use mz_storage_types::sources::mysql::MySqlSourceDetails;
 use mz_storage_types::sources::postgres::PostgresSourcePublicationDetails;
+use mz_storage_types::sources::sql_server::SqlServerSourceDetails;
 use mz_storage_types::sources::{GenericSourceConnection, SourceConnection};
 use prost::Message;
 use protobuf_native::compiler::{SourceTreeDescriptorDatabase, VirtualSourceTree};
@@ -58,7 +62,9 @@ use crate::ast::{
     CreateSourceSubsource, CreateSubsourceStatement, CsrConnectionAvro, CsrConnectionProtobuf,
     Format, ProtobufSchema, ReferencedSubsources, Value, WithOptionValue,
 };
-use crate::catalog::{CatalogItemType, SessionCatalog, SubsourceCatalog};
+use crate::catalog::{
+    CatalogItemType, CatalogType, CatalogTypeDetails, SessionCatalog, SubsourceCatalog,
+};
 use crate::kafka_util::{KafkaSinkConfigOptionExtracted, KafkaSourceConfigOptionExtracted};
 use crate::names::{
     Aug, FullItemName, PartialItemName, ResolvedColumnName, ResolvedDataType, ResolvedIds,
@@ -71,12 +77,15 @@ use crate::{kafka_util, normalize};
 
 use self::error::{
     CsrPurificationError, KafkaSinkPurificationError, KafkaSourcePurificationError,
-    LoadGeneratorSourcePurificationError, MySqlSourcePurificationError, PgSourcePurificationError,
+    LoadGeneratorSourcePurificationError, MongoDbSourcePurificationError,
+    MySqlSourcePurificationError, PgSourcePurificationError, SqlServerSourcePurificationError,
 };
 
 pub(crate) mod error;
+mod mongodb;
 mod mysql;
 mod postgres;
+mod sql_server;
 
 pub(crate) struct RequestedSubsource<'a, T> {
     upstream_name: UnresolvedItemName,
@@ -154,6 +163,71 @@ fn subsource_name_gen(
     Ok(UnresolvedItemName::from(partial))
 }
 
+/// Returns the catalog enum type named `name` that represents an upstream enum with `labels`.
+///
+/// An existing enum type of that name is reused if it has the same labels. Otherwise, a `CREATE
+/// TYPE` statement for it is added to `types` under a transient ID, so that it is created along
+/// with the subsources that use it.
+fn upstream_enum_type<F>(
+    scx: &StatementContext,
+    name: UnresolvedItemName,
+    labels: &[String],
+    get_transient_id: &mut F,
+    types: &mut Vec<(GlobalId, CreateTypeStatement<Aug>)>,
+) -> Result<ResolvedDataType, PlanError>
+where
+    F: FnMut() -> u64,
+{
+    let partial = normalize::unresolved_item_name(name)?;
+    let qualified = scx.allocate_qualified_name(partial.clone())?;
+    let full_name = scx.allocate_full_name(partial)?;
+    let conflict = || PlanError::UpstreamEnumTypeConflict {
+        name: full_name.to_string(),
+    };
+
+    let type_name = UnresolvedItemName::from(full_name.clone());
+    let pending = types.iter().find(|(_, stmt)| stmt.name == type_name);
+    let id = match pending {
+        Some((id, stmt)) => match &stmt.as_type {
+            CreateTypeAs::Enum { labels: pending } if pending == labels => *id,
+            _ => return Err(conflict()),
+        },
+        None => match scx
+            .catalog
+            .resolve_item_or_type(&PartialItemName::from(full_name.clone()))
+        {
+            Ok(item) => match item.type_details() {
+                Some(CatalogTypeDetails {
+                    typ: CatalogType::Enum { labels: existing },
+                    ..
+                }) if existing == labels => item.id(),
+                _ => return Err(conflict()),
+            },
+            Err(_) => {
+                let id = GlobalId::Transient(get_transient_id());
+                types.push((
+                    id,
+                    CreateTypeStatement {
+                        name: type_name,
+                        as_type: CreateTypeAs::Enum {
+                            labels: labels.to_vec(),
+                        },
+                    },
+                ));
+                id
+            }
+        },
+    };
+
+    Ok(ResolvedDataType::Named {
+        id,
+        qualifiers: qualified.qualifiers,
+        full_name,
+        modifiers: vec![],
+        print_id: true,
+    })
+}
+
 /// Validates the requested subsources do not have name conflicts with each other
 /// and that the same upstream table is not referenced multiple times.
 fn validate_subsource_names<T>(
@@ -221,6 +295,9 @@ fn validate_subsource_names<T>(
 /// See the section on [purification](crate#purification) in the crate
 /// documentation for details.
 ///
+/// Along with the purified statement, returns the statements of the types and subsources that
+/// must be created with it, identified by transient IDs.
+///
 /// Note that this doesn't handle CREATE MATERIALIZED VIEW, which is
 /// handled by [purify_create_materialized_view_options] instead.
 /// This could be made more consistent by a refactoring discussed here:
@@ -232,6 +309,7 @@ pub async fn purify_statement(
     storage_configuration: &StorageConfiguration,
 ) -> Result<
     (
+        Vec<(GlobalId, CreateTypeStatement<Aug>)>,
         Vec<(GlobalId, CreateSubsourceStatement<Aug>)>,
         Statement<Aug>,
     ),
@@ -246,7 +324,7 @@ pub async fn purify_statement(
         }
         Statement::CreateSink(stmt) => {
             let r = purify_create_sink(catalog, stmt, storage_configuration).await?;
-            Ok((vec![], r))
+            Ok((vec![], vec![], r))
         }
         o => unreachable!("{:?} does not need to be purified", o),
     }
@@ -373,6 +451,7 @@ async fn purify_create_sink(
             connection,
             options,
             key: _,
+            headers: _,
         } => {
             let scx = StatementContext::new(None, &catalog);
             let connection = {
@@ -426,6 +505,63 @@ async fn purify_create_sink(
                 Err(KafkaSinkPurificationError::ZeroBrokers)?;
             }
         }
+        // HTTP endpoints are only expected to accept the requests produced by
+        // the sink itself, so there is nothing to check ahead of time.
+        CreateSinkConnection::Http { .. } => {}
+        CreateSinkConnection::Postgres {
+            connection,
+            options,
+            key: _,
+        } => {
+            let scx = StatementContext::new(None, &catalog);
+            let connection = {
+                let item = scx.get_item_by_resolved_name(connection)?;
+                // Get Postgres connection
+                match item.connection()? {
+                    Connection::Postgres(connection) => {
+                        connection.clone().into_inline_connection(scx.catalog)
+                    }
+                    _ => sql_bail!(
+                        "{} is not a postgres connection",
+                        scx.catalog.resolve_full_name(item.name())
+                    ),
+                }
+            };
+
+            let crate::plan::statement::PostgresSinkConfigOptionExtracted { schema, table, .. } =
+                options.clone().try_into()?;
+            let table = table.ok_or_else(|| sql_err!("TABLE option is required"))?;
+
+            // Verify that we can connect upstream and that the target table
+            // exists. The progress table is created by the sink itself.
+            let config = connection
+                .config(
+                    &*storage_configuration.connection_context.secrets_reader,
+                    storage_configuration,
+                )
+                .await?;
+            let client = config
+                .connect(
+                    "postgres sink purification",
+                    &storage_configuration.connection_context.ssh_tunnel_manager,
+                )
+                .await?;
+            let exists = client
+                .query_opt(
+                    "SELECT 1 FROM pg_catalog.pg_tables WHERE schemaname = $1 AND tablename = $2",
+                    &[&schema, &table],
+                )
+                .await
+                .map_err(mz_postgres_util::PostgresError::from)?
+                .is_some();
+            if !exists {
+                sql_bail!(
+                    "upstream table {}.{} does not exist",
+                    schema.quoted(),
+                    table.quoted()
+                );
+            }
+        }
     }
 
     if let Some(format) = format {
@@ -461,12 +597,15 @@ async fn purify_create_sink(
                     .map_err(|e| CsrPurificationError::ListSubjectsError(Arc::new(e)))?;
             }
             Format::Avro(AvroSchema::InlineSchema { .. })
+            | Format::AvroOcf { .. }
             | Format::Bytes
             | Format::Csv { .. }
             | Format::Json { .. }
+            | Format::JsonCsr { .. }
             | Format::Protobuf(ProtobufSchema::InlineSchema { .. })
             | Format::Regex(..)
-            | Format::Text => {}
+            | Format::Text
+            | Format::Parquet => {}
         }
     }
 
@@ -480,6 +619,7 @@ async fn purify_create_source(
     storage_configuration: &StorageConfiguration,
 ) -> Result<
     (
+        Vec<(GlobalId, CreateTypeStatement<Aug>)>,
         Vec<(GlobalId, CreateSubsourceStatement<Aug>)>,
         Statement<Aug>,
     ),
@@ -516,6 +656,7 @@ async fn purify_create_source(
     };
 
     let mut subsources = vec![];
+    let mut types = vec![];
 
     let progress_desc = match &connection {
         CreateSourceConnection::Kafka { .. } => {
@@ -527,6 +668,13 @@ async fn purify_create_source(
         CreateSourceConnection::MySql { .. } => {
             &mz_storage_types::sources::mysql::MYSQL_PROGRESS_DESC
         }
+        CreateSourceConnection::SqlServer { .. } => {
+            &mz_storage_types::sources::sql_server::SQL_SERVER_PROGRESS_DESC
+        }
+        CreateSourceConnection::MongoDb { .. } => {
+            &mz_storage_types::sources::mongodb::MONGODB_PROGRESS_DESC
+        }
+        CreateSourceConnection::S3 { .. } => &mz_storage_types::sources::s3::S3_PROGRESS_DESC,
         CreateSourceConnection::LoadGenerator { .. } => {
             &mz_storage_types::sources::load_generator::LOAD_GEN_PROGRESS_DESC
         }
@@ -842,16 +990,18 @@ async fn purify_create_source(
                 text_cols_option.value = Some(WithOptionValue::Sequence(seq));
             }
 
-            let (targeted_subsources, new_subsources) = postgres::generate_targeted_subsources(
-                &scx,
-                validated_requested_subsources,
-                text_cols_dict,
-                get_transient_subsource_id,
-                &publication_tables,
-            )?;
+            let (targeted_subsources, new_subsources, new_types) =
+                postgres::generate_targeted_subsources(
+                    &scx,
+                    validated_requested_subsources,
+                    text_cols_dict,
+                    get_transient_subsource_id,
+                    &publication_tables,
+                )?;
 
             *referenced_subsources = Some(ReferencedSubsources::SubsetTables(targeted_subsources));
             subsources.extend(new_subsources);
+            types.extend(new_types);
 
             // Record the active replication timeline_id to allow detection of a future upstream
             // point-in-time-recovery that will put the source into an error state.
@@ -1100,14 +1250,16 @@ async fn purify_create_source(
 
             // TODO(roshan): Implement privileges check for MySQL
 
-            let (targeted_subsources, new_subsources) = mysql::generate_targeted_subsources(
-                &scx,
-                validated_requested_subsources,
-                get_transient_subsource_id,
-            )?;
+            let (targeted_subsources, new_subsources, new_types) =
+                mysql::generate_targeted_subsources(
+                    &scx,
+                    validated_requested_subsources,
+                    get_transient_subsource_id,
+                )?;
 
             *referenced_subsources = Some(ReferencedSubsources::SubsetTables(targeted_subsources));
             subsources.extend(new_subsources);
+            types.extend(new_types);
 
             // Retrieve the current @gtid_executed value of the server to mark as the effective
             // initial snapshot point such that we can ensure consistency if the initial source
@@ -1129,6 +1281,343 @@ async fn purify_create_source(
                 )))),
             })
         }
+        CreateSourceConnection::SqlServer {
+            connection,
+            options,
+        } => {
+            let scx = StatementContext::new(None, &catalog);
+            let connection_item = scx.get_item_by_resolved_name(connection)?;
+            let connection = match connection_item.connection()? {
+                Connection::SqlServer(connection) => {
+                    connection.clone().into_inline_connection(&catalog)
+                }
+                _ => Err(SqlServerSourcePurificationError::NotSqlServerConnection(
+                    scx.catalog.resolve_full_name(connection_item.name()),
+                ))?,
+            };
+            let crate::plan::statement::ddl::SqlServerConfigOptionExtracted { details, seen: _ } =
+                options.clone().try_into()?;
+
+            if details.is_some() {
+                Err(SqlServerSourcePurificationError::UserSpecifiedDetails)?;
+            }
+
+            let config = connection
+                .config(
+                    &*storage_configuration.connection_context.secrets_reader,
+                    storage_configuration,
+                )
+                .await?;
+
+            let mut client = config
+                .connect(
+                    "sql server purification",
+                    &storage_configuration.connection_context.ssh_tunnel_manager,
+                )
+                .await?;
+
+            if !mz_sql_server_util::is_cdc_enabled(&mut client).await? {
+                Err(mz_sql_server_util::SqlServerError::CdcNotEnabled(
+                    connection.database.clone(),
+                ))?;
+            }
+
+            // SQL Server has databases and schemas, but a connection is bound to a single
+            // database so we only accept `schema_name.table_name` references here.
+            let table_schema_request = match referenced_subsources
+                .as_mut()
+                .ok_or(SqlServerSourcePurificationError::RequiresReferencedSubsources)?
+            {
+                ReferencedSubsources::All => mz_sql_server_util::SchemaRequest::All,
+                ReferencedSubsources::SubsetSchemas(schemas) => {
+                    mz_sql_server_util::SchemaRequest::Schemas(
+                        schemas.iter().map(|s| s.as_str()).collect::<Vec<_>>(),
+                    )
+                }
+                ReferencedSubsources::SubsetTables(tables) => {
+                    mz_sql_server_util::SchemaRequest::Tables(
+                        tables
+                            .iter()
+                            .map(|t| {
+                                let idents = &t.reference.0;
+                                if idents.len() != 2 {
+                                    Err(SqlServerSourcePurificationError::InvalidTableReference(
+                                        t.reference.to_ast_string(),
+                                    ))?;
+                                }
+                                Ok((idents[0].as_str(), idents[1].as_str()))
+                            })
+                            .collect::<Result<Vec<_>, SqlServerSourcePurificationError>>()?,
+                    )
+                }
+            };
+
+            // Retrieve schemas for all requested tables
+            let tables = mz_sql_server_util::schema_info(&mut client, &table_schema_request)
+                .await
+                .map_err(|err| match err {
+                    mz_sql_server_util::SqlServerError::UnsupportedDataTypes { columns } => {
+                        PlanError::from(SqlServerSourcePurificationError::UnrecognizedTypes {
+                            cols: columns
+                                .into_iter()
+                                .map(|c| (c.qualified_table_name, c.column_name, c.column_type))
+                                .collect(),
+                        })
+                    }
+                    _ => err.into(),
+                })?;
+            client.close().await?;
+
+            if tables.is_empty() {
+                Err(SqlServerSourcePurificationError::NoCdcTables)?;
+            }
+
+            let database = connection.database.as_str();
+            let sql_server_catalog = sql_server::derive_catalog_from_tables(database, &tables);
+
+            let mut validated_requested_subsources = vec![];
+            match referenced_subsources
+                .as_mut()
+                .ok_or(SqlServerSourcePurificationError::RequiresReferencedSubsources)?
+            {
+                ReferencedSubsources::All => {
+                    for table in &tables {
+                        let upstream_name = sql_server::sql_server_upstream_name(database, table)?;
+                        let subsource_name = subsource_name_gen(source_name, &table.name)?;
+                        validated_requested_subsources.push(RequestedSubsource {
+                            upstream_name,
+                            subsource_name,
+                            table,
+                        });
+                    }
+                }
+                ReferencedSubsources::SubsetSchemas(schemas) => {
+                    let available_schemas: BTreeSet<_> =
+                        tables.iter().map(|t| t.schema_name.as_str()).collect();
+                    let requested_schemas: BTreeSet<_> =
+                        schemas.iter().map(|s| s.as_str()).collect();
+                    let missing_schemas: Vec<_> = requested_schemas
+                        .difference(&available_schemas)
+                        .map(|s| s.to_string())
+                        .collect();
+                    if !missing_schemas.is_empty() {
+                        Err(SqlServerSourcePurificationError::NoTablesFoundForSchemas(
+                            missing_schemas,
+                        ))?;
+                    }
+
+                    for table in &tables {
+                        if !requested_schemas.contains(table.schema_name.as_str()) {
+                            continue;
+                        }
+
+                        let upstream_name = sql_server::sql_server_upstream_name(database, table)?;
+                        let subsource_name = subsource_name_gen(source_name, &table.name)?;
+                        validated_requested_subsources.push(RequestedSubsource {
+                            upstream_name,
+                            subsource_name,
+                            table,
+                        });
+                    }
+                }
+                ReferencedSubsources::SubsetTables(subsources) => {
+                    // The user manually selected a subset of upstream tables so we need to
+                    // validate that the names actually exist and are not ambiguous
+                    validated_requested_subsources.extend(subsource_gen(
+                        subsources,
+                        &sql_server_catalog,
+                        source_name,
+                    )?);
+                }
+            }
+
+            if validated_requested_subsources.is_empty() {
+                sql_bail!(
+                    "[internal error]: SQL Server source must ingest at least one table, but {} matched none",
+                    referenced_subsources.as_ref().unwrap().to_ast_string()
+                );
+            }
+
+            validate_subsource_names(&validated_requested_subsources)?;
+
+            let (targeted_subsources, new_subsources) = sql_server::generate_targeted_subsources(
+                &scx,
+                validated_requested_subsources,
+                get_transient_subsource_id,
+            )?;
+
+            *referenced_subsources = Some(ReferencedSubsources::SubsetTables(targeted_subsources));
+            subsources.extend(new_subsources);
+
+            // Remove any old detail references
+            options.retain(|SqlServerConfigOption { name, .. }| {
+                name != &SqlServerConfigOptionName::Details
+            });
+            let details = SqlServerSourceDetails { tables };
+            options.push(SqlServerConfigOption {
+                name: SqlServerConfigOptionName::Details,
+                value: Some(WithOptionValue::Value(Value::String(hex::encode(
+                    details.into_proto().encode_to_vec(),
+                )))),
+            })
+        }
+        CreateSourceConnection::MongoDb {
+            connection,
+            options,
+        } => {
+            let scx = StatementContext::new(None, &catalog);
+            let connection_item = scx.get_item_by_resolved_name(connection)?;
+            let connection = match connection_item.connection()? {
+                Connection::MongoDb(connection) => {
+                    connection.clone().into_inline_connection(&catalog)
+                }
+                _ => Err(MongoDbSourcePurificationError::NotMongoDbConnection(
+                    scx.catalog.resolve_full_name(connection_item.name()),
+                ))?,
+            };
+            let crate::plan::statement::ddl::MongoDbConfigOptionExtracted { details, seen: _ } =
+                options.clone().try_into()?;
+
+            if details.is_some() {
+                Err(MongoDbSourcePurificationError::UserSpecifiedDetails)?;
+            }
+
+            let config = connection
+                .config(
+                    &*storage_configuration.connection_context.secrets_reader,
+                    storage_configuration,
+                )
+                .await?;
+
+            let client = config
+                .connect(
+                    "mongodb purification",
+                    &storage_configuration.connection_context.ssh_tunnel_manager,
+                )
+                .await?;
+            let db = client.db();
+
+            // Ensure that the deployment supports change streams.
+            mz_mongodb_util::get_cluster_time(&db).await?;
+
+            // A connection is bound to a single database, so collections can
+            // only be referenced by their name, optionally qualified by the
+            // database and the fake `mongodb` database.
+            let database = connection.database.as_str();
+            let collection_request = match referenced_subsources.as_ref() {
+                None | Some(ReferencedSubsources::All) => mz_mongodb_util::CollectionRequest::All,
+                Some(ReferencedSubsources::SubsetSchemas(_)) => {
+                    Err(MongoDbSourcePurificationError::SchemasNotSupported)?
+                }
+                Some(ReferencedSubsources::SubsetTables(collections)) => {
+                    mz_mongodb_util::CollectionRequest::Collections(
+                        collections
+                            .iter()
+                            .map(|c| c.reference.0.last().expect("non-empty name").as_str())
+                            .collect(),
+                    )
+                }
+            };
+
+            let collection_infos =
+                mz_mongodb_util::collection_info(&db, &collection_request).await?;
+            mz_mongodb_util::ensure_pre_and_post_images(&collection_infos).map_err(
+                |err| match err {
+                    mz_mongodb_util::MongoDbError::PreAndPostImagesDisabled { collections } => {
+                        PlanError::from(MongoDbSourcePurificationError::PreAndPostImagesDisabled {
+                            collections,
+                        })
+                    }
+                    _ => err.into(),
+                },
+            )?;
+            let collections: Vec<String> = collection_infos.into_iter().map(|c| c.name).collect();
+
+            if collections.is_empty() {
+                Err(MongoDbSourcePurificationError::NoCollections(
+                    database.to_string(),
+                ))?;
+            }
+
+            let mongodb_catalog = mongodb::derive_catalog_from_collections(database, &collections);
+
+            let mut validated_requested_subsources = vec![];
+            match referenced_subsources.as_mut() {
+                // Without a FOR clause, all collections are ingested into the
+                // source itself.
+                None => {}
+                Some(ReferencedSubsources::All) => {
+                    for collection in &collections {
+                        let upstream_name = mongodb::mongodb_upstream_name(database, collection)?;
+                        let subsource_name = subsource_name_gen(source_name, collection)?;
+                        validated_requested_subsources.push(RequestedSubsource {
+                            upstream_name,
+                            subsource_name,
+                            table: collection,
+                        });
+                    }
+                }
+                Some(ReferencedSubsources::SubsetSchemas(_)) => {
+                    unreachable!("rejected above")
+                }
+                Some(ReferencedSubsources::SubsetTables(subsources)) => {
+                    // The user manually selected a subset of upstream collections so we need
+                    // to validate that the names actually exist and are not ambiguous
+                    validated_requested_subsources.extend(subsource_gen(
+                        subsources,
+                        &mongodb_catalog,
+                        source_name,
+                    )?);
+                }
+            }
+
+            let has_subsources = referenced_subsources.is_some();
+            let collections = if has_subsources {
+                validate_subsource_names(&validated_requested_subsources)?;
+
+                // Only ingest the collections that were requested, in the
+                // order that determines their output index.
+                let mut requested: Vec<_> = validated_requested_subsources
+                    .iter()
+                    .map(|subsource| subsource.table.clone())
+                    .collect();
+                requested.sort();
+                requested.dedup();
+
+                let (targeted_subsources, new_subsources) = mongodb::generate_targeted_subsources(
+                    &scx,
+                    validated_requested_subsources,
+                    get_transient_subsource_id,
+                )?;
+
+                *referenced_subsources =
+                    Some(ReferencedSubsources::SubsetTables(targeted_subsources));
+                subsources.extend(new_subsources);
+                requested
+            } else {
+                collections.clone()
+            };
+
+            // Remove any old detail references
+            options.retain(|MongoDbConfigOption { name, .. }| {
+                name != &MongoDbConfigOptionName::Details
+            });
+            let details = MongoDbSourceDetails {
+                collections,
+                subsources: has_subsources,
+            };
+            options.push(MongoDbConfigOption {
+                name: MongoDbConfigOptionName::Details,
+                value: Some(WithOptionValue::Value(Value::String(hex::encode(
+                    details.into_proto().encode_to_vec(),
+                )))),
+            })
+        }
+        CreateSourceConnection::S3 { .. } => {
+            if referenced_subsources.is_some() {
+                sql_bail!("S3 sources do not support subsources");
+            }
+        }
         CreateSourceConnection::LoadGenerator { generator, options } => {
             let scx = StatementContext::new(None, &catalog);
 
@@ -1276,7 +1765,7 @@ async fn purify_create_source(
     )
     .await?;
 
-    Ok((subsources, Statement::CreateSource(stmt)))
+    Ok((types, subsources, Statement::CreateSource(stmt)))
 }
 
 /// Equivalent to `purify_create_source` but for `AlterSourceStatement`.
@@ -1291,6 +1780,7 @@ async fn purify_alter_source(
     storage_configuration: &StorageConfiguration,
 ) -> Result<
     (
+        Vec<(GlobalId, CreateTypeStatement<Aug>)>,
         Vec<(GlobalId, CreateSubsourceStatement<Aug>)>,
         Statement<Aug>,
     ),
@@ -1309,7 +1799,7 @@ async fn purify_alter_source(
         let item = match scx.resolve_item(RawItemName::Name(source_name.clone())) {
             Ok(item) => item,
             Err(_) if *if_exists => {
-                return Ok((vec![], Statement::AlterSource(stmt)));
+                return Ok((vec![], vec![], Statement::AlterSource(stmt)));
             }
             Err(e) => return Err(e),
         };
@@ -1340,7 +1830,7 @@ async fn purify_alter_source(
             details,
             options,
         } => (subsources, details, options),
-        _ => return Ok((vec![], Statement::AlterSource(stmt))),
+        _ => return Ok((vec![], vec![], Statement::AlterSource(stmt))),
     };
 
     assert!(
@@ -1467,7 +1957,7 @@ async fn purify_alter_source(
         text_cols_option.value = Some(WithOptionValue::Sequence(seq));
     }
 
-    let (named_subsources, new_subsources) = postgres::generate_targeted_subsources(
+    let (named_subsources, new_subsources, new_types) = postgres::generate_targeted_subsources(
         &scx,
         validated_requested_subsources,
         text_cols_dict,
@@ -1531,7 +2021,7 @@ async fn purify_alter_source(
         new_details.into_proto().encode_to_vec(),
     ))));
 
-    Ok((new_subsources, Statement::AlterSource(stmt)))
+    Ok((new_types, new_subsources, Statement::AlterSource(stmt)))
 }
 
 async fn purify_source_format(
@@ -1604,11 +2094,23 @@ async fn purify_source_format_single(
             }
             ProtobufSchema::InlineSchema { .. } => {}
         },
+        Format::JsonCsr { csr_connection } => {
+            purify_csr_connection_json(
+                catalog,
+                connection,
+                csr_connection,
+                envelope,
+                storage_configuration,
+            )
+            .await?
+        }
         Format::Bytes
+        | Format::AvroOcf { .. }
         | Format::Regex(_)
         | Format::Json { .. }
         | Format::Text
-        | Format::Csv { .. } => (),
+        | Format::Csv { .. }
+        | Format::Parquet => (),
     }
     Ok(())
 }
@@ -1725,6 +2227,61 @@ async fn purify_csr_connection_avro(
     Ok(())
 }
 
+async fn purify_csr_connection_json(
+    catalog: &dyn SessionCatalog,
+    connection: &mut CreateSourceConnection<Aug>,
+    csr_connection: &mut CsrConnectionJson<Aug>,
+    envelope: &Option<SourceEnvelope>,
+    storage_configuration: &StorageConfiguration,
+) -> Result<(), PlanError> {
+    let topic = if let CreateSourceConnection::Kafka { options, .. } = connection {
+        let KafkaSourceConfigOptionExtracted { topic, .. } = options
+            .clone()
+            .try_into()
+            .expect("already verified options valid provided");
+        topic.expect("already validated topic provided")
+    } else {
+        sql_bail!("Confluent Schema Registry is only supported with Kafka sources")
+    };
+
+    let CsrConnectionJson {
+        connection: CsrConnection { connection, .. },
+        seed,
+    } = csr_connection;
+    if seed.is_none() {
+        let scx = StatementContext::new(None, &*catalog);
+        let csr_connection = match scx.get_item_by_resolved_name(connection)?.connection()? {
+            Connection::Csr(connection) => connection.clone().into_inline_connection(catalog),
+            _ => sql_bail!("{} is not a schema registry connection", connection),
+        };
+        let ccsr_client = csr_connection
+            .connect(storage_configuration)
+            .await
+            .map_err(|e| CsrPurificationError::ClientError(Arc::new(e)))?;
+
+        let Schema {
+            key_schema,
+            value_schema,
+        } = get_remote_csr_schema(
+            &ccsr_client,
+            ReaderSchemaSelectionStrategy::Latest,
+            ReaderSchemaSelectionStrategy::Latest,
+            topic,
+        )
+        .await?;
+        if matches!(envelope, Some(SourceEnvelope::Debezium)) && key_schema.is_none() {
+            sql_bail!("Key schema is required for ENVELOPE DEBEZIUM");
+        }
+
+        *seed = Some(CsrSeedJson {
+            key_schema,
+            value_schema,
+        })
+    }
+
+    Ok(())
+}
+
 #[derive(Debug)]
 pub struct Schema {
     pub key_schema: Option<String>,
@@ -1862,6 +2419,7 @@ pub fn purify_create_materialized_view_options(
                 args: FunctionArgs::Args {
                     args: Vec::new(),
                     order_by: Vec::new(),
+                    within_group: false,
                 },
                 filter: None,
                 over: None,
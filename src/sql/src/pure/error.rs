@@ -313,3 +313,108 @@ impl MySqlSourcePurificationError {
         }
     }
 }
+
+/// Logical errors detectable during purification for a SQL SERVER SOURCE.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum SqlServerSourcePurificationError {
+    #[error("CREATE SOURCE specifies DETAILS option")]
+    UserSpecifiedDetails,
+    #[error("{0} is not a SQL SERVER CONNECTION")]
+    NotSqlServerConnection(FullItemName),
+    #[error("referenced tables use unsupported types")]
+    UnrecognizedTypes { cols: Vec<(String, String, String)> },
+    #[error("Invalid SQL Server table reference: {0}")]
+    InvalidTableReference(String),
+    #[error("No tables with change data capture enabled found")]
+    NoCdcTables,
+    #[error("missing TABLES specification")]
+    RequiresReferencedSubsources,
+    #[error("No tables found in referenced schemas")]
+    NoTablesFoundForSchemas(Vec<String>),
+}
+
+impl SqlServerSourcePurificationError {
+    pub fn detail(&self) -> Option<String> {
+        match self {
+            Self::UnrecognizedTypes { cols } => Some(format!(
+                "the following columns contain unsupported types:\n{}",
+                itertools::join(
+                    cols.into_iter().map(|(table, column, data_type)| format!(
+                        "'{}' for {}.{}",
+                        data_type, column, table
+                    )),
+                    "\n"
+                )
+            )),
+            Self::NoTablesFoundForSchemas(schemas) => Some(format!(
+                "missing schemas: {}",
+                itertools::join(schemas.iter(), ", ")
+            )),
+            _ => None,
+        }
+    }
+
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            Self::UserSpecifiedDetails => Some(
+                "If trying to use the output of SHOW CREATE SOURCE, remove the DETAILS option."
+                    .into(),
+            ),
+            Self::RequiresReferencedSubsources => {
+                Some("provide a FOR TABLES (..), FOR SCHEMAS (..), or FOR ALL TABLES clause".into())
+            }
+            Self::InvalidTableReference(_) => Some(
+                "Specify tables names as SCHEMA_NAME.TABLE_NAME in a FOR TABLES (..) clause".into(),
+            ),
+            Self::NoCdcTables => Some(
+                "Enable change data capture on the upstream tables using sys.sp_cdc_enable_table."
+                    .into(),
+            ),
+            _ => None,
+        }
+    }
+}
+
+/// Logical errors detectable during purification for a MONGODB SOURCE.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum MongoDbSourcePurificationError {
+    #[error("CREATE SOURCE specifies DETAILS option")]
+    UserSpecifiedDetails,
+    #[error("{0} is not a MONGODB CONNECTION")]
+    NotMongoDbConnection(FullItemName),
+    #[error("No collections found in database {0}")]
+    NoCollections(String),
+    #[error("FOR SCHEMAS (..) is not supported for MongoDB sources")]
+    SchemasNotSupported,
+    #[error("referenced collections do not have change stream pre- and post-images enabled")]
+    PreAndPostImagesDisabled { collections: Vec<String> },
+}
+
+impl MongoDbSourcePurificationError {
+    pub fn detail(&self) -> Option<String> {
+        match self {
+            Self::PreAndPostImagesDisabled { collections } => Some(format!(
+                "affected collections: {}",
+                itertools::join(collections.iter(), ", ")
+            )),
+            _ => None,
+        }
+    }
+
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            Self::UserSpecifiedDetails => Some(
+                "If trying to use the output of SHOW CREATE SOURCE, remove the DETAILS option."
+                    .into(),
+            ),
+            Self::SchemasNotSupported => {
+                Some("provide a FOR TABLES (..) or FOR ALL TABLES clause, or omit it".into())
+            }
+            Self::PreAndPostImagesDisabled { .. } => Some(
+                "Enable them using db.runCommand({collMod: <collection>, changeStreamPreAndPostImages: {enabled: true}})."
+                    .into(),
+            ),
+            _ => None,
+        }
+    }
+}
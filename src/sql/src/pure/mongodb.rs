@@ -0,0 +1,139 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! MongoDB utilities for SQL purification.
+
+use std::collections::BTreeMap;
+
+use mz_repr::GlobalId;
+use mz_sql_parser::ast::{
+    ColumnDef, ColumnOption, ColumnOptionDef, CreateSourceSubsource, CreateSubsourceOption,
+    CreateSubsourceOptionName, CreateSubsourceStatement, DeferredItemName, Ident, IdentError,
+    TableConstraint, UnresolvedItemName, Value, WithOptionValue,
+};
+
+use crate::catalog::SubsourceCatalog;
+use crate::names::Aug;
+use crate::plan::{PlanError, StatementContext};
+
+use super::RequestedSubsource;
+
+/// The name of the database that MongoDB collections are referenced by.
+/// MongoDB only has a 2-layer namespace, so, like for MySQL, we put the
+/// upstream databases into a fake database.
+pub(super) static MONGODB_DATABASE_NAME: &str = "mongodb";
+
+/// Returns the fully qualified upstream name of `collection` in `database`.
+pub(super) fn mongodb_upstream_name(
+    database: &str,
+    collection: &str,
+) -> Result<UnresolvedItemName, IdentError> {
+    Ok(UnresolvedItemName::qualified(&[
+        Ident::new(MONGODB_DATABASE_NAME)?,
+        Ident::new(database)?,
+        Ident::new(collection)?,
+    ]))
+}
+
+pub(super) fn derive_catalog_from_collections<'a>(
+    database: &str,
+    collections: &'a [String],
+) -> SubsourceCatalog<&'a String> {
+    // An index from collection name -> database name -> fake database name -> collection
+    let mut collections_by_name = BTreeMap::new();
+    for collection in collections.iter() {
+        collections_by_name
+            .entry(collection.clone())
+            .or_insert_with(BTreeMap::new)
+            .entry(database.to_string())
+            .or_insert_with(BTreeMap::new)
+            .entry(MONGODB_DATABASE_NAME.to_string())
+            .or_insert(collection);
+    }
+
+    SubsourceCatalog(collections_by_name)
+}
+
+pub(super) fn generate_targeted_subsources<F>(
+    scx: &StatementContext,
+    validated_requested_subsources: Vec<RequestedSubsource<String>>,
+    mut get_transient_subsource_id: F,
+) -> Result<
+    (
+        Vec<CreateSourceSubsource<Aug>>,
+        Vec<(GlobalId, CreateSubsourceStatement<Aug>)>,
+    ),
+    PlanError,
+>
+where
+    F: FnMut() -> u64,
+{
+    let mut targeted_subsources = vec![];
+    let mut subsources = vec![];
+
+    for RequestedSubsource {
+        upstream_name,
+        subsource_name,
+        table: _,
+    } in validated_requested_subsources.into_iter()
+    {
+        // Every collection has the same schema: documents are ingested as
+        // `jsonb` and keyed by their `_id`, which MongoDB enforces to be
+        // unique within a collection.
+        let jsonb = scx.resolve_type(mz_pgrepr::Type::Jsonb)?;
+        let columns = ["_id", "document"]
+            .into_iter()
+            .map(|name| {
+                Ok(ColumnDef {
+                    name: Ident::new(name)?,
+                    data_type: jsonb.clone(),
+                    collation: None,
+                    options: vec![ColumnOptionDef {
+                        name: None,
+                        option: ColumnOption::NotNull,
+                    }],
+                })
+            })
+            .collect::<Result<Vec<_>, PlanError>>()?;
+
+        let constraints = vec![TableConstraint::Unique {
+            name: None,
+            columns: vec![Ident::new("_id")?],
+            is_primary: true,
+            nulls_not_distinct: false,
+        }];
+
+        // Create the targeted AST node for the original CREATE SOURCE statement
+        let transient_id = GlobalId::Transient(get_transient_subsource_id());
+
+        let subsource = scx.allocate_resolved_item_name(transient_id, subsource_name.clone())?;
+
+        targeted_subsources.push(CreateSourceSubsource {
+            reference: upstream_name,
+            subsource: Some(DeferredItemName::Named(subsource)),
+        });
+
+        // Create the subsource statement
+        let subsource = CreateSubsourceStatement {
+            name: subsource_name,
+            columns,
+            constraints,
+            if_not_exists: false,
+            with_options: vec![CreateSubsourceOption {
+                name: CreateSubsourceOptionName::References,
+                value: Some(WithOptionValue::Value(Value::Boolean(true))),
+            }],
+        };
+        subsources.push((transient_id, subsource));
+    }
+
+    targeted_subsources.sort();
+
+    Ok((targeted_subsources, subsources))
+}
@@ -12,11 +12,11 @@
 use std::collections::{BTreeMap, BTreeSet};
 
 use mz_mysql_util::{MySqlTableDesc, QualifiedTableRef};
-use mz_repr::GlobalId;
+use mz_repr::{GlobalId, ScalarType};
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::{
     ColumnDef, CreateSubsourceOption, CreateSubsourceOptionName, CreateSubsourceStatement,
-    DeferredItemName, Ident, IdentError, Value, WithOptionValue,
+    CreateTypeStatement, DeferredItemName, Ident, IdentError, Value, WithOptionValue,
 };
 use mz_sql_parser::ast::{CreateSourceSubsource, UnresolvedItemName};
 
@@ -68,6 +68,7 @@ pub(super) fn generate_targeted_subsources<F>(
     (
         Vec<CreateSourceSubsource<Aug>>,
         Vec<(GlobalId, CreateSubsourceStatement<Aug>)>,
+        Vec<(GlobalId, CreateTypeStatement<Aug>)>,
     ),
     PlanError,
 >
@@ -76,6 +77,7 @@ where
 {
     let mut targeted_subsources = vec![];
     let mut subsources = vec![];
+    let mut types = vec![];
 
     // Now that we have an explicit list of validated requested subsources we can create them
     for RequestedSubsource {
@@ -94,8 +96,24 @@ where
                 Some(ref column_type) => {
                     let name = Ident::new(&c.name)?;
 
-                    let ty = mz_pgrepr::Type::from(&column_type.scalar_type);
-                    let data_type = scx.resolve_type(ty)?;
+                    let data_type = match &column_type.scalar_type {
+                        // MySQL enums are anonymous, so each one maps onto an enum type named
+                        // after its subsource and column.
+                        ScalarType::Enum { labels, .. } => {
+                            let item = subsource_name.0.last().expect("at least one ident");
+                            super::upstream_enum_type(
+                                scx,
+                                super::subsource_name_gen(
+                                    &subsource_name,
+                                    &format!("{}_{}", item.as_str(), c.name),
+                                )?,
+                                labels,
+                                &mut get_transient_subsource_id,
+                                &mut types,
+                            )?
+                        }
+                        scalar_type => scx.resolve_type(mz_pgrepr::Type::from(scalar_type))?,
+                    };
                     let mut col_options = vec![];
 
                     if !column_type.nullable {
@@ -160,7 +178,7 @@ where
 
     targeted_subsources.sort();
 
-    Ok((targeted_subsources, subsources))
+    Ok((targeted_subsources, subsources, types))
 }
 
 /// Map a list of column references to a map of table references to column names.
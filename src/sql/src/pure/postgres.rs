@@ -18,7 +18,7 @@ use mz_repr::GlobalId;
 use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::{
     ColumnDef, CreateSubsourceOption, CreateSubsourceOptionName, CreateSubsourceStatement,
-    DeferredItemName, Ident, Value, WithOptionValue,
+    CreateTypeStatement, DeferredItemName, Ident, Value, WithOptionValue,
 };
 use mz_sql_parser::ast::{CreateSourceSubsource, UnresolvedItemName};
 use mz_ssh_util::tunnel_manager::SshTunnelManager;
@@ -165,6 +165,7 @@ pub(crate) fn generate_targeted_subsources<F>(
     (
         Vec<CreateSourceSubsource<Aug>>,
         Vec<(GlobalId, CreateSubsourceStatement<Aug>)>,
+        Vec<(GlobalId, CreateTypeStatement<Aug>)>,
     ),
     PlanError,
 >
@@ -173,6 +174,7 @@ where
 {
     let mut targeted_subsources = vec![];
     let mut subsources = vec![];
+    let mut types = vec![];
 
     // Aggregate all unrecognized types.
     let mut unsupported_cols = vec![];
@@ -189,10 +191,21 @@ where
         let text_cols_dict = text_cols_dict.remove(&table.oid);
         for c in table.columns.iter() {
             let name = Ident::new(c.name.clone())?;
-            let ty = match &text_cols_dict {
-                Some(names) if names.contains(&c.name) => mz_pgrepr::Type::Text,
+            let data_type = match (&text_cols_dict, &c.enum_labels, &c.enum_name) {
+                (Some(names), _, _) if names.contains(&c.name) => {
+                    scx.resolve_type(mz_pgrepr::Type::Text)?
+                }
+                // Upstream enums map onto an enum type of the same name in the subsource's
+                // schema.
+                (_, Some(labels), Some(enum_name)) => super::upstream_enum_type(
+                    scx,
+                    super::subsource_name_gen(&subsource_name, enum_name)?,
+                    labels,
+                    &mut get_transient_subsource_id,
+                    &mut types,
+                )?,
                 _ => match mz_pgrepr::Type::from_oid_and_typmod(c.type_oid, c.type_mod) {
-                    Ok(t) => t,
+                    Ok(t) => scx.resolve_type(t)?,
                     Err(_) => {
                         let mut full_name = upstream_name.0.clone();
                         full_name.push(name);
@@ -205,7 +218,6 @@ where
                 },
             };
 
-            let data_type = scx.resolve_type(ty)?;
             let mut options = vec![];
 
             if !c.nullable {
@@ -320,7 +332,7 @@ where
 
     targeted_subsources.sort();
 
-    Ok((targeted_subsources, subsources))
+    Ok((targeted_subsources, subsources, types))
 }
 
 mod privileges {
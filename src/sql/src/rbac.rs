@@ -563,6 +563,22 @@ fn generate_rbac_requirements(
             item_usage: &CREATE_ITEM_USAGE,
             ..Default::default()
         },
+        Plan::CreateFunction(plan::CreateFunctionPlan {
+            name,
+            function: _,
+            replace,
+        }) => RbacRequirements {
+            ownership: replace
+                .map(|id| vec![ObjectId::Item(id)])
+                .unwrap_or_default(),
+            privileges: vec![(
+                SystemObjectId::Object(name.qualifiers.clone().into()),
+                AclMode::CREATE,
+                role_id,
+            )],
+            item_usage: &CREATE_ITEM_USAGE,
+            ..Default::default()
+        },
         Plan::Comment(plan::CommentPlan {
             object_id,
             sub_component: _,
@@ -1123,6 +1139,7 @@ fn generate_rbac_requirements(
                 MutationKind::Insert => AclMode::INSERT,
                 MutationKind::Update => AclMode::UPDATE,
                 MutationKind::Delete => AclMode::DELETE,
+                MutationKind::Upsert => AclMode::INSERT.union(AclMode::UPDATE),
             };
             let schema_id: ObjectId = catalog.get_item(id).name().qualifiers.clone().into();
             let mut privileges = vec![
@@ -1484,6 +1501,7 @@ fn generate_read_privileges_inner(
 ) -> Vec<(SystemObjectId, AclMode, RoleId)> {
     let mut privileges = Vec::new();
     let mut views = Vec::new();
+    let mut functions = Vec::new();
 
     for id in ids {
         if seen.insert((id.into(), role_id)) {
@@ -1503,11 +1521,26 @@ fn generate_read_privileges_inner(
                 CatalogItemType::Type | CatalogItemType::Secret | CatalogItemType::Connection => {
                     privileges.push((SystemObjectId::Object(id.into()), AclMode::USAGE, role_id));
                 }
-                CatalogItemType::Sink | CatalogItemType::Index | CatalogItemType::Func => {}
+                CatalogItemType::Func => {
+                    // User-defined functions are inlined into the calling
+                    // query, so the caller must be able to read everything the
+                    // function reads.
+                    functions.push(item.references().0.clone().into_iter());
+                }
+                CatalogItemType::Sink | CatalogItemType::Index => {}
             }
         }
     }
 
+    for function_ids in functions {
+        privileges.extend_from_slice(&generate_read_privileges_inner(
+            catalog,
+            function_ids,
+            role_id,
+            seen,
+        ));
+    }
+
     for (view_ids, view_owner) in views {
         privileges.extend_from_slice(&generate_read_privileges_inner(
             catalog, view_ids, view_owner, seen,
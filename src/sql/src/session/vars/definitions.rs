@@ -1270,8 +1270,8 @@ pub static EMIT_INTROSPECTION_QUERY_NOTICE: VarDefinition = VarDefinition::new(
 pub static ENABLE_SESSION_CARDINALITY_ESTIMATES: VarDefinition = VarDefinition::new(
     "enable_session_cardinality_estimates",
     value!(bool; false),
-    "Feature flag indicating whether to use cardinality estimates when optimizing queries; \
-        does not affect EXPLAIN WITH(cardinality) (Materialize).",
+    "Feature flag indicating whether to use cardinality estimates and column statistics when \
+        optimizing queries and in EXPLAIN WITH(cardinality) (Materialize).",
     false,
 )
 .with_feature_flag(&ENABLE_CARDINALITY_ESTIMATES);
@@ -1943,6 +1943,13 @@ feature_flags!(
         internal: true,
         enable_for_item_parsing: true,
     },
+    {
+        name: enable_notices_for_join_hint_not_applied,
+        desc: "emitting notices for JoinHintNotApplied (doesn't affect EXPLAIN)",
+        default: true,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
     {
         name: enable_explain_broken,
         desc: "EXPLAIN ... BROKEN <query> syntax",
@@ -2020,6 +2027,76 @@ feature_flags!(
         internal: true,
         enable_for_item_parsing: false,
     },
+    {
+        name: enable_http_sink,
+        desc: "CREATE CONNECTION ... TO HTTP and CREATE SINK ... INTO HTTP CONNECTION",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_kafka_sink_headers,
+        desc: "KAFKA sinks with HEADERS",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_kafka_sink_partition_by,
+        desc: "KAFKA sinks with PARTITION BY",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_postgres_sink,
+        desc: "CREATE SINK ... INTO POSTGRES CONNECTION",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_s3_source,
+        desc: "CREATE SOURCE ... FROM S3 CONNECTION",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_sql_server_source,
+        desc: "Create a SQL Server connection or source",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_mongodb_source,
+        desc: "Create a MongoDB connection or source",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_avro_ocf_format,
+        desc: "FORMAT AVRO OCF",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_json_schema_format,
+        desc: "FORMAT JSON USING CONFLUENT SCHEMA REGISTRY",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_create_function,
+        desc: "CREATE FUNCTION ... LANGUAGE SQL",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
     {
         name: enable_expressions_in_limit_syntax,
         desc: "LIMIT <expr> syntax",
@@ -2063,6 +2140,20 @@ feature_flags!(
         internal: true,
         enable_for_item_parsing: false,
     },
+    {
+        name: enable_incremental_window_functions,
+        desc: "incremental rendering of window functions",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
+    {
+        name: enable_explain_analyze,
+        desc: "EXPLAIN ANALYZE",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: false,
+    },
     {
         name: enable_worker_core_affinity,
         desc: "set core affinity for replica worker threads",
@@ -2112,6 +2203,13 @@ feature_flags!(
         internal: true,
         enable_for_item_parsing: false,
     },
+    {
+        name: enable_join_hints,
+        desc: "SELECT ... OPTIONS (JOIN ORDER ..., JOIN IMPLEMENTATION ...) syntax",
+        default: false,
+        internal: true,
+        enable_for_item_parsing: true,
+    },
 );
 
 impl From<&super::SystemVars> for OptimizerFeatures {
@@ -2120,6 +2218,7 @@ impl From<&super::SystemVars> for OptimizerFeatures {
             enable_consolidate_after_union_negate: vars.enable_consolidate_after_union_negate(),
             enable_eager_delta_joins: vars.enable_eager_delta_joins(),
             enable_equivalence_propagation: vars.enable_equivalence_propagation(),
+            enable_incremental_window_functions: vars.enable_incremental_window_functions(),
             enable_new_outer_join_lowering: vars.enable_new_outer_join_lowering(),
             enable_reduce_mfp_fusion: vars.enable_reduce_mfp_fusion(),
             enable_variadic_left_join_lowering: vars.enable_variadic_left_join_lowering(),
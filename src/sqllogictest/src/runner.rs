@@ -60,7 +60,7 @@ use mz_persist_client::rpc::{
     MetricsSameProcessPubSubSender, PersistGrpcPubSubServer, PubSubClientConnection, PubSubSender,
 };
 use mz_persist_client::PersistLocation;
-use mz_pgrepr::{oid, Interval, Jsonb, Numeric, UInt2, UInt4, UInt8, Value};
+use mz_pgrepr::{oid, Inet, Interval, Jsonb, MacAddr, Numeric, UInt2, UInt4, UInt8, Value};
 use mz_repr::adt::date::Date;
 use mz_repr::adt::mz_acl_item::{AclItem, MzAclItem};
 use mz_repr::adt::numeric;
@@ -450,7 +450,7 @@ impl<'a> FromSql<'a> for Slt {
             PgType::REGCLASS => Self(Value::Oid(types::oid_from_sql(raw)?)),
             PgType::REGPROC => Self(Value::Oid(types::oid_from_sql(raw)?)),
             PgType::REGTYPE => Self(Value::Oid(types::oid_from_sql(raw)?)),
-            PgType::TEXT | PgType::BPCHAR | PgType::VARCHAR => {
+            PgType::TEXT | PgType::BPCHAR | PgType::VARCHAR | PgType::ANYENUM => {
                 Self(Value::Text(types::text_from_sql(raw)?.to_string()))
             }
             PgType::TIME => Self(Value::Time(NaiveTime::from_sql(ty, raw)?)),
@@ -461,6 +461,9 @@ impl<'a> FromSql<'a> for Slt {
                 DateTime::<Utc>::from_sql(ty, raw)?.try_into()?,
             )),
             PgType::UUID => Self(Value::Uuid(Uuid::from_sql(ty, raw)?)),
+            PgType::INET => Self(Value::Inet(Inet::from_sql(ty, raw)?.0)),
+            PgType::CIDR => Self(Value::Cidr(Inet::from_sql(ty, raw)?.0)),
+            PgType::MACADDR => Self(Value::MacAddr(MacAddr::from_sql(ty, raw)?.0)),
             PgType::RECORD => {
                 let num_fields = read_be_i32(&mut raw)?;
                 let mut tuple = vec![];
@@ -569,10 +572,14 @@ impl<'a> FromSql<'a> for Slt {
                 | PgType::TEXT
                 | PgType::BPCHAR
                 | PgType::VARCHAR
+                | PgType::ANYENUM
                 | PgType::TIME
                 | PgType::TIMESTAMP
                 | PgType::TIMESTAMPTZ
                 | PgType::UUID
+                | PgType::INET
+                | PgType::CIDR
+                | PgType::MACADDR
                 | PgType::INT4_RANGE
                 | PgType::INT4_RANGE_ARRAY
                 | PgType::INT8_RANGE
@@ -18,14 +18,15 @@ use differential_dataflow::Collection;
 use http::Uri;
 use mz_aws_util::s3_uploader::{
     CompletedUpload, S3MultiPartUploadError, S3MultiPartUploader, S3MultiPartUploaderConfig,
+    AWS_S3_MAX_PART_COUNT,
 };
 use mz_ore::task::JoinHandleExt;
-use mz_pgcopy::{encode_copy_format, CopyFormatParams};
+use mz_pgcopy::encode_copy_format;
 use mz_repr::{Diff, GlobalId, RelationDesc, Row, Timestamp};
 use mz_storage_types::connections::aws::AwsConnection;
 use mz_storage_types::connections::ConnectionContext;
 use mz_storage_types::errors::DataflowError;
-use mz_storage_types::sinks::S3UploadInfo;
+use mz_storage_types::sinks::{S3SinkFormat, S3UploadInfo};
 use mz_timely_util::builder_async::{Event as AsyncEvent, OperatorBuilder as AsyncOperatorBuilder};
 use timely::dataflow::channels::pact::Pipeline;
 use timely::dataflow::Scope;
@@ -33,6 +34,13 @@ use timely::progress::Antichain;
 use timely::PartialOrder;
 use tracing::info;
 
+use crate::s3_oneshot_sink::parquet::{desc_to_schema, ArrowBuilder, ParquetFileWriter};
+
+mod parquet;
+
+/// The target size of the row groups of uploaded parquet files.
+const PARQUET_ROW_GROUP_SIZE: ByteSize = ByteSize::mib(128);
+
 pub fn copy_to<G, F>(
     input_collection: Collection<G, (Row, ()), Diff>,
     err_collection: Collection<G, (DataflowError, ()), Diff>,
@@ -145,8 +153,8 @@ pub fn copy_to<G, F>(
 struct CopyToS3Uploader {
     /// The output description.
     desc: RelationDesc,
-    /// Params to format the data.
-    format: CopyFormatParams<'static>,
+    /// The format to encode the data in.
+    format: S3SinkFormat,
     /// The index of the current file.
     file_index: usize,
     /// The prefix for the file names.
@@ -167,8 +175,14 @@ struct CopyToS3Uploader {
     current_file_uploader: Option<S3MultiPartUploader>,
     /// Temporary buffer to store the encoded bytes.
     /// Currently at a time this will only store one single encoded row
-    /// before getting added to the `current_file_uploader`'s buffer.
+    /// or row group before getting added to the `current_file_uploader`'s buffer.
     buf: Vec<u8>,
+    /// Rows buffered for the next parquet row group.
+    /// Only used if `format` is [`S3SinkFormat::Parquet`].
+    arrow_builder: Option<ArrowBuilder>,
+    /// Parquet writer for the current file.
+    /// Only used if `format` is [`S3SinkFormat::Parquet`].
+    parquet_writer: Option<ParquetFileWriter>,
 }
 
 impl CopyToS3Uploader {
@@ -178,6 +192,10 @@ impl CopyToS3Uploader {
         file_name_prefix: String,
     ) -> CopyToS3Uploader {
         let (bucket, path_prefix) = Self::extract_s3_bucket_path(&connection_details.prefix);
+        let arrow_builder = match connection_details.format {
+            S3SinkFormat::PgCopy(_) => None,
+            S3SinkFormat::Parquet => Some(ArrowBuilder::new(&connection_details.desc)),
+        };
         CopyToS3Uploader {
             desc: connection_details.desc,
             sdk_config: Some(sdk_config),
@@ -189,12 +207,14 @@ impl CopyToS3Uploader {
             file_index: 0,
             current_file_uploader: None,
             buf: Vec::new(),
+            arrow_builder,
+            parquet_writer: None,
         }
     }
 
     /// Creates the uploader for the next file and starts the multi part upload.
     async fn start_new_file_upload(&mut self) -> Result<(), anyhow::Error> {
-        self.flush().await?;
+        self.finish_current_file().await?;
         assert!(self.current_file_uploader.is_none());
 
         self.file_index += 1;
@@ -206,7 +226,16 @@ impl CopyToS3Uploader {
             .sdk_config
             .take()
             .expect("sdk_config should always be present");
-        let max_file_size = self.max_file_size;
+        let part_size_limit = ByteSize::mib(10).as_u64();
+        let file_size_limit = match self.format {
+            S3SinkFormat::PgCopy(_) => self.max_file_size,
+            // A parquet file can't be split at arbitrary offsets, so we decide
+            // when to roll over to a new file at row group boundaries instead
+            // and only limit the upload by the number of parts S3 allows.
+            S3SinkFormat::Parquet => {
+                part_size_limit * u64::try_from(AWS_S3_MAX_PART_COUNT).expect("i32 to u64")
+            }
+        };
         // Moving the aws s3 calls onto tokio tasks instead of using timely runtime.
         let handle = mz_ore::task::spawn(|| "s3_uploader::try_new", async move {
             let uploader = S3MultiPartUploader::try_new(
@@ -214,8 +243,8 @@ impl CopyToS3Uploader {
                 bucket,
                 file_path,
                 S3MultiPartUploaderConfig {
-                    part_size_limit: ByteSize::mib(10).as_u64(),
-                    file_size_limit: max_file_size,
+                    part_size_limit,
+                    file_size_limit,
                 },
             )
             .await;
@@ -224,14 +253,20 @@ impl CopyToS3Uploader {
         let (uploader, sdk_config) = handle.wait_and_assert_finished().await;
         self.sdk_config = Some(sdk_config);
         self.current_file_uploader = Some(uploader?);
+        if let S3SinkFormat::Parquet = self.format {
+            self.parquet_writer = Some(ParquetFileWriter::new(desc_to_schema(&self.desc))?);
+        }
         Ok(())
     }
 
     fn current_file_path(&self) -> String {
-        // TODO: remove hard-coded file extension .csv
+        let extension = match self.format {
+            S3SinkFormat::PgCopy(_) => "csv",
+            S3SinkFormat::Parquet => "parquet",
+        };
         format!(
-            "{}/{}-{:04}.csv",
-            self.path_prefix, self.file_name_prefix, self.file_index
+            "{}/{}-{:04}.{}",
+            self.path_prefix, self.file_name_prefix, self.file_index, extension
         )
     }
 
@@ -243,8 +278,21 @@ impl CopyToS3Uploader {
         (bucket.to_string(), path.to_string())
     }
 
-    /// Finishes any remaining in-progress upload.
+    /// Writes out any buffered rows and finishes any remaining in-progress upload.
     async fn flush(&mut self) -> Result<(), anyhow::Error> {
+        if self.arrow_builder.as_ref().is_some_and(|b| !b.is_empty()) {
+            self.write_row_group().await?;
+        }
+        self.finish_current_file().await
+    }
+
+    /// Finishes the in-progress upload, if any.
+    async fn finish_current_file(&mut self) -> Result<(), anyhow::Error> {
+        if let Some(writer) = self.parquet_writer.take() {
+            // Upload the parquet footer.
+            self.buf = writer.finish()?;
+            self.upload_buffer().await?;
+        }
         if let Some(uploader) = self.current_file_uploader.take() {
             let current_file = self.current_file_path();
             // Moving the aws s3 calls onto tokio tasks instead of using timely runtime.
@@ -282,14 +330,25 @@ impl CopyToS3Uploader {
         Ok(())
     }
 
+    /// Appends the row to the in-progress upload.
+    async fn append_row(&mut self, row: &Row) -> Result<(), anyhow::Error> {
+        match self.format {
+            S3SinkFormat::PgCopy(_) => self.append_pgcopy_row(row).await,
+            S3SinkFormat::Parquet => self.append_parquet_row(row).await,
+        }
+    }
+
     /// Appends the row to the in-progress upload where it is buffered till it reaches the configured
     /// `part_size_limit` after which the `S3MultiPartUploader` will upload that part. In case it will
     /// exceed the max file size of the ongoing upload, then a new `S3MultiPartUploader` for a new file will
     /// be created and the row data will be appended there.
-    async fn append_row(&mut self, row: &Row) -> Result<(), anyhow::Error> {
+    async fn append_pgcopy_row(&mut self, row: &Row) -> Result<(), anyhow::Error> {
+        let S3SinkFormat::PgCopy(format) = &self.format else {
+            unreachable!("only called for pgcopy formats");
+        };
         self.buf.clear();
         // encode the row and write to temp buffer.
-        encode_copy_format(self.format.clone(), row, self.desc.typ(), &mut self.buf)
+        encode_copy_format(format.clone(), row, self.desc.typ(), &mut self.buf)
             .map_err(|_| anyhow!("error encoding row"))?;
 
         if self.current_file_uploader.is_none() {
@@ -310,6 +369,53 @@ impl CopyToS3Uploader {
 
         Ok(())
     }
+
+    /// Buffers the row for the next parquet row group, which is written once
+    /// the buffered rows reach the row group size.
+    async fn append_parquet_row(&mut self, row: &Row) -> Result<(), anyhow::Error> {
+        let row_group_size = self.row_group_size();
+        let builder = self
+            .arrow_builder
+            .as_mut()
+            .expect("parquet uploads have an arrow builder");
+        builder.push_row(row)?;
+        if builder.bytes() >= row_group_size {
+            self.write_row_group().await?;
+        }
+        Ok(())
+    }
+
+    /// The approximate size of each parquet row group.
+    fn row_group_size(&self) -> u64 {
+        std::cmp::min(PARQUET_ROW_GROUP_SIZE.as_u64(), self.max_file_size)
+    }
+
+    /// Writes the buffered rows as a parquet row group. If the row group would
+    /// make the current file exceed the max file size, the current file is
+    /// finished and the row group is written to a new file instead.
+    async fn write_row_group(&mut self) -> Result<(), anyhow::Error> {
+        let builder = self
+            .arrow_builder
+            .as_mut()
+            .expect("parquet uploads have an arrow builder");
+        let estimated_size = builder.bytes();
+        let chunk = builder.finish();
+
+        let exceeds_max_file_size = self.parquet_writer.as_ref().is_some_and(|w| {
+            w.bytes_written() > 0 && w.bytes_written() + estimated_size > self.max_file_size
+        });
+        if self.current_file_uploader.is_none() || exceeds_max_file_size {
+            self.start_new_file_upload().await?;
+        }
+
+        self.buf = self
+            .parquet_writer
+            .as_mut()
+            .expect("parquet writer for the current file")
+            .write_row_group(chunk)?;
+        self.upload_buffer().await?;
+        Ok(())
+    }
 }
 
 /// On CI, these tests are enabled by adding the scratch-aws-access plugin
@@ -328,7 +434,9 @@ impl CopyToS3Uploader {
 /// to run `aws sso login` if you haven't recently.
 #[cfg(test)]
 mod tests {
+    use arrow2::io::parquet::read::read_metadata;
     use bytesize::ByteSize;
+    use mz_pgcopy::CopyFormatParams;
     use mz_repr::{ColumnName, ColumnType, Datum, RelationType};
     use uuid::Uuid;
 
@@ -372,7 +480,7 @@ mod tests {
                 // this is only for testing, users will not be able to set value smaller than 16MB.
                 max_file_size: ByteSize::b(6).as_u64(),
                 desc,
-                format: CopyFormatParams::Csv(Default::default()),
+                format: S3SinkFormat::PgCopy(CopyFormatParams::Csv(Default::default())),
             },
             "part".to_string(),
         );
@@ -417,6 +525,60 @@ mod tests {
         let expected_body: &[u8] = b"\n5678\n";
         assert_eq!(body, *expected_body);
 
+        Ok(())
+    }
+    #[mz_ore::test(tokio::test(flavor = "multi_thread"))]
+    #[cfg_attr(coverage, ignore)] // https://github.com/MaterializeInc/materialize/issues/18898
+    #[cfg_attr(miri, ignore)] // error: unsupported operation: can't call foreign function `TLS_method` on OS `linux`
+    async fn test_multiple_parquet_files() -> Result<(), anyhow::Error> {
+        let sdk_config = mz_aws_util::defaults().load().await;
+        let (bucket, path) = match s3_bucket_path_for_test() {
+            Some(tuple) => tuple,
+            None => return Ok(()),
+        };
+        let typ: RelationType = RelationType::new(vec![ColumnType {
+            scalar_type: mz_repr::ScalarType::String,
+            nullable: true,
+        }]);
+        let column_names = vec![ColumnName::from("col1")];
+        let desc = RelationDesc::new(typ, column_names.into_iter());
+        let mut uploader = CopyToS3Uploader::new(
+            sdk_config.clone(),
+            S3UploadInfo {
+                prefix: format!("s3://{}/{}", bucket, path),
+                // this is only for testing, users will not be able to set value smaller than 16MB.
+                max_file_size: ByteSize::b(6).as_u64(),
+                desc,
+                format: S3SinkFormat::Parquet,
+            },
+            "part".to_string(),
+        );
+        let mut row = Row::default();
+        // Each row exceeds the max_file_size, so each is written as its own
+        // row group, and each row group is written to its own file.
+        row.packer().push(Datum::from("1234567"));
+        uploader.append_row(&row).await?;
+
+        row.packer().push(Datum::Null);
+        uploader.append_row(&row).await?;
+
+        uploader.flush().await?;
+
+        let s3_client = mz_aws_util::s3::new_client(&sdk_config);
+        for file in ["part-0001.parquet", "part-0002.parquet"] {
+            let object = s3_client
+                .get_object()
+                .bucket(bucket.clone())
+                .key(format!("{}/{}", path, file))
+                .send()
+                .await
+                .unwrap();
+            let body = object.body.collect().await.unwrap().into_bytes();
+            let metadata = read_metadata(&mut std::io::Cursor::new(body))?;
+            assert_eq!(metadata.num_rows, 1);
+            assert_eq!(metadata.row_groups.len(), 1);
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,787 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Parquet encoding of rows for the COPY TO S3 oneshot sink.
+//!
+//! Rows are first buffered into Arrow arrays by an [`ArrowBuilder`], which are
+//! then written out as a single Parquet row group by a [`ParquetFileWriter`].
+
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+use anyhow::{anyhow, bail};
+use arrow2::array::{
+    Array, ListArray, MutableArray, MutableBinaryArray, MutableBooleanArray,
+    MutableFixedSizeBinaryArray, MutablePrimitiveArray, MutableUtf8Array, StructArray,
+};
+use arrow2::bitmap::MutableBitmap;
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow2::io::parquet::write::{
+    transverse, CompressionOptions, Encoding, FileWriter, RowGroupIterator, Version, WriteOptions,
+};
+use arrow2::offset::Offsets;
+use chrono::Timelike;
+use mz_ore::cast::CastFrom;
+use mz_repr::adt::jsonb::JsonbRef;
+use mz_repr::adt::numeric::{self, Numeric, NumericMaxScale};
+use mz_repr::{ColumnName, Datum, RelationDesc, Row, ScalarType};
+
+/// The maximum precision of the decimals we write, which is bounded by the
+/// 128-bit Arrow decimal representation.
+const DECIMAL_MAX_PRECISION: u8 = 38;
+
+/// Returns the Arrow schema used to write rows described by `desc`.
+pub(crate) fn desc_to_schema(desc: &RelationDesc) -> Schema {
+    let fields = desc
+        .iter()
+        .map(|(name, typ)| {
+            Field::new(
+                name.as_str(),
+                scalar_to_arrow(&typ.scalar_type),
+                typ.nullable,
+            )
+        })
+        .collect::<Vec<_>>();
+    Schema::from(fields)
+}
+
+/// Maps a [`ScalarType`] to the Arrow type it is written as.
+///
+/// Types without a faithful Arrow representation are written as their text
+/// representation.
+fn scalar_to_arrow(typ: &ScalarType) -> DataType {
+    match typ {
+        ScalarType::Bool => DataType::Boolean,
+        ScalarType::PgLegacyChar => DataType::UInt8,
+        ScalarType::Int16 => DataType::Int16,
+        ScalarType::Int32 => DataType::Int32,
+        ScalarType::Int64 => DataType::Int64,
+        ScalarType::UInt16 => DataType::UInt16,
+        ScalarType::UInt32
+        | ScalarType::Oid
+        | ScalarType::RegClass
+        | ScalarType::RegProc
+        | ScalarType::RegType => DataType::UInt32,
+        ScalarType::UInt64 | ScalarType::MzTimestamp => DataType::UInt64,
+        ScalarType::Float32 => DataType::Float32,
+        ScalarType::Float64 => DataType::Float64,
+        // Arrow decimals have a fixed scale, so numerics without a declared
+        // scale cannot be represented losslessly.
+        ScalarType::Numeric {
+            max_scale: Some(max_scale),
+        } => DataType::Decimal(
+            usize::from(DECIMAL_MAX_PRECISION),
+            usize::from(max_scale.into_u8()),
+        ),
+        ScalarType::Numeric { max_scale: None } => DataType::Utf8,
+        ScalarType::Date => DataType::Date32,
+        ScalarType::Time => DataType::Time64(TimeUnit::Microsecond),
+        ScalarType::Timestamp { .. } => DataType::Timestamp(TimeUnit::Microsecond, None),
+        ScalarType::TimestampTz { .. } => {
+            DataType::Timestamp(TimeUnit::Microsecond, Some("+00:00".into()))
+        }
+        // Parquet's INTERVAL type only has millisecond precision, so we write
+        // intervals as their individual components instead.
+        ScalarType::Interval => DataType::Struct(vec![
+            Field::new("months", DataType::Int32, true),
+            Field::new("days", DataType::Int32, true),
+            Field::new("micros", DataType::Int64, true),
+        ]),
+        ScalarType::Bytes => DataType::Binary,
+        ScalarType::String
+        | ScalarType::Char { .. }
+        | ScalarType::VarChar { .. }
+        | ScalarType::PgLegacyName
+        | ScalarType::Jsonb
+        | ScalarType::Range { .. }
+        | ScalarType::AclItem
+        | ScalarType::MzAclItem
+        | ScalarType::Enum { .. }
+        | ScalarType::Inet
+        | ScalarType::Cidr
+        | ScalarType::MacAddr => DataType::Utf8,
+        ScalarType::Uuid => DataType::FixedSizeBinary(16),
+        ScalarType::Array(element_type) | ScalarType::List { element_type, .. } => DataType::List(
+            Box::new(Field::new("item", scalar_to_arrow(element_type), true)),
+        ),
+        ScalarType::Int2Vector => {
+            DataType::List(Box::new(Field::new("item", DataType::Int16, true)))
+        }
+        ScalarType::Record { fields, .. } => DataType::Struct(
+            fields
+                .iter()
+                .map(|(name, typ)| {
+                    Field::new(name.as_str(), scalar_to_arrow(&typ.scalar_type), true)
+                })
+                .collect(),
+        ),
+        // This is the physical layout Parquet uses for its MAP type.
+        ScalarType::Map { value_type, .. } => DataType::List(Box::new(Field::new(
+            "entries",
+            scalar_to_arrow(&map_entry_type(value_type)),
+            false,
+        ))),
+    }
+}
+
+/// Returns the type of the entries of a map, which is written as a list of
+/// key-value records.
+fn map_entry_type(value_type: &ScalarType) -> ScalarType {
+    ScalarType::Record {
+        fields: vec![
+            (ColumnName::from("key"), ScalarType::String.nullable(false)),
+            (ColumnName::from("value"), value_type.clone().nullable(true)),
+        ],
+        custom_id: None,
+    }
+}
+
+/// Buffers rows into Arrow arrays until they are written out as a row group.
+pub(crate) struct ArrowBuilder {
+    columns: Vec<ArrowColumn>,
+    /// The number of rows buffered.
+    len: usize,
+    /// The size of the buffered rows, used to estimate the size of the row
+    /// group they will be written as.
+    bytes: u64,
+}
+
+impl ArrowBuilder {
+    pub fn new(desc: &RelationDesc) -> Self {
+        ArrowBuilder {
+            columns: desc
+                .iter_types()
+                .map(|typ| ArrowColumn::new(&typ.scalar_type))
+                .collect(),
+            len: 0,
+            bytes: 0,
+        }
+    }
+
+    /// Appends `row` to the buffered arrays.
+    pub fn push_row(&mut self, row: &Row) -> Result<(), anyhow::Error> {
+        for (column, datum) in self.columns.iter_mut().zip(row.iter()) {
+            column.push(datum)?;
+        }
+        self.len += 1;
+        self.bytes += u64::cast_from(row.byte_len());
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the approximate size of the buffered rows.
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Returns the buffered arrays, leaving the builder empty.
+    pub fn finish(&mut self) -> Chunk<Box<dyn Array>> {
+        self.len = 0;
+        self.bytes = 0;
+        Chunk::new(self.columns.iter_mut().map(|c| c.finish()).collect())
+    }
+}
+
+/// Accumulates the datums of a single column into an Arrow array.
+struct ArrowColumn {
+    /// The type of the datums pushed into this column.
+    typ: ScalarType,
+    builder: ColumnBuilder,
+}
+
+enum ColumnBuilder {
+    Boolean(MutableBooleanArray),
+    UInt8(MutablePrimitiveArray<u8>),
+    Int16(MutablePrimitiveArray<i16>),
+    Int32(MutablePrimitiveArray<i32>),
+    Int64(MutablePrimitiveArray<i64>),
+    UInt16(MutablePrimitiveArray<u16>),
+    UInt32(MutablePrimitiveArray<u32>),
+    UInt64(MutablePrimitiveArray<u64>),
+    Float32(MutablePrimitiveArray<f32>),
+    Float64(MutablePrimitiveArray<f64>),
+    Decimal(MutablePrimitiveArray<i128>),
+    Utf8(MutableUtf8Array<i32>),
+    Binary(MutableBinaryArray<i32>),
+    FixedSizeBinary(MutableFixedSizeBinaryArray),
+    List {
+        data_type: DataType,
+        offsets: Offsets<i32>,
+        validity: MutableBitmap,
+        values: Box<ArrowColumn>,
+    },
+    Struct {
+        data_type: DataType,
+        validity: MutableBitmap,
+        fields: Vec<ArrowColumn>,
+    },
+}
+
+impl ArrowColumn {
+    fn new(typ: &ScalarType) -> Self {
+        let data_type = scalar_to_arrow(typ);
+        let builder = match typ {
+            ScalarType::Interval => ColumnBuilder::Struct {
+                data_type,
+                validity: MutableBitmap::new(),
+                fields: vec![
+                    ArrowColumn::new(&ScalarType::Int32),
+                    ArrowColumn::new(&ScalarType::Int32),
+                    ArrowColumn::new(&ScalarType::Int64),
+                ],
+            },
+            ScalarType::Record { fields, .. } => ColumnBuilder::Struct {
+                data_type,
+                validity: MutableBitmap::new(),
+                fields: fields
+                    .iter()
+                    .map(|(_, typ)| ArrowColumn::new(&typ.scalar_type))
+                    .collect(),
+            },
+            ScalarType::Array(element_type) | ScalarType::List { element_type, .. } => {
+                ColumnBuilder::List {
+                    data_type,
+                    offsets: Offsets::new(),
+                    validity: MutableBitmap::new(),
+                    values: Box::new(ArrowColumn::new(element_type)),
+                }
+            }
+            ScalarType::Int2Vector => ColumnBuilder::List {
+                data_type,
+                offsets: Offsets::new(),
+                validity: MutableBitmap::new(),
+                values: Box::new(ArrowColumn::new(&ScalarType::Int16)),
+            },
+            ScalarType::Map { value_type, .. } => ColumnBuilder::List {
+                data_type,
+                offsets: Offsets::new(),
+                validity: MutableBitmap::new(),
+                values: Box::new(ArrowColumn::new(&map_entry_type(value_type))),
+            },
+            _ => match data_type {
+                DataType::Boolean => ColumnBuilder::Boolean(MutableBooleanArray::new()),
+                DataType::UInt8 => ColumnBuilder::UInt8(MutablePrimitiveArray::new()),
+                DataType::Int16 => ColumnBuilder::Int16(MutablePrimitiveArray::new()),
+                DataType::Int32 | DataType::Date32 => {
+                    ColumnBuilder::Int32(MutablePrimitiveArray::new().to(data_type))
+                }
+                DataType::Int64 | DataType::Time64(_) | DataType::Timestamp(..) => {
+                    ColumnBuilder::Int64(MutablePrimitiveArray::new().to(data_type))
+                }
+                DataType::UInt16 => ColumnBuilder::UInt16(MutablePrimitiveArray::new()),
+                DataType::UInt32 => ColumnBuilder::UInt32(MutablePrimitiveArray::new()),
+                DataType::UInt64 => ColumnBuilder::UInt64(MutablePrimitiveArray::new()),
+                DataType::Float32 => ColumnBuilder::Float32(MutablePrimitiveArray::new()),
+                DataType::Float64 => ColumnBuilder::Float64(MutablePrimitiveArray::new()),
+                DataType::Decimal(..) => {
+                    ColumnBuilder::Decimal(MutablePrimitiveArray::new().to(data_type))
+                }
+                DataType::Utf8 => ColumnBuilder::Utf8(MutableUtf8Array::new()),
+                DataType::Binary => ColumnBuilder::Binary(MutableBinaryArray::new()),
+                DataType::FixedSizeBinary(size) => {
+                    ColumnBuilder::FixedSizeBinary(MutableFixedSizeBinaryArray::new(size))
+                }
+                data_type => unreachable!("{typ:?} is not written as {data_type:?}"),
+            },
+        };
+        ArrowColumn {
+            typ: typ.clone(),
+            builder,
+        }
+    }
+
+    fn push(&mut self, datum: Datum) -> Result<(), anyhow::Error> {
+        if datum.is_null() {
+            self.push_null();
+            return Ok(());
+        }
+        match (&self.typ, &mut self.builder) {
+            (ScalarType::Bool, ColumnBuilder::Boolean(b)) => b.push(Some(datum.unwrap_bool())),
+            (ScalarType::PgLegacyChar, ColumnBuilder::UInt8(b)) => {
+                b.push(Some(datum.unwrap_uint8()))
+            }
+            (ScalarType::Int16, ColumnBuilder::Int16(b)) => b.push(Some(datum.unwrap_int16())),
+            (ScalarType::Int32, ColumnBuilder::Int32(b)) => b.push(Some(datum.unwrap_int32())),
+            (ScalarType::Int64, ColumnBuilder::Int64(b)) => b.push(Some(datum.unwrap_int64())),
+            (ScalarType::UInt16, ColumnBuilder::UInt16(b)) => b.push(Some(datum.unwrap_uint16())),
+            (
+                ScalarType::UInt32
+                | ScalarType::Oid
+                | ScalarType::RegClass
+                | ScalarType::RegProc
+                | ScalarType::RegType,
+                ColumnBuilder::UInt32(b),
+            ) => b.push(Some(datum.unwrap_uint32())),
+            (ScalarType::UInt64, ColumnBuilder::UInt64(b)) => b.push(Some(datum.unwrap_uint64())),
+            (ScalarType::MzTimestamp, ColumnBuilder::UInt64(b)) => {
+                b.push(Some(u64::from(datum.unwrap_mz_timestamp())))
+            }
+            (ScalarType::Float32, ColumnBuilder::Float32(b)) => {
+                b.push(Some(datum.unwrap_float32()))
+            }
+            (ScalarType::Float64, ColumnBuilder::Float64(b)) => {
+                b.push(Some(datum.unwrap_float64()))
+            }
+            (
+                ScalarType::Numeric {
+                    max_scale: Some(max_scale),
+                },
+                ColumnBuilder::Decimal(b),
+            ) => b.push(Some(numeric_to_decimal(
+                datum.unwrap_numeric().0,
+                *max_scale,
+            )?)),
+            (ScalarType::Numeric { max_scale: None }, ColumnBuilder::Utf8(b)) => {
+                b.push(Some(datum.unwrap_numeric().0.to_standard_notation_string()))
+            }
+            (ScalarType::Date, ColumnBuilder::Int32(b)) => {
+                b.push(Some(datum.unwrap_date().unix_epoch_days()))
+            }
+            (ScalarType::Time, ColumnBuilder::Int64(b)) => {
+                let time = datum.unwrap_time();
+                b.push(Some(
+                    i64::from(time.num_seconds_from_midnight()) * 1_000_000
+                        + i64::from(time.nanosecond()) / 1_000,
+                ))
+            }
+            (ScalarType::Timestamp { .. }, ColumnBuilder::Int64(b)) => {
+                b.push(Some(datum.unwrap_timestamp().to_naive().timestamp_micros()))
+            }
+            (ScalarType::TimestampTz { .. }, ColumnBuilder::Int64(b)) => b.push(Some(
+                datum.unwrap_timestamptz().to_naive().timestamp_micros(),
+            )),
+            (
+                ScalarType::Interval,
+                ColumnBuilder::Struct {
+                    validity, fields, ..
+                },
+            ) => {
+                let iv = datum.unwrap_interval();
+                validity.push(true);
+                fields[0].push(Datum::Int32(iv.months))?;
+                fields[1].push(Datum::Int32(iv.days))?;
+                fields[2].push(Datum::Int64(iv.micros))?;
+            }
+            (ScalarType::Bytes, ColumnBuilder::Binary(b)) => b.push(Some(datum.unwrap_bytes())),
+            (
+                ScalarType::String | ScalarType::VarChar { .. } | ScalarType::PgLegacyName,
+                ColumnBuilder::Utf8(b),
+            ) => b.push(Some(datum.unwrap_str())),
+            (ScalarType::Char { length }, ColumnBuilder::Utf8(b)) => b.push(Some(
+                mz_repr::adt::char::format_str_pad(datum.unwrap_str(), *length),
+            )),
+            (ScalarType::Jsonb, ColumnBuilder::Utf8(b)) => {
+                b.push(Some(JsonbRef::from_datum(datum).to_string()))
+            }
+            (ScalarType::Range { .. }, ColumnBuilder::Utf8(b)) => {
+                b.push(Some(datum.unwrap_range().to_string()))
+            }
+            (ScalarType::AclItem, ColumnBuilder::Utf8(b)) => {
+                b.push(Some(datum.unwrap_acl_item().to_string()))
+            }
+            (ScalarType::MzAclItem, ColumnBuilder::Utf8(b)) => {
+                b.push(Some(datum.unwrap_mz_acl_item().to_string()))
+            }
+            (ScalarType::Enum { labels, .. }, ColumnBuilder::Utf8(b)) => {
+                b.push(Some(&labels[usize::cast_from(datum.unwrap_uint32())]))
+            }
+            (ScalarType::Inet, ColumnBuilder::Utf8(b)) => {
+                b.push(Some(datum.unwrap_inet().to_string()))
+            }
+            (ScalarType::Cidr, ColumnBuilder::Utf8(b)) => {
+                b.push(Some(datum.unwrap_inet().to_cidr_string()))
+            }
+            (ScalarType::MacAddr, ColumnBuilder::Utf8(b)) => {
+                b.push(Some(datum.unwrap_macaddr().to_string()))
+            }
+            (ScalarType::Uuid, ColumnBuilder::FixedSizeBinary(b)) => {
+                b.push(Some(datum.unwrap_uuid().as_bytes()))
+            }
+            (
+                ScalarType::Array(_) | ScalarType::Int2Vector,
+                ColumnBuilder::List {
+                    offsets,
+                    validity,
+                    values,
+                    ..
+                },
+            ) => {
+                let array = datum.unwrap_array();
+                if array.dims().ndims() > 1 {
+                    bail!("multi-dimensional arrays are not supported in parquet");
+                }
+                let mut len = 0;
+                for datum in array.elements().iter() {
+                    values.push(datum)?;
+                    len += 1;
+                }
+                offsets.try_push_usize(len)?;
+                validity.push(true);
+            }
+            (
+                ScalarType::List { .. },
+                ColumnBuilder::List {
+                    offsets,
+                    validity,
+                    values,
+                    ..
+                },
+            ) => {
+                let mut len = 0;
+                for datum in datum.unwrap_list().iter() {
+                    values.push(datum)?;
+                    len += 1;
+                }
+                offsets.try_push_usize(len)?;
+                validity.push(true);
+            }
+            (
+                ScalarType::Map { .. },
+                ColumnBuilder::List {
+                    offsets,
+                    validity,
+                    values,
+                    ..
+                },
+            ) => {
+                let ColumnBuilder::Struct {
+                    validity: entry_validity,
+                    fields: entry_fields,
+                    ..
+                } = &mut values.builder
+                else {
+                    unreachable!("map entries are written as structs")
+                };
+                let mut len = 0;
+                for (key, value) in datum.unwrap_map().iter() {
+                    entry_validity.push(true);
+                    entry_fields[0].push(Datum::String(key))?;
+                    entry_fields[1].push(value)?;
+                    len += 1;
+                }
+                offsets.try_push_usize(len)?;
+                validity.push(true);
+            }
+            (
+                ScalarType::Record { .. },
+                ColumnBuilder::Struct {
+                    validity, fields, ..
+                },
+            ) => {
+                for (field, datum) in fields.iter_mut().zip(datum.unwrap_list().iter()) {
+                    field.push(datum)?;
+                }
+                validity.push(true);
+            }
+            (typ, _) => unreachable!("mismatched arrow builder for {typ:?}"),
+        }
+        Ok(())
+    }
+
+    fn push_null(&mut self) {
+        match &mut self.builder {
+            ColumnBuilder::Boolean(b) => b.push(None),
+            ColumnBuilder::UInt8(b) => b.push(None),
+            ColumnBuilder::Int16(b) => b.push(None),
+            ColumnBuilder::Int32(b) => b.push(None),
+            ColumnBuilder::Int64(b) => b.push(None),
+            ColumnBuilder::UInt16(b) => b.push(None),
+            ColumnBuilder::UInt32(b) => b.push(None),
+            ColumnBuilder::UInt64(b) => b.push(None),
+            ColumnBuilder::Float32(b) => b.push(None),
+            ColumnBuilder::Float64(b) => b.push(None),
+            ColumnBuilder::Decimal(b) => b.push(None),
+            ColumnBuilder::Utf8(b) => b.push::<&str>(None),
+            ColumnBuilder::Binary(b) => b.push::<&[u8]>(None),
+            ColumnBuilder::FixedSizeBinary(b) => b.push::<&[u8]>(None),
+            ColumnBuilder::List {
+                offsets, validity, ..
+            } => {
+                offsets.extend_constant(1);
+                validity.push(false);
+            }
+            // Struct children must have the same length as the struct itself,
+            // so push a null into each of them as well.
+            ColumnBuilder::Struct {
+                validity, fields, ..
+            } => {
+                for field in fields {
+                    field.push_null();
+                }
+                validity.push(false);
+            }
+        }
+    }
+
+    /// Returns the accumulated array, leaving the column empty.
+    fn finish(&mut self) -> Box<dyn Array> {
+        match &mut self.builder {
+            ColumnBuilder::Boolean(b) => b.as_box(),
+            ColumnBuilder::UInt8(b) => b.as_box(),
+            ColumnBuilder::Int16(b) => b.as_box(),
+            ColumnBuilder::Int32(b) => b.as_box(),
+            ColumnBuilder::Int64(b) => b.as_box(),
+            ColumnBuilder::UInt16(b) => b.as_box(),
+            ColumnBuilder::UInt32(b) => b.as_box(),
+            ColumnBuilder::UInt64(b) => b.as_box(),
+            ColumnBuilder::Float32(b) => b.as_box(),
+            ColumnBuilder::Float64(b) => b.as_box(),
+            ColumnBuilder::Decimal(b) => b.as_box(),
+            ColumnBuilder::Utf8(b) => b.as_box(),
+            ColumnBuilder::Binary(b) => b.as_box(),
+            ColumnBuilder::FixedSizeBinary(b) => b.as_box(),
+            ColumnBuilder::List {
+                data_type,
+                offsets,
+                validity,
+                values,
+            } => ListArray::<i32>::new(
+                data_type.clone(),
+                std::mem::replace(offsets, Offsets::new()).into(),
+                values.finish(),
+                std::mem::take(validity).into(),
+            )
+            .boxed(),
+            ColumnBuilder::Struct {
+                data_type,
+                validity,
+                fields,
+            } => StructArray::new(
+                data_type.clone(),
+                fields.iter_mut().map(|f| f.finish()).collect(),
+                std::mem::take(validity).into(),
+            )
+            .boxed(),
+        }
+    }
+}
+
+/// Converts `n` into the unscaled representation of an Arrow decimal with
+/// scale `max_scale`.
+fn numeric_to_decimal(mut n: Numeric, max_scale: NumericMaxScale) -> Result<i128, anyhow::Error> {
+    if n.is_special() {
+        bail!("cannot write numeric value {} to parquet", n);
+    }
+    let scale = max_scale.into_u8();
+    numeric::rescale(&mut n, scale)?;
+    if numeric::get_precision(&n) > u32::from(DECIMAL_MAX_PRECISION) {
+        bail!(
+            "numeric value {} exceeds the maximum parquet decimal precision {}",
+            n,
+            DECIMAL_MAX_PRECISION
+        );
+    }
+    // Shift the decimal point so that `n` is its own unscaled coefficient.
+    numeric::cx_datum().scaleb(&mut n, &Numeric::from(i32::from(scale)));
+    i128::try_from(n).map_err(|_| anyhow!("numeric value {} does not fit in a decimal", n))
+}
+
+/// A [`Write`] implementation whose contents can be drained while the
+/// [`FileWriter`] that owns it is still in use.
+#[derive(Clone, Default)]
+struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn take(&self) -> Vec<u8> {
+        std::mem::take(&mut *self.0.borrow_mut())
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Writes a single Parquet file, one row group at a time.
+///
+/// The encoded bytes are handed back to the caller as they are produced, so
+/// that they can be uploaded without buffering the whole file in memory.
+pub(crate) struct ParquetFileWriter {
+    schema: Schema,
+    options: WriteOptions,
+    writer: FileWriter<SharedBuffer>,
+    buffer: SharedBuffer,
+    /// The number of bytes written to the file so far.
+    bytes_written: u64,
+}
+
+impl ParquetFileWriter {
+    pub fn new(schema: Schema) -> Result<Self, anyhow::Error> {
+        let options = WriteOptions {
+            write_statistics: true,
+            compression: CompressionOptions::Uncompressed,
+            version: Version::V2,
+            data_pagesize_limit: None, // use default limit
+        };
+        let buffer = SharedBuffer::default();
+        let writer = FileWriter::try_new(buffer.clone(), schema.clone(), options)?;
+        Ok(ParquetFileWriter {
+            schema,
+            options,
+            writer,
+            buffer,
+            bytes_written: 0,
+        })
+    }
+
+    /// Returns the number of bytes written to the file so far.
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    /// Writes `chunk` as a new row group, returning the encoded bytes.
+    pub fn write_row_group(
+        &mut self,
+        chunk: Chunk<Box<dyn Array>>,
+    ) -> Result<Vec<u8>, anyhow::Error> {
+        let encodings = self
+            .schema
+            .fields
+            .iter()
+            .map(|f| transverse(&f.data_type, |_| Encoding::Plain))
+            .collect();
+        let row_groups = RowGroupIterator::try_new(
+            std::iter::once(Ok(chunk)),
+            &self.schema,
+            self.options,
+            encodings,
+        )?;
+        for group in row_groups {
+            self.writer.write(group?)?;
+        }
+        Ok(self.take_buffer())
+    }
+
+    /// Writes the file footer, returning the remaining encoded bytes.
+    pub fn finish(mut self) -> Result<Vec<u8>, anyhow::Error> {
+        self.writer.end(None)?;
+        Ok(self.take_buffer())
+    }
+
+    fn take_buffer(&mut self) -> Vec<u8> {
+        let buf = self.buffer.take();
+        self.bytes_written += u64::cast_from(buf.len());
+        buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use arrow2::array::{Int32Array, PrimitiveArray, Utf8Array};
+    use arrow2::io::parquet::read::{infer_schema, read_metadata, FileReader};
+    use mz_repr::adt::interval::Interval;
+    use mz_repr::{ColumnType, RelationType};
+
+    use super::*;
+
+    #[mz_ore::test]
+    #[cfg_attr(miri, ignore)] // unsupported operation: can't call foreign function
+    fn test_roundtrip() -> Result<(), anyhow::Error> {
+        let desc = RelationDesc::new(
+            RelationType::new(vec![
+                ScalarType::Int32.nullable(true),
+                ScalarType::Numeric {
+                    max_scale: Some(NumericMaxScale::try_from(2i64)?),
+                }
+                .nullable(false),
+                ScalarType::Interval.nullable(false),
+                ScalarType::List {
+                    element_type: Box::new(ScalarType::String),
+                    custom_id: None,
+                }
+                .nullable(false),
+                ColumnType {
+                    scalar_type: ScalarType::Map {
+                        value_type: Box::new(ScalarType::Int64),
+                        custom_id: None,
+                    },
+                    nullable: false,
+                },
+            ]),
+            ["a", "b", "c", "d", "e"].into_iter().map(ColumnName::from),
+        );
+
+        let mut builder = ArrowBuilder::new(&desc);
+        let mut row = Row::default();
+        let mut packer = row.packer();
+        packer.push(Datum::Int32(1));
+        packer.push(Datum::from(Numeric::from(1.5f64)));
+        packer.push(Datum::Interval(Interval::new(1, 2, 3)));
+        packer.push_list([Datum::String("x"), Datum::Null]);
+        packer.push_dict([("k", Datum::Int64(7))]);
+        builder.push_row(&row)?;
+        let mut packer = row.packer();
+        packer.push(Datum::Null);
+        packer.push(Datum::from(Numeric::from(-2i32)));
+        packer.push(Datum::Interval(Interval::new(0, 0, 0)));
+        packer.push_list(std::iter::empty::<Datum>());
+        packer.push_dict(std::iter::empty::<(&str, Datum)>());
+        builder.push_row(&row)?;
+        assert_eq!(builder.len(), 2);
+
+        let mut writer = ParquetFileWriter::new(desc_to_schema(&desc))?;
+        let mut file = writer.write_row_group(builder.finish())?;
+        assert!(builder.is_empty());
+        file.extend(writer.finish()?);
+
+        let mut reader = Cursor::new(file);
+        let metadata = read_metadata(&mut reader)?;
+        assert_eq!(metadata.row_groups.len(), 1);
+        let schema = infer_schema(&metadata)?;
+        assert_eq!(schema.fields.len(), 5);
+        let chunk = FileReader::new(reader, metadata.row_groups, schema, None, None, None)
+            .next()
+            .expect("one chunk")?;
+        assert_eq!(chunk.len(), 2);
+
+        let a = chunk.arrays()[0]
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .expect("int32");
+        assert_eq!(a, &Int32Array::from([Some(1), None]));
+        let b = chunk.arrays()[1]
+            .as_any()
+            .downcast_ref::<PrimitiveArray<i128>>()
+            .expect("decimal");
+        assert_eq!((b.value(0), b.value(1)), (150, -200));
+        let c = chunk.arrays()[2]
+            .as_any()
+            .downcast_ref::<StructArray>()
+            .expect("struct");
+        assert_eq!(c.values().len(), 3);
+        let d = chunk.arrays()[3]
+            .as_any()
+            .downcast_ref::<ListArray<i32>>()
+            .expect("list");
+        let items = d
+            .values()
+            .as_any()
+            .downcast_ref::<Utf8Array<i32>>()
+            .expect("utf8");
+        assert_eq!(items, &Utf8Array::<i32>::from([Some("x"), None]));
+        Ok(())
+    }
+}
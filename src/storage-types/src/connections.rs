@@ -13,6 +13,7 @@ use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{anyhow, Context};
 use itertools::Itertools;
@@ -196,6 +197,9 @@ pub enum Connection<C: ConnectionAccess = InlinedConnection> {
     Aws(AwsConnection),
     AwsPrivatelink(AwsPrivatelinkConnection),
     MySql(MySqlConnection<C>),
+    Http(HttpConnection),
+    SqlServer(SqlServerConnection<C>),
+    MongoDb(MongoDbConnection<C>),
 }
 
 impl<R: ConnectionResolver> IntoInlineConnection<Connection, R>
@@ -210,6 +214,11 @@ impl<R: ConnectionResolver> IntoInlineConnection<Connection, R>
             Connection::Aws(aws) => Connection::Aws(aws),
             Connection::AwsPrivatelink(awspl) => Connection::AwsPrivatelink(awspl),
             Connection::MySql(mysql) => Connection::MySql(mysql.into_inline_connection(r)),
+            Connection::Http(http) => Connection::Http(http),
+            Connection::SqlServer(sql_server) => {
+                Connection::SqlServer(sql_server.into_inline_connection(r))
+            }
+            Connection::MongoDb(mongodb) => Connection::MongoDb(mongodb.into_inline_connection(r)),
         }
     }
 }
@@ -225,6 +234,9 @@ impl<C: ConnectionAccess> Connection<C> {
             Connection::Aws(conn) => conn.validate_by_default(),
             Connection::AwsPrivatelink(conn) => conn.validate_by_default(),
             Connection::MySql(conn) => conn.validate_by_default(),
+            Connection::Http(conn) => conn.validate_by_default(),
+            Connection::SqlServer(conn) => conn.validate_by_default(),
+            Connection::MongoDb(conn) => conn.validate_by_default(),
         }
     }
 }
@@ -244,6 +256,9 @@ impl Connection<InlinedConnection> {
             Connection::Aws(conn) => conn.validate(id, storage_configuration).await?,
             Connection::AwsPrivatelink(conn) => conn.validate(id, storage_configuration).await?,
             Connection::MySql(conn) => conn.validate(id, storage_configuration).await?,
+            Connection::Http(conn) => conn.validate(id, storage_configuration).await?,
+            Connection::SqlServer(conn) => conn.validate(id, storage_configuration).await?,
+            Connection::MongoDb(conn) => conn.validate(id, storage_configuration).await?,
         }
         Ok(())
     }
@@ -269,6 +284,20 @@ impl Connection<InlinedConnection> {
         }
     }
 
+    pub fn unwrap_sql_server(self) -> <InlinedConnection as ConnectionAccess>::SqlServer {
+        match self {
+            Self::SqlServer(conn) => conn,
+            o => unreachable!("{o:?} is not a SQL Server connection"),
+        }
+    }
+
+    pub fn unwrap_mongodb(self) -> <InlinedConnection as ConnectionAccess>::MongoDb {
+        match self {
+            Self::MongoDb(conn) => conn,
+            o => unreachable!("{o:?} is not a MongoDB connection"),
+        }
+    }
+
     pub fn unwrap_ssh(self) -> <InlinedConnection as ConnectionAccess>::Ssh {
         match self {
             Self::Ssh(conn) => conn,
@@ -282,6 +311,20 @@ impl Connection<InlinedConnection> {
             o => unreachable!("{o:?} is not a Kafka connection"),
         }
     }
+
+    pub fn unwrap_http(self) -> <InlinedConnection as ConnectionAccess>::Http {
+        match self {
+            Self::Http(conn) => conn,
+            o => unreachable!("{o:?} is not an HTTP connection"),
+        }
+    }
+
+    pub fn unwrap_aws(self) -> <InlinedConnection as ConnectionAccess>::Aws {
+        match self {
+            Self::Aws(conn) => conn,
+            o => unreachable!("{o:?} is not an AWS connection"),
+        }
+    }
 }
 
 /// An error returned by [`Connection::validate`].
@@ -956,6 +999,77 @@ impl RustType<ProtoCsrConnection> for CsrConnection {
     }
 }
 
+/// A connection to an HTTP endpoint that accepts `POST` requests.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Arbitrary)]
+pub struct HttpConnection {
+    /// The URL to which requests are sent.
+    #[proptest(strategy = "any_url()")]
+    pub url: Url,
+    /// The ID of the secret containing the key used to sign request bodies
+    /// with HMAC-SHA256, if any.
+    pub hmac_secret: Option<GlobalId>,
+}
+
+impl HttpConnection {
+    /// Reads the HMAC signing key for this connection, if one is configured.
+    pub async fn hmac_key(
+        &self,
+        storage_configuration: &StorageConfiguration,
+    ) -> Result<Option<Vec<u8>>, anyhow::Error> {
+        match self.hmac_secret {
+            None => Ok(None),
+            Some(id) => Ok(Some(
+                storage_configuration
+                    .connection_context
+                    .secrets_reader
+                    .read(id)
+                    .await?,
+            )),
+        }
+    }
+
+    async fn validate(
+        &self,
+        _id: GlobalId,
+        storage_configuration: &StorageConfiguration,
+    ) -> Result<(), anyhow::Error> {
+        // Make sure the secret exists before trying to reach the endpoint.
+        self.hmac_key(storage_configuration).await?;
+        // Endpoints are only expected to accept `POST` requests carrying sink
+        // data, so any HTTP response at all is taken as proof that the
+        // endpoint is reachable.
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()?;
+        client
+            .head(self.url.clone())
+            .send()
+            .await
+            .context("connecting to HTTP endpoint")?;
+        Ok(())
+    }
+
+    fn validate_by_default(&self) -> bool {
+        false
+    }
+}
+
+impl RustType<ProtoHttpConnection> for HttpConnection {
+    fn into_proto(&self) -> ProtoHttpConnection {
+        ProtoHttpConnection {
+            url: Some(self.url.into_proto()),
+            hmac_secret: self.hmac_secret.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoHttpConnection) -> Result<Self, TryFromProtoError> {
+        Ok(HttpConnection {
+            url: proto.url.into_rust_if_some("ProtoHttpConnection::url")?,
+            hmac_secret: proto.hmac_secret.into_rust()?,
+        })
+    }
+}
+
 /// A TLS key pair used for client identity.
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct TlsIdentity {
@@ -1486,6 +1600,314 @@ impl RustType<ProtoMySqlConnection> for MySqlConnection {
     }
 }
 
+/// A connection to a SQL Server database.
+///
+/// Connections are not encrypted, since TLS is not yet supported for SQL
+/// Server. Use an SSH tunnel or AWS PrivateLink to reach servers over
+/// untrusted networks.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Arbitrary)]
+pub struct SqlServerConnection<C: ConnectionAccess = InlinedConnection> {
+    /// The hostname of the server.
+    pub host: String,
+    /// The port of the server.
+    pub port: u16,
+    /// The name of the database to connect to.
+    pub database: String,
+    /// The username to authenticate as.
+    pub user: StringOrSecret,
+    /// An optional password for authentication.
+    pub password: Option<GlobalId>,
+    /// A tunnel through which to route traffic.
+    pub tunnel: Tunnel<C>,
+}
+
+impl<R: ConnectionResolver> IntoInlineConnection<SqlServerConnection, R>
+    for SqlServerConnection<ReferencedConnection>
+{
+    fn into_inline_connection(self, r: R) -> SqlServerConnection {
+        let SqlServerConnection {
+            host,
+            port,
+            database,
+            user,
+            password,
+            tunnel,
+        } = self;
+
+        SqlServerConnection {
+            host,
+            port,
+            database,
+            user,
+            password,
+            tunnel: tunnel.into_inline_connection(r),
+        }
+    }
+}
+
+impl<C: ConnectionAccess> SqlServerConnection<C> {
+    fn validate_by_default(&self) -> bool {
+        true
+    }
+}
+
+impl SqlServerConnection<InlinedConnection> {
+    pub async fn config(
+        &self,
+        secrets_reader: &dyn mz_secrets::SecretsReader,
+        storage_configuration: &StorageConfiguration,
+    ) -> Result<mz_sql_server_util::Config, anyhow::Error> {
+        let user = self.user.get_string(secrets_reader).await?;
+        let password = match self.password {
+            Some(password) => secrets_reader.read_string(password).await?,
+            None => String::new(),
+        };
+
+        let mut config = tiberius::Config::new();
+        config.database(&self.database);
+        config.application_name("materialize");
+        config.authentication(tiberius::AuthMethod::sql_server(user, password));
+        config.encryption(tiberius::EncryptionLevel::NotSupported);
+
+        let tunnel = match &self.tunnel {
+            Tunnel::Direct => mz_sql_server_util::TunnelConfig::Direct,
+            Tunnel::Ssh(SshTunnel {
+                connection_id,
+                connection,
+            }) => {
+                let secret = secrets_reader.read(*connection_id).await?;
+                let key_pair = SshKeyPair::from_bytes(&secret)?;
+                mz_sql_server_util::TunnelConfig::Ssh {
+                    config: SshTunnelConfig {
+                        host: connection.host.clone(),
+                        port: connection.port,
+                        user: connection.user.clone(),
+                        key_pair,
+                    },
+                }
+            }
+            Tunnel::AwsPrivatelink(connection) => {
+                assert!(connection.port.is_none());
+                mz_sql_server_util::TunnelConfig::AwsPrivatelink {
+                    connection_id: connection.connection_id,
+                }
+            }
+        };
+
+        Ok(mz_sql_server_util::Config::new(
+            self.host.clone(),
+            self.port,
+            config,
+            tunnel,
+            storage_configuration.parameters.ssh_timeout_config,
+        ))
+    }
+
+    async fn validate(
+        &self,
+        _id: GlobalId,
+        storage_configuration: &StorageConfiguration,
+    ) -> Result<(), anyhow::Error> {
+        let config = self
+            .config(
+                &*storage_configuration.connection_context.secrets_reader,
+                storage_configuration,
+            )
+            .await?;
+        let mut client = config
+            .connect(
+                "connection validation",
+                &storage_configuration.connection_context.ssh_tunnel_manager,
+            )
+            .await?;
+        if !mz_sql_server_util::is_cdc_enabled(&mut client).await? {
+            Err(mz_sql_server_util::SqlServerError::CdcNotEnabled(
+                self.database.clone(),
+            ))?;
+        }
+        client.close().await?;
+        Ok(())
+    }
+}
+
+impl RustType<ProtoSqlServerConnection> for SqlServerConnection {
+    fn into_proto(&self) -> ProtoSqlServerConnection {
+        ProtoSqlServerConnection {
+            host: self.host.into_proto(),
+            port: self.port.into_proto(),
+            database: self.database.into_proto(),
+            user: Some(self.user.into_proto()),
+            password: self.password.into_proto(),
+            tunnel: Some(self.tunnel.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoSqlServerConnection) -> Result<Self, TryFromProtoError> {
+        Ok(SqlServerConnection {
+            host: proto.host,
+            port: proto.port.into_rust()?,
+            database: proto.database,
+            user: proto
+                .user
+                .into_rust_if_some("ProtoSqlServerConnection::user")?,
+            password: proto.password.into_rust()?,
+            tunnel: proto
+                .tunnel
+                .into_rust_if_some("ProtoSqlServerConnection::tunnel")?,
+        })
+    }
+}
+
+/// A connection to a MongoDB deployment.
+///
+/// Connections are not encrypted, since TLS is not yet supported for MongoDB.
+/// Use an SSH tunnel or AWS PrivateLink to reach servers over untrusted
+/// networks.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, Arbitrary)]
+pub struct MongoDbConnection<C: ConnectionAccess = InlinedConnection> {
+    /// The hostname of the server.
+    pub host: String,
+    /// The port of the server.
+    pub port: u16,
+    /// The name of the database to connect to.
+    pub database: String,
+    /// The username to authenticate as.
+    pub user: StringOrSecret,
+    /// The password for authentication.
+    pub password: GlobalId,
+    /// A tunnel through which to route traffic.
+    pub tunnel: Tunnel<C>,
+}
+
+impl<R: ConnectionResolver> IntoInlineConnection<MongoDbConnection, R>
+    for MongoDbConnection<ReferencedConnection>
+{
+    fn into_inline_connection(self, r: R) -> MongoDbConnection {
+        let MongoDbConnection {
+            host,
+            port,
+            database,
+            user,
+            password,
+            tunnel,
+        } = self;
+
+        MongoDbConnection {
+            host,
+            port,
+            database,
+            user,
+            password,
+            tunnel: tunnel.into_inline_connection(r),
+        }
+    }
+}
+
+impl<C: ConnectionAccess> MongoDbConnection<C> {
+    fn validate_by_default(&self) -> bool {
+        true
+    }
+}
+
+impl MongoDbConnection<InlinedConnection> {
+    pub async fn config(
+        &self,
+        secrets_reader: &dyn mz_secrets::SecretsReader,
+        storage_configuration: &StorageConfiguration,
+    ) -> Result<mz_mongodb_util::Config, anyhow::Error> {
+        let user = self.user.get_string(secrets_reader).await?;
+        let password = secrets_reader.read_string(self.password).await?;
+
+        let tunnel = match &self.tunnel {
+            Tunnel::Direct => mz_mongodb_util::TunnelConfig::Direct,
+            Tunnel::Ssh(SshTunnel {
+                connection_id,
+                connection,
+            }) => {
+                let secret = secrets_reader.read(*connection_id).await?;
+                let key_pair = SshKeyPair::from_bytes(&secret)?;
+                mz_mongodb_util::TunnelConfig::Ssh {
+                    config: SshTunnelConfig {
+                        host: connection.host.clone(),
+                        port: connection.port,
+                        user: connection.user.clone(),
+                        key_pair,
+                    },
+                }
+            }
+            Tunnel::AwsPrivatelink(connection) => {
+                assert!(connection.port.is_none());
+                mz_mongodb_util::TunnelConfig::AwsPrivatelink {
+                    connection_id: connection.connection_id,
+                }
+            }
+        };
+
+        Ok(mz_mongodb_util::Config::new(
+            self.host.clone(),
+            self.port,
+            user,
+            password,
+            self.database.clone(),
+            tunnel,
+            storage_configuration.parameters.ssh_timeout_config,
+        ))
+    }
+
+    async fn validate(
+        &self,
+        _id: GlobalId,
+        storage_configuration: &StorageConfiguration,
+    ) -> Result<(), anyhow::Error> {
+        let config = self
+            .config(
+                &*storage_configuration.connection_context.secrets_reader,
+                storage_configuration,
+            )
+            .await?;
+        let client = config
+            .connect(
+                "connection validation",
+                &storage_configuration.connection_context.ssh_tunnel_manager,
+            )
+            .await?;
+        // Change streams are only available on replica sets and sharded
+        // clusters, which are the only deployments that report cluster times.
+        mz_mongodb_util::get_cluster_time(&client.db()).await?;
+        Ok(())
+    }
+}
+
+impl RustType<ProtoMongoDbConnection> for MongoDbConnection {
+    fn into_proto(&self) -> ProtoMongoDbConnection {
+        ProtoMongoDbConnection {
+            host: self.host.into_proto(),
+            port: self.port.into_proto(),
+            database: self.database.into_proto(),
+            user: Some(self.user.into_proto()),
+            password: Some(self.password.into_proto()),
+            tunnel: Some(self.tunnel.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoMongoDbConnection) -> Result<Self, TryFromProtoError> {
+        Ok(MongoDbConnection {
+            host: proto.host,
+            port: proto.port.into_rust()?,
+            database: proto.database,
+            user: proto
+                .user
+                .into_rust_if_some("ProtoMongoDbConnection::user")?,
+            password: proto
+                .password
+                .into_rust_if_some("ProtoMongoDbConnection::password")?,
+            tunnel: proto
+                .tunnel
+                .into_rust_if_some("ProtoMongoDbConnection::tunnel")?,
+        })
+    }
+}
+
 /// A connection to a SSH tunnel.
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct SshConnection {
@@ -88,6 +88,38 @@ pub trait ConnectionAccess:
         + Hash
         + Serialize
         + for<'a> Deserialize<'a>;
+    type SqlServer: Arbitrary
+        + Clone
+        + Debug
+        + Eq
+        + PartialEq
+        + Hash
+        + Serialize
+        + for<'a> Deserialize<'a>;
+    type MongoDb: Arbitrary
+        + Clone
+        + Debug
+        + Eq
+        + PartialEq
+        + Hash
+        + Serialize
+        + for<'a> Deserialize<'a>;
+    type Http: Arbitrary
+        + Clone
+        + Debug
+        + Eq
+        + PartialEq
+        + Hash
+        + Serialize
+        + for<'a> Deserialize<'a>;
+    type Aws: Arbitrary
+        + Clone
+        + Debug
+        + Eq
+        + PartialEq
+        + Hash
+        + Serialize
+        + for<'a> Deserialize<'a>;
 }
 
 /// Expresses that the struct contains references to connections. Use a
@@ -102,6 +134,10 @@ impl ConnectionAccess for ReferencedConnection {
     type Ssh = GlobalId;
     type Csr = GlobalId;
     type MySql = GlobalId;
+    type SqlServer = GlobalId;
+    type MongoDb = GlobalId;
+    type Http = GlobalId;
+    type Aws = GlobalId;
 }
 
 /// Expresses that the struct contains an inlined definition of a connection.
@@ -114,4 +150,8 @@ impl ConnectionAccess for InlinedConnection {
     type Ssh = super::SshConnection;
     type Csr = super::CsrConnection;
     type MySql = super::MySqlConnection;
+    type SqlServer = super::SqlServerConnection;
+    type MongoDb = super::MongoDbConnection;
+    type Http = super::HttpConnection;
+    type Aws = super::aws::AwsConnection;
 }
@@ -12,6 +12,7 @@
 use std::borrow::Cow;
 use std::fmt::Debug;
 
+use mz_expr::MirScalarExpr;
 use mz_persist_client::ShardId;
 use mz_pgcopy::CopyFormatParams;
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
@@ -238,6 +239,8 @@ impl RustType<ProtoSinkEnvelope> for SinkEnvelope {
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum StorageSinkConnection<C: ConnectionAccess = InlinedConnection> {
     Kafka(KafkaSinkConnection<C>),
+    Http(HttpSinkConnection<C>),
+    Postgres(PostgresSinkConnection<C>),
 }
 
 impl<C: ConnectionAccess> StorageSinkConnection<C> {
@@ -257,6 +260,20 @@ impl<C: ConnectionAccess> StorageSinkConnection<C> {
             (StorageSinkConnection::Kafka(s), StorageSinkConnection::Kafka(o)) => {
                 s.alter_compatible(id, o)?
             }
+            (StorageSinkConnection::Http(s), StorageSinkConnection::Http(o)) => {
+                s.alter_compatible(id, o)?
+            }
+            (StorageSinkConnection::Postgres(s), StorageSinkConnection::Postgres(o)) => {
+                s.alter_compatible(id, o)?
+            }
+            _ => {
+                tracing::warn!(
+                    "StorageSinkConnection incompatible:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+                return Err(StorageError::InvalidAlter { id });
+            }
         }
 
         Ok(())
@@ -269,6 +286,8 @@ impl<R: ConnectionResolver> IntoInlineConnection<StorageSinkConnection, R>
     fn into_inline_connection(self, r: R) -> StorageSinkConnection {
         match self {
             Self::Kafka(conn) => StorageSinkConnection::Kafka(conn.into_inline_connection(r)),
+            Self::Http(conn) => StorageSinkConnection::Http(conn.into_inline_connection(r)),
+            Self::Postgres(conn) => StorageSinkConnection::Postgres(conn.into_inline_connection(r)),
         }
     }
 }
@@ -280,6 +299,8 @@ impl RustType<ProtoStorageSinkConnection> for StorageSinkConnection {
         ProtoStorageSinkConnection {
             kind: Some(match self {
                 Self::Kafka(conn) => KafkaV2(conn.into_proto()),
+                Self::Http(conn) => Http(conn.into_proto()),
+                Self::Postgres(conn) => Postgres(conn.into_proto()),
             }),
         }
     }
@@ -292,6 +313,8 @@ impl RustType<ProtoStorageSinkConnection> for StorageSinkConnection {
 
         Ok(match kind {
             KafkaV2(proto) => Self::Kafka(proto.into_rust()?),
+            Http(proto) => Self::Http(proto.into_rust()?),
+            Postgres(proto) => Self::Postgres(proto.into_rust()?),
         })
     }
 }
@@ -302,6 +325,8 @@ impl<C: ConnectionAccess> StorageSinkConnection<C> {
         use StorageSinkConnection::*;
         match self {
             Kafka(KafkaSinkConnection { connection_id, .. }) => Some(*connection_id),
+            Http(HttpSinkConnection { connection_id, .. }) => Some(*connection_id),
+            Postgres(PostgresSinkConnection { connection_id, .. }) => Some(*connection_id),
         }
     }
 
@@ -310,6 +335,8 @@ impl<C: ConnectionAccess> StorageSinkConnection<C> {
         use StorageSinkConnection::*;
         match self {
             Kafka(_) => "kafka",
+            Http(_) => "http",
+            Postgres(_) => "postgres",
         }
     }
 }
@@ -383,6 +410,12 @@ pub struct KafkaSinkConnection<C: ConnectionAccess = InlinedConnection> {
     /// The user-specified key for the sink.
     pub key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
     pub value_desc: RelationDesc,
+    /// The index of the column in `value_desc` whose entries are emitted as
+    /// record headers.
+    pub headers_index: Option<usize>,
+    /// An expression that determines the partition of each record, evaluated
+    /// over the key if the sink has a key and over the value otherwise.
+    pub partition_by: Option<MirScalarExpr>,
     pub topic: String,
     pub compression_type: KafkaSinkCompressionType,
     pub progress_group_id: KafkaIdStyle,
@@ -468,6 +501,8 @@ impl<C: ConnectionAccess> KafkaSinkConnection<C> {
             relation_key_indices,
             key_desc_and_indices,
             value_desc,
+            headers_index,
+            partition_by,
             topic,
             compression_type,
             progress_group_id,
@@ -486,6 +521,8 @@ impl<C: ConnectionAccess> KafkaSinkConnection<C> {
                 "key_desc_and_indices",
             ),
             (value_desc == &other.value_desc, "value_desc"),
+            (headers_index == &other.headers_index, "headers_index"),
+            (partition_by == &other.partition_by, "partition_by"),
             (topic == &other.topic, "topic"),
             (
                 compression_type == &other.compression_type,
@@ -527,6 +564,8 @@ impl<R: ConnectionResolver> IntoInlineConnection<KafkaSinkConnection, R>
             relation_key_indices,
             key_desc_and_indices,
             value_desc,
+            headers_index,
+            partition_by,
             topic,
             compression_type,
             progress_group_id,
@@ -539,6 +578,8 @@ impl<R: ConnectionResolver> IntoInlineConnection<KafkaSinkConnection, R>
             relation_key_indices,
             key_desc_and_indices,
             value_desc,
+            headers_index,
+            partition_by,
             topic,
             compression_type,
             progress_group_id,
@@ -593,6 +634,8 @@ impl RustType<ProtoKafkaSinkConnectionV2> for KafkaSinkConnection {
             key_desc_and_indices: self.key_desc_and_indices.into_proto(),
             relation_key_indices: self.relation_key_indices.into_proto(),
             value_desc: Some(self.value_desc.into_proto()),
+            headers_index: self.headers_index.into_proto(),
+            partition_by: self.partition_by.into_proto(),
             topic: self.topic.clone(),
             compression_type: Some(match self.compression_type {
                 KafkaSinkCompressionType::None => CompressionType::None(()),
@@ -623,6 +666,8 @@ impl RustType<ProtoKafkaSinkConnectionV2> for KafkaSinkConnection {
             value_desc: proto
                 .value_desc
                 .into_rust_if_some("ProtoKafkaSinkConnectionV2::value_desc")?,
+            headers_index: proto.headers_index.into_rust()?,
+            partition_by: proto.partition_by.into_rust()?,
             topic: proto.topic,
             compression_type: match proto.compression_type {
                 Some(CompressionType::None(())) => KafkaSinkCompressionType::None,
@@ -646,6 +691,254 @@ impl RustType<ProtoKafkaSinkConnectionV2> for KafkaSinkConnection {
     }
 }
 
+/// A sink that `POST`s batches of JSON-encoded updates to an HTTP endpoint.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct HttpSinkConnection<C: ConnectionAccess = InlinedConnection> {
+    pub connection_id: GlobalId,
+    pub connection: C::Http,
+    /// A natural key of the sinked relation (view or source).
+    pub relation_key_indices: Option<Vec<usize>>,
+    /// The user-specified key for the sink.
+    pub key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
+    pub value_desc: RelationDesc,
+}
+
+impl<C: ConnectionAccess> HttpSinkConnection<C> {
+    /// Determines if `self` is compatible with another `HttpSinkConnection`,
+    /// in such a way that it is possible to turn `self` into `other` through a
+    /// valid series of transformations (e.g. no transformation or `ALTER
+    /// CONNECTION`).
+    pub fn alter_compatible(
+        &self,
+        id: GlobalId,
+        other: &HttpSinkConnection<C>,
+    ) -> Result<(), StorageError> {
+        if self == other {
+            return Ok(());
+        }
+        let HttpSinkConnection {
+            connection_id,
+            // The details of the HTTP connection itself may change
+            connection: _,
+            relation_key_indices,
+            key_desc_and_indices,
+            value_desc,
+        } = self;
+
+        let compatibility_checks = [
+            (connection_id == &other.connection_id, "connection_id"),
+            (
+                relation_key_indices == &other.relation_key_indices,
+                "relation_key_indices",
+            ),
+            (
+                key_desc_and_indices == &other.key_desc_and_indices,
+                "key_desc_and_indices",
+            ),
+            (value_desc == &other.value_desc, "value_desc"),
+        ];
+        for (compatible, field) in compatibility_checks {
+            if !compatible {
+                tracing::warn!(
+                    "HttpSinkConnection incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+
+                return Err(StorageError::InvalidAlter { id });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: ConnectionResolver> IntoInlineConnection<HttpSinkConnection, R>
+    for HttpSinkConnection<ReferencedConnection>
+{
+    fn into_inline_connection(self, r: R) -> HttpSinkConnection {
+        let HttpSinkConnection {
+            connection_id,
+            connection,
+            relation_key_indices,
+            key_desc_and_indices,
+            value_desc,
+        } = self;
+        HttpSinkConnection {
+            connection_id,
+            connection: r.resolve_connection(connection).unwrap_http(),
+            relation_key_indices,
+            key_desc_and_indices,
+            value_desc,
+        }
+    }
+}
+
+impl RustType<ProtoHttpSinkConnection> for HttpSinkConnection {
+    fn into_proto(&self) -> ProtoHttpSinkConnection {
+        ProtoHttpSinkConnection {
+            connection_id: Some(self.connection_id.into_proto()),
+            connection: Some(self.connection.into_proto()),
+            key_desc_and_indices: self.key_desc_and_indices.into_proto(),
+            relation_key_indices: self.relation_key_indices.into_proto(),
+            value_desc: Some(self.value_desc.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoHttpSinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(HttpSinkConnection {
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoHttpSinkConnection::connection_id")?,
+            connection: proto
+                .connection
+                .into_rust_if_some("ProtoHttpSinkConnection::connection")?,
+            key_desc_and_indices: proto.key_desc_and_indices.into_rust()?,
+            relation_key_indices: proto.relation_key_indices.into_rust()?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoHttpSinkConnection::value_desc")?,
+        })
+    }
+}
+
+/// A sink that upserts the sinked relation into a table of an upstream PostgreSQL database.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PostgresSinkConnection<C: ConnectionAccess = InlinedConnection> {
+    pub connection_id: GlobalId,
+    pub connection: C::Pg,
+    /// The schema of the upstream table.
+    pub schema: String,
+    /// The name of the upstream table that receives the sinked relation.
+    pub table: String,
+    /// The name of the upstream table, in `schema`, that records the progress of the sink.
+    pub progress_table: String,
+    /// The key of the sink, which must match a unique constraint of the upstream table.
+    pub key_desc_and_indices: (RelationDesc, Vec<usize>),
+    /// A natural key of the sinked relation (view or source).
+    pub relation_key_indices: Option<Vec<usize>>,
+    pub value_desc: RelationDesc,
+}
+
+impl<C: ConnectionAccess> PostgresSinkConnection<C> {
+    /// Determines if `self` is compatible with another `PostgresSinkConnection`,
+    /// in such a way that it is possible to turn `self` into `other` through a
+    /// valid series of transformations (e.g. no transformation or `ALTER
+    /// CONNECTION`).
+    pub fn alter_compatible(
+        &self,
+        id: GlobalId,
+        other: &PostgresSinkConnection<C>,
+    ) -> Result<(), StorageError> {
+        if self == other {
+            return Ok(());
+        }
+        let PostgresSinkConnection {
+            connection_id,
+            // The details of the Postgres connection itself may change
+            connection: _,
+            schema,
+            table,
+            progress_table,
+            key_desc_and_indices,
+            relation_key_indices,
+            value_desc,
+        } = self;
+
+        let compatibility_checks = [
+            (connection_id == &other.connection_id, "connection_id"),
+            (schema == &other.schema, "schema"),
+            (table == &other.table, "table"),
+            (progress_table == &other.progress_table, "progress_table"),
+            (
+                key_desc_and_indices == &other.key_desc_and_indices,
+                "key_desc_and_indices",
+            ),
+            (
+                relation_key_indices == &other.relation_key_indices,
+                "relation_key_indices",
+            ),
+            (value_desc == &other.value_desc, "value_desc"),
+        ];
+        for (compatible, field) in compatibility_checks {
+            if !compatible {
+                tracing::warn!(
+                    "PostgresSinkConnection incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+
+                return Err(StorageError::InvalidAlter { id });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: ConnectionResolver> IntoInlineConnection<PostgresSinkConnection, R>
+    for PostgresSinkConnection<ReferencedConnection>
+{
+    fn into_inline_connection(self, r: R) -> PostgresSinkConnection {
+        let PostgresSinkConnection {
+            connection_id,
+            connection,
+            schema,
+            table,
+            progress_table,
+            key_desc_and_indices,
+            relation_key_indices,
+            value_desc,
+        } = self;
+        PostgresSinkConnection {
+            connection_id,
+            connection: r.resolve_connection(connection).unwrap_pg(),
+            schema,
+            table,
+            progress_table,
+            key_desc_and_indices,
+            relation_key_indices,
+            value_desc,
+        }
+    }
+}
+
+impl RustType<ProtoPostgresSinkConnection> for PostgresSinkConnection {
+    fn into_proto(&self) -> ProtoPostgresSinkConnection {
+        ProtoPostgresSinkConnection {
+            connection_id: Some(self.connection_id.into_proto()),
+            connection: Some(self.connection.into_proto()),
+            schema: self.schema.clone(),
+            table: self.table.clone(),
+            progress_table: self.progress_table.clone(),
+            key_desc_and_indices: Some(self.key_desc_and_indices.into_proto()),
+            relation_key_indices: self.relation_key_indices.into_proto(),
+            value_desc: Some(self.value_desc.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoPostgresSinkConnection) -> Result<Self, TryFromProtoError> {
+        Ok(PostgresSinkConnection {
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoPostgresSinkConnection::connection_id")?,
+            connection: proto
+                .connection
+                .into_rust_if_some("ProtoPostgresSinkConnection::connection")?,
+            schema: proto.schema,
+            table: proto.table,
+            progress_table: proto.progress_table,
+            key_desc_and_indices: proto
+                .key_desc_and_indices
+                .into_rust_if_some("ProtoPostgresSinkConnection::key_desc_and_indices")?,
+            relation_key_indices: proto.relation_key_indices.into_rust()?,
+            value_desc: proto
+                .value_desc
+                .into_rust_if_some("ProtoPostgresSinkConnection::value_desc")?,
+        })
+    }
+}
+
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum KafkaSinkFormat<C: ConnectionAccess = InlinedConnection> {
     Avro {
@@ -654,6 +947,11 @@ pub enum KafkaSinkFormat<C: ConnectionAccess = InlinedConnection> {
         csr_connection: C::Csr,
     },
     Json,
+    Protobuf {
+        key_schema: Option<String>,
+        value_schema: String,
+        csr_connection: C::Csr,
+    },
 }
 
 impl<C: ConnectionAccess> KafkaSinkFormat<C> {
@@ -661,6 +959,7 @@ impl<C: ConnectionAccess> KafkaSinkFormat<C> {
         match self {
             Self::Avro { .. } => "avro",
             Self::Json => "json",
+            Self::Protobuf { .. } => "protobuf",
         }
     }
 
@@ -682,6 +981,18 @@ impl<C: ConnectionAccess> KafkaSinkFormat<C> {
                     value_schema: other_value_schema,
                     csr_connection: _,
                 },
+            )
+            | (
+                Self::Protobuf {
+                    key_schema,
+                    value_schema,
+                    csr_connection: _,
+                },
+                Self::Protobuf {
+                    key_schema: other_key_schema,
+                    value_schema: other_value_schema,
+                    csr_connection: _,
+                },
             ) => {
                 let compatibility_checks = [
                     (key_schema == other_key_schema, "key_schema"),
@@ -690,7 +1001,8 @@ impl<C: ConnectionAccess> KafkaSinkFormat<C> {
                 for (compatible, field) in compatibility_checks {
                     if !compatible {
                         tracing::warn!(
-                            "KafkaSinkAvroFormatState::Avro incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
+                            "KafkaSinkFormat::{} incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
+                            self.get_format_name(),
                             self,
                             other
                         );
@@ -730,6 +1042,15 @@ impl<R: ConnectionResolver> IntoInlineConnection<KafkaSinkFormat, R>
                 csr_connection: r.resolve_connection(csr_connection).unwrap_csr(),
             },
             Self::Json => KafkaSinkFormat::Json,
+            Self::Protobuf {
+                key_schema,
+                value_schema,
+                csr_connection,
+            } => KafkaSinkFormat::Protobuf {
+                key_schema,
+                value_schema,
+                csr_connection: r.resolve_connection(csr_connection).unwrap_csr(),
+            },
         }
     }
 }
@@ -749,6 +1070,15 @@ impl RustType<ProtoKafkaSinkFormat> for KafkaSinkFormat {
                     csr_connection: Some(csr_connection.into_proto()),
                 }),
                 Self::Json => Kind::Json(()),
+                Self::Protobuf {
+                    key_schema,
+                    value_schema,
+                    csr_connection,
+                } => Kind::Protobuf(proto_kafka_sink_format::ProtoKafkaSinkProtobufFormat {
+                    key_schema: key_schema.clone(),
+                    value_schema: value_schema.clone(),
+                    csr_connection: Some(csr_connection.into_proto()),
+                }),
             }),
         }
     }
@@ -768,6 +1098,13 @@ impl RustType<ProtoKafkaSinkFormat> for KafkaSinkFormat {
                     .into_rust_if_some("ProtoKafkaSinkAvroFormat::csr_connection")?,
             },
             Kind::Json(()) => Self::Json,
+            Kind::Protobuf(proto) => Self::Protobuf {
+                key_schema: proto.key_schema,
+                value_schema: proto.value_schema,
+                csr_connection: proto
+                    .csr_connection
+                    .into_rust_if_some("ProtoKafkaSinkProtobufFormat::csr_connection")?,
+            },
         })
     }
 }
@@ -782,7 +1119,42 @@ pub struct S3UploadInfo {
     /// The relation desc of the data to be uploaded to S3.
     pub desc: RelationDesc,
     /// The selected sink format.
-    pub format: CopyFormatParams<'static>,
+    pub format: S3SinkFormat,
+}
+
+/// The format of the files written by a COPY TO S3 sink.
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub enum S3SinkFormat {
+    /// Encoded using the PG `COPY` protocol, with one of its supported
+    /// formats.
+    PgCopy(CopyFormatParams<'static>),
+    /// Encoded as Parquet, with the column types derived from the
+    /// `RelationDesc` of the data being copied.
+    Parquet,
+}
+
+impl RustType<ProtoS3SinkFormat> for S3SinkFormat {
+    fn into_proto(&self) -> ProtoS3SinkFormat {
+        use proto_s3_sink_format::Kind;
+        ProtoS3SinkFormat {
+            kind: Some(match self {
+                Self::PgCopy(params) => Kind::Pgcopy(params.into_proto()),
+                Self::Parquet => Kind::Parquet(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoS3SinkFormat) -> Result<Self, TryFromProtoError> {
+        use proto_s3_sink_format::Kind;
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoS3SinkFormat::kind"))?;
+
+        Ok(match kind {
+            Kind::Pgcopy(proto) => Self::PgCopy(proto.into_rust()?),
+            Kind::Parquet(()) => Self::Parquet,
+        })
+    }
 }
 
 impl RustType<ProtoS3UploadInfo> for S3UploadInfo {
@@ -52,14 +52,20 @@ pub mod encoding;
 pub mod envelope;
 pub mod kafka;
 pub mod load_generator;
+pub mod mongodb;
 pub mod mysql;
 pub mod postgres;
+pub mod s3;
+pub mod sql_server;
 
 pub use crate::sources::envelope::SourceEnvelope;
 pub use crate::sources::kafka::KafkaSourceConnection;
 pub use crate::sources::load_generator::LoadGeneratorSourceConnection;
+pub use crate::sources::mongodb::MongoDbSourceConnection;
 pub use crate::sources::mysql::MySqlSourceConnection;
 pub use crate::sources::postgres::PostgresSourceConnection;
+pub use crate::sources::s3::S3SourceConnection;
+pub use crate::sources::sql_server::SqlServerSourceConnection;
 
 include!(concat!(env!("OUT_DIR"), "/mz_storage_types.sources.rs"));
 
@@ -699,6 +705,16 @@ impl<C: ConnectionAccess> SourceDesc<C> {
                 connection: GenericSourceConnection::MySql(_),
                 ..
             } => false,
+            // SQL Server can produce retractions (deletes)
+            SourceDesc {
+                connection: GenericSourceConnection::SqlServer(_),
+                ..
+            } => false,
+            // MongoDB can produce retractions (updates and deletes)
+            SourceDesc {
+                connection: GenericSourceConnection::MongoDb(_),
+                ..
+            } => false,
             // Loadgen can produce retractions (deletes)
             SourceDesc {
                 connection: GenericSourceConnection::LoadGenerator(g),
@@ -712,7 +728,7 @@ impl<C: ConnectionAccess> SourceDesc<C> {
             // Other combinations may produce retractions.
             SourceDesc {
                 envelope: SourceEnvelope::Upsert(_) | SourceEnvelope::CdcV2,
-                connection: GenericSourceConnection::Kafka(_),
+                connection: GenericSourceConnection::Kafka(_) | GenericSourceConnection::S3(_),
                 ..
             } => false,
         }
@@ -773,6 +789,9 @@ pub enum GenericSourceConnection<C: ConnectionAccess = InlinedConnection> {
     Postgres(PostgresSourceConnection<C>),
     MySql(MySqlSourceConnection<C>),
     LoadGenerator(LoadGeneratorSourceConnection),
+    S3(S3SourceConnection<C>),
+    SqlServer(SqlServerSourceConnection<C>),
+    MongoDb(MongoDbSourceConnection<C>),
 }
 
 impl<C: ConnectionAccess> From<KafkaSourceConnection<C>> for GenericSourceConnection<C> {
@@ -799,6 +818,24 @@ impl<C: ConnectionAccess> From<LoadGeneratorSourceConnection> for GenericSourceC
     }
 }
 
+impl<C: ConnectionAccess> From<S3SourceConnection<C>> for GenericSourceConnection<C> {
+    fn from(conn: S3SourceConnection<C>) -> Self {
+        Self::S3(conn)
+    }
+}
+
+impl<C: ConnectionAccess> From<SqlServerSourceConnection<C>> for GenericSourceConnection<C> {
+    fn from(conn: SqlServerSourceConnection<C>) -> Self {
+        Self::SqlServer(conn)
+    }
+}
+
+impl<C: ConnectionAccess> From<MongoDbSourceConnection<C>> for GenericSourceConnection<C> {
+    fn from(conn: MongoDbSourceConnection<C>) -> Self {
+        Self::MongoDb(conn)
+    }
+}
+
 impl<R: ConnectionResolver> IntoInlineConnection<GenericSourceConnection, R>
     for GenericSourceConnection<ReferencedConnection>
 {
@@ -816,6 +853,15 @@ impl<R: ConnectionResolver> IntoInlineConnection<GenericSourceConnection, R>
             GenericSourceConnection::LoadGenerator(lg) => {
                 GenericSourceConnection::LoadGenerator(lg)
             }
+            GenericSourceConnection::S3(s3) => {
+                GenericSourceConnection::S3(s3.into_inline_connection(r))
+            }
+            GenericSourceConnection::SqlServer(sql_server) => {
+                GenericSourceConnection::SqlServer(sql_server.into_inline_connection(r))
+            }
+            GenericSourceConnection::MongoDb(mongodb) => {
+                GenericSourceConnection::MongoDb(mongodb.into_inline_connection(r))
+            }
         }
     }
 }
@@ -827,6 +873,9 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             Self::Postgres(conn) => conn.name(),
             Self::MySql(conn) => conn.name(),
             Self::LoadGenerator(conn) => conn.name(),
+            Self::S3(conn) => conn.name(),
+            Self::SqlServer(conn) => conn.name(),
+            Self::MongoDb(conn) => conn.name(),
         }
     }
 
@@ -836,6 +885,9 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             Self::Postgres(conn) => conn.upstream_name(),
             Self::MySql(conn) => conn.upstream_name(),
             Self::LoadGenerator(conn) => conn.upstream_name(),
+            Self::S3(conn) => conn.upstream_name(),
+            Self::SqlServer(conn) => conn.upstream_name(),
+            Self::MongoDb(conn) => conn.upstream_name(),
         }
     }
 
@@ -845,6 +897,9 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             Self::Postgres(conn) => conn.key_desc(),
             Self::MySql(conn) => conn.key_desc(),
             Self::LoadGenerator(conn) => conn.key_desc(),
+            Self::S3(conn) => conn.key_desc(),
+            Self::SqlServer(conn) => conn.key_desc(),
+            Self::MongoDb(conn) => conn.key_desc(),
         }
     }
 
@@ -854,6 +909,9 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             Self::Postgres(conn) => conn.value_desc(),
             Self::MySql(conn) => conn.value_desc(),
             Self::LoadGenerator(conn) => conn.value_desc(),
+            Self::S3(conn) => conn.value_desc(),
+            Self::SqlServer(conn) => conn.value_desc(),
+            Self::MongoDb(conn) => conn.value_desc(),
         }
     }
 
@@ -863,6 +921,9 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             Self::Postgres(conn) => conn.timestamp_desc(),
             Self::MySql(conn) => conn.timestamp_desc(),
             Self::LoadGenerator(conn) => conn.timestamp_desc(),
+            Self::S3(conn) => conn.timestamp_desc(),
+            Self::SqlServer(conn) => conn.timestamp_desc(),
+            Self::MongoDb(conn) => conn.timestamp_desc(),
         }
     }
 
@@ -872,6 +933,9 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             Self::Postgres(conn) => conn.connection_id(),
             Self::MySql(conn) => conn.connection_id(),
             Self::LoadGenerator(conn) => conn.connection_id(),
+            Self::S3(conn) => conn.connection_id(),
+            Self::SqlServer(conn) => conn.connection_id(),
+            Self::MongoDb(conn) => conn.connection_id(),
         }
     }
 
@@ -881,6 +945,9 @@ impl<C: ConnectionAccess> SourceConnection for GenericSourceConnection<C> {
             Self::Postgres(conn) => conn.metadata_columns(),
             Self::MySql(conn) => conn.metadata_columns(),
             Self::LoadGenerator(conn) => conn.metadata_columns(),
+            Self::S3(conn) => conn.metadata_columns(),
+            Self::SqlServer(conn) => conn.metadata_columns(),
+            Self::MongoDb(conn) => conn.metadata_columns(),
         }
     }
 }
@@ -896,6 +963,9 @@ impl<C: ConnectionAccess> crate::AlterCompatible for GenericSourceConnection<C>
             (Self::LoadGenerator(conn), Self::LoadGenerator(other)) => {
                 conn.alter_compatible(id, other)
             }
+            (Self::S3(conn), Self::S3(other)) => conn.alter_compatible(id, other),
+            (Self::SqlServer(conn), Self::SqlServer(other)) => conn.alter_compatible(id, other),
+            (Self::MongoDb(conn), Self::MongoDb(other)) => conn.alter_compatible(id, other),
             _ => Err(StorageError::InvalidAlter { id }),
         };
 
@@ -924,6 +994,11 @@ impl RustType<ProtoSourceConnection> for GenericSourceConnection<InlinedConnecti
                 GenericSourceConnection::LoadGenerator(loadgen) => {
                     Kind::Loadgen(loadgen.into_proto())
                 }
+                GenericSourceConnection::S3(s3) => Kind::S3(s3.into_proto()),
+                GenericSourceConnection::SqlServer(sql_server) => {
+                    Kind::SqlServer(sql_server.into_proto())
+                }
+                GenericSourceConnection::MongoDb(mongodb) => Kind::Mongodb(mongodb.into_proto()),
             }),
         }
     }
@@ -938,6 +1013,11 @@ impl RustType<ProtoSourceConnection> for GenericSourceConnection<InlinedConnecti
             Kind::Postgres(postgres) => GenericSourceConnection::Postgres(postgres.into_rust()?),
             Kind::Mysql(mysql) => GenericSourceConnection::MySql(mysql.into_rust()?),
             Kind::Loadgen(loadgen) => GenericSourceConnection::LoadGenerator(loadgen.into_rust()?),
+            Kind::S3(s3) => GenericSourceConnection::S3(s3.into_rust()?),
+            Kind::SqlServer(sql_server) => {
+                GenericSourceConnection::SqlServer(sql_server.into_rust()?)
+            }
+            Kind::Mongodb(mongodb) => GenericSourceConnection::MongoDb(mongodb.into_rust()?),
         })
     }
 }
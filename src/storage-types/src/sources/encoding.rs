@@ -10,7 +10,7 @@
 //! Types and traits related to the *decoding* of data for sources.
 
 use anyhow::Context;
-use mz_interchange::{avro, protobuf};
+use mz_interchange::{avro, json_schema, protobuf};
 use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
 use mz_repr::adt::regex::any_regex;
 use mz_repr::{ColumnType, RelationDesc, ScalarType};
@@ -78,11 +78,13 @@ impl RustType<ProtoSourceDataEncoding> for SourceDataEncoding {
 #[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub enum DataEncoding<C: ConnectionAccess = InlinedConnection> {
     Avro(AvroEncoding<C>),
+    AvroOcf(AvroOcfEncoding),
     Protobuf(ProtobufEncoding),
     Csv(CsvEncoding),
     Regex(RegexEncoding),
     Bytes,
     Json,
+    JsonSchema(JsonSchemaEncoding),
     Text,
 }
 
@@ -92,11 +94,13 @@ impl<R: ConnectionResolver> IntoInlineConnection<DataEncoding, R>
     fn into_inline_connection(self, r: R) -> DataEncoding {
         match self {
             Self::Avro(conn) => DataEncoding::Avro(conn.into_inline_connection(r)),
+            Self::AvroOcf(conn) => DataEncoding::AvroOcf(conn),
             Self::Protobuf(conn) => DataEncoding::Protobuf(conn),
             Self::Csv(conn) => DataEncoding::Csv(conn),
             Self::Regex(conn) => DataEncoding::Regex(conn),
             Self::Bytes => DataEncoding::Bytes,
             Self::Json => DataEncoding::Json,
+            Self::JsonSchema(conn) => DataEncoding::JsonSchema(conn),
             Self::Text => DataEncoding::Text,
         }
     }
@@ -108,12 +112,14 @@ impl RustType<ProtoDataEncoding> for DataEncoding {
         ProtoDataEncoding {
             kind: Some(match self {
                 DataEncoding::Avro(e) => Kind::Avro(e.into_proto()),
+                DataEncoding::AvroOcf(e) => Kind::AvroOcf(e.into_proto()),
                 DataEncoding::Protobuf(e) => Kind::Protobuf(e.into_proto()),
                 DataEncoding::Csv(e) => Kind::Csv(e.into_proto()),
                 DataEncoding::Regex(e) => Kind::Regex(e.into_proto()),
                 DataEncoding::Bytes => Kind::Bytes(()),
                 DataEncoding::Text => Kind::Text(()),
                 DataEncoding::Json => Kind::Json(()),
+                DataEncoding::JsonSchema(e) => Kind::JsonSchema(e.into_proto()),
             }),
         }
     }
@@ -125,12 +131,14 @@ impl RustType<ProtoDataEncoding> for DataEncoding {
             .ok_or_else(|| TryFromProtoError::missing_field("ProtoDataEncoding::kind"))?;
         Ok(match kind {
             Kind::Avro(e) => DataEncoding::Avro(e.into_rust()?),
+            Kind::AvroOcf(e) => DataEncoding::AvroOcf(e.into_rust()?),
             Kind::Protobuf(e) => DataEncoding::Protobuf(e.into_rust()?),
             Kind::Csv(e) => DataEncoding::Csv(e.into_rust()?),
             Kind::Regex(e) => DataEncoding::Regex(e.into_rust()?),
             Kind::Bytes(()) => DataEncoding::Bytes,
             Kind::Text(()) => DataEncoding::Text,
             Kind::Json(()) => DataEncoding::Json,
+            Kind::JsonSchema(e) => DataEncoding::JsonSchema(e.into_rust()?),
         })
     }
 }
@@ -148,11 +156,13 @@ impl<C: ConnectionAccess> DataEncoding<C> {
     pub fn type_(&self) -> &str {
         match self {
             Self::Avro(_) => "avro",
+            Self::AvroOcf(_) => "avro-ocf",
             Self::Protobuf(_) => "protobuf",
             Self::Csv(_) => "csv",
             Self::Regex(_) => "regex",
             Self::Bytes => "bytes",
             Self::Json => "json",
+            Self::JsonSchema(_) => "json-schema",
             Self::Text => "text",
         }
     }
@@ -168,10 +178,19 @@ impl<C: ConnectionAccess> DataEncoding<C> {
             Self::Json => {
                 RelationDesc::empty().with_column("data", ScalarType::Jsonb.nullable(false))
             }
-            Self::Avro(AvroEncoding { schema, .. }) => {
+            Self::Avro(AvroEncoding { schema, .. }) | Self::AvroOcf(AvroOcfEncoding { schema }) => {
                 let parsed_schema = avro::parse_schema(schema).context("validating avro schema")?;
                 avro::schema_to_relationdesc(parsed_schema).context("validating avro schema")?
             }
+            Self::JsonSchema(JsonSchemaEncoding { schema, .. }) => {
+                json_schema::DecodedSchema::from_json_str(schema)
+                    .context("validating JSON schema")?
+                    .columns()
+                    .iter()
+                    .fold(RelationDesc::empty(), |desc, (name, ty)| {
+                        desc.with_column(name, ty.clone())
+                    })
+            }
             Self::Protobuf(ProtobufEncoding {
                 descriptors,
                 message_name,
@@ -219,7 +238,9 @@ impl<C: ConnectionAccess> DataEncoding<C> {
         match self {
             Self::Bytes => "Bytes",
             Self::Json => "Json",
+            Self::JsonSchema(_) => "JsonSchema",
             Self::Avro(_) => "Avro",
+            Self::AvroOcf(_) => "AvroOcf",
             Self::Protobuf(_) => "Protobuf",
             Self::Regex { .. } => "Regex",
             Self::Csv(_) => "Csv",
@@ -271,6 +292,29 @@ impl RustType<ProtoAvroEncoding> for AvroEncoding {
     }
 }
 
+/// Encoding as Avro object container files, each of which contains any number
+/// of records and the schema they were written with.
+#[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct AvroOcfEncoding {
+    /// The reader schema that the writer schema of each file is resolved
+    /// against.
+    pub schema: String,
+}
+
+impl RustType<ProtoAvroOcfEncoding> for AvroOcfEncoding {
+    fn into_proto(&self) -> ProtoAvroOcfEncoding {
+        ProtoAvroOcfEncoding {
+            schema: self.schema.clone(),
+        }
+    }
+
+    fn from_proto(proto: ProtoAvroOcfEncoding) -> Result<Self, TryFromProtoError> {
+        Ok(AvroOcfEncoding {
+            schema: proto.schema,
+        })
+    }
+}
+
 /// Encoding in Protobuf format.
 #[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ProtobufEncoding {
@@ -297,6 +341,30 @@ impl RustType<ProtoProtobufEncoding> for ProtobufEncoding {
     }
 }
 
+/// Encoding as JSON documents whose shape is described by a JSON Schema.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct JsonSchemaEncoding {
+    /// The JSON Schema that describes the documents.
+    pub schema: String,
+    pub confluent_wire_format: bool,
+}
+
+impl RustType<ProtoJsonSchemaEncoding> for JsonSchemaEncoding {
+    fn into_proto(&self) -> ProtoJsonSchemaEncoding {
+        ProtoJsonSchemaEncoding {
+            schema: self.schema.clone(),
+            confluent_wire_format: self.confluent_wire_format,
+        }
+    }
+
+    fn from_proto(proto: ProtoJsonSchemaEncoding) -> Result<Self, TryFromProtoError> {
+        Ok(JsonSchemaEncoding {
+            schema: proto.schema,
+            confluent_wire_format: proto.confluent_wire_format,
+        })
+    }
+}
+
 /// Arguments necessary to define how to decode from CSV format
 #[derive(Arbitrary, Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub struct CsvEncoding {
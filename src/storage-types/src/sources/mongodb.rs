@@ -0,0 +1,173 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Types related to MongoDB sources
+
+use mz_mongodb_util::ResumeToken;
+use mz_proto::{IntoRustIfSome, RustType, TryFromProtoError};
+use mz_repr::{ColumnType, Datum, GlobalId, RelationDesc, Row, ScalarType};
+use once_cell::sync::Lazy;
+use proptest::prelude::any;
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+use crate::connections::inline::{
+    ConnectionAccess, ConnectionResolver, InlinedConnection, IntoInlineConnection,
+    ReferencedConnection,
+};
+use crate::sources::{SourceConnection, SourceTimestamp};
+
+include!(concat!(
+    env!("OUT_DIR"),
+    "/mz_storage_types.sources.mongodb.rs"
+));
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub struct MongoDbSourceConnection<C: ConnectionAccess = InlinedConnection> {
+    pub connection_id: GlobalId,
+    pub connection: C::MongoDb,
+    pub details: MongoDbSourceDetails,
+}
+
+impl<R: ConnectionResolver> IntoInlineConnection<MongoDbSourceConnection, R>
+    for MongoDbSourceConnection<ReferencedConnection>
+{
+    fn into_inline_connection(self, r: R) -> MongoDbSourceConnection {
+        let MongoDbSourceConnection {
+            connection_id,
+            connection,
+            details,
+        } = self;
+
+        MongoDbSourceConnection {
+            connection_id,
+            connection: r.resolve_connection(connection).unwrap_mongodb(),
+            details,
+        }
+    }
+}
+
+pub static MONGODB_PROGRESS_DESC: Lazy<RelationDesc> = Lazy::new(|| {
+    RelationDesc::empty().with_column("resume_token", ScalarType::Bytes.nullable(false))
+});
+
+/// The relation of a MongoDB source that ingests all of its collections into
+/// the source itself rather than into subsources.
+static MONGODB_SOURCE_DESC: Lazy<RelationDesc> = Lazy::new(|| {
+    RelationDesc::empty()
+        .with_column("collection", ScalarType::String.nullable(false))
+        .with_column("_id", ScalarType::Jsonb.nullable(false))
+        .with_column("document", ScalarType::Jsonb.nullable(false))
+        .with_key(vec![0, 1])
+});
+
+impl<C: ConnectionAccess> SourceConnection for MongoDbSourceConnection<C> {
+    fn name(&self) -> &'static str {
+        "mongodb"
+    }
+
+    fn upstream_name(&self) -> Option<&str> {
+        None
+    }
+
+    fn key_desc(&self) -> RelationDesc {
+        RelationDesc::empty()
+    }
+
+    fn value_desc(&self) -> RelationDesc {
+        if self.details.subsources {
+            // The catalog object representing the source itself is just an
+            // empty relation with no columns when all data goes to subsources.
+            RelationDesc::empty()
+        } else {
+            MONGODB_SOURCE_DESC.clone()
+        }
+    }
+
+    fn timestamp_desc(&self) -> RelationDesc {
+        MONGODB_PROGRESS_DESC.clone()
+    }
+
+    fn connection_id(&self) -> Option<GlobalId> {
+        Some(self.connection_id)
+    }
+
+    fn metadata_columns(&self) -> Vec<(&str, ColumnType)> {
+        vec![]
+    }
+}
+
+impl<C: ConnectionAccess> crate::AlterCompatible for MongoDbSourceConnection<C> {}
+
+impl RustType<ProtoMongoDbSourceConnection> for MongoDbSourceConnection {
+    fn into_proto(&self) -> ProtoMongoDbSourceConnection {
+        ProtoMongoDbSourceConnection {
+            connection: Some(self.connection.into_proto()),
+            connection_id: Some(self.connection_id.into_proto()),
+            details: Some(self.details.into_proto()),
+        }
+    }
+
+    fn from_proto(proto: ProtoMongoDbSourceConnection) -> Result<Self, TryFromProtoError> {
+        Ok(MongoDbSourceConnection {
+            connection: proto
+                .connection
+                .into_rust_if_some("ProtoMongoDbSourceConnection::connection")?,
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoMongoDbSourceConnection::connection_id")?,
+            details: proto
+                .details
+                .into_rust_if_some("ProtoMongoDbSourceConnection::details")?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
+pub struct MongoDbSourceDetails {
+    /// The collections ingested by this source, as determined during
+    /// purification.
+    #[proptest(strategy = "proptest::collection::vec(any::<String>(), 0..4)")]
+    pub collections: Vec<String>,
+    /// Whether each collection is ingested into its own subsource, in which
+    /// case the order of `collections` determines the output index of each
+    /// collection. Otherwise all collections are ingested into the source
+    /// itself.
+    pub subsources: bool,
+}
+
+impl RustType<ProtoMongoDbSourceDetails> for MongoDbSourceDetails {
+    fn into_proto(&self) -> ProtoMongoDbSourceDetails {
+        ProtoMongoDbSourceDetails {
+            collections: self.collections.clone(),
+            subsources: self.subsources,
+        }
+    }
+
+    fn from_proto(proto: ProtoMongoDbSourceDetails) -> Result<Self, TryFromProtoError> {
+        Ok(MongoDbSourceDetails {
+            collections: proto.collections,
+            subsources: proto.subsources,
+        })
+    }
+}
+
+impl SourceTimestamp for ResumeToken {
+    fn encode_row(&self) -> Row {
+        Row::pack([Datum::Bytes(self.as_bytes())])
+    }
+
+    fn decode_row(row: &Row) -> Self {
+        let mut datums = row.iter();
+        match (datums.next(), datums.next()) {
+            (Some(Datum::Bytes(bytes)), None) => ResumeToken::from_bytes(bytes.to_vec()),
+            _ => panic!("invalid row {row:?}"),
+        }
+    }
+}
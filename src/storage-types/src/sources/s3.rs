@@ -0,0 +1,316 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Types related to S3 sources
+
+use std::time::Duration;
+
+use mz_proto::{IntoRustIfSome, ProtoType, RustType, TryFromProtoError};
+use mz_repr::{ColumnType, Datum, GlobalId, RelationDesc, Row, ScalarType};
+use mz_timely_util::order::Partitioned;
+use once_cell::sync::Lazy;
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+
+use crate::connections::inline::{
+    ConnectionAccess, ConnectionResolver, InlinedConnection, IntoInlineConnection,
+    ReferencedConnection,
+};
+use crate::controller::StorageError;
+use crate::sources::kafka::{BoundKind, RangeBound};
+use crate::sources::{MzOffset, SourceConnection, SourceTimestamp};
+use crate::AlterCompatible;
+
+include!(concat!(env!("OUT_DIR"), "/mz_storage_types.sources.s3.rs"));
+
+/// A source that ingests the objects stored under a prefix of an S3 bucket.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct S3SourceConnection<C: ConnectionAccess = InlinedConnection> {
+    pub connection: C::Aws,
+    pub connection_id: GlobalId,
+    /// The bucket that contains the objects.
+    pub bucket: String,
+    /// Only objects whose key starts with this prefix are ingested.
+    pub prefix: String,
+    /// How the contents of each object are turned into records.
+    pub format: S3ObjectFormat,
+    /// How often to list the bucket to discover new objects. If not set, the
+    /// source ingests the objects that exist when it first starts and then
+    /// completes. Only new objects whose keys come after the keys of all
+    /// ingested objects are discovered.
+    pub poll_interval: Option<Duration>,
+}
+
+impl<R: ConnectionResolver> IntoInlineConnection<S3SourceConnection, R>
+    for S3SourceConnection<ReferencedConnection>
+{
+    fn into_inline_connection(self, r: R) -> S3SourceConnection {
+        let S3SourceConnection {
+            connection,
+            connection_id,
+            bucket,
+            prefix,
+            format,
+            poll_interval,
+        } = self;
+        S3SourceConnection {
+            connection: r.resolve_connection(connection).unwrap_aws(),
+            connection_id,
+            bucket,
+            prefix,
+            format,
+            poll_interval,
+        }
+    }
+}
+
+/// Describes how the bytes of an object are split into records.
+#[derive(Arbitrary, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum S3ObjectFormat {
+    /// Every line of the object is a record that is decoded by the source's
+    /// `FORMAT`.
+    Lines {
+        /// Whether the first line of each object is a header that must be
+        /// skipped.
+        header: bool,
+    },
+    /// The object is a Parquet file, every row of which becomes a record.
+    Parquet,
+}
+
+impl RustType<ProtoS3ObjectFormat> for S3ObjectFormat {
+    fn into_proto(&self) -> ProtoS3ObjectFormat {
+        use proto_s3_object_format::{Kind, ProtoLines};
+        ProtoS3ObjectFormat {
+            kind: Some(match self {
+                S3ObjectFormat::Lines { header } => Kind::Lines(ProtoLines { header: *header }),
+                S3ObjectFormat::Parquet => Kind::Parquet(()),
+            }),
+        }
+    }
+
+    fn from_proto(proto: ProtoS3ObjectFormat) -> Result<Self, TryFromProtoError> {
+        use proto_s3_object_format::{Kind, ProtoLines};
+        let kind = proto
+            .kind
+            .ok_or_else(|| TryFromProtoError::missing_field("ProtoS3ObjectFormat::kind"))?;
+        Ok(match kind {
+            Kind::Lines(ProtoLines { header }) => S3ObjectFormat::Lines { header },
+            Kind::Parquet(()) => S3ObjectFormat::Parquet,
+        })
+    }
+}
+
+/// The progress of an S3 source: the range of keys after the greatest key
+/// ingested so far, in which objects are still ingested. Its lower bound is
+/// `NULL` until the first object has been ingested and its upper bound is
+/// always `NULL`.
+pub static S3_PROGRESS_DESC: Lazy<RelationDesc> = Lazy::new(|| {
+    RelationDesc::empty()
+        .with_column("object_lower", ScalarType::String.nullable(true))
+        .with_column("object_upper", ScalarType::String.nullable(true))
+        .with_column("offset", ScalarType::UInt64.nullable(false))
+});
+
+/// The name of the single column produced by S3 sources with
+/// [`S3ObjectFormat::Parquet`].
+pub const S3_PARQUET_COLUMN: &str = "data";
+
+impl<C: ConnectionAccess> SourceConnection for S3SourceConnection<C> {
+    fn name(&self) -> &'static str {
+        "s3"
+    }
+
+    fn upstream_name(&self) -> Option<&str> {
+        Some(self.bucket.as_str())
+    }
+
+    fn key_desc(&self) -> RelationDesc {
+        RelationDesc::empty()
+    }
+
+    fn value_desc(&self) -> RelationDesc {
+        match self.format {
+            // Lines are handed to the decoding pipeline as raw bytes.
+            S3ObjectFormat::Lines { .. } => {
+                RelationDesc::empty().with_column("value", ScalarType::Bytes.nullable(true))
+            }
+            // Parquet files are decoded by the source itself, because a single
+            // object contains many records.
+            S3ObjectFormat::Parquet => RelationDesc::empty()
+                .with_column(S3_PARQUET_COLUMN, ScalarType::Jsonb.nullable(false)),
+        }
+    }
+
+    fn timestamp_desc(&self) -> RelationDesc {
+        S3_PROGRESS_DESC.clone()
+    }
+
+    fn connection_id(&self) -> Option<GlobalId> {
+        Some(self.connection_id)
+    }
+
+    fn metadata_columns(&self) -> Vec<(&str, ColumnType)> {
+        vec![]
+    }
+}
+
+impl<C: ConnectionAccess> AlterCompatible for S3SourceConnection<C> {
+    fn alter_compatible(&self, id: GlobalId, other: &Self) -> Result<(), StorageError> {
+        if self == other {
+            return Ok(());
+        }
+
+        let S3SourceConnection {
+            // Connection details may change
+            connection: _,
+            connection_id,
+            bucket,
+            prefix,
+            format,
+            poll_interval: _,
+        } = self;
+
+        let compatibility_checks = [
+            (connection_id == &other.connection_id, "connection_id"),
+            (bucket == &other.bucket, "bucket"),
+            (prefix == &other.prefix, "prefix"),
+            (format == &other.format, "format"),
+        ];
+
+        for (compatible, field) in compatibility_checks {
+            if !compatible {
+                tracing::warn!(
+                    "S3SourceConnection incompatible at {field}:\nself:\n{:#?}\n\nother\n{:#?}",
+                    self,
+                    other
+                );
+
+                return Err(StorageError::InvalidAlter { id });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl RustType<ProtoS3SourceConnection> for S3SourceConnection {
+    fn into_proto(&self) -> ProtoS3SourceConnection {
+        ProtoS3SourceConnection {
+            connection: Some(self.connection.into_proto()),
+            connection_id: Some(self.connection_id.into_proto()),
+            bucket: self.bucket.clone(),
+            prefix: self.prefix.clone(),
+            format: Some(self.format.into_proto()),
+            poll_interval: self.poll_interval.into_proto(),
+        }
+    }
+
+    fn from_proto(proto: ProtoS3SourceConnection) -> Result<Self, TryFromProtoError> {
+        Ok(S3SourceConnection {
+            connection: proto
+                .connection
+                .into_rust_if_some("ProtoS3SourceConnection::connection")?,
+            connection_id: proto
+                .connection_id
+                .into_rust_if_some("ProtoS3SourceConnection::connection_id")?,
+            bucket: proto.bucket,
+            prefix: proto.prefix,
+            format: proto
+                .format
+                .into_rust_if_some("ProtoS3SourceConnection::format")?,
+            poll_interval: proto.poll_interval.into_rust()?,
+        })
+    }
+}
+
+impl SourceTimestamp for Partitioned<RangeBound<String>, MzOffset> {
+    fn encode_row(&self) -> Row {
+        let (lower, lower_inclusive) = match &self.interval().lower {
+            RangeBound::NegInfinity => (Datum::Null, false),
+            RangeBound::Elem(key, BoundKind::After) => (Datum::String(key), false),
+            RangeBound::Elem(key, BoundKind::At) => (Datum::String(key), true),
+            lower => unreachable!("invalid lower bound {lower:?}"),
+        };
+        let (upper, upper_inclusive) = match &self.interval().upper {
+            RangeBound::PosInfinity => (Datum::Null, false),
+            RangeBound::Elem(key, BoundKind::Before) => (Datum::String(key), false),
+            RangeBound::Elem(key, BoundKind::At) => (Datum::String(key), true),
+            upper => unreachable!("invalid upper bound {upper:?}"),
+        };
+        assert_eq!(lower_inclusive, upper_inclusive, "invalid range {self}");
+
+        Row::pack([lower, upper, Datum::UInt64(self.timestamp().offset)])
+    }
+
+    fn decode_row(row: &Row) -> Self {
+        let mut datums = row.iter();
+        match (datums.next(), datums.next(), datums.next(), datums.next()) {
+            (Some(lower), Some(upper), Some(Datum::UInt64(offset)), None) => {
+                let offset = MzOffset::from(offset);
+                match (lower, upper) {
+                    // Exclusive ranges can never have equal bounds, so equal
+                    // bounds always describe a single object.
+                    (Datum::String(lower), Datum::String(upper)) if lower == upper => {
+                        Partitioned::new_singleton(RangeBound::exact(lower.to_owned()), offset)
+                    }
+                    (lower, upper) => {
+                        let lower = match lower {
+                            Datum::String(key) => RangeBound::after(key.to_owned()),
+                            _ => RangeBound::NegInfinity,
+                        };
+                        let upper = match upper {
+                            Datum::String(key) => RangeBound::before(key.to_owned()),
+                            _ => RangeBound::PosInfinity,
+                        };
+                        Partitioned::new_range(lower, upper, offset)
+                    }
+                }
+            }
+            _ => panic!("invalid row {row:?}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use timely::progress::Timestamp;
+
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_progress_roundtrip() {
+        let timestamps = [
+            Partitioned::minimum(),
+            Partitioned::new_singleton(RangeBound::exact("a/1.csv".to_owned()), MzOffset::from(1)),
+            Partitioned::new_range(
+                RangeBound::NegInfinity,
+                RangeBound::before("a/1.csv".to_owned()),
+                MzOffset::from(0),
+            ),
+            Partitioned::new_range(
+                RangeBound::after("a/1.csv".to_owned()),
+                RangeBound::before("a/2.csv".to_owned()),
+                MzOffset::from(0),
+            ),
+            Partitioned::new_range(
+                RangeBound::after("a/2.csv".to_owned()),
+                RangeBound::PosInfinity,
+                MzOffset::from(0),
+            ),
+        ];
+        for ts in timestamps {
+            let row = ts.encode_row();
+            assert_eq!(
+                Partitioned::<RangeBound<String>, MzOffset>::decode_row(&row),
+                ts
+            );
+        }
+    }
+}
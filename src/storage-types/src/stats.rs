@@ -308,34 +308,33 @@ impl RelationPartStats<'_> {
         num_oks.map(|num_oks| num_results - num_oks)
     }
 
-    fn col_values<'a>(&'a self, idx: usize, arena: &'a RowArena) -> Option<ResultSpec> {
-        struct ColValues<'a>(
+    /// Returns the smallest and largest non-null values and the number of nulls in column `idx`,
+    /// if the part has statistics for it.
+    pub fn col_min_max_nulls<'a>(
+        &'a self,
+        idx: usize,
+        arena: &'a RowArena,
+    ) -> Option<(Option<Datum<'a>>, Option<Datum<'a>>, usize)> {
+        struct ColMinMaxNulls<'a>(
             &'a Metrics,
             &'a str,
             &'a str,
             &'a dyn DynStats,
             &'a RowArena,
-            Option<usize>,
         );
-        impl<'a> DatumToPersistFn<Option<ResultSpec<'a>>> for ColValues<'a> {
-            fn call<T: DatumToPersist>(self) -> Option<ResultSpec<'a>> {
-                let ColValues(metrics, name, col_name, stats, arena, total_count) = self;
+        impl<'a> DatumToPersistFn<Option<(Option<Datum<'a>>, Option<Datum<'a>>, usize)>>
+            for ColMinMaxNulls<'a>
+        {
+            fn call<T: DatumToPersist>(
+                self,
+            ) -> Option<(Option<Datum<'a>>, Option<Datum<'a>>, usize)> {
+                let ColMinMaxNulls(metrics, name, col_name, stats, arena) = self;
                 let stats = downcast_stats::<T::Data>(metrics, name, col_name, stats)?;
                 let make_datum = |lower| arena.make_datum(|packer| T::decode(lower, packer));
                 let min = stats.lower().map(make_datum);
                 let max = stats.upper().map(make_datum);
                 let null_count = stats.none_count();
-                let values = match (total_count, min, max) {
-                    (Some(total_count), _, _) if total_count == null_count => ResultSpec::nothing(),
-                    (_, Some(min), Some(max)) => ResultSpec::value_between(min, max),
-                    _ => ResultSpec::value_all(),
-                };
-                let nulls = if null_count > 0 {
-                    ResultSpec::null()
-                } else {
-                    ResultSpec::nothing()
-                };
-                Some(values.union(nulls))
+                Some((min, max, null_count))
             }
         }
 
@@ -347,15 +346,29 @@ impl RelationPartStats<'_> {
             .col::<Option<DynStruct>>("ok")
             .expect("ok column should be a struct")?;
         let stats = ok_stats.some.cols.get(name.as_str())?;
-        typ.to_persist(ColValues(
+        typ.to_persist(ColMinMaxNulls(
             self.metrics,
             self.name,
             name.as_str(),
             stats.as_ref(),
             arena,
-            self.len(),
         ))?
     }
+
+    fn col_values<'a>(&'a self, idx: usize, arena: &'a RowArena) -> Option<ResultSpec> {
+        let (min, max, null_count) = self.col_min_max_nulls(idx, arena)?;
+        let values = match (self.len(), min, max) {
+            (Some(total_count), _, _) if total_count == null_count => ResultSpec::nothing(),
+            (_, Some(min), Some(max)) => ResultSpec::value_between(min, max),
+            _ => ResultSpec::value_all(),
+        };
+        let nulls = if null_count > 0 {
+            ResultSpec::null()
+        } else {
+            ResultSpec::nothing()
+        };
+        Some(values.union(nulls))
+    }
 }
 
 #[cfg(test)]
@@ -7,7 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use mz_interchange::avro::Decoder;
+use mz_interchange::avro::{Decoder, OcfDecoder};
 use mz_ore::error::ErrorExt;
 use mz_repr::Row;
 use mz_storage_types::errors::DecodeErrorKind;
@@ -48,3 +48,26 @@ impl AvroDecoderState {
         Ok(result)
     }
 }
+
+#[derive(Debug)]
+pub struct AvroOcfDecoderState {
+    decoder: OcfDecoder,
+}
+
+impl AvroOcfDecoderState {
+    pub fn new(reader_schema: &str, debug_name: String) -> Result<Self, anyhow::Error> {
+        Ok(AvroOcfDecoderState {
+            decoder: OcfDecoder::new(reader_schema, debug_name)?,
+        })
+    }
+
+    /// Decodes the object container file in `bytes` into one row per record.
+    pub fn decode(&mut self, bytes: &[u8]) -> Result<Vec<Row>, DecodeErrorKind> {
+        self.decoder.decode(bytes).map_err(|err| {
+            DecodeErrorKind::Text(format!(
+                "avro ocf deserialization error: {}",
+                err.display_with_causes()
+            ))
+        })
+    }
+}
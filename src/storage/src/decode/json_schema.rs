@@ -0,0 +1,43 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use mz_interchange::json_schema::{DecodedSchema, Decoder};
+use mz_ore::error::ErrorExt;
+use mz_repr::Row;
+use mz_storage_types::errors::DecodeErrorKind;
+use mz_storage_types::sources::encoding::JsonSchemaEncoding;
+
+#[derive(Debug)]
+pub struct JsonSchemaDecoderState {
+    decoder: Decoder,
+}
+
+impl JsonSchemaDecoderState {
+    pub fn new(
+        JsonSchemaEncoding {
+            schema,
+            confluent_wire_format,
+        }: JsonSchemaEncoding,
+    ) -> Result<Self, anyhow::Error> {
+        let schema = DecodedSchema::from_json_str(&schema)?;
+        Ok(JsonSchemaDecoderState {
+            decoder: Decoder::new(schema, confluent_wire_format),
+        })
+    }
+
+    pub fn get_value(&mut self, bytes: &[u8]) -> Result<Option<Row>, DecodeErrorKind> {
+        match self.decoder.decode(bytes) {
+            Ok(row) => Ok(Some(row)),
+            Err(err) => Err(DecodeErrorKind::Text(format!(
+                "JSON schema violation: {}",
+                err.display_with_causes()
+            ))),
+        }
+    }
+}
@@ -25,7 +25,9 @@ use mz_ore::future::OreFutureExt;
 use mz_repr::{Datum, Diff, Row};
 use mz_storage_types::configuration::StorageConfiguration;
 use mz_storage_types::errors::{CsrConnectError, DecodeError, DecodeErrorKind};
-use mz_storage_types::sources::encoding::{AvroEncoding, DataEncoding, RegexEncoding};
+use mz_storage_types::sources::encoding::{
+    AvroEncoding, AvroOcfEncoding, DataEncoding, RegexEncoding,
+};
 use mz_timely_util::builder_async::{
     Event as AsyncEvent, OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton,
 };
@@ -37,8 +39,9 @@ use timely::progress::Timestamp;
 use timely::scheduling::SyncActivator;
 use tracing::error;
 
-use crate::decode::avro::AvroDecoderState;
+use crate::decode::avro::{AvroDecoderState, AvroOcfDecoderState};
 use crate::decode::csv::CsvDecoderState;
+use crate::decode::json_schema::JsonSchemaDecoderState;
 use crate::decode::protobuf::ProtobufDecoderState;
 use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
 use crate::metrics::decode::DecodeMetricDefs;
@@ -46,6 +49,8 @@ use crate::source::types::{DecodeResult, SourceOutput};
 
 mod avro;
 mod csv;
+mod json_schema;
+pub(crate) mod parquet;
 mod protobuf;
 
 /// Decode delimited CDCv2 messages.
@@ -168,6 +173,7 @@ pub(crate) enum PreDelimitedFormat {
     Bytes,
     Text,
     Json,
+    JsonSchema(JsonSchemaDecoderState),
     Regex(Regex, Row),
     Protobuf(ProtobufDecoderState),
 }
@@ -190,6 +196,7 @@ impl PreDelimitedFormat {
                 })?;
                 Ok(Some(j.into_row()))
             }
+            PreDelimitedFormat::JsonSchema(json) => json.get_value(bytes),
             PreDelimitedFormat::Text => {
                 let s = std::str::from_utf8(bytes)
                     .map_err(|_| DecodeErrorKind::Text("Failed to decode UTF-8".to_string()))?;
@@ -218,6 +225,9 @@ impl PreDelimitedFormat {
 #[derive(Debug)]
 pub(crate) enum DataDecoderInner {
     Avro(AvroDecoderState),
+    /// Decodes each message into any number of rows, so it is only driven by
+    /// [`decode_delimited_records`].
+    AvroOcf(AvroOcfDecoderState),
     DelimitedBytes {
         delimiter: u8,
         format: PreDelimitedFormat,
@@ -250,6 +260,9 @@ impl DataDecoder {
                 }
             }
             DataDecoderInner::Avro(avro) => avro.decode(bytes).await?,
+            DataDecoderInner::AvroOcf(_) => {
+                unreachable!("avro ocf messages are decoded by decode_delimited_records")
+            }
             DataDecoderInner::Csv(csv) => csv.decode(bytes),
             DataDecoderInner::PreDelimited(format) => {
                 let result = format.decode(*bytes);
@@ -339,9 +352,18 @@ async fn get_decoder(
                 metrics,
             }
         }
+        DataEncoding::AvroOcf(AvroOcfEncoding { schema }) => {
+            let state = avro::AvroOcfDecoderState::new(&schema, debug_name.to_string())
+                .expect("Failed to create avro ocf decoder, even though we validated the schema in planning.");
+            DataDecoder {
+                inner: DataDecoderInner::AvroOcf(state),
+                metrics,
+            }
+        }
         DataEncoding::Text
         | DataEncoding::Bytes
         | DataEncoding::Json
+        | DataEncoding::JsonSchema(_)
         | DataEncoding::Protobuf(_)
         | DataEncoding::Regex(_) => {
             let after_delimiting = match encoding {
@@ -356,6 +378,10 @@ async fn get_decoder(
                 }
                 DataEncoding::Bytes => PreDelimitedFormat::Bytes,
                 DataEncoding::Json => PreDelimitedFormat::Json,
+                DataEncoding::JsonSchema(encoding) => PreDelimitedFormat::JsonSchema(
+                    JsonSchemaDecoderState::new(encoding)
+                        .expect("JSON schemas provided to sources are validated in planning"),
+                ),
                 DataEncoding::Text => PreDelimitedFormat::Text,
                 _ => unreachable!(),
             };
@@ -409,6 +435,27 @@ async fn decode_delimited(
     }))
 }
 
+/// Like [`decode_delimited`], but for formats whose messages each contain any
+/// number of values, like Avro object container files.
+///
+/// For all other formats this returns exactly one element.
+async fn decode_delimited_records(
+    decoder: &mut DataDecoder,
+    buf: &[u8],
+) -> Result<Vec<Option<Result<Row, DecodeError>>>, CsrConnectError> {
+    let values = match &mut decoder.inner {
+        DataDecoderInner::AvroOcf(ocf) => match ocf.decode(buf) {
+            Ok(rows) => rows.into_iter().map(|row| Some(Ok(row))).collect(),
+            Err(kind) => vec![Some(Err(DecodeError {
+                kind,
+                raw: buf.to_vec(),
+            }))],
+        },
+        _ => vec![decode_delimited(decoder, buf).await?.transpose()],
+    };
+    Ok(values)
+}
+
 /// Decode already delimited records of data.
 ///
 /// Precondition: each record in the stream has at most one key and at most one value.
@@ -495,27 +542,29 @@ pub fn render_decode_delimited<G: Scope, FromTime: Timestamp>(
                                 None => None,
                             };
 
-                            let value = match output.value.unpack_first() {
+                            let values = match output.value.unpack_first() {
                                 Datum::Bytes(buf) => {
-                                    decode_delimited(&mut value_decoder, buf).await?.transpose()
+                                    decode_delimited_records(&mut value_decoder, buf).await?
                                 }
-                                Datum::Null => None,
+                                Datum::Null => vec![None],
                                 d => unreachable!("invalid datum: {d}"),
                             };
 
-                            if matches!(&key, Some(Err(_))) || matches!(&value, Some(Err(_))) {
-                                n_errors += 1;
-                            } else if matches!(&value, Some(Ok(_))) {
-                                n_successes += 1;
-                            }
+                            for value in values {
+                                if matches!(&key, Some(Err(_))) || matches!(&value, Some(Err(_))) {
+                                    n_errors += 1;
+                                } else if matches!(&value, Some(Ok(_))) {
+                                    n_successes += 1;
+                                }
 
-                            let result = DecodeResult {
-                                key,
-                                value,
-                                metadata: output.metadata.clone(),
-                                from_time: output.from_time.clone(),
-                            };
-                            output_container.push((result, ts.clone(), *diff));
+                                let result = DecodeResult {
+                                    key: key.clone(),
+                                    value,
+                                    metadata: output.metadata.clone(),
+                                    from_time: output.from_time.clone(),
+                                };
+                                output_container.push((result, ts.clone(), *diff));
+                            }
                         }
 
                         // Matching historical practice, we only log metrics on the value decoder.
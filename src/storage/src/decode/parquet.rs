@@ -0,0 +1,228 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Decoding of Parquet files.
+//!
+//! Unlike the other decoders, which turn a single delimited message into a
+//! single row, a Parquet file contains an arbitrary number of records and
+//! carries its own schema. Every record is decoded into a single `jsonb`
+//! object whose keys are the names of the top-level Parquet columns, much like
+//! `FORMAT JSON` does for JSON documents.
+
+use std::io::Cursor;
+
+use anyhow::{anyhow, bail};
+use arrow2::array::{
+    Array, BinaryArray, BooleanArray, FixedSizeBinaryArray, ListArray, PrimitiveArray, StructArray,
+    Utf8Array,
+};
+use arrow2::datatypes::{DataType, TimeUnit};
+use arrow2::io::parquet::read::{infer_schema, read_metadata, FileReader};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use mz_repr::adt::jsonb::Jsonb;
+use mz_repr::Row;
+use serde_json::{Map, Number, Value};
+
+/// The number of days between 0001-01-01 (the chrono epoch used by
+/// [`NaiveDate::from_num_days_from_ce_opt`]) and the Unix epoch.
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+/// Decodes whole Parquet files into rows containing a single `jsonb` column.
+#[derive(Debug, Default)]
+pub struct ParquetDecoder {
+    record: Map<String, Value>,
+}
+
+impl ParquetDecoder {
+    /// Decodes every record of the Parquet file in `bytes`.
+    pub fn decode(&mut self, bytes: Vec<u8>) -> Result<Vec<Row>, anyhow::Error> {
+        let mut reader = Cursor::new(bytes);
+        let metadata = read_metadata(&mut reader)?;
+        let schema = infer_schema(&metadata)?;
+        let names: Vec<_> = schema.fields.iter().map(|f| f.name.clone()).collect();
+        let chunks = FileReader::new(reader, metadata.row_groups, schema, None, None, None);
+
+        let mut rows = vec![];
+        for chunk in chunks {
+            let chunk = chunk?;
+            for idx in 0..chunk.len() {
+                for (name, array) in names.iter().zip(chunk.arrays()) {
+                    let value = array_value(array.as_ref(), idx)
+                        .map_err(|e| anyhow!("decoding column {name}: {e}"))?;
+                    self.record.insert(name.clone(), value);
+                }
+                let record = Value::Object(std::mem::take(&mut self.record));
+                rows.push(Jsonb::from_serde_json(record)?.into_row());
+            }
+        }
+        Ok(rows)
+    }
+}
+
+/// Converts the value at position `idx` of `array` into JSON.
+///
+/// Values without a JSON counterpart are represented by their text
+/// representation, e.g. dates, timestamps and decimals become strings, and
+/// binary values become hex-encoded strings.
+fn array_value(array: &dyn Array, idx: usize) -> Result<Value, anyhow::Error> {
+    if array.is_null(idx) {
+        return Ok(Value::Null);
+    }
+
+    fn downcast<A: 'static>(array: &dyn Array) -> &A {
+        array
+            .as_any()
+            .downcast_ref::<A>()
+            .expect("array type matches its data type")
+    }
+
+    fn float(f: f64) -> Value {
+        match Number::from_f64(f) {
+            Some(n) => Value::Number(n),
+            // NaN and infinities are not representable as JSON numbers.
+            None => Value::String(f.to_string()),
+        }
+    }
+
+    fn timestamp(value: i64, unit: &TimeUnit) -> Option<NaiveDateTime> {
+        let (secs, nanos) = match unit {
+            TimeUnit::Second => (value, 0),
+            TimeUnit::Millisecond => (value.div_euclid(1_000), value.rem_euclid(1_000) * 1_000_000),
+            TimeUnit::Microsecond => (
+                value.div_euclid(1_000_000),
+                value.rem_euclid(1_000_000) * 1_000,
+            ),
+            TimeUnit::Nanosecond => (
+                value.div_euclid(1_000_000_000),
+                value.rem_euclid(1_000_000_000),
+            ),
+        };
+        NaiveDateTime::from_timestamp_opt(secs, u32::try_from(nanos).ok()?)
+    }
+
+    fn time(value: i64, unit: &TimeUnit) -> Option<NaiveTime> {
+        let nanos = match unit {
+            TimeUnit::Second => value.checked_mul(1_000_000_000)?,
+            TimeUnit::Millisecond => value.checked_mul(1_000_000)?,
+            TimeUnit::Microsecond => value.checked_mul(1_000)?,
+            TimeUnit::Nanosecond => value,
+        };
+        let secs = u32::try_from(nanos.div_euclid(1_000_000_000)).ok()?;
+        let nanos = u32::try_from(nanos.rem_euclid(1_000_000_000)).ok()?;
+        NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos)
+    }
+
+    let out_of_range = || anyhow!("value out of range for {:?}", array.data_type());
+
+    let value = match array.data_type().to_logical_type() {
+        DataType::Boolean => Value::Bool(downcast::<BooleanArray>(array).value(idx)),
+        DataType::Int8 => downcast::<PrimitiveArray<i8>>(array).value(idx).into(),
+        DataType::Int16 => downcast::<PrimitiveArray<i16>>(array).value(idx).into(),
+        DataType::Int32 => downcast::<PrimitiveArray<i32>>(array).value(idx).into(),
+        DataType::Int64 => downcast::<PrimitiveArray<i64>>(array).value(idx).into(),
+        DataType::UInt8 => downcast::<PrimitiveArray<u8>>(array).value(idx).into(),
+        DataType::UInt16 => downcast::<PrimitiveArray<u16>>(array).value(idx).into(),
+        DataType::UInt32 => downcast::<PrimitiveArray<u32>>(array).value(idx).into(),
+        DataType::UInt64 => downcast::<PrimitiveArray<u64>>(array).value(idx).into(),
+        DataType::Float32 => float(downcast::<PrimitiveArray<f32>>(array).value(idx).into()),
+        DataType::Float64 => float(downcast::<PrimitiveArray<f64>>(array).value(idx)),
+        DataType::Decimal(_precision, scale) => {
+            let unscaled = downcast::<PrimitiveArray<i128>>(array).value(idx);
+            Value::String(format_decimal(unscaled, *scale))
+        }
+        DataType::Utf8 => downcast::<Utf8Array<i32>>(array).value(idx).into(),
+        DataType::LargeUtf8 => downcast::<Utf8Array<i64>>(array).value(idx).into(),
+        DataType::Binary => format_bytes(downcast::<BinaryArray<i32>>(array).value(idx)),
+        DataType::LargeBinary => format_bytes(downcast::<BinaryArray<i64>>(array).value(idx)),
+        DataType::FixedSizeBinary(_) => {
+            format_bytes(downcast::<FixedSizeBinaryArray>(array).value(idx))
+        }
+        DataType::Date32 => {
+            let days = downcast::<PrimitiveArray<i32>>(array).value(idx);
+            let date = days
+                .checked_add(UNIX_EPOCH_DAYS_FROM_CE)
+                .and_then(NaiveDate::from_num_days_from_ce_opt)
+                .ok_or_else(out_of_range)?;
+            Value::String(date.to_string())
+        }
+        DataType::Date64 => {
+            let millis = downcast::<PrimitiveArray<i64>>(array).value(idx);
+            let ts = timestamp(millis, &TimeUnit::Millisecond).ok_or_else(out_of_range)?;
+            Value::String(ts.date().to_string())
+        }
+        DataType::Time32(unit) => {
+            let value = downcast::<PrimitiveArray<i32>>(array).value(idx);
+            let time = time(value.into(), unit).ok_or_else(out_of_range)?;
+            Value::String(time.to_string())
+        }
+        DataType::Time64(unit) => {
+            let value = downcast::<PrimitiveArray<i64>>(array).value(idx);
+            let time = time(value, unit).ok_or_else(out_of_range)?;
+            Value::String(time.to_string())
+        }
+        DataType::Timestamp(unit, tz) => {
+            let value = downcast::<PrimitiveArray<i64>>(array).value(idx);
+            let ts = timestamp(value, unit).ok_or_else(out_of_range)?;
+            // Timestamps with a time zone are normalized to UTC.
+            match tz {
+                Some(_) => Value::String(format!("{ts}+00:00")),
+                None => Value::String(ts.to_string()),
+            }
+        }
+        DataType::List(_) => list_value(downcast::<ListArray<i32>>(array).value(idx).as_ref())?,
+        DataType::LargeList(_) => {
+            list_value(downcast::<ListArray<i64>>(array).value(idx).as_ref())?
+        }
+        DataType::Struct(_) => {
+            let array = downcast::<StructArray>(array);
+            let mut object = Map::new();
+            for (field, values) in array.fields().iter().zip(array.values()) {
+                object.insert(field.name.clone(), array_value(values.as_ref(), idx)?);
+            }
+            Value::Object(object)
+        }
+        typ => bail!("unsupported Parquet type {typ:?}"),
+    };
+    Ok(value)
+}
+
+fn list_value(values: &dyn Array) -> Result<Value, anyhow::Error> {
+    let elements = (0..values.len())
+        .map(|idx| array_value(values, idx))
+        .collect::<Result<_, _>>()?;
+    Ok(Value::Array(elements))
+}
+
+fn format_bytes(bytes: &[u8]) -> Value {
+    Value::String(format!("\\x{}", hex::encode(bytes)))
+}
+
+fn format_decimal(unscaled: i128, scale: usize) -> String {
+    let digits = unscaled.unsigned_abs().to_string();
+    let sign = if unscaled < 0 { "-" } else { "" };
+    if scale == 0 {
+        return format!("{sign}{digits}");
+    }
+    let digits = format!("{digits:0>width$}", width = scale + 1);
+    let (int, frac) = digits.split_at(digits.len() - scale);
+    format!("{sign}{int}.{frac}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_format_decimal() {
+        assert_eq!(format_decimal(12345, 2), "123.45");
+        assert_eq!(format_decimal(-5, 3), "-0.005");
+        assert_eq!(format_decimal(42, 0), "42");
+        assert_eq!(format_decimal(0, 1), "0.0");
+    }
+}
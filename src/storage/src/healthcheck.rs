@@ -53,6 +53,10 @@ pub enum StatusNamespace {
     Kafka,
     Postgres,
     MySql,
+    MongoDb,
+    Http,
+    S3,
+    SqlServer,
     Ssh,
     Upsert,
     Decode,
@@ -73,6 +77,10 @@ impl fmt::Display for StatusNamespace {
             Kafka => write!(f, "kafka"),
             Postgres => write!(f, "postgres"),
             MySql => write!(f, "mysql"),
+            MongoDb => write!(f, "mongodb"),
+            Http => write!(f, "http"),
+            S3 => write!(f, "s3"),
+            SqlServer => write!(f, "sql-server"),
             Ssh => write!(f, "ssh"),
             Upsert => write!(f, "upsert"),
             Decode => write!(f, "decode"),
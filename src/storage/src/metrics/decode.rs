@@ -36,11 +36,13 @@ impl DecodeMetricDefs {
     fn counter_inc(&self, decoder: &DataDecoderInner, success: bool, n: usize) {
         let format_label = match decoder {
             DataDecoderInner::Avro(_) => "avro",
+            DataDecoderInner::AvroOcf(_) => "avro_ocf",
             DataDecoderInner::Csv(_) => "csv",
             DataDecoderInner::DelimitedBytes { format, .. }
             | DataDecoderInner::PreDelimited(format) => match format {
                 PreDelimitedFormat::Bytes => "raw",
                 PreDelimitedFormat::Json => "json",
+                PreDelimitedFormat::JsonSchema(..) => "json_schema",
                 PreDelimitedFormat::Text => "text",
                 PreDelimitedFormat::Regex(..) => "regex",
                 PreDelimitedFormat::Protobuf(..) => "protobuf",
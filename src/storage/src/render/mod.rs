@@ -286,6 +286,42 @@ pub fn build_ingestion_dataflow<A: Allocate>(
                     &feedback,
                     storage_state,
                 ),
+                GenericSourceConnection::SqlServer(c) => crate::render::sources::render_source(
+                    mz_scope,
+                    &debug_name,
+                    primary_source_id,
+                    c,
+                    description.clone(),
+                    as_of.clone(),
+                    resume_uppers.clone(),
+                    source_resume_uppers,
+                    &feedback,
+                    storage_state,
+                ),
+                GenericSourceConnection::MongoDb(c) => crate::render::sources::render_source(
+                    mz_scope,
+                    &debug_name,
+                    primary_source_id,
+                    c,
+                    description.clone(),
+                    as_of.clone(),
+                    resume_uppers.clone(),
+                    source_resume_uppers,
+                    &feedback,
+                    storage_state,
+                ),
+                GenericSourceConnection::S3(c) => crate::render::sources::render_source(
+                    mz_scope,
+                    &debug_name,
+                    primary_source_id,
+                    c,
+                    description.clone(),
+                    as_of.clone(),
+                    resume_uppers.clone(),
+                    source_resume_uppers,
+                    &feedback,
+                    storage_state,
+                ),
                 GenericSourceConnection::LoadGenerator(c) => crate::render::sources::render_source(
                     mz_scope,
                     &debug_name,
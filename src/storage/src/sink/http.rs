@@ -0,0 +1,688 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Code to render the sink dataflow of an [`HttpSinkConnection`]. The dataflow consists of a
+//! stateless encoding step followed by a single operator that `POST`s the encoded updates to the
+//! configured endpoint.
+//!
+//! ```text
+//!        ┏━━━━━━━━━━━━━━┓
+//!        ┃   persist    ┃
+//!        ┃    source    ┃
+//!        ┗━━━━━━┯━━━━━━━┛
+//!               │ row data, the input to this module
+//!               │
+//!        ┏━━━━━━v━━━━━━┓
+//!        ┃    json     ┃
+//!        ┃   encoder   ┃
+//!        ┗━━━━━━┯━━━━━━┛
+//!               │ encoded data
+//!               │
+//!        ┏━━━━━━v━━━━━━┓
+//!        ┃    http     ┃ (single worker)
+//!        ┃    sink     ┃
+//!        ┗━━━━━━┯━━━━━━┛
+//!               │ POST requests
+//!           ╭───v──────╮
+//!           │ endpoint │
+//!           ╰──────────╯
+//! ```
+//!
+//! # Batching
+//!
+//! Updates are buffered until the input frontier advances past their timestamp. All updates of
+//! a timestamp are then sorted, so that they are produced in the same order by every incarnation
+//! of the sink, and split into batches of at most [`MAX_BATCH_RECORDS`] records or
+//! [`MAX_BATCH_BYTES`] bytes. Each batch is sent as a JSON array of `{"key": ..., "value": ...}`
+//! objects in the body of a single `POST` request. Timestamps are sent in ascending order and
+//! batches of the same timestamp are sent in order, one request at a time.
+//!
+//! Every request carries the following headers:
+//!
+//! * `Idempotency-Key`: `<sink id>-<timestamp>-<batch index>`.
+//! * `Materialize-Timestamp`: the timestamp of all the updates in the request.
+//! * `Materialize-Batch-Index` and `Materialize-Batch-Count`: the position of the request among
+//!   all the requests of its timestamp.
+//! * `Materialize-Progress-Key`: `<environment id>-<sink id>`, which identifies the sink across
+//!   restarts and environments.
+//! * `Materialize-Upper`: the upper frontier of the sink once the request has been applied,
+//!   either a timestamp or `null` for the empty frontier. Only the last batch of a timestamp
+//!   advances the upper past that timestamp.
+//! * `Materialize-Signature`: `sha256=<hex digest>`, the HMAC-SHA256 of the request body keyed
+//!   with the connection's `HMAC SECRET`, if one is configured.
+//!
+//! When the input frontier advances without any updates, the sink sends a request with an empty
+//! JSON array as its body, no timestamp or batch headers, and an `Idempotency-Key` of
+//! `<sink id>-progress-<upper>`, so that the endpoint can record the new upper.
+//!
+//! # Exactly once
+//!
+//! Like the progress topic of Kafka sinks, the progress of an HTTP sink is stored in the system
+//! it writes to. The endpoint must apply each request and record its `Materialize-Upper` for its
+//! `Materialize-Progress-Key` atomically. On startup the sink sends a `GET` request with the
+//! `Materialize-Progress-Key` header to the connection's URL, to which the endpoint responds
+//! with `{"upper": <timestamp or null>}`, or with `404 Not Found` if it has not recorded any
+//! progress for the sink yet. The sink then skips all updates that are not beyond the recorded
+//! upper, which makes it resumable without sending any update twice.
+//!
+//! A request whose response was lost is retried with the same `Idempotency-Key`, which endpoints
+//! use to ignore requests they have already applied.
+//!
+//! # Retries
+//!
+//! Requests that fail with a connection error, a `408`, a `429`, or a `5xx` status are retried
+//! with exponential backoff. Any other status is considered a permanent failure. Either kind of
+//! failure is eventually wired up with our health monitoring and will trigger a restart of the
+//! sink dataflow.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use differential_dataflow::{Collection, Hashable};
+use hmac::{Hmac, Mac};
+use mz_interchange::encode::Encode;
+use mz_interchange::json::JsonEncoder;
+use mz_ore::cast::CastFrom;
+use mz_ore::error::ErrorExt;
+use mz_ore::retry::{Retry, RetryResult};
+use mz_repr::{Diff, GlobalId, Row, Timestamp};
+use mz_storage_types::configuration::StorageConfiguration;
+use mz_storage_types::errors::DataflowError;
+use mz_storage_types::sinks::{HttpSinkConnection, MetadataFilled, SinkEnvelope, StorageSinkDesc};
+use mz_timely_util::antichain::AntichainExt;
+use mz_timely_util::builder_async::{
+    Event, OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton,
+};
+use reqwest::header::CONTENT_TYPE;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use sha2::Sha256;
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::{Concatenate, Map, ToStream};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::{Antichain, Timestamp as _};
+use timely::PartialOrder;
+use tracing::{error, info};
+
+use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
+use crate::render::sinks::SinkRender;
+use crate::statistics::SinkStatistics;
+use crate::storage_state::StorageState;
+
+/// The maximum number of records sent in a single request.
+const MAX_BATCH_RECORDS: usize = 1000;
+/// The maximum number of encoded bytes sent in a single request. A single record larger than
+/// this limit is sent in a request of its own.
+const MAX_BATCH_BYTES: usize = 1 << 20;
+/// The timeout of a single request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// How long a single batch is retried before the sink reports an error and restarts.
+const MAX_RETRY_DURATION: Duration = Duration::from_secs(300);
+
+impl<G: Scope<Timestamp = Timestamp>> SinkRender<G> for HttpSinkConnection {
+    fn uses_keys(&self) -> bool {
+        true
+    }
+
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        self.key_desc_and_indices
+            .as_ref()
+            .map(|(_desc, indices)| indices.as_slice())
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        self.relation_key_indices.as_deref()
+    }
+
+    fn render_continuous_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<MetadataFilled, Timestamp>,
+        sink_id: GlobalId,
+        input: Collection<G, (Option<Row>, Option<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+    ) -> (Stream<G, HealthStatusMessage>, Vec<PressOnDropButton>) {
+        let mut scope = input.scope();
+
+        let write_frontier = Rc::new(RefCell::new(Antichain::from_elem(Timestamp::minimum())));
+        storage_state
+            .sink_write_frontiers
+            .insert(sink_id, Rc::clone(&write_frontier));
+
+        let encoded = encode_collection(&input, sink.envelope, self);
+
+        let statistics = storage_state
+            .aggregated_statistics
+            .get_sink(&sink_id)
+            .expect("statistics initialized")
+            .clone();
+
+        let (sink_status, sink_token) = sink_collection(
+            format!("http-{sink_id}-sink"),
+            &encoded,
+            sink_id,
+            self.clone(),
+            storage_state.storage_configuration.clone(),
+            sink.as_of.clone(),
+            statistics,
+            write_frontier,
+        );
+
+        let running_status = Some(HealthStatusMessage {
+            index: 0,
+            update: HealthStatusUpdate::Running,
+            namespace: StatusNamespace::Http,
+        })
+        .to_stream(&mut scope);
+
+        let status = scope.concatenate([running_status, sink_status]);
+
+        (status, vec![sink_token])
+    }
+}
+
+/// Encodes a stream of `(Option<Row>, Option<Row>)` updates as JSON.
+fn encode_collection<G: Scope>(
+    input: &Collection<G, (Option<Row>, Option<Row>), Diff>,
+    envelope: SinkEnvelope,
+    connection: &HttpSinkConnection,
+) -> Collection<G, (Option<Vec<u8>>, Option<Vec<u8>>), Diff> {
+    let encoder = JsonEncoder::new(
+        connection
+            .key_desc_and_indices
+            .as_ref()
+            .map(|(desc, _indices)| desc.clone()),
+        connection.value_desc.clone(),
+        matches!(envelope, SinkEnvelope::Debezium),
+    );
+    input.map(move |(key, value)| {
+        let key = key.map(|key| encoder.encode_key_unchecked(key));
+        let value = value.map(|value| encoder.encode_value_unchecked(value));
+        (key, value)
+    })
+}
+
+/// Sends batches of encoded updates to the endpoint of an [`HttpSinkConnection`] and records the
+/// progress of the sink there.
+struct HttpSender {
+    sink_id: GlobalId,
+    client: reqwest::Client,
+    url: reqwest::Url,
+    /// The key under which the endpoint records the progress of this sink.
+    progress_key: String,
+    /// The key used to sign request bodies, if any.
+    hmac_key: Option<Vec<u8>>,
+    /// The upper last acknowledged by the endpoint.
+    upper: Antichain<Timestamp>,
+    /// A handle to the statistics associated with this sink.
+    statistics: SinkStatistics,
+}
+
+/// The response of an endpoint to a progress request.
+#[derive(Debug, Deserialize)]
+struct ProgressResponse {
+    upper: Option<u64>,
+}
+
+impl HttpSender {
+    /// Returns a sender that resumes from the upper the endpoint has recorded for this sink.
+    async fn new(
+        sink_id: GlobalId,
+        client: reqwest::Client,
+        url: reqwest::Url,
+        progress_key: String,
+        hmac_key: Option<Vec<u8>>,
+        statistics: SinkStatistics,
+    ) -> Result<Self, anyhow::Error> {
+        let mut sender = HttpSender {
+            sink_id,
+            client,
+            url,
+            progress_key,
+            hmac_key,
+            upper: Antichain::from_elem(Timestamp::minimum()),
+            statistics,
+        };
+        sender.upper = sender.fetch_upper().await?;
+        Ok(sender)
+    }
+
+    /// Reads the upper the endpoint has recorded for this sink, retrying transient failures.
+    async fn fetch_upper(&self) -> Result<Antichain<Timestamp>, anyhow::Error> {
+        Retry::default()
+            .initial_backoff(Duration::from_millis(100))
+            .clamp_backoff(Duration::from_secs(30))
+            .max_duration(MAX_RETRY_DURATION)
+            .retry_async(|_| async move {
+                let request = self
+                    .client
+                    .get(self.url.clone())
+                    .header("materialize-progress-key", self.progress_key.as_str());
+                match request.send().await {
+                    Ok(response) if response.status() == StatusCode::NOT_FOUND => {
+                        RetryResult::Ok(Antichain::from_elem(Timestamp::minimum()))
+                    }
+                    Ok(response) if response.status().is_success() => {
+                        let body = match response.bytes().await {
+                            Ok(body) => body,
+                            Err(err) => {
+                                return RetryResult::RetryableErr(
+                                    anyhow::Error::from(err)
+                                        .context("reading sink progress from HTTP endpoint"),
+                                )
+                            }
+                        };
+                        match serde_json::from_slice::<ProgressResponse>(&body) {
+                            Ok(ProgressResponse { upper }) => {
+                                RetryResult::Ok(upper.map(Timestamp::from).into_iter().collect())
+                            }
+                            Err(err) => RetryResult::FatalErr(
+                                anyhow::Error::from(err)
+                                    .context("decoding sink progress from HTTP endpoint"),
+                            ),
+                        }
+                    }
+                    Ok(response) => response_error(response.status()),
+                    Err(err) => RetryResult::RetryableErr(
+                        anyhow::Error::from(err)
+                            .context("reading sink progress from HTTP endpoint"),
+                    ),
+                }
+            })
+            .await
+    }
+
+    /// Sends all the updates of timestamp `time` and advances the upper of the sink to
+    /// `new_upper`, returning once every batch has been acknowledged by the endpoint.
+    async fn send_time(
+        &mut self,
+        time: Timestamp,
+        records: &[(Option<Vec<u8>>, Option<Vec<u8>>)],
+        new_upper: Antichain<Timestamp>,
+    ) -> Result<(), anyhow::Error> {
+        let batches = encode_batches(records);
+        let count = batches.len();
+        for (index, (body, records)) in batches.into_iter().enumerate() {
+            // Only the last batch of a timestamp completes it.
+            let upper = if index + 1 == count {
+                new_upper.clone()
+            } else {
+                Antichain::from_elem(time)
+            };
+            let bytes = u64::cast_from(body.len());
+            self.statistics.inc_messages_staged_by(records);
+            self.statistics.inc_bytes_staged_by(bytes);
+            let batch = Some(BatchHeaders { time, index, count });
+            self.post(batch, &upper, body).await?;
+            self.statistics.inc_messages_committed_by(records);
+            self.statistics.inc_bytes_committed_by(bytes);
+        }
+        self.upper = new_upper;
+        Ok(())
+    }
+
+    /// Advances the upper of the sink to `new_upper` without sending any updates.
+    async fn send_progress(
+        &mut self,
+        new_upper: Antichain<Timestamp>,
+    ) -> Result<(), anyhow::Error> {
+        self.post(None, &new_upper, b"[]".to_vec()).await?;
+        self.upper = new_upper;
+        Ok(())
+    }
+
+    /// Sends a single request, retrying transient failures.
+    async fn post(
+        &self,
+        batch: Option<BatchHeaders>,
+        upper: &Antichain<Timestamp>,
+        body: Vec<u8>,
+    ) -> Result<(), anyhow::Error> {
+        let upper = encode_upper(upper);
+        let idempotency_key = match &batch {
+            Some(BatchHeaders { time, index, .. }) => {
+                format!("{}-{}-{}", self.sink_id, time, index)
+            }
+            None => format!("{}-progress-{}", self.sink_id, upper),
+        };
+        let signature = self.hmac_key.as_ref().map(|key| sign(key, &body));
+
+        let body = &body;
+        let batch = &batch;
+        let upper = &upper;
+        let idempotency_key = &idempotency_key;
+        let signature = &signature;
+        Retry::default()
+            .initial_backoff(Duration::from_millis(100))
+            .clamp_backoff(Duration::from_secs(30))
+            .max_duration(MAX_RETRY_DURATION)
+            .retry_async(|_| async move {
+                let mut request = self
+                    .client
+                    .post(self.url.clone())
+                    .header(CONTENT_TYPE, "application/json")
+                    .header("idempotency-key", idempotency_key.as_str())
+                    .header("materialize-progress-key", self.progress_key.as_str())
+                    .header("materialize-upper", upper.as_str())
+                    .body(body.clone());
+                if let Some(BatchHeaders { time, index, count }) = batch {
+                    request = request
+                        .header("materialize-timestamp", time.to_string())
+                        .header("materialize-batch-index", index.to_string())
+                        .header("materialize-batch-count", count.to_string());
+                }
+                if let Some(signature) = signature {
+                    request =
+                        request.header("materialize-signature", format!("sha256={signature}"));
+                }
+                match request.send().await {
+                    Ok(response) if response.status().is_success() => RetryResult::Ok(()),
+                    Ok(response) => response_error(response.status()),
+                    Err(err) => RetryResult::RetryableErr(
+                        anyhow::Error::from(err).context("sending request to HTTP endpoint"),
+                    ),
+                }
+            })
+            .await
+    }
+}
+
+/// The position of a request among all the requests of its timestamp.
+struct BatchHeaders {
+    time: Timestamp,
+    index: usize,
+    count: usize,
+}
+
+/// Classifies an unsuccessful response of the endpoint as a transient or permanent failure.
+fn response_error<T>(status: StatusCode) -> RetryResult<T, anyhow::Error> {
+    let err = anyhow!("HTTP endpoint responded with {status}");
+    if is_retryable(status) {
+        RetryResult::RetryableErr(err)
+    } else {
+        RetryResult::FatalErr(err)
+    }
+}
+
+/// Encodes an upper frontier for the `Materialize-Upper` header.
+fn encode_upper(upper: &Antichain<Timestamp>) -> String {
+    match upper.as_option() {
+        Some(t) => t.to_string(),
+        None => "null".into(),
+    }
+}
+
+/// Reports whether a request that failed with `status` may succeed when retried.
+fn is_retryable(status: StatusCode) -> bool {
+    status.is_server_error()
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+/// Computes the hex-encoded HMAC-SHA256 of `body` keyed with `key`.
+fn sign(key: &[u8], body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key size");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Splits the encoded updates of a single timestamp into request bodies, returning each body
+/// along with the number of records it contains.
+///
+/// Each body is a JSON array of `{"key": ..., "value": ...}` objects where absent keys and values
+/// are encoded as `null`.
+fn encode_batches(records: &[(Option<Vec<u8>>, Option<Vec<u8>>)]) -> Vec<(Vec<u8>, u64)> {
+    const NULL: &[u8] = b"null";
+
+    let mut batches = vec![];
+    let mut body = vec![];
+    let mut count = 0;
+    for (key, value) in records {
+        let key = key.as_deref().unwrap_or(NULL);
+        let value = value.as_deref().unwrap_or(NULL);
+        let record_len = key.len() + value.len();
+        if count > 0 && (count == MAX_BATCH_RECORDS || body.len() + record_len > MAX_BATCH_BYTES) {
+            body.push(b']');
+            batches.push((std::mem::take(&mut body), u64::cast_from(count)));
+            count = 0;
+        }
+        body.push(if count == 0 { b'[' } else { b',' });
+        body.extend_from_slice(b"{\"key\":");
+        body.extend_from_slice(key);
+        body.extend_from_slice(b",\"value\":");
+        body.extend_from_slice(value);
+        body.push(b'}');
+        count += 1;
+    }
+    if count > 0 {
+        body.push(b']');
+        batches.push((body, u64::cast_from(count)));
+    }
+    batches
+}
+
+/// Sinks a collection of encoded rows to an HTTP endpoint.
+///
+/// This operator exchanges all updates to a single worker by hashing on the given sink `id`.
+///
+/// Updates are sent in ascending timestamp order.
+fn sink_collection<G: Scope<Timestamp = Timestamp>>(
+    name: String,
+    input: &Collection<G, (Option<Vec<u8>>, Option<Vec<u8>>), Diff>,
+    sink_id: GlobalId,
+    connection: HttpSinkConnection,
+    storage_configuration: StorageConfiguration,
+    as_of: Antichain<Timestamp>,
+    statistics: SinkStatistics,
+    write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+) -> (Stream<G, HealthStatusMessage>, PressOnDropButton) {
+    let scope = input.scope();
+    let mut builder = AsyncOperatorBuilder::new(name.clone(), input.inner.scope());
+
+    // We want exactly one worker to send all the data to the endpoint.
+    let hashed_id = sink_id.hashed();
+    let is_active_worker = usize::cast_from(hashed_id) % scope.peers() == scope.index();
+
+    let mut input = builder.new_disconnected_input(&input.inner, Exchange::new(move |_| hashed_id));
+
+    let (button, errors) = builder.build_fallible(move |_caps| {
+        Box::pin(async move {
+            if !is_active_worker {
+                write_frontier.borrow_mut().clear();
+                return Ok(());
+            }
+
+            let hmac_key = connection
+                .connection
+                .hmac_key(&storage_configuration)
+                .await?;
+            let client = reqwest::Client::builder()
+                .timeout(REQUEST_TIMEOUT)
+                .build()?;
+            let progress_key = format!(
+                "{}-{}",
+                storage_configuration.connection_context.environment_id, sink_id
+            );
+            let mut sender = HttpSender::new(
+                sink_id,
+                client,
+                connection.connection.url.clone(),
+                progress_key,
+                hmac_key,
+                statistics,
+            )
+            .await?;
+            let resume_upper = sender.upper.clone();
+
+            // The input has overcompacted if
+            let overcompacted =
+                // ..we have made some progress in the past
+                *resume_upper != [Timestamp::minimum()] &&
+                // ..but the since frontier is now beyond that
+                !PartialOrder::less_equal(&as_of, &resume_upper);
+            if overcompacted {
+                let err = format!(
+                    "{name}: input compacted past resume upper: as_of {}, resume_upper: {}",
+                    as_of.pretty(),
+                    resume_upper.pretty()
+                );
+                // This would normally be an assertion but because it can happen after a
+                // Materialize backup/restore we log an error so that it appears on Sentry but
+                // leaves the rest of the objects in the cluster unaffected.
+                error!("{err}");
+                return Err(anyhow!("{err}"));
+            }
+
+            info!(
+                "{name}: as_of: {}, resume upper: {}",
+                as_of.pretty(),
+                resume_upper.pretty()
+            );
+            *write_frontier.borrow_mut() = resume_upper.clone();
+            if resume_upper.is_empty() {
+                return Ok(());
+            }
+
+            // The section below relies on TotalOrder for correctness so we'll work with timestamps
+            // directly to make sure this doesn't compile if someone attempts to make this operator
+            // generic over partial orders in the future.
+            let mut pending: BTreeMap<Timestamp, Vec<(Option<Vec<u8>>, Option<Vec<u8>>)>> =
+                BTreeMap::new();
+            while let Some(event) = input.next().await {
+                match event {
+                    Event::Data(_cap, batch) => {
+                        for ((key, value), time, diff) in batch {
+                            // Updates not beyond the resume upper have already been sent.
+                            if !resume_upper.less_equal(&time) {
+                                continue;
+                            }
+                            assert_eq!(diff, 1, "invalid sink update");
+                            pending.entry(time).or_default().push((key, value));
+                        }
+                    }
+                    Event::Progress(progress) => {
+                        // Ignore progress updates before our resumption frontier
+                        if !PartialOrder::less_equal(&resume_upper, &progress) {
+                            continue;
+                        }
+                        // Also ignore progress updates until we are past the as_of frontier. The
+                        // snapshot appears at the as_of and we must not report any progress
+                        // before it has been sent, as an earlier as_of with strictly more
+                        // information could otherwise be skipped on restart. See the Kafka
+                        // sink for a detailed description of the scenario.
+                        if !as_of.iter().all(|t| !progress.less_equal(t)) {
+                            continue;
+                        }
+                        let ready = match progress.as_option() {
+                            Some(upper) => {
+                                let not_ready = pending.split_off(upper);
+                                std::mem::replace(&mut pending, not_ready)
+                            }
+                            None => std::mem::take(&mut pending),
+                        };
+
+                        let mut ready = ready.into_iter().peekable();
+                        while let Some((time, mut records)) = ready.next() {
+                            // The last ready timestamp advances the upper all the way to the
+                            // input frontier, which saves a progress request per progress update.
+                            let new_upper = match ready.peek() {
+                                Some(_) => Antichain::from_elem(time.step_forward()),
+                                None => progress.clone(),
+                            };
+                            // Sort the records so that every incarnation of the sink produces
+                            // identical batches, and therefore identical idempotency keys.
+                            records.sort_unstable();
+                            sender.send_time(time, &records, new_upper).await?;
+                        }
+                        if sender.upper != progress {
+                            sender.send_progress(progress.clone()).await?;
+                        }
+
+                        info!("{name}: committed frontier {}", progress.pretty());
+                        *write_frontier.borrow_mut() = progress.clone();
+                        if progress.is_empty() {
+                            break;
+                        }
+                    }
+                }
+            }
+            Ok::<(), anyhow::Error>(())
+        })
+    });
+
+    let statuses = errors.map(|error: Rc<anyhow::Error>| HealthStatusMessage {
+        index: 0,
+        update: HealthStatusUpdate::halting(format!("{}", error.display_with_causes()), None),
+        namespace: StatusNamespace::Http,
+    });
+
+    (statuses, button.press_on_drop())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_encode_batches() {
+        let records = vec![
+            (
+                Some(br#"{"a":1}"#.to_vec()),
+                Some(br#"{"a":1,"b":2}"#.to_vec()),
+            ),
+            (None, Some(br#"{"a":2,"b":3}"#.to_vec())),
+            (Some(br#"{"a":3}"#.to_vec()), None),
+        ];
+        let batches = encode_batches(&records);
+        assert_eq!(batches.len(), 1);
+        let (body, count) = &batches[0];
+        assert_eq!(*count, 3);
+        let body: serde_json::Value = serde_json::from_slice(body).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!([
+                {"key": {"a": 1}, "value": {"a": 1, "b": 2}},
+                {"key": null, "value": {"a": 2, "b": 3}},
+                {"key": {"a": 3}, "value": null},
+            ])
+        );
+
+        let records = vec![(None, Some(b"1".to_vec())); MAX_BATCH_RECORDS + 1];
+        let batches = encode_batches(&records);
+        let counts: Vec<_> = batches.iter().map(|(_, count)| *count).collect();
+        assert_eq!(counts, [u64::cast_from(MAX_BATCH_RECORDS), 1]);
+        for (body, _) in batches {
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap();
+        }
+
+        assert!(encode_batches(&[]).is_empty());
+    }
+
+    #[mz_ore::test]
+    fn test_encode_upper() {
+        assert_eq!(encode_upper(&Antichain::from_elem(Timestamp::from(5))), "5");
+        assert_eq!(encode_upper(&Antichain::new()), "null");
+        let ProgressResponse { upper } = serde_json::from_str(r#"{"upper": 5}"#).unwrap();
+        assert_eq!(upper, Some(5));
+        let ProgressResponse { upper } = serde_json::from_str(r#"{"upper": null}"#).unwrap();
+        assert_eq!(upper, None);
+    }
+
+    #[mz_ore::test]
+    fn test_sign() {
+        // Test vector from RFC 4231, test case 2.
+        assert_eq!(
+            sign(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
@@ -41,6 +41,10 @@
 //! initialization step that makes sure the schemas are published to the Schema Registry. After
 //! that step the operator just encodes each batch it receives record by record.
 //!
+//! The encoding step also evaluates the sink's `HEADERS` column and `PARTITION BY` expression, if
+//! any, because both need the unencoded rows. Records with a partition hash are explicitly
+//! assigned to partition `hash % partition_count` when they are produced.
+//!
 //! # Sinking
 //!
 //! The other part of the dataflow, and what this module mostly deals with, is interacting with the
@@ -81,12 +85,13 @@ use differential_dataflow::{AsCollection, Collection, Hashable};
 use mz_interchange::avro::{AvroEncoder, AvroSchemaGenerator, AvroSchemaOptions};
 use mz_interchange::encode::Encode;
 use mz_interchange::json::JsonEncoder;
+use mz_interchange::protobuf::{ProtobufEncoder, ProtobufSchemaGenerator, ProtobufSchemaOptions};
 use mz_kafka_util::client::{MzClientContext, TunnelingClientContext};
 use mz_ore::cast::CastFrom;
 use mz_ore::error::ErrorExt;
 use mz_ore::task;
 use mz_ore::vec::VecExt;
-use mz_repr::{Diff, GlobalId, Row, Timestamp};
+use mz_repr::{Datum, Diff, GlobalId, Row, RowArena, Timestamp};
 use mz_storage_client::sink::progress_key::ProgressKey;
 use mz_storage_client::sink::{ProgressRecord, TopicCleanupPolicy, TopicConfig};
 use mz_storage_types::configuration::StorageConfiguration;
@@ -104,6 +109,7 @@ use rdkafka::metadata::Metadata;
 use rdkafka::producer::{BaseProducer, BaseRecord, Producer};
 use rdkafka::types::RDKafkaErrorCode;
 use rdkafka::Statistics;
+use serde::{Deserialize, Serialize};
 use timely::dataflow::channels::pact::{Exchange, Pipeline};
 use timely::dataflow::operators::{CapabilitySet, Concatenate, Map, ToStream};
 use timely::dataflow::{Scope, Stream};
@@ -213,6 +219,9 @@ struct TransactionalProducer {
     socket_timeout: Duration,
     /// The maximum duration of a transaction.
     transaction_timeout: Duration,
+    /// The number of partitions of the data topic, if records are explicitly assigned to
+    /// partitions.
+    partition_count: Option<u64>,
 }
 
 impl TransactionalProducer {
@@ -293,6 +302,7 @@ impl TransactionalProducer {
             staged_bytes: 0,
             socket_timeout: timeout_config.socket_timeout,
             transaction_timeout: timeout_config.transaction_timeout,
+            partition_count: None,
         };
 
         let timeout = timeout_config.socket_timeout;
@@ -324,6 +334,26 @@ impl TransactionalProducer {
             .await
     }
 
+    /// Looks up the number of partitions of the data topic, which records with a hash are
+    /// distributed over from now on.
+    ///
+    /// Partitions added to the topic later are not used until the sink restarts.
+    async fn fetch_partition_count(&mut self) -> Result<(), ContextCreationError> {
+        let meta = self.fetch_metadata().await?;
+        let topic = meta
+            .topics()
+            .iter()
+            .find(|t| t.name() == self.data_topic)
+            .ok_or_else(|| anyhow!("sink data topic {} is missing", self.data_topic))?;
+        match u64::cast_from(topic.partitions().len()) {
+            0 => Err(anyhow!("sink data topic {} has no partitions", self.data_topic).into()),
+            count => {
+                self.partition_count = Some(count);
+                Ok(())
+            }
+        }
+    }
+
     async fn begin_transaction(&mut self) -> Result<(), ContextCreationError> {
         self.spawn_blocking(|p| p.begin_transaction()).await
     }
@@ -335,23 +365,36 @@ impl TransactionalProducer {
     /// the system.
     async fn send(
         &mut self,
-        key: Option<&[u8]>,
-        value: Option<&[u8]>,
+        message: &KafkaMessage,
         time: Timestamp,
         diff: Diff,
     ) -> Result<(), ContextCreationError> {
         assert_eq!(diff, 1, "invalid sink update");
 
-        let headers = OwnedHeaders::new().insert(Header {
+        let mut headers = OwnedHeaders::new().insert(Header {
             key: "materialize-timestamp",
             value: Some(time.to_string().as_bytes()),
         });
+        for header in &message.headers {
+            headers = headers.insert(Header {
+                key: &header.key,
+                value: header.value.as_deref(),
+            });
+        }
+        let partition = match (message.hash, self.partition_count) {
+            (Some(hash), Some(count)) => {
+                Some(i32::try_from(hash % count).expect("partition counts are at most i32::MAX"))
+            }
+            _ => None,
+        };
+        let key = message.key.as_deref();
+        let value = message.value.as_deref();
         let record = BaseRecord {
             topic: &self.data_topic,
             key,
             payload: value,
             headers: Some(headers),
-            partition: None,
+            partition,
             timestamp: None,
             delivery_opaque: (),
         };
@@ -488,7 +531,7 @@ async fn collect_statistics(mut receiver: watch::Receiver<Statistics>, metrics:
 /// Updates are sent in ascending timestamp order.
 fn sink_collection<G: Scope<Timestamp = Timestamp>>(
     name: String,
-    input: &Collection<G, (Option<Vec<u8>>, Option<Vec<u8>>), Diff>,
+    input: &Collection<G, KafkaMessage, Diff>,
     sink_id: GlobalId,
     connection: KafkaSinkConnection,
     storage_configuration: StorageConfiguration,
@@ -568,6 +611,10 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
                 }
             };
 
+            if connection.partition_by.is_some() {
+                producer.fetch_partition_count().await?;
+            }
+
             // The input has overcompacted if
             let overcompacted =
                 // ..we have made some progress in the past
@@ -608,7 +655,7 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
             while let Some(event) = input.next().await {
                 match event {
                     Event::Data(_cap, batch) => {
-                        for ((key, value), time, diff) in batch {
+                        for (message, time, diff) in batch {
                             // We want to publish updates in time order and we know that we have
                             // already committed all times not beyond `upper`. Therefore, if this
                             // update happens *exactly* at upper then it is the minimum pending
@@ -620,15 +667,13 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
                             // can be *exactly* at upper but we can't know ahead of time which one
                             // will be advanced in the next progress message.
                             match upper.cmp(&time) {
-                                Ordering::Less => deferred_updates.push(((key, value), time, diff)),
+                                Ordering::Less => deferred_updates.push((message, time, diff)),
                                 Ordering::Equal => {
                                     if !transaction_begun {
                                         producer.begin_transaction().await?;
                                         transaction_begun = true;
                                     }
-                                    producer
-                                        .send(key.as_deref(), value.as_deref(), time, diff)
-                                        .await?;
+                                    producer.send(&message, time, diff).await?;
                                 }
                                 Ordering::Greater => continue,
                             }
@@ -669,10 +714,8 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
                                 .drain_filter_swapping(|(_, time, _)| !progress.less_equal(time)),
                         );
                         extra_updates.sort_unstable_by(|a, b| a.1.cmp(&b.1));
-                        for ((key, value), time, diff) in extra_updates.drain(..) {
-                            producer
-                                .send(key.as_deref(), value.as_deref(), time, diff)
-                                .await?;
+                        for (message, time, diff) in extra_updates.drain(..) {
+                            producer.send(&message, time, diff).await?;
                         }
 
                         info!("{name}: committing transaction for {}", progress.pretty());
@@ -719,6 +762,25 @@ fn sink_collection<G: Scope<Timestamp = Timestamp>>(
     (statuses, button.press_on_drop())
 }
 
+/// A record to produce to the data topic.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct KafkaMessage {
+    /// The result of the sink's `PARTITION BY` expression, which determines the partition of the
+    /// record. Records without a hash are partitioned by librdkafka.
+    hash: Option<u64>,
+    key: Option<Vec<u8>>,
+    value: Option<Vec<u8>>,
+    /// Headers from the sink's `HEADERS` column, in addition to the headers that are always
+    /// emitted.
+    headers: Vec<KafkaHeader>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct KafkaHeader {
+    key: String,
+    value: Option<Vec<u8>>,
+}
+
 /// Encodes a stream of `(Option<Row>, Option<Row>)` updates using the specified encoder.
 ///
 /// Input [`Row`] updates must me compatible with the given implementor of [`Encode`].
@@ -729,7 +791,7 @@ fn encode_collection<G: Scope>(
     connection: KafkaSinkConnection,
     storage_configuration: StorageConfiguration,
 ) -> (
-    Collection<G, (Option<Vec<u8>>, Option<Vec<u8>>), Diff>,
+    Collection<G, KafkaMessage, Diff>,
     Stream<G, HealthStatusMessage>,
     PressOnDropButton,
 ) {
@@ -746,6 +808,8 @@ fn encode_collection<G: Scope>(
                 .as_ref()
                 .map(|(desc, _indices)| desc.clone());
             let value_desc = connection.value_desc;
+            let headers_index = connection.headers_index;
+            let partition_by = connection.partition_by;
 
             let encoder: Box<dyn Encode> = match connection.format {
                 KafkaSinkFormat::Avro {
@@ -788,6 +852,36 @@ fn encode_collection<G: Scope>(
                     value_desc,
                     matches!(envelope, SinkEnvelope::Debezium),
                 )),
+                KafkaSinkFormat::Protobuf {
+                    key_schema,
+                    value_schema,
+                    csr_connection,
+                } => {
+                    let ccsr = csr_connection.connect(&storage_configuration).await?;
+                    let (key_schema_id, value_schema_id) =
+                        mz_storage_client::sink::publish_kafka_schemas(
+                            &ccsr,
+                            &connection.topic,
+                            key_schema.as_deref(),
+                            Some(mz_ccsr::SchemaType::Protobuf),
+                            &value_schema,
+                            mz_ccsr::SchemaType::Protobuf,
+                        )
+                        .await
+                        .context("error publishing kafka schemas for sink")?;
+
+                    let options = ProtobufSchemaOptions {
+                        is_debezium: matches!(envelope, SinkEnvelope::Debezium),
+                    };
+                    let schema_generator =
+                        ProtobufSchemaGenerator::new(key_desc, value_desc, options)
+                            .expect("protobuf schema validated");
+                    Box::new(ProtobufEncoder::new(
+                        schema_generator,
+                        key_schema_id,
+                        value_schema_id,
+                    ))
+                }
             };
 
             // !IMPORTANT!
@@ -800,9 +894,44 @@ fn encode_collection<G: Scope>(
             while let Some(event) = input.next().await {
                 if let Event::Data(cap, rows) = event {
                     for ((key, value), time, diff) in rows {
+                        let mut hash = None;
+                        let mut headers = vec![];
+                        if headers_index.is_some() || partition_by.is_some() {
+                            let key_datums = key.as_ref().map(|key| key.unpack());
+                            let value_datums = value
+                                .as_ref()
+                                .map(|value| sinked_datums(value.unpack(), envelope));
+                            if let (Some(index), Some(datums)) = (headers_index, &value_datums) {
+                                headers = encode_headers(datums[index]);
+                            }
+                            if let Some(partition_by) = &partition_by {
+                                // The expression refers to the key if the sink has one, and to the
+                                // value otherwise.
+                                let datums = match (&key_datums, &value_datums) {
+                                    (Some(datums), _) => Some(&datums[..]),
+                                    (None, Some(datums)) => Some(&datums[..]),
+                                    (None, None) => None,
+                                };
+                                let temp_storage = RowArena::new();
+                                hash = Some(
+                                    match datums.map(|d| partition_by.eval(d, &temp_storage)) {
+                                        Some(Ok(Datum::UInt64(hash))) => hash,
+                                        // Records whose partition can't be determined all go to the
+                                        // first partition, which keeps the assignment deterministic.
+                                        _ => 0,
+                                    },
+                                );
+                            }
+                        }
                         let key = key.map(|key| encoder.encode_key_unchecked(key));
                         let value = value.map(|value| encoder.encode_value_unchecked(value));
-                        output.give(&cap, ((key, value), time, diff)).await;
+                        let message = KafkaMessage {
+                            hash,
+                            key,
+                            value,
+                            headers,
+                        };
+                        output.give(&cap, (message, time, diff)).await;
                     }
                 }
             }
@@ -818,3 +947,63 @@ fn encode_collection<G: Scope>(
 
     (stream.as_collection(), statuses, button.press_on_drop())
 }
+
+/// Returns the columns of the sinked relation described by `datums`, the datums of an encoded
+/// value.
+///
+/// Debezium-formatted values wrap the columns in `before` and `after` records, in which case the
+/// columns of `after` are returned, or of `before` if the update is a deletion.
+fn sinked_datums<'a>(datums: Vec<Datum<'a>>, envelope: SinkEnvelope) -> Vec<Datum<'a>> {
+    match envelope {
+        SinkEnvelope::Upsert => datums,
+        SinkEnvelope::Debezium => {
+            let record = match datums[..] {
+                [before, Datum::Null] => before,
+                [_, after] => after,
+                _ => unreachable!("debezium values have two columns"),
+            };
+            record.unwrap_list().iter().collect()
+        }
+    }
+}
+
+/// Converts the value of a `HEADERS` column into record headers.
+///
+/// The column is either a map from header names to values, or a list of records with `key` and
+/// `value` fields. Values are either `text` or `bytea`, and a `NULL` value produces a header
+/// without a value. `NULL` header names are skipped.
+fn encode_headers(datum: Datum) -> Vec<KafkaHeader> {
+    fn header_value(datum: Datum) -> Option<Vec<u8>> {
+        match datum {
+            Datum::Null => None,
+            Datum::String(s) => Some(s.as_bytes().to_vec()),
+            Datum::Bytes(b) => Some(b.to_vec()),
+            _ => unreachable!("header values are text or bytea"),
+        }
+    }
+
+    match datum {
+        Datum::Null => vec![],
+        Datum::Map(map) => map
+            .iter()
+            .map(|(key, value)| KafkaHeader {
+                key: key.to_owned(),
+                value: header_value(value),
+            })
+            .collect(),
+        Datum::List(list) => list
+            .iter()
+            .filter(|record| !record.is_null())
+            .filter_map(|record| {
+                let mut fields = record.unwrap_list().iter();
+                let key = fields.next().expect("header records have two fields");
+                let value = fields.next().expect("header records have two fields");
+                (!key.is_null()).then(|| KafkaHeader {
+                    key: key.unwrap_str().to_owned(),
+                    value: header_value(value),
+                })
+            })
+            .collect(),
+        _ => unreachable!("headers are maps or lists"),
+    }
+}
@@ -9,4 +9,6 @@
 
 //! Moving data to external systems
 
+mod http;
 mod kafka;
+mod postgres;
@@ -0,0 +1,624 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Code to render the sink dataflow of a [`PostgresSinkConnection`]. The dataflow consists of a
+//! single operator that applies the upserts and deletes produced by the sink envelope to a table
+//! of an upstream PostgreSQL database.
+//!
+//! ```text
+//!        ┏━━━━━━━━━━━━━━┓
+//!        ┃   persist    ┃
+//!        ┃    source    ┃
+//!        ┗━━━━━━┯━━━━━━━┛
+//!               │ row data, the input to this module
+//!               │
+//!        ┏━━━━━━v━━━━━━┓
+//!        ┃  postgres   ┃ (single worker)
+//!        ┃    sink     ┃
+//!        ┗━━━━━━┯━━━━━━┛
+//!               │ INSERT ... ON CONFLICT / DELETE
+//!        ╭──────v───────╮
+//!        │ upstream     │
+//!        │ table        │
+//!        ╰──────────────╯
+//! ```
+//!
+//! # Exactly once
+//!
+//! The sink keeps one row per sink in a progress table, created in the schema of the target
+//! table if it does not exist yet. The row records the upper frontier of the updates that have
+//! been applied to the target table, where a `NULL` upper represents the empty frontier.
+//!
+//! The updates of each timestamp are applied in a single transaction that also advances the
+//! upper recorded in the progress table. On startup the sink reads the recorded upper and
+//! skips all updates that are not beyond it, which makes the sink resumable without applying
+//! any update twice.
+//!
+//! Each transaction locks the progress row and verifies that it still holds the upper this
+//! incarnation of the sink last committed. This fences out any older incarnation that might
+//! still be running concurrently, e.g. during a replica restart.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::io;
+use std::rc::Rc;
+
+use anyhow::{anyhow, bail};
+use bytes::BytesMut;
+use differential_dataflow::{Collection, Hashable};
+use mz_ore::cast::CastFrom;
+use mz_ore::error::ErrorExt;
+use mz_pgrepr::Value;
+use mz_repr::{Datum, Diff, GlobalId, RelationDesc, Row, ScalarType, Timestamp};
+use mz_sql_parser::ast::display::{escaped_string_literal, AstDisplay};
+use mz_sql_parser::ast::Ident;
+use mz_storage_types::configuration::StorageConfiguration;
+use mz_storage_types::errors::DataflowError;
+use mz_storage_types::sinks::{MetadataFilled, PostgresSinkConnection, StorageSinkDesc};
+use mz_timely_util::antichain::AntichainExt;
+use mz_timely_util::builder_async::{
+    Event, OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton,
+};
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::{Concatenate, Map, ToStream};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::{Antichain, Timestamp as _};
+use timely::PartialOrder;
+use tokio_postgres::Client;
+use tracing::{error, info};
+
+use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
+use crate::render::sinks::SinkRender;
+use crate::statistics::SinkStatistics;
+use crate::storage_state::StorageState;
+
+/// The maximum number of statements sent to the upstream database in a single round trip.
+const MAX_BATCH_STATEMENTS: usize = 1000;
+
+impl<G: Scope<Timestamp = Timestamp>> SinkRender<G> for PostgresSinkConnection {
+    fn uses_keys(&self) -> bool {
+        true
+    }
+
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        let (_desc, indices) = &self.key_desc_and_indices;
+        Some(indices.as_slice())
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        self.relation_key_indices.as_deref()
+    }
+
+    fn render_continuous_sink(
+        &self,
+        storage_state: &mut StorageState,
+        sink: &StorageSinkDesc<MetadataFilled, Timestamp>,
+        sink_id: GlobalId,
+        input: Collection<G, (Option<Row>, Option<Row>), Diff>,
+        _err_collection: Collection<G, DataflowError, Diff>,
+    ) -> (Stream<G, HealthStatusMessage>, Vec<PressOnDropButton>) {
+        let mut scope = input.scope();
+
+        let write_frontier = Rc::new(RefCell::new(Antichain::from_elem(Timestamp::minimum())));
+        storage_state
+            .sink_write_frontiers
+            .insert(sink_id, Rc::clone(&write_frontier));
+
+        let statistics = storage_state
+            .aggregated_statistics
+            .get_sink(&sink_id)
+            .expect("statistics initialized")
+            .clone();
+
+        let (sink_status, sink_token) = sink_collection(
+            format!("postgres-{sink_id}-sink"),
+            &input,
+            sink_id,
+            self.clone(),
+            storage_state.storage_configuration.clone(),
+            sink.as_of.clone(),
+            statistics,
+            write_frontier,
+        );
+
+        let running_status = Some(HealthStatusMessage {
+            index: 0,
+            update: HealthStatusUpdate::Running,
+            namespace: StatusNamespace::Postgres,
+        })
+        .to_stream(&mut scope);
+
+        let status = scope.concatenate([running_status, sink_status]);
+
+        (status, vec![sink_token])
+    }
+}
+
+/// Returns the quoted form of an upstream identifier. Identifiers are always quoted because
+/// PostgreSQL reserves keywords that we don't.
+fn quote_ident(ident: &str) -> String {
+    Ident::new_unchecked(ident).to_ast_string_stable()
+}
+
+/// Returns the quoted, schema qualified name of an upstream table.
+fn qualified_name(schema: &str, table: &str) -> String {
+    format!("{}.{}", quote_ident(schema), quote_ident(table))
+}
+
+/// Renders the statements that apply the updates of the sink to the upstream table.
+///
+/// Values are rendered as string literals of unknown type using the PostgreSQL text encoding, so
+/// that the upstream database coerces them to the types of the target columns.
+struct StatementBuilder {
+    /// The quoted, schema qualified name of the target table.
+    table: String,
+    /// The quoted names and the types of all the columns of the sinked relation.
+    columns: Vec<(String, ScalarType)>,
+    /// The indices of the key columns in `columns`.
+    key_indices: Vec<usize>,
+    /// The `ON CONFLICT` clause of every upsert, which only depends on the relation.
+    on_conflict: String,
+    /// Scratch space to encode values.
+    buf: BytesMut,
+}
+
+impl StatementBuilder {
+    fn new(table: String, value_desc: &RelationDesc, key_indices: &[usize]) -> Self {
+        let columns: Vec<_> = value_desc
+            .iter()
+            .map(|(name, typ)| (quote_ident(name.as_str()), typ.scalar_type.clone()))
+            .collect();
+
+        let conflict_target = key_indices
+            .iter()
+            .map(|i| columns[*i].0.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let assignments = columns
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !key_indices.contains(i))
+            .map(|(_, (name, _))| format!("{name} = EXCLUDED.{name}"))
+            .collect::<Vec<_>>();
+        let on_conflict = if assignments.is_empty() {
+            format!("ON CONFLICT ({conflict_target}) DO NOTHING")
+        } else {
+            format!(
+                "ON CONFLICT ({conflict_target}) DO UPDATE SET {}",
+                assignments.join(", ")
+            )
+        };
+
+        StatementBuilder {
+            table,
+            columns,
+            key_indices: key_indices.to_vec(),
+            on_conflict,
+            buf: BytesMut::new(),
+        }
+    }
+
+    /// Renders `datum` as a SQL literal.
+    fn literal(&mut self, datum: Datum, typ: &ScalarType) -> Result<String, io::Error> {
+        match Value::from_datum(datum, typ)? {
+            None => Ok("NULL".into()),
+            Some(value) => {
+                self.buf.clear();
+                value.encode_text(&mut self.buf);
+                let text = std::str::from_utf8(&self.buf).expect("text encoding is valid utf8");
+                Ok(escaped_string_literal(text).to_string())
+            }
+        }
+    }
+
+    /// Renders a statement that inserts `value` or updates the existing row with the same key.
+    fn upsert(&mut self, value: &Row) -> Result<String, io::Error> {
+        let columns = std::mem::take(&mut self.columns);
+        let names = columns
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let literals = value
+            .iter()
+            .zip(columns.iter())
+            .map(|(datum, (_, typ))| self.literal(datum, typ))
+            .collect::<Result<Vec<_>, _>>();
+        self.columns = columns;
+        Ok(format!(
+            "INSERT INTO {} ({names}) VALUES ({}) {}",
+            self.table,
+            literals?.join(", "),
+            self.on_conflict
+        ))
+    }
+
+    /// Renders a statement that deletes the row identified by `key`.
+    fn delete(&mut self, key: &Row) -> Result<String, io::Error> {
+        let columns = std::mem::take(&mut self.columns);
+        let key_indices = std::mem::take(&mut self.key_indices);
+        let predicates = key
+            .iter()
+            .zip(key_indices.iter())
+            .map(|(datum, i)| {
+                let (name, typ) = &columns[*i];
+                if datum.is_null() {
+                    Ok(format!("{name} IS NULL"))
+                } else {
+                    Ok(format!("{name} = {}", self.literal(datum, typ)?))
+                }
+            })
+            .collect::<Result<Vec<_>, io::Error>>();
+        self.columns = columns;
+        self.key_indices = key_indices;
+        Ok(format!(
+            "DELETE FROM {} WHERE {}",
+            self.table,
+            predicates?.join(" AND ")
+        ))
+    }
+}
+
+/// Applies updates to the upstream table and records the progress of the sink.
+struct ProgressWriter {
+    client: Client,
+    /// The quoted, schema qualified name of the progress table.
+    progress_table: String,
+    /// The key of the row of this sink in the progress table.
+    progress_key: String,
+    /// The upper last committed to the progress table.
+    upper: Antichain<Timestamp>,
+    /// A handle to the statistics associated with this sink.
+    statistics: SinkStatistics,
+}
+
+impl ProgressWriter {
+    /// Creates the progress table and the row of this sink if they don't exist yet, and returns
+    /// a writer that resumes from the upper recorded in it.
+    async fn new(
+        client: Client,
+        progress_table: String,
+        progress_key: String,
+        statistics: SinkStatistics,
+    ) -> Result<Self, anyhow::Error> {
+        client
+            .batch_execute(&format!(
+                "CREATE TABLE IF NOT EXISTS {progress_table} \
+                    (sink_id text PRIMARY KEY, upper bigint)"
+            ))
+            .await?;
+        client
+            .execute(
+                &format!(
+                    "INSERT INTO {progress_table} (sink_id, upper) VALUES ($1, 0) \
+                        ON CONFLICT (sink_id) DO NOTHING"
+                ),
+                &[&progress_key],
+            )
+            .await?;
+        let row = client
+            .query_one(
+                &format!("SELECT upper FROM {progress_table} WHERE sink_id = $1"),
+                &[&progress_key],
+            )
+            .await?;
+        let upper = decode_upper(row.get(0))?;
+
+        Ok(ProgressWriter {
+            client,
+            progress_table,
+            progress_key,
+            upper,
+            statistics,
+        })
+    }
+
+    /// Executes `statements` and advances the recorded upper to `new_upper` in a single
+    /// transaction.
+    async fn commit(
+        &mut self,
+        statements: &[String],
+        new_upper: Antichain<Timestamp>,
+    ) -> Result<(), anyhow::Error> {
+        let bytes: usize = statements.iter().map(|s| s.len()).sum();
+        let messages = u64::cast_from(statements.len());
+        let bytes = u64::cast_from(bytes);
+        self.statistics.inc_messages_staged_by(messages);
+        self.statistics.inc_bytes_staged_by(bytes);
+
+        let txn = self.client.transaction().await?;
+        let row = txn
+            .query_one(
+                &format!(
+                    "SELECT upper FROM {} WHERE sink_id = $1 FOR UPDATE",
+                    self.progress_table
+                ),
+                &[&self.progress_key],
+            )
+            .await?;
+        let committed_upper = decode_upper(row.get(0))?;
+        if committed_upper != self.upper {
+            bail!(
+                "sink progress was advanced concurrently: expected upper {}, found {}",
+                self.upper.pretty(),
+                committed_upper.pretty()
+            );
+        }
+        for batch in statements.chunks(MAX_BATCH_STATEMENTS) {
+            txn.batch_execute(&batch.join(";\n")).await?;
+        }
+        txn.execute(
+            &format!(
+                "UPDATE {} SET upper = $2 WHERE sink_id = $1",
+                self.progress_table
+            ),
+            &[&self.progress_key, &encode_upper(&new_upper)?],
+        )
+        .await?;
+        txn.commit().await?;
+
+        self.statistics.inc_messages_committed_by(messages);
+        self.statistics.inc_bytes_committed_by(bytes);
+        self.upper = new_upper;
+        Ok(())
+    }
+}
+
+/// Encodes an upper frontier for the progress table.
+fn encode_upper(upper: &Antichain<Timestamp>) -> Result<Option<i64>, anyhow::Error> {
+    upper
+        .as_option()
+        .map(|t| i64::try_from(u64::from(t)).map_err(|_| anyhow!("timestamp {t} out of range")))
+        .transpose()
+}
+
+/// Decodes an upper frontier read from the progress table.
+fn decode_upper(upper: Option<i64>) -> Result<Antichain<Timestamp>, anyhow::Error> {
+    match upper {
+        None => Ok(Antichain::new()),
+        Some(t) => {
+            let t = u64::try_from(t).map_err(|_| anyhow!("invalid sink progress upper {t}"))?;
+            Ok(Antichain::from_elem(Timestamp::from(t)))
+        }
+    }
+}
+
+/// Sinks a collection of keyed rows to an upstream PostgreSQL table.
+///
+/// This operator exchanges all updates to a single worker by hashing on the given sink `id`.
+///
+/// Updates are applied in ascending timestamp order, one transaction per timestamp.
+fn sink_collection<G: Scope<Timestamp = Timestamp>>(
+    name: String,
+    input: &Collection<G, (Option<Row>, Option<Row>), Diff>,
+    sink_id: GlobalId,
+    connection: PostgresSinkConnection,
+    storage_configuration: StorageConfiguration,
+    as_of: Antichain<Timestamp>,
+    statistics: SinkStatistics,
+    write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+) -> (Stream<G, HealthStatusMessage>, PressOnDropButton) {
+    let scope = input.scope();
+    let mut builder = AsyncOperatorBuilder::new(name.clone(), input.inner.scope());
+
+    // We want exactly one worker to write all the data to the upstream table.
+    let hashed_id = sink_id.hashed();
+    let is_active_worker = usize::cast_from(hashed_id) % scope.peers() == scope.index();
+
+    let mut input = builder.new_disconnected_input(&input.inner, Exchange::new(move |_| hashed_id));
+
+    let (button, errors) = builder.build_fallible(move |_caps| {
+        Box::pin(async move {
+            if !is_active_worker {
+                write_frontier.borrow_mut().clear();
+                return Ok(());
+            }
+
+            let connection_context = &storage_configuration.connection_context;
+            let config = connection
+                .connection
+                .config(&*connection_context.secrets_reader, &storage_configuration)
+                .await?;
+            let client = config
+                .connect(&name, &connection_context.ssh_tunnel_manager)
+                .await?;
+
+            let progress_key = format!("{}-{}", connection_context.environment_id, sink_id);
+            let mut writer = ProgressWriter::new(
+                client,
+                qualified_name(&connection.schema, &connection.progress_table),
+                progress_key,
+                statistics,
+            )
+            .await?;
+            let resume_upper = writer.upper.clone();
+
+            // The input has overcompacted if
+            let overcompacted =
+                // ..we have made some progress in the past
+                *resume_upper != [Timestamp::minimum()] &&
+                // ..but the since frontier is now beyond that
+                !PartialOrder::less_equal(&as_of, &resume_upper);
+            if overcompacted {
+                let err = format!(
+                    "{name}: input compacted past resume upper: as_of {}, resume_upper: {}",
+                    as_of.pretty(),
+                    resume_upper.pretty()
+                );
+                // This would normally be an assertion but because it can happen after a
+                // Materialize backup/restore we log an error so that it appears on Sentry but
+                // leaves the rest of the objects in the cluster unaffected.
+                error!("{err}");
+                return Err(anyhow!("{err}"));
+            }
+
+            info!(
+                "{name}: as_of: {}, resume upper: {}",
+                as_of.pretty(),
+                resume_upper.pretty()
+            );
+            *write_frontier.borrow_mut() = resume_upper.clone();
+            if resume_upper.is_empty() {
+                return Ok(());
+            }
+
+            let (_key_desc, key_indices) = &connection.key_desc_and_indices;
+            let mut statements = StatementBuilder::new(
+                qualified_name(&connection.schema, &connection.table),
+                &connection.value_desc,
+                key_indices,
+            );
+            // The section below relies on TotalOrder for correctness so we'll work with timestamps
+            // directly to make sure this doesn't compile if someone attempts to make this operator
+            // generic over partial orders in the future.
+            let mut pending: BTreeMap<Timestamp, Vec<(Option<Row>, Option<Row>)>> = BTreeMap::new();
+            while let Some(event) = input.next().await {
+                match event {
+                    Event::Data(_cap, batch) => {
+                        for ((key, value), time, diff) in batch {
+                            // Updates not beyond the resume upper have already been applied.
+                            if !resume_upper.less_equal(&time) {
+                                continue;
+                            }
+                            assert_eq!(diff, 1, "invalid sink update");
+                            pending.entry(time).or_default().push((key, value));
+                        }
+                    }
+                    Event::Progress(progress) => {
+                        // Ignore progress updates before our resumption frontier
+                        if !PartialOrder::less_equal(&resume_upper, &progress) {
+                            continue;
+                        }
+                        // Also ignore progress updates until we are past the as_of frontier. See
+                        // the Kafka sink for a description of the scenario this prevents.
+                        if !as_of.iter().all(|t| !progress.less_equal(t)) {
+                            continue;
+                        }
+                        let ready = match progress.as_option() {
+                            Some(upper) => {
+                                let not_ready = pending.split_off(upper);
+                                std::mem::replace(&mut pending, not_ready)
+                            }
+                            None => std::mem::take(&mut pending),
+                        };
+
+                        let mut ready = ready.into_iter().peekable();
+                        while let Some((time, updates)) = ready.next() {
+                            // The last ready timestamp advances the upper all the way to the
+                            // input frontier, which saves a transaction per progress update.
+                            let new_upper = match ready.peek() {
+                                Some(_) => Antichain::from_elem(time.step_forward()),
+                                None => progress.clone(),
+                            };
+                            let updates: Vec<_> = updates
+                                .into_iter()
+                                .map(|(key, value)| match (key, value) {
+                                    (_, Some(value)) => statements.upsert(&value).unwrap(),
+                                    (Some(key), None) => statements.delete(&key).unwrap(),
+                                    (None, None) => unreachable!("upsert sinks are keyed"),
+                                })
+                                .collect::<Result<_, _>>()?;
+                            writer.commit(&updates, new_upper).await?;
+                        }
+                        if writer.upper != progress {
+                            writer.commit(&[], progress.clone()).await?;
+                        }
+
+                        *write_frontier.borrow_mut() = progress.clone();
+                        if progress.is_empty() {
+                            break;
+                        }
+                    }
+                }
+            }
+            Ok::<(), anyhow::Error>(())
+        })
+    });
+
+    let statuses = errors.map(|error: Rc<anyhow::Error>| HealthStatusMessage {
+        index: 0,
+        update: HealthStatusUpdate::halting(format!("{}", error.display_with_causes()), None),
+        namespace: StatusNamespace::Postgres,
+    });
+
+    (statuses, button.press_on_drop())
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::{ColumnName, ColumnType, RelationType};
+
+    use super::*;
+
+    fn statement_builder(key_indices: &[usize]) -> StatementBuilder {
+        let value_desc = RelationDesc::new(
+            RelationType::new(vec![
+                ColumnType {
+                    scalar_type: ScalarType::Int32,
+                    nullable: false,
+                },
+                ColumnType {
+                    scalar_type: ScalarType::String,
+                    nullable: true,
+                },
+            ]),
+            vec![ColumnName::from("id"), ColumnName::from("Name")],
+        );
+        StatementBuilder::new(
+            qualified_name("public", "results"),
+            &value_desc,
+            key_indices,
+        )
+    }
+
+    #[mz_ore::test]
+    fn test_statements() {
+        let mut statements = statement_builder(&[0]);
+        let value = Row::pack_slice(&[Datum::Int32(1), Datum::String("it's")]);
+        assert_eq!(
+            statements.upsert(&value).unwrap(),
+            r#"INSERT INTO "public"."results" ("id", "Name") VALUES ('1', 'it''s') ON CONFLICT ("id") DO UPDATE SET "Name" = EXCLUDED."Name""#
+        );
+        let value = Row::pack_slice(&[Datum::Int32(2), Datum::Null]);
+        assert_eq!(
+            statements.upsert(&value).unwrap(),
+            r#"INSERT INTO "public"."results" ("id", "Name") VALUES ('2', NULL) ON CONFLICT ("id") DO UPDATE SET "Name" = EXCLUDED."Name""#
+        );
+        let key = Row::pack_slice(&[Datum::Int32(1)]);
+        assert_eq!(
+            statements.delete(&key).unwrap(),
+            r#"DELETE FROM "public"."results" WHERE "id" = '1'"#
+        );
+
+        let mut statements = statement_builder(&[0, 1]);
+        let value = Row::pack_slice(&[Datum::Int32(1), Datum::String("a")]);
+        assert_eq!(
+            statements.upsert(&value).unwrap(),
+            r#"INSERT INTO "public"."results" ("id", "Name") VALUES ('1', 'a') ON CONFLICT ("id", "Name") DO NOTHING"#
+        );
+        let key = Row::pack_slice(&[Datum::Int32(1), Datum::Null]);
+        assert_eq!(
+            statements.delete(&key).unwrap(),
+            r#"DELETE FROM "public"."results" WHERE "id" = '1' AND "Name" IS NULL"#
+        );
+    }
+
+    #[mz_ore::test]
+    fn test_upper_roundtrip() {
+        for upper in [
+            Antichain::new(),
+            Antichain::from_elem(Timestamp::minimum()),
+            Antichain::from_elem(Timestamp::from(1_700_000_000_000u64)),
+        ] {
+            assert_eq!(decode_upper(encode_upper(&upper).unwrap()).unwrap(), upper);
+        }
+        assert!(encode_upper(&Antichain::from_elem(Timestamp::from(u64::MAX))).is_err());
+        assert!(decode_upper(Some(-1)).is_err());
+    }
+}
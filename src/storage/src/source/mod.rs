@@ -29,8 +29,11 @@ use crate::source::types::{SourceMessage, SourceReaderError};
 
 pub mod generator;
 mod kafka;
+mod mongodb;
 mod mysql;
 mod postgres;
+mod s3;
+mod sql_server;
 pub(crate) mod reclock;
 mod source_reader_pipeline;
 mod statistics;
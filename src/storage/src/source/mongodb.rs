@@ -0,0 +1,194 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Code to render the ingestion dataflow of a [`MongoDbSourceConnection`].
+//!
+//! MongoDB exposes the changes of a database through change streams. Every change event carries
+//! a resume token that orders it among all other events of the deployment, which is the timestamp
+//! of this dataflow.
+//!
+//! The dataflow consists of a single replication operator that snapshots collections that have
+//! not been snapshot yet and then tails a change stream of the database. See the [replication]
+//! module for more information.
+//!
+//! Documents are ingested as `jsonb`, keyed by their `_id`. Sources created with subsources have
+//! one output per collection. All other sources ingest every collection into the source itself,
+//! prefixing each row with the name of its collection.
+//!
+//! # Error handling
+//!
+//! Errors that are caused by the data of a specific collection, like documents that cannot be
+//! decoded or a collection that was dropped, are [`DefiniteError`]s and end up in the error
+//! collection of that collection's output.
+//!
+//! All other errors are [`TransientError`]s. They are published to the source status and trigger
+//! a restart of the dataflow.
+
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::rc::Rc;
+
+use differential_dataflow::Collection;
+use serde::{Deserialize, Serialize};
+use timely::dataflow::operators::{Concat, Map, ToStream};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::Antichain;
+
+use mz_mongodb_util::{MongoDbError, ResumeToken};
+use mz_ore::error::ErrorExt;
+use mz_repr::{Diff, Row};
+use mz_storage_types::errors::SourceErrorDetails;
+use mz_storage_types::sources::{MongoDbSourceConnection, SourceTimestamp};
+use mz_timely_util::builder_async::PressOnDropButton;
+
+use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
+use crate::source::types::{ProgressStatisticsUpdate, SourceRender};
+use crate::source::{RawSourceCreationConfig, SourceMessage, SourceReaderError};
+
+mod replication;
+mod statistics;
+
+impl SourceRender for MongoDbSourceConnection {
+    type Time = ResumeToken;
+
+    const STATUS_NAMESPACE: StatusNamespace = StatusNamespace::MongoDb;
+
+    /// Render the ingestion dataflow. This function only connects things together and contains no
+    /// actual processing logic.
+    fn render<G: Scope<Timestamp = ResumeToken>>(
+        self,
+        scope: &mut G,
+        config: RawSourceCreationConfig,
+        resume_uppers: impl futures::Stream<Item = Antichain<ResumeToken>> + 'static,
+        _start_signal: impl std::future::Future<Output = ()> + 'static,
+    ) -> (
+        Collection<G, (usize, Result<SourceMessage, SourceReaderError>), Diff>,
+        Option<Stream<G, Infallible>>,
+        Stream<G, HealthStatusMessage>,
+        Stream<G, ProgressStatisticsUpdate>,
+        Vec<PressOnDropButton>,
+    ) {
+        let subsource_resume_uppers: BTreeMap<_, _> = config
+            .source_resume_uppers
+            .iter()
+            .map(|(id, upper)| {
+                assert!(
+                    config.source_exports.contains_key(id),
+                    "all source resume uppers must be present in source exports"
+                );
+
+                (
+                    *id,
+                    Antichain::from_iter(upper.iter().map(ResumeToken::decode_row)),
+                )
+            })
+            .collect();
+
+        // Collect the collections that we will be ingesting and their output index. Index zero
+        // maps to the main source, which receives all collections if there are no subsources.
+        let subsources = self.details.subsources;
+        let collection_outputs: BTreeMap<_, _> = self
+            .details
+            .collections
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.clone(), if subsources { i + 1 } else { 0 }))
+            .collect();
+
+        let (repl_updates, uppers, repl_err, repl_token) = replication::render(
+            scope.clone(),
+            config.clone(),
+            self.clone(),
+            subsource_resume_uppers,
+            collection_outputs,
+        );
+
+        let (stats_stream, stats_err, stats_token) =
+            statistics::render(scope.clone(), config, self, resume_uppers);
+
+        let updates = repl_updates.map(|(output, res)| {
+            let res = res.map(|row| SourceMessage {
+                key: Row::default(),
+                value: row,
+                metadata: Row::default(),
+            });
+            (output, res)
+        });
+
+        let health_init = std::iter::once(HealthStatusMessage {
+            index: 0,
+            namespace: Self::STATUS_NAMESPACE,
+            update: HealthStatusUpdate::Running,
+        })
+        .to_stream(scope);
+
+        let health_errs = repl_err.concat(&stats_err).map(move |err| {
+            // This update will cause the dataflow to restart
+            let err_string = err.display_with_causes().to_string();
+            let update = HealthStatusUpdate::halting(err_string, None);
+
+            let namespace = match &*err {
+                TransientError::MongoDb(MongoDbError::Ssh(_)) => StatusNamespace::Ssh,
+                _ => Self::STATUS_NAMESPACE,
+            };
+
+            HealthStatusMessage {
+                index: 0,
+                namespace,
+                update,
+            }
+        });
+        let health = health_init.concat(&health_errs);
+
+        (
+            updates,
+            Some(uppers),
+            health,
+            stats_stream,
+            vec![repl_token, stats_token],
+        )
+    }
+}
+
+/// A transient error that never ends up in the collection of a specific output.
+#[derive(Debug, thiserror::Error)]
+pub enum TransientError {
+    #[error(transparent)]
+    MongoDb(#[from] MongoDbError),
+    #[error(transparent)]
+    Generic(#[from] anyhow::Error),
+}
+
+impl From<mongodb::error::Error> for TransientError {
+    fn from(err: mongodb::error::Error) -> Self {
+        TransientError::MongoDb(err.into())
+    }
+}
+
+/// The stream of transient errors produced by an operator of this dataflow.
+type TransientErrors<G> = Stream<G, Rc<TransientError>>;
+
+/// A definite error that always ends up in the collection of a specific output.
+#[derive(Debug, Clone, Serialize, Deserialize, thiserror::Error)]
+pub enum DefiniteError {
+    #[error("unable to decode: {0}")]
+    ValueDecodeError(String),
+    #[error("collection was dropped or renamed: {0}")]
+    CollectionDropped(String),
+    #[error("change stream pre- and post-images are no longer enabled for collection: {0}")]
+    PreAndPostImagesDisabled(String),
+}
+
+impl From<DefiniteError> for SourceReaderError {
+    fn from(err: DefiniteError) -> Self {
+        SourceReaderError {
+            inner: SourceErrorDetails::Other(err.to_string()),
+        }
+    }
+}
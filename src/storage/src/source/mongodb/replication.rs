@@ -0,0 +1,346 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Renders the replication side of the [`MongoDbSourceConnection`] ingestion dataflow.
+//!
+//! # Progress tracking
+//!
+//! Changes are emitted at the resume token of the change event that made them. Because the
+//! events of a transaction share a cluster time, the frontier is only advanced to the smallest
+//! token of a cluster time once an event with that cluster time is seen, which keeps transactions
+//! atomic. While the change stream is idle, the frontier follows the post batch resume token that
+//! the server reports with every empty batch.
+//!
+//! # Snapshots
+//!
+//! Collections whose output has not been snapshot yet are read with the `snapshot` read concern
+//! at the current cluster time, and the change stream is opened right after that cluster time.
+//! Snapshot updates are emitted at the minimum timestamp.
+//!
+//! # Rewinds
+//!
+//! When a collection is added to a source that already made progress, replication resumes from
+//! the frontier of the existing collections, which is before the snapshot of the new collection.
+//! Changes of the new collection that are already part of its snapshot are retracted at the
+//! minimum timestamp and emitted again at their resume token, such that the snapshot appears to
+//! have been taken at the resume point.
+//!
+//! # Updates and deletes
+//!
+//! Change events only carry the previous version of a document if the collection has
+//! `changeStreamPreAndPostImages` enabled, which purification verifies. The change stream is
+//! opened with `fullDocument` and `fullDocumentBeforeChange` set to `required`, so the server
+//! fails the stream rather than silently omitting an image that has expired.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::Infallible;
+use std::time::Duration;
+
+use bson::{doc, Bson, Document};
+use differential_dataflow::{AsCollection, Collection};
+use mongodb::change_stream::event::OperationType;
+use mongodb::options::{ChangeStreamOptions, FullDocumentBeforeChangeType, FullDocumentType};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::{Antichain, Timestamp};
+use tracing::{info, trace};
+
+use mz_mongodb_util::{
+    collection_info, document_id, get_cluster_time, pack_bson, CollectionRequest, MongoDbError,
+    ResumeToken, SnapshotCursor, StartPoint,
+};
+use mz_ore::result::ResultExt;
+use mz_repr::{Datum, Diff, GlobalId, Row};
+use mz_storage_types::sources::MongoDbSourceConnection;
+use mz_timely_util::builder_async::{OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton};
+
+use crate::source::types::SourceReaderError;
+use crate::source::RawSourceCreationConfig;
+
+use super::{DefiniteError, TransientError, TransientErrors};
+
+/// Used as a partition id to determine if the worker is responsible for reading from the change
+/// stream.
+static REPL_READER: &str = "reader";
+
+/// How long the server waits for new events before returning an empty batch, which determines
+/// how often the frontier advances while the change stream is idle.
+const MAX_AWAIT_TIME: Duration = Duration::from_secs(1);
+
+/// Renders the replication dataflow. See the module documentation for more information.
+pub(crate) fn render<G: Scope<Timestamp = ResumeToken>>(
+    scope: G,
+    config: RawSourceCreationConfig,
+    connection: MongoDbSourceConnection,
+    subsource_resume_uppers: BTreeMap<GlobalId, Antichain<ResumeToken>>,
+    collection_outputs: BTreeMap<String, usize>,
+) -> (
+    Collection<G, (usize, Result<Row, SourceReaderError>), Diff>,
+    Stream<G, Infallible>,
+    TransientErrors<G>,
+    PressOnDropButton,
+) {
+    let op_name = format!("MongoDbReplicationReader({})", config.id);
+    let mut builder = AsyncOperatorBuilder::new(op_name, scope);
+
+    let (mut data_output, data_stream) = builder.new_output();
+    let (_upper_output, upper_stream) = builder.new_output();
+
+    let (button, transient_errors) = builder.build_fallible::<TransientError, _>(move |caps| {
+        Box::pin(async move {
+            let (id, worker_id) = (config.id, config.worker_id);
+            let [data_cap_set, upper_cap_set]: &mut [_; 2] = caps.try_into().unwrap();
+
+            // Only run the replication reader on the worker responsible for it.
+            if !config.responsible_for(REPL_READER) {
+                return Ok(());
+            }
+
+            let connection_config = connection
+                .connection
+                .config(
+                    &*config.config.connection_context.secrets_reader,
+                    &config.config,
+                )
+                .await?;
+
+            let client = connection_config
+                .connect(
+                    &format!("timely-{worker_id} MongoDB replication reader"),
+                    &config.config.connection_context.ssh_tunnel_manager,
+                )
+                .await?;
+            let db = client.db();
+
+            // Rows of the main source are prefixed with the name of their collection.
+            let subsources = connection.details.subsources;
+
+            // Determine which outputs still need to be snapshot, and the frontier of the ones
+            // that already have been. Without subsources the main source is the only output.
+            let mut snapshot_outputs = BTreeSet::new();
+            let mut resume_upper = Antichain::new();
+            for (export_id, upper) in subsource_resume_uppers {
+                if export_id == id && subsources {
+                    continue;
+                }
+                if *upper == [ResumeToken::minimum()] {
+                    snapshot_outputs.insert(config.source_exports[&export_id].output_index);
+                } else {
+                    resume_upper.extend(upper.iter().cloned());
+                }
+            }
+
+            // Verify that the upstream collections still exist and can be replicated.
+            let request = CollectionRequest::Collections(
+                collection_outputs.keys().map(|c| c.as_str()).collect(),
+            );
+            let upstream_collections = collection_info(&db, &request).await?;
+
+            let mut collections = BTreeMap::new();
+            for (name, output_index) in collection_outputs {
+                let upstream = upstream_collections.iter().find(|c| c.name == name);
+                let err = match upstream {
+                    None => Some(DefiniteError::CollectionDropped(name.clone())),
+                    Some(upstream) if !upstream.pre_and_post_images => {
+                        Some(DefiniteError::PreAndPostImagesDisabled(name.clone()))
+                    }
+                    Some(_) => None,
+                };
+                match err {
+                    None => {
+                        collections.insert(name, output_index);
+                    }
+                    Some(err) => {
+                        let update = ((output_index, Err(err)), ResumeToken::minimum(), 1);
+                        data_output.give(&data_cap_set[0], update).await;
+                    }
+                }
+            }
+
+            // Snapshot all collections that need it as of the same cluster time.
+            let mut row_buf = Row::default();
+            let to_snapshot: Vec<_> = collections
+                .iter()
+                .filter(|(_, output_index)| snapshot_outputs.contains(*output_index))
+                .collect();
+            let mut rewinds = BTreeMap::new();
+            if !to_snapshot.is_empty() {
+                info!(%id, "timely-{worker_id} snapshotting {} collections", to_snapshot.len());
+                let cluster_time = get_cluster_time(&db).await?;
+                for (name, output_index) in to_snapshot.iter() {
+                    let mut cursor = SnapshotCursor::new(&db, name.to_string(), cluster_time);
+                    while let Some(batch) = cursor.next_batch().await? {
+                        for document in batch {
+                            let row = decode_document(&mut row_buf, name, subsources, document)?;
+                            let update = ((**output_index, row), ResumeToken::minimum(), 1);
+                            data_output.give(&data_cap_set[0], update).await;
+                        }
+                    }
+                }
+
+                let snapshot_upper = ResumeToken::after_cluster_time(cluster_time)
+                    .expect("cluster time space exhausted");
+                trace!(%id, "timely-{worker_id} snapshot upper {snapshot_upper}");
+                if resume_upper.is_empty() {
+                    resume_upper.insert(snapshot_upper.clone());
+                }
+                for (_, output_index) in to_snapshot {
+                    rewinds.insert(*output_index, snapshot_upper.clone());
+                }
+            }
+
+            let Some(mut upper) = resume_upper.into_option() else {
+                // There is nothing left to ingest.
+                return Ok(());
+            };
+            rewinds.retain(|_, snapshot_upper| *snapshot_upper > upper);
+
+            let mut options = ChangeStreamOptions::default();
+            options.full_document = Some(FullDocumentType::Required);
+            options.full_document_before_change = Some(FullDocumentBeforeChangeType::Required);
+            options.max_await_time = Some(MAX_AWAIT_TIME);
+            match upper.start_point()? {
+                Some(StartPoint::AtOperationTime(cluster_time)) => {
+                    options.start_at_operation_time = Some(cluster_time);
+                }
+                Some(StartPoint::AfterToken(token)) => options.resume_after = Some(token),
+                None => unreachable!("replication never starts at the minimum token"),
+            }
+            let names: Vec<_> = collections.keys().cloned().collect();
+            let pipeline = [doc! { "$match": { "ns.coll": { "$in": names } } }];
+            let mut stream = db.watch(pipeline, options).await?;
+
+            // Hold on to the minimum timestamp until all rewinds have been performed.
+            let mut rewind_cap =
+                (!rewinds.is_empty()).then(|| data_cap_set.delayed(&ResumeToken::minimum()));
+            data_cap_set.downgrade([&upper]);
+            upper_cap_set.downgrade([&upper]);
+            trace!(%id, "timely-{worker_id} replication reader started at {upper}");
+
+            loop {
+                let new_upper = match stream.next_if_any().await? {
+                    Some(event) => {
+                        let token = ResumeToken::from_event_token(&event.id)?;
+                        let Some(cluster_time) = token.cluster_time() else {
+                            return Err(MongoDbError::InvalidResumeToken(token.to_string()).into());
+                        };
+
+                        let Some(name) = event.ns.and_then(|ns| ns.coll) else {
+                            if event.operation_type == OperationType::Invalidate {
+                                Err(anyhow::anyhow!("change stream was invalidated"))?;
+                            }
+                            continue;
+                        };
+                        let Some(&output_index) = collections.get(&name) else {
+                            continue;
+                        };
+
+                        let changes = match event.operation_type {
+                            OperationType::Insert => vec![(event.full_document, 1)],
+                            OperationType::Update | OperationType::Replace => vec![
+                                (event.full_document_before_change, -1),
+                                (event.full_document, 1),
+                            ],
+                            OperationType::Delete => {
+                                vec![(event.full_document_before_change, -1)]
+                            }
+                            OperationType::Drop | OperationType::Rename => {
+                                let err = DefiniteError::CollectionDropped(name.clone());
+                                let update = ((output_index, Err(err)), token.clone(), 1);
+                                data_output.give(&data_cap_set[0], update).await;
+                                collections.remove(&name);
+                                rewinds.remove(&output_index);
+                                vec![]
+                            }
+                            _ => vec![],
+                        };
+
+                        for (document, diff) in changes {
+                            let document = document.ok_or_else(|| {
+                                anyhow::anyhow!("change event for {name} is missing a document")
+                            })?;
+                            let row = decode_document(&mut row_buf, &name, subsources, document)?;
+
+                            if let Some(snapshot_upper) = rewinds.get(&output_index) {
+                                if token < *snapshot_upper {
+                                    let cap = rewind_cap.as_ref().expect("rewinds pending");
+                                    let update = (
+                                        (output_index, row.clone()),
+                                        ResumeToken::minimum(),
+                                        -diff,
+                                    );
+                                    data_output.give(cap, update).await;
+                                }
+                            }
+                            let update = ((output_index, row), token.clone(), diff);
+                            data_output.give(&data_cap_set[0], update).await;
+                        }
+
+                        // All events before the first one of a cluster time are complete, while
+                        // more events of this cluster time might still follow.
+                        ResumeToken::at_cluster_time(cluster_time)
+                    }
+                    None => match stream.resume_token() {
+                        Some(token) => ResumeToken::from_event_token(&token)?.successor(),
+                        None => continue,
+                    },
+                };
+
+                if new_upper > upper {
+                    upper = new_upper;
+                    rewinds.retain(|_, snapshot_upper| *snapshot_upper > upper);
+                    if rewinds.is_empty() {
+                        rewind_cap = None;
+                    }
+                    data_cap_set.downgrade([&upper]);
+                    upper_cap_set.downgrade([&upper]);
+                    trace!(%id, "timely-{worker_id} replication reader advanced to {upper}");
+                }
+            }
+        })
+    });
+
+    let replication_updates = data_stream
+        .as_collection()
+        .map(|(output_index, row)| (output_index, row.err_into()));
+
+    (
+        replication_updates,
+        upper_stream,
+        transient_errors,
+        button.press_on_drop(),
+    )
+}
+
+/// Decodes `document` of `collection` into a row of its output, which is prefixed with the name
+/// of the collection when ingesting into the main source. Documents that cannot be decoded are
+/// reported as definite errors in the collection of the output.
+fn decode_document(
+    row_buf: &mut Row,
+    collection: &str,
+    subsources: bool,
+    document: Document,
+) -> Result<Result<Row, DefiniteError>, TransientError> {
+    let result = {
+        let mut packer = row_buf.packer();
+        if !subsources {
+            packer.push(Datum::String(collection));
+        }
+        document_id(collection, &document).and_then(|id| {
+            pack_bson(&mut packer, collection, id)?;
+            pack_bson(&mut packer, collection, Bson::Document(document))
+        })
+    };
+    match result {
+        Ok(()) => Ok(Ok(row_buf.clone())),
+        Err(err @ MongoDbError::ValueDecodeError { .. }) => {
+            Ok(Err(DefiniteError::ValueDecodeError(err.to_string())))
+        }
+        Err(err) => Err(err.into()),
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Renders the statistics collection of the [`MongoDbSourceConnection`] ingestion dataflow.
+
+use std::time::Duration;
+
+use futures::StreamExt;
+use timely::dataflow::{Scope, Stream};
+use timely::progress::Antichain;
+
+use mz_mongodb_util::{get_cluster_time, ResumeToken};
+use mz_storage_types::sources::MongoDbSourceConnection;
+use mz_timely_util::builder_async::{OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton};
+
+use crate::source::types::ProgressStatisticsUpdate;
+use crate::source::RawSourceCreationConfig;
+
+use super::{TransientError, TransientErrors};
+
+static STATISTICS: &str = "statistics";
+
+/// Renders the statistics dataflow.
+pub(crate) fn render<G: Scope<Timestamp = ResumeToken>>(
+    scope: G,
+    config: RawSourceCreationConfig,
+    connection: MongoDbSourceConnection,
+    resume_uppers: impl futures::Stream<Item = Antichain<ResumeToken>> + 'static,
+) -> (
+    Stream<G, ProgressStatisticsUpdate>,
+    TransientErrors<G>,
+    PressOnDropButton,
+) {
+    let op_name = format!("MongoDbStatistics({})", config.id);
+    let mut builder = AsyncOperatorBuilder::new(op_name, scope);
+
+    let (mut stats_output, stats_stream) = builder.new_output();
+
+    let (button, transient_errors) = builder.build_fallible::<TransientError, _>(move |caps| {
+        Box::pin(async move {
+            let worker_id = config.worker_id;
+            let [stats_cap]: &mut [_; 1] = caps.try_into().unwrap();
+
+            // Only run the statistics reader on the worker responsible for it.
+            if !config.responsible_for(STATISTICS) {
+                // Emit 0, to mark this worker as having started up correctly.
+                stats_output
+                    .give(
+                        &stats_cap[0],
+                        ProgressStatisticsUpdate::SteadyState {
+                            offset_known: 0,
+                            offset_committed: 0,
+                        },
+                    )
+                    .await;
+                return Ok(());
+            }
+
+            let connection_config = connection
+                .connection
+                .config(
+                    &*config.config.connection_context.secrets_reader,
+                    &config.config,
+                )
+                .await?;
+
+            let client = connection_config
+                .connect(
+                    &format!("timely-{worker_id} MongoDB replication statistics"),
+                    &config.config.connection_context.ssh_tunnel_manager,
+                )
+                .await?;
+            let db = client.db();
+
+            tokio::pin!(resume_uppers);
+
+            let mut offset_known = None;
+            let mut offset_committed = None;
+
+            let upstream_stream = async_stream::stream!({
+                let mut interval = tokio::time::interval(Duration::from_secs(10));
+                loop {
+                    interval.tick().await;
+                    yield get_cluster_time(&db).await;
+                }
+            });
+            tokio::pin!(upstream_stream);
+
+            loop {
+                tokio::select! {
+                    Some(cluster_time) = upstream_stream.next() => {
+                        let known = ResumeToken::at_cluster_time(cluster_time?);
+                        offset_known = Some(known.abbreviate());
+                    },
+                    Some(committed_frontier) = resume_uppers.next() => {
+                        offset_committed = committed_frontier
+                            .into_option()
+                            .map(|token| token.abbreviate())
+                            .or(offset_committed);
+                    },
+                    else => break
+                };
+
+                if let (Some(offset_known), Some(offset_committed)) =
+                    (offset_known, offset_committed)
+                {
+                    stats_output
+                        .give(
+                            &stats_cap[0],
+                            ProgressStatisticsUpdate::SteadyState {
+                                offset_known,
+                                offset_committed,
+                            },
+                        )
+                        .await;
+                }
+            }
+
+            Ok(())
+        })
+    });
+
+    (stats_stream, transient_errors, button.press_on_drop())
+}
@@ -0,0 +1,495 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Code to render the ingestion dataflow of an [`S3SourceConnection`].
+//!
+//! An S3 source ingests every object stored under a prefix of a bucket. Its
+//! timestamps are ranges of object keys. S3 lists objects in lexicographic
+//! order of their keys, so the source ingests objects in that order and
+//! records its progress as a single watermark: the frontier of the source is
+//! the range of keys after the greatest key ingested so far. Every poll lists
+//! only the keys after the watermark, one page at a time. The objects of a
+//! page are emitted at the current watermark range, after which the watermark
+//! advances past the last key of the page.
+//!
+//! Objects are assumed to be immutable and to be added in increasing key order,
+//! e.g. because their keys start with a date. An object is ingested exactly
+//! once, even if it is overwritten or deleted later, and objects added with a
+//! key before the watermark are never ingested. To surface such objects, every
+//! poll also lists one page of the keys before the watermark, cycling through
+//! all of them over successive polls, and looks for objects that were last
+//! modified after the watermark passed their key. Once it finds any, the source
+//! reports a non-halting stalled status that names them.
+//!
+//! A single worker lists and ingests all objects, which keeps the watermark
+//! consistent. It fetches up to [`MAX_CONCURRENT_FETCHES`] objects at a time.
+
+use std::collections::{BTreeSet, VecDeque};
+use std::convert::Infallible;
+use std::time::{Duration, SystemTime};
+
+use anyhow::anyhow;
+use aws_sdk_s3::error::SdkError;
+use aws_sdk_s3::Client;
+use differential_dataflow::{AsCollection, Collection};
+use futures::{stream, StreamExt};
+use mz_ore::error::ErrorExt;
+use mz_repr::{Datum, Diff, Row};
+use mz_storage_types::sources::kafka::RangeBound;
+use mz_storage_types::sources::s3::{S3ObjectFormat, S3SourceConnection};
+use mz_storage_types::sources::{MzOffset, SourceTimestamp};
+use mz_timely_util::builder_async::{OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton};
+use mz_timely_util::order::Partitioned;
+use timely::dataflow::{Scope, Stream};
+use timely::progress::Antichain;
+use tracing::info;
+
+use crate::decode::parquet::ParquetDecoder;
+use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
+use crate::source::types::{ProgressStatisticsUpdate, SourceRender};
+use crate::source::{RawSourceCreationConfig, SourceMessage, SourceReaderError};
+
+type S3Timestamp = Partitioned<RangeBound<String>, MzOffset>;
+
+/// The maximum number of objects fetched concurrently.
+const MAX_CONCURRENT_FETCHES: usize = 16;
+
+/// How much later than the listing that passed its key an object must have
+/// been modified to be reported as not ingested. Covers the clock skew between
+/// S3 and the replica.
+const MAX_CLOCK_SKEW: Duration = Duration::from_secs(5);
+
+impl SourceRender for S3SourceConnection {
+    type Time = S3Timestamp;
+
+    const STATUS_NAMESPACE: StatusNamespace = StatusNamespace::S3;
+
+    fn render<G: Scope<Timestamp = S3Timestamp>>(
+        self,
+        scope: &mut G,
+        config: RawSourceCreationConfig,
+        resume_uppers: impl futures::Stream<Item = Antichain<S3Timestamp>> + 'static,
+        start_signal: impl std::future::Future<Output = ()> + 'static,
+    ) -> (
+        Collection<G, (usize, Result<SourceMessage, SourceReaderError>), Diff>,
+        Option<Stream<G, Infallible>>,
+        Stream<G, HealthStatusMessage>,
+        Stream<G, ProgressStatisticsUpdate>,
+        Vec<PressOnDropButton>,
+    ) {
+        let mut builder = AsyncOperatorBuilder::new(config.name.clone(), scope.clone());
+
+        let (mut data_output, stream) = builder.new_output();
+        let (mut health_output, health_stream) = builder.new_output();
+        let (mut stats_output, stats_stream) = builder.new_output();
+
+        let button = builder.build(move |caps| async move {
+            let [data_cap, health_cap, stats_cap]: [_; 3] = caps.try_into().unwrap();
+
+            let S3SourceConnection {
+                connection,
+                connection_id,
+                bucket,
+                prefix,
+                format,
+                poll_interval,
+            } = self;
+
+            // Only one worker ingests objects, so that the watermark reflects
+            // all ingested objects.
+            if !config.responsible_for(()) {
+                return;
+            }
+
+            let resume_upper = Antichain::from_iter(
+                config.source_resume_uppers[&config.id]
+                    .iter()
+                    .map(S3Timestamp::decode_row),
+            );
+            // The range of keys after the watermark. Its lower bound is the
+            // watermark and its upper bound is always positive infinity.
+            let Some(resume_ts) = resume_upper
+                .elements()
+                .iter()
+                .find(|ts| ts.interval().upper == RangeBound::PosInfinity)
+            else {
+                // The source has already ingested everything it ever will.
+                return;
+            };
+            let mut cap = data_cap.delayed(resume_ts);
+            drop(data_cap);
+
+            start_signal.await;
+            // The last key of each listing that advanced the watermark, along
+            // with the time of the listing, in increasing key order. Keys
+            // before the resume watermark were passed before the source
+            // started.
+            let mut passes: Vec<(String, SystemTime)> = vec![];
+            if let RangeBound::Elem(key, _) = &cap.time().interval().lower {
+                passes.push((key.clone(), SystemTime::now()));
+            }
+            info!(
+                source_id = config.id.to_string(),
+                worker_id = config.worker_id,
+                num_workers = config.worker_count,
+                "instantiating S3 source reader for s3://{bucket}/{prefix}"
+            );
+
+            let client = match connection
+                .load_sdk_config(&config.config.connection_context, connection_id)
+                .await
+            {
+                Ok(sdk_config) => mz_aws_util::s3::new_client(&sdk_config),
+                Err(e) => {
+                    let update = HealthStatusUpdate::halting(
+                        format!("failed to load AWS config: {}", e.display_with_causes()),
+                        None,
+                    );
+                    health_output
+                        .give(
+                            &health_cap,
+                            HealthStatusMessage {
+                                index: 0,
+                                namespace: Self::STATUS_NAMESPACE.clone(),
+                                update,
+                            },
+                        )
+                        .await;
+                    // IMPORTANT: wedge forever until the `SuspendAndRestart` is processed.
+                    std::future::pending::<()>().await;
+                    unreachable!("pending future never returns");
+                }
+            };
+
+            let mut resume_uppers = std::pin::pin!(resume_uppers);
+            let mut offset_known = 0;
+            let mut offset_committed = 0;
+            // The watermarks not yet known to be durably recorded, along with
+            // the number of objects ingested once they are reached.
+            let mut uncommitted = VecDeque::new();
+            let mut parquet_decoder = ParquetDecoder::default();
+            // Where the next scan for objects added before the watermark
+            // continues, and the objects found so far.
+            let mut scan_after = None;
+            let mut skipped = BTreeSet::new();
+
+            loop {
+                let result: Result<(), anyhow::Error> = async {
+                    loop {
+                        let start_after = match &cap.time().interval().lower {
+                            RangeBound::Elem(key, _) => Some(key.clone()),
+                            _ => None,
+                        };
+                        let listed_at = SystemTime::now();
+                        let page = list_objects(&client, &bucket, &prefix, start_after).await?;
+                        let Some(last) = page.objects.last().map(|o| o.key.clone()) else {
+                            break;
+                        };
+
+                        // Directory placeholder objects, whose keys end in `/`,
+                        // are skipped but still advance the watermark.
+                        let keys = page
+                            .objects
+                            .iter()
+                            .map(|o| &o.key)
+                            .filter(|key| !key.ends_with('/'));
+                        let (client, bucket) = (&client, &bucket);
+                        let mut objects = stream::iter(keys)
+                            .map(|key| async move {
+                                get_object(client, bucket, key)
+                                    .await
+                                    .map(|bytes| (key, bytes))
+                            })
+                            .buffered(MAX_CONCURRENT_FETCHES);
+                        while let Some(object) = objects.next().await {
+                            let (key, Some(bytes)) = object? else {
+                                // The object was deleted before we got to it.
+                                continue;
+                            };
+                            let messages = match &format {
+                                S3ObjectFormat::Lines { header } => {
+                                    let mut lines = split_lines(&bytes);
+                                    if *header {
+                                        lines.next();
+                                    }
+                                    lines
+                                        .map(|line| Ok(message(Row::pack([Datum::Bytes(line)]))))
+                                        .collect()
+                                }
+                                S3ObjectFormat::Parquet => match parquet_decoder.decode(bytes) {
+                                    Ok(rows) => {
+                                        rows.into_iter().map(|row| Ok(message(row))).collect()
+                                    }
+                                    Err(e) => {
+                                        let err = anyhow!(
+                                            "failed to decode s3://{bucket}/{key}: {}",
+                                            e.display_with_causes()
+                                        );
+                                        vec![Err(SourceReaderError::other_definite(err))]
+                                    }
+                                },
+                            };
+                            for message in messages {
+                                data_output
+                                    .give(&cap, ((0, message), cap.time().clone(), 1))
+                                    .await;
+                            }
+                            offset_known += 1;
+                        }
+
+                        // Advancing the watermark past the last key of the page
+                        // completes all the objects emitted above.
+                        passes.push((last.clone(), listed_at));
+                        let watermark = RangeBound::after(last);
+                        cap.downgrade(&Partitioned::new_range(
+                            watermark.clone(),
+                            RangeBound::PosInfinity,
+                            MzOffset::from(0),
+                        ));
+                        uncommitted.push_back((watermark, offset_known));
+
+                        if !page.truncated {
+                            break;
+                        }
+                    }
+
+                    // Scan the next page of the keys before the watermark for
+                    // objects that appeared after the watermark passed them.
+                    let Some((watermark, _)) = passes.last() else {
+                        return Ok(());
+                    };
+                    let page = list_objects(&client, &bucket, &prefix, scan_after.take()).await?;
+                    for object in page.objects.iter() {
+                        if object.key > *watermark {
+                            break;
+                        }
+                        let i = passes.partition_point(|(last, _)| *last < object.key);
+                        let passed_at = passes[i].1;
+                        if !object.key.ends_with('/')
+                            && object
+                                .last_modified
+                                .is_some_and(|modified| modified > passed_at + MAX_CLOCK_SKEW)
+                        {
+                            skipped.insert(object.key.clone());
+                        }
+                    }
+                    scan_after = match page.objects.last() {
+                        Some(object) if page.truncated && object.key < *watermark => {
+                            Some(object.key.clone())
+                        }
+                        // Start over from the first key with the next poll.
+                        _ => None,
+                    };
+                    Ok(())
+                }
+                .await;
+
+                let update = match (&result, skipped.first()) {
+                    (Ok(()), None) => HealthStatusUpdate::running(),
+                    (Ok(()), Some(example)) => HealthStatusUpdate::stalled(
+                        format!(
+                            "objects added or overwritten under s3://{bucket}/{prefix} after the \
+                            source passed their keys were not ingested: found {}, including \
+                            s3://{bucket}/{example}",
+                            skipped.len()
+                        ),
+                        Some(
+                            "The source ingests objects in key order, so new objects must have \
+                            keys after those of all existing objects."
+                                .into(),
+                        ),
+                    ),
+                    (Err(e), _) => {
+                        HealthStatusUpdate::halting(format!("{}", e.display_with_causes()), None)
+                    }
+                };
+                health_output
+                    .give(
+                        &health_cap,
+                        HealthStatusMessage {
+                            index: 0,
+                            namespace: Self::STATUS_NAMESPACE.clone(),
+                            update,
+                        },
+                    )
+                    .await;
+                if result.is_err() {
+                    // IMPORTANT: wedge forever until the `SuspendAndRestart` is processed.
+                    std::future::pending::<()>().await;
+                    unreachable!("pending future never returns");
+                }
+
+                stats_output
+                    .give(
+                        &stats_cap,
+                        ProgressStatisticsUpdate::SteadyState {
+                            offset_known,
+                            offset_committed,
+                        },
+                    )
+                    .await;
+
+                let Some(poll_interval) = poll_interval else {
+                    // Without a poll interval the source only ingests the
+                    // objects it found in its first listing. Dropping all
+                    // capabilities completes the source.
+                    return;
+                };
+                let mut sleep = std::pin::pin!(tokio::time::sleep(poll_interval));
+                loop {
+                    tokio::select! {
+                        _ = &mut sleep => break,
+                        Some(frontier) = resume_uppers.next() => {
+                            // The watermark only grows, so the frontier is
+                            // either empty or a single range after it.
+                            let committed = |watermark: &RangeBound<String>| {
+                                frontier
+                                    .elements()
+                                    .iter()
+                                    .all(|ts| *watermark <= ts.interval().lower)
+                            };
+                            while let Some((watermark, count)) = uncommitted.front() {
+                                if !committed(watermark) {
+                                    break;
+                                }
+                                offset_committed = *count;
+                                uncommitted.pop_front();
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        (
+            stream.as_collection(),
+            None,
+            health_stream,
+            stats_stream,
+            vec![button.press_on_drop()],
+        )
+    }
+}
+
+/// Wraps a decoded value into a [`SourceMessage`].
+fn message(value: Row) -> SourceMessage {
+    SourceMessage {
+        key: Row::default(),
+        value,
+        metadata: Row::default(),
+    }
+}
+
+/// Splits the contents of an object into lines, accepting both `\n` and
+/// `\r\n` line endings. A trailing line ending does not produce an empty line.
+fn split_lines(bytes: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let bytes = bytes.strip_suffix(b"\n").unwrap_or(bytes);
+    let mut lines = bytes.split(|b| *b == b'\n');
+    if bytes.is_empty() {
+        // Exhaust the iterator so that empty objects produce no lines.
+        lines.next();
+    }
+    lines.map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+}
+
+/// An object listed under a prefix.
+struct ListedObject {
+    /// The key of the object.
+    key: String,
+    /// When the object was last written, if S3 reported it.
+    last_modified: Option<SystemTime>,
+}
+
+/// A page of the objects under a prefix.
+struct ListPage {
+    /// The objects of the page, in lexicographic order of their keys.
+    objects: Vec<ListedObject>,
+    /// Whether there are more keys after the last key of the page.
+    truncated: bool,
+}
+
+/// Lists a page of the objects in `bucket` whose keys start with `prefix` and
+/// come after `start_after`.
+async fn list_objects(
+    client: &Client,
+    bucket: &str,
+    prefix: &str,
+    start_after: Option<String>,
+) -> Result<ListPage, anyhow::Error> {
+    let resp = client
+        .list_objects_v2()
+        .bucket(bucket)
+        .prefix(prefix)
+        .set_start_after(start_after)
+        .send()
+        .await
+        .map_err(|e| {
+            anyhow!(
+                "failed to list s3://{bucket}/{prefix}: {}",
+                e.display_with_causes()
+            )
+        })?;
+    let objects = resp
+        .contents
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|object| {
+            Some(ListedObject {
+                key: object.key?,
+                last_modified: object
+                    .last_modified
+                    .and_then(|modified| SystemTime::try_from(modified).ok()),
+            })
+        })
+        .collect();
+    Ok(ListPage {
+        objects,
+        truncated: resp.is_truncated.unwrap_or(false),
+    })
+}
+
+/// Fetches the contents of an object, returning `None` if it does not exist.
+async fn get_object(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+) -> Result<Option<Vec<u8>>, anyhow::Error> {
+    let object = match client.get_object().bucket(bucket).key(key).send().await {
+        Ok(object) => object,
+        Err(SdkError::ServiceError(err)) if err.err().is_no_such_key() => return Ok(None),
+        Err(e) => {
+            return Err(anyhow!(
+                "failed to get s3://{bucket}/{key}: {}",
+                e.display_with_causes()
+            ))
+        }
+    };
+    let body = object.body.collect().await.map_err(|e| {
+        anyhow!(
+            "failed to read s3://{bucket}/{key}: {}",
+            e.display_with_causes()
+        )
+    })?;
+    Ok(Some(body.into_bytes().to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[mz_ore::test]
+    fn test_split_lines() {
+        let lines = |bytes: &'static [u8]| split_lines(bytes).collect::<Vec<_>>();
+        assert_eq!(lines(b""), Vec::<&[u8]>::new());
+        assert_eq!(lines(b"a"), vec![b"a".as_slice()]);
+        assert_eq!(lines(b"a\n"), vec![b"a".as_slice()]);
+        assert_eq!(lines(b"a\r\nb\r\n"), vec![b"a".as_slice(), b"b"]);
+        assert_eq!(lines(b"a\n\nb"), vec![b"a".as_slice(), b"", b"b"]);
+    }
+}
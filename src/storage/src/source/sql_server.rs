@@ -0,0 +1,188 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Code to render the ingestion dataflow of a [`SqlServerSourceConnection`].
+//!
+//! SQL Server exposes the changes of tables that have change data capture (CDC) enabled through
+//! change tables, which a capture job fills from the transaction log. Each change is tagged with
+//! the commit LSN of its transaction, which is the timestamp of this dataflow.
+//!
+//! The dataflow consists of a single replication operator that snapshots tables that have not
+//! been snapshot yet and then polls the change tables. See the [replication] module for more
+//! information.
+//!
+//! # Error handling
+//!
+//! Errors that are caused by the data of a specific table, like values that cannot be decoded or
+//! a table whose schema changed incompatibly, are [`DefiniteError`]s and end up in the error
+//! collection of that table's subsource.
+//!
+//! All other errors are [`TransientError`]s. They are published to the source status and trigger
+//! a restart of the dataflow.
+
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::rc::Rc;
+
+use differential_dataflow::Collection;
+use serde::{Deserialize, Serialize};
+use timely::dataflow::operators::{Concat, Map, ToStream};
+use timely::dataflow::{Scope, Stream};
+use timely::progress::Antichain;
+
+use mz_ore::error::ErrorExt;
+use mz_repr::{Diff, Row};
+use mz_sql_server_util::{Lsn, SqlServerError};
+use mz_storage_types::errors::SourceErrorDetails;
+use mz_storage_types::sources::{SourceTimestamp, SqlServerSourceConnection};
+use mz_timely_util::builder_async::PressOnDropButton;
+
+use crate::healthcheck::{HealthStatusMessage, HealthStatusUpdate, StatusNamespace};
+use crate::source::types::{ProgressStatisticsUpdate, SourceRender};
+use crate::source::{RawSourceCreationConfig, SourceMessage, SourceReaderError};
+
+mod replication;
+mod statistics;
+
+impl SourceRender for SqlServerSourceConnection {
+    type Time = Lsn;
+
+    const STATUS_NAMESPACE: StatusNamespace = StatusNamespace::SqlServer;
+
+    /// Render the ingestion dataflow. This function only connects things together and contains no
+    /// actual processing logic.
+    fn render<G: Scope<Timestamp = Lsn>>(
+        self,
+        scope: &mut G,
+        config: RawSourceCreationConfig,
+        resume_uppers: impl futures::Stream<Item = Antichain<Lsn>> + 'static,
+        _start_signal: impl std::future::Future<Output = ()> + 'static,
+    ) -> (
+        Collection<G, (usize, Result<SourceMessage, SourceReaderError>), Diff>,
+        Option<Stream<G, Infallible>>,
+        Stream<G, HealthStatusMessage>,
+        Stream<G, ProgressStatisticsUpdate>,
+        Vec<PressOnDropButton>,
+    ) {
+        let subsource_resume_uppers: BTreeMap<_, _> = config
+            .source_resume_uppers
+            .iter()
+            .map(|(id, upper)| {
+                assert!(
+                    config.source_exports.contains_key(id),
+                    "all source resume uppers must be present in source exports"
+                );
+
+                (*id, Antichain::from_iter(upper.iter().map(Lsn::decode_row)))
+            })
+            .collect();
+
+        // Collect the tables that we will be ingesting, keyed by their output index. Index zero
+        // maps to the main source.
+        let table_info: BTreeMap<_, _> = self
+            .details
+            .tables
+            .iter()
+            .enumerate()
+            .map(|(i, desc)| (i + 1, desc.clone()))
+            .collect();
+
+        let (repl_updates, uppers, repl_err, repl_token) = replication::render(
+            scope.clone(),
+            config.clone(),
+            self.clone(),
+            subsource_resume_uppers,
+            table_info,
+        );
+
+        let (stats_stream, stats_err, stats_token) =
+            statistics::render(scope.clone(), config, self, resume_uppers);
+
+        let updates = repl_updates.map(|(output, res)| {
+            let res = res.map(|row| SourceMessage {
+                key: Row::default(),
+                value: row,
+                metadata: Row::default(),
+            });
+            (output, res)
+        });
+
+        let health_init = std::iter::once(HealthStatusMessage {
+            index: 0,
+            namespace: Self::STATUS_NAMESPACE,
+            update: HealthStatusUpdate::Running,
+        })
+        .to_stream(scope);
+
+        let health_errs = repl_err.concat(&stats_err).map(move |err| {
+            // This update will cause the dataflow to restart
+            let err_string = err.display_with_causes().to_string();
+            let update = HealthStatusUpdate::halting(err_string, None);
+
+            let namespace = match &*err {
+                TransientError::SqlServer(SqlServerError::Ssh(_)) => StatusNamespace::Ssh,
+                _ => Self::STATUS_NAMESPACE,
+            };
+
+            HealthStatusMessage {
+                index: 0,
+                namespace,
+                update,
+            }
+        });
+        let health = health_init.concat(&health_errs);
+
+        (
+            updates,
+            Some(uppers),
+            health,
+            stats_stream,
+            vec![repl_token, stats_token],
+        )
+    }
+}
+
+/// A transient error that never ends up in the collection of a specific table.
+#[derive(Debug, thiserror::Error)]
+pub enum TransientError {
+    #[error(transparent)]
+    SqlServer(#[from] SqlServerError),
+    #[error(transparent)]
+    Generic(#[from] anyhow::Error),
+}
+
+impl From<tiberius::error::Error> for TransientError {
+    fn from(err: tiberius::error::Error) -> Self {
+        TransientError::SqlServer(err.into())
+    }
+}
+
+/// The stream of transient errors produced by an operator of this dataflow.
+type TransientErrors<G> = Stream<G, Rc<TransientError>>;
+
+/// A definite error that always ends up in the collection of a specific table.
+#[derive(Debug, Clone, Serialize, Deserialize, thiserror::Error)]
+pub enum DefiniteError {
+    #[error("unable to decode: {0}")]
+    ValueDecodeError(String),
+    #[error("table was dropped or no longer has change data capture enabled: {0}")]
+    TableDropped(String),
+    #[error("incompatible schema change: {0}")]
+    IncompatibleSchema(String),
+    #[error("change data for {0} is no longer available at LSN {1}")]
+    ChangesNotAvailable(String, Lsn),
+}
+
+impl From<DefiniteError> for SourceReaderError {
+    fn from(err: DefiniteError) -> Self {
+        SourceReaderError {
+            inner: SourceErrorDetails::Other(err.to_string()),
+        }
+    }
+}
@@ -0,0 +1,335 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Renders the replication side of the [`SqlServerSourceConnection`] ingestion dataflow.
+//!
+//! # Progress tracking
+//!
+//! Changes are emitted at the commit LSN of the transaction that made them. The frontier of the
+//! dataflow is the LSN following the largest LSN that the capture job has copied into the change
+//! tables, which `sys.fn_cdc_get_max_lsn` reports. All changes below that frontier are present in
+//! the change tables and have been emitted.
+//!
+//! # Snapshots
+//!
+//! Tables whose subsource has not been snapshot yet are read inside a single `SERIALIZABLE`
+//! transaction that takes shared table locks (`TABLOCK, HOLDLOCK`). Once all tables have been
+//! read the end of the transaction log is recorded before the transaction commits. Because the
+//! locks prevent concurrent writes to the tables, the snapshot contains exactly the changes up
+//! to and including that LSN. Snapshot updates are emitted at the minimum timestamp.
+//!
+//! The locks block writers for the duration of the snapshot, so large tables should be added to
+//! a source while the upstream database is quiet.
+//!
+//! # Rewinds
+//!
+//! When a table is added to a source that already made progress, replication resumes from the
+//! frontier of the existing tables, which is before the snapshot of the new table. Changes of the
+//! new table that are already part of its snapshot are retracted at the minimum timestamp and
+//! emitted again at their LSN, such that the snapshot appears to have been taken at the resume
+//! point.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::Infallible;
+use std::time::Duration;
+
+use differential_dataflow::{AsCollection, Collection};
+use futures::StreamExt;
+use itertools::Itertools;
+use timely::dataflow::{Scope, Stream};
+use timely::progress::{Antichain, Timestamp};
+use tracing::{info, trace};
+
+use mz_ore::result::ResultExt;
+use mz_repr::{Diff, GlobalId, Row};
+use mz_sql_server_util::{
+    get_changes, get_log_end_lsn, get_max_lsn, get_min_lsn, pack_sql_server_row, quote_identifier,
+    schema_info, CdcChange, Lsn, SchemaRequest, SqlServerError, SqlServerTableDesc,
+};
+use mz_storage_types::sources::SqlServerSourceConnection;
+use mz_timely_util::builder_async::{OperatorBuilder as AsyncOperatorBuilder, PressOnDropButton};
+
+use crate::source::types::SourceReaderError;
+use crate::source::RawSourceCreationConfig;
+
+use super::{DefiniteError, TransientError, TransientErrors};
+
+/// Used as a partition id to determine if the worker is responsible for reading from the change
+/// tables.
+static REPL_READER: &str = "reader";
+
+/// How often the change tables are polled for new changes.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Renders the replication dataflow. See the module documentation for more information.
+pub(crate) fn render<G: Scope<Timestamp = Lsn>>(
+    scope: G,
+    config: RawSourceCreationConfig,
+    connection: SqlServerSourceConnection,
+    subsource_resume_uppers: BTreeMap<GlobalId, Antichain<Lsn>>,
+    table_info: BTreeMap<usize, SqlServerTableDesc>,
+) -> (
+    Collection<G, (usize, Result<Row, SourceReaderError>), Diff>,
+    Stream<G, Infallible>,
+    TransientErrors<G>,
+    PressOnDropButton,
+) {
+    let op_name = format!("SqlServerReplicationReader({})", config.id);
+    let mut builder = AsyncOperatorBuilder::new(op_name, scope);
+
+    let (mut data_output, data_stream) = builder.new_output();
+    let (_upper_output, upper_stream) = builder.new_output();
+
+    let (button, transient_errors) = builder.build_fallible::<TransientError, _>(move |caps| {
+        Box::pin(async move {
+            let (id, worker_id) = (config.id, config.worker_id);
+            let [data_cap_set, upper_cap_set]: &mut [_; 2] = caps.try_into().unwrap();
+
+            // Only run the replication reader on the worker responsible for it.
+            if !config.responsible_for(REPL_READER) {
+                return Ok(());
+            }
+
+            let connection_config = connection
+                .connection
+                .config(
+                    &*config.config.connection_context.secrets_reader,
+                    &config.config,
+                )
+                .await?;
+
+            let mut client = connection_config
+                .connect(
+                    &format!("timely-{worker_id} SQL Server replication reader"),
+                    &config.config.connection_context.ssh_tunnel_manager,
+                )
+                .await?;
+
+            // Determine which outputs still need to be snapshot, and the frontier of the ones
+            // that already have been.
+            let mut snapshot_outputs = BTreeSet::new();
+            let mut resume_upper = Antichain::new();
+            for (export_id, upper) in subsource_resume_uppers {
+                if export_id == id {
+                    continue;
+                }
+                if *upper == [Lsn::minimum()] {
+                    snapshot_outputs.insert(config.source_exports[&export_id].output_index);
+                } else {
+                    resume_upper.extend(upper.iter().cloned());
+                }
+            }
+
+            // Verify that the upstream tables still match the ones recorded during purification.
+            // Change tables keep their shape for the lifetime of their capture instance, so a
+            // changed capture instance or column list makes the table unusable.
+            let request = SchemaRequest::Tables(
+                table_info
+                    .values()
+                    .map(|desc| (desc.schema_name.as_str(), desc.name.as_str()))
+                    .collect(),
+            );
+            let upstream_tables = schema_info(&mut client, &request).await?;
+
+            let mut tables = BTreeMap::new();
+            for (output_index, desc) in table_info {
+                let upstream = upstream_tables
+                    .iter()
+                    .find(|t| t.schema_name == desc.schema_name && t.name == desc.name);
+                let err = match upstream {
+                    None => Some(DefiniteError::TableDropped(qualified_name(&desc))),
+                    Some(upstream) => match desc.determine_compatibility(upstream) {
+                        Ok(()) => None,
+                        Err(err) => Some(DefiniteError::IncompatibleSchema(err.to_string())),
+                    },
+                };
+                match err {
+                    None => {
+                        tables.insert(output_index, desc);
+                    }
+                    Some(err) => {
+                        let update = ((output_index, Err(err)), Lsn::minimum(), 1);
+                        data_output.give(&data_cap_set[0], update).await;
+                    }
+                }
+            }
+
+            // Snapshot all tables that need it in a single transaction.
+            let mut row_buf = Row::default();
+            let to_snapshot = tables
+                .iter()
+                .filter(|(output_index, _)| snapshot_outputs.contains(*output_index))
+                .collect_vec();
+            let mut rewinds = BTreeMap::new();
+            if !to_snapshot.is_empty() {
+                info!(%id, "timely-{worker_id} snapshotting {} tables", to_snapshot.len());
+                client
+                    .execute("SET TRANSACTION ISOLATION LEVEL SERIALIZABLE", &[])
+                    .await?;
+                client.execute("BEGIN TRANSACTION", &[]).await?;
+                for (output_index, desc) in to_snapshot.iter() {
+                    let columns = desc
+                        .columns
+                        .iter()
+                        .map(|c| quote_identifier(&c.name))
+                        .join(", ");
+                    let query = format!(
+                        "SELECT {columns} FROM {}.{} WITH (TABLOCK, HOLDLOCK)",
+                        quote_identifier(&desc.schema_name),
+                        quote_identifier(&desc.name),
+                    );
+                    let mut rows = client.simple_query(query).await?.into_row_stream();
+                    while let Some(row) = rows.next().await {
+                        let row = decode_row(&mut row_buf, row?, 0, desc)?;
+                        let update = ((**output_index, row), Lsn::minimum(), 1);
+                        data_output.give(&data_cap_set[0], update).await;
+                    }
+                }
+                let snapshot_lsn = get_log_end_lsn(&mut client).await?;
+                client.execute("COMMIT TRANSACTION", &[]).await?;
+
+                let snapshot_upper = snapshot_lsn.increment().expect("LSN space exhausted");
+                trace!(%id, "timely-{worker_id} snapshot upper {snapshot_upper}");
+                if resume_upper.is_empty() {
+                    resume_upper.insert(snapshot_upper);
+                }
+                for (output_index, _) in to_snapshot {
+                    rewinds.insert(*output_index, snapshot_upper);
+                }
+            }
+
+            let Some(mut upper) = resume_upper.into_option() else {
+                // There is nothing left to ingest.
+                return Ok(());
+            };
+            rewinds.retain(|_, snapshot_upper| *snapshot_upper > upper);
+
+            // Determine where each table starts reading its changes. Changes that the CDC cleanup
+            // job already removed cannot be recovered.
+            let mut lowers = BTreeMap::new();
+            for (output_index, desc) in tables.iter() {
+                let min_lsn = get_min_lsn(&mut client, &desc.capture_instance).await?;
+                let lower = match (min_lsn, rewinds.get(output_index)) {
+                    (None, _) => Err(DefiniteError::TableDropped(qualified_name(desc))),
+                    (Some(min_lsn), None) if min_lsn <= upper => Ok(upper),
+                    // The capture instance may have been created after the resume point, in which
+                    // case there are no changes before its minimum LSN.
+                    (Some(min_lsn), Some(snapshot_upper)) if min_lsn <= *snapshot_upper => {
+                        Ok(std::cmp::max(min_lsn, upper))
+                    }
+                    (Some(_), _) => Err(DefiniteError::ChangesNotAvailable(
+                        qualified_name(desc),
+                        upper,
+                    )),
+                };
+                match lower {
+                    Ok(lower) => {
+                        lowers.insert(*output_index, lower);
+                    }
+                    Err(err) => {
+                        let update = ((*output_index, Err(err)), Lsn::minimum(), 1);
+                        data_output.give(&data_cap_set[0], update).await;
+                    }
+                }
+            }
+            tables.retain(|output_index, _| lowers.contains_key(output_index));
+            rewinds.retain(|output_index, _| lowers.contains_key(output_index));
+
+            // Hold on to the minimum timestamp until all rewinds have been performed.
+            let mut rewind_cap =
+                (!rewinds.is_empty()).then(|| data_cap_set.delayed(&Lsn::minimum()));
+            data_cap_set.downgrade([&upper]);
+            upper_cap_set.downgrade([&upper]);
+            trace!(%id, "timely-{worker_id} replication reader started at {upper}");
+
+            loop {
+                let max_lsn = get_max_lsn(&mut client).await?;
+                let Some(max_lsn) = max_lsn.filter(|max_lsn| *max_lsn >= upper) else {
+                    tokio::time::sleep(POLL_INTERVAL).await;
+                    continue;
+                };
+
+                for (output_index, desc) in tables.iter() {
+                    let lower = lowers[output_index];
+                    if lower > max_lsn {
+                        continue;
+                    }
+                    let mut changes = get_changes(&mut client, desc, lower, max_lsn).await?;
+                    while let Some(change) = changes.next().await {
+                        let change = change?;
+                        let diff = change.operation.diff();
+                        let lsn = change.lsn;
+                        let row =
+                            decode_row(&mut row_buf, change.row, CdcChange::DATA_OFFSET, desc)?;
+
+                        if let Some(snapshot_upper) = rewinds.get(output_index) {
+                            if lsn < *snapshot_upper {
+                                let cap = rewind_cap.as_ref().expect("rewinds pending");
+                                let update = ((*output_index, row.clone()), Lsn::minimum(), -diff);
+                                data_output.give(cap, update).await;
+                            }
+                        }
+                        let update = ((*output_index, row), lsn, diff);
+                        data_output.give(&data_cap_set[0], update).await;
+                    }
+                }
+
+                upper = max_lsn.increment().expect("LSN space exhausted");
+                for lower in lowers.values_mut() {
+                    *lower = upper;
+                }
+                rewinds.retain(|_, snapshot_upper| *snapshot_upper > upper);
+                if rewinds.is_empty() {
+                    rewind_cap = None;
+                }
+                data_cap_set.downgrade([&upper]);
+                upper_cap_set.downgrade([&upper]);
+                trace!(%id, "timely-{worker_id} replication reader advanced to {upper}");
+
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        })
+    });
+
+    let replication_updates = data_stream
+        .as_collection()
+        .map(|(output_index, row)| (output_index, row.err_into()));
+
+    (
+        replication_updates,
+        upper_stream,
+        transient_errors,
+        button.press_on_drop(),
+    )
+}
+
+/// Decodes the columns of `row` starting at `offset`. Values that cannot be decoded are reported
+/// as definite errors in the collection of the table.
+fn decode_row(
+    row_buf: &mut Row,
+    row: tiberius::Row,
+    offset: usize,
+    desc: &SqlServerTableDesc,
+) -> Result<Result<Row, DefiniteError>, TransientError> {
+    match pack_sql_server_row(row_buf, row, offset, desc) {
+        Ok(row) => Ok(Ok(row)),
+        Err(err @ SqlServerError::ValueDecodeError { .. }) => {
+            Ok(Err(DefiniteError::ValueDecodeError(err.to_string())))
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn qualified_name(desc: &SqlServerTableDesc) -> String {
+    format!(
+        "{}.{}",
+        quote_identifier(&desc.schema_name),
+        quote_identifier(&desc.name)
+    )
+}
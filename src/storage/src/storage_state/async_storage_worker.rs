@@ -28,8 +28,9 @@ use mz_service::local::Activatable;
 use mz_storage_types::controller::CollectionMetadata;
 use mz_storage_types::sources::{
     GenericSourceConnection, IngestionDescription, KafkaSourceConnection,
-    LoadGeneratorSourceConnection, MySqlSourceConnection, PostgresSourceConnection,
-    SourceConnection, SourceData, SourceEnvelope, SourceTimestamp,
+    LoadGeneratorSourceConnection, MongoDbSourceConnection, MySqlSourceConnection,
+    PostgresSourceConnection, S3SourceConnection, SourceConnection, SourceData, SourceEnvelope,
+    SourceTimestamp, SqlServerSourceConnection,
 };
 use timely::order::PartialOrder;
 use timely::progress::{Antichain, Timestamp};
@@ -370,6 +371,39 @@ impl<T: Timestamp + Lattice + Codec64 + Display> AsyncStorageWorker<T> {
                                 .await;
                                 to_vec_row(uppers)
                             }
+                            GenericSourceConnection::SqlServer(_) => {
+                                let uppers = reclock_resume_uppers::<SqlServerSourceConnection, _>(
+                                    &id,
+                                    &persist_clients,
+                                    &ingestion_description,
+                                    as_of.clone(),
+                                    &resume_uppers,
+                                )
+                                .await;
+                                to_vec_row(uppers)
+                            }
+                            GenericSourceConnection::MongoDb(_) => {
+                                let uppers = reclock_resume_uppers::<MongoDbSourceConnection, _>(
+                                    &id,
+                                    &persist_clients,
+                                    &ingestion_description,
+                                    as_of.clone(),
+                                    &resume_uppers,
+                                )
+                                .await;
+                                to_vec_row(uppers)
+                            }
+                            GenericSourceConnection::S3(_) => {
+                                let uppers = reclock_resume_uppers::<S3SourceConnection, _>(
+                                    &id,
+                                    &persist_clients,
+                                    &ingestion_description,
+                                    as_of.clone(),
+                                    &resume_uppers,
+                                )
+                                .await;
+                                to_vec_row(uppers)
+                            }
                             GenericSourceConnection::LoadGenerator(_) => {
                                 let uppers =
                                     reclock_resume_uppers::<LoadGeneratorSourceConnection, _>(
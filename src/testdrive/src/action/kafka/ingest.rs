@@ -35,6 +35,9 @@ enum Format {
         schema: String,
         confluent_wire_format: bool,
     },
+    AvroOcf {
+        schema: String,
+    },
     Protobuf {
         descriptor_file: String,
         message: String,
@@ -55,6 +58,11 @@ enum Transcoder {
         schema: Schema,
         schema_id: i32,
     },
+    /// Writes an Avro object container file from each row, which must be a
+    /// JSON array of records.
+    AvroOcf {
+        schema: Schema,
+    },
     Protobuf {
         message: MessageDescriptor,
         confluent_wire_format: bool,
@@ -112,6 +120,18 @@ impl Transcoder {
                     Ok(None)
                 }
             }
+            Transcoder::AvroOcf { schema } => {
+                if let Some(vals) = Self::decode_json::<_, Vec<serde_json::Value>>(row)? {
+                    let mut writer = avro::Writer::new(schema.clone(), vec![]);
+                    for val in vals {
+                        writer.append(avro::from_json(&val, schema.top_node())?)?;
+                    }
+                    writer.flush()?;
+                    Ok(Some(writer.into_inner()))
+                } else {
+                    Ok(None)
+                }
+            }
             Transcoder::Protobuf {
                 message,
                 confluent_wire_format,
@@ -179,6 +199,9 @@ pub async fn run_ingest(
             schema: cmd.args.string("schema")?,
             confluent_wire_format: cmd.args.opt_bool("confluent-wire-format")?.unwrap_or(true),
         },
+        "avro-ocf" => Format::AvroOcf {
+            schema: cmd.args.string("schema")?,
+        },
         "protobuf" => {
             let descriptor_file = cmd.args.string("descriptor-file")?;
             let message = cmd.args.string("message")?;
@@ -292,7 +315,7 @@ pub async fn run_ingest(
                     confluent_wire_format,
                     ..
                 } => Some(*confluent_wire_format),
-                Format::Bytes { .. } => None,
+                Format::AvroOcf { .. } | Format::Bytes { .. } => None,
             }
         }
         match (is_confluent_format(kf), is_confluent_format(&format)) {
@@ -425,6 +448,11 @@ async fn make_transcoder(
                 Ok(Transcoder::PlainAvro { schema })
             }
         }
+        Format::AvroOcf { schema } => {
+            let schema = avro::parse_schema(&schema)
+                .with_context(|| format!("parsing avro schema: {}", schema))?;
+            Ok(Transcoder::AvroOcf { schema })
+        }
         Format::Protobuf {
             descriptor_file,
             message,
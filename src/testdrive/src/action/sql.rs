@@ -18,7 +18,7 @@ use md5::{Digest, Md5};
 use mz_ore::collections::CollectionExt;
 use mz_ore::retry::Retry;
 use mz_ore::str::StrExt;
-use mz_pgrepr::{Interval, Jsonb, Numeric, UInt2, UInt4, UInt8};
+use mz_pgrepr::{Inet, Interval, Jsonb, MacAddr, Numeric, UInt2, UInt4, UInt8};
 use mz_repr::adt::range::Range;
 use mz_sql_parser::ast::{Raw, Statement};
 use postgres_array::Array;
@@ -64,6 +64,7 @@ pub async fn run_sql(mut cmd: SqlCommand, state: &State) -> Result<ControlFlow,
         | CreateTable(_)
         | CreateIndex(_)
         | CreateType(_)
+        | CreateFunction(_)
         | CreateRole(_)
         | AlterObjectRename(_)
         | AlterIndex(_)
@@ -584,6 +585,9 @@ pub fn decode_row(
             Type::INTERVAL => row.get::<_, Option<Interval>>(i).map(|x| x.to_string()),
             Type::JSONB => row.get::<_, Option<Jsonb>>(i).map(|v| v.0.to_string()),
             Type::UUID => row.get::<_, Option<uuid::Uuid>>(i).map(|v| v.to_string()),
+            Type::INET => row.get::<_, Option<Inet>>(i).map(|v| v.0.to_string()),
+            Type::CIDR => row.get::<_, Option<Inet>>(i).map(|v| v.0.to_cidr_string()),
+            Type::MACADDR => row.get::<_, Option<MacAddr>>(i).map(|v| v.0.to_string()),
             Type::BOOL_ARRAY => row
                 .get::<_, Option<Array<ArrayElement<bool>>>>(i)
                 .map(|a| a.to_string()),
@@ -16,7 +16,8 @@ use mz_expr::{
     VariadicFunc,
 };
 use mz_ore::cast::CastLossy;
-use mz_repr::GlobalId;
+use mz_repr::stats::{CollectionStatistics, ColumnStatistics};
+use mz_repr::{Datum, GlobalId};
 
 use mz_repr::explain::ExprHumanizer;
 use ordered_float::OrderedFloat;
@@ -42,12 +43,18 @@ pub struct Cardinality {
 
 impl Default for Cardinality {
     fn default() -> Self {
+        Cardinality::with_statistics(BTreeMap::new())
+    }
+}
+
+impl Cardinality {
+    /// Creates a [`Cardinality`] attribute that uses the given statistics about global
+    /// collections instead of symbolic variables where they are available
+    pub fn with_statistics(statistics: BTreeMap<GlobalId, CollectionStatistics>) -> Self {
         Cardinality {
             env: Env::default(),
             results: Vec::new(),
-            factorize: Box::new(WorstCaseFactorizer {
-                cardinalities: BTreeMap::new(),
-            }),
+            factorize: Box::new(WorstCaseFactorizer { statistics }),
         }
     }
 }
@@ -70,27 +77,42 @@ pub type SymExp = SymbolicExpression<FactorizerVariable>;
 
 /// A `Factorizer` computes selectivity factors
 pub trait Factorizer {
+    /// Computes the cardinality of the global collection `id`
+    fn global(&self, id: GlobalId) -> SymExp;
     /// Compute selectivity for the flat map of `tf`
     fn flat_map(&self, tf: &TableFunc, input: &SymExp) -> SymExp;
     /// Computes selectivity of the predicate `expr`, given that `unique_columns` are indexed/unique
     ///
+    /// `source` is the global collection whose columns `expr` refers to, if known
+    ///
     /// The result should be in the range [0, 1.0]
-    fn predicate(&self, expr: &MirScalarExpr, unique_columns: &BTreeSet<usize>) -> SymExp;
+    fn predicate(
+        &self,
+        expr: &MirScalarExpr,
+        unique_columns: &BTreeSet<usize>,
+        source: Option<GlobalId>,
+    ) -> SymExp;
     /// Computes selectivity for a filter
+    ///
+    /// `source` is the global collection whose columns the predicates refer to, if known
     fn filter(
         &self,
         predicates: &Vec<MirScalarExpr>,
         keys: &Vec<Vec<usize>>,
+        source: Option<GlobalId>,
         input: &SymExp,
     ) -> SymExp;
     /// Computes selectivity for a join; the cardinality estimate for each input is paired with the keys on that input
     ///
     /// `unique_columns` maps column references (that are indexed/unique) to their relation's index in `inputs`
+    ///
+    /// `sources` maps column references to the global collection and column they read, if known
     fn join(
         &self,
         equivalences: &Vec<Vec<MirScalarExpr>>,
         implementation: &JoinImplementation,
         unique_columns: BTreeMap<usize, usize>,
+        sources: BTreeMap<usize, (GlobalId, usize)>,
         inputs: Vec<&SymExp>,
     ) -> SymExp;
     /// Computes selectivity for a reduce
@@ -113,10 +135,109 @@ pub trait Factorizer {
 }
 
 /// The simplest possible `Factorizer` that aims to generate worst-case, upper-bound cardinalities
+///
+/// Where statistics about a global collection are available, its cardinality is used instead of
+/// a symbolic variable, and the statistics about its columns are used for the selectivity of
+/// comparisons with literals and of equi-joins.
 #[derive(Debug)]
 pub struct WorstCaseFactorizer {
-    /// cardinalities for each `GlobalId` and its unique values
-    pub cardinalities: BTreeMap<FactorizerVariable, usize>,
+    /// statistics for each `GlobalId` and its columns
+    pub statistics: BTreeMap<GlobalId, CollectionStatistics>,
+}
+
+impl WorstCaseFactorizer {
+    /// Returns the statistics about the column `expr` of `source`, if `expr` is a column reference
+    fn column_statistics(
+        &self,
+        expr: &MirScalarExpr,
+        source: Option<GlobalId>,
+    ) -> Option<&ColumnStatistics> {
+        match expr {
+            MirScalarExpr::Column(col) => self.statistics.get(&source?)?.columns.get(col),
+            _ => None,
+        }
+    }
+
+    /// Computes the selectivity of `expr` from the statistics about the columns of `source`
+    ///
+    /// Only `IS NULL` tests and comparisons between a column and a literal are supported; returns
+    /// `None` for other predicates and for columns without statistics.
+    fn column_selectivity(&self, expr: &MirScalarExpr, source: Option<GlobalId>) -> Option<f64> {
+        let literal = |expr: &MirScalarExpr| match expr.as_literal() {
+            Some(Ok(datum)) if !datum.is_null() => Some(datum),
+            _ => None,
+        };
+
+        match expr {
+            MirScalarExpr::CallUnary {
+                func: UnaryFunc::IsNull(_),
+                expr,
+            } => {
+                let column = self.column_statistics(expr, source)?;
+                let cardinality = self.statistics.get(&source?)?.cardinality;
+                if cardinality == 0 {
+                    return None;
+                }
+                Some(f64::min(
+                    1.0,
+                    f64::cast_lossy(column.null_count) / f64::cast_lossy(cardinality),
+                ))
+            }
+            MirScalarExpr::CallBinary { func, expr1, expr2 } => {
+                // whether the column is on the left-hand side of the comparison
+                let (column, datum, column_on_left): (_, Datum, _) = match (
+                    self.column_statistics(expr1, source),
+                    self.column_statistics(expr2, source),
+                ) {
+                    (Some(column), _) => (column, literal(expr2)?, true),
+                    (None, Some(column)) => (column, literal(expr1)?, false),
+                    (None, None) => return None,
+                };
+
+                let equality = 1.0 / f64::cast_lossy(std::cmp::max(column.distinct_count, 1));
+                match func {
+                    BinaryFunc::Eq => Some(equality),
+                    BinaryFunc::NotEq => Some(1.0 - equality),
+                    BinaryFunc::Lt | BinaryFunc::Lte | BinaryFunc::Gt | BinaryFunc::Gte => {
+                        let below = column.fraction_below(datum)?;
+                        let column_is_less =
+                            matches!(func, BinaryFunc::Lt | BinaryFunc::Lte) == column_on_left;
+                        if column_is_less {
+                            Some(below)
+                        } else {
+                            Some(1.0 - below)
+                        }
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Computes the selectivity of the equivalence `equiv` from the number of distinct values of
+    /// its columns: matching rows are bounded by the column with the most distinct values
+    ///
+    /// Returns `None` if none of the columns have statistics.
+    fn equivalence_selectivity(
+        &self,
+        equiv: &[MirScalarExpr],
+        sources: &BTreeMap<usize, (GlobalId, usize)>,
+    ) -> Option<f64> {
+        let distinct_count = equiv
+            .iter()
+            .filter_map(|expr| match expr {
+                MirScalarExpr::Column(col) => {
+                    let (id, col) = sources.get(col)?;
+                    let column = self.statistics.get(id)?.columns.get(col)?;
+                    Some(column.distinct_count)
+                }
+                _ => None,
+            })
+            .max()?;
+
+        Some(1.0 / f64::cast_lossy(std::cmp::max(distinct_count, 1)))
+    }
 }
 
 /// The default selectivity for predicates we know nothing about.
@@ -125,6 +246,13 @@ pub struct WorstCaseFactorizer {
 pub const WORST_CASE_SELECTIVITY: f64 = 0.1;
 
 impl Factorizer for WorstCaseFactorizer {
+    fn global(&self, id: GlobalId) -> SymExp {
+        match self.statistics.get(&id) {
+            Some(statistics) => SymExp::usize(statistics.cardinality),
+            None => SymbolicExpression::symbolic(FactorizerVariable::Id(id)),
+        }
+    }
+
     fn flat_map(&self, tf: &TableFunc, input: &SymExp) -> SymExp {
         match tf {
             TableFunc::Wrap { types, width } => {
@@ -137,7 +265,16 @@ impl Factorizer for WorstCaseFactorizer {
         }
     }
 
-    fn predicate(&self, expr: &MirScalarExpr, unique_columns: &BTreeSet<usize>) -> SymExp {
+    fn predicate(
+        &self,
+        expr: &MirScalarExpr,
+        unique_columns: &BTreeSet<usize>,
+        source: Option<GlobalId>,
+    ) -> SymExp {
+        if let Some(selectivity) = self.column_selectivity(expr, source) {
+            return SymExp::f64(selectivity);
+        }
+
         let index_cardinality = |expr: &MirScalarExpr| -> Option<SymExp> {
             match expr {
                 MirScalarExpr::Column(col) => {
@@ -158,7 +295,7 @@ impl Factorizer for WorstCaseFactorizer {
             | MirScalarExpr::Literal(_, _)
             | MirScalarExpr::CallUnmaterializable(_) => SymExp::from(1.0),
             MirScalarExpr::CallUnary { func, expr } => match func {
-                UnaryFunc::Not(_) => 1.0 - self.predicate(expr, unique_columns),
+                UnaryFunc::Not(_) => 1.0 - self.predicate(expr, unique_columns, source),
                 UnaryFunc::IsTrue(_) | UnaryFunc::IsFalse(_) => SymExp::from(0.5),
                 UnaryFunc::IsNull(_) => {
                     if let Some(icard) = index_cardinality(expr) {
@@ -198,7 +335,7 @@ impl Factorizer for WorstCaseFactorizer {
                     let mut factor = SymExp::from(1.0);
 
                     for expr in exprs {
-                        factor = factor * self.predicate(expr, unique_columns);
+                        factor = factor * self.predicate(expr, unique_columns, source);
                     }
 
                     factor
@@ -212,13 +349,13 @@ impl Factorizer for WorstCaseFactorizer {
                     let mut expr1;
 
                     if let Some(first) = exprs.next() {
-                        expr1 = self.predicate(first, unique_columns);
+                        expr1 = self.predicate(first, unique_columns, source);
                     } else {
                         return SymExp::from(1.0);
                     }
 
                     for expr2 in exprs {
-                        let expr2 = self.predicate(expr2, unique_columns);
+                        let expr2 = self.predicate(expr2, unique_columns, source);
 
                         // TODO(mgree) a big expression! two things could help: hash-consing and simplification
                         expr1 = expr1.clone() + expr2.clone() - expr1.clone() * expr2;
@@ -228,8 +365,8 @@ impl Factorizer for WorstCaseFactorizer {
                 _ => SymExp::from(1.0),
             },
             MirScalarExpr::If { cond: _, then, els } => SymExp::max(
-                self.predicate(then, unique_columns),
-                self.predicate(els, unique_columns),
+                self.predicate(then, unique_columns, source),
+                self.predicate(els, unique_columns, source),
             ),
         }
     }
@@ -238,6 +375,7 @@ impl Factorizer for WorstCaseFactorizer {
         &self,
         predicates: &Vec<MirScalarExpr>,
         keys: &Vec<Vec<usize>>,
+        source: Option<GlobalId>,
         input: &SymExp,
     ) -> SymExp {
         // TODO(mgree): should we try to do something for indices built on multiple columns?
@@ -252,7 +390,7 @@ impl Factorizer for WorstCaseFactorizer {
         let mut factor = SymExp::from(1.0);
 
         for expr in predicates {
-            let predicate_scaling_factor = self.predicate(expr, &unique_columns);
+            let predicate_scaling_factor = self.predicate(expr, &unique_columns, source);
 
             // constant scaling factors should be in [0,1]
             debug_assert!(
@@ -274,9 +412,12 @@ impl Factorizer for WorstCaseFactorizer {
         equivalences: &Vec<Vec<MirScalarExpr>>,
         _implementation: &JoinImplementation,
         unique_columns: BTreeMap<usize, usize>,
+        sources: BTreeMap<usize, (GlobalId, usize)>,
         inputs: Vec<&SymExp>,
     ) -> SymExp {
         let mut inputs = inputs.into_iter().cloned().collect::<Vec<_>>();
+        // selectivity of the equivalences that don't involve unique columns
+        let mut factor = 1.0;
 
         for equiv in equivalences {
             // those sources which have a unique key
@@ -297,6 +438,9 @@ impl Factorizer for WorstCaseFactorizer {
 
             // no unique columns in this equivalence
             if unique_sources.is_empty() {
+                if let Some(selectivity) = self.equivalence_selectivity(equiv, &sources) {
+                    factor *= selectivity;
+                }
                 continue;
             }
 
@@ -326,6 +470,10 @@ impl Factorizer for WorstCaseFactorizer {
             }
         }
 
+        if factor != 1.0 {
+            inputs.push(SymExp::f64(factor));
+        }
+
         SymbolicExpression::product(inputs)
     }
 
@@ -396,9 +544,7 @@ impl Attribute for Cardinality {
                             .push(SymExp::symbolic(FactorizerVariable::Unknown));
                     }
                 },
-                Id::Global(id) => self
-                    .results
-                    .push(SymbolicExpression::symbolic(FactorizerVariable::Id(*id))),
+                Id::Global(id) => self.results.push(self.factorize.global(*id)),
             },
             Let { .. } | Project { .. } | Map { .. } | ArrangeBy { .. } | Negate { .. } => {
                 let input = self.results[n - 1].clone();
@@ -425,11 +571,12 @@ impl Attribute for Cardinality {
                 let input = &self.results[n - 1];
                 self.results.push(self.factorize.flat_map(func, input));
             }
-            Filter { predicates, .. } => {
+            Filter { input, predicates } => {
+                let source = source_collection(input);
                 let input = &self.results[n - 1];
                 let keys = &deps.get_results::<UniqueKeys>()[n - 1];
                 self.results
-                    .push(self.factorize.filter(predicates, keys, input));
+                    .push(self.factorize.filter(predicates, keys, source, input));
             }
             Join {
                 equivalences,
@@ -437,33 +584,48 @@ impl Attribute for Cardinality {
                 inputs,
                 ..
             } => {
+                // the results, arities, and keys of the inputs, visiting the last input first
+                let mut input_attributes = Vec::with_capacity(inputs.len());
+                let mut offset = 1;
+                for _ in 0..inputs.len() {
+                    input_attributes.push((
+                        &self.results[n - offset],
+                        deps.get_results::<Arity>()[n - offset],
+                        &deps.get_results::<UniqueKeys>()[n - offset],
+                    ));
+                    offset += &deps.get_results::<SubtreeSize>()[n - offset];
+                }
+                input_attributes.reverse();
+
                 let mut input_results = Vec::with_capacity(inputs.len());
 
                 // maps a column to the index in `inputs` that it belongs to
                 let mut unique_columns = BTreeMap::new();
+                // maps a column to the global collection and column that it reads
+                let mut sources = BTreeMap::new();
                 let mut key_offset = 0;
 
-                let mut offset = 1;
-                for idx in 0..inputs.len() {
-                    let input = &self.results[n - offset];
+                for (idx, (input, arity, keys)) in input_attributes.into_iter().enumerate() {
                     input_results.push(input);
 
-                    let arity = deps.get_results::<Arity>()[n - offset];
-                    let keys = &deps.get_results::<UniqueKeys>()[n - offset];
                     for key in keys {
                         if key.len() == 1 {
                             unique_columns.insert(key_offset + key[0], idx);
                         }
                     }
+                    if let Some(id) = source_collection(&inputs[idx]) {
+                        for col in 0..arity {
+                            sources.insert(key_offset + col, (id, col));
+                        }
+                    }
                     key_offset += arity;
-
-                    offset += &deps.get_results::<SubtreeSize>()[n - offset];
                 }
 
                 self.results.push(self.factorize.join(
                     equivalences,
                     implementation,
                     unique_columns,
+                    sources,
                     input_results,
                 ));
             }
@@ -527,6 +689,19 @@ impl Attribute for Cardinality {
     }
 }
 
+/// Returns the global collection that `expr` reads without changing its columns, if any
+fn source_collection(expr: &MirRelationExpr) -> Option<GlobalId> {
+    match expr {
+        MirRelationExpr::Get {
+            id: Id::Global(id), ..
+        } => Some(*id),
+        MirRelationExpr::ArrangeBy { input, .. } | MirRelationExpr::Filter { input, .. } => {
+            source_collection(input)
+        }
+        _ => None,
+    }
+}
+
 impl SymExp {
     /// Render a symbolic expression nicely
     pub fn humanize(
@@ -653,3 +828,71 @@ impl<'a, 'b> std::fmt::Display for HumanizedSymbolicExpression<'a, 'b> {
         self.expr.normalize().humanize(self.humanizer, f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::ScalarType;
+
+    use super::*;
+
+    /// Statistics about a collection of `cardinality` rows whose column 1 holds the integers
+    /// from 1 to 13
+    fn statistics(cardinality: usize) -> CollectionStatistics {
+        let column = ColumnStatistics::new(
+            0,
+            cardinality,
+            Some(Datum::Int32(1)),
+            Some(Datum::Int32(13)),
+        );
+        CollectionStatistics {
+            cardinality,
+            columns: BTreeMap::from([(1, column)]),
+        }
+    }
+
+    #[mz_ore::test]
+    fn filter_with_statistics() {
+        let id = GlobalId::User(1);
+        let factorizer = WorstCaseFactorizer {
+            statistics: BTreeMap::from([(id, statistics(611))]),
+        };
+
+        let predicate = MirScalarExpr::column(1).call_binary(
+            MirScalarExpr::literal_ok(Datum::Int32(3), ScalarType::Int32),
+            BinaryFunc::Eq,
+        );
+        let estimate =
+            factorizer.filter(&vec![predicate], &vec![], Some(id), &factorizer.global(id));
+        assert_eq!(estimate.evaluate(&|_| 0.0).round(), 47.0);
+
+        // without statistics about the columns, the worst case selectivity applies
+        let estimate = factorizer.filter(
+            &vec![MirScalarExpr::column(0).call_binary(
+                MirScalarExpr::literal_ok(Datum::Int32(3), ScalarType::Int32),
+                BinaryFunc::Eq,
+            )],
+            &vec![],
+            Some(id),
+            &factorizer.global(id),
+        );
+        assert_eq!(estimate.evaluate(&|_| 0.0).round(), 61.0);
+    }
+
+    #[mz_ore::test]
+    fn join_with_statistics() {
+        let (t3, t4) = (GlobalId::User(1), GlobalId::User(2));
+        let factorizer = WorstCaseFactorizer {
+            statistics: BTreeMap::from([(t3, statistics(169)), (t4, statistics(611))]),
+        };
+
+        let inputs = [factorizer.global(t3), factorizer.global(t4)];
+        let estimate = factorizer.join(
+            &vec![vec![MirScalarExpr::column(1), MirScalarExpr::column(3)]],
+            &JoinImplementation::Unimplemented,
+            BTreeMap::new(),
+            BTreeMap::from([(1, (t3, 1)), (3, (t4, 1))]),
+            inputs.iter().collect(),
+        );
+        assert_eq!(estimate.evaluate(&|_| 0.0).round(), 7943.0);
+    }
+}
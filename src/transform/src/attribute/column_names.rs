@@ -131,6 +131,7 @@ impl<'c> ColumnNames<'c> {
                 inputs: _,
                 equivalences: _,
                 implementation: _,
+                hints: _,
             } => {
                 let mut column_names = vec![];
                 for input_column_names in input_results {
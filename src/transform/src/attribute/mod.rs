@@ -284,7 +284,7 @@ impl<'c> From<&ExplainContext<'c>> for DerivedAttributes<'c> {
             builder.require(UniqueKeys::default());
         }
         if context.config.cardinality {
-            builder.require(Cardinality::default());
+            builder.require(Cardinality::with_statistics(context.statistics.clone()));
         }
         if context.config.column_names || context.config.humanized_exprs {
             builder.require(ColumnNames::new(context.humanizer));
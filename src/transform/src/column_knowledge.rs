@@ -429,7 +429,14 @@ impl ColumnKnowledge {
                                 // These methods propagate constant values exactly.
                                 knowledge
                             }
-                            AggregateFunc::Count => DatumKnowledge::any(false),
+                            AggregateFunc::Count | AggregateFunc::ApproxCountDistinct { .. } => {
+                                DatumKnowledge::any(false)
+                            }
+                            // Percentiles take a (value, fraction) record, but
+                            // are null for out-of-range fractions or if all
+                            // values are null.
+                            AggregateFunc::PercentileCont { .. }
+                            | AggregateFunc::PercentileDisc { .. } => DatumKnowledge::any(true),
                             _ => {
                                 // The remaining aggregates are non-null if
                                 // their inputs are non-null. This is correct
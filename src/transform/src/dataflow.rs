@@ -25,11 +25,12 @@ use mz_expr::{
 use mz_ore::stack::{CheckedRecursion, RecursionGuard, RecursionLimitError};
 use mz_ore::{soft_assert_eq_or_log, soft_assert_or_log, soft_panic_or_log};
 use mz_repr::explain::{IndexUsageType, UsedIndexes};
+use mz_repr::stats::CollectionStatistics;
 use mz_repr::GlobalId;
 
 use crate::monotonic::MonotonicFlag;
 use crate::notice::RawOptimizerNotice;
-use crate::{IndexOracle, Optimizer, TransformCtx, TransformError};
+use crate::{IndexOracle, Optimizer, StatisticsOracle, TransformCtx, TransformError};
 
 /// Optimizes the implementation of each dataflow.
 ///
@@ -88,6 +89,8 @@ pub fn optimize_dataflow(
         transform_ctx.df_meta,
     )?;
 
+    record_dataflow_statistics(dataflow, transform_ctx.stats, transform_ctx.df_meta);
+
     mz_repr::explain::trace_plan(dataflow);
 
     Ok(())
@@ -439,6 +442,25 @@ pub fn optimize_dataflow_monotonic(dataflow: &mut DataflowDesc) -> Result<(), Tr
     Ok(())
 }
 
+/// Records in the `DataflowMetainfo` the statistics about the collections read by `dataflow`
+/// that were available to the optimizer, so that `EXPLAIN` can show the estimates derived from
+/// them.
+fn record_dataflow_statistics(
+    dataflow: &DataflowDesc,
+    stats: &dyn StatisticsOracle,
+    dataflow_metainfo: &mut DataflowMetainfo,
+) {
+    let mut ids = BTreeSet::new();
+    for build_desc in dataflow.objects_to_build.iter() {
+        build_desc.plan.depends_on_into(&mut ids);
+    }
+    for id in ids {
+        if let Some(statistics) = stats.collection_statistics(id) {
+            dataflow_metainfo.statistics.insert(id, statistics.clone());
+        }
+    }
+}
+
 /// Restricts the indexes imported by `dataflow` to only the ones it needs.
 /// It also adds to the `DataflowMetainfo` how each index will be used.
 /// It also annotates global `Get`s with whether they will be reads from Persist or an index, plus
@@ -1164,6 +1186,9 @@ pub struct DataflowMetainfo<Notice = RawOptimizerNotice> {
     /// What kind of operation (full scan, lookup, ...) will access each index. Computed by
     /// `prune_and_annotate_dataflow_index_imports`.
     pub index_usage_types: BTreeMap<GlobalId, Vec<IndexUsageType>>,
+    /// Statistics about the collections read by the dataflow that were available to the
+    /// optimizer. Computed by `record_dataflow_statistics`.
+    pub statistics: BTreeMap<GlobalId, CollectionStatistics>,
 }
 
 impl Default for DataflowMetainfo {
@@ -1171,6 +1196,7 @@ impl Default for DataflowMetainfo {
         DataflowMetainfo {
             optimizer_notices: Vec::new(),
             index_usage_types: BTreeMap::new(),
+            statistics: BTreeMap::new(),
         }
     }
 }
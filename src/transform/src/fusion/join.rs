@@ -84,6 +84,7 @@ impl Join {
         if let MirRelationExpr::Join {
             inputs,
             equivalences,
+            hints,
             ..
         } = relation
         {
@@ -126,10 +127,11 @@ impl Join {
                         if let MirRelationExpr::Join {
                             inputs,
                             equivalences,
+                            hints,
                             ..
                         } = inner
                         {
-                            Ok((mfp, (inputs, equivalences)))
+                            Ok((mfp, (inputs, equivalences, hints)))
                         } else {
                             Err((mfp.projection.len(), expr))
                         }
@@ -154,11 +156,15 @@ impl Join {
                 let mut arity_so_far = 0;
 
                 let mut new_inputs = Vec::new();
+                // The hints of the fused joins are retained, with those of the outer join taking
+                // precedence.
+                let mut new_hints = hints.clone();
                 for child in children.into_iter() {
                     match child {
-                        Ok((mut mfp, (inputs, equivalences))) => {
+                        Ok((mut mfp, (inputs, equivalences, hints))) => {
                             // Add the join inputs to the new join inputs.
                             new_inputs.extend(inputs.iter().cloned());
+                            new_hints.merge(hints);
 
                             mfp.optimize();
                             let (mut map, mut filter, mut project) = mfp.as_map_filter_project();
@@ -226,7 +232,7 @@ impl Join {
                 *relation = match new_inputs.len() {
                     0 => MirRelationExpr::constant(vec![vec![]], mz_repr::RelationType::empty()),
                     1 => new_inputs.pop().unwrap(),
-                    _ => MirRelationExpr::join(new_inputs, Vec::new()),
+                    _ => MirRelationExpr::join(new_inputs, Vec::new()).with_join_hints(new_hints),
                 }
                 .map(map)
                 .filter(filter)
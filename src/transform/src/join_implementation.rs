@@ -21,13 +21,20 @@ use std::collections::BTreeMap;
 use mz_expr::visit::{Visit, VisitChildren};
 use mz_expr::JoinImplementation::{Differential, IndexedFilter, Unimplemented};
 use mz_expr::{
-    FilterCharacteristics, Id, JoinInputCharacteristics, JoinInputMapper, MapFilterProject,
-    MirRelationExpr, MirScalarExpr, RECURSION_LIMIT,
+    FilterCharacteristics, Id, JoinHints, JoinImplementationHint, JoinInputCharacteristics,
+    JoinInputMapper, MapFilterProject, MirRelationExpr, MirScalarExpr, RECURSION_LIMIT,
 };
+use mz_ore::cast::CastLossy;
 use mz_ore::stack::{CheckedRecursion, RecursionGuard};
 use mz_ore::{soft_assert_or_log, soft_panic_or_log};
+use mz_repr::GlobalId;
 
+use crate::attribute::cardinality::{
+    Factorizer, SymExp, WorstCaseFactorizer, WORST_CASE_SELECTIVITY,
+};
+use crate::dataflow::DataflowMetainfo;
 use crate::join_implementation::index_map::IndexMap;
+use crate::notice::JoinHintNotApplied;
 use crate::predicate_pushdown::PredicatePushdown;
 use crate::{StatisticsOracle, TransformCtx, TransformError};
 
@@ -69,6 +76,7 @@ impl crate::Transform for JoinImplementation {
             &mut IndexMap::new(ctx.indexes),
             ctx.stats,
             ctx.features.enable_eager_delta_joins,
+            ctx.df_meta,
         );
         mz_repr::explain::trace_plan(&*relation);
         result
@@ -86,10 +94,11 @@ impl JoinImplementation {
         indexes: &mut IndexMap,
         stats: &dyn StatisticsOracle,
         eager_delta_joins: bool,
+        df_meta: &mut DataflowMetainfo,
     ) -> Result<(), TransformError> {
         self.checked_recur(|_| {
             if let MirRelationExpr::Let { id, value, body } = relation {
-                self.action_recursive(value, indexes, stats, eager_delta_joins)?;
+                self.action_recursive(value, indexes, stats, eager_delta_joins, df_meta)?;
                 match &**value {
                     MirRelationExpr::ArrangeBy { keys, .. } => {
                         for key in keys {
@@ -104,16 +113,16 @@ impl JoinImplementation {
                     }
                     _ => {}
                 }
-                self.action_recursive(body, indexes, stats, eager_delta_joins)?;
+                self.action_recursive(body, indexes, stats, eager_delta_joins, df_meta)?;
                 indexes.remove_local(*id);
                 Ok(())
             } else {
                 let (mfp, mfp_input) =
                     MapFilterProject::extract_non_errors_from_expr_ref_mut(relation);
                 mfp_input.try_visit_mut_children(|e| {
-                    self.action_recursive(e, indexes, stats, eager_delta_joins)
+                    self.action_recursive(e, indexes, stats, eager_delta_joins, df_meta)
                 })?;
-                self.action(mfp_input, mfp, indexes, stats, eager_delta_joins)?;
+                self.action(mfp_input, mfp, indexes, stats, eager_delta_joins, df_meta)?;
                 Ok(())
             }
         })
@@ -125,8 +134,9 @@ impl JoinImplementation {
         relation: &mut MirRelationExpr,
         mfp_above: MapFilterProject,
         indexes: &IndexMap,
-        _stats: &dyn StatisticsOracle,
+        stats: &dyn StatisticsOracle,
         eager_delta_joins: bool,
+        df_meta: &mut DataflowMetainfo,
     ) -> Result<(), TransformError> {
         if let MirRelationExpr::Join {
             inputs,
@@ -147,8 +157,11 @@ impl JoinImplementation {
             // refactor this to make ArrangeBy unambiguous somehow. Maybe move JoinImplementation
             // to the lowering.)
             implementation: implementation @ (Unimplemented | Differential(..)),
+            hints,
         } = relation
         {
+            let hints = hints.clone();
+
             // If we eagerly plan delta joins, we don't need the second run to "pick up" delta joins
             // that could be planned with the arrangements from a differential. If such a delta
             // join were viable, we'd have already planned it the first time.
@@ -268,10 +281,14 @@ impl JoinImplementation {
                 }
                 let push_down_characteristics =
                     FilterCharacteristics::filter_characteristics(&push_downs[index])?;
-                // let push_down_factor = push_down_characteristics.worst_case_scaling_factor();
+                let push_down_factor = push_down_characteristics.worst_case_scaling_factor();
                 characteristics |= push_down_characteristics;
 
-                cardinalities.push(None);
+                cardinalities.push(estimate_cardinality(
+                    &inputs[index],
+                    push_down_factor,
+                    stats,
+                ));
                 filters.push(characteristics);
 
                 // Collect available arrangements on this input.
@@ -337,6 +354,9 @@ impl JoinImplementation {
                 });
             }
 
+            // Translate a `JOIN ORDER` hint into the input indexes that should be joined first.
+            let order_hint = hinted_order(inputs, &hints, df_meta);
+
             let old_implementation = implementation.clone();
             let num_inputs = inputs.len();
             // We've already planned a differential join... should we replace it with a delta join?
@@ -353,6 +373,11 @@ impl JoinImplementation {
                     return Ok(());
                 }
 
+                // A hinted implementation was already decided in the first run.
+                if hints.implementation.is_some() {
+                    return Ok(());
+                }
+
                 // Only plan a delta join if it's no new arrangements (beyond what differential planned).
                 if let Ok((delta_query_plan, 0)) = delta_queries::plan(
                     relation,
@@ -361,6 +386,7 @@ impl JoinImplementation {
                     &unique_keys,
                     &cardinalities,
                     &filters,
+                    &order_hint,
                 ) {
                     tracing::debug!(plan = ?delta_query_plan, "replacing differential join with delta join");
                     *relation = delta_query_plan;
@@ -379,6 +405,7 @@ impl JoinImplementation {
                 &unique_keys,
                 &cardinalities,
                 &filters,
+                &order_hint,
             )
             .expect("Failed to produce a differential join plan");
 
@@ -400,6 +427,23 @@ impl JoinImplementation {
                 // See more details here:
                 // https://github.com/MaterializeInc/materialize/pull/16099#issuecomment-1316857374
                 // https://github.com/MaterializeInc/materialize/pull/17708#discussion_r1112848747
+                if hints.implementation == Some(JoinImplementationHint::Delta) {
+                    df_meta.push_optimizer_notice_dedup(
+                        JoinHintNotApplied::ImplementationNotPossible {
+                            implementation: JoinImplementationHint::Delta,
+                            reason: "Delta joins are only possible for joins with at least \
+                                three inputs."
+                                .to_string(),
+                        },
+                    );
+                }
+                *relation = differential_query_plan;
+
+                return Ok(());
+            }
+
+            // The user asked for a differential join, so there is nothing to compare.
+            if hints.implementation == Some(JoinImplementationHint::Differential) {
                 *relation = differential_query_plan;
 
                 return Ok(());
@@ -443,7 +487,18 @@ impl JoinImplementation {
                 &unique_keys,
                 &cardinalities,
                 &filters,
+                &order_hint,
             ) {
+                // If the user asked for a delta join, pick it regardless of new arrangements.
+                Ok((delta_query_plan, delta_new_arrangements))
+                    if hints.implementation == Some(JoinImplementationHint::Delta) =>
+                {
+                    tracing::debug!(
+                        plan = ?delta_query_plan,
+                        delta_new_arrangements = delta_new_arrangements,
+                        "picking delta query plan (hinted)");
+                    *relation = delta_query_plan;
+                }
                 // If delta plan's inputs need no new arrangements, pick the delta plan.
                 Ok((delta_query_plan, 0)) => {
                     soft_assert_or_log!(
@@ -489,6 +544,14 @@ impl JoinImplementation {
                 // If we can't plan a delta join, plan a differential join.
                 Err(err) => {
                     soft_panic_or_log!("delta planning failed: {err}");
+                    if hints.implementation == Some(JoinImplementationHint::Delta) {
+                        df_meta.push_optimizer_notice_dedup(
+                            JoinHintNotApplied::ImplementationNotPossible {
+                                implementation: JoinImplementationHint::Delta,
+                                reason: format!("Planning the delta join failed: {err}."),
+                            },
+                        );
+                    }
                     tracing::debug!(
                         plan = ?differential_query_plan,
                         "picking differential query plan (delta planning failed)");
@@ -570,6 +633,7 @@ mod delta_queries {
         unique_keys: &[Vec<Vec<usize>>],
         cardinalities: &[Option<usize>],
         filters: &[FilterCharacteristics],
+        order_hint: &[usize],
     ) -> Result<(MirRelationExpr, usize), TransformError> {
         let mut new_join = join.clone();
 
@@ -577,6 +641,7 @@ mod delta_queries {
             inputs,
             equivalences,
             implementation,
+            hints: _,
         } = &mut new_join
         {
             // Determine a viable order for each relation, or return `Err` if none found.
@@ -587,6 +652,7 @@ mod delta_queries {
                 cardinalities,
                 filters,
                 input_mapper,
+                order_hint,
             )?;
 
             // Count new arrangements.
@@ -658,6 +724,7 @@ mod differential {
         unique_keys: &[Vec<Vec<usize>>],
         cardinalities: &[Option<usize>],
         filters: &[FilterCharacteristics],
+        order_hint: &[usize],
     ) -> Result<(MirRelationExpr, usize), TransformError> {
         let mut new_join = join.clone();
 
@@ -665,6 +732,7 @@ mod differential {
             inputs,
             equivalences,
             implementation,
+            hints: _,
         } = &mut new_join
         {
             // We compute one order for each possible starting point, and we will choose one from
@@ -683,6 +751,7 @@ mod differential {
                 cardinalities,
                 filters,
                 input_mapper,
+                order_hint,
             )?;
 
             // Count new arrangements.
@@ -717,6 +786,12 @@ mod differential {
                 }
             });
 
+            // If the user provided a join order, only the order starting with the first hinted
+            // input is a candidate.
+            if let Some(hinted_start) = order_hint.first() {
+                orders.retain(|o| o.first().map(|(_, _, input)| input) == Some(hinted_start));
+            }
+
             // `orders` has one order for each starting collection, and now we have to choose one
             // from these. First, we find the worst `Characteristics` inside each order, and then we
             // find the best one among these across all orders, which goes into
@@ -960,9 +1035,119 @@ fn permute_order(
     })
 }
 
+/// Maps the relations listed in the `JOIN ORDER` hint of a join to the indexes of the inputs
+/// that read them.
+///
+/// Relations that are not read by exactly one input are skipped, and a notice is emitted for them.
+fn hinted_order(
+    inputs: &[MirRelationExpr],
+    hints: &JoinHints,
+    df_meta: &mut DataflowMetainfo,
+) -> Vec<usize> {
+    if hints.order.is_empty() {
+        return Vec::new();
+    }
+    let input_ids = inputs.iter().map(input_global_id).collect::<Vec<_>>();
+    let mut order_hint = Vec::with_capacity(hints.order.len());
+    for id in hints.order.iter() {
+        let mut matches = input_ids
+            .iter()
+            .enumerate()
+            .filter(|(_, input_id)| input_id.as_ref() == Some(id))
+            .map(|(index, _)| index);
+        match (matches.next(), matches.next()) {
+            (Some(index), None) => order_hint.push(index),
+            (Some(_), Some(_)) => {
+                df_meta.push_optimizer_notice_dedup(JoinHintNotApplied::AmbiguousInput { id: *id })
+            }
+            (None, _) => {
+                df_meta.push_optimizer_notice_dedup(JoinHintNotApplied::UnknownInput { id: *id })
+            }
+        }
+    }
+    order_hint
+}
+
+/// Estimates the number of rows that a join input contributes to the join.
+///
+/// The estimate starts from the cardinality of the global collection that the input reads, scaled
+/// by the selectivity of the filters around the input (using column statistics where the filter
+/// refers to the columns of the collection) and by `push_down_factor` for the predicates that can
+/// be pushed down from above the join. Returns `None` if the input doesn't read a global
+/// collection or if there is no cardinality estimate for it.
+fn estimate_cardinality(
+    input: &MirRelationExpr,
+    push_down_factor: f64,
+    stats: &dyn StatisticsOracle,
+) -> Option<usize> {
+    let id = input_global_id(input)?;
+    let cardinality = stats.cardinality_estimate(id)?;
+
+    let factorizer = WorstCaseFactorizer {
+        statistics: stats
+            .collection_statistics(id)
+            .map(|statistics| BTreeMap::from([(id, statistics.clone())]))
+            .unwrap_or_default(),
+    };
+    // Without keys, the selectivity of a filter has no symbolic variables.
+    let selectivity = |filter: &Vec<MirScalarExpr>, source: Option<GlobalId>| {
+        factorizer
+            .filter(filter, &Vec::new(), source, &SymExp::f64(1.0))
+            .evaluate(&|_| 1.0)
+    };
+
+    let mut estimate = f64::cast_lossy(cardinality) * push_down_factor;
+    let (mfp, mut input) = MapFilterProject::extract_non_errors_from_expr(input);
+    let (_, filter, _) = mfp.as_map_filter_project();
+    // The columns of the outer filter are those of the collection, unless an mfp below an
+    // arrangement rearranged them.
+    let mut outer_source = Some(id);
+    if let MirRelationExpr::ArrangeBy { input: inner, .. } = input {
+        let (mfp, inner) = MapFilterProject::extract_non_errors_from_expr(inner);
+        let (_, inner_filter, _) = mfp.as_map_filter_project();
+        estimate *= selectivity(&inner_filter, Some(id));
+        if !mfp.is_identity() {
+            outer_source = None;
+        }
+        input = inner;
+    }
+    estimate *= selectivity(&filter, outer_source);
+    // IndexedFilter joins are constructed from literal equality filters.
+    if let MirRelationExpr::Join {
+        implementation: IndexedFilter(..),
+        ..
+    } = input
+    {
+        estimate *= WORST_CASE_SELECTIVITY;
+    }
+
+    Some(usize::cast_lossy(estimate.ceil()))
+}
+
+/// Returns the id of the global collection that a join input reads, looking through the mfps and
+/// arrangements that are placed around inputs during join planning.
+fn input_global_id(input: &MirRelationExpr) -> Option<GlobalId> {
+    let (_, mut input) = MapFilterProject::extract_non_errors_from_expr(input);
+    if let MirRelationExpr::ArrangeBy { input: inner, .. } = input {
+        (_, input) = MapFilterProject::extract_non_errors_from_expr(inner);
+    }
+    match input {
+        MirRelationExpr::Get {
+            id: Id::Global(id), ..
+        } => Some(*id),
+        MirRelationExpr::Join {
+            implementation: IndexedFilter(id, ..),
+            ..
+        } => Some(*id),
+        _ => None,
+    }
+}
+
 // Computes the best join orders for each input.
 //
-// If there are N inputs, returns N orders, with the ith input starting the ith order.
+// If there are N inputs, returns N orders, with the ith input starting the ith order. Each order
+// continues with the inputs of `order_hint` (except for its starting input) before placing the
+// remaining inputs.
 fn optimize_orders(
     equivalences: &[Vec<MirScalarExpr>], // join equivalences: inside a Vec, the exprs are equivalent
     available: &[Vec<Vec<MirScalarExpr>>], // available arrangements per input
@@ -970,6 +1155,7 @@ fn optimize_orders(
     cardinalities: &[Option<usize>],     // cardinalities of input relations
     filters: &[FilterCharacteristics],   // filter characteristics per input
     input_mapper: &JoinInputMapper,      // join helper
+    order_hint: &[usize],                // inputs that the user asked to be joined first
 ) -> Result<Vec<Vec<(JoinInputCharacteristics, Vec<MirScalarExpr>, usize)>>, TransformError> {
     let mut orderer = Orderer::new(
         equivalences,
@@ -980,7 +1166,7 @@ fn optimize_orders(
         input_mapper,
     );
     (0..available.len())
-        .map(move |i| orderer.optimize_order_for(i))
+        .map(move |i| orderer.optimize_order_for(i, order_hint))
         .collect::<Result<Vec<_>, _>>()
 }
 
@@ -1062,6 +1248,7 @@ impl<'a> Orderer<'a> {
     fn optimize_order_for(
         &mut self,
         start: usize,
+        order_hint: &[usize],
     ) -> Result<Vec<(JoinInputCharacteristics, Vec<MirScalarExpr>, usize)>, TransformError> {
         self.order.clear();
         self.priority_queue.clear();
@@ -1115,6 +1302,14 @@ impl<'a> Orderer<'a> {
         // Main loop, ordering all the inputs.
         if self.inputs > 1 {
             self.order_input(start);
+            // Inputs listed in the order hint are placed first, each with its best available key.
+            for input in order_hint.iter().filter(|input| **input != start) {
+                if !self.placed[*input] {
+                    let next = self.pop_best_for(*input);
+                    self.order.push(next);
+                    self.order_input(*input);
+                }
+            }
             while self.order.len() < self.inputs - 1 {
                 let (characteristics, key, input) = self.priority_queue.pop().unwrap();
                 // put the tuple into `self.order` unless the tuple with the same
@@ -1203,6 +1398,23 @@ impl<'a> Orderer<'a> {
         Ok(std::mem::replace(&mut self.order, Vec::new()))
     }
 
+    /// Removes and returns the best candidate for placing `input` next from the priority queue.
+    ///
+    /// Every input has at least one candidate, as all of them are introduced as cross joins.
+    fn pop_best_for(
+        &mut self,
+        input: usize,
+    ) -> (JoinInputCharacteristics, Vec<MirScalarExpr>, usize) {
+        let (candidates, others): (Vec<_>, Vec<_>) = std::mem::take(&mut self.priority_queue)
+            .into_iter()
+            .partition(|(_, _, i)| *i == input);
+        self.priority_queue = others.into();
+        candidates
+            .into_iter()
+            .max()
+            .expect("every input is introduced as a cross join")
+    }
+
     /// Introduces a specific input and keys to the order, along with its characteristics.
     ///
     /// This method places a next element in the order, and updates the associated state
@@ -1300,3 +1512,58 @@ impl<'a> Orderer<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use mz_expr::BinaryFunc;
+    use mz_repr::stats::{CollectionStatistics, ColumnStatistics};
+    use mz_repr::{Datum, RelationType, ScalarType};
+
+    use crate::EmptyStatisticsOracle;
+
+    use super::*;
+
+    /// A [`StatisticsOracle`] with fixed statistics.
+    #[derive(Debug)]
+    struct FixedStatisticsOracle(BTreeMap<GlobalId, CollectionStatistics>);
+
+    impl StatisticsOracle for FixedStatisticsOracle {
+        fn cardinality_estimate(&self, id: GlobalId) -> Option<usize> {
+            self.0.get(&id).map(|statistics| statistics.cardinality)
+        }
+
+        fn collection_statistics(&self, id: GlobalId) -> Option<&CollectionStatistics> {
+            self.0.get(&id)
+        }
+    }
+
+    #[mz_ore::test]
+    fn estimate_cardinality_with_statistics() {
+        let id = GlobalId::User(1);
+        let column = ColumnStatistics::new(0, 611, Some(Datum::Int32(1)), Some(Datum::Int32(13)));
+        let stats = FixedStatisticsOracle(BTreeMap::from([(
+            id,
+            CollectionStatistics {
+                cardinality: 611,
+                columns: BTreeMap::from([(1, column)]),
+            },
+        )]));
+
+        let input = MirRelationExpr::global_get(
+            id,
+            RelationType::new(vec![ScalarType::Int32.nullable(false); 2]),
+        );
+        assert_eq!(estimate_cardinality(&input, 1.0, &stats), Some(611));
+        assert_eq!(estimate_cardinality(&input, 0.5, &stats), Some(306));
+        assert_eq!(
+            estimate_cardinality(&input, 1.0, &EmptyStatisticsOracle),
+            None
+        );
+
+        let filtered = input.filter(vec![MirScalarExpr::column(1).call_binary(
+            MirScalarExpr::literal_ok(Datum::Int32(3), ScalarType::Int32),
+            BinaryFunc::Eq,
+        )]);
+        assert_eq!(estimate_cardinality(&filtered, 1.0, &stats), Some(47));
+    }
+}
@@ -29,6 +29,7 @@ use mz_expr::{MirRelationExpr, MirScalarExpr};
 use mz_ore::id_gen::IdGen;
 use mz_ore::stack::RecursionLimitError;
 use mz_repr::optimize::OptimizerFeatures;
+use mz_repr::stats::CollectionStatistics;
 use mz_repr::GlobalId;
 use tracing::error;
 
@@ -251,6 +252,14 @@ pub trait StatisticsOracle: fmt::Debug + Send {
     ///
     /// Returning `None` means "no estimate"; returning `Some(0)` means estimating that the shard backing `id` is empty
     fn cardinality_estimate(&self, id: GlobalId) -> Option<usize>;
+
+    /// Returns statistics about the contents of the given identifier, including statistics about
+    /// the values of its columns
+    ///
+    /// Returning `None` means "no statistics"
+    fn collection_statistics(&self, _id: GlobalId) -> Option<&CollectionStatistics> {
+        None
+    }
 }
 
 /// A [`StatisticsOracle`] that knows nothing and can give no estimates.
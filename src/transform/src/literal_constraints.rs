@@ -24,7 +24,9 @@ use itertools::Itertools;
 use mz_expr::canonicalize::canonicalize_predicates;
 use mz_expr::visit::{Visit, VisitChildren};
 use mz_expr::JoinImplementation::IndexedFilter;
-use mz_expr::{BinaryFunc, Id, MapFilterProject, MirRelationExpr, MirScalarExpr, VariadicFunc};
+use mz_expr::{
+    BinaryFunc, Id, JoinHints, MapFilterProject, MirRelationExpr, MirScalarExpr, VariadicFunc,
+};
 use mz_ore::collections::CollectionExt;
 use mz_ore::iter::IteratorExt;
 use mz_ore::stack::RecursionLimitError;
@@ -169,6 +171,7 @@ impl LiteralConstraints {
                             })
                             .collect(),
                         implementation: IndexedFilter(inp_id, idx_id, key.clone(), possible_vals),
+                        hints: JoinHints::default(),
                     };
 
                     // Rebuild the MFP to add the projection that removes the columns coming from
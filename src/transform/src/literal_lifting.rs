@@ -458,6 +458,7 @@ impl LiteralLifting {
                     inputs,
                     equivalences,
                     implementation,
+                    hints: _,
                 } => {
                     if !matches!(implementation, IndexedFilter(..)) {
                         // before lifting, save the original shape of the inputs
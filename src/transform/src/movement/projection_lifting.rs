@@ -210,6 +210,7 @@ impl ProjectionLifting {
                     inputs,
                     equivalences,
                     implementation,
+                    hints: _,
                 } => {
                     for input in inputs.iter_mut() {
                         self.action(input, gets)?;
@@ -0,0 +1,115 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Hosts [`JoinHintNotApplied`].
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use mz_expr::JoinImplementationHint;
+use mz_repr::explain::ExprHumanizer;
+use mz_repr::GlobalId;
+
+use crate::notice::{ActionKind, OptimizerNoticeApi};
+
+/// A `JOIN ORDER` or `JOIN IMPLEMENTATION` hint provided in the `OPTIONS` of
+/// a `SELECT` could not be honored when planning the implementation of a join.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum JoinHintNotApplied {
+    /// A relation listed in `JOIN ORDER` is not read by any input of the join
+    /// at the time its implementation is planned, for example because it has
+    /// been inlined or optimized away.
+    UnknownInput {
+        /// The id of the relation listed in the hint.
+        id: GlobalId,
+    },
+    /// A relation listed in `JOIN ORDER` is read by more than one input of the
+    /// join, so the hint does not identify a single input.
+    AmbiguousInput {
+        /// The id of the relation listed in the hint.
+        id: GlobalId,
+    },
+    /// The requested join implementation is not possible for the join.
+    ImplementationNotPossible {
+        /// The requested implementation.
+        implementation: JoinImplementationHint,
+        /// Why the requested implementation is not possible, as a sentence.
+        reason: String,
+    },
+}
+
+impl OptimizerNoticeApi for JoinHintNotApplied {
+    fn dependencies(&self) -> BTreeSet<GlobalId> {
+        match self {
+            Self::UnknownInput { id } | Self::AmbiguousInput { id } => BTreeSet::from([*id]),
+            Self::ImplementationNotPossible { .. } => BTreeSet::new(),
+        }
+    }
+
+    fn fmt_message(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        humanizer: &dyn ExprHumanizer,
+        _redacted: bool,
+    ) -> fmt::Result {
+        match self {
+            Self::UnknownInput { id } => {
+                let name = humanizer.humanize_id(*id).unwrap_or_else(|| id.to_string());
+                write!(
+                    f,
+                    "JOIN ORDER hint for {name} was not applied, \
+                    because {name} is not an input of the join."
+                )
+            }
+            Self::AmbiguousInput { id } => {
+                let name = humanizer.humanize_id(*id).unwrap_or_else(|| id.to_string());
+                write!(
+                    f,
+                    "JOIN ORDER hint for {name} was not applied, \
+                    because {name} is read by more than one input of the join."
+                )
+            }
+            Self::ImplementationNotPossible { implementation, .. } => write!(
+                f,
+                "JOIN IMPLEMENTATION {} hint was not applied, \
+                because the join cannot be implemented as a {implementation} join.",
+                implementation.to_string().to_uppercase()
+            ),
+        }
+    }
+
+    fn fmt_hint(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        _humanizer: &dyn ExprHumanizer,
+        _redacted: bool,
+    ) -> fmt::Result {
+        match self {
+            Self::UnknownInput { .. } | Self::AmbiguousInput { .. } => write!(
+                f,
+                "Only list relations in JOIN ORDER that are read exactly once by the join. \
+                Views that are inlined into the query cannot be referenced."
+            ),
+            Self::ImplementationNotPossible { reason, .. } => write!(f, "{reason}"),
+        }
+    }
+
+    fn fmt_action(
+        &self,
+        _f: &mut fmt::Formatter<'_>,
+        _humanizer: &dyn ExprHumanizer,
+        _redacted: bool,
+    ) -> fmt::Result {
+        Ok(())
+    }
+
+    fn action_kind(&self, _humanizer: &dyn ExprHumanizer) -> ActionKind {
+        ActionKind::None
+    }
+}
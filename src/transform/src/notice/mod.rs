@@ -31,10 +31,12 @@
 mod index_already_exists;
 mod index_key_empty;
 mod index_too_wide_for_literal_constraints;
+mod join_hint_not_applied;
 
 pub use index_already_exists::IndexAlreadyExists;
 pub use index_key_empty::IndexKeyEmpty;
 pub use index_too_wide_for_literal_constraints::IndexTooWideForLiteralConstraints;
+pub use join_hint_not_applied::JoinHintNotApplied;
 
 use std::collections::BTreeSet;
 use std::fmt::{self, Error, Formatter, Write};
@@ -355,6 +357,7 @@ raw_optimizer_notices![
     IndexAlreadyExists => "An identical index already exists",
     IndexTooWideForLiteralConstraints => "Index too wide for literal constraints",
     IndexKeyEmpty => "Empty index key",
+    JoinHintNotApplied => "Join hint not applied",
 ];
 
 impl RawOptimizerNotice {
@@ -74,10 +74,12 @@ impl ReduceElision {
                     .value::<UniqueKeys>()
                     .expect("UniqueKeys required");
 
-                if input_keys.iter().any(|keys| {
-                    keys.iter()
-                        .all(|k| group_key.contains(&mz_expr::MirScalarExpr::Column(*k)))
-                }) {
+                if aggregates.iter().all(|a| a.has_unique_form())
+                    && input_keys.iter().any(|keys| {
+                        keys.iter()
+                            .all(|k| group_key.contains(&mz_expr::MirScalarExpr::Column(*k)))
+                    })
+                {
                     let map_scalars = aggregates
                         .iter()
                         .map(|a| a.on_unique(input_type))
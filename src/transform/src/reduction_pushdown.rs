@@ -139,6 +139,7 @@ impl ReductionPushdown {
                 inputs,
                 equivalences,
                 implementation: _,
+                hints: _,
             } = &mut **input
             {
                 if let Some(new_relation_expr) = try_push_reduce_through_join(
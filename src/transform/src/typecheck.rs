@@ -620,6 +620,7 @@ impl Typecheck {
                 inputs,
                 equivalences,
                 implementation,
+                hints: _,
             } => {
                 let mut t_in_global = Vec::new();
                 let mut t_in_local = vec![Vec::new(); inputs.len()];
@@ -153,6 +153,7 @@ mod tests {
                     finishing: Default::default(),
                     duration: Default::default(),
                     optimizer_notices: Vec::new(),
+                    statistics: Default::default(),
                 };
 
                 Explainable(&mut rel.clone())
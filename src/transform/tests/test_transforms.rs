@@ -60,6 +60,7 @@ fn handle_explain(
         finishing: Default::default(),
         duration: Default::default(),
         optimizer_notices: Vec::default(),
+        statistics: Default::default(),
     };
 
     // Parse the relation, returning early on parse error.
@@ -66,7 +66,7 @@ SERVICES = [
 
 
 def workflow_default(c: Composition) -> None:
-    for name in ["secrets-manager", "aws-connection", "copy-to-s3"]:
+    for name in ["secrets-manager", "aws-connection", "copy-to-s3", "s3-source"]:
         with c.test_case(name):
             c.workflow(name)
 
@@ -267,3 +267,56 @@ def workflow_copy_to_s3(c: Composition) -> None:
             ][0]["Key"]
             == f"{path_prefix}/3/part-0001.csv"
         )
+
+        assert (
+            s3_client.list_objects_v2(Bucket=bucket_name, Prefix=f"{path_prefix}/4/")[
+                "Contents"
+            ][0]["Key"]
+            == f"{path_prefix}/4/part-0001.parquet"
+        )
+
+
+def workflow_s3_source(c: Composition) -> None:
+    with c.override(
+        Materialized(
+            depends_on=["localstack"],
+            environment_extra=[
+                f"AWS_ENDPOINT_URL={AWS_ENDPOINT_URL_MZ}",
+                f"AWS_ACCESS_KEY_ID={AWS_ACCESS_KEY_ID}",
+                f"AWS_SECRET_ACCESS_KEY={AWS_SECRET_ACCESS_KEY}",
+            ],
+        )
+    ):
+        c.up("localstack", "materialized")
+        localhost_aws_endpoint_url = f"http://localhost:{c.port('localstack', 4566)}"
+        s3_client = boto3.client(
+            "s3",
+            endpoint_url=localhost_aws_endpoint_url,
+            region_name=DEFAULT_CLOUD_REGION,
+            aws_access_key_id=AWS_ACCESS_KEY_ID,
+            aws_secret_access_key=AWS_SECRET_ACCESS_KEY,
+        )
+        bucket_name = "s3-source"
+        s3_client.create_bucket(Bucket=bucket_name)
+        path_prefix = str(uuid.uuid4())
+        objects = {
+            "csv/1.csv": "id,name\n1,alice\n2,bob\n",
+            "csv/2.csv": "id,name\r\n3,carol\r\n",
+            # Directory placeholders are not ingested.
+            "csv/dir/": "",
+            "json/1.json": '{"a": 1}\n{"a": 2}',
+            "text/1.txt": "hello\n",
+        }
+        for key, body in objects.items():
+            s3_client.put_object(
+                Bucket=bucket_name, Key=f"{path_prefix}/{key}", Body=body.encode()
+            )
+        c.run_testdrive_files(
+            f"--var=endpoint={AWS_ENDPOINT_URL_MZ}",
+            f"--var=access-key={AWS_ACCESS_KEY_ID}",
+            f"--var=secret-key={AWS_SECRET_ACCESS_KEY}",
+            f"--var=s3-prefix={bucket_name}/{path_prefix}",
+            f"--var=s3-path={path_prefix}",
+            f"--var=region={DEFAULT_CLOUD_REGION}",
+            "s3-source/s3-source.td",
+        )
@@ -0,0 +1,112 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+"""An HTTP sink endpoint that implements the exactly once protocol of HTTP sinks.
+
+Requests to `/` are handled like a well-behaved endpoint would: signatures are
+verified, requests are deduplicated by their idempotency key, and the records
+and upper of each request are applied atomically, keyed by the progress key of
+the sink.
+
+Requests to `/control/...` let the test inspect the state of the endpoint and
+inject transient failures.
+"""
+
+import hashlib
+import hmac
+import json
+import os
+import threading
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+
+HMAC_SECRET = os.environ["HMAC_SECRET"].encode()
+
+lock = threading.Lock()
+state = {
+    # Progress key -> records received, in order.
+    "records": {},
+    # Progress key -> last recorded upper.
+    "uppers": {},
+    # Idempotency keys of the applied requests.
+    "applied": [],
+    # Requests that repeated an already applied idempotency key.
+    "duplicates": 0,
+    # Requests rejected because of a missing or invalid signature.
+    "bad_signatures": 0,
+    # Requests that failed because of an injected failure.
+    "injected_failures": 0,
+    # Number of requests that still have to fail.
+    "fail_next": 0,
+}
+
+
+class Handler(BaseHTTPRequestHandler):
+    def do_HEAD(self) -> None:
+        self.reply(200)
+
+    def do_GET(self) -> None:
+        with lock:
+            if self.path == "/control/state":
+                self.reply(200, state)
+                return
+            upper = state["uppers"].get(self.headers["Materialize-Progress-Key"], ())
+            if upper == ():
+                self.reply(404)
+            else:
+                self.reply(200, {"upper": upper})
+
+    def do_POST(self) -> None:
+        body = self.rfile.read(int(self.headers["Content-Length"]))
+        with lock:
+            if self.path == "/control/fail":
+                state["fail_next"] = json.loads(body)["count"]
+                self.reply(200)
+                return
+
+            expected = "sha256=" + hmac.new(HMAC_SECRET, body, hashlib.sha256).hexdigest()
+            signature = self.headers.get("Materialize-Signature", "")
+            if not hmac.compare_digest(signature, expected):
+                state["bad_signatures"] += 1
+                self.reply(401)
+                return
+
+            if state["fail_next"] > 0:
+                state["fail_next"] -= 1
+                state["injected_failures"] += 1
+                self.reply(503)
+                return
+
+            idempotency_key = self.headers["Idempotency-Key"]
+            if idempotency_key in state["applied"]:
+                state["duplicates"] += 1
+                self.reply(200)
+                return
+
+            progress_key = self.headers["Materialize-Progress-Key"]
+            timestamp = self.headers.get("Materialize-Timestamp")
+            records = state["records"].setdefault(progress_key, [])
+            for record in json.loads(body):
+                records.append({"timestamp": timestamp, **record})
+            state["uppers"][progress_key] = json.loads(
+                self.headers["Materialize-Upper"]
+            )
+            state["applied"].append(idempotency_key)
+            self.reply(200)
+
+    def reply(self, status: int, body: object | None = None) -> None:
+        payload = b"" if body is None else json.dumps(body).encode()
+        self.send_response(status)
+        self.send_header("Content-Type", "application/json")
+        self.send_header("Content-Length", str(len(payload)))
+        self.end_headers()
+        if self.command != "HEAD":
+            self.wfile.write(payload)
+
+
+ThreadingHTTPServer(("0.0.0.0", 8080), Handler).serve_forever()
@@ -0,0 +1,97 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+"""
+Tests HTTP sinks against a real endpoint: request signing, retries of transient
+failures, and resumption without resending updates after a restart.
+"""
+
+import time
+from typing import Any
+
+import requests
+
+from materialize.mzcompose.composition import Composition, Service
+from materialize.mzcompose.services.materialized import Materialized
+from materialize.mzcompose.services.testdrive import Testdrive
+
+HMAC_SECRET = "s3cr3t"
+
+SERVICES = [
+    Materialized(),
+    Service(
+        name="endpoint",
+        config={
+            "image": "python:3.11-alpine",
+            "command": ["python3", "-u", "/endpoint.py"],
+            "environment": [f"HMAC_SECRET={HMAC_SECRET}"],
+            "volumes": ["./endpoint.py:/endpoint.py"],
+            "ports": [8080],
+        },
+    ),
+    Testdrive(no_reset=True, default_timeout="120s"),
+]
+
+
+def endpoint_url(c: Composition, path: str) -> str:
+    return f"http://localhost:{c.port('endpoint', 8080)}{path}"
+
+
+def endpoint_state(c: Composition) -> dict[str, Any]:
+    return requests.get(endpoint_url(c, "/control/state")).json()
+
+
+def received_ids(c: Composition) -> list[int]:
+    """Returns the ids of all the rows the good sink has sent, in order."""
+    records = endpoint_state(c)["records"]
+    # Only the sink with the correct secret ever gets a request applied.
+    assert len(records) <= 1, records
+    return [
+        record["value"]["after"]["id"]
+        for sink_records in records.values()
+        for record in sink_records
+    ]
+
+
+def wait_for_ids(c: Composition, expected: list[int]) -> None:
+    deadline = time.monotonic() + 120
+    while True:
+        ids = received_ids(c)
+        if sorted(ids) == expected:
+            return
+        assert time.monotonic() < deadline, f"expected {expected}, got {sorted(ids)}"
+        time.sleep(1)
+
+
+def workflow_default(c: Composition) -> None:
+    c.up("materialized", "endpoint")
+
+    c.run_testdrive_files(f"--var=hmac-secret={HMAC_SECRET}", "10-create-sink.td")
+    wait_for_ids(c, [1, 2, 3])
+    # The sink with the wrong secret must not have gotten any request through.
+    assert endpoint_state(c)["bad_signatures"] > 0
+
+    # Transient failures are retried until the endpoint accepts the request.
+    requests.post(endpoint_url(c, "/control/fail"), json={"count": 3})
+    c.run_testdrive_files("20-insert.td")
+    wait_for_ids(c, [1, 2, 3, 4, 5])
+    assert endpoint_state(c)["injected_failures"] == 3
+
+    # After a restart the sink resumes from the upper recorded by the endpoint
+    # instead of sending a new snapshot.
+    c.kill("materialized")
+    c.up("materialized")
+    c.run_testdrive_files("30-after-restart.td")
+    wait_for_ids(c, [1, 2, 3, 4, 5, 6])
+
+    # A resent snapshot would have shown up as duplicate ids above.
+    state = endpoint_state(c)
+    assert state["duplicates"] == 0, state
+    (upper,) = state["uppers"].values()
+    assert upper is not None and upper > 0, upper
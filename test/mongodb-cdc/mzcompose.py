@@ -0,0 +1,74 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+from materialize.mzcompose.composition import Composition, WorkflowArgumentParser
+from materialize.mzcompose.services.materialized import Materialized
+from materialize.mzcompose.services.mongodb import MongoDb
+from materialize.mzcompose.services.testdrive import Testdrive
+
+SERVICES = [
+    Materialized(
+        additional_system_parameter_defaults={
+            "log_filter": "mz_storage::source::mongodb=trace,info"
+        },
+    ),
+    MongoDb(),
+    Testdrive(default_timeout="60s"),
+]
+
+MONGODB_PASSWORD = "p@ssw0rd"
+
+
+def mongosh(c: Composition, script: str) -> None:
+    c.exec("mongodb", "mongosh", "--quiet", "test", "--eval", script)
+
+
+def workflow_default(c: Composition) -> None:
+    c.up("materialized", "mongodb")
+
+    # Turn the server into a single-member replica set so that it supports
+    # change streams, and create the user that Materialize connects as.
+    mongosh(
+        c, 'rs.initiate({_id: "rs0", members: [{_id: 0, host: "mongodb:27017"}]})'
+    )
+    mongosh(
+        c,
+        f"""
+        while (!db.hello().isWritablePrimary) {{ sleep(100); }}
+        db.createUser({{user: "materialize", pwd: "{MONGODB_PASSWORD}", roles: ["readWrite"]}});
+        db.createCollection("orders", {{changeStreamPreAndPostImages: {{enabled: true}}}});
+        db.createCollection("users", {{changeStreamPreAndPostImages: {{enabled: true}}}});
+        db.createCollection("no_images");
+        db.orders.insertMany([{{_id: 1, item: "apple", qty: 5}}, {{_id: 2, item: "pear", qty: 1}}]);
+        db.users.insertOne({{_id: "alice", tags: ["admin"]}});
+        """,
+    )
+
+    c.run_testdrive_files(
+        f"--var=mongodb-password={MONGODB_PASSWORD}",
+        "10-create-connection.td",
+        "20-source-snapshot.td",
+    )
+
+    # Testdrive cannot talk to MongoDB, so make the upstream changes here.
+    mongosh(
+        c,
+        """
+        db.orders.insertOne({_id: 3, item: "plum", qty: 2});
+        db.orders.updateOne({_id: 1}, {$set: {qty: 4}});
+        db.orders.deleteOne({_id: 2});
+        db.users.replaceOne({_id: "alice"}, {_id: "alice", tags: []});
+        db.no_images.drop();
+        """,
+    )
+
+    c.run_testdrive_files(
+        f"--var=mongodb-password={MONGODB_PASSWORD}",
+        "30-source-changes.td",
+    )
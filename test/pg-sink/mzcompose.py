@@ -0,0 +1,37 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+"""
+Tests Postgres sinks against a real Postgres server: upserts and deletes
+applied in one transaction per timestamp, resumption from the progress table
+after a restart, and fencing of concurrent writers.
+"""
+
+from materialize.mzcompose.composition import Composition
+from materialize.mzcompose.services.materialized import Materialized
+from materialize.mzcompose.services.postgres import Postgres
+from materialize.mzcompose.services.testdrive import Testdrive
+
+SERVICES = [
+    Materialized(),
+    Postgres(),
+    Testdrive(no_reset=True, default_timeout="120s"),
+]
+
+
+def workflow_default(c: Composition) -> None:
+    c.down(destroy_volumes=True)
+    c.up("materialized", "postgres")
+
+    c.run_testdrive_files("10-create-sink.td", "20-upserts-deletes.td")
+
+    c.kill("materialized")
+    c.up("materialized")
+
+    c.run_testdrive_files("30-after-restart.td", "40-fencing.td")
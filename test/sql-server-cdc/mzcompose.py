@@ -0,0 +1,40 @@
+# Copyright Materialize, Inc. and contributors. All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file at the root of this repository.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+from materialize.mzcompose.composition import Composition, WorkflowArgumentParser
+from materialize.mzcompose.services.materialized import Materialized
+from materialize.mzcompose.services.sql_server import SqlServer
+from materialize.mzcompose.services.testdrive import Testdrive
+
+SERVICES = [
+    Materialized(
+        additional_system_parameter_defaults={
+            "log_filter": "mz_storage::source::sql_server=trace,info"
+        },
+    ),
+    SqlServer(),
+    Testdrive(default_timeout="60s"),
+]
+
+
+def workflow_default(c: Composition, parser: WorkflowArgumentParser) -> None:
+    parser.add_argument(
+        "filter",
+        nargs="*",
+        default=["*.td"],
+        help="limit to only the files matching filter",
+    )
+    args = parser.parse_args()
+
+    c.up("materialized", "sql-server")
+
+    c.run_testdrive_files(
+        f"--var=sa-password={SqlServer.DEFAULT_SA_PASSWORD}",
+        *args.filter,
+    )